@@ -0,0 +1,1443 @@
+# English UI strings, also the fallback for keys a translation leaves out.
+# To translate, copy this file to <lang>.ftl (e.g. de.ftl) in the i18n folder of the
+# DroidView config directory and translate the text after each `=`.
+# `{ $name }` placeholders are filled in by DroidView and must stay as they are.
+
+language-name = English
+
+## Settings
+settings-window = Settings
+settings-title = Application Settings
+settings-paths = Paths
+settings-adb-path = ADB Path:
+settings-scrcpy-path = Scrcpy Path:
+settings-gnirehtet-path = gnirehtet Path (reverse tethering):
+settings-path-auto = Found automatically when empty
+settings-download = Download
+settings-redetect = Re-detect
+settings-redetect-hint = Search PATH and common install locations for adb and scrcpy again
+settings-remote-adb = Remote ADB Server
+settings-remote-adb-enable = Use an adb server on another machine
+settings-remote-adb-host = Host:
+settings-remote-adb-port = Port:
+settings-remote-adb-hint = Start it there with `adb -a nodaemon server start`. Mirroring tunnels its video through that host.
+settings-api = Control API
+settings-api-enable = Let other programs on this computer control DroidView over HTTP
+settings-api-port = Port:
+settings-api-token = Token:
+settings-api-copy = Copy the token
+settings-api-new-token = Make a new token; programs using the old one stop working
+settings-api-hint = Listens on 127.0.0.1 only. Send the token as `Authorization: Bearer <token>` or `?token=<token>`.
+settings-captures = Captures
+settings-capture-folder = Folder for screenshots and recordings:
+settings-screenshot-template = Screenshot filename template:
+settings-screenshot-clipboard = Also copy screenshots to the clipboard
+settings-bugreport-folder = Bug report folder:
+settings-bugreport-folder-hint = Capture folder
+settings-bugreport-open-folder = Open the folder when a bug report is saved
+settings-video = Video Settings
+settings-bitrate = Bitrate:
+settings-video-codec = Video codec:
+settings-orientation = Orientation:
+settings-show-touches = Show touches
+settings-turn-screen-off = Turn screen off
+settings-fullscreen = Fullscreen
+settings-max-dimension = Max dimension:
+settings-auto-start = Start mirroring devices set to auto-start when they connect
+settings-auto-start-hint = Turn off to keep every device from starting on its own without changing their settings
+settings-legacy-audio = Enable audio (legacy)
+settings-legacy-audio-hint = Forward audio with sndcpy on Android 10, or when scrcpy is older than 2.0. Plays through VLC or ffplay.
+settings-sndcpy-apk = sndcpy APK:
+settings-mirror-shortcuts = In-Mirror Shortcuts
+settings-capture-hotkeys = Capture hotkeys, which work from any window, also while the mirror has the focus. Registered on Save.
+settings-capture-hotkey-screenshot = Screenshot:
+settings-capture-hotkey-record = Start / stop recording:
+settings-templates = Session Templates
+settings-panels = Panels
+settings-auto-reconnect = Reconnect wireless devices that drop off
+settings-panel-swipe = Swipe Panel
+settings-panel-toolkit = Toolkit Panel
+settings-panel-bottom = Bottom Panel
+settings-panel-layout-reset = Reset panel layout
+settings-panel-layout-hint = Drag panels by their tab to rearrange them, or out of the window to detach them
+settings-device-thumbnails = Live screen thumbnails in the device list
+settings-device-poll = Check for devices every
+settings-device-poll-hint = How often DroidView looks for devices coming and going and for a running scrcpy; longer periods spawn fewer adb processes
+settings-wireless = Wireless ADB
+settings-extra-args = Extra Arguments
+settings-extra-args-label = Additional scrcpy arguments:
+settings-theme = Theme
+settings-theme-default = Default
+settings-theme-dark = Dark
+settings-theme-light = Light
+theme-accent = Accent color
+theme-tint = Background tint
+theme-tint-strength = Strength
+theme-corner-radius = Corner rounding
+theme-reset = Reset colors and rounding
+theme-preview = Preview
+theme-preview-button = Button
+theme-preview-selected = Selected
+theme-preview-link = Link
+settings-scaling = Scaling
+settings-ui-scale = Interface scale
+settings-font-size = Text size
+settings-scaling-reset = Reset scaling
+settings-scaling-hint = Ctrl + and Ctrl - also zoom until the next restart
+settings-language = Language
+settings-language-system = System default
+settings-language-hint = Add translations as <lang>.ftl files in { $folder }
+settings-logging = Logging
+settings-log-level = Log level:
+settings-open-log-folder = Open log folder
+settings-updates = Updates
+settings-check-updates = Check for a new version at startup
+settings-check-now = Check now
+settings-transfer = Export / Import
+settings-experimental = Experimental
+settings-save = 💾 Save
+settings-save-anyway = 💾 Save anyway
+settings-tool-failed = A tool path failed its test
+settings-cancel = ❌ Cancel
+settings-reset = 🔄 Reset to Defaults
+settings-import-save-failed = Imported, but saving failed: { $error }
+settings-browse = Browse
+settings-locate = Locate { $tool }
+settings-download-tool = Download { $tool }
+settings-download-tool-hint = Download the official { $package }, check its checksum and use it
+settings-capture-folder-hint = Desktop
+settings-template-example = Example: { $example }
+settings-bitrate-value = Value
+settings-bitrate-current = Current: { $bitrate }
+settings-orientation-default = Default
+settings-orientation-flip = Flip { $angle }°
+settings-max-dimension-custom = Custom
+settings-force-adb-forward = Force ADB Forward (--force-adb-forward)
+settings-wireless-reconnect-startup = Reconnect saved wireless devices on startup
+settings-wireless-none = No saved wireless devices yet
+settings-wireless-forget = Forget
+settings-log-hint = Logs rotate at 5 MB; debug and trace include every adb and scrcpy command.
+settings-updates-hint = This is DroidView { $version }. The check only asks GitHub for the latest release.
+settings-updates-skipping = Skipping { $version }
+settings-updates-remind = Remind me again
+settings-share-opt-in = Allow view-only sharing on the local network
+settings-share-opt-in-hint = Serves the device screen to anyone on the LAN with a time-limited secret link
+settings-test-tool = Test
+settings-test-tool-hint = Run { $command }
+settings-test-tool-checking = Checking { $tool }...
+settings-transfer-hint = Moves settings, session templates and profiles to another machine.
+settings-transfer-export = Export...
+settings-transfer-filter = DroidView settings
+settings-transfer-exported = Exported to { $path }
+settings-transfer-export-failed = Export failed: { $error }
+settings-transfer-import = Import...
+settings-transfer-pending = Export from { $date } with { $templates } session template(s) and { $profiles } other profile(s).
+settings-transfer-merge = Merge
+settings-transfer-merge-hint = Keep current settings; add missing templates, endpoints, intents and profiles
+settings-transfer-replace = Replace
+settings-transfer-replace-hint = Use the imported settings; profiles with the same name are overwritten
+settings-transfer-cancel = Cancel
+settings-transfer-import-failed = Import failed: { $error }
+settings-templates-hint = Launch from Scrcpy Controls > Templates, a global hotkey or the tray menu.
+settings-template-name = Name:
+settings-template-device = Device serial:
+settings-template-device-hint = selected device
+settings-template-hotkey = Hotkey:
+settings-hotkey-example = e.g. { $hotkey }
+settings-template-native = native
+settings-template-fullscreen = fullscreen
+settings-template-update = Update from current settings
+settings-remove = Remove
+settings-template-new-name = Template name
+settings-template-save = Save current settings as template
+scrcpy-shortcut-fullscreen = Toggle fullscreen
+scrcpy-shortcut-rotate-display = Rotate display
+scrcpy-shortcut-resize-1-1 = Resize window to 1:1
+scrcpy-shortcut-remove-borders = Remove black borders
+scrcpy-shortcut-home = Home
+scrcpy-shortcut-back = Back
+scrcpy-shortcut-app-switch = App switch
+scrcpy-shortcut-menu = Menu
+scrcpy-shortcut-volume = Volume up / down
+scrcpy-shortcut-power = Power
+scrcpy-shortcut-screen-off = Turn device screen off / on
+scrcpy-shortcut-rotate-device = Rotate device screen
+scrcpy-shortcut-notifications = Expand / collapse notification panel
+scrcpy-shortcut-clipboard = Copy / cut / paste via device clipboard
+scrcpy-shortcut-inject-clipboard = Inject computer clipboard as text
+scrcpy-shortcut-fps = Toggle FPS counter
+settings-shortcut-mod = Modifier (MOD) used for scrcpy shortcuts inside the mirror window:
+settings-shortcut-mod-default = Using scrcpy default (Left Alt or Left Super)
+settings-shortcut-mod-add = Add modifier
+settings-shortcut-mod-reset = Use scrcpy default
+settings-shortcut-reference = Shortcut reference
+
+## Quick actions
+quick-actions = Quick Actions
+quick-refresh-devices = 🔄 Refresh Devices
+quick-restart-adb = 🔄 Restart ADB
+quick-settings = 🔧 Settings
+quick-gallery = Gallery
+quick-recordings = Recordings
+quick-logs = Logs
+quick-shortcuts = Keyboard shortcuts
+quick-scripts = Scripts
+quick-run-script = Run on the selected device
+
+## Missing tool banner
+banner-adb-missing = adb was not found. DroidView needs it to talk to devices.
+banner-scrcpy-missing = scrcpy was not found. DroidView needs it to mirror the screen.
+banner-locate = Locate…
+banner-install = Install
+banner-install-hint = Download the official { $package } into DroidView's folder
+banner-download = Download…
+banner-download-hint = Open the download page
+banner-dismiss = Dismiss
+banner-battery-simulation = { $device } is reporting a simulated battery
+tools-redetected = Re-detected tools: adb { $adb }, scrcpy { $scrcpy }
+tools-not-found = not found
+tools-set = { $tool } set to { $path }
+tools-already-downloading = Already downloading { $tool }
+tools-install-failed = Could not install { $tool }: { $error }
+tools-installed = Installed { $tool } { $version }
+tools-stage = { $tool }: { $stage }
+tools-resolving = Looking up the latest release
+tools-downloading-of = Downloading { $received } of { $total } ({ $percent }%)
+tools-downloading = Downloading { $received }
+tools-verifying = Verifying checksum
+tools-extracting = Unpacking
+tools-locate = Locate { $tool }
+
+## Main window
+controls-start = ▶ Start Scrcpy
+controls-stop = ■ Stop Scrcpy
+status-scrcpy-running = 🟢 scrcpy running
+status-scrcpy-stopped = 🔴 scrcpy stopped
+status-notifications = Notifications
+settings-saved = Settings saved and applied.
+main-refresh = 🔄 Refresh
+adb-restart-failed = ADB restart failed: { $error }
+adb-restarted = ADB restarted
+recording-status = REC { $elapsed } / { $duration }
+recording-stop-now = Stop now
+bugreport-progress = Bug report
+bugreport-cancel = Cancel
+processing = Processing...
+theme-dark = Dark
+theme-light = Light
+about = About
+apks-installing = Installing dropped APK(s)...
+companion-granted = Granted WRITE_SECURE_SETTINGS to { $package }
+scrcpy-debug-mode = Scrcpy is disabled in debug mode
+scrcpy-started = Scrcpy started
+scrcpy-started-template = Scrcpy started ({ $template })
+scrcpy-started-without-audio = Audio capture failed on this device, so scrcpy was started without audio (--no-audio)
+scrcpy-start-failed-without-audio = Failed to start scrcpy (also without audio): { $error }
+scrcpy-start-failed = Failed to start scrcpy: { $error }
+scrcpy-stopped = Scrcpy stopped
+template-not-found = No session template named '{ $name }'
+template-device-missing = { $template }: device { $device } is not connected
+shortcuts-save-failed = Failed to save shortcuts: { $error }
+snippet-save-failed = Failed to save the snippet: { $error }
+snippets-save-failed = Failed to save the snippets: { $error }
+screenshot-copied = Screenshot copied to clipboard
+screenshot-copy-failed = Could not copy screenshot: { $error }
+screenshot-error = Screenshot error: { $error }
+screenshot-failed-status = Screenshot failed: exit code { $status }
+apk-installed = Installed APK: { $path }
+apk-install-failed-status = Install failed: exit code { $status }
+apk-install-error = Install error: { $error }
+shell-opened = Opened ADB shell in terminal
+
+## Keyboard shortcuts
+shortcut-refresh-devices = Refresh devices
+shortcut-screenshot = Take a screenshot
+shortcut-start-scrcpy = Start scrcpy
+shortcut-open-settings = Open settings
+shortcut-show-help = Show help
+shortcut-show-shortcuts = Show keyboard shortcuts
+shortcuts-title = Keyboard Shortcuts
+shortcuts-press-keys = Press keys… (Esc cancels)
+shortcuts-change = Change
+shortcuts-clear = Clear
+shortcuts-reset = Reset
+shortcuts-in-use = { $shortcut } is already used for "{ $action }"
+shortcuts-footer = Shortcuts work while DroidView has focus; Ctrl is Cmd on macOS.
+
+## Panel docking
+dock-panel-session = Control Panel
+dock-panel-devices = Devices
+dock-panel-wireless = Wireless ADB
+dock-panel-toolkit = Toolkit
+dock-panel-quick-actions = Quick Actions
+dock-panel-plugins = Plugins
+dock-panel-device-controls = Device Controls
+dock-reset = Reset layout
+dock-close = Close
+dock-eject = Open in a window
+dock-close-disabled = This area holds panels that cannot be closed.
+dock-close-window = Close window
+dock-close-window-menu-hint = Right-click to close this window.
+dock-close-window-modifier-hint = Hold Shift to close this window.
+dock-close-window-modifier-menu-hint = Hold Shift or right-click to close this window.
+dock-close-window-disabled = This window holds panels that cannot be closed.
+dock-minimize-window = Minimize window
+dock-minimize-window-menu-hint = Right-click to minimize this window.
+dock-minimize-window-modifier-hint = Hold Shift to minimize this window.
+dock-minimize-window-modifier-menu-hint = Hold Shift or right-click to minimize this window.
+
+## Command palette
+shortcut-command-palette = Command palette
+palette-title = Command Palette
+palette-hint = Type a command…
+palette-no-matches = No matching commands
+palette-category-app = DroidView
+palette-category-panels = Panels
+palette-stop-scrcpy = Stop scrcpy
+palette-restart-adb = Restart the ADB server
+palette-open-gallery = Open the screenshot gallery
+palette-open-recordings = Open the recordings library
+palette-toggle-logs = Show or hide the log console
+palette-toggle-theme = Switch between dark and light theme
+palette-about = About DroidView
+palette-show-panel = Show { $panel }
+palette-hide-panel = Hide { $panel }
+palette-select-device = Select { $device }
+palette-selected = selected
+toolkit-screenshot = Screenshot
+toolkit-copy-screenshot = Copy screenshot
+toolkit-record-screen = Record screen
+toolkit-install-apk = Install APK
+toolkit-open-shell = ADB shell
+toolkit-shell-console = Shell console
+toolkit-shell-command = Run shell command
+toolkit-send-intent = Send intent
+toolkit-capabilities = Capabilities
+toolkit-adb-keys = ADB keys
+toolkit-automation = Automation
+toolkit-macros = Macros
+toolkit-tap-picker = Tap picker
+toolkit-activities = Activities
+toolkit-clipboard-history = Device clipboard
+toolkit-share-view = Share view
+toolkit-benchmark = Benchmark
+toolkit-frame-timing = Profile frame timing
+toolkit-perfetto = Record Perfetto trace
+toolkit-bugreport = Capture bug report
+toolkit-show-imei = Show IMEI
+toolkit-display-info = Display info
+toolkit-resolution = Resolution and density
+toolkit-locale = Locale, time zone and clock
+toolkit-standby = Doze and app standby
+toolkit-mock-location = Mock location
+toolkit-layout-inspector = Layout inspector
+toolkit-ocr = Copy screen text (OCR)
+toolkit-long-screenshot = Long screenshot
+toolkit-screenshot-diff = Compare screenshots
+toolkit-native-preview = Native preview (experimental)
+toolkit-live-preview = Live preview
+toolkit-color-picker = Color picker
+toolkit-wifi-info = Wi-Fi info
+toolkit-device-report = Export device report
+toolkit-reverse-tether = Share internet
+toolkit-battery-info = Battery info
+toolkit-uninstall-app = Uninstall app
+toolkit-disable-app = Disable app
+toolkit-reboot = Reboot device
+toolkit-shutdown = Shut down device
+toolkit-reboot-recovery = Reboot to recovery
+toolkit-reboot-bootloader = Reboot to bootloader
+toolkit-reboot-fastbootd = Reboot to fastbootd
+toolkit-reboot-safe-mode = Reboot to safe mode
+toolkit-reboot-sideload = Reboot to sideload
+toolkit-reboot-edl = Reboot to EDL
+toolkit-bootloader-lock = Unlock or lock bootloader
+toolkit-sideload = Sideload OTA or ROM zip
+
+## Wireless history
+wireless-history = Recent wireless devices
+wireless-history-connect = Connect
+wireless-history-connected = Connected
+wireless-history-rename = Rename
+wireless-history-forget = Forget
+wireless-history-last-seen = Last seen { $time }
+wireless-history-paired = Paired with a code
+
+## Bootloader wizard
+bootloader-title = Bootloader unlock / lock
+bootloader-no-fastboot = fastboot was not found. It comes with Android platform-tools, next to adb.
+bootloader-unlock = Unlock the bootloader
+bootloader-lock = Lock the bootloader
+bootloader-unlock-warning = Unlocking erases everything on the device, and some apps stop working on unlocked devices.
+bootloader-lock-warning = Locking erases everything on the device. Only lock with the stock firmware installed: a modified system may no longer boot.
+bootloader-manufacturer = Manufacturer
+bootloader-next = Next
+bootloader-next-reboot = Reboot { $device } to the bootloader
+bootloader-back = Back
+bootloader-rescan = Look again
+bootloader-waiting = Waiting for a device in fastboot mode…
+bootloader-manual-hint = If the device does not show up, power it off and hold power and volume down. On Windows, fastboot may need the device's USB driver.
+bootloader-state-unlocked = unlocked
+bootloader-state-locked = locked
+bootloader-state-unknown = lock state unknown
+bootloader-already = The bootloader is already in that state.
+bootloader-no-unlock-ability = The bootloader refuses to unlock: turn on "OEM unlocking" in developer options, then come back.
+bootloader-confirm-device = Device { $device }
+bootloader-confirm-backup = I have backed up everything I want to keep
+bootloader-confirm-wipe = I understand that all data on the device is erased
+bootloader-confirm-lock = I understand that locking with modified firmware can leave the device unable to boot
+bootloader-confirm-type = Type { $word } to continue:
+bootloader-run-unlock = Unlock bootloader
+bootloader-run-lock = Lock bootloader
+bootloader-running = Waiting for the device…
+bootloader-answer-on-device = Confirm on the device: choose with the volume keys and press power.
+bootloader-reboot = Reboot device
+bootloader-close = Close
+bootloader-device-gone = { $device } is no longer in fastboot mode.
+bootloader-now-unlocked = The bootloader of { $device } is now unlocked.
+bootloader-now-locked = The bootloader of { $device } is now locked.
+bootloader-unchanged = The bootloader of { $device } is still { $state }. The request may have been declined on the device.
+bootloader-state-unreported = fastboot finished, but { $device } did not report its lock state. Check it on the device's bootloader screen.
+
+## Wi-Fi
+wifi-title = Wi-Fi
+wifi-refresh = Refresh
+wifi-ssid = Network
+wifi-ip = IP address
+wifi-use-ip = Copy, and use for wireless ADB
+wifi-ip-used = { $ip } copied and filled in for wireless ADB
+wifi-signal = Signal
+wifi-link-speed = Link speed
+wifi-band = Band
+wifi-disconnected = Wi-Fi is on but not connected to a network.
+wifi-off = Wi-Fi is off.
+
+## Legacy audio
+sndcpy-started = Forwarding audio from { $device } with sndcpy
+sndcpy-failed = Audio forwarding failed: { $error }
+sndcpy-no-player = Audio forwarding needs VLC or ffplay installed
+sndcpy-stopped = Audio forwarding stopped
+
+## Reverse tethering
+tether-enabled = Sharing this computer's internet with { $device }. Accept the VPN request on the device if asked.
+tether-disabled = Stopped sharing internet with { $device }
+tether-failed = Reverse tethering failed: { $error }
+tether-not-found = gnirehtet not found. Download it and set its path in Settings.
+tether-relay-stopped = The gnirehtet relay stopped; devices no longer share this computer's internet
+
+## Frame timing
+frames-title = Frame timing
+frames-package = App
+frames-foreground = Use foreground app
+frames-refresh-rate = Display
+frames-start = Start
+frames-pause = Pause
+frames-reset = Reset
+frames-count = { $count } frames
+frames-janky = Janky: { $count } ({ $percent }%)
+frames-dropped = Dropped vsyncs: { $count }
+frames-waiting = Waiting for frames: interact with the app on the device or in the mirror.
+frames-hint = Enter an app, press Start, then use the app. Frames are sampled from dumpsys gfxinfo every second.
+
+## Perfetto
+perfetto-title = Perfetto trace
+perfetto-intro = Records what the system does for a few seconds: CPU scheduling, frames, input and more. Reproduce the slow or janky moment while the trace runs.
+perfetto-categories = Categories
+perfetto-duration = Duration
+perfetto-start = Record trace
+perfetto-stop = Stop early
+perfetto-recording = Recording { $elapsed } / { $total } s
+perfetto-saving = Saving the trace…
+perfetto-saved = Saved { $path }
+perfetto-open-ui = Open ui.perfetto.dev
+perfetto-show-file = Show file
+perfetto-open-hint = Drop the trace file onto ui.perfetto.dev, or use "Open trace file" there. The trace is read in the browser and not uploaded.
+perfetto-trace-saved = Trace saved to { $path }
+perfetto-failed = Trace failed: { $error }
+
+## Sideload
+sideload-title = Sideload
+sideload-intro = Installs an OTA or ROM zip through recovery. Keep the device plugged in and charged until the recovery reports it is done.
+sideload-device = Device
+sideload-choose-device = Choose a device
+sideload-pick-zip = Choose zip…
+sideload-no-zip = No zip chosen
+sideload-reboot-first = Reboot into sideload mode first
+sideload-reboot-hint = Off when the device already waits in recovery under "Apply update from ADB"
+sideload-start = Sideload
+sideload-cancel = Cancel
+sideload-rebooting = Rebooting into recovery…
+sideload-waiting = Waiting for the device in sideload mode…
+sideload-transferring = Sending the zip…
+sideload-installing-hint = The recovery installs the package now; follow it on the device screen and reboot from there.
+sideload-done = { $zip } sent to { $device }
+sideload-failed = Sideload failed: { $error }
+
+## ADB keys
+adb-keys-title = ADB keys
+adb-keys-recheck = Re-check
+adb-keys-host = This computer
+adb-keys-copy = Copy fingerprint
+adb-keys-regenerate = Regenerate keys…
+adb-keys-regenerate-hint = Create a new key pair; the old one is kept as a .bak file
+adb-keys-regenerate-warning = Every device will ask to allow USB debugging again, and wireless pairings have to be redone. The adb server restarts, which stops any mirroring.
+adb-keys-regenerate-confirm = Regenerate
+adb-keys-regenerated = New key { $fingerprint }. Allow it on each device when asked.
+adb-keys-remote = The adb server at { $socket } signs in with the keys of its own machine
+adb-keys-cancel = Cancel
+adb-keys-device = Device
+adb-keys-no-devices = No devices connected
+adb-keys-choose-device = Choose a device
+adb-keys-trusted = Trusted computers: { $count }
+adb-keys-this-computer = This computer
+adb-keys-needs-root = The device is connected, so it trusts this computer. Listing the other computers it trusts needs root.
+adb-keys-revoke = Revoke authorizations…
+adb-keys-revoke-hint = Make the device forget every computer it allowed
+adb-keys-revoke-warning = The device will ask again the next time any computer connects, including this one. Without root, developer options open so you can tap "Revoke USB debugging authorizations".
+adb-keys-revoke-confirm = Revoke
+adb-keys-revoked = Authorizations revoked. They take effect when the device reconnects.
+adb-keys-revoke-manually = Developer options are open on the device: tap "Revoke USB debugging authorizations"
+adb-keys-unauthorized = The device has not allowed USB debugging from this computer yet
+adb-keys-step-unlock = Unlock the device and look for the "Allow USB debugging?" prompt.
+adb-keys-step-allow = Check that it shows the fingerprint { $fingerprint }, tick "Always allow from this computer" and tap Allow.
+adb-keys-step-replug = No prompt? Unplug and replug the cable, or turn USB debugging off and on again.
+adb-keys-step-revoke = Still nothing? Tap "Revoke USB debugging authorizations" in developer options, then reconnect.
+adb-keys-step-writable = If the prompt comes back every time, adb cannot keep its key: check that { $dir } is writable.
+adb-keys-no-permission = This computer may not open the USB device. On Linux, add a udev rule for it or join the plugdev group.
+adb-keys-offline = The device is offline. Reconnect it to see its authorization state.
+
+## Automation
+automation-title = Automation
+automation-hint = Rules run by themselves while DroidView is open: when a device connects, at a time of day, or when its battery runs low.
+automation-empty = No rules yet
+automation-add = Add rule
+automation-edit = Edit
+automation-delete = Delete
+automation-run-now = Run now
+automation-name = Name
+automation-device = Device
+automation-any-device = Any device
+automation-when = When
+automation-then = Then
+automation-trigger-connected = Device connects
+automation-trigger-time = At
+automation-trigger-battery = Battery below
+automation-action-mirror = Start mirroring
+automation-action-screenshot = Take a screenshot
+automation-action-shell = Run a shell command
+automation-save = Save
+automation-cancel = Cancel
+automation-duplicate = Another rule already has this name
+automation-ran = { $rule } ran on { $device }
+automation-failed = { $rule } failed on { $device }: { $error }
+automation-no-device = No connected device for { $rule }
+
+## Macros
+macros-title = Macros
+macros-device = Device
+macros-choose-device = Choose a device
+macros-speed = Speed
+macros-empty = No macros yet
+macros-steps = { $count } step(s)
+macros-play = Play
+macros-stop = Stop
+macros-delete = Delete
+macros-record = Record macro
+macros-recording = Recording
+macros-recording-hint = Inputs sent below, and swipes from the swipe panel, are sent to the device and recorded with the pauses between them.
+macros-tap = Tap
+macros-swipe = Swipe
+macros-key = Key
+macros-text = Text
+macros-send = Send
+macros-no-steps = Nothing recorded yet
+macros-name = Name
+macros-save = Save
+macros-discard = Discard
+macros-finished = Macro { $name } finished
+macros-failed = Macro { $name }: { $error }
+
+## Gestures
+gestures-title = Gesture designer
+gestures-canvas-hint = Click to add points
+gestures-path = Path
+gestures-pinch = Pinch
+gestures-points = { $count } point(s)
+gestures-undo = Undo
+gestures-clear = Clear
+gestures-pinch-hint = Click to place the fingers. Pinches use sendevent, which some devices only allow with root.
+gestures-from = Start apart
+gestures-to = End apart
+gestures-duration = Duration
+gestures-name = Name
+gestures-try = Try
+gestures-save = Save
+gestures-empty = No saved gestures yet
+gestures-edit = Edit
+gestures-delete = Delete
+gestures-done = Gesture { $name } sent
+gestures-failed = Gesture failed: { $error }
+
+## Tap picker
+tap-picker-title = Tap picker
+tap-picker-refresh = Take a new screenshot
+tap-picker-refresh-after = Refresh after each tap
+tap-picker-long-press = Long press
+tap-picker-hint = Click the screenshot to tap there on the device. Right-click or hold the button for a long press.
+tap-picker-empty = No screenshot yet
+
+## Scripts
+scripts-title = Scripts
+scripts-new = New script
+scripts-empty = No scripts yet
+scripts-pin = Show in quick actions
+scripts-name = Name
+scripts-save = Save
+scripts-delete = Delete
+scripts-check = Check
+scripts-check-ok = No syntax errors
+scripts-device = Device
+scripts-run = Run
+scripts-stop = Stop
+scripts-untitled = Untitled script
+scripts-finished = Script finished
+scripts-done = Script { $name } finished
+scripts-failed = Script { $name }: { $error }
+scripts-busy = Another script is still running
+
+## Plugins
+plugins-title = Plugins
+plugins-reload = Look for plugins again
+plugins-open-folder = Open the plugins folder
+plugins-empty = No plugins installed. Put a folder with a plugin.toml in the plugins folder.
+plugins-failed = { $command }: { $error }
+
+controls-title = Device Controls
+controls-refresh = Read the values from the device again
+controls-no-device = Select a device to change its brightness, volume and quick settings.
+controls-unavailable = Not available on this device
+controls-brightness = Screen brightness
+controls-auto-brightness = Auto
+controls-media-volume = Media volume
+controls-ring-volume = Ring volume
+controls-volume-down = Volume down
+controls-volume-up = Volume up
+controls-mute = Mute
+controls-wifi = Wi-Fi
+controls-mobile-data = Mobile data
+controls-airplane-mode = Airplane mode
+controls-do-not-disturb = Do Not Disturb
+controls-auto-rotate = Auto-rotate
+controls-flashlight = Flashlight
+controls-flashlight-hint = Flashlight: opens Quick Settings on the device, as Android cannot switch the torch over adb
+controls-toggle-on = { $name }: on
+controls-toggle-off = { $name }: off
+controls-toggle-unknown = { $name }: the device does not report this
+controls-developer-options = Developer options
+controls-layout-bounds = Show layout bounds
+controls-pointer-location = Pointer location
+controls-gpu-overdraw = Show GPU overdraw
+controls-strict-mode = Flash screen on strict mode violations
+controls-animation-scale = Animations
+controls-animation-off = Off
+controls-battery-simulation = Battery simulation
+controls-battery-level = Level
+controls-battery-status = Status
+controls-battery-charging = Charging
+controls-battery-discharging = Discharging
+controls-battery-not-charging = Plugged in, not charging
+controls-battery-full = Full
+controls-battery-simulate = Simulate
+controls-battery-reset = Reset battery
+controls-battery-reset-hint = Report the real battery again
+controls-battery-active = Simulation active: apps see the values above
+
+resolution-title = Resolution and density
+resolution-refresh = Refresh
+resolution-panel = Screen
+resolution-override = Overridden to
+resolution-size = Resolution
+resolution-density = Density
+resolution-apply = Apply
+resolution-reset = Reset
+resolution-reset-hint = Go back to the screen's own resolution and density
+resolution-recovery = If the screen becomes unusable, Reset here or run `adb shell wm size reset` and `adb shell wm density reset`.
+resolution-risk-small = This is narrower than Android's layouts are made for; dialogs may not fit.
+resolution-risk-large = This is more than twice the screen's pixels, which can make the device very slow.
+resolution-risk-aspect = This is a different shape than the screen, so parts of it may be cut off or unreachable.
+resolution-risk-density-low = Text and buttons will be too small to use at this density.
+resolution-risk-density-high = So little fits on screen at this density that Settings may be unreachable.
+resolution-accept-risk = Apply anyway, I can reset from here
+
+locale-title = Locale and time
+locale-refresh = Refresh
+locale-current = Locale
+locale-timezone-current = Time zone
+locale-auto-timezone = The time zone is set automatically; choosing one turns that off.
+locale-language = Language
+locale-timezone = Time zone
+locale-search = Search…
+locale-use = Use { $tag }
+locale-clock = Clock
+locale-clock-check = Check
+locale-clock-in-sync = in sync
+locale-clock-ahead = { $amount } ahead
+locale-clock-behind = { $amount } behind
+locale-clock-seconds = { $seconds } s
+locale-clock-minutes = { $minutes } min { $seconds } s
+locale-clock-hours = { $hours } h { $minutes } min
+locale-clock-days = { $days } days { $hours } h
+locale-clock-sync = Sync to this computer
+locale-clock-sync-hint = Set the device's date and time from this computer's clock
+locale-clock-synced = Device clock set from this computer
+locale-clock-network-time = The clock cannot be set without root on this device, so automatic network time was switched on
+locale-clock-failed = Could not set the clock: { $error }
+standby-title = Doze and app standby
+standby-refresh = Refresh
+standby-doze = Doze
+standby-deep = Deep
+standby-light = Light
+standby-force-idle = Force idle
+standby-force-idle-hint = Enter deep Doze now; the device stays there until you exit
+standby-unforce = Exit idle
+standby-app = App
+standby-foreground = Foreground app
+standby-press-enter = Press Enter to look the app up
+standby-bucket = Standby bucket
+standby-bucket-other = Currently { $bucket }
+standby-whitelisted = Exempt from Doze
+standby-whitelist = Apps exempted over adb or by the user ({ $count })
+standby-remove = Remove from the whitelist
+standby-system-whitelist = { $count } more are exempted by the system
+mock-location-title = Mock location
+mock-location-refresh = Refresh
+mock-location-provider = Provider
+mock-location-emulator = Emulators take locations from their console, no provider app needed
+mock-location-helper-ready = The helper app is the mock location app
+mock-location-helper-missing = The helper app is not set up
+mock-location-prepare = Set up helper
+mock-location-prepare-hint = Installs { $apk } if needed and picks it as the mock location app
+mock-location-providers = Allowed to mock: { $packages }
+mock-location-use-provider = Use as mock location app
+mock-location-position = Position
+mock-location-latitude = Latitude
+mock-location-longitude = Longitude
+mock-location-set = Set location
+mock-location-stop = Stop mocking
+mock-location-route = Route
+mock-location-open-gpx = Open GPX…
+mock-location-route-points = { $name }: { $count } points
+mock-location-speed = Speed
+mock-location-play = Play route
+mock-location-stop-route = Stop
+mock-location-route-progress = Point { $point } of { $count }
+mock-location-route-finished = Route played to the end
+mock-location-route-failed = Route playback stopped: { $error }
+hierarchy-title = Layout inspector
+hierarchy-refresh = Dump the screen again
+hierarchy-hint = Hover a view in the tree or on the screenshot to outline it; click to see its attributes
+hierarchy-empty = Nothing dumped yet
+hierarchy-select = Click a view to see its attributes
+long-screenshot-started = Scrolling and capturing; leave the device alone until it finishes
+long-screenshot-saved = Long screenshot stitched from { $frames } screens
+long-screenshot-failed = Long screenshot failed: { $error }
+pip-title = Live preview
+pip-no-device = No device selected
+pip-close = Hide the live preview
+pip-mirror = Double-click to open the scrcpy window
+native-preview-title = Native preview
+native-preview-starting = Starting the scrcpy server…
+native-preview-stop = Stop
+native-preview-hint = Experimental and view-only: DroidView runs the scrcpy server and decodes the video with ffmpeg. Use the scrcpy window for input and audio.
+native-preview-failed = Native preview failed: { $error }
+native-preview-ended = The native preview stream ended
+native-preview-no-server = { $file } not found next to scrcpy; set SCRCPY_SERVER_PATH to it
+native-preview-no-ffmpeg = The native preview decodes with ffmpeg. Install it or set its path in the stream settings.
+shell-console-title = Shell console
+shell-console-ended = The shell ended
+shell-console-restart = Restart
+shell-console-clear = Clear
+shell-console-copy = Copy all
+shell-console-interrupt = Interrupt the running command
+shell-console-hint = Type a command and press Enter
+shell-console-failed = Could not start the shell: { $error }
+shell-console-no-terminal = No terminal emulator found; opened the shell console instead
+shell-command-title = Run shell command
+shell-command-no-device = No device selected
+shell-command-hint = A shell command, such as getprop ro.build.version.release (Ctrl+Enter runs it)
+shell-command-run = Run
+shell-command-snippet-name = Snippet name
+shell-command-save = Save snippet
+shell-command-save-hint = Keep this command under a name; saving over an existing name replaces it
+shell-command-snippets = Snippets ({ $count })
+shell-command-history = History on this device ({ $count })
+shell-command-copy = Copy the output
+shell-command-exit = Exit code { $code }
+shell-command-exit-unknown = Exit code unknown
+shell-command-empty = The output of the command shows here
+shell-command-running = Wait for the running command to finish
+shell-command-busy = A shell command is still running; the new one was added to the history
+screenshot-diff-title = Compare screenshots
+screenshot-diff-before = Before
+screenshot-diff-after = After
+screenshot-diff-capture = Capture
+screenshot-diff-open = Open image…
+screenshot-diff-side-by-side = Side by side
+screenshot-diff-overlay = Overlay
+screenshot-diff-heatmap = Heatmap
+screenshot-diff-opacity = After opacity
+screenshot-diff-threshold = Threshold
+screenshot-diff-summary = { $percent }% of pixels differ ({ $changed } of { $total })
+screenshot-diff-scaled = The screenshots differ in size; the after one was scaled to the before one
+screenshot-diff-empty = Capture or open a screenshot on each side to compare them
+screenshot-diff-images = Images
+color-picker-title = Color picker
+color-picker-refresh = Take the screenshot again
+color-picker-hint = Hover the screenshot to read a pixel; click to keep its color
+color-picker-empty = No screenshot yet
+color-picker-hover = Point at the screenshot
+color-picker-picked = Picked colors
+color-picker-clear = Clear
+color-picker-click = Click the screenshot to keep a color here
+color-picker-copy = Copy
+ocr-copied = Copied { $lines } lines of screen text to the clipboard
+ocr-copy-failed = Could not copy the screen text: { $error }
+
+## SSH tunnels
+tunnels-title = SSH tunnels
+tunnels-button = SSH tunnels…
+tunnels-button-hint = Reach devices attached to another machine through an SSH port forward
+tunnels-hint = Each tunnel forwards a local port to a device's adb port on the SSH host and connects to it. The host must accept a key or agent login.
+tunnels-empty = No tunnels yet
+tunnels-open = Open
+tunnels-close = Close
+tunnels-edit = Edit
+tunnels-delete = Delete
+tunnels-add = Add tunnel
+tunnels-name = Name
+tunnels-ssh-host = SSH host
+tunnels-ssh-port = SSH port
+tunnels-target = Device address
+tunnels-target-hint = The device's adb address as seen from the SSH host
+tunnels-local-port = Local port
+tunnels-identity = Identity file
+tunnels-save = Save
+tunnels-cancel = Cancel
+tunnels-duplicate = Another tunnel already has this name
+tunnels-port-in-use = Local port { $port } is already used by { $name }
+tunnels-opened = Tunnel { $name } is open, connected to { $endpoint }
+tunnels-connect-failed = Tunnel { $name } is open but adb could not connect: { $error }
+tunnels-open-failed = Could not open tunnel { $name }: { $error }
+tunnels-dropped = Tunnel { $name } closed unexpectedly
+tunnels-error-name = Give the tunnel a name
+tunnels-error-host = Enter the SSH host, e.g. user@lab.example.com
+tunnels-error-target = The device address needs a host and port, e.g. localhost:5555
+tunnels-error-port = Choose a local port
+
+## Device list
+devices-favorite = Pin to the top
+devices-unfavorite = Unpin
+devices-rename = Rename…
+devices-clear-nickname = Use the model name
+devices-compact = Compact list
+devices-detailed = Show Android version, battery and connection
+devices-android-api = Android { $version } (API { $api })
+devices-android = Android { $version }
+devices-api = API { $api }
+devices-battery = { $level }% battery
+devices-auto-start = Start mirroring when connected
+devices-auto-start-on = Mirroring starts when this device connects
+devices-auto-start-off = Mirroring starts when this device connects (auto-start is turned off in Settings)
+devices-auto-starting = Starting mirroring on { $name }
+devices-health-latency = Connection: answers in { $ms } ms
+devices-health-unresponsive = Connection: not answering
+devices-reconnecting = Reconnecting…
+devices-reconnect-attempt = Attempt { $attempt } of { $max }
+devices-reconnect-next = Next attempt in { $seconds } s
+devices-reconnect-cancel = Stop reconnecting
+devices-unauthorized-hint = The device has not allowed USB debugging from this computer. Click for help.
+devices-reconnect-lost = Lost { $name }, reconnecting…
+devices-reconnected = Reconnected { $name }
+devices-reconnect-gave-up = Could not reconnect { $name }
+devices-search = Serial, name or IP
+devices-heading = Connected Devices
+devices-none = No devices found
+devices-no-match = No matching devices
+devices-status-connected = Connected
+devices-status-offline = Offline
+devices-status-unauthorized = Unauthorized
+devices-status-no-permission = No Permission
+devices-info-id = ID: { $id }
+devices-info-product = Product: { $product }
+devices-info-model = Model: { $model }
+devices-info-device = Device: { $device }
+devices-found = Found { $count } device(s)
+devices-new-display = New display detected: { $name }
+
+## Device capabilities
+capabilities-missing = { $capability } is blocked on this device, see Device Capabilities
+capabilities-title = Device Capabilities
+capabilities-recheck = Re-check
+capabilities-checking = Checking what the shell user may change...
+capabilities-no-device = No device selected or ADB not configured
+capabilities-manufacturer = Manufacturer: { $name }
+capabilities-used-by = Used by: { $features }
+capabilities-how-to = How to allow these changes
+capabilities-companion = Alternatively, grant WRITE_SECURE_SETTINGS to a companion app:
+capabilities-grant = Grant
+
+## Toolkit panel
+swipe-title = Swipe Controls
+swipe-swipe-up = Swipe Up
+swipe-swipe-down = Swipe Down
+swipe-swipe-left = Swipe Left
+swipe-swipe-right = Swipe Right
+swipe-gesture-designer = Gesture designer…
+toolkit-button-screenshot = Screenshot
+toolkit-button-copy-screenshot = Copy Screenshot
+toolkit-button-long-screenshot = Long Screenshot
+toolkit-button-compare-screenshots = Compare Screenshots
+toolkit-button-native-preview = Native Preview
+toolkit-button-live-preview = Live Preview
+toolkit-button-record-screen = Record Screen
+toolkit-button-install-apk = Install APK
+toolkit-button-adb-shell = ADB Shell
+toolkit-button-shell-console = Shell Console
+toolkit-button-run-command = Run Command
+toolkit-button-send-intent = Send Intent
+toolkit-button-capabilities = Capabilities
+toolkit-button-adb-keys = ADB Keys
+toolkit-button-automation = Automation
+toolkit-button-macros = Macros
+toolkit-button-tap-picker = Tap Picker
+toolkit-button-activities = Activities
+toolkit-button-device-clipboard = Device Clipboard
+toolkit-button-share-view = Share View
+toolkit-button-benchmark = Benchmark
+toolkit-button-frame-timing = Frame Timing
+toolkit-button-trace = Trace
+toolkit-button-bug-report = Bug Report
+toolkit-button-show-imei = Show IMEI
+toolkit-button-display-info = Display Info
+toolkit-button-resolution = Resolution
+toolkit-button-locale-time = Locale & Time
+toolkit-button-doze-standby = Doze & Standby
+toolkit-button-mock-location = Mock Location
+toolkit-button-layout-inspector = Layout Inspector
+toolkit-button-copy-screen-text = Copy Screen Text
+toolkit-button-color-picker = Color Picker
+toolkit-button-battery-info = Battery Info
+toolkit-button-wi-fi-info = Wi-Fi Info
+toolkit-button-device-report = Device Report
+toolkit-button-share-internet = Share Internet
+toolkit-button-uninstall-app = Uninstall App
+toolkit-button-disable-app = Disable App
+toolkit-long-screenshot-hint = Scroll the screen and stitch the captures into one tall image
+toolkit-screenshot-diff-hint = Overlay two screenshots or see what changed between them
+toolkit-native-preview-hint = Experimental: mirror the screen inside DroidView, without the scrcpy window
+toolkit-live-preview-hint = Show or hide a small live preview of the selected device's screen
+toolkit-shell-console-hint = Run an adb shell in a console inside DroidView
+toolkit-shell-command-hint = Run a shell command and see its output, with history and saved snippets
+toolkit-resolution-hint = Override the screen size and density with wm size / wm density
+toolkit-locale-hint = Change the device's language and time zone
+toolkit-standby-hint = Force Doze, whitelist apps and set their standby buckets
+toolkit-mock-location-hint = Set the device's location by hand or play back a GPX route
+toolkit-layout-inspector-hint = Browse the views on screen and see where each one is
+toolkit-ocr-hint = Read the text on the screen with tesseract and copy it to the clipboard
+toolkit-color-picker-hint = Read the color of any pixel on the screen in hex or RGB
+toolkit-device-report-hint = Save model, build, storage, battery, display and app list as HTML or JSON
+toolkit-reverse-tether-hint = Share this computer's internet connection with the device (gnirehtet)
+toolkit-device-control = Device Control
+toolkit-reboot-hint = Reboot Device
+    Restart the device normally
+toolkit-shutdown-hint = Shutdown Device
+    Power off the device completely
+toolkit-reboot-recovery-hint = Reboot to Recovery
+    Restart device in recovery mode for system maintenance
+toolkit-reboot-bootloader-hint = Reboot to Bootloader
+    Restart device in bootloader mode for flashing
+toolkit-reboot-checking = Checking what the device supports…
+toolkit-reboot-more-hint = More Reboot Targets
+toolkit-reboot-fastbootd-unsupported = Needs dynamic partitions (Android 10 or later)
+toolkit-reboot-edl-unsupported = Only Qualcomm devices have emergency download mode
+    fastbootd, safe mode, sideload or EDL
+toolkit-bootloader-lock-hint = Unlock or Lock Bootloader
+    Guided fastboot flashing unlock/lock
+toolkit-sideload-hint = Sideload OTA / ROM
+    Install a zip through recovery with adb sideload
+confirm-reboot-title = Confirm Reboot
+confirm-reboot = Are you sure you want to reboot the device?
+confirm-shutdown-title = Confirm Shutdown
+confirm-shutdown = Are you sure you want to shutdown the device?
+confirm-recovery-title = Confirm Recovery Reboot
+confirm-recovery = Are you sure you want to reboot to recovery mode?
+confirm-bootloader-title = Confirm Bootloader Reboot
+confirm-bootloader = Are you sure you want to reboot to bootloader?
+confirm-fastbootd = Are you sure you want to reboot to fastbootd?
+confirm-safe-mode = Are you sure you want to reboot to safe mode? Third-party apps stay off until the next reboot.
+confirm-sideload = Are you sure you want to reboot to recovery's sideload mode?
+confirm-edl = Are you sure you want to reboot to emergency download mode? Leaving it needs a long press of the power button.
+confirm-reboot-target-title = Confirm { $target }
+confirm-ok = OK
+confirm-cancel = Cancel
+bugreport-already-running = A bug report is already being captured
+bugreport-save-to = Save bug report to
+bugreport-capturing = Capturing bug report; this can take a few minutes...
+bugreport-saved = Bug report saved to { $path }
+bugreport-failed = Bug report failed: { $error }
+open-folder-failed = Could not open the folder: { $error }
+device-report-save = Save device report
+device-report-gathering = Gathering the device report...
+device-report-saved = Device report saved to { $path }
+device-report-failed = Device report failed: { $error }
+imei-android-id = Android ID: { $value }
+imei-imei = IMEI: { $value }
+imei-imei1 = IMEI1: { $value }
+imei-imei2 = IMEI2: { $value }
+imei-legacy = Legacy IMEI: { $value }
+imei-serial = Serial: { $value }
+imei-unavailable =
+    No IMEI/Device ID information available. This may be due to:
+    • Android security restrictions (Android 10+)
+    • Missing READ_PHONE_STATE permission
+    • Device not supporting IMEI retrieval
+imei-loading = Loading IMEI...
+imei-loaded = IMEI retrieved successfully
+display-info-display = 📱 Display Information:
+display-info-size = 📐 Window Manager Size:
+display-info-density = 📊 Window Manager Density:
+display-info-failed = Failed to retrieve display info
+display-info-loading = Loading display info...
+display-info-loaded = Display info retrieved successfully
+battery-info-failed = Failed to retrieve battery info
+battery-info-loading = Loading battery info...
+battery-info-loaded = Battery info retrieved successfully
+apps-loaded = App list loaded successfully
+reboot-started = Device reboot initiated
+reboot-failed-status = Reboot failed: exit code { $status }
+reboot-error = Reboot error: { $error }
+shutdown-started = Device shutdown initiated
+shutdown-failed-status = Shutdown failed: exit code { $status }
+shutdown-error = Shutdown error: { $error }
+reboot-recovery-started = Device rebooting to recovery mode
+reboot-recovery-failed-status = Recovery reboot failed: exit code { $status }
+reboot-recovery-error = Recovery reboot error: { $error }
+reboot-bootloader-started = Device rebooting to bootloader
+reboot-bootloader-failed-status = Bootloader reboot failed: exit code { $status }
+reboot-bootloader-error = Bootloader reboot error: { $error }
+
+## Wireless ADB
+wireless-title = Wireless ADB
+wireless-direct = Direct TCP/IP Connection
+wireless-ip = IP Address:
+wireless-port = Port:
+wireless-connect = 🔗 Connect
+wireless-enable-tcpip-title = Enable TCP/IP on Device
+wireless-no-devices = No devices connected
+wireless-select-device = Select a device
+wireless-enable-tcpip = 🌐 Enable TCP/IP
+wireless-pair-title = Pair via Code
+wireless-pairing-code = Pairing Code:
+wireless-pair = 🔐 Pair
+wireless-reconnecting = Reconnecting saved wireless devices...
+wireless-reconnected = Reconnected { $connected } of { $total } wireless device(s)
+wireless-connected = Connected to { $endpoint }
+wireless-already-connected = Already connected to { $endpoint }
+wireless-connect-failed = Connection failed: { $error }
+wireless-tcpip-enabled = TCP/IP enabled on { $device }:{ $port }
+wireless-tcpip-failed = TCP/IP enable failed: { $error }
+wireless-paired = Paired with { $endpoint }
+wireless-pair-failed = Pairing failed: { $error }
+
+## Remote assistance
+remote-assist-step-network = Reach the phone
+remote-assist-step-developer = Developer options
+remote-assist-step-pair = Pair
+remote-assist-step-connect = Connect
+remote-assist-step-start = Start helping
+remote-assist-title = Remote Assistance
+remote-assist-network = Wireless debugging only works when this computer can reach the phone's address.
+remote-assist-network-same = Same Wi-Fi (visiting, or at home): nothing to set up.
+remote-assist-network-vpn = Different places: install the same VPN app (for example Tailscale or ZeroTier) on both this computer and the phone, and sign both in to the same account.
+remote-assist-network-wifi = Ask them to keep the phone plugged in and on Wi-Fi, not mobile data.
+remote-assist-developer = Read these steps to them:
+remote-assist-developer-about = Open Settings, then About phone.
+remote-assist-developer-build = Tap "Build number" seven times until it says you are a developer.
+remote-assist-developer-options = Go back to Settings, open System (or Additional settings), then Developer options.
+remote-assist-developer-wireless = Turn on "Wireless debugging" and confirm.
+remote-assist-pair = Ask them to tap "Wireless debugging", then "Pair device with pairing code", and read out what appears.
+remote-assist-address = IP address & port
+remote-assist-code = Wi-Fi pairing code
+remote-assist-pair-button = Pair
+remote-assist-pair-hint = The code expires when they leave that screen; ask for a new one if pairing fails.
+remote-assist-connect = Ask them to go back to the "Wireless debugging" screen and read the "IP address & port" shown at the top.
+remote-assist-connect-button = Connect
+remote-assist-connected = Connected
+remote-assist-start = The remote assistance preset streams at 1 Mbps, 15 fps and 800 pixels without audio, so it stays responsive on slow links.
+remote-assist-not-connected = The phone is not connected yet; go back to step 4.
+remote-assist-start-button = Start remote assistance
+remote-assist-done = When you are done, they can turn Wireless debugging off again.
+remote-assist-back = Back
+remote-assist-next = Next
+
+## Intent sender
+intent-title = Send Intent
+intent-activity = Activity
+intent-broadcast = Broadcast
+intent-action = Action:
+intent-data = Data URI:
+intent-data-hint = https://example.com/path or myapp://deep/link
+intent-component = Component:
+intent-category = Category:
+intent-extras = Extras:
+intent-extra-key = key
+intent-extra-value = value
+intent-add-extra = Add extra
+intent-send = Send
+intent-clear = Clear
+intent-history = History ({ $count })
+intent-sent = Intent sent
+intent-failed = Intent failed: { $error }
+
+## Gallery
+gallery-title = Gallery
+gallery-refresh = Refresh
+gallery-open-folder = Open folder
+gallery-empty = No captures yet. Screenshots and recordings taken in DroidView show up here.
+gallery-frame-title = Device Frame
+gallery-frame = Frame
+gallery-bezel = Bezel
+gallery-shadow = Shadow
+gallery-background = Background
+gallery-transparent = Transparent
+gallery-export-png = Export PNG
+gallery-saved = Saved { $name }
+gallery-open = Open
+gallery-show-in-folder = Show in file manager
+gallery-export-frame = Export in a device frame
+gallery-rename = Rename
+gallery-delete-confirm = Delete?
+gallery-delete = Delete
+
+## Recording library
+recordings-title = Recordings
+recordings-refresh = Refresh
+recordings-empty = No recordings yet. Use Record Screen in the toolkit to make one.
+recordings-file = File
+recordings-device = Device
+recordings-recorded = Recorded
+recordings-duration = Duration
+recordings-resolution = Resolution
+recordings-bitrate = Bitrate
+recordings-size = Size
+recordings-export = Export
+recordings-converting = Converting...
+recordings-convert = Convert to { $format }
+recordings-close = Close
+recordings-saved = Saved { $path }
+recordings-open = Open
+recordings-show-in-folder = Show in file manager
+recordings-play = Play in the default player
+recordings-export-hint = Export as GIF or WebM
+recordings-delete-confirm = Delete?
+recordings-delete = Delete
+recordings-ffmpeg-missing = ffmpeg not found. Install it or set its path in the stream settings.
+recordings-converting-to = Converting recording to { $format }...
+recordings-exported = Exported { $path }
+screenrecord-already-running = A screen recording is already running
+screenrecord-recording = Recording screen...
+screenrecord-stopping = Stopping screen recording...
+screenrecord-saved = Screen recording saved
+screenrecord-saved-to = Screen recording saved to { $path }
+screenrecord-stop-failed = Could not stop the screen recording: { $error }
+
+## App lists
+apps-sort-name = Name (A–Z)
+apps-sort-install-date = Install date (newest)
+apps-sort-size = Size (largest)
+apps-details = Details…
+apps-launch = Launch
+apps-permissions = Permissions…
+apps-force-stop = Force stop
+apps-clear-data = Clear data…
+apps-user = User:
+apps-user-not-running = { $user } – not running
+apps-search = Search packages
+apps-show-user = User
+apps-show-system = System
+apps-show-disabled = Disabled
+apps-system = system
+apps-disabled = disabled
+apps-loading = Loading app list...
+apps-launched = Launched { $package }
+apps-force-stopped = Force-stopped { $package }
+apps-cleared = Cleared data of { $package }
+apps-launch-failed = Failed to launch { $package }: { $error }
+apps-force-stop-failed = Failed to force-stop { $package }: { $error }
+apps-clear-failed = Failed to clear data of { $package }: { $error }
+apps-uninstall-title = Uninstall Application
+apps-disable-title = Disable Application
+apps-none = No apps found or failed to load app list.
+apps-showing = Showing { $shown } of { $total } apps:
+apps-row-hint = Click for details, right-click for actions
+apps-selected = Selected { $count } app(s)
+apps-selected-hidden = Selected { $count } app(s), { $hidden } more hidden by the filter and left out
+apps-uninstall-selected = Uninstall Selected
+apps-disable-selected = Disable Selected
+apps-select-all = Select All
+apps-clear-selection = Clear Selection
+apps-uninstalled = Successfully uninstalled { $count } app(s)
+apps-uninstalled-partly = Uninstalled { $count } app(s), { $failed } failed
+apps-uninstall-none = Please select at least one app to uninstall
+apps-disabled-all = Successfully disabled { $count } app(s)
+apps-disabled-partly = Disabled { $count } app(s), { $failed } failed
+apps-disable-none = Please select at least one app to disable
+apps-clear-data-title = Clear App Data
+apps-clear-data-confirm = Clear all data of { $package }?
+apps-clear-data-warning = Accounts, settings, databases and cached files are deleted. This cannot be undone.
+apps-clear-data-button = Clear Data
+
+## Streaming
+stream-title = Stream
+stream-url = URL:
+stream-show-url = Show
+stream-video-bitrate = Video bitrate:
+stream-audio-bitrate = Audio bitrate:
+stream-max-size = Max size:
+stream-device-resolution = (device resolution)
+stream-ffmpeg-auto = Auto-detect
+stream-show-mirror = Show the scrcpy window while streaming
+stream-preflight = Preflight check
+stream-stop = Stop
+stream-start = Start
+stream-stopped-toast = Streaming stopped
+stream-stopped = Stream stopped
+stream-ended = Stream ended: { $reason }
+stream-started = Streaming started
+stream-start-failed = Failed to start stream
+stream-streaming-to = Streaming to { $url }
+
+## App details
+app-details-title = App Details
+app-details-loading = Loading details...
+app-details-version = Version
+app-details-target-sdk = Target SDK
+app-details-min-sdk = Min SDK
+app-details-installed = Installed
+app-details-updated = Updated
+app-details-installer = Installer
+app-details-apk-size = APK size
+app-details-data-size = Data size
+app-details-apk-paths = APK paths ({ $count })
+app-details-refresh = Refresh
+app-details-permissions = Permissions…
+
+## Share view
+share-title = Share View (experimental)
+share-warning = Anyone on your local network who has the link can watch the device screen until the share expires. Viewers cannot control the device.
+share-disabled = Sharing is off. Enable "Allow view-only sharing" under Settings › Experimental first.
+share-link = Link:
+share-copy = Copy
+share-status = Expires in { $time } · { $viewers } viewer(s)
+share-stop = Stop sharing
+share-port = Port:
+share-expires-after = Expires after:
+share-minutes = { $minutes } min
+share-start = Start sharing
+share-started = View-only sharing started
+share-stopped = View-only sharing stopped
+
+## Log console
+log-filter-name = Log
+log-export-failed = Could not write { $path }: { $error }
+log-title = Log
+log-filter = Filter
+log-follow = Follow
+log-copy = Copy
+log-export = Export
+log-clear = Clear
+log-hide = Hide log
+
+## File push
+push-title = Push to Device
+push-destination = Destination:
+push-presets = Presets
+push-start = Push
+push-clear = Clear
+push-pushing = Pushing files to device...
+push-done = Pushed { $count } item(s) to device
+push-partly-failed = Pushed { $count } item(s), { $failed } failed
+
+## Help
+help-topic-wireless = Wireless debugging
+help-topic-scrcpy = scrcpy options
+help-topic-troubleshooting = Troubleshooting
+help-button-hint = Help: { $topic } (F1)
+help-title = Help
+
+## Permissions
+permissions-title = Permissions
+permissions-dangerous-only = Dangerous only
+permissions-refresh = Refresh
+permissions-none = This package requests no runtime permissions.
+permissions-flags = flags: { $flags }
+permissions-dangerous = Dangerous permission
+permissions-fixed = Fixed by the system or device policy
+permissions-grant-failed = Failed to grant { $permission }: { $error }
+permissions-revoke-failed = Failed to revoke { $permission }: { $error }
+
+## Benchmark
+benchmark-title = Benchmark
+benchmark-measuring = Measuring { $current } of { $total }
+benchmark-cancel = Cancel
+benchmark-run = Run
+benchmark-configuration = Configuration
+benchmark-avg-fps = Avg fps
+benchmark-min-fps = Min fps
+benchmark-skipped = Skipped
+benchmark-best = best
+benchmark-apply-best = Apply best ({ $case })
+benchmark-intro = Each configuration opens scrcpy for { $seconds } seconds. Keep something moving on the device (a video or an animation) so the encoder has frames to deliver.
+benchmark-running = Running scrcpy benchmark...
+benchmark-finished = Benchmark finished
+
+## Activities
+activities-title = Activities
+activities-refresh = Refresh
+activities-copy-all = Copy all
+activities-foreground = Foreground:
+activities-unknown = unknown
+activities-tasks = Tasks ({ $count })
+activities-recents = Recents ({ $count })
+activities-copy = Copy
+
+## Device clipboard
+clipboard-title = Device Clipboard
+clipboard-watch = Watch device clipboard
+clipboard-clear = Clear
+clipboard-session-only = Text copied on the device is kept here for this session only.
+clipboard-empty = Nothing copied yet.
+clipboard-copy = Copy to host clipboard
+
+## Notifications
+notifications-dismiss = Dismiss
+notifications-title = Notifications
+notifications-clear = Clear
+notifications-copy-all = Copy all
+notifications-empty = No notifications yet.
+
+## Updates
+update-available = DroidView { $version } is available
+update-remind = Remind me next time
+update-current = You have { $version }.
+update-whats-new = What's new
+update-skip = Skip this version
+update-latest = DroidView { $version } is the latest version
+update-check-failed = Could not check for updates: { $error }
+
+## Messages
+# Shared by many actions
+no-device-adb = No device selected or ADB not configured
+no-device-scrcpy = No device selected or scrcpy not configured
+adb-not-configured = ADB not configured
+error = Error: { $error }
+apks-installed = Installed { $count } APK(s)
+apks-partly-installed = Installed { $installed } of { $total } APK(s); { $name }: { $error }
+browser-failed = Failed to open browser: { $error }
+bootloader-reboot-failed = Could not reboot { $device } to the bootloader
+device-rebooting = Device rebooting
+reboot-failed = Reboot failed: { $error }
+input-failed = Input failed: { $error }
+macros-save-failed = Failed to save macros: { $error }
+gestures-save-failed = Failed to save gestures: { $error }
+scripts-save-failed = Failed to save scripts: { $error }
+automation-save-failed = Failed to save automation rules: { $error }
+tunnels-save-failed = Failed to save tunnels: { $error }
+shell-write-failed = Could not write to the shell: { $error }
+benchmark-applied = Applied { $case }
+crash-title = DroidView Crashed
+crash-saved = DroidView closed unexpectedly last time. A crash report was saved:
+crash-older = { $count } older report(s) are in the same folder.
+crash-open-report = Open Report
+crash-open-folder = Open Folder
+crash-report-issue = Report Issue…
+screenshot-success-title = Screenshot Success
+screenshot-saved = Screenshot Saved Successfully!
+screenshot-saved-to = Screenshot saved to { $path }
+screenshot-open-folder = Open folder
+screenrecord-success-title = Screen Recording Success
+screenrecord-saved-dialog = Screen Recording Saved Successfully!
+dialog-ok = OK
+drop-to-push = Drop to push to device (APKs are installed)
+screenrecord-not-running = screenrecord is not running
+screenrecord-error = Screenrecord error: { $error }
+screenrecord-pull-error = Pull error: { $error }
+screenrecord-pull-failed = Pull failed: { $error }
+
+## Control panel
+control-panel-title = Control Panel
+control-panel-selected = Selected Device: { $model }
+control-panel-id = ID: { $id }
+control-panel-status = Status: { $status }
+control-panel-no-device = No device selected
+control-panel-scrcpy = Scrcpy Controls
+control-panel-live = Live
+control-panel-stream = Stream
+control-panel-templates = Templates
+control-panel-no-templates = Save one under Settings > Session Templates
+control-panel-remote-assist-hint = Built-in: 1 Mbps, 15 fps, 800px, no audio
+control-panel-remote-assist-setup = Remote assistance setup...
+control-panel-bitrate = Bitrate
+control-panel-unit = Unit
+control-panel-current-bitrate = Current: { $bitrate }
+control-panel-video-codec = Video codec:
+control-panel-codec-unsupported = Not supported by this device's encoders
+control-panel-codec-unsupported-here = unsupported on this device
+control-panel-display = Display:
+control-panel-display-id = Display { $id }
+control-panel-display-default = Default
+control-panel-quick-settings = Quick Settings:
+control-panel-show-touches = Show touches
+control-panel-fullscreen = Fullscreen
+control-panel-screen-off = Turn screen off
+control-panel-max-dimensions = Max dimensions:
+control-panel-unlimited = Unlimited
+control-panel-dimension = ({ $pixels } px)
+control-panel-dimension-unlimited = (unlimited)
+session-usage = Session (host usage)
+session-usage-adb = adb server
+session-usage-cpu = CPU { $percent }%
+session-usage-ram = RAM { $size }
+session-usage-not-running = not running
+session-usage-saturated = Decoding is saturating a CPU core. Try a lower max dimension or add --max-fps=30 to extra args.
+api-start-failed = Control API not started: { $error }
+swipe-failed = Failed to send swipe command: { $error }
+swipe-sent = Swipe sent successfully
+swipe-command-failed = Swipe command failed: { $error }
+
+## Dialogs
+dialog-close = Close
+dialog-cancel = Cancel
+dialog-dismiss = Dismiss
+imei-title = Device IMEI
+display-info-title = Display Information
+battery-info-title = Battery Information
+new-display-title = New Display Detected
+new-display-appeared = "{ $name }" (display { $id }) appeared on { $device }.
+new-display-question = Do you want to mirror it?
+new-display-mirror = Mirror
+reconnect-title = Wireless Reconnect
+reconnect-retry = Retry
+screenrecord-title = Screen Recording Settings
+screenrecord-duration = Duration (seconds):
+screenrecord-bitrate = Bitrate (KB/s):
+screenrecord-start = Start Recording
+about-version = Version { $version }
+about-portable = Portable mode
+about-profile = Profile: { $profile }
+about-license = License: GNU General Public License v3
+about-license-free = This program is free software: you can redistribute it and/or modify it under the terms of the GNU General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+about-license-warranty = This program is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+about-website = Website:
+about-license-link = License:
+about-view-license = 📄 View Full License
@@ -0,0 +1,139 @@
+use anyhow::Result;
+use crate::bridge::adb_command;
+
+/// A task and the activities in it, topmost first
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskEntry {
+    pub id: String,
+    pub affinity: String,
+    pub activities: Vec<String>,
+}
+
+/// Snapshot of the activity manager state
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ActivityStack {
+    pub resumed: Option<String>,
+    pub tasks: Vec<TaskEntry>,
+    pub recents: Vec<TaskEntry>,
+}
+
+impl ActivityStack {
+    /// Plain-text rendering for the clipboard
+    pub fn to_text(&self) -> String {
+        let mut text = format!("Foreground: {}\n", self.resumed.as_deref().unwrap_or("unknown"));
+        text.push_str("\nTasks:\n");
+        for task in &self.tasks {
+            text.push_str(&format!("  #{} {}\n", task.id, task.affinity));
+            for activity in &task.activities {
+                text.push_str(&format!("    {}\n", activity));
+            }
+        }
+        text.push_str("\nRecents:\n");
+        for task in &self.recents {
+            text.push_str(&format!("  #{} {}\n", task.id, task.affinity));
+        }
+        text
+    }
+}
+
+pub fn get_activity_stack(adb_path: &str, device_id: &str) -> Result<ActivityStack> {
+    let dumpsys = |section: &str| -> Result<String> {
+        let output = adb_command(adb_path)
+            .args(["-s", device_id, "shell", "dumpsys", "activity", section])
+            .output()?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!("Failed to execute dumpsys activity {}", section));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    };
+
+    let mut stack = parse_activities(&dumpsys("activities")?);
+    stack.recents = parse_recents(&dumpsys("recents").unwrap_or_default());
+    Ok(stack)
+}
+
+/// Pull `com.pkg/.Activity` out of `ActivityRecord{1a2b u0 com.pkg/.Activity t12}`
+fn activity_component(line: &str) -> Option<String> {
+    let record = line.split("ActivityRecord{").nth(1)?;
+    let record = record.split('}').next()?;
+    record
+        .split_whitespace()
+        .find(|token| token.contains('/'))
+        .map(str::to_string)
+}
+
+/// Parse the `#12` id and `A=10123:com.pkg` / `A=com.pkg` affinity of a task header
+fn parse_task_header(line: &str) -> Option<TaskEntry> {
+    if !line.contains("Task{") && !line.contains("TaskRecord{") {
+        return None;
+    }
+    let mut entry = TaskEntry::default();
+    for token in line.split_whitespace() {
+        if let Some(id) = token.strip_prefix('#') {
+            entry.id = id.to_string();
+        } else if let Some(affinity) = token.strip_prefix("A=") {
+            // Newer releases prefix the affinity with the uid
+            entry.affinity = affinity.split_once(':').map(|(_, a)| a).unwrap_or(affinity).to_string();
+        }
+    }
+    (!entry.id.is_empty()).then_some(entry)
+}
+
+/// Parse `dumpsys activity activities` across the Android 7–15 formats
+pub fn parse_activities(output: &str) -> ActivityStack {
+    let mut stack = ActivityStack::default();
+    let mut current: Option<TaskEntry> = None;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+
+        if stack.resumed.is_none()
+            && (trimmed.starts_with("mResumedActivity:")
+                || trimmed.starts_with("ResumedActivity:")
+                || trimmed.starts_with("topResumedActivity="))
+        {
+            stack.resumed = activity_component(trimmed);
+            continue;
+        }
+
+        if trimmed.starts_with('*')
+            && let Some(task) = parse_task_header(trimmed)
+        {
+            if let Some(done) = current.take() {
+                stack.tasks.push(done);
+            }
+            current = Some(task);
+            continue;
+        }
+
+        // Activity rows look like "* Hist #0: ActivityRecord{...}" or "* ActivityRecord{...}"
+        if trimmed.starts_with('*')
+            && trimmed.contains("ActivityRecord{")
+            && let (Some(task), Some(component)) = (current.as_mut(), activity_component(trimmed))
+            && !task.activities.contains(&component)
+        {
+            task.activities.push(component);
+        }
+    }
+
+    if let Some(done) = current {
+        stack.tasks.push(done);
+    }
+    // Tasks without activities are containers (root tasks) rather than real tasks
+    stack.tasks.retain(|task| !task.activities.is_empty());
+    stack
+}
+
+/// Parse `dumpsys activity recents` into task headers, most recent first
+pub fn parse_recents(output: &str) -> Vec<TaskEntry> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with("* Recent #"))
+        .filter_map(|line| {
+            // "* Recent #0: Task{...}" - skip the "#0:" position marker
+            let (_, task) = line.split_once(": ")?;
+            parse_task_header(task)
+        })
+        .collect()
+}
@@ -0,0 +1,210 @@
+//! The RSA key adb uses to authenticate to devices, and the devices' lists of trusted keys
+//!
+//! The adb server signs in with `adbkey` from the user's Android directory and creates the
+//! pair when it is missing. A device trusts the keys in `/data/misc/adb/adb_keys`, which only
+//! root can read, and shows the MD5 fingerprint of a new key when asking to allow debugging.
+
+use crate::bridge::adb_command;
+use anyhow::Result;
+use base64::Engine;
+use md5::{Digest, Md5};
+use std::path::PathBuf;
+
+const PRIVATE_KEY: &str = "adbkey";
+const PUBLIC_KEY: &str = "adbkey.pub";
+const DEVICE_KEYS: &str = "/data/misc/adb/adb_keys";
+/// Printed before the exit status of `su` when reading `DEVICE_KEYS`
+const STATUS_MARKER: &str = "droidview-status:";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostKey {
+    pub path: PathBuf,
+    pub fingerprint: String,
+    /// Usually `user@host`
+    pub comment: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorizedKey {
+    pub fingerprint: String,
+    pub comment: String,
+}
+
+/// What a device says about the computers it trusts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeviceKeys {
+    /// The list needs root; the device trusts this computer if it is connected
+    NeedsRoot,
+    Listed(Vec<AuthorizedKey>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevokeOutcome {
+    /// The trusted keys were deleted with root
+    Revoked,
+    /// Developer options were opened so the user can revoke them there
+    OpenedSettings,
+}
+
+/// Directory adb keeps its keys in
+pub fn key_dir() -> Option<PathBuf> {
+    match std::env::var_os("ANDROID_USER_HOME") {
+        Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir)),
+        _ => dirs::home_dir().map(|home| home.join(".android")),
+    }
+}
+
+/// Colon-separated MD5 of the key data, in the form the device's prompt shows
+pub fn fingerprint(public_key: &str) -> Result<String> {
+    let encoded = public_key
+        .split_whitespace()
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("Empty public key"))?;
+    let key = base64::engine::general_purpose::STANDARD.decode(encoded)?;
+    let digest = Md5::digest(&key);
+    Ok(digest
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<Vec<_>>()
+        .join(":"))
+}
+
+/// Keys in `adb_keys` format, one `<base64> <comment>` per line; lines that are not keys are skipped
+pub fn parse_authorized_keys(text: &str) -> Vec<AuthorizedKey> {
+    text.lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let fingerprint = fingerprint(line).ok()?;
+            let comment = line.split_once(char::is_whitespace).map_or("", |(_, comment)| comment.trim());
+            Some(AuthorizedKey {
+                fingerprint,
+                comment: comment.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// The public half of this computer's key
+pub fn read_host_key() -> Result<HostKey> {
+    let dir = key_dir().ok_or_else(|| anyhow::anyhow!("Could not find the home directory"))?;
+    let path = dir.join(PUBLIC_KEY);
+    let text = std::fs::read_to_string(&path)
+        .map_err(|e| anyhow::anyhow!("Could not read {}: {}", path.display(), e))?;
+    let key = parse_authorized_keys(&text)
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("{} does not hold a key", path.display()))?;
+    Ok(HostKey {
+        path,
+        fingerprint: key.fingerprint,
+        comment: key.comment,
+    })
+}
+
+/// Move the key pair aside and restart the adb server so it creates a new one.
+/// Every device has to allow this computer again afterwards.
+pub fn regenerate_host_key(adb_path: &str) -> Result<HostKey> {
+    if let Some(socket) = crate::bridge::adb_server_socket() {
+        return Err(anyhow::anyhow!("The adb server at {} uses the keys of its own machine", socket));
+    }
+    let dir = key_dir().ok_or_else(|| anyhow::anyhow!("Could not find the home directory"))?;
+
+    let _ = adb_command(adb_path).arg("kill-server").status();
+    let suffix = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    for name in [PRIVATE_KEY, PUBLIC_KEY] {
+        let path = dir.join(name);
+        if path.exists() {
+            std::fs::rename(&path, dir.join(format!("{}.{}.bak", name, suffix)))?;
+        }
+    }
+    let status = adb_command(adb_path).arg("start-server").status()?;
+    if !status.success() {
+        return Err(anyhow::anyhow!("Failed to start ADB server"));
+    }
+    tracing::info!("Regenerated adb keys in {}", dir.display());
+    read_host_key()
+}
+
+/// The device's trusted keys, read with root when it has it
+pub fn device_keys(adb_path: &str, device_id: &str) -> DeviceKeys {
+    // Older adb reports success whatever the command returned, so su's exit status is printed
+    // after the keys. A missing file is no keys rather than no root.
+    let command = format!("su -c 'cat {} 2>/dev/null; true'; echo \"{}$?\"", DEVICE_KEYS, STATUS_MARKER);
+    match adb_command(adb_path).args(["-s", device_id, "shell", &command]).output() {
+        Ok(output) => parse_device_keys(&String::from_utf8_lossy(&output.stdout)),
+        Err(_) => DeviceKeys::NeedsRoot,
+    }
+}
+
+/// The keys printed by `device_keys`' command, if su ran it successfully
+fn parse_device_keys(output: &str) -> DeviceKeys {
+    let Some((keys, status)) = output.rsplit_once(STATUS_MARKER) else {
+        return DeviceKeys::NeedsRoot;
+    };
+    match status.trim() {
+        "0" => DeviceKeys::Listed(parse_authorized_keys(keys)),
+        _ => DeviceKeys::NeedsRoot,
+    }
+}
+
+/// Delete the device's trusted keys with root, or open developer options where the user can.
+/// The current connection stays up until the device is reconnected.
+pub fn revoke_authorizations(adb_path: &str, device_id: &str) -> Result<RevokeOutcome> {
+    let removed = adb_command(adb_path)
+        .args(["-s", device_id, "shell", &format!("su -c 'rm -f {}' && echo ok", DEVICE_KEYS)])
+        .output()?;
+    if removed.status.success() && String::from_utf8_lossy(&removed.stdout).trim() == "ok" {
+        return Ok(RevokeOutcome::Revoked);
+    }
+
+    let opened = adb_command(adb_path)
+        .args([
+            "-s",
+            device_id,
+            "shell",
+            "am start -a android.settings.APPLICATION_DEVELOPMENT_SETTINGS",
+        ])
+        .output()?;
+    if !opened.status.success() {
+        return Err(anyhow::anyhow!("Could not open developer options"));
+    }
+    Ok(RevokeOutcome::OpenedSettings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fingerprints_match_the_device_prompt() {
+        // "QUJD" is "ABC"
+        assert_eq!(
+            fingerprint("QUJD user@host").unwrap(),
+            "90:2F:BD:D2:B1:DF:0C:4F:70:B4:A5:D2:35:25:E9:32"
+        );
+        assert!(fingerprint("not base64!").is_err());
+        assert!(fingerprint("").is_err());
+    }
+
+    #[test]
+    fn parses_authorized_keys() {
+        let keys = parse_authorized_keys("QUJD alice@laptop\n\nsu: not found\nQUJD\n");
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[0].comment, "alice@laptop");
+        assert_eq!(keys[1].comment, "");
+        assert_eq!(keys[0].fingerprint, keys[1].fingerprint);
+    }
+
+    #[test]
+    fn needs_root_unless_su_succeeded() {
+        let DeviceKeys::Listed(keys) = parse_device_keys("QUJD alice@laptop\ndroidview-status:0\n") else {
+            panic!("keys not listed");
+        };
+        assert_eq!(keys[0].comment, "alice@laptop");
+        assert_eq!(parse_device_keys("droidview-status:0\n"), DeviceKeys::Listed(Vec::new()));
+        // su missing, or refused
+        assert_eq!(parse_device_keys("droidview-status:127\n"), DeviceKeys::NeedsRoot);
+        assert_eq!(parse_device_keys("droidview-status:1\n"), DeviceKeys::NeedsRoot);
+        assert_eq!(parse_device_keys(""), DeviceKeys::NeedsRoot);
+    }
+}
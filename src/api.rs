@@ -0,0 +1,540 @@
+//! Local HTTP control API, so test automation and stream decks can drive DroidView
+//!
+//! The server only listens on 127.0.0.1, and every request has to carry the token from Settings,
+//! as `Authorization: Bearer <token>` or `?token=<token>`. Answers are JSON apart from screenshots.
+//!
+//! | Request                                 | Does                                                     |
+//! |-----------------------------------------|----------------------------------------------------------|
+//! | `GET /devices`                          | Connected devices, and whether scrcpy is running         |
+//! | `POST /devices/{serial}/mirror`         | Start mirroring; `?preset=<name>` uses a session template |
+//! | `POST /mirror/stop`                     | Stop mirroring                                           |
+//! | `GET /devices/{serial}/screenshot`      | The screen as PNG                                        |
+//! | `GET /toolkit`                          | Ids and names of the toolkit actions                     |
+//! | `POST /devices/{serial}/toolkit/{id}`   | Run a toolkit action; reboots need `?confirm=1`          |
+//! | `GET /events`                           | WebSocket of `ApiEvent`s as JSON text messages           |
+//!
+//! Requests are handed to the app through `ApiServer::poll`, which answers them on the UI thread.
+
+use crate::device::Device;
+use crate::ui::ToolkitAction;
+use anyhow::Result;
+use axum::extract::State;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade, rejection::WebSocketUpgradeRejection};
+use axum::http::{HeaderMap, Method, StatusCode, Uri, header};
+use axum::response::{IntoResponse, Response};
+use constant_time_eq::constant_time_eq;
+use serde::Serialize;
+use serde_json::json;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// How long a request waits for the app to answer
+const REPLY_TIMEOUT: Duration = Duration::from_secs(30);
+/// Requests waiting for the app at once; more queue until one of them is answered
+const MAX_WAITING_REQUESTS: usize = 8;
+/// Event streams are pinged this often; a client that has not answered by the next ping is dropped
+const PING_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Something that happened in DroidView, sent to every client of `/events`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ApiEvent {
+    DeviceConnected { serial: String, model: String },
+    DeviceDisconnected { serial: String },
+    MirrorStarted,
+    MirrorStopped,
+    /// A task the user started, such as a screenshot, recording or bug report, is done
+    TaskFinished {
+        task: String,
+        ok: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+}
+
+/// Events for devices that became usable or stopped being usable between two device lists
+pub fn device_changes(previous: &[Device], current: &[Device]) -> Vec<ApiEvent> {
+    let usable = |devices: &[Device], serial: &str| {
+        devices.iter().any(|device| device.identifier == serial && device.is_usable())
+    };
+    let connected = current
+        .iter()
+        .filter(|device| device.is_usable() && !usable(previous, &device.identifier))
+        .map(|device| ApiEvent::DeviceConnected {
+            serial: device.identifier.clone(),
+            model: device.model.clone(),
+        });
+    let disconnected = previous
+        .iter()
+        .filter(|device| device.is_usable() && !usable(current, &device.identifier))
+        .map(|device| ApiEvent::DeviceDisconnected { serial: device.identifier.clone() });
+    connected.chain(disconnected).collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApiCommand {
+    Devices,
+    Mirror { serial: String, preset: Option<String> },
+    StopMirror,
+    Screenshot { serial: String },
+    ToolkitActions,
+    Toolkit { serial: String, action: ToolkitAction },
+}
+
+/// An answer: a status and JSON body, or a screenshot
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApiReply {
+    Json(u16, serde_json::Value),
+    Png(Vec<u8>),
+}
+
+impl ApiReply {
+    pub fn ok() -> Self {
+        Self::Json(200, json!({ "ok": true }))
+    }
+
+    pub fn error(status: u16, message: impl Into<String>) -> Self {
+        Self::Json(status, json!({ "error": message.into() }))
+    }
+}
+
+/// A request waiting for the app; answer it with `respond`
+pub struct ApiRequest {
+    pub command: ApiCommand,
+    reply: mpsc::Sender<ApiReply>,
+}
+
+impl ApiRequest {
+    /// Can be called from any thread, e.g. once a screenshot has been taken
+    pub fn respond(self, reply: ApiReply) {
+        // The client may have given up waiting
+        let _ = self.reply.send(reply);
+    }
+}
+
+/// State the request handlers and event streams share
+struct Shared {
+    token: String,
+    /// Set to `true` when the server stops, which also ends the event streams
+    stop: watch::Sender<bool>,
+    /// One sender per open event stream, carrying JSON messages
+    subscribers: Mutex<Vec<tokio::sync::mpsc::UnboundedSender<String>>>,
+    requests: mpsc::Sender<ApiRequest>,
+    ctx: egui::Context,
+}
+
+pub struct ApiServer {
+    port: u16,
+    shared: Arc<Shared>,
+    thread: Option<JoinHandle<()>>,
+    receiver: mpsc::Receiver<ApiRequest>,
+}
+
+impl ApiServer {
+    /// Listen on `port` of 127.0.0.1; `ctx` is woken for each request so an idle window answers too
+    pub fn start(port: u16, token: String, ctx: egui::Context) -> Result<Self> {
+        let listener = std::net::TcpListener::bind(("127.0.0.1", port))
+            .and_then(|listener| listener.set_nonblocking(true).map(|()| listener))
+            .map_err(|e| anyhow::anyhow!("Could not listen on port {}: {}", port, e))?;
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .max_blocking_threads(MAX_WAITING_REQUESTS)
+            .build()?;
+        let (requests, receiver) = mpsc::channel();
+        let shared = Arc::new(Shared {
+            token,
+            stop: watch::Sender::new(false),
+            subscribers: Mutex::new(Vec::new()),
+            requests,
+            ctx,
+        });
+        let thread = {
+            let shared = shared.clone();
+            std::thread::spawn(move || runtime.block_on(serve(listener, shared)))
+        };
+        tracing::info!("Control API listening on 127.0.0.1:{}", port);
+        Ok(Self {
+            port,
+            shared,
+            thread: Some(thread),
+            receiver,
+        })
+    }
+
+    /// Whether this server already runs with `settings`
+    pub fn serves(&self, settings: &crate::config::ControlApi) -> bool {
+        self.port == settings.port && self.shared.token == settings.token
+    }
+
+    /// Send `event` to every open event stream
+    pub fn emit(&self, event: ApiEvent) {
+        let mut subscribers = self.shared.subscribers.lock().unwrap();
+        if subscribers.is_empty() {
+            return;
+        }
+        let message = event_message(&event, chrono::Local::now());
+        // Streams that closed have dropped their receiver
+        subscribers.retain(|subscriber| subscriber.send(message.clone()).is_ok());
+    }
+
+    /// The next request for the app to answer
+    pub fn poll(&self) -> Option<ApiRequest> {
+        self.receiver.try_recv().ok()
+    }
+
+    pub fn stop(mut self) {
+        self.shared.stop.send_replace(true);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        tracing::info!("Control API stopped");
+    }
+}
+
+async fn serve(listener: std::net::TcpListener, shared: Arc<Shared>) {
+    let listener = match tokio::net::TcpListener::from_std(listener) {
+        Ok(listener) => listener,
+        Err(e) => {
+            tracing::warn!("Control API error: {}", e);
+            return;
+        }
+    };
+    let stopped = stopped(shared.stop.subscribe());
+    let app = axum::Router::new().fallback(handle).with_state(shared);
+    if let Err(e) = axum::serve(listener, app).with_graceful_shutdown(stopped).await {
+        tracing::warn!("Control API error: {}", e);
+    }
+}
+
+/// Resolves once the server is told to stop
+async fn stopped(mut stop: watch::Receiver<bool>) {
+    let _ = stop.wait_for(|stop| *stop).await;
+}
+
+async fn handle(
+    State(shared): State<Arc<Shared>>,
+    method: Method,
+    uri: Uri,
+    headers: HeaderMap,
+    upgrade: Result<WebSocketUpgrade, WebSocketUpgradeRejection>,
+) -> Response {
+    let (path, query) = (uri.path(), uri.query().unwrap_or(""));
+    let bearer = headers.get(header::AUTHORIZATION).and_then(|value| value.to_str().ok());
+    let authorized = authorized(bearer, query, &shared.token);
+
+    if authorized && method == Method::GET && path.trim_end_matches('/') == "/events" {
+        return match upgrade {
+            Ok(upgrade) => upgrade.on_upgrade(move |socket| stream_events(socket, shared)),
+            Err(_) => response(ApiReply::error(400, "/events needs a WebSocket connection")),
+        };
+    }
+    let reply = if !authorized {
+        ApiReply::error(401, "Missing or wrong token")
+    } else {
+        match route(&method, path, query) {
+            // Waiting for the app blocks, so it happens off the server's thread
+            Ok(command) => tokio::task::spawn_blocking(move || ask(&shared, command))
+                .await
+                .unwrap_or_else(|_| ApiReply::error(500, "The request failed")),
+            Err(reply) => reply,
+        }
+    };
+    tracing::debug!("Control API {} {}: {}", method, path, status(&reply));
+    response(reply)
+}
+
+/// Pass `command` to the app and wait for its answer
+fn ask(shared: &Shared, command: ApiCommand) -> ApiReply {
+    let (reply, answer) = mpsc::channel();
+    if shared.requests.send(ApiRequest { command, reply }).is_err() {
+        return ApiReply::error(503, "DroidView is shutting down");
+    }
+    shared.ctx.request_repaint();
+    answer
+        .recv_timeout(REPLY_TIMEOUT)
+        .unwrap_or_else(|_| ApiReply::error(504, "DroidView did not answer in time"))
+}
+
+/// Pass on events until the client or the server goes away
+///
+/// Pings from the client are answered and its close is confirmed by the WebSocket itself.
+/// A client that has not answered the last ping by the time of the next one is taken as gone.
+async fn stream_events(mut socket: WebSocket, shared: Arc<Shared>) {
+    let (subscriber, mut events) = tokio::sync::mpsc::unbounded_channel();
+    shared.subscribers.lock().unwrap().push(subscriber);
+    let stopped = stopped(shared.stop.subscribe());
+    tokio::pin!(stopped);
+    let mut ping = tokio::time::interval_at(tokio::time::Instant::now() + PING_INTERVAL, PING_INTERVAL);
+    let mut awaiting_pong = false;
+    tracing::debug!("Control API event stream opened");
+
+    loop {
+        tokio::select! {
+            event = events.recv() => {
+                let Some(message) = event else { break };
+                if socket.send(Message::Text(message.into())).await.is_err() {
+                    break;
+                }
+            }
+            message = socket.recv() => match message {
+                Some(Ok(Message::Pong(_))) => awaiting_pong = false,
+                // What clients send is not used; a close is confirmed on the next receive, which then ends
+                Some(Ok(_)) => {}
+                Some(Err(_)) | None => break,
+            },
+            _ = ping.tick() => {
+                if awaiting_pong {
+                    tracing::debug!("Control API event stream client stopped answering pings");
+                    break;
+                }
+                awaiting_pong = true;
+                if socket.send(Message::Ping(Default::default())).await.is_err() {
+                    break;
+                }
+            }
+            () = &mut stopped => {
+                let _ = socket.send(Message::Close(None)).await;
+                break;
+            }
+        }
+    }
+    tracing::debug!("Control API event stream closed");
+}
+
+/// The event as JSON with the time it happened
+fn event_message(event: &ApiEvent, time: chrono::DateTime<chrono::Local>) -> String {
+    let mut message = serde_json::to_value(event).unwrap_or_default();
+    message["time"] = json!(time.to_rfc3339());
+    message.to_string()
+}
+
+fn status(reply: &ApiReply) -> u16 {
+    match reply {
+        ApiReply::Json(status, _) => *status,
+        ApiReply::Png(_) => 200,
+    }
+}
+
+fn response(reply: ApiReply) -> Response {
+    let (status, content_type, body) = match reply {
+        ApiReply::Json(status, value) => (status, "application/json", value.to_string().into_bytes()),
+        ApiReply::Png(png) => (200, "image/png", png),
+    };
+    let status = StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    (status, [(header::CONTENT_TYPE, content_type)], body).into_response()
+}
+
+fn authorized(bearer: Option<&str>, query: &str, token: &str) -> bool {
+    if token.is_empty() {
+        return false;
+    }
+    // Compared in constant time, so the time taken does not give away how much of it matched
+    let matches = |given: &str| constant_time_eq(given.as_bytes(), token.as_bytes());
+    bearer.and_then(|value| value.strip_prefix("Bearer ")).map(str::trim).is_some_and(matches)
+        || query_value(query, "token").is_some_and(|given| matches(&given))
+}
+
+/// The command for a request, or the reply when it asks for something that does not exist
+fn route(method: &Method, path: &str, query: &str) -> Result<ApiCommand, ApiReply> {
+    let segments: Vec<String> = path.split('/').filter(|segment| !segment.is_empty()).map(decode).collect();
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+    let serial = |serial: &str| serial.to_string();
+
+    match (method.as_str(), segments.as_slice()) {
+        ("GET", ["devices"]) => Ok(ApiCommand::Devices),
+        ("POST", ["devices", device, "mirror"]) => Ok(ApiCommand::Mirror {
+            serial: serial(device),
+            preset: query_value(query, "preset"),
+        }),
+        ("POST", ["mirror", "stop"]) => Ok(ApiCommand::StopMirror),
+        ("GET", ["devices", device, "screenshot"]) => Ok(ApiCommand::Screenshot { serial: serial(device) }),
+        ("GET", ["toolkit"]) => Ok(ApiCommand::ToolkitActions),
+        ("POST", ["devices", device, "toolkit", id]) => {
+            let action = ToolkitAction::from_id(id)
+                .ok_or_else(|| ApiReply::error(404, format!("No toolkit action '{}'", id)))?;
+            if action.needs_confirmation() && query_value(query, "confirm").as_deref() != Some("1") {
+                return Err(ApiReply::error(409, format!("'{}' only runs with ?confirm=1", id)));
+            }
+            Ok(ApiCommand::Toolkit { serial: serial(device), action })
+        }
+        _ => Err(ApiReply::error(404, format!("No endpoint {} {}", method, path))),
+    }
+}
+
+fn query_value(query: &str, key: &str) -> Option<String> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(name, _)| *name == key)
+        .map(|(_, value)| decode(&value.replace('+', " ")))
+}
+
+/// Undo percent-encoding, e.g. of a template name with spaces
+fn decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let escaped = (bytes[index] == b'%')
+            .then(|| text.get(index + 1..index + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_requests() {
+        assert_eq!(route(&Method::GET, "/devices", ""), Ok(ApiCommand::Devices));
+        assert_eq!(
+            route(&Method::POST, "/devices/192.168.1.20:5555/mirror", "preset=Low%20latency"),
+            Ok(ApiCommand::Mirror {
+                serial: "192.168.1.20:5555".to_string(),
+                preset: Some("Low latency".to_string()),
+            })
+        );
+        assert_eq!(route(&Method::POST, "/mirror/stop/", ""), Ok(ApiCommand::StopMirror));
+        assert_eq!(
+            route(&Method::GET, "/devices/ABC/screenshot", ""),
+            Ok(ApiCommand::Screenshot { serial: "ABC".to_string() })
+        );
+        assert_eq!(
+            route(&Method::POST, "/devices/ABC/toolkit/screenshot", ""),
+            Ok(ApiCommand::Toolkit { serial: "ABC".to_string(), action: ToolkitAction::Screenshot })
+        );
+        assert!(route(&Method::GET, "/devices/ABC/mirror", "").is_err());
+        assert!(route(&Method::POST, "/devices/ABC/toolkit/format_disk", "").is_err());
+    }
+
+    #[test]
+    fn reboots_need_confirmation() {
+        let reply = route(&Method::POST, "/devices/ABC/toolkit/reboot", "").unwrap_err();
+        assert!(matches!(reply, ApiReply::Json(409, _)));
+        assert_eq!(
+            route(&Method::POST, "/devices/ABC/toolkit/reboot", "confirm=1"),
+            Ok(ApiCommand::Toolkit { serial: "ABC".to_string(), action: ToolkitAction::Reboot })
+        );
+    }
+
+    #[test]
+    fn checks_the_token() {
+        assert!(authorized(Some("Bearer secret"), "", "secret"));
+        assert!(authorized(None, "preset=x&token=secret", "secret"));
+        assert!(!authorized(Some("Bearer wrong"), "token=wrong", "secret"));
+        assert!(!authorized(None, "", "secret"));
+        // An empty token in the config never lets anyone in
+        assert!(!authorized(Some("Bearer "), "token=", ""));
+    }
+
+    #[test]
+    fn toolkit_ids_are_unique() {
+        for action in ToolkitAction::ALL {
+            assert_eq!(ToolkitAction::from_id(action.id()), Some(action));
+        }
+    }
+
+    #[test]
+    fn describes_events() {
+        let time = chrono::Local::now();
+        let finished = ApiEvent::TaskFinished { task: "screenrecord".to_string(), ok: true, error: None };
+        let value: serde_json::Value = serde_json::from_str(&event_message(&finished, time)).unwrap();
+        assert_eq!(value["type"], "task_finished");
+        assert_eq!(value["task"], "screenrecord");
+        assert_eq!(value["ok"], true);
+        assert!(value.get("error").is_none());
+        assert_eq!(value["time"], time.to_rfc3339());
+        let failed = ApiEvent::TaskFinished { task: "bugreport".to_string(), ok: false, error: Some("No device".to_string()) };
+        let value: serde_json::Value = serde_json::from_str(&event_message(&failed, time)).unwrap();
+        assert_eq!((&value["ok"], &value["error"]), (&json!(false), &json!("No device")));
+        assert!(event_message(&ApiEvent::MirrorStarted, time).contains("\"mirror_started\""));
+    }
+
+    #[test]
+    fn reports_device_changes() {
+        use crate::device::DeviceStatus;
+        let device = |serial: &str, status: DeviceStatus| Device {
+            identifier: serial.to_string(),
+            status,
+            product: String::new(),
+            model: "Pixel".to_string(),
+            device: String::new(),
+            transport_id: String::new(),
+            details: Default::default(),
+        };
+        let previous = [device("A", DeviceStatus::Device), device("B", DeviceStatus::Device)];
+        let current = [device("B", DeviceStatus::Offline), device("C", DeviceStatus::Device)];
+        assert_eq!(
+            device_changes(&previous, &current),
+            [
+                ApiEvent::DeviceConnected { serial: "C".to_string(), model: "Pixel".to_string() },
+                ApiEvent::DeviceDisconnected { serial: "A".to_string() },
+                ApiEvent::DeviceDisconnected { serial: "B".to_string() },
+            ]
+        );
+        assert!(device_changes(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn streams_events_over_websocket() {
+        use std::io::{BufRead, BufReader, Read, Write};
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let server = ApiServer::start(port, "secret".to_string(), egui::Context::default()).unwrap();
+
+        let mut stream = std::net::TcpStream::connect(("127.0.0.1", port)).unwrap();
+        stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        write!(
+            stream,
+            "GET /events?token=secret HTTP/1.1\r\nHost: localhost\r\nUpgrade: websocket\r\n\
+             Connection: Upgrade\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\nSec-WebSocket-Version: 13\r\n\r\n"
+        )
+        .unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut head = String::new();
+        while !head.ends_with("\r\n\r\n") {
+            reader.read_line(&mut head).unwrap();
+        }
+        assert!(head.starts_with("HTTP/1.1 101"), "{}", head);
+        assert!(head.contains("s3pPLMBiTxaQ9kYGzzhZRbK+xOo="));
+
+        // The stream subscribes right after the handshake
+        while server.shared.subscribers.lock().unwrap().is_empty() {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        server.emit(ApiEvent::MirrorStarted);
+        let mut header = [0; 2];
+        reader.read_exact(&mut header).unwrap();
+        let mut payload = vec![0; header[1] as usize];
+        reader.read_exact(&mut payload).unwrap();
+        assert_eq!(header[0], 0x81);
+        assert!(String::from_utf8(payload).unwrap().contains("mirror_started"));
+
+        // A close from the client, masked as clients send frames, is confirmed
+        reader.get_mut().write_all(&[0x88, 0x80, 1, 2, 3, 4]).unwrap();
+        reader.read_exact(&mut header).unwrap();
+        assert_eq!(header[0], 0x88);
+        server.stop();
+    }
+
+    #[test]
+    fn decodes_percent_escapes() {
+        assert_eq!(decode("Low%20latency"), "Low latency");
+        assert_eq!(decode("100%"), "100%");
+        assert_eq!(decode("%E2%9C%93"), "✓");
+    }
+}
@@ -16,11 +16,34 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
-use crate::bridge::{AdbBridge, ScrcpyBridge};
-use crate::config::AppConfig;
-use crate::device::{get_devices, Device};
+use crate::activity_stack::ActivityStack;
+use crate::adb_keys::{DeviceKeys, HostKey, RevokeOutcome};
+use crate::api::{ApiCommand, ApiEvent, ApiReply, ApiRequest, ApiServer};
+use crate::benchmark::{BenchmarkCase, BenchmarkResult};
+use crate::bridge::{AdbBridge, ConnectOutcome, ScrcpyBridge, ScrcpyOptions};
+use crate::capabilities::{Capability, CapabilityStatus, DeviceCapabilities};
+use crate::config::{AppConfig, SessionTemplate, ShellSnippet, WindowGeometry, REMOTE_ASSISTANCE};
+use crate::device::{get_devices, Device, DeviceStatus, DisplayEntry};
+use crate::dock::{DockLayout, DockPanel};
+use crate::fastboot::{FastbootBridge, FastbootDevice};
+use crate::intent::IntentSpec;
+use crate::monitor::{ResourceMonitor, SessionUsage};
+use crate::reboot::RebootTarget;
+use crate::reconnect::Reconnector;
+use crate::packages::{self, AppDetails, DeviceUser, PackageInfo, RuntimePermission};
+use crate::hotkeys::{GlobalHotkeys, HotkeyTarget};
+use crate::share::ShareServer;
+use crate::shortcuts::{ShortcutAction, Shortcuts};
+use crate::sideload::SideloadStage;
+use crate::t;
+use crate::stream::{PreflightCheck, StreamSession};
+use crate::tools::{InstallStage, Installed};
+use crate::tunnel::OpenTunnel;
+use crate::update::UpdateInfo;
 use crate::ui::{
-    BottomPanel, DeviceList, SettingsWindow, SwipePanel, ToolkitPanel, WirelessAdbPanel,
+    ActivityViewer, ActivityViewerAction, app_context_menu, BenchmarkAction, BenchmarkWindow, BootloaderAction, BootloaderWizard, app_details, AppAction, AppDetailsAction, AppDetailsWindow, CapabilityAction, CapabilityWizard, ClipboardHistoryWindow, ColorPickerAction, ColorPickerWindow, CommandPalette, AppListFilter, user_selector, BottomPanel, DeviceList, DeviceListAction, FilePushAction, FrameProfilerAction, FrameProfilerWindow,
+    FilePushDialog, GalleryWindow, HealthBanner, HealthBannerAction, help_button, HelpTopic, HelpWindow, HierarchyAction, HierarchyWindow, INSPECTOR_EDGE, IntentSenderAction, IntentSenderWindow, LogConsole, PermissionsAction, PermissionsWindow, PipAction, PipPane, PIP_EDGE, PushStatus, RecordingLibraryAction, RecordingLibraryWindow, RemoteAssistAction, RemoteAssistWizard, ScreenshotDiffAction, ScreenshotDiffWindow, SettingsWindow, ShareAction, ShareWindow, ShellCommandAction, ShellCommandWindow, ShellConsoleAction, ShellConsoleWindow, StreamAction, PluginAction, PluginsPanel, ScriptsAction, ScriptsWindow, StreamWindow, SwipeAction, SwipePanel, TapPicker, TapPickerAction, PICKER_EDGE, THUMBNAIL_EDGE, THUMBNAIL_INTERVAL, ToolkitPanel,
+    AdbKeysAction, AdbKeysWindow, AutomationWindow, DeviceControlsAction, DeviceControlsPanel, LocaleAction, LocaleWindow, MockLocationAction, MockLocationWindow, NativePreviewAction, NativePreviewWindow, ResolutionAction, ResolutionWindow, AutomationWindowAction, GestureDesigner, GestureDesignerAction, MacroAction, MacroWindow, PaletteEntry, PerfettoAction, PerfettoWindow, ShortcutsAction, ShortcutsWindow, SideloadAction, SideloadWindow, StandbyAction, StandbyWindow, Toasts, Tool, TunnelAction, TunnelsWindow, UpdateNotice, UpdateNoticeAction, WifiAction, WifiWindow, WirelessAdbPanel,
 };
 use eframe::egui;
 use egui::{Color32, RichText, Ui};
@@ -29,7 +52,7 @@ use tokio::sync::Mutex;
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
 use tracing::{error, info};
-use crate::utils::is_process_running;
+use crate::utils::{format_file_size, is_process_running, tool_missing};
 use crate::ui::BottomPanelAction;
 use std::collections::HashMap;
 use egui_knob::{Knob, KnobStyle, LabelPosition};
@@ -38,18 +61,240 @@ use egui_phosphor;
 // Background task results
 #[derive(Debug)]
 enum BackgroundTaskResult {
-    AppList(Vec<(String, String)>),
-    DisableAppList(Vec<(String, String)>),
+    AppList(Vec<PackageInfo>),
+    DisableAppList(Vec<PackageInfo>),
+    DeviceUsers(String, Vec<DeviceUser>),
     Imei(String),
     DisplayInfo(String),
     BatteryInfo(String),
+    WirelessReconnect(Vec<(String, Result<(), String>)>),
+    VideoCodecs(String, Vec<String>),
+    Displays(String, Vec<DisplayEntry>),
+    PushProgress(usize, PushStatus),
+    PushFinished { pushed: usize, failed: usize },
+    DroppedApksInstalled(Vec<(String, Result<(), String>)>),
+    SessionUsage(SessionUsage),
+    AppActionFinished(Result<String, String>),
+    Permissions {
+        package: String,
+        permissions: Vec<RuntimePermission>,
+        error: Option<String>,
+    },
+    AppDetails(String, Result<AppDetails, String>),
+    ToolsDetected {
+        adb: Option<std::path::PathBuf>,
+        scrcpy: Option<std::path::PathBuf>,
+        redetect: bool,
+    },
+    IntentSent(Result<String, String>),
+    ShellCommand(Result<crate::shell_console::CommandOutput, String>),
+    Capabilities(String, DeviceCapabilities),
+    RebootTargets(String, Result<Vec<RebootTarget>, String>),
+    CompanionGranted(Result<String, String>),
+    ActivityStack(Result<ActivityStack, String>),
+    DeviceClipboard(String, Result<Option<String>, String>),
+    Thumbnails(Vec<(String, egui::ColorImage)>),
+    Pip(String, Result<egui::ColorImage, String>),
+    DeviceStates(Vec<(String, DeviceStatus)>),
+    Devices(Result<Vec<Device>, String>),
+    ScrcpyStatus(bool),
+    AutoReconnect(String, Result<(), String>),
+    FastbootScan(Result<Vec<FastbootDevice>, String>),
+    BootloaderFlash(Result<String, String>),
+    BootloaderManufacturer(String),
+    AdbKeys(Result<HostKey, String>, Option<(String, DeviceKeys)>),
+    AdbKeyRegenerated(Result<HostKey, String>),
+    AdbKeysRevoked(Result<RevokeOutcome, String>),
+    TunnelOpened(String, Result<(OpenTunnel, Result<ConnectOutcome, String>), String>),
+    Health(Vec<(String, Option<std::time::Duration>)>),
+    StreamPreflight(Vec<PreflightCheck>),
+    StreamStarted(Result<StreamSession, String>),
+    BenchmarkProgress(usize, Option<BenchmarkResult>),
+    BenchmarkFinished,
+    SideloadProgress(SideloadStage),
+    MacroProgress(usize),
+    MacroFinished(Result<(), String>),
+    MockRouteProgress(usize),
+    MockRouteFinished(Result<(), String>),
+    ScriptOutput(String),
+    Plugin(String, Result<String, String>),
+    ScriptFinished(String, Result<(), String>),
+    Gesture(String, Result<(), String>),
+    TapPicker(Result<(egui::ColorImage, (u32, u32)), String>),
+    Ocr(Result<String, String>),
+    Hierarchy(Result<(crate::hierarchy::Node, egui::ColorImage, (u32, u32)), String>),
+    ColorPicker(Result<egui::ColorImage, String>),
+    LongScreenshot(Result<(std::path::PathBuf, usize), String>),
+    ScreenshotDiff(usize, String, Result<image::RgbaImage, String>),
+    BugreportProgress(u8),
+    PerfettoFinished(Result<std::path::PathBuf, String>),
+    Gfxinfo(String, Result<Vec<crate::gfxinfo::Frame>, String>),
+    ForegroundPackage(Result<String, String>),
+    Wifi(Result<crate::wifi::WifiInfo, String>),
+    DeviceControls(String, Result<crate::controls::ControlState, String>),
+    Resolution(Result<crate::report::DisplayInfo, String>),
+    Locale(Result<crate::locale::LocaleInfo, String>, Option<String>),
+    Clock(Result<i64, String>, Option<Result<crate::clock::SyncOutcome, String>>),
+    Standby(Result<crate::standby::StandbyInfo, String>, Option<String>),
+    MockLocation(Result<crate::mock_location::MockLocationInfo, String>, Option<String>),
+    Tether(String, bool, Option<crate::gnirehtet::Relay>, Result<(), String>),
+    Sndcpy(String, Result<Option<crate::sndcpy::AudioSession>, String>),
+    NativePreview(Result<crate::native_preview::PreviewSession, String>),
+    Automation(String, String, Result<String, String>),
+    AutomationBattery(Vec<(String, Option<u8>)>),
+    BugreportFinished(Result<std::path::PathBuf, String>),
+    SideloadFinished(Result<String, String>),
+    ScreenrecordFinished(Result<std::path::PathBuf, String>),
+    ScreenrecordStopped(Result<(), String>),
+    ExportFinished(Result<std::path::PathBuf, String>),
+    DeviceReportFinished(Result<std::path::PathBuf, String>),
+    ToolInstallProgress(Tool, InstallStage),
+    ToolInstalled(Tool, Result<Installed, String>),
+    /// `manual` checks also report when there is nothing new
+    UpdateChecked { result: Result<Option<UpdateInfo>, String>, manual: bool },
+}
+
+impl BackgroundTaskResult {
+    /// The user-started task this result ends and how it went, for the control API's events;
+    /// `None` for progress updates and the app's own polling
+    fn finished_task(&self) -> Option<(&'static str, Result<(), String>)> {
+        fn outcome<T>(result: &Result<T, String>) -> Result<(), String> {
+            result.as_ref().map(|_| ()).map_err(Clone::clone)
+        }
+        fn all<T>(results: &[(T, Result<(), String>)]) -> Result<(), String> {
+            let failed: Vec<&str> = results.iter().filter_map(|(_, result)| result.as_ref().err()).map(String::as_str).collect();
+            if failed.is_empty() {
+                Ok(())
+            } else {
+                Err(failed.join("; "))
+            }
+        }
+        let finished = match self {
+            Self::WirelessReconnect(results) => ("wireless_reconnect", all(results)),
+            Self::DroppedApksInstalled(results) => ("drop_install", all(results)),
+            Self::AppActionFinished(result) => ("app_action", outcome(result)),
+            Self::IntentSent(result) => ("send_intent", outcome(result)),
+            Self::ShellCommand(result) => ("shell_command", outcome(result)),
+            Self::CompanionGranted(result) => ("companion_grant", outcome(result)),
+            Self::BootloaderFlash(result) => ("bootloader_flashing", outcome(result)),
+            Self::AdbKeyRegenerated(result) => ("adb_keys_regenerate", outcome(result)),
+            Self::AdbKeysRevoked(result) => ("adb_keys_revoke", outcome(result)),
+            Self::TunnelOpened(_, result) => ("tunnel", outcome(result)),
+            Self::StreamStarted(result) => ("stream_start", outcome(result)),
+            Self::BenchmarkFinished => ("benchmark", Ok(())),
+            Self::MacroFinished(result) => ("macro", outcome(result)),
+            Self::MockRouteFinished(result) => ("mock_route", outcome(result)),
+            Self::Plugin(_, result) => ("plugin", outcome(result)),
+            Self::ScriptFinished(_, result) => ("script", outcome(result)),
+            Self::Gesture(_, result) => ("gesture", outcome(result)),
+            Self::Ocr(result) => ("ocr", outcome(result)),
+            Self::LongScreenshot(result) => ("long_screenshot", outcome(result)),
+            Self::PerfettoFinished(result) => ("perfetto", outcome(result)),
+            Self::Tether(_, _, _, result) => ("tether", outcome(result)),
+            Self::Sndcpy(_, result) => ("sndcpy", outcome(result)),
+            Self::NativePreview(result) => ("native_preview", outcome(result)),
+            Self::Automation(_, _, result) => ("automation", outcome(result)),
+            Self::BugreportFinished(result) => ("bugreport", outcome(result)),
+            Self::SideloadFinished(result) => ("sideload", outcome(result)),
+            Self::ScreenrecordFinished(result) => ("screenrecord", outcome(result)),
+            Self::ExportFinished(result) => ("export_recording", outcome(result)),
+            Self::DeviceReportFinished(result) => ("device_report", outcome(result)),
+            Self::ToolInstalled(_, result) => ("tool_install", outcome(result)),
+            _ => return None,
+        };
+        Some(finished)
+    }
 }
 
 // Wrapper types for different task results
-pub struct AppListResult(pub Vec<(String, String)>);
-pub struct DisableAppListResult(pub Vec<(String, String)>);
+pub struct AppListResult(pub Vec<PackageInfo>);
+pub struct DisableAppListResult(pub Vec<PackageInfo>);
+pub struct DeviceUsersResult(pub String, pub Vec<DeviceUser>);
 pub struct ImeiResult(pub String);
 pub struct BatteryInfoResult(pub String);
+pub struct WirelessReconnectResult(pub Vec<(String, Result<(), String>)>);
+pub struct VideoCodecsResult(pub String, pub Vec<String>);
+pub struct DisplaysResult(pub String, pub Vec<DisplayEntry>);
+pub struct DroppedApksResult(pub Vec<(String, Result<(), String>)>);
+pub struct SessionUsageResult(pub SessionUsage);
+pub struct AppActionResult(pub Result<String, String>);
+pub struct AppDetailsResult(pub String, pub Result<AppDetails, String>);
+pub struct IntentSentResult(pub Result<String, String>);
+pub struct ShellCommandResult(pub Result<crate::shell_console::CommandOutput, String>);
+pub struct CapabilitiesResult(pub String, pub DeviceCapabilities);
+pub struct RebootTargetsResult(pub String, pub Result<Vec<RebootTarget>, String>);
+pub struct CompanionGrantResult(pub Result<String, String>);
+pub struct ActivityStackResult(pub Result<ActivityStack, String>);
+pub struct DeviceClipboardResult(pub String, pub Result<Option<String>, String>);
+pub struct ThumbnailsResult(pub Vec<(String, egui::ColorImage)>);
+/// A screenshot of a device for the live preview
+pub struct PipResult(pub String, pub Result<egui::ColorImage, String>);
+pub struct DeviceStatesResult(pub Vec<(String, DeviceStatus)>);
+pub struct DevicesResult(pub Result<Vec<Device>, String>);
+/// Whether a scrcpy process is running on this computer
+pub struct ScrcpyStatusResult(pub bool);
+pub struct AutoReconnectResult(pub String, pub Result<(), String>);
+pub struct FastbootScanResult(pub Result<Vec<FastbootDevice>, String>);
+pub struct BootloaderFlashResult(pub Result<String, String>);
+pub struct BootloaderManufacturerResult(pub String);
+pub struct AdbKeysResult(pub Result<HostKey, String>, pub Option<(String, DeviceKeys)>);
+pub struct AdbKeyRegeneratedResult(pub Result<HostKey, String>);
+pub struct AdbKeysRevokedResult(pub Result<RevokeOutcome, String>);
+pub struct TunnelOpenedResult(pub String, pub Result<(OpenTunnel, Result<ConnectOutcome, String>), String>);
+pub struct HealthResult(pub Vec<(String, Option<std::time::Duration>)>);
+pub struct StreamPreflightResult(pub Vec<PreflightCheck>);
+pub struct StreamStartedResult(pub Result<StreamSession, String>);
+pub struct ScreenrecordResult(pub Result<std::path::PathBuf, String>);
+pub struct ScreenrecordStopResult(pub Result<(), String>);
+pub struct PerfettoResult(pub Result<std::path::PathBuf, String>);
+pub struct GfxinfoResult(pub String, pub Result<Vec<crate::gfxinfo::Frame>, String>);
+pub struct ForegroundPackageResult(pub Result<String, String>);
+pub struct WifiResult(pub Result<crate::wifi::WifiInfo, String>);
+/// Device, and its brightness and volumes after reading or changing them
+pub struct DeviceControlsResult(pub String, pub Result<crate::controls::ControlState, String>);
+/// Size and density after reading or changing them
+pub struct ResolutionResult(pub Result<crate::report::DisplayInfo, String>);
+/// Locale and time zone as read back, and why a change failed
+pub struct LocaleResult(pub Result<crate::locale::LocaleInfo, String>, pub Option<String>);
+/// Clock drift in milliseconds, after setting the clock when that was asked for
+pub struct ClockResult(pub Result<i64, String>, pub Option<Result<crate::clock::SyncOutcome, String>>);
+/// Doze and standby state as read back, and why a change failed
+pub struct StandbyResult(pub Result<crate::standby::StandbyInfo, String>, pub Option<String>);
+/// Mock location provider state as read back, and why a change failed
+pub struct MockLocationResult(pub Result<crate::mock_location::MockLocationInfo, String>, pub Option<String>);
+/// Rule, device and what running it printed or the error
+pub struct AutomationResult(pub String, pub String, pub Result<String, String>);
+pub struct AutomationBatteryResult(pub Vec<(String, Option<u8>)>);
+/// Device, and the audio session when sndcpy was needed for it
+pub struct SndcpyResult(pub String, pub Result<Option<crate::sndcpy::AudioSession>, String>);
+/// The scrcpy server started and decoding for the native preview
+pub struct NativePreviewResult(pub Result<crate::native_preview::PreviewSession, String>);
+/// Device, whether tethering was being turned on, a relay started for it, and the outcome
+pub struct TetherResult(pub String, pub bool, pub Option<crate::gnirehtet::Relay>, pub Result<(), String>);
+pub struct ExportResult(pub Result<std::path::PathBuf, String>);
+pub struct DeviceReportResult(pub Result<std::path::PathBuf, String>);
+/// Label of the plugin command that ran and what it printed
+pub struct PluginResult(pub String, pub Result<String, String>);
+/// A screenshot for the tap picker and the screen size it was taken at
+pub struct TapPickerResult(pub Result<(egui::ColorImage, (u32, u32)), String>);
+/// Text read off the screen
+pub struct OcrResult(pub Result<String, String>);
+/// View hierarchy on screen, a screenshot of it and the screen size
+pub struct HierarchyResult(pub Result<(crate::hierarchy::Node, egui::ColorImage, (u32, u32)), String>);
+/// Where a long screenshot was saved and how many screens it took
+pub struct LongScreenshotResult(pub Result<(std::path::PathBuf, usize), String>);
+/// A screenshot for one side of the comparison, and the device it is from
+pub struct ScreenshotDiffResult(pub usize, pub String, pub Result<image::RgbaImage, String>);
+/// Full-resolution screenshot for the color picker
+pub struct ColorPickerResult(pub Result<egui::ColorImage, String>);
+/// Name of the gesture performed, which is empty for an unsaved one, and the outcome
+pub struct GestureResult(pub String, pub Result<(), String>);
+pub struct ToolsDetectedResult {
+    pub adb: Option<std::path::PathBuf>,
+    pub scrcpy: Option<std::path::PathBuf>,
+    pub redetect: bool,
+}
+pub struct PermissionsResult(pub String, pub Vec<RuntimePermission>, pub Option<String>);
 
 impl From<AppListResult> for BackgroundTaskResult {
     fn from(result: AppListResult) -> Self {
@@ -75,8 +320,376 @@ impl From<BatteryInfoResult> for BackgroundTaskResult {
     }
 }
 
-impl From<Vec<(String, String)>> for BackgroundTaskResult {
-    fn from(apps: Vec<(String, String)>) -> Self {
+impl From<WirelessReconnectResult> for BackgroundTaskResult {
+    fn from(result: WirelessReconnectResult) -> Self {
+        BackgroundTaskResult::WirelessReconnect(result.0)
+    }
+}
+
+impl From<VideoCodecsResult> for BackgroundTaskResult {
+    fn from(result: VideoCodecsResult) -> Self {
+        BackgroundTaskResult::VideoCodecs(result.0, result.1)
+    }
+}
+
+impl From<DisplaysResult> for BackgroundTaskResult {
+    fn from(result: DisplaysResult) -> Self {
+        BackgroundTaskResult::Displays(result.0, result.1)
+    }
+}
+
+impl From<DroppedApksResult> for BackgroundTaskResult {
+    fn from(result: DroppedApksResult) -> Self {
+        BackgroundTaskResult::DroppedApksInstalled(result.0)
+    }
+}
+
+impl From<PermissionsResult> for BackgroundTaskResult {
+    fn from(result: PermissionsResult) -> Self {
+        BackgroundTaskResult::Permissions {
+            package: result.0,
+            permissions: result.1,
+            error: result.2,
+        }
+    }
+}
+
+impl From<CapabilitiesResult> for BackgroundTaskResult {
+    fn from(result: CapabilitiesResult) -> Self {
+        BackgroundTaskResult::Capabilities(result.0, result.1)
+    }
+}
+
+impl From<RebootTargetsResult> for BackgroundTaskResult {
+    fn from(result: RebootTargetsResult) -> Self {
+        BackgroundTaskResult::RebootTargets(result.0, result.1)
+    }
+}
+
+impl From<StreamPreflightResult> for BackgroundTaskResult {
+    fn from(result: StreamPreflightResult) -> Self {
+        BackgroundTaskResult::StreamPreflight(result.0)
+    }
+}
+
+impl From<StreamStartedResult> for BackgroundTaskResult {
+    fn from(result: StreamStartedResult) -> Self {
+        BackgroundTaskResult::StreamStarted(result.0)
+    }
+}
+
+impl From<GfxinfoResult> for BackgroundTaskResult {
+    fn from(result: GfxinfoResult) -> Self {
+        BackgroundTaskResult::Gfxinfo(result.0, result.1)
+    }
+}
+
+impl From<WifiResult> for BackgroundTaskResult {
+    fn from(result: WifiResult) -> Self {
+        BackgroundTaskResult::Wifi(result.0)
+    }
+}
+
+impl From<DeviceControlsResult> for BackgroundTaskResult {
+    fn from(result: DeviceControlsResult) -> Self {
+        BackgroundTaskResult::DeviceControls(result.0, result.1)
+    }
+}
+
+impl From<ResolutionResult> for BackgroundTaskResult {
+    fn from(result: ResolutionResult) -> Self {
+        BackgroundTaskResult::Resolution(result.0)
+    }
+}
+
+impl From<LocaleResult> for BackgroundTaskResult {
+    fn from(result: LocaleResult) -> Self {
+        BackgroundTaskResult::Locale(result.0, result.1)
+    }
+}
+
+impl From<StandbyResult> for BackgroundTaskResult {
+    fn from(result: StandbyResult) -> Self {
+        BackgroundTaskResult::Standby(result.0, result.1)
+    }
+}
+
+impl From<MockLocationResult> for BackgroundTaskResult {
+    fn from(result: MockLocationResult) -> Self {
+        BackgroundTaskResult::MockLocation(result.0, result.1)
+    }
+}
+
+impl From<ClockResult> for BackgroundTaskResult {
+    fn from(result: ClockResult) -> Self {
+        BackgroundTaskResult::Clock(result.0, result.1)
+    }
+}
+
+impl From<AutomationResult> for BackgroundTaskResult {
+    fn from(result: AutomationResult) -> Self {
+        BackgroundTaskResult::Automation(result.0, result.1, result.2)
+    }
+}
+
+impl From<AutomationBatteryResult> for BackgroundTaskResult {
+    fn from(result: AutomationBatteryResult) -> Self {
+        BackgroundTaskResult::AutomationBattery(result.0)
+    }
+}
+
+impl From<NativePreviewResult> for BackgroundTaskResult {
+    fn from(result: NativePreviewResult) -> Self {
+        BackgroundTaskResult::NativePreview(result.0)
+    }
+}
+
+impl From<SndcpyResult> for BackgroundTaskResult {
+    fn from(result: SndcpyResult) -> Self {
+        BackgroundTaskResult::Sndcpy(result.0, result.1)
+    }
+}
+
+impl From<PluginResult> for BackgroundTaskResult {
+    fn from(result: PluginResult) -> Self {
+        BackgroundTaskResult::Plugin(result.0, result.1)
+    }
+}
+
+impl From<TapPickerResult> for BackgroundTaskResult {
+    fn from(result: TapPickerResult) -> Self {
+        BackgroundTaskResult::TapPicker(result.0)
+    }
+}
+
+impl From<OcrResult> for BackgroundTaskResult {
+    fn from(result: OcrResult) -> Self {
+        BackgroundTaskResult::Ocr(result.0)
+    }
+}
+
+impl From<LongScreenshotResult> for BackgroundTaskResult {
+    fn from(result: LongScreenshotResult) -> Self {
+        BackgroundTaskResult::LongScreenshot(result.0)
+    }
+}
+
+impl From<ScreenshotDiffResult> for BackgroundTaskResult {
+    fn from(result: ScreenshotDiffResult) -> Self {
+        BackgroundTaskResult::ScreenshotDiff(result.0, result.1, result.2)
+    }
+}
+
+impl From<ColorPickerResult> for BackgroundTaskResult {
+    fn from(result: ColorPickerResult) -> Self {
+        BackgroundTaskResult::ColorPicker(result.0)
+    }
+}
+
+impl From<HierarchyResult> for BackgroundTaskResult {
+    fn from(result: HierarchyResult) -> Self {
+        BackgroundTaskResult::Hierarchy(result.0)
+    }
+}
+
+impl From<GestureResult> for BackgroundTaskResult {
+    fn from(result: GestureResult) -> Self {
+        BackgroundTaskResult::Gesture(result.0, result.1)
+    }
+}
+
+impl From<TetherResult> for BackgroundTaskResult {
+    fn from(result: TetherResult) -> Self {
+        BackgroundTaskResult::Tether(result.0, result.1, result.2, result.3)
+    }
+}
+
+impl From<ForegroundPackageResult> for BackgroundTaskResult {
+    fn from(result: ForegroundPackageResult) -> Self {
+        BackgroundTaskResult::ForegroundPackage(result.0)
+    }
+}
+
+impl From<PerfettoResult> for BackgroundTaskResult {
+    fn from(result: PerfettoResult) -> Self {
+        BackgroundTaskResult::PerfettoFinished(result.0)
+    }
+}
+
+impl From<ScreenrecordResult> for BackgroundTaskResult {
+    fn from(result: ScreenrecordResult) -> Self {
+        BackgroundTaskResult::ScreenrecordFinished(result.0)
+    }
+}
+
+impl From<ScreenrecordStopResult> for BackgroundTaskResult {
+    fn from(result: ScreenrecordStopResult) -> Self {
+        BackgroundTaskResult::ScreenrecordStopped(result.0)
+    }
+}
+
+impl From<ExportResult> for BackgroundTaskResult {
+    fn from(result: ExportResult) -> Self {
+        BackgroundTaskResult::ExportFinished(result.0)
+    }
+}
+
+impl From<DeviceReportResult> for BackgroundTaskResult {
+    fn from(result: DeviceReportResult) -> Self {
+        BackgroundTaskResult::DeviceReportFinished(result.0)
+    }
+}
+
+impl From<ActivityStackResult> for BackgroundTaskResult {
+    fn from(result: ActivityStackResult) -> Self {
+        BackgroundTaskResult::ActivityStack(result.0)
+    }
+}
+
+impl From<DeviceClipboardResult> for BackgroundTaskResult {
+    fn from(result: DeviceClipboardResult) -> Self {
+        BackgroundTaskResult::DeviceClipboard(result.0, result.1)
+    }
+}
+
+impl From<PipResult> for BackgroundTaskResult {
+    fn from(result: PipResult) -> Self {
+        BackgroundTaskResult::Pip(result.0, result.1)
+    }
+}
+
+impl From<ThumbnailsResult> for BackgroundTaskResult {
+    fn from(result: ThumbnailsResult) -> Self {
+        BackgroundTaskResult::Thumbnails(result.0)
+    }
+}
+
+impl From<DeviceStatesResult> for BackgroundTaskResult {
+    fn from(result: DeviceStatesResult) -> Self {
+        BackgroundTaskResult::DeviceStates(result.0)
+    }
+}
+
+impl From<DevicesResult> for BackgroundTaskResult {
+    fn from(result: DevicesResult) -> Self {
+        BackgroundTaskResult::Devices(result.0)
+    }
+}
+
+impl From<ScrcpyStatusResult> for BackgroundTaskResult {
+    fn from(result: ScrcpyStatusResult) -> Self {
+        BackgroundTaskResult::ScrcpyStatus(result.0)
+    }
+}
+
+impl From<HealthResult> for BackgroundTaskResult {
+    fn from(result: HealthResult) -> Self {
+        BackgroundTaskResult::Health(result.0)
+    }
+}
+
+impl From<AutoReconnectResult> for BackgroundTaskResult {
+    fn from(result: AutoReconnectResult) -> Self {
+        BackgroundTaskResult::AutoReconnect(result.0, result.1)
+    }
+}
+
+impl From<FastbootScanResult> for BackgroundTaskResult {
+    fn from(result: FastbootScanResult) -> Self {
+        BackgroundTaskResult::FastbootScan(result.0)
+    }
+}
+
+impl From<BootloaderFlashResult> for BackgroundTaskResult {
+    fn from(result: BootloaderFlashResult) -> Self {
+        BackgroundTaskResult::BootloaderFlash(result.0)
+    }
+}
+
+impl From<BootloaderManufacturerResult> for BackgroundTaskResult {
+    fn from(result: BootloaderManufacturerResult) -> Self {
+        BackgroundTaskResult::BootloaderManufacturer(result.0)
+    }
+}
+
+impl From<AdbKeysResult> for BackgroundTaskResult {
+    fn from(result: AdbKeysResult) -> Self {
+        BackgroundTaskResult::AdbKeys(result.0, result.1)
+    }
+}
+
+impl From<AdbKeyRegeneratedResult> for BackgroundTaskResult {
+    fn from(result: AdbKeyRegeneratedResult) -> Self {
+        BackgroundTaskResult::AdbKeyRegenerated(result.0)
+    }
+}
+
+impl From<AdbKeysRevokedResult> for BackgroundTaskResult {
+    fn from(result: AdbKeysRevokedResult) -> Self {
+        BackgroundTaskResult::AdbKeysRevoked(result.0)
+    }
+}
+
+impl From<TunnelOpenedResult> for BackgroundTaskResult {
+    fn from(result: TunnelOpenedResult) -> Self {
+        BackgroundTaskResult::TunnelOpened(result.0, result.1)
+    }
+}
+
+impl From<CompanionGrantResult> for BackgroundTaskResult {
+    fn from(result: CompanionGrantResult) -> Self {
+        BackgroundTaskResult::CompanionGranted(result.0)
+    }
+}
+
+impl From<IntentSentResult> for BackgroundTaskResult {
+    fn from(result: IntentSentResult) -> Self {
+        BackgroundTaskResult::IntentSent(result.0)
+    }
+}
+
+impl From<ShellCommandResult> for BackgroundTaskResult {
+    fn from(result: ShellCommandResult) -> Self {
+        BackgroundTaskResult::ShellCommand(result.0)
+    }
+}
+
+impl From<ToolsDetectedResult> for BackgroundTaskResult {
+    fn from(result: ToolsDetectedResult) -> Self {
+        BackgroundTaskResult::ToolsDetected {
+            adb: result.adb,
+            scrcpy: result.scrcpy,
+            redetect: result.redetect,
+        }
+    }
+}
+
+impl From<AppDetailsResult> for BackgroundTaskResult {
+    fn from(result: AppDetailsResult) -> Self {
+        BackgroundTaskResult::AppDetails(result.0, result.1)
+    }
+}
+
+impl From<AppActionResult> for BackgroundTaskResult {
+    fn from(result: AppActionResult) -> Self {
+        BackgroundTaskResult::AppActionFinished(result.0)
+    }
+}
+
+impl From<SessionUsageResult> for BackgroundTaskResult {
+    fn from(result: SessionUsageResult) -> Self {
+        BackgroundTaskResult::SessionUsage(result.0)
+    }
+}
+
+impl From<DeviceUsersResult> for BackgroundTaskResult {
+    fn from(result: DeviceUsersResult) -> Self {
+        BackgroundTaskResult::DeviceUsers(result.0, result.1)
+    }
+}
+
+impl From<Vec<PackageInfo>> for BackgroundTaskResult {
+    fn from(apps: Vec<PackageInfo>) -> Self {
         BackgroundTaskResult::AppList(apps)
     }
 }
@@ -87,38 +700,169 @@ impl From<String> for BackgroundTaskResult {
     }
 }
 
+/// What a command palette entry runs
+#[derive(Debug, Clone)]
+enum PaletteCommand {
+    Shortcut(ShortcutAction),
+    Toolkit(crate::ui::panels::ToolkitAction),
+    QuickAction(BottomPanelAction),
+    StopScrcpy,
+    ToggleSwipePanel,
+    TogglePanel(DockPanel),
+    ResetLayout,
+    ToggleTheme,
+    OpenTunnels,
+    About,
+    SelectDevice(String),
+}
+
 // Embed the icon at compile time
 pub const ICON_PNG: &[u8] = include_bytes!("../assets/icon.png");
 
+/// Wait after a device appears before mirroring it starts, so it can finish coming up
+const AUTO_START_GRACE: std::time::Duration = std::time::Duration::from_secs(3);
+/// Checks on processes and sessions DroidView runs itself, which spawn nothing
+const HOUSEKEEPING_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+/// How soon the UI looks for results again while background tasks are out
+const TASK_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+/// How long to wait before reading a device's reboot targets again after that failed
+const REBOOT_PROBE_RETRY: std::time::Duration = std::time::Duration::from_secs(10);
+
 pub struct DroidViewApp {
     config: Arc<Mutex<AppConfig>>,
     devices: Vec<Device>,
+    /// Devices set to auto-start and when to start them
+    pending_auto_starts: Vec<(String, std::time::Instant)>,
+    /// Wireless devices that dropped off and are being retried
+    reconnector: Reconnector,
     device_list: DeviceList,
     swipe_panel: SwipePanel,
     toolkit_panel: ToolkitPanel,
     bottom_panel: BottomPanel,
     wireless_adb_panel: WirelessAdbPanel,
+    dock: DockLayout,
     settings_window: SettingsWindow,
+    file_push_dialog: FilePushDialog,
+    health_banner: HealthBanner,
+    permissions_window: PermissionsWindow,
+    app_details_window: AppDetailsWindow,
+    intent_sender: IntentSenderWindow,
+    capability_wizard: CapabilityWizard,
+    activity_viewer: ActivityViewer,
+    clipboard_history: ClipboardHistoryWindow,
+    tunnels_window: TunnelsWindow,
+    adb_keys_window: AdbKeysWindow,
+    bootloader_wizard: BootloaderWizard,
+    sideload_window: SideloadWindow,
+    sideload_cancel: Arc<std::sync::atomic::AtomicBool>,
+    last_fastboot_scan: std::time::Instant,
+    /// SSH forwards that are up, and the names of those still opening
+    tunnels: Vec<OpenTunnel>,
+    opening_tunnels: Vec<String>,
+    /// The gnirehtet relay and the devices using it for internet
+    gnirehtet_relay: Option<crate::gnirehtet::Relay>,
+    tethered: Vec<String>,
+    /// sndcpy forwarding audio next to a mirror that has none
+    audio_session: Option<crate::sndcpy::AudioSession>,
+    native_preview: Option<crate::native_preview::PreviewSession>,
+    native_preview_window: NativePreviewWindow,
+    shell_session: Option<crate::shell_console::ShellSession>,
+    shell_console: ShellConsoleWindow,
+    shell_command: ShellCommandWindow,
+    stream_window: StreamWindow,
+    stream_session: Option<StreamSession>, // scrcpy + ffmpeg pushing to the stream endpoint
+    share_window: ShareWindow,
+    help_window: HelpWindow,
+    gallery_window: GalleryWindow,
+    recording_library: RecordingLibraryWindow,
+    benchmark_window: BenchmarkWindow,
+    benchmark_cancel: Arc<std::sync::atomic::AtomicBool>,
+    share_server: Option<ShareServer>, // view-only LAN relay while sharing
+    api_server: Option<ApiServer>,     // local control API, when enabled in Settings
+    global_hotkeys: GlobalHotkeys,
+    #[cfg(feature = "tray")]
+    tray: crate::tray::Tray,
+    capabilities: HashMap<String, DeviceCapabilities>, // device id -> probed shell capabilities
+    reboot_targets: HashMap<String, Vec<RebootTarget>>, // device id -> extra reboot targets it supports
+    reboot_probe_failed: HashMap<String, std::time::Instant>, // device id -> when its reboot targets could not be read
+    missing_tools: Vec<Tool>, // dependencies that could not be detected
+    tool_install: Option<(Tool, InstallStage)>, // built-in download in progress
     adb_bridge: Option<AdbBridge>,
     scrcpy_bridge: Option<ScrcpyBridge>,
-    status_message: String,
+    toasts: Toasts,
+    update_notice: UpdateNotice,
+    shortcuts: Shortcuts,
+    shortcuts_window: ShortcutsWindow,
+    command_palette: CommandPalette,
+    log_console: LogConsole,
+    remote_assist: RemoteAssistWizard,
     scrcpy_running: bool,
     debug_disable_scrcpy: bool,
     imei_popup: Option<String>,
     display_popup: Option<String>,
     battery_popup: Option<String>,
+    reconnect_popup: Option<Vec<(String, Result<(), String>)>>,
     screenrecord_dialog: bool,
     screenrecord_duration: u32,
     screenrecord_bitrate: u32,
+    recording: Option<(String, std::time::Instant)>, // serial and start of the on-device screenrecord in progress
+    bugreport_progress: Option<u8>, // percent of the bug report being generated
+    bugreport_cancel: Arc<std::sync::atomic::AtomicBool>,
+    perfetto_window: PerfettoWindow,
+    frame_profiler: FrameProfilerWindow,
+    wifi_window: WifiWindow,
+    resolution_window: ResolutionWindow,
+    locale_window: LocaleWindow,
+    standby_window: StandbyWindow,
+    mock_location_window: MockLocationWindow,
+    /// Stops the mock location route being played
+    route_cancel: Arc<std::sync::atomic::AtomicBool>,
+    automation_window: AutomationWindow,
+    macro_window: MacroWindow,
+    gesture_designer: GestureDesigner,
+    tap_picker: TapPicker,
+    hierarchy_window: HierarchyWindow,
+    color_picker: ColorPickerWindow,
+    screenshot_diff: ScreenshotDiffWindow,
+    scripts_window: ScriptsWindow,
+    plugins: Vec<crate::plugins::Plugin>,
+    /// Plugin folders whose manifest could not be read
+    plugin_errors: Vec<String>,
+    plugins_panel: PluginsPanel,
+    device_controls: DeviceControlsPanel,
+    /// Devices reporting a simulated battery, reset when DroidView closes
+    battery_simulations: Vec<String>,
+    /// Stops the running script
+    script_cancel: Arc<std::sync::atomic::AtomicBool>,
+    /// Name of the macro being played, and the flag that stops it
+    playing_macro: Option<String>,
+    macro_cancel: Arc<std::sync::atomic::AtomicBool>,
+    scheduler: crate::automation::Scheduler,
+    last_automation_tick: std::time::Instant,
+    last_battery_poll: std::time::Instant,
+    last_gfxinfo_poll: std::time::Instant,
+    perfetto_device: Option<String>, // device a trace is being recorded on
     uninstall_dialog: bool,
-    app_list: Vec<(String, String)>, // (package_name, app_name)
+    app_list: Vec<PackageInfo>,
+    app_filter: AppListFilter,
     selected_apps: std::collections::HashSet<String>, // package names
     disable_dialog: bool,
-    disable_app_list: Vec<(String, String)>, // (package_name, app_name)
+    disable_app_list: Vec<PackageInfo>,
+    disable_app_filter: AppListFilter,
     selected_disable_apps: std::collections::HashSet<String>, // package names
+    device_users: Option<(String, Vec<DeviceUser>)>, // users and profiles of a device
+    package_user: u32, // user the app dialogs and installs act on
+    clear_data_confirm: Option<String>, // package awaiting confirmation of pm clear
+    video_codecs: HashMap<String, Vec<String>>, // device id -> supported codecs (empty if unknown)
+    displays: HashMap<String, Vec<DisplayEntry>>, // device id -> displays seen by the watcher
+    new_display_prompt: Option<(String, DisplayEntry)>, // (device id, newly appeared display)
+    resource_monitor: Arc<std::sync::Mutex<ResourceMonitor>>,
+    session_usage: Option<SessionUsage>, // host usage of scrcpy/adb while a session runs
     about_dialog: bool,
+    crash_reports: Vec<std::path::PathBuf>, // reports from earlier runs not yet shown
+    clipboard: Option<arboard::Clipboard>, // kept alive so copied images outlive the copy on X11/Wayland
     // Success dialogs
-    screenshot_success_dialog: Option<String>,
+    screenshot_success_dialog: Option<std::path::PathBuf>,
     screenrecord_success_dialog: Option<String>,
     // Async processing states
     loading_apps: bool,
@@ -126,18 +870,33 @@ pub struct DroidViewApp {
     loading_imei: bool,
     loading_display_info: bool,
     loading_battery_info: bool,
+    loading_reconnect: bool,
+    detecting_tools: bool,
     // Background task management
     task_handles: HashMap<String, JoinHandle<()>>,
     result_receiver: mpsc::UnboundedReceiver<BackgroundTaskResult>,
     result_sender: mpsc::UnboundedSender<BackgroundTaskResult>,
     // Performance optimization: timing for periodic updates
-    last_bridge_update: std::time::Instant,
     last_scrcpy_status_update: std::time::Instant,
+    last_display_poll: std::time::Instant,
+    last_clipboard_poll: std::time::Instant,
+    last_thumbnail_poll: std::time::Instant,
+    pip: PipPane,
+    last_pip_poll: std::time::Instant,
+    last_device_watch: std::time::Instant,
+    /// How often the bridges, the devices and scrcpy are checked, from the settings
+    poll_interval: std::time::Duration,
+    /// Whether the device list reload on its way was asked for by the user
+    announce_devices: bool,
+    last_health_poll: std::time::Instant,
+    last_usage_poll: std::time::Instant,
+    /// Latest main window geometry, written to the config on exit
+    window_geometry: Option<WindowGeometry>,
 }
 
 impl DroidViewApp {
     pub fn new(
-        _cc: &eframe::CreationContext<'_>,
+        cc: &eframe::CreationContext<'_>,
         config: Arc<Mutex<AppConfig>>,
         debug_disable_scrcpy: bool,
     ) -> Self {
@@ -146,30 +905,126 @@ impl DroidViewApp {
         let mut app = Self {
             config: config.clone(),
             devices: Vec::new(),
+            pending_auto_starts: Vec::new(),
+            reconnector: Reconnector::default(),
             device_list: DeviceList::new(),
             swipe_panel: SwipePanel::new(),
             toolkit_panel: ToolkitPanel::new(),
             bottom_panel: BottomPanel::new(),
+            dock: crate::dock::default_layout(),
             wireless_adb_panel: WirelessAdbPanel::new(),
             settings_window: SettingsWindow::new(config.clone()),
+            file_push_dialog: FilePushDialog::new(),
+            health_banner: HealthBanner::new(),
+            permissions_window: PermissionsWindow::new(),
+            app_details_window: AppDetailsWindow::new(),
+            intent_sender: IntentSenderWindow::new(),
+            capability_wizard: CapabilityWizard::new(),
+            activity_viewer: ActivityViewer::new(),
+            clipboard_history: ClipboardHistoryWindow::new(),
+            tunnels_window: TunnelsWindow::new(),
+            adb_keys_window: AdbKeysWindow::new(),
+            bootloader_wizard: BootloaderWizard::new(),
+            sideload_window: SideloadWindow::new(),
+            sideload_cancel: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            last_fastboot_scan: std::time::Instant::now(),
+            tunnels: Vec::new(),
+            opening_tunnels: Vec::new(),
+            gnirehtet_relay: None,
+            tethered: Vec::new(),
+            audio_session: None,
+            native_preview: None,
+            native_preview_window: NativePreviewWindow::new(),
+            shell_session: None,
+            shell_console: ShellConsoleWindow::new(),
+            shell_command: ShellCommandWindow::new(),
+            stream_window: StreamWindow::new(),
+            stream_session: None,
+            share_window: ShareWindow::new(),
+            help_window: HelpWindow::new(),
+            gallery_window: GalleryWindow::new(),
+            recording_library: RecordingLibraryWindow::new(),
+            benchmark_window: BenchmarkWindow::new(),
+            benchmark_cancel: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            share_server: None,
+            api_server: None,
+            global_hotkeys: GlobalHotkeys::new(cc.egui_ctx.clone()),
+            #[cfg(feature = "tray")]
+            tray: crate::tray::Tray::new(Vec::new(), cc.egui_ctx.clone()),
+            capabilities: HashMap::new(),
+            reboot_targets: HashMap::new(),
+            reboot_probe_failed: HashMap::new(),
+            missing_tools: Vec::new(),
+            tool_install: None,
             adb_bridge: None,
             scrcpy_bridge: None,
-            status_message: String::new(),
+            toasts: Toasts::new(),
+            update_notice: UpdateNotice::new(),
+            shortcuts: Shortcuts::default(),
+            shortcuts_window: ShortcutsWindow::new(),
+            command_palette: CommandPalette::new(),
+            log_console: LogConsole::new(),
+            remote_assist: RemoteAssistWizard::new(),
             scrcpy_running: false,
             debug_disable_scrcpy,
             imei_popup: None,
             display_popup: None,
             battery_popup: None,
+            reconnect_popup: None,
             screenrecord_dialog: false,
             screenrecord_duration: 10,
             screenrecord_bitrate: 8000000,
+            recording: None,
+            bugreport_progress: None,
+            bugreport_cancel: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            perfetto_window: PerfettoWindow::new(),
+            frame_profiler: FrameProfilerWindow::new(),
+            wifi_window: WifiWindow::new(),
+            resolution_window: ResolutionWindow::new(),
+            locale_window: LocaleWindow::new(),
+            standby_window: StandbyWindow::new(),
+            mock_location_window: MockLocationWindow::new(),
+            route_cancel: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            automation_window: AutomationWindow::new(),
+            macro_window: MacroWindow::new(),
+            gesture_designer: GestureDesigner::new(),
+            tap_picker: TapPicker::new(),
+            hierarchy_window: HierarchyWindow::new(),
+            color_picker: ColorPickerWindow::new(),
+            screenshot_diff: ScreenshotDiffWindow::new(),
+            scripts_window: ScriptsWindow::new(),
+            plugins: Vec::new(),
+            plugin_errors: Vec::new(),
+            plugins_panel: PluginsPanel::new(),
+            device_controls: DeviceControlsPanel::new(),
+            battery_simulations: Vec::new(),
+            script_cancel: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            playing_macro: None,
+            macro_cancel: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            scheduler: crate::automation::Scheduler::new(),
+            last_automation_tick: std::time::Instant::now(),
+            last_battery_poll: std::time::Instant::now(),
+            last_gfxinfo_poll: std::time::Instant::now(),
+            perfetto_device: None,
             uninstall_dialog: false,
             app_list: Vec::new(),
+            app_filter: AppListFilter::new(None, Some(true)),
             selected_apps: std::collections::HashSet::new(),
             disable_dialog: false,
             disable_app_list: Vec::new(),
+            disable_app_filter: AppListFilter::new(Some(true), None),
             selected_disable_apps: std::collections::HashSet::new(),
+            clear_data_confirm: None,
+            device_users: None,
+            package_user: 0,
+            video_codecs: HashMap::new(),
+            displays: HashMap::new(),
+            new_display_prompt: None,
+            resource_monitor: Arc::new(std::sync::Mutex::new(ResourceMonitor::new())),
+            session_usage: None,
             about_dialog: false,
+            crash_reports: crate::crash::pending_reports(),
+            clipboard: None,
             // Success dialogs
             screenshot_success_dialog: None,
             screenrecord_success_dialog: None,
@@ -179,112 +1034,2708 @@ impl DroidViewApp {
             loading_imei: false,
             loading_display_info: false,
             loading_battery_info: false,
+            loading_reconnect: false,
+            detecting_tools: false,
             // Background task management
             task_handles: HashMap::new(),
             result_receiver,
             result_sender,
             // Performance optimization: timing for periodic updates
-            last_bridge_update: std::time::Instant::now(),
             last_scrcpy_status_update: std::time::Instant::now(),
+            last_display_poll: std::time::Instant::now(),
+            last_clipboard_poll: std::time::Instant::now(),
+            last_thumbnail_poll: std::time::Instant::now(),
+            pip: PipPane::new(),
+            last_pip_poll: std::time::Instant::now(),
+            last_device_watch: std::time::Instant::now(),
+            poll_interval: std::time::Duration::from_secs(crate::config::DEFAULT_DEVICE_POLL_SECS),
+            announce_devices: false,
+            last_health_poll: std::time::Instant::now(),
+            last_usage_poll: std::time::Instant::now(),
+            window_geometry: config.try_lock().ok().and_then(|config| config.window),
         };
         
+        let language = config.try_lock().ok().and_then(|config| config.language.clone());
+        crate::i18n::set_language(language.as_deref());
+
         // Set config for wireless ADB panel to remember IPs
         app.wireless_adb_panel.set_config(config);
+
+        app.apply_panel_visibility_from_config();
+        app.apply_poll_interval();
+        app.apply_session_templates();
+        app.reload_plugins();
+        app.apply_control_api(&cc.egui_ctx);
+        app.apply_shortcuts();
+        app.apply_theme(&cc.egui_ctx);
+        app.apply_scaling(&cc.egui_ctx);
         
+        // Look for unset tools off the UI thread; startup reconnects wait for the result
+        app.detect_tools(false);
+
         // Initial setup: update bridges and refresh devices on first launch
+        app.apply_adb_server();
         app.update_bridges();
         app.refresh_devices();
+
+        if app.config.try_lock().is_ok_and(|config| config.check_for_updates) {
+            app.check_for_updates(false);
+        }
         
         app
     }
 
-    fn update_bridges(&mut self) {
-        let mut config = self.config.try_lock().unwrap();
+    /// No background task is running or has a result waiting
+    #[cfg(feature = "test-harness")]
+    pub(crate) fn is_idle(&self) -> bool {
+        self.task_handles.values().all(|handle| handle.is_finished()) && self.result_receiver.is_empty()
+    }
+
+    /// Serial of the device the panels act on
+    #[cfg(feature = "test-harness")]
+    pub(crate) fn selected_serial(&self) -> Option<String> {
+        self.device_list.selected_device().map(|device| device.identifier.clone())
+    }
+
+    /// Probe for adb and scrcpy in the background, once at startup and on "Re-detect"
+    ///
+    /// A re-detect replaces configured paths with whatever is found; the startup run only
+    /// fills in paths that are unset (or stale in portable mode).
+    fn detect_tools(&mut self, redetect: bool) {
+        if self.task_handles.contains_key("tool_detection") {
+            return;
+        }
+
+        self.detecting_tools = true;
+        self.run_background_task("tool_detection".to_string(), move || {
+            let (adb, scrcpy) = std::thread::scope(|scope| {
+                let adb = scope.spawn(crate::utils::find_adb);
+                let scrcpy = scope.spawn(crate::utils::find_scrcpy);
+                (adb.join().ok().flatten(), scrcpy.join().ok().flatten())
+            });
+            ToolsDetectedResult { adb, scrcpy, redetect }
+        });
+    }
+
+    fn apply_detected_tools(
+        &mut self,
+        adb: Option<std::path::PathBuf>,
+        scrcpy: Option<std::path::PathBuf>,
+        redetect: bool,
+    ) {
+        self.detecting_tools = false;
+
+        let (adb_changed, reconnect_on_startup) = {
+            let mut config = self.config.try_lock().unwrap();
+            // A portable install may come back on another drive letter, so replace stale paths
+            let portable = crate::config::is_portable();
+            let old_adb = config.adb_path.clone();
 
-        // Auto-detect adb if not configured
-        if config.adb_path.is_none() {
-            if let Some(adb_path) = crate::utils::find_adb() {
+            if let Some(adb_path) = adb
+                && (redetect || tool_unset(config.adb_path.as_deref()) || (portable && tool_missing(config.adb_path.as_deref())))
+            {
                 config.adb_path = Some(adb_path.display().to_string());
-                info!(
-                    "Auto-detected ADB at: {}",
-                    config.adb_path.as_ref().unwrap()
-                );
+                info!("Auto-detected ADB at: {}", adb_path.display());
             }
-        }
 
-        // Auto-detect scrcpy if not configured
-        if config.scrcpy_path.is_none() {
-            if let Some(scrcpy_path) = crate::utils::find_scrcpy() {
+            if let Some(scrcpy_path) = scrcpy
+                && (redetect || tool_unset(config.scrcpy_path.as_deref()) || (portable && tool_missing(config.scrcpy_path.as_deref())))
+            {
                 config.scrcpy_path = Some(scrcpy_path.display().to_string());
-                info!(
-                    "Auto-detected scrcpy at: {}",
-                    config.scrcpy_path.as_ref().unwrap()
-                );
+                info!("Auto-detected scrcpy at: {}", scrcpy_path.display());
             }
-        }
 
-        // Create ADB bridge
-        if let Some(adb_path) = &config.adb_path {
-            if self.adb_bridge.as_ref().map(|b| b.path()) != Some(adb_path.as_str()) {
-                self.adb_bridge = Some(AdbBridge::new(adb_path.clone()));
+            if redetect {
+                let not_found = t!("tools-not-found");
+                self.toasts.info(t!(
+                    "tools-redetected",
+                    adb = config.adb_path.as_deref().filter(|p| !p.is_empty()).unwrap_or(&not_found),
+                    scrcpy = config.scrcpy_path.as_deref().filter(|p| !p.is_empty()).unwrap_or(&not_found),
+                ));
+                let _ = config.save();
             }
+
+            (config.adb_path != old_adb, config.wireless_adb.reconnect_on_startup)
+        };
+
+        self.update_bridges();
+        if adb_changed {
+            self.refresh_devices();
         }
 
-        // Create scrcpy bridge
-        if let Some(scrcpy_path) = &config.scrcpy_path {
-            if self.scrcpy_bridge.as_ref().map(|b| b.path()) != Some(scrcpy_path.as_str()) {
-                self.scrcpy_bridge = Some(ScrcpyBridge::new(scrcpy_path.clone()));
-            }
+        // Bring saved wireless devices back if the user opted in
+        if !redetect && reconnect_on_startup {
+            self.reconnect_saved_devices();
         }
     }
 
-    fn refresh_devices(&mut self) {
+    fn reconnect_saved_devices(&mut self) {
+        if self.loading_reconnect || self.task_handles.contains_key("wireless_reconnect") {
+            return;
+        }
+
+        let endpoints = match self.config.try_lock() {
+            Ok(config) => config
+                .wireless_adb
+                .saved_endpoints
+                .iter()
+                .map(|saved| saved.endpoint.clone())
+                .collect::<Vec<_>>(),
+            Err(_) => return,
+        };
+        if endpoints.is_empty() {
+            return;
+        }
+
         if let Some(adb_bridge) = &self.adb_bridge {
-            match get_devices(adb_bridge.path()) {
-                Ok(devices) => {
-                    self.devices = devices;
-                    self.device_list.update_devices(self.devices.clone());
-                    self.status_message = format!("Found {} device(s)", self.devices.len());
-                }
-                Err(e) => {
-                    error!("Failed to get devices: {}", e);
-                    self.status_message = format!("Error: {}", e);
-                }
-            }
+            self.loading_reconnect = true;
+            let adb_path = adb_bridge.path().to_string();
+
+            self.run_background_task("wireless_reconnect".to_string(), move || {
+                let results = AdbBridge::new(adb_path)
+                    .connect_all(&endpoints)
+                    .into_iter()
+                    .map(|(endpoint, result)| (endpoint, result.map(|_| ()).map_err(|e| e.to_string())))
+                    .collect();
+                WirelessReconnectResult(results)
+            });
+
+            self.toasts.info(t!("wireless-reconnecting"));
         } else {
-            self.status_message = "ADB not configured".to_string();
+            self.toasts.warning(t!("adb-not-configured"));
         }
     }
 
-    fn update_scrcpy_status(&mut self) {
-        let was_running = self.scrcpy_running;
-        self.scrcpy_running = is_process_running("scrcpy");
+    fn update_bridges(&mut self) {
+        // A background task may hold the config; the next poll tick tries again
+        let Ok(config) = self.config.try_lock() else {
+            return;
+        };
 
-        // Log status changes for debugging
-        if was_running != self.scrcpy_running {
-            if self.scrcpy_running {
-                info!("Scrcpy process detected as running");
-            } else {
-                info!("Scrcpy process no longer detected");
-            }
+        // Paths are auto-detected by detect_tools; this only (re)builds the bridges
+
+        // Create ADB bridge
+        if let Some(adb_path) = &config.adb_path
+            && self.adb_bridge.as_ref().map(|b| b.path()) != Some(adb_path.as_str()) {
+            self.adb_bridge = Some(AdbBridge::new(adb_path.clone()));
         }
-    }
 
-    fn apply_panel_visibility_from_config(&mut self) {
-        if let Ok(config) = self.config.try_lock() {
+        // Create scrcpy bridge
+        if let Some(scrcpy_path) = &config.scrcpy_path
+            && self.scrcpy_bridge.as_ref().map(|b| b.path()) != Some(scrcpy_path.as_str()) {
+            self.scrcpy_bridge = Some(ScrcpyBridge::new(scrcpy_path.clone()));
+        }
+
+        self.missing_tools.clear();
+        // Don't flash the banner while the startup probe is still looking
+        if self.detecting_tools {
+            return;
+        }
+        if tool_missing(config.adb_path.as_deref()) {
+            self.missing_tools.push(Tool::Adb);
+        }
+        if tool_missing(config.scrcpy_path.as_deref()) {
+            self.missing_tools.push(Tool::Scrcpy);
+        }
+    }
+
+    /// Let the user pick a dependency's executable from the health banner
+    fn locate_tool(&mut self, tool: Tool) {
+        let Some(path) = rfd::FileDialog::new()
+            .set_title(t!("tools-locate", tool = tool.name()))
+            .pick_file()
+        else {
+            return;
+        };
+
+        {
+            let mut config = self.config.try_lock().unwrap();
+            let path = Some(path.display().to_string());
+            match tool {
+                Tool::Adb => config.adb_path = path,
+                Tool::Scrcpy => config.scrcpy_path = path,
+            }
+            let _ = config.save();
+        }
+
+        self.update_bridges();
+        if tool == Tool::Adb {
+            self.refresh_devices();
+        }
+        self.toasts.success(t!("tools-set", tool = tool.name(), path = path.display()));
+    }
+
+    /// Download the official build of `tool` into the tools folder and point the config at it
+    fn install_tool(&mut self, tool: Tool) {
+        if let Some((installing, _)) = self.tool_install {
+            self.toasts.warning(t!("tools-already-downloading", tool = installing.name()));
+            return;
+        }
+
+        self.tool_install = Some((tool, InstallStage::Resolving));
+        let sender = self.result_sender.clone();
+        let handle = tokio::spawn(async move {
+            let progress = {
+                let sender = sender.clone();
+                move |stage| {
+                    let _ = sender.send(BackgroundTaskResult::ToolInstallProgress(tool, stage));
+                }
+            };
+            let result = crate::tools::install(tool.package(), &progress).await.map_err(|e| e.to_string());
+            let _ = sender.send(BackgroundTaskResult::ToolInstalled(tool, result));
+        });
+        self.task_handles.insert("tool_install".to_string(), handle);
+    }
+
+    /// Look for a newer DroidView release in the background
+    fn check_for_updates(&mut self, manual: bool) {
+        if self.task_handles.get("update_check").is_some_and(|handle| !handle.is_finished()) {
+            return;
+        }
+
+        let sender = self.result_sender.clone();
+        let handle = tokio::spawn(async move {
+            let result = crate::update::check_for_update().await.map_err(|e| e.to_string());
+            let _ = sender.send(BackgroundTaskResult::UpdateChecked { result, manual });
+        });
+        self.task_handles.insert("update_check".to_string(), handle);
+    }
+
+    fn apply_update_check(&mut self, result: Result<Option<UpdateInfo>, String>, manual: bool) {
+        match result {
+            Ok(Some(update)) => {
+                let skipped = self
+                    .config
+                    .try_lock()
+                    .is_ok_and(|config| config.skipped_update.as_deref() == Some(update.version.as_str()));
+                info!("DroidView {} is available", update.version);
+                // Asking explicitly shows even a skipped version
+                if manual || !skipped {
+                    self.update_notice.set(update);
+                }
+            }
+            Ok(None) if manual => {
+                self.toasts.success(t!("update-latest", version = crate::update::CURRENT_VERSION));
+            }
+            Ok(None) => {}
+            Err(e) => {
+                tracing::warn!("Update check failed: {}", e);
+                if manual {
+                    self.toasts.error(t!("update-check-failed", error = e));
+                }
+            }
+        }
+    }
+
+    fn apply_installed_tool(&mut self, tool: Tool, result: Result<Installed, String>) {
+        self.tool_install = None;
+        let installed = match result {
+            Ok(installed) => installed,
+            Err(e) => {
+                error!("Installing {} failed: {}", tool.package().name(), e);
+                self.toasts.error(t!("tools-install-failed", tool = tool.name(), error = e));
+                return;
+            }
+        };
+
+        {
+            let mut config = self.config.try_lock().unwrap();
+            let path = Some(installed.executable.display().to_string());
+            match tool {
+                Tool::Adb => config.adb_path = path,
+                Tool::Scrcpy => config.scrcpy_path = path,
+            }
+            let _ = config.save();
+        }
+
+        self.update_bridges();
+        if tool == Tool::Adb {
+            self.refresh_devices();
+        }
+        self.toasts.success(t!("tools-installed", tool = tool.name(), version = installed.version));
+    }
+
+    fn refresh_devices(&mut self) {
+        self.reload_devices(true);
+    }
+
+    /// Read the device list again in the background; `announce` reports the result, which the
+    /// tracker does not
+    fn reload_devices(&mut self, announce: bool) {
+        let Some(adb_bridge) = &self.adb_bridge else {
+            if announce {
+                self.toasts.warning(t!("adb-not-configured"));
+            }
+            return;
+        };
+        // A reload already on its way reports for this request too
+        self.announce_devices |= announce;
+        if self.task_handles.contains_key("device_reload") {
+            return;
+        }
+        let adb_path = adb_bridge.path().to_string();
+        self.run_background_task("device_reload".to_string(), move || {
+            DevicesResult(get_devices(&adb_path).map_err(|e| e.to_string()))
+        });
+    }
+
+    fn apply_devices(&mut self, result: Result<Vec<Device>, String>) {
+        let announce = std::mem::take(&mut self.announce_devices);
+        match result {
+            Ok(devices) => {
+                let previous = std::mem::replace(&mut self.devices, devices);
+                self.device_list.update_devices(self.devices.clone());
+                for event in crate::api::device_changes(&previous, &self.devices) {
+                    self.emit_api_event(event);
+                }
+                if announce {
+                    self.toasts.success(t!("devices-found", count = self.devices.len()));
+                }
+                self.queue_auto_starts(&previous);
+                self.fire_connect_rules(&previous);
+                self.track_wireless_drops(&previous);
+                self.note_wireless_seen();
+            }
+            Err(e) => {
+                error!("Failed to get devices: {}", e);
+                if announce {
+                    self.toasts.error(t!("error", error = e));
+                }
+            }
+        }
+    }
+
+    /// Stamp the saved wireless endpoints that are connected now with the current time
+    fn note_wireless_seen(&mut self) {
+        let Ok(mut config) = self.config.try_lock() else {
+            return;
+        };
+        let mut seen = false;
+        for device in self.devices.iter().filter(|device| device.is_usable()) {
+            seen |= config.wireless_endpoint_seen(&device.identifier);
+        }
+        if seen && let Err(e) = config.save() {
+            error!("Failed to save wireless history: {}", e);
+        }
+    }
+
+    /// Ask ADB which devices are attached, to notice devices coming, going or being authorized
+    fn watch_devices(&mut self) {
+        if self.task_handles.contains_key("device_watch") {
+            return;
+        }
+        let Some(adb_bridge) = &self.adb_bridge else {
+            return;
+        };
+        let adb_path = adb_bridge.path().to_string();
+        self.run_background_task("device_watch".to_string(), move || {
+            let states = match crate::device::get_device_states(&adb_path) {
+                Ok(states) => states,
+                Err(e) => {
+                    tracing::debug!("Device watch failed: {}", e);
+                    Vec::new()
+                }
+            };
+            DeviceStatesResult(states)
+        });
+    }
+
+    /// Reload the device list when the tracker saw a different set of devices or states
+    fn handle_device_states(&mut self, mut states: Vec<(String, DeviceStatus)>) {
+        let mut known: Vec<(String, DeviceStatus)> = self
+            .devices
+            .iter()
+            .map(|device| (device.identifier.clone(), device.status.clone()))
+            .collect();
+        states.sort_by(|a, b| a.0.cmp(&b.0));
+        known.sort_by(|a, b| a.0.cmp(&b.0));
+        if states != known {
+            self.reload_devices(false);
+        }
+    }
+
+    /// Retry wireless devices that were usable in `previous` and no longer are, and stop
+    /// retrying the ones that are back
+    fn track_wireless_drops(&mut self, previous: &[Device]) {
+        let enabled = self
+            .config
+            .try_lock()
+            .map(|config| config.wireless_adb.auto_reconnect)
+            .unwrap_or(false);
+        let now = std::time::Instant::now();
+        for device in self.devices.iter().filter(|device| device.is_usable()) {
+            if self.reconnector.cancel(&device.identifier) {
+                info!("Wireless device {} is back", device.identifier);
+                self.toasts.success(t!("devices-reconnected", name = self.device_list.display_name(device)));
+            }
+        }
+        if !enabled {
+            return;
+        }
+        for old in previous.iter().filter(|old| old.is_usable()) {
+            let usable = self
+                .devices
+                .iter()
+                .any(|device| device.identifier == old.identifier && device.is_usable());
+            if !usable && crate::reconnect::is_endpoint(&old.identifier) {
+                info!("Wireless device {} dropped off, reconnecting", old.identifier);
+                self.reconnector.track(&old.identifier, now);
+                self.toasts.info(t!("devices-reconnect-lost", name = self.device_list.display_name(old)));
+            }
+        }
+    }
+
+    /// Run `adb connect` for the dropped wireless devices whose next attempt is due
+    fn run_due_reconnects(&mut self) {
+        if self.reconnector.is_empty() {
+            return;
+        }
+        let Some(adb_bridge) = &self.adb_bridge else {
+            return;
+        };
+        let adb_path = adb_bridge.path().to_string();
+        for endpoint in self.reconnector.due(std::time::Instant::now()) {
+            let adb_path = adb_path.clone();
+            self.run_background_task(format!("auto_reconnect:{}", endpoint), move || {
+                let result = AdbBridge::new(adb_path)
+                    .reconnect(&endpoint)
+                    .map(|_| ())
+                    .map_err(|e| e.to_string());
+                AutoReconnectResult(endpoint, result)
+            });
+        }
+    }
+
+    fn handle_auto_reconnect(&mut self, endpoint: String, result: Result<(), String>) {
+        if let Err(e) = &result {
+            tracing::debug!("Reconnecting {} failed: {}", endpoint, e);
+        }
+        // Connected is not yet usable: the device list reload cancels the retries once it is
+        if !self.reconnector.finished(&endpoint, std::time::Instant::now()) {
+            let name = self.config.try_lock().ok().and_then(|config| {
+                config.device_labels.get(&endpoint).and_then(|label| label.nickname.clone())
+            });
+            self.toasts.warning(t!("devices-reconnect-gave-up", name = name.unwrap_or_else(|| endpoint.clone())));
+        }
+        if result.is_ok() {
+            self.reload_devices(false);
+        }
+    }
+
+    /// Open the bootloader wizard for the selected device, if adb sees one
+    fn open_bootloader_wizard(&mut self) {
+        let adb_path = self.adb_bridge.as_ref().map(|bridge| bridge.path().to_string());
+        let fastboot_found = FastbootBridge::locate(adb_path.as_deref()).is_some();
+        let device = self.device_list.selected_device().map(|device| device.identifier.clone());
+        self.bootloader_wizard.open(device.clone(), fastboot_found);
+
+        if let (Some(adb_path), Some(device)) = (adb_path, device) {
+            self.run_background_task("bootloader_manufacturer".to_string(), move || {
+                let manufacturer = AdbBridge::new(adb_path)
+                    .shell("getprop ro.product.manufacturer", Some(&device))
+                    .map(|output| output.trim().to_string())
+                    .unwrap_or_default();
+                BootloaderManufacturerResult(manufacturer)
+            });
+        }
+    }
+
+    fn scan_fastboot(&mut self) {
+        self.last_fastboot_scan = std::time::Instant::now();
+        if self.task_handles.contains_key("fastboot_scan") {
+            return;
+        }
+        let Some(fastboot) = FastbootBridge::locate(self.adb_bridge.as_ref().map(|bridge| bridge.path())) else {
+            return;
+        };
+        self.run_background_task("fastboot_scan".to_string(), move || {
+            FastbootScanResult(fastboot.scan().map_err(|e| e.to_string()))
+        });
+    }
+
+    fn run_bootloader_flashing(&mut self, serial: String, lock: bool) {
+        let Some(fastboot) = FastbootBridge::locate(self.adb_bridge.as_ref().map(|bridge| bridge.path())) else {
+            self.bootloader_wizard.set_result(Err(t!("bootloader-no-fastboot")));
+            return;
+        };
+        self.run_background_task("bootloader_flashing".to_string(), move || {
+            let result = fastboot.flashing(&serial, lock).map_err(|e| e.to_string()).and_then(|()| {
+                // The command returning says nothing about what the user chose on the device
+                match fastboot.getvar(&serial, "unlocked").map(|unlocked| unlocked == "yes") {
+                    Some(true) if !lock => Ok(t!("bootloader-now-unlocked", device = serial)),
+                    Some(false) if lock => Ok(t!("bootloader-now-locked", device = serial)),
+                    Some(unlocked) => {
+                        let state = if unlocked { t!("bootloader-state-unlocked") } else { t!("bootloader-state-locked") };
+                        Err(t!("bootloader-unchanged", device = serial, state = state))
+                    }
+                    None => Err(t!("bootloader-state-unreported", device = serial)),
+                }
+            });
+            BootloaderFlashResult(result)
+        });
+    }
+
+    /// Reboot into sideload mode if asked, wait for it, then stream the zip, reporting each stage
+    fn start_sideload(&mut self, serial: String, zip: std::path::PathBuf, reboot: bool) {
+        let Some(adb_bridge) = &self.adb_bridge else {
+            self.sideload_window.finish(Err(t!("adb-not-configured")));
+            return;
+        };
+        if self.task_handles.contains_key("sideload") {
+            return;
+        }
+
+        let adb_path = adb_bridge.path().to_string();
+        let sender = self.result_sender.clone();
+        let cancel = self.sideload_cancel.clone();
+        cancel.store(false, std::sync::atomic::Ordering::Relaxed);
+
+        let handle = tokio::task::spawn_blocking(move || {
+            let run = || -> anyhow::Result<()> {
+                if reboot {
+                    crate::sideload::reboot_to_sideload(&adb_path, &serial)?;
+                }
+                let _ = sender.send(BackgroundTaskResult::SideloadProgress(SideloadStage::Waiting));
+                crate::sideload::wait_for_sideload(&adb_path, &serial, &cancel)?;
+                let _ = sender.send(BackgroundTaskResult::SideloadProgress(SideloadStage::Transferring(0)));
+                crate::sideload::sideload(&adb_path, &serial, &zip, &cancel, |percent| {
+                    let _ = sender.send(BackgroundTaskResult::SideloadProgress(SideloadStage::Transferring(percent)));
+                })
+            };
+            let result = run()
+                .map(|()| {
+                    let name = zip.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+                    t!("sideload-done", zip = name, device = serial.clone())
+                })
+                .map_err(|e| e.to_string());
+            let _ = sender.send(BackgroundTaskResult::SideloadFinished(result));
+        });
+
+        self.task_handles.insert("sideload".to_string(), handle);
+    }
+
+    /// Replay a macro on `serial`, reporting each step to the macro window
+    fn play_macro(&mut self, serial: String, name: String, steps: Vec<crate::config::MacroStep>, speed: f32) {
+        let Some(adb_bridge) = &self.adb_bridge else {
+            self.macro_window.finish(Err(t!("adb-not-configured")));
+            return;
+        };
+        if self.task_handles.contains_key("macro") {
+            return;
+        }
+
+        let adb_path = adb_bridge.path().to_string();
+        let sender = self.result_sender.clone();
+        let cancel = self.macro_cancel.clone();
+        cancel.store(false, std::sync::atomic::Ordering::Relaxed);
+        self.playing_macro = Some(name);
+
+        let handle = tokio::task::spawn_blocking(move || {
+            let result = crate::macros::play(&adb_path, &serial, &steps, speed, &cancel, |step| {
+                let _ = sender.send(BackgroundTaskResult::MacroProgress(step));
+            });
+            let _ = sender.send(BackgroundTaskResult::MacroFinished(result.map_err(|e| e.to_string())));
+        });
+
+        self.task_handles.insert("macro".to_string(), handle);
+    }
+
+    fn open_adb_keys(&mut self, device: Option<String>) {
+        self.adb_keys_window.open(device);
+        self.check_adb_keys(self.adb_keys_window.device().map(str::to_string));
+    }
+
+    /// Read this computer's key, and the keys `device` trusts when it is connected
+    fn check_adb_keys(&mut self, device: Option<String>) {
+        if self.task_handles.contains_key("adb_keys") {
+            return;
+        }
+        let adb_path = self.adb_bridge.as_ref().map(|bridge| bridge.path().to_string());
+        // Only an authorized device answers at all
+        let device = device.filter(|id| {
+            self.devices
+                .iter()
+                .any(|device| device.identifier == *id && device.status == DeviceStatus::Device)
+        });
+        self.adb_keys_window.set_checking();
+        self.run_background_task("adb_keys".to_string(), move || {
+            let host_key = crate::adb_keys::read_host_key().map_err(|e| e.to_string());
+            let device_keys = adb_path
+                .zip(device)
+                .map(|(adb_path, id)| (id.clone(), crate::adb_keys::device_keys(&adb_path, &id)));
+            AdbKeysResult(host_key, device_keys)
+        });
+    }
+
+    fn regenerate_adb_keys(&mut self) {
+        let Some(adb_bridge) = &self.adb_bridge else {
+            self.adb_keys_window.set_message(Err(t!("adb-not-configured")));
+            return;
+        };
+        let adb_path = adb_bridge.path().to_string();
+        self.run_background_task("adb_keys_regenerate".to_string(), move || {
+            AdbKeyRegeneratedResult(crate::adb_keys::regenerate_host_key(&adb_path).map_err(|e| e.to_string()))
+        });
+    }
+
+    fn revoke_adb_keys(&mut self, device: String) {
+        let Some(adb_bridge) = &self.adb_bridge else {
+            self.adb_keys_window.set_message(Err(t!("adb-not-configured")));
+            return;
+        };
+        let adb_path = adb_bridge.path().to_string();
+        self.run_background_task("adb_keys_revoke".to_string(), move || {
+            AdbKeysRevokedResult(crate::adb_keys::revoke_authorizations(&adb_path, &device).map_err(|e| e.to_string()))
+        });
+    }
+
+    fn open_tunnels_window(&mut self) {
+        let profiles = self.config.try_lock().map(|config| config.ssh_tunnels.clone()).unwrap_or_default();
+        self.tunnels_window.open(profiles);
+    }
+
+    /// Start ssh for the profile and `adb connect` through it in the background
+    fn open_tunnel(&mut self, profile: crate::config::SshTunnelProfile) {
+        let Some(adb_bridge) = &self.adb_bridge else {
+            self.toasts.warning(t!("adb-not-configured"));
+            return;
+        };
+        let task_id = format!("tunnel:{}", profile.name);
+        if self.task_handles.contains_key(&task_id) || self.tunnels.iter().any(|tunnel| tunnel.name == profile.name) {
+            return;
+        }
+        if let Some(other) = self.tunnels.iter().find(|tunnel| tunnel.local_port == profile.local_port) {
+            self.toasts.error(t!("tunnels-port-in-use", port = profile.local_port, name = other.name.clone()));
+            return;
+        }
+        let adb_path = adb_bridge.path().to_string();
+        self.opening_tunnels.push(profile.name.clone());
+        self.run_background_task(task_id, move || {
+            let result = OpenTunnel::open(&profile).map_err(|e| e.to_string()).map(|tunnel| {
+                let connected = tunnel.connect(&AdbBridge::new(adb_path));
+                (tunnel, connected)
+            });
+            TunnelOpenedResult(profile.name, result)
+        });
+    }
+
+    fn handle_tunnel_opened(
+        &mut self,
+        name: String,
+        result: Result<(OpenTunnel, Result<ConnectOutcome, String>), String>,
+    ) {
+        self.opening_tunnels.retain(|opening| *opening != name);
+        match result {
+            Ok((tunnel, connected)) => {
+                match connected {
+                    Ok(_) => self.toasts.success(t!("tunnels-opened", name = name.clone(), endpoint = tunnel.endpoint())),
+                    // The forward is up; the device behind it may just need a moment
+                    Err(e) => self.toasts.warning(t!("tunnels-connect-failed", name = name.clone(), error = e)),
+                }
+                self.tunnels.push(tunnel);
+                self.refresh_devices();
+            }
+            Err(e) => {
+                error!("Failed to open SSH tunnel '{}': {}", name, e);
+                self.toasts.error(t!("tunnels-open-failed", name = name, error = e));
+            }
+        }
+    }
+
+    fn close_tunnel(&mut self, name: &str) {
+        let Some(index) = self.tunnels.iter().position(|tunnel| tunnel.name == name) else {
+            return;
+        };
+        let tunnel = self.tunnels.remove(index);
+        self.reconnector.cancel(&tunnel.endpoint());
+        tunnel.close(self.adb_bridge.as_ref());
+        self.refresh_devices();
+    }
+
+    /// Drop tunnels whose ssh process has exited, e.g. when the connection to the host broke
+    fn reap_tunnels(&mut self) {
+        let mut closed = Vec::new();
+        self.tunnels.retain_mut(|tunnel| match tunnel.exit_reason() {
+            Some(reason) => {
+                closed.push((tunnel.name.clone(), tunnel.endpoint(), reason));
+                false
+            }
+            None => true,
+        });
+        for (name, endpoint, reason) in closed {
+            tracing::warn!("SSH tunnel '{}' closed: {}", name, reason);
+            self.reconnector.cancel(&endpoint);
+            self.toasts.warning(t!("tunnels-dropped", name = name));
+        }
+    }
+
+    fn automation_rules(&self) -> Vec<crate::config::AutomationRule> {
+        self.config.try_lock().map(|config| config.automation_rules.clone()).unwrap_or_default()
+    }
+
+    fn usable_serials(&self) -> Vec<String> {
+        self.devices
+            .iter()
+            .filter(|device| device.is_usable())
+            .map(|device| device.identifier.clone())
+            .collect()
+    }
+
+    /// Run the rules set off by devices that became usable since `previous`
+    fn fire_connect_rules(&mut self, previous: &[Device]) {
+        let rules = self.automation_rules();
+        if rules.is_empty() {
+            return;
+        }
+        let mut firings = Vec::new();
+        for device in self.devices.iter().filter(|device| device.is_usable()) {
+            let was_usable = previous
+                .iter()
+                .any(|old| old.identifier == device.identifier && old.is_usable());
+            if !was_usable {
+                firings.extend(self.scheduler.on_connected(&rules, &device.identifier));
+            }
+        }
+        self.run_firings(firings);
+    }
+
+    /// Run time-of-day rules that are due and read battery levels for battery rules
+    fn tick_automation(&mut self) {
+        let now = std::time::Instant::now();
+        if now.duration_since(self.last_automation_tick) < crate::automation::TICK {
+            return;
+        }
+        self.last_automation_tick = now;
+        let rules = self.automation_rules();
+        if !rules.iter().any(|rule| rule.enabled) {
+            return;
+        }
+        let serials = self.usable_serials();
+        let firings = self.scheduler.on_time(&rules, &serials, chrono::Local::now().naive_local());
+        self.run_firings(firings);
+
+        if crate::automation::Scheduler::watches_battery(&rules)
+            && now.duration_since(self.last_battery_poll) >= crate::automation::BATTERY_POLL
+            && !self.task_handles.contains_key("automation_battery")
+            && let Some(adb_bridge) = &self.adb_bridge
+        {
+            self.last_battery_poll = now;
+            let adb_path = adb_bridge.path().to_string();
+            self.run_background_task("automation_battery".to_string(), move || {
+                AutomationBatteryResult(
+                    serials
+                        .into_iter()
+                        .map(|serial| {
+                            let battery = crate::device::get_device_details(&adb_path, &serial).battery;
+                            (serial, battery)
+                        })
+                        .collect(),
+                )
+            });
+        }
+    }
+
+    fn run_firings(&mut self, firings: Vec<crate::automation::Firing>) {
+        use crate::config::AutomationAction;
+        for firing in firings {
+            info!("Automation '{}' runs on {}", firing.rule, firing.serial);
+            let task_id = format!("automation:{}:{}", firing.rule, firing.serial);
+            let Some(adb_bridge) = &self.adb_bridge else {
+                return;
+            };
+            if self.task_handles.contains_key(&task_id) {
+                continue;
+            }
+            let adb_path = adb_bridge.path().to_string();
+            let crate::automation::Firing { rule, serial, action } = firing;
+            match action {
+                AutomationAction::StartMirroring => {
+                    if self.device_list.select(&serial) {
+                        self.start_scrcpy();
+                    }
+                }
+                AutomationAction::Screenshot => {
+                    let model = self
+                        .devices
+                        .iter()
+                        .find(|device| device.identifier == serial)
+                        .map(|device| device.model.clone())
+                        .unwrap_or_default();
+                    let (dir, template) = match self.config.try_lock() {
+                        Ok(config) => (config.screenshot_dir(), config.screenshot_template.clone()),
+                        Err(_) => (dirs::desktop_dir().unwrap_or_default(), "screenshot_{timestamp}".to_string()),
+                    };
+                    self.run_background_task(task_id, move || {
+                        let saved = crate::automation::screenshot(&adb_path, &serial, &model, &dir, &template);
+                        let result = saved.map(|path| path.display().to_string()).map_err(|e| e.to_string());
+                        AutomationResult(rule, serial, result)
+                    });
+                }
+                AutomationAction::Shell { command } => {
+                    self.run_background_task(task_id, move || {
+                        let result = crate::automation::shell(&adb_path, &serial, &command).map_err(|e| e.to_string());
+                        AutomationResult(rule, serial, result)
+                    });
+                }
+            }
+        }
+    }
+
+    /// Share this computer's internet with the selected device through gnirehtet, or stop sharing
+    fn toggle_reverse_tether(&mut self) {
+        if self.task_handles.contains_key("tether") {
+            return;
+        }
+        let (Some(adb_bridge), Some(device)) = (&self.adb_bridge, self.device_list.selected_device()) else {
+            return;
+        };
+        let adb_path = adb_bridge.path().to_string();
+        let device_id = device.identifier.clone();
+
+        if let Some(index) = self.tethered.iter().position(|serial| *serial == device_id) {
+            self.tethered.remove(index);
+            self.run_background_task("tether".to_string(), move || {
+                let result = crate::gnirehtet::disable(&adb_path, &device_id).map_err(|e| e.to_string());
+                TetherResult(device_id, false, None, result)
+            });
+            return;
+        }
+
+        let configured = self.config.try_lock().ok().and_then(|config| config.gnirehtet_path.clone());
+        let Some(binary) = crate::gnirehtet::locate(configured.as_deref()) else {
+            self.toasts.error(t!("tether-not-found"));
+            return;
+        };
+        let start_relay = self.gnirehtet_relay.is_none();
+        self.run_background_task("tether".to_string(), move || {
+            let relay = match start_relay.then(|| crate::gnirehtet::Relay::start(&binary)).transpose() {
+                Ok(relay) => relay,
+                Err(e) => return TetherResult(device_id, true, None, Err(e.to_string())),
+            };
+            let result = crate::gnirehtet::enable(&adb_path, &device_id, &binary).map_err(|e| e.to_string());
+            TetherResult(device_id, true, relay, result)
+        });
+    }
+
+    /// Forget the tethered devices when the relay died, so the toggle reflects it
+    fn check_relay(&mut self) {
+        let Some(reason) = self.gnirehtet_relay.as_mut().and_then(|relay| relay.exit_reason()) else {
+            return;
+        };
+        tracing::warn!("{}", reason);
+        self.gnirehtet_relay = None;
+        self.tethered.clear();
+        self.toasts.warning(t!("tether-relay-stopped"));
+    }
+
+    /// Schedule mirroring for devices set to auto-start that became usable since `previous`
+    fn queue_auto_starts(&mut self, previous: &[Device]) {
+        let Ok(config) = self.config.try_lock() else {
+            return;
+        };
+        if !config.auto_start_enabled {
+            return;
+        }
+        let due = std::time::Instant::now() + AUTO_START_GRACE;
+        for device in self.devices.iter().filter(|device| device.is_usable()) {
+            let was_usable = previous
+                .iter()
+                .any(|old| old.identifier == device.identifier && old.is_usable());
+            let wanted = config
+                .device_labels
+                .get(&device.identifier)
+                .is_some_and(|label| label.auto_start);
+            if wanted
+                && !was_usable
+                && !self.pending_auto_starts.iter().any(|(serial, _)| *serial == device.identifier)
+            {
+                info!("Auto-start scheduled for {}", device.identifier);
+                self.pending_auto_starts.push((device.identifier.clone(), due));
+            }
+        }
+    }
+
+    /// Start mirroring the devices whose grace delay is over and that are still usable
+    fn run_due_auto_starts(&mut self) {
+        if self.pending_auto_starts.is_empty() {
+            return;
+        }
+        let now = std::time::Instant::now();
+        let (due, waiting): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pending_auto_starts)
+            .into_iter()
+            .partition(|(_, at)| *at <= now);
+        self.pending_auto_starts = waiting;
+
+        // The switch may have been turned off during the delay
+        let enabled = self.config.try_lock().map(|config| config.auto_start_enabled).unwrap_or(false);
+        for (serial, _) in due {
+            let usable = self.devices.iter().any(|device| device.identifier == serial && device.is_usable());
+            if !enabled || !usable || !self.device_list.select(&serial) {
+                continue;
+            }
+            let name = self
+                .device_list
+                .selected_device()
+                .map(|device| self.device_list.display_name(device))
+                .unwrap_or_else(|| serial.clone());
+            self.toasts.info(t!("devices-auto-starting", name = name));
+            self.start_scrcpy();
+        }
+    }
+
+    /// Look for a scrcpy process in the background, also one not started from DroidView
+    fn check_scrcpy_process(&mut self) {
+        if self.task_handles.contains_key("scrcpy_status") {
+            return;
+        }
+        self.run_background_task("scrcpy_status".to_string(), || ScrcpyStatusResult(is_process_running("scrcpy")));
+    }
+
+    fn check_legacy_audio(&mut self) {
+        if self.audio_session.as_mut().is_some_and(|session| !session.is_running()) {
+            self.stop_legacy_audio();
+            self.toasts.warning(t!("sndcpy-stopped"));
+        }
+    }
+
+    fn apply_scrcpy_status(&mut self, running: bool) {
+        let was_running = self.scrcpy_running;
+        self.scrcpy_running = running;
+        if was_running && !self.scrcpy_running {
+            self.stop_legacy_audio();
+        }
+
+        // Log status changes for debugging
+        if was_running != self.scrcpy_running {
+            if self.scrcpy_running {
+                info!("Scrcpy process detected as running");
+                self.emit_api_event(ApiEvent::MirrorStarted);
+            } else {
+                info!("Scrcpy process no longer detected");
+                self.emit_api_event(ApiEvent::MirrorStopped);
+            }
+        }
+    }
+
+    /// Probe the selected device's video encoders once and cache the result
+    fn probe_video_codecs(&mut self) {
+        if self.task_handles.contains_key("video_codecs") {
+            return;
+        }
+
+        if let (Some(scrcpy_bridge), Some(device)) =
+            (&self.scrcpy_bridge, self.device_list.selected_device())
+        {
+            if !device.is_usable() || self.video_codecs.contains_key(&device.identifier) {
+                return;
+            }
+
+            let scrcpy_path = scrcpy_bridge.path().to_string();
+            let device_id = device.identifier.clone();
+
+            self.run_background_task("video_codecs".to_string(), move || {
+                let codecs = match ScrcpyBridge::new(scrcpy_path).list_video_codecs(&device_id) {
+                    Ok(codecs) => codecs,
+                    Err(e) => {
+                        tracing::warn!("Codec probe for {} failed: {}", device_id, e);
+                        Vec::new()
+                    }
+                };
+                VideoCodecsResult(device_id, codecs)
+            });
+        }
+    }
+
+    /// Run a context-menu action for a package on the selected device
+    /// User package operations act on; `None` on single-user devices, which keeps adb's defaults
+    fn package_user(&self) -> Option<u32> {
+        let device = self.device_list.selected_device()?;
+        match &self.device_users {
+            Some((id, users)) if *id == device.identifier && users.len() > 1 => Some(self.package_user),
+            _ => None,
+        }
+    }
+
+    /// Look up the users of the selected device unless they are known
+    fn load_device_users(&mut self) {
+        if self.task_handles.contains_key("device_users") {
+            return;
+        }
+        if let (Some(adb_bridge), Some(device)) = (self.adb_bridge.as_ref(), self.device_list.selected_device()) {
+            if self.device_users.as_ref().is_some_and(|(id, _)| *id == device.identifier) {
+                return;
+            }
+            let adb_path = adb_bridge.path().to_string();
+            let device_id = device.identifier.clone();
+            self.run_background_task("device_users".to_string(), move || {
+                let users = packages::list_users(&adb_path, &device_id).unwrap_or_default();
+                DeviceUsersResult(device_id, users)
+            });
+        }
+    }
+
+    /// Fetch the packages of the chosen user for the uninstall or the disable dialog
+    fn load_app_list(&mut self, for_disable: bool) {
+        let (loading, task) = if for_disable {
+            (self.loading_disable_apps, "disable_app_list")
+        } else {
+            (self.loading_apps, "app_list")
+        };
+        if loading || self.task_handles.contains_key(task) {
+            return;
+        }
+        if let (Some(adb_bridge), Some(device)) = (self.adb_bridge.as_ref(), self.device_list.selected_device()) {
+            let adb_path = adb_bridge.path().to_string();
+            let device_id = device.identifier.clone();
+            let user = self.package_user();
+            if for_disable {
+                self.loading_disable_apps = true;
+            } else {
+                self.loading_apps = true;
+            }
+
+            // Spawn background task
+            self.run_background_task(task.to_string(), move || {
+                let mut apps = packages::list_packages(&adb_path, &device_id, user).unwrap_or_default();
+                // What `pm list packages -e` and `-3` would list: any enabled app can be disabled,
+                // but only apps outside the system image can be uninstalled
+                apps.retain(|app| if for_disable { app.enabled } else { !app.system });
+                let result: BackgroundTaskResult = if for_disable {
+                    DisableAppListResult(apps).into()
+                } else {
+                    AppListResult(apps).into()
+                };
+                result
+            });
+
+            self.toasts.info(t!("apps-loading"));
+        } else {
+            self.toasts.warning(t!("no-device-adb"));
+        }
+    }
+
+    fn run_app_action(&mut self, action: AppAction, package: String) {
+        type Run = fn(&AdbBridge, &str, Option<&str>, Option<u32>) -> anyhow::Result<()>;
+        let (run, done, failed): (Run, &str, &str) = match action {
+            AppAction::Launch => (AdbBridge::launch_app, "apps-launched", "apps-launch-failed"),
+            AppAction::ForceStop => (AdbBridge::force_stop, "apps-force-stopped", "apps-force-stop-failed"),
+            AppAction::ClearData => (AdbBridge::clear_data, "apps-cleared", "apps-clear-failed"),
+            AppAction::Permissions => {
+                self.permissions_window.open(&package);
+                self.load_permissions(package, None);
+                return;
+            }
+            AppAction::Details => {
+                self.app_details_window.open(&package);
+                self.load_app_details(package);
+                return;
+            }
+        };
+
+        if let (Some(adb_bridge), Some(device)) = (self.adb_bridge.as_ref(), self.device_list.selected_device()) {
+            let adb_path = adb_bridge.path().to_string();
+            let device_id = device.identifier.clone();
+            let user = self.package_user();
+
+            self.run_background_task(format!("app_action_{}", package), move || {
+                let bridge = AdbBridge::new(adb_path);
+                let message = match run(&bridge, &package, Some(&device_id), user) {
+                    Ok(()) => Ok(t!(done, package = package)),
+                    Err(e) => Err(t!(failed, package = package, error = e)),
+                };
+                AppActionResult(message)
+            });
+        } else {
+            self.toasts.warning(t!("no-device-adb"));
+        }
+    }
+
+    /// Persist the stream settings the window was using
+    fn remember_stream_config(&mut self, stream_config: &crate::config::StreamConfig) {
+        if let Ok(mut config) = self.config.try_lock() {
+            config.stream = stream_config.clone();
+            let _ = config.save();
+        }
+    }
+
+    fn preflight_stream(&mut self, stream_config: crate::config::StreamConfig) {
+        self.remember_stream_config(&stream_config);
+        let scrcpy_path = self.scrcpy_bridge.as_ref().map(|bridge| bridge.path().to_string());
+
+        self.run_background_task("stream_preflight".to_string(), move || {
+            StreamPreflightResult(crate::stream::preflight(scrcpy_path.as_deref(), &stream_config))
+        });
+    }
+
+    fn start_stream(&mut self, stream_config: crate::config::StreamConfig) {
+        self.remember_stream_config(&stream_config);
+        if self.stream_session.is_some() || self.task_handles.contains_key("stream_start") {
+            return;
+        }
+
+        if let (Some(scrcpy_bridge), Some(device)) = (self.scrcpy_bridge.as_ref(), self.device_list.selected_device()) {
+            let scrcpy_path = scrcpy_bridge.path().to_string();
+            let device_id = device.identifier.clone();
+
+            self.run_background_task("stream_start".to_string(), move || {
+                let session = StreamSession::start(&scrcpy_path, &device_id, &stream_config).map_err(|e| e.to_string());
+                StreamStartedResult(session)
+            });
+        } else {
+            self.stream_window.set_streaming(false, Err(t!("no-device-scrcpy")));
+        }
+    }
+
+    fn stop_stream(&mut self) {
+        if let Some(session) = self.stream_session.take() {
+            session.stop();
+            self.toasts.info(t!("stream-stopped-toast"));
+        }
+        self.stream_window.set_streaming(false, Ok(t!("stream-stopped")));
+    }
+
+    /// Tear the pipeline down when scrcpy or ffmpeg dies on its own
+    fn check_stream(&mut self) {
+        if let Some(reason) = self.stream_session.as_mut().and_then(|session| session.exit_reason()) {
+            if let Some(session) = self.stream_session.take() {
+                session.stop();
+            }
+            error!("Stream ended: {}", reason);
+            self.toasts.warning(t!("stream-ended", reason = reason));
+            self.stream_window.set_streaming(false, Err(reason));
+        }
+    }
+
+    /// Measure each case in turn, reporting through the result channel as cases finish
+    fn start_benchmark(&mut self, cases: Vec<BenchmarkCase>) {
+        let (Some(scrcpy_bridge), Some(device)) = (&self.scrcpy_bridge, self.device_list.selected_device()) else {
+            self.toasts.warning(t!("no-device-scrcpy"));
+            self.benchmark_window.finish();
+            return;
+        };
+
+        let scrcpy_path = scrcpy_bridge.path().to_string();
+        let device_id = device.identifier.clone();
+        let sender = self.result_sender.clone();
+        let cancel = self.benchmark_cancel.clone();
+        cancel.store(false, std::sync::atomic::Ordering::Relaxed);
+
+        let handle = tokio::task::spawn_blocking(move || {
+            for (index, case) in cases.iter().enumerate() {
+                if cancel.load(std::sync::atomic::Ordering::Relaxed) {
+                    break;
+                }
+                let _ = sender.send(BackgroundTaskResult::BenchmarkProgress(index, None));
+                let result = crate::benchmark::run_case(&scrcpy_path, &device_id, case, &cancel);
+                let _ = sender.send(BackgroundTaskResult::BenchmarkProgress(index, Some(result)));
+            }
+            let _ = sender.send(BackgroundTaskResult::BenchmarkFinished);
+        });
+
+        self.task_handles.insert("benchmark".to_string(), handle);
+        self.toasts.info(t!("benchmark-running"));
+    }
+
+    /// Record on the device without blocking the UI, then pull the file into the capture folder
+    fn start_screenrecord(&mut self) {
+        if self.recording.is_some() {
+            self.toasts.warning(t!("screenrecord-already-running"));
+            return;
+        }
+        let (Some(adb_bridge), Some(device)) = (&self.adb_bridge, self.device_list.selected_device()) else {
+            self.toasts.warning(t!("no-device-adb"));
+            return;
+        };
+
+        let adb_path = adb_bridge.path().to_string();
+        let device = device.clone();
+        let time_limit = self.screenrecord_duration;
+        let bit_rate = self.screenrecord_bitrate * 1000;
+        let dir = match self.config.try_lock() {
+            Ok(config) => config.screenshot_dir(),
+            Err(_) => dirs::desktop_dir().unwrap_or_default(),
+        };
+
+        self.recording = Some((device.identifier.clone(), std::time::Instant::now()));
+        self.toasts.info(t!("screenrecord-recording"));
+        self.run_background_task("screenrecord".to_string(), move || {
+            ScreenrecordResult(record_screen(&adb_path, &device, time_limit, bit_rate, &dir))
+        });
+    }
+
+    /// Ask where to save, then generate the bug report in the background, reporting its percentage
+    fn start_bugreport(&mut self) {
+        if self.bugreport_progress.is_some() {
+            self.toasts.warning(t!("bugreport-already-running"));
+            return;
+        }
+        let (Some(adb_bridge), Some(device)) = (&self.adb_bridge, self.device_list.selected_device()) else {
+            self.toasts.warning(t!("no-device-adb"));
+            return;
+        };
+        let adb_path = adb_bridge.path().to_string();
+        let device_id = device.identifier.clone();
+
+        let default_dir = match self.config.try_lock() {
+            Ok(config) => config.bugreport_dir(),
+            Err(_) => dirs::desktop_dir().unwrap_or_default(),
+        };
+        let Some(dir) = rfd::FileDialog::new()
+            .set_title(t!("bugreport-save-to"))
+            .set_directory(&default_dir)
+            .pick_folder()
+        else {
+            return;
+        };
+        if dir != default_dir
+            && let Ok(mut config) = self.config.try_lock()
+        {
+            config.bugreport_dir = Some(dir.to_string_lossy().to_string());
+            let _ = config.save();
+        }
+
+        let sender = self.result_sender.clone();
+        let cancel = self.bugreport_cancel.clone();
+        cancel.store(false, std::sync::atomic::Ordering::Relaxed);
+        self.bugreport_progress = Some(0);
+        self.toasts.info(t!("bugreport-capturing"));
+
+        let handle = tokio::task::spawn_blocking(move || {
+            let result = crate::bugreport::capture(&adb_path, &device_id, &dir, &cancel, |percent| {
+                let _ = sender.send(BackgroundTaskResult::BugreportProgress(percent));
+            })
+            .map_err(|e| e.to_string());
+            let _ = sender.send(BackgroundTaskResult::BugreportFinished(result));
+        });
+        self.task_handles.insert("bugreport".to_string(), handle);
+    }
+
+    /// Record a Perfetto trace on the selected device, pulled into the capture folder
+    fn start_perfetto(&mut self, categories: Vec<&'static str>, duration_secs: u32) {
+        if self.task_handles.contains_key("perfetto") {
+            return;
+        }
+        let (Some(adb_bridge), Some(device)) = (&self.adb_bridge, self.device_list.selected_device()) else {
+            self.perfetto_window.finish(Err(t!("no-device-adb")));
+            return;
+        };
+        let adb_path = adb_bridge.path().to_string();
+        let device_id = device.identifier.clone();
+        let model = device.model.clone();
+        let dir = match self.config.try_lock() {
+            Ok(config) => config.screenshot_dir(),
+            Err(_) => dirs::desktop_dir().unwrap_or_default(),
+        };
+
+        self.perfetto_device = Some(device_id.clone());
+        self.perfetto_window.set_started();
+        self.run_background_task("perfetto".to_string(), move || {
+            let result = crate::perfetto::record(&adb_path, &device_id, &model, &categories, duration_secs, &dir);
+            PerfettoResult(result.map_err(|e| e.to_string()))
+        });
+    }
+
+    fn stop_perfetto(&mut self) {
+        let (Some(adb_bridge), Some(device_id)) = (&self.adb_bridge, self.perfetto_device.clone()) else {
+            return;
+        };
+        let adb_path = adb_bridge.path().to_string();
+        std::thread::spawn(move || {
+            if let Err(e) = crate::perfetto::stop(&adb_path, &device_id) {
+                error!("Failed to stop the trace: {}", e);
+            }
+        });
+    }
+
+    /// Convert a recording with ffmpeg (the one from the stream settings, else from PATH)
+    fn export_recording(
+        &mut self,
+        path: std::path::PathBuf,
+        format: crate::export::ExportFormat,
+        preset: crate::export::ExportPreset,
+    ) {
+        let ffmpeg = self
+            .config
+            .try_lock()
+            .ok()
+            .and_then(|config| crate::stream::resolve_ffmpeg(&config.stream));
+        let Some(ffmpeg) = ffmpeg else {
+            self.recording_library.set_export_result(Err(t!("recordings-ffmpeg-missing")));
+            return;
+        };
+
+        self.toasts.info(t!("recordings-converting-to", format = format.label()));
+        self.run_background_task("export_recording".to_string(), move || {
+            ExportResult(crate::export::export_recording(&ffmpeg, &path, format, preset).map_err(|e| e.to_string()))
+        });
+    }
+
+    /// Interrupt screenrecord so it finalizes the file early; the recording task then pulls it
+    fn stop_screenrecord(&mut self) {
+        // The recording device, which need not be the one selected now
+        let (Some(adb_bridge), Some((device_id, _))) = (&self.adb_bridge, &self.recording) else {
+            return;
+        };
+        if self.task_handles.contains_key("screenrecord_stop") {
+            return;
+        }
+        let adb_path = adb_bridge.path().to_string();
+        let device_id = device_id.clone();
+        self.run_background_task("screenrecord_stop".to_string(), move || {
+            ScreenrecordStopResult(interrupt_screenrecord(&adb_path, &device_id))
+        });
+        self.toasts.info(t!("screenrecord-stopping"));
+    }
+
+    fn start_share(&mut self, port: u16, duration: std::time::Duration) {
+        if self.share_server.is_some() {
+            return;
+        }
+
+        if let (Some(adb_bridge), Some(device)) = (self.adb_bridge.as_ref(), self.device_list.selected_device()) {
+            match ShareServer::start(adb_bridge.path().to_string(), device.identifier.clone(), port, duration) {
+                Ok(server) => {
+                    self.toasts.success(t!("share-started"));
+                    self.share_server = Some(server);
+                }
+                Err(e) => self.share_window.set_error(Some(e.to_string())),
+            }
+        } else {
+            self.share_window
+                .set_error(Some(t!("no-device-adb")));
+        }
+    }
+
+    fn stop_share(&mut self) {
+        if let Some(server) = self.share_server.take() {
+            server.stop();
+            self.toasts.info(t!("share-stopped"));
+        }
+    }
+
+    fn load_activity_stack(&mut self) {
+        if self.task_handles.contains_key("activity_stack") {
+            return;
+        }
+
+        if let (Some(adb_bridge), Some(device)) = (self.adb_bridge.as_ref(), self.device_list.selected_device()) {
+            let adb_path = adb_bridge.path().to_string();
+            let device_id = device.identifier.clone();
+
+            self.run_background_task("activity_stack".to_string(), move || {
+                let stack = crate::activity_stack::get_activity_stack(&adb_path, &device_id).map_err(|e| e.to_string());
+                ActivityStackResult(stack)
+            });
+        } else {
+            self.activity_viewer
+                .set_stack(Err(t!("no-device-adb")));
+        }
+    }
+
+    /// Check which privileged settings the shell user may change on the selected device
+    fn probe_capabilities(&mut self) {
+        if self.task_handles.contains_key("capabilities") {
+            return;
+        }
+
+        if let (Some(adb_bridge), Some(device)) = (self.adb_bridge.as_ref(), self.device_list.selected_device()) {
+            let adb_path = adb_bridge.path().to_string();
+            let device_id = device.identifier.clone();
+
+            self.capability_wizard
+                .set_capabilities(self.capabilities.get(&device_id).cloned());
+            self.capability_wizard.set_probing();
+            self.run_background_task("capabilities".to_string(), move || {
+                let capabilities = crate::capabilities::probe_capabilities(&adb_path, &device_id);
+                CapabilitiesResult(device_id, capabilities)
+            });
+        } else {
+            self.capability_wizard.set_capabilities(None);
+        }
+    }
+
+    /// Whether the selected device lets the shell user do what `capability` covers
+    ///
+    /// When it was found to be blocked, the capability wizard opens on it instead. A device that
+    /// was not checked yet is checked in the background, and the tool is tried meanwhile.
+    fn has_capability(&mut self, capability: Capability) -> bool {
+        let Some(device) = self.device_list.selected_device() else {
+            return true;
+        };
+        let denied = match self.capabilities.get(&device.identifier) {
+            Some(capabilities) => matches!(capabilities.status(capability), Some(CapabilityStatus::Denied(_))),
+            None => {
+                self.probe_capabilities();
+                return true;
+            }
+        };
+        if denied {
+            self.toasts.warning(t!("capabilities-missing", capability = capability.label()));
+            self.capability_wizard.open(Some(capability));
+        }
+        !denied
+    }
+
+    /// The reboot targets of the selected device, checked once per device
+    fn reboot_targets(&mut self) -> Option<Vec<RebootTarget>> {
+        let (adb_bridge, device) = (self.adb_bridge.as_ref()?, self.device_list.selected_device()?);
+        if let Some(targets) = self.reboot_targets.get(&device.identifier) {
+            return Some(targets.clone());
+        }
+        let retry_later = self
+            .reboot_probe_failed
+            .get(&device.identifier)
+            .is_some_and(|failed| failed.elapsed() < REBOOT_PROBE_RETRY);
+        if !retry_later && !self.task_handles.contains_key("reboot_targets") {
+            let adb_path = adb_bridge.path().to_string();
+            let device_id = device.identifier.clone();
+            self.run_background_task("reboot_targets".to_string(), move || {
+                let targets = crate::reboot::probe(&adb_path, &device_id).map_err(|e| e.to_string());
+                RebootTargetsResult(device_id, targets)
+            });
+        }
+        None
+    }
+
+    fn grant_companion(&mut self, package: String) {
+        if let (Some(adb_bridge), Some(device)) = (self.adb_bridge.as_ref(), self.device_list.selected_device()) {
+            let adb_path = adb_bridge.path().to_string();
+            let device_id = device.identifier.clone();
+
+            self.run_background_task("companion_grant".to_string(), move || {
+                let result = crate::capabilities::grant_to_companion(&adb_path, &device_id, &package)
+                    .map(|()| t!("companion-granted", package = package));
+                CompanionGrantResult(result)
+            });
+        } else {
+            self.capability_wizard.set_message(Err(t!("no-device-adb")));
+        }
+    }
+
+    /// Run a composed intent on the selected device and remember it in the history
+    fn send_intent(&mut self, spec: IntentSpec) {
+        if let (Some(adb_bridge), Some(device)) = (self.adb_bridge.as_ref(), self.device_list.selected_device()) {
+            let adb_path = adb_bridge.path().to_string();
+            let device_id = device.identifier.clone();
+            let command = spec.to_command();
+
+            if let Ok(mut config) = self.config.try_lock() {
+                config.remember_intent(&spec);
+                let _ = config.save();
+            }
+
+            self.run_background_task("send_intent".to_string(), move || {
+                let output = AdbBridge::new(adb_path)
+                    .shell(&command, Some(&device_id))
+                    .map_err(|e| e.to_string())
+                    .and_then(|output| {
+                        // am reports failures on stdout with a zero exit code
+                        if output.contains("Error:") || output.contains("Exception") {
+                            Err(output.trim().to_string())
+                        } else {
+                            Ok(output)
+                        }
+                    });
+                IntentSentResult(output)
+            });
+        } else {
+            self.intent_sender.set_output(Err(t!("no-device-adb")));
+        }
+    }
+
+    /// Gather version, install and size details of a package in the background
+    fn load_app_details(&mut self, package: String) {
+        if let (Some(adb_bridge), Some(device)) = (self.adb_bridge.as_ref(), self.device_list.selected_device()) {
+            let adb_path = adb_bridge.path().to_string();
+            let device_id = device.identifier.clone();
+
+            self.run_background_task(format!("app_details_{}", package), move || {
+                let details = packages::get_app_details(&adb_path, &device_id, &package).map_err(|e| e.to_string());
+                AppDetailsResult(package, details)
+            });
+        } else {
+            self.toasts.warning(t!("no-device-adb"));
+        }
+    }
+
+    /// Optionally grant/revoke one permission, then reload the package's runtime permissions
+    fn load_permissions(&mut self, package: String, change: Option<(String, bool)>) {
+        if let (Some(adb_bridge), Some(device)) = (self.adb_bridge.as_ref(), self.device_list.selected_device()) {
+            let adb_path = adb_bridge.path().to_string();
+            let device_id = device.identifier.clone();
+
+            self.run_background_task(format!("permissions_{}", package), move || {
+                let mut error = None;
+                if let Some((permission, grant)) = change
+                    && let Err(e) = packages::set_permission(&adb_path, &device_id, &package, &permission, grant)
+                {
+                    let key = if grant { "permissions-grant-failed" } else { "permissions-revoke-failed" };
+                    error = Some(t!(key, permission = permission, error = e));
+                }
+
+                match packages::get_runtime_permissions(&adb_path, &device_id, &package) {
+                    Ok(permissions) => PermissionsResult(package, permissions, error),
+                    Err(e) => PermissionsResult(package, Vec::new(), Some(e.to_string())),
+                }
+            });
+        } else {
+            self.toasts.warning(t!("no-device-adb"));
+        }
+    }
+
+    /// Measure CPU and memory of the scrcpy and adb server processes on the host
+    fn sample_session_usage(&mut self) {
+        if self.task_handles.contains_key("session_usage") {
+            return;
+        }
+
+        let monitor = self.resource_monitor.clone();
+        self.run_background_task("session_usage".to_string(), move || {
+            let usage = match monitor.lock() {
+                Ok(mut monitor) => monitor.sample(),
+                Err(_) => SessionUsage::default(),
+            };
+            SessionUsageResult(usage)
+        });
+    }
+
+    /// Poll `dumpsys display` on the selected device so new (DeX, cast, virtual) displays are noticed
+    fn poll_displays(&mut self) {
+        if self.task_handles.contains_key("display_watch") {
+            return;
+        }
+
+        if let (Some(adb_bridge), Some(device)) =
+            (&self.adb_bridge, self.device_list.selected_device())
+        {
+            if !device.is_usable() {
+                return;
+            }
+
+            let adb_path = adb_bridge.path().to_string();
+            let device_id = device.identifier.clone();
+
+            self.run_background_task("display_watch".to_string(), move || {
+                let displays = crate::device::get_displays(&adb_path, &device_id).unwrap_or_default();
+                DisplaysResult(device_id, displays)
+            });
+        }
+    }
+
+    /// Time a trivial shell command on every usable device, in parallel so one stalled
+    /// connection does not hold up the others
+    fn poll_health(&mut self) {
+        if self.task_handles.contains_key("health") {
+            return;
+        }
+        let Some(adb_bridge) = &self.adb_bridge else {
+            return;
+        };
+        let device_ids: Vec<String> = self
+            .devices
+            .iter()
+            .filter(|device| device.is_usable())
+            .map(|device| device.identifier.clone())
+            .collect();
+        if device_ids.is_empty() {
+            return;
+        }
+
+        let adb_path = adb_bridge.path().to_string();
+        self.run_background_task("health".to_string(), move || {
+            let samples = std::thread::scope(|scope| {
+                let handles: Vec<_> = device_ids
+                    .iter()
+                    .map(|device_id| {
+                        let adb_path = &adb_path;
+                        scope.spawn(move || {
+                            crate::device::ping(adb_path, device_id, crate::device::HEALTH_TIMEOUT)
+                        })
+                    })
+                    .collect();
+                device_ids
+                    .iter()
+                    .cloned()
+                    .zip(handles)
+                    .map(|(device_id, handle)| (device_id, handle.join().ok().flatten()))
+                    .collect()
+            });
+            HealthResult(samples)
+        });
+    }
+
+    /// Capture a small screenshot of every usable device for the device list
+    fn poll_thumbnails(&mut self) {
+        if self.task_handles.contains_key("thumbnails") {
+            return;
+        }
+        let Some(adb_bridge) = &self.adb_bridge else {
+            return;
+        };
+        let device_ids: Vec<String> = self
+            .device_list
+            .devices()
+            .iter()
+            .filter(|device| device.is_usable())
+            .map(|device| device.identifier.clone())
+            .collect();
+        if device_ids.is_empty() {
+            return;
+        }
+
+        let adb_path = adb_bridge.path().to_string();
+        self.run_background_task("thumbnails".to_string(), move || {
+            let thumbnails = device_ids
+                .into_iter()
+                .filter_map(|device_id| {
+                    match crate::device::capture_thumbnail(&adb_path, &device_id, THUMBNAIL_EDGE) {
+                        Ok(image) => {
+                            let size = [image.width() as usize, image.height() as usize];
+                            Some((device_id, egui::ColorImage::from_rgba_unmultiplied(size, &image)))
+                        }
+                        Err(e) => {
+                            tracing::debug!("Thumbnail of {} failed: {}", device_id, e);
+                            None
+                        }
+                    }
+                })
+                .collect();
+            ThumbnailsResult(thumbnails)
+        });
+    }
+
+    /// Capture the selected device for the live preview, one screenshot at a time
+    fn poll_pip(&mut self) {
+        if self.task_handles.contains_key("pip") {
+            return;
+        }
+        let (Some(adb_bridge), Some(device)) = (&self.adb_bridge, self.device_list.selected_device()) else {
+            return;
+        };
+        if !device.is_usable() {
+            return;
+        }
+        let adb_path = adb_bridge.path().to_string();
+        let device_id = device.identifier.clone();
+        self.run_background_task("pip".to_string(), move || {
+            let result = crate::device::capture_thumbnail(&adb_path, &device_id, PIP_EDGE).map(|image| {
+                let size = [image.width() as usize, image.height() as usize];
+                egui::ColorImage::from_rgba_unmultiplied(size, &image)
+            });
+            PipResult(device_id, result.map_err(|e| e.to_string()))
+        });
+    }
+
+    /// Read the selected device's clipboard into the history while watching is enabled
+    fn poll_device_clipboard(&mut self) {
+        if self.task_handles.contains_key("clipboard_watch") {
+            return;
+        }
+
+        if let (Some(adb_bridge), Some(device)) =
+            (&self.adb_bridge, self.device_list.selected_device())
+        {
+            if !device.is_usable() {
+                return;
+            }
+
+            let adb_path = adb_bridge.path().to_string();
+            let device_id = device.identifier.clone();
+
+            self.run_background_task("clipboard_watch".to_string(), move || {
+                let clip = crate::device_clipboard::read_clipboard(&adb_path, &device_id).map_err(|e| e.to_string());
+                DeviceClipboardResult(device_id, clip)
+            });
+        }
+    }
+
+    /// Sample the frame times of the app the frame profiler watches
+    fn poll_gfxinfo(&mut self) {
+        if self.task_handles.contains_key("gfxinfo") {
+            return;
+        }
+        let (Some(adb_bridge), Some(device), Some(package)) =
+            (&self.adb_bridge, self.device_list.selected_device(), self.frame_profiler.sampling())
+        else {
+            return;
+        };
+        if !device.is_usable() {
+            return;
+        }
+        let adb_path = adb_bridge.path().to_string();
+        let device_id = device.identifier.clone();
+        let package = package.to_string();
+        self.run_background_task("gfxinfo".to_string(), move || {
+            let frames = crate::gfxinfo::sample(&adb_path, &device_id, &package).map_err(|e| e.to_string());
+            GfxinfoResult(package, frames)
+        });
+    }
+
+    fn load_wifi_info(&mut self) {
+        if self.task_handles.contains_key("wifi_info") {
+            return;
+        }
+        let (Some(adb_bridge), Some(device)) = (&self.adb_bridge, self.device_list.selected_device()) else {
+            return;
+        };
+        let adb_path = adb_bridge.path().to_string();
+        let device_id = device.identifier.clone();
+        self.wifi_window.open(device_id.clone());
+        self.wifi_window.set_loading();
+        self.run_background_task("wifi_info".to_string(), move || {
+            WifiResult(crate::wifi::wifi_info(&adb_path, &device_id).map_err(|e| e.to_string()))
+        });
+    }
+
+    /// Open the resolution window for the selected device, applying any new size or density first
+    fn change_resolution(&mut self, size: Option<(u32, u32)>, density: Option<u32>) {
+        if self.task_handles.contains_key("resolution") {
+            return;
+        }
+        let (Some(adb_bridge), Some(device)) = (&self.adb_bridge, self.device_list.selected_device()) else {
+            self.toasts.warning(t!("no-device-adb"));
+            return;
+        };
+        let adb_path = adb_bridge.path().to_string();
+        let device_id = device.identifier.clone();
+        self.resolution_window.open(device_id.clone());
+        self.resolution_window.set_busy();
+        self.run_background_task("resolution".to_string(), move || {
+            let result = crate::resolution::apply(&adb_path, &device_id, size, density)
+                .and_then(|()| crate::resolution::read(&adb_path, &device_id));
+            ResolutionResult(result.map_err(|e| e.to_string()))
+        });
+    }
+
+    /// Open the locale window for the selected device, changing its locale or time zone first when asked
+    fn change_locale(&mut self, action: LocaleAction) {
+        if self.task_handles.contains_key("locale") {
+            return;
+        }
+        let (Some(adb_bridge), Some(device)) = (&self.adb_bridge, self.device_list.selected_device()) else {
+            self.toasts.warning(t!("no-device-adb"));
+            return;
+        };
+        let adb_path = adb_bridge.path().to_string();
+        let device_id = device.identifier.clone();
+        self.locale_window.open(device_id.clone());
+        self.locale_window.set_busy();
+        self.run_background_task("locale".to_string(), move || {
+            let change = match action {
+                LocaleAction::SetLocale(tag) => crate::locale::set_locale(&adb_path, &device_id, &tag),
+                LocaleAction::SetTimezone(timezone) => crate::locale::set_timezone(&adb_path, &device_id, &timezone),
+                LocaleAction::None | LocaleAction::Refresh | LocaleAction::CheckClock | LocaleAction::SyncClock => {
+                    Ok(())
+                }
+            };
+            let info = crate::locale::read(&adb_path, &device_id).map_err(|e| e.to_string());
+            LocaleResult(info, change.err().map(|e| e.to_string()))
+        });
+    }
+
+    /// Apply a Doze or standby change on the selected device and read the state back
+    fn change_standby(&mut self, action: StandbyAction) {
+        if self.task_handles.contains_key("standby") {
+            return;
+        }
+        let (Some(adb_bridge), Some(device)) = (&self.adb_bridge, self.device_list.selected_device()) else {
+            self.toasts.warning(t!("no-device-adb"));
+            return;
+        };
+        let adb_path = adb_bridge.path().to_string();
+        let device_id = device.identifier.clone();
+        let mut package = self.standby_window.package();
+        self.standby_window.open(device_id.clone());
+        self.standby_window.set_busy();
+        self.run_background_task("standby".to_string(), move || {
+            let change = match action {
+                StandbyAction::ForceIdle(idle) => crate::standby::force_idle(&adb_path, &device_id, idle),
+                StandbyAction::Whitelist(name, whitelisted) => {
+                    crate::standby::set_whitelisted(&adb_path, &device_id, &name, whitelisted)
+                }
+                StandbyAction::SetBucket(name, bucket) => {
+                    crate::standby::set_bucket(&adb_path, &device_id, &name, bucket)
+                }
+                StandbyAction::UseForeground => {
+                    crate::gfxinfo::foreground_package(&adb_path, &device_id).map(|name| package = Some(name))
+                }
+                StandbyAction::Refresh(name) => {
+                    package = name;
+                    Ok(())
+                }
+                StandbyAction::None => Ok(()),
+            };
+            let info = crate::standby::read(&adb_path, &device_id, package.as_deref()).map_err(|e| e.to_string());
+            StandbyResult(info, change.err().map(|e| e.to_string()))
+        });
+    }
+
+    /// Change the mock location of the selected device and read its provider state back
+    fn change_mock_location(&mut self, action: MockLocationAction) {
+        if self.task_handles.contains_key("mock_location") {
+            return;
+        }
+        let (Some(adb_bridge), Some(device)) = (&self.adb_bridge, self.device_list.selected_device()) else {
+            self.toasts.warning(t!("no-device-adb"));
+            return;
+        };
+        let adb_path = adb_bridge.path().to_string();
+        let device_id = device.identifier.clone();
+        if let MockLocationAction::PlayRoute { route, speed } = action {
+            self.play_route(adb_path, device_id, route, speed);
+            return;
+        }
+        self.mock_location_window.open(device_id.clone());
+        self.mock_location_window.set_busy();
+        self.run_background_task("mock_location".to_string(), move || {
+            let change = match action {
+                MockLocationAction::PrepareHelper => crate::mock_location::prepare_helper(&adb_path, &device_id),
+                MockLocationAction::SetProvider(package) => {
+                    crate::mock_location::set_provider(&adb_path, &device_id, &package)
+                }
+                MockLocationAction::Set(position) => crate::mock_location::set(&adb_path, &device_id, position),
+                MockLocationAction::Stop => crate::mock_location::stop(&adb_path, &device_id),
+                _ => Ok(()),
+            };
+            let info = crate::mock_location::read(&adb_path, &device_id).map_err(|e| e.to_string());
+            MockLocationResult(info, change.err().map(|e| e.to_string()))
+        });
+    }
+
+    /// Play a GPX route on `serial`, reporting each point to the mock location window
+    fn play_route(
+        &mut self,
+        adb_path: String,
+        serial: String,
+        route: Vec<crate::mock_location::RoutePoint>,
+        speed: f32,
+    ) {
+        if self.task_handles.contains_key("mock_route") {
+            return;
+        }
+        let sender = self.result_sender.clone();
+        let cancel = self.route_cancel.clone();
+        cancel.store(false, std::sync::atomic::Ordering::Relaxed);
+        self.mock_location_window.set_playing(Some(0));
+
+        let handle = tokio::task::spawn_blocking(move || {
+            let result = crate::mock_location::play_route(&adb_path, &serial, &route, speed, &cancel, |point| {
+                let _ = sender.send(BackgroundTaskResult::MockRouteProgress(point));
+            });
+            let _ = sender.send(BackgroundTaskResult::MockRouteFinished(result.map_err(|e| e.to_string())));
+        });
+
+        self.task_handles.insert("mock_route".to_string(), handle);
+    }
+
+    /// Measure the drift of the selected device's clock, setting it from this computer first when `sync`
+    fn check_clock(&mut self, sync: bool) {
+        if self.task_handles.contains_key("clock") {
+            return;
+        }
+        // Setting the clock turns off network time, a global setting
+        if sync && !self.has_capability(Capability::GlobalSettings) {
+            return;
+        }
+        let (Some(adb_bridge), Some(device)) = (&self.adb_bridge, self.device_list.selected_device()) else {
+            return;
+        };
+        let adb_path = adb_bridge.path().to_string();
+        let device_id = device.identifier.clone();
+        self.locale_window.set_clock_busy();
+        self.run_background_task("clock".to_string(), move || {
+            let synced = sync.then(|| crate::clock::sync(&adb_path, &device_id).map_err(|e| e.to_string()));
+            ClockResult(crate::clock::drift(&adb_path, &device_id).map_err(|e| e.to_string()), synced)
+        });
+    }
+
+    /// Ask where to save a device report, then gather it in the background
+    fn export_device_report(&mut self) {
+        if self.task_handles.contains_key("device_report") {
+            return;
+        }
+        let (Some(adb_bridge), Some(device)) = (&self.adb_bridge, self.device_list.selected_device()) else {
+            self.toasts.warning(t!("no-device-adb"));
+            return;
+        };
+        let adb_path = adb_bridge.path().to_string();
+        let device_id = device.identifier.clone();
+        let file_name = crate::utils::expand_filename_template(
+            "device_report_{serial}_{timestamp}",
+            &device.identifier,
+            &device.model,
+            chrono::Local::now(),
+            "html",
+        );
+        let default_dir = match self.config.try_lock() {
+            Ok(config) => config.screenshot_dir(),
+            Err(_) => dirs::desktop_dir().unwrap_or_default(),
+        };
+        let Some(path) = rfd::FileDialog::new()
+            .set_title(t!("device-report-save"))
+            .set_directory(&default_dir)
+            .set_file_name(file_name)
+            .add_filter("HTML", &["html"])
+            .add_filter("JSON", &["json"])
+            .save_file()
+        else {
+            return;
+        };
+
+        self.toasts.info(t!("device-report-gathering"));
+        self.run_background_task("device_report".to_string(), move || {
+            DeviceReportResult(crate::report::export(&adb_path, &device_id, &path).map_err(|e| e.to_string()))
+        });
+    }
+
+    fn load_foreground_package(&mut self) {
+        let (Some(adb_bridge), Some(device)) = (&self.adb_bridge, self.device_list.selected_device()) else {
+            self.frame_profiler.set_error(t!("no-device-adb"));
+            return;
+        };
+        let adb_path = adb_bridge.path().to_string();
+        let device_id = device.identifier.clone();
+        self.run_background_task("foreground_package".to_string(), move || {
+            ForegroundPackageResult(crate::gfxinfo::foreground_package(&adb_path, &device_id).map_err(|e| e.to_string()))
+        });
+    }
+
+    /// Route files dropped onto the window: APKs are installed, everything else goes to the push dialog
+    fn handle_dropped_files(&mut self, ctx: &egui::Context) {
+        let dropped: Vec<std::path::PathBuf> = ctx.input(|i| {
+            i.raw
+                .dropped_files
+                .iter()
+                .filter_map(|file| file.path.clone())
+                .collect()
+        });
+        if dropped.is_empty() {
+            return;
+        }
+
+        let (apks, others): (Vec<_>, Vec<_>) = dropped.into_iter().partition(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .map(|ext| ext.eq_ignore_ascii_case("apk"))
+                    .unwrap_or(false)
+        });
+
+        if !others.is_empty() {
+            self.file_push_dialog.open_with(others);
+        }
+
+        if !apks.is_empty() && !self.task_handles.contains_key("drop_install") {
+            if let (Some(adb_bridge), Some(device)) =
+                (&self.adb_bridge, self.device_list.selected_device())
+            {
+                let adb_path = adb_bridge.path().to_string();
+                let device_id = device.identifier.clone();
+                let user = self.package_user();
+                self.run_background_task("drop_install".to_string(), move || {
+                    let bridge = AdbBridge::new(adb_path);
+                    let results = apks
+                        .iter()
+                        .map(|apk| {
+                            let name = apk
+                                .file_name()
+                                .map(|n| n.to_string_lossy().to_string())
+                                .unwrap_or_default();
+                            (name, bridge.install(apk, Some(&device_id), user).map_err(|e| e.to_string()))
+                        })
+                        .collect();
+                    DroppedApksResult(results)
+                });
+                self.toasts.info(t!("apks-installing"));
+            } else {
+                self.toasts.warning(t!("no-device-adb"));
+            }
+        }
+    }
+
+    fn start_file_push(&mut self, files: Vec<(usize, std::path::PathBuf)>, destination: String) {
+        let (Some(adb_bridge), Some(device)) =
+            (&self.adb_bridge, self.device_list.selected_device())
+        else {
+            self.toasts.warning(t!("no-device-adb"));
+            self.file_push_dialog.finish();
+            return;
+        };
+
+        let adb_path = adb_bridge.path().to_string();
+        let device_id = device.identifier.clone();
+        let sender = self.result_sender.clone();
+
+        // Report progress per file through the result channel as each push completes
+        let handle = tokio::task::spawn_blocking(move || {
+            let bridge = AdbBridge::new(adb_path);
+            let mut pushed = 0;
+            let mut failed = 0;
+            for (index, path) in files {
+                let _ = sender.send(BackgroundTaskResult::PushProgress(index, PushStatus::Pushing));
+                let status = match bridge.push(&path, &destination, Some(&device_id)) {
+                    Ok(()) => {
+                        pushed += 1;
+                        PushStatus::Done
+                    }
+                    Err(e) => {
+                        failed += 1;
+                        PushStatus::Failed(e.to_string())
+                    }
+                };
+                let _ = sender.send(BackgroundTaskResult::PushProgress(index, status));
+            }
+            let _ = sender.send(BackgroundTaskResult::PushFinished { pushed, failed });
+        });
+
+        self.task_handles.insert("file_push".to_string(), handle);
+        self.toasts.info(t!("push-pushing"));
+    }
+
+    fn apply_panel_visibility_from_config(&mut self) {
+        if let Ok(config) = self.config.try_lock() {
             self.bottom_panel.visible = config.panels.bottom;
             self.toolkit_panel.visible = config.panels.toolkit;
             self.swipe_panel.visible = config.panels.swipe;
+            self.dock = config.dock.clone();
+            self.device_list.set_labels(config.device_labels.clone());
+            self.device_list.set_detailed(config.device_list_detailed);
+            self.device_list.set_auto_start_enabled(config.auto_start_enabled);
+            if !config.device_thumbnails {
+                self.device_list.clear_thumbnails();
+            }
+        }
+    }
+
+    fn apply_poll_interval(&mut self) {
+        if let Ok(config) = self.config.try_lock() {
+            self.poll_interval = std::time::Duration::from_secs(config.device_poll_secs.max(1));
+        }
+    }
+
+    /// Whether `panel` is switched on and has something to show
+    fn shows_panel(&self, panel: DockPanel) -> bool {
+        match panel {
+            DockPanel::Toolkit => self.toolkit_panel.visible,
+            DockPanel::QuickActions => self.bottom_panel.visible,
+            DockPanel::Plugins => !self.plugins.is_empty() || !self.plugin_errors.is_empty(),
+            DockPanel::Session | DockPanel::Devices | DockPanel::Wireless | DockPanel::DeviceControls => true,
+        }
+    }
+
+    fn reset_layout(&mut self) {
+        self.dock = crate::dock::default_layout();
+        self.save_layout();
+    }
+
+    /// Write the panel layout to the config if it changed since it was last saved
+    fn save_layout(&mut self) {
+        if let Ok(mut config) = self.config.try_lock() {
+            if serde_json::to_value(&config.dock).ok() == serde_json::to_value(&self.dock).ok() {
+                return;
+            }
+            config.dock = self.dock.clone();
+            if let Err(e) = config.save() {
+                error!("Failed to save panel layout: {}", e);
+            }
+        }
+    }
+
+    fn show_dock_panel(&mut self, ui: &mut Ui, panel: DockPanel) {
+        match panel {
+            DockPanel::Session => self.show_control_panel(ui),
+            DockPanel::Devices => self.show_devices_panel(ui),
+            DockPanel::Wireless => {
+                if let Some(action) = self.wireless_adb_panel.show(ui, self.adb_bridge.as_ref(), &self.devices) {
+                    self.handle_wireless_adb_action(action);
+                }
+            }
+            DockPanel::Toolkit => self.show_toolkit_panel(ui),
+            DockPanel::QuickActions => self.show_quick_actions(ui),
+            DockPanel::Plugins => {
+                if let Some(action) = self.plugins_panel.show(ui, &self.plugins, &self.plugin_errors) {
+                    self.handle_plugin_action(action);
+                }
+            }
+            DockPanel::DeviceControls => self.show_device_controls(ui),
+        }
+    }
+
+    /// Brightness and volume sliders, read again whenever another device is selected
+    fn show_device_controls(&mut self, ui: &mut Ui) {
+        let selected = self.device_list.selected_device().map(|device| device.identifier.clone());
+        if self.device_controls.device() != selected.as_deref() {
+            self.device_controls.set_device(selected);
+            self.change_device_control(None);
+        }
+        let simulating = self.device_controls.device().is_some_and(|d| self.battery_simulations.iter().any(|s| s == d));
+        self.device_controls.set_simulating(simulating);
+        match self.device_controls.show(ui) {
+            Some(DeviceControlsAction::Refresh) => self.change_device_control(None),
+            Some(DeviceControlsAction::Change(change)) => self.change_device_control(Some(change)),
+            None => {}
+        }
+    }
+
+    /// Apply `change`, if any, to the shown device and read its controls back
+    fn change_device_control(&mut self, change: Option<crate::controls::ControlChange>) {
+        if let Some(capability) = change.and_then(crate::controls::ControlChange::capability)
+            && !self.has_capability(capability)
+        {
+            return;
+        }
+        let (Some(adb_bridge), Some(device_id)) = (&self.adb_bridge, self.device_controls.device()) else {
+            return;
+        };
+        let adb_path = adb_bridge.path().to_string();
+        let device_id = device_id.to_string();
+        match change {
+            Some(crate::controls::ControlChange::SimulateBattery { .. })
+                if !self.battery_simulations.contains(&device_id) =>
+            {
+                self.battery_simulations.push(device_id.clone());
+            }
+            Some(crate::controls::ControlChange::ResetBattery) => self.battery_simulations.retain(|d| *d != device_id),
+            _ => {}
+        }
+        self.device_controls.set_loading();
+        self.run_background_task("device_controls".to_string(), move || {
+            let result = change
+                .map_or(Ok(()), |change| crate::controls::apply(&adb_path, &device_id, change))
+                .and_then(|()| crate::controls::read(&adb_path, &device_id))
+                .map_err(|e| e.to_string());
+            DeviceControlsResult(device_id, result)
+        });
+    }
+
+    /// Reset a battery simulation from the banner, whichever device the panel shows
+    fn reset_battery_simulation(&mut self, device_id: String) {
+        let Some(adb_bridge) = &self.adb_bridge else {
+            return;
+        };
+        let adb_path = adb_bridge.path().to_string();
+        self.battery_simulations.retain(|d| *d != device_id);
+        self.run_background_task(format!("battery_reset_{}", device_id), move || {
+            let result = crate::controls::apply(&adb_path, &device_id, crate::controls::ControlChange::ResetBattery)
+                .and_then(|()| crate::controls::read(&adb_path, &device_id))
+                .map_err(|e| e.to_string());
+            DeviceControlsResult(device_id, result)
+        });
+    }
+
+    fn handle_device_list_action(&mut self, action: DeviceListAction) {
+        if let DeviceListAction::FixAuthorization(serial) = action {
+            self.open_adb_keys(Some(serial));
+            return;
+        }
+        let Ok(mut config) = self.config.try_lock() else {
+            return;
+        };
+        match action {
+            DeviceListAction::None | DeviceListAction::FixAuthorization(_) => return,
+            DeviceListAction::Rename(serial, nickname) => {
+                config.device_labels.entry(serial).or_default().nickname = nickname;
+            }
+            DeviceListAction::SetFavorite(serial, favorite) => {
+                config.device_labels.entry(serial).or_default().favorite = favorite;
+            }
+            DeviceListAction::SetAutoStart(serial, auto_start) => {
+                config.device_labels.entry(serial).or_default().auto_start = auto_start;
+            }
+            DeviceListAction::CancelReconnect(endpoint) => {
+                self.reconnector.cancel(&endpoint);
+                return;
+            }
+            DeviceListAction::SetDetailed(detailed) => {
+                config.device_list_detailed = detailed;
+                self.device_list.set_detailed(detailed);
+            }
+        }
+        config.device_labels.retain(|_, label| *label != crate::config::DeviceLabel::default());
+        if let Err(e) = config.save() {
+            error!("Failed to save device labels: {}", e);
+        }
+        self.device_list.set_labels(config.device_labels.clone());
+    }
+
+    /// Device list with the status bar and ADB controls under it
+    fn show_devices_panel(&mut self, ui: &mut Ui) {
+        let action = self.device_list.show(ui);
+        self.handle_device_list_action(action);
+        // Status bar below device list
+        ui.separator();
+        ui.horizontal(|ui| {
+            let unseen = self.toasts.unseen_errors();
+            let bell = if unseen > 0 {
+                RichText::new(format!("{} {}", egui_phosphor::fill::BELL, unseen)).color(Color32::RED)
+            } else {
+                RichText::new(egui_phosphor::fill::BELL)
+            };
+            if ui.small_button(bell).on_hover_text(t!("status-notifications")).clicked() {
+                self.toasts.toggle_history();
+            }
+            if let Some((tool, stage)) = self.tool_install {
+                ui.add(egui::Spinner::new().size(12.0));
+                ui.label(t!("tools-stage", tool = tool.name(), stage = stage.describe()));
+            } else if let Some(toast) = self.toasts.latest() {
+                ui.label(RichText::new(&toast.text).color(toast.severity.color()))
+                    .on_hover_text(toast.at.format("%H:%M:%S").to_string());
+            }
+            if self.scrcpy_running {
+                ui.label(RichText::new(t!("status-scrcpy-running")).color(Color32::GREEN));
+            } else {
+                ui.label(RichText::new(t!("status-scrcpy-stopped")).color(Color32::RED));
+            }
+        });
+        ui.separator();
+        ui.horizontal(|ui| {
+            if ui.button(t!("main-refresh")).clicked() {
+                self.refresh_devices();
+            }
+            if ui.button(t!("quick-restart-adb")).clicked()
+                && let Some(adb_bridge) = &self.adb_bridge {
+                if let Err(e) = crate::device::restart_adb_server(adb_bridge.path()) {
+                    error!("Failed to restart ADB: {}", e);
+                    self.toasts.error(t!("adb-restart-failed", error = e));
+                } else {
+                    self.toasts.success(t!("adb-restarted"));
+                    self.refresh_devices();
+                }
+            }
+        });
+    }
+
+    fn show_toolkit_panel(&mut self, ui: &mut Ui) {
+        use crate::ui::panels::ToolkitLoadingState;
+        let ctx = ui.ctx().clone();
+        let loading = ToolkitLoadingState {
+            screenshot: false,
+            long_screenshot: self.task_handles.contains_key("long_screenshot"),
+            native_preview: self.task_handles.contains_key("native_preview"),
+            record_screen: self.recording.is_some(),
+            install_apk: false,
+            open_shell: false,
+            bugreport: self.bugreport_progress.is_some(),
+            reverse_tether: self
+                .device_list
+                .selected_device()
+                .is_some_and(|device| self.tethered.contains(&device.identifier)),
+            show_imei: self.loading_imei,
+            display_info: self.loading_display_info,
+            battery_info: self.loading_battery_info,
+            device_report: self.task_handles.contains_key("device_report"),
+            ocr: self.task_handles.contains_key("ocr"),
+            uninstall_app: self.loading_apps,
+            disable_app: self.loading_disable_apps,
+        };
+        self.toolkit_panel.reboot_targets = self.reboot_targets();
+        let toolkit_action = self.toolkit_panel.show(ui, &loading);
+        self.handle_toolkit_action(toolkit_action);
+        if self.toolkit_panel.visible
+            && let Some((plugin, command)) = crate::ui::plugins::toolkit_buttons(ui, &self.plugins)
+        {
+            let values = self.plugins_panel.values(&self.plugins, plugin, command);
+            self.run_plugin_command(plugin, command, values);
+        }
+        
+        if let Some((_, since)) = self.recording {
+            let elapsed = since.elapsed().as_secs();
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{} {}",
+                        egui_phosphor::fill::RECORD,
+                        t!(
+                            "recording-status",
+                            elapsed = format!("{:02}:{:02}", elapsed / 60, elapsed % 60),
+                            duration = format!("{:02}:{:02}", self.screenrecord_duration / 60, self.screenrecord_duration % 60),
+                        )
+                    ))
+                    .size(13.0)
+                    .color(Color32::RED),
+                );
+                if ui.button(format!("{} {}", egui_phosphor::fill::STOP, t!("recording-stop-now"))).clicked() {
+                    self.stop_screenrecord();
+                }
+            });
+            ctx.request_repaint_after(std::time::Duration::from_secs(1));
+        }
+
+        if let Some(percent) = self.bugreport_progress {
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(format!("{} {}", egui_phosphor::fill::BUG, t!("bugreport-progress"))).size(13.0));
+                ui.add(egui::ProgressBar::new(f32::from(percent) / 100.0).show_percentage().desired_width(120.0));
+                if ui.button(format!("{} {}", egui_phosphor::fill::X, t!("bugreport-cancel"))).clicked() {
+                    self.bugreport_cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+                }
+            });
+        }
+
+        // Add processing status below the toolkit
+        if self.is_processing() {
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.add(egui::Spinner::new().size(16.0));
+                ui.label(egui::RichText::new(t!("processing")).size(13.0).color(Color32::YELLOW));
+            });
+        }
+        
+        // Theme switch and About button
+        ui.separator();
+        ui.horizontal(|ui| {
+            // Theme toggle button
+            let current_theme = if let Ok(config) = self.config.try_lock() {
+                config.theme.clone()
+            } else {
+                "default".to_string()
+            };
+            
+            let theme_text = match current_theme.as_str() {
+                "dark" => format!("{} {}", egui_phosphor::fill::MOON, t!("theme-dark")),
+                "light" => format!("{} {}", egui_phosphor::fill::SUN, t!("theme-light")),
+                _ => format!("{} {}", egui_phosphor::fill::MOON, t!("theme-dark"))
+            };
+            
+            if ui.button(egui::RichText::new(theme_text).size(12.0)).clicked() {
+                self.toggle_theme(&ctx);
+            }
+            
+            ui.separator();
+            
+            // About button
+            if ui.button(egui::RichText::new(format!("{} {}", egui_phosphor::fill::INFO, t!("about"))).size(12.0)).clicked() {
+                self.about_dialog = true;
+            }
+        });
+    }
+
+    fn show_quick_actions(&mut self, ui: &mut Ui) {
+        let pinned = self.pinned_scripts();
+        let names: Vec<String> = pinned.iter().map(|script| script.name.clone()).collect();
+        match self.bottom_panel.show(ui, &names) {
+            BottomPanelAction::RunScript(index) => {
+                let Some(script) = pinned.into_iter().nth(index) else {
+                    return;
+                };
+                let Some(device) = self.device_list.selected_device() else {
+                    self.toasts.warning(t!("no-device-adb"));
+                    return;
+                };
+                let serial = device.identifier.clone();
+                if self.task_handles.contains_key("script") {
+                    self.toasts.warning(t!("scripts-busy"));
+                    return;
+                }
+                self.scripts_window.started(&script.name);
+                self.run_script(serial, script.name, script.source);
+            }
+            action => self.handle_bottom_panel_action(action),
+        }
+    }
+
+    /// Start, restart or stop the control API to match the settings
+    fn apply_control_api(&mut self, ctx: &egui::Context) {
+        let settings = {
+            let Ok(mut config) = self.config.try_lock() else {
+                return;
+            };
+            if config.control_api.enabled && config.control_api.token.is_empty() {
+                config.control_api.token = crate::config::ControlApi::new_token();
+                if let Err(e) = config.save() {
+                    error!("Failed to save the control API token: {}", e);
+                }
+            }
+            config.control_api.clone()
+        };
+        if let Some(server) = &self.api_server
+            && settings.enabled
+            && server.serves(&settings)
+        {
+            return;
+        }
+        if let Some(server) = self.api_server.take() {
+            server.stop();
+        }
+        if !settings.enabled {
+            return;
+        }
+        match ApiServer::start(settings.port, settings.token, ctx.clone()) {
+            Ok(server) => self.api_server = Some(server),
+            Err(e) => {
+                error!("Control API not started: {}", e);
+                self.toasts.error(t!("api-start-failed", error = e));
+            }
+        }
+    }
+
+    /// Tell the control API's event streams, when it runs
+    fn emit_api_event(&self, event: ApiEvent) {
+        if let Some(server) = &self.api_server {
+            server.emit(event);
+        }
+    }
+
+    /// Answer a request from the control API
+    /// Run `act` as if the device with this serial were selected, then select the device that
+    /// was before, so API calls leave the device list alone; false when it is not connected
+    fn on_device(&mut self, serial: &str, act: impl FnOnce(&mut Self)) -> bool {
+        let selection = self.device_list.selection();
+        if !self.device_list.select(serial) {
+            return false;
+        }
+        act(self);
+        self.device_list.restore_selection(selection);
+        true
+    }
+
+    fn handle_api_request(&mut self, request: ApiRequest) {
+        let reply = match &request.command {
+            ApiCommand::Devices => ApiReply::Json(
+                200,
+                serde_json::json!({ "devices": self.device_list.devices(), "mirroring": self.scrcpy_running }),
+            ),
+            ApiCommand::ToolkitActions => {
+                let actions: Vec<_> = crate::ui::panels::ToolkitAction::ALL
+                    .into_iter()
+                    .map(|action| serde_json::json!({ "id": action.id(), "label": action.label() }))
+                    .collect();
+                ApiReply::Json(200, serde_json::json!({ "actions": actions }))
+            }
+            ApiCommand::Mirror { serial, preset } => {
+                let template = preset.as_ref().map(|name| {
+                    let config = self.config.try_lock().ok();
+                    config.and_then(|config| crate::cli::find_preset(&config, name)).ok_or(name)
+                });
+                match template.transpose() {
+                    Err(name) => ApiReply::error(404, format!("No session template named '{}'", name)),
+                    Ok(template) => {
+                        let started = self.on_device(serial, |app| {
+                            info!("Control API: mirroring {}", serial);
+                            app.start_scrcpy_with(template.as_ref());
+                        });
+                        if started {
+                            ApiReply::ok()
+                        } else {
+                            ApiReply::error(404, format!("Device {} is not connected", serial))
+                        }
+                    }
+                }
+            }
+            ApiCommand::StopMirror => {
+                self.stop_scrcpy();
+                ApiReply::ok()
+            }
+            ApiCommand::Screenshot { serial } => {
+                let Some(adb_bridge) = &self.adb_bridge else {
+                    request.respond(ApiReply::error(503, "ADB is not configured"));
+                    return;
+                };
+                let (adb_path, serial) = (adb_bridge.path().to_string(), serial.clone());
+                std::thread::spawn(move || {
+                    let reply = match crate::automation::screencap(&adb_path, &serial) {
+                        Ok(png) => ApiReply::Png(png),
+                        Err(e) => ApiReply::error(502, e.to_string()),
+                    };
+                    request.respond(reply);
+                });
+                return;
+            }
+            ApiCommand::Toolkit { serial, action } => {
+                let ran = self.on_device(serial, |app| {
+                    info!("Control API: {} on {}", action.id(), serial);
+                    app.handle_toolkit_action(*action);
+                });
+                if ran {
+                    ApiReply::ok()
+                } else {
+                    ApiReply::error(404, format!("Device {} is not connected", serial))
+                }
+            }
+        };
+        request.respond(reply);
+    }
+
+    fn reload_plugins(&mut self) {
+        match crate::plugins::plugins_dir() {
+            Ok(dir) => (self.plugins, self.plugin_errors) = crate::plugins::discover(&dir),
+            Err(e) => self.plugin_errors = vec![e.to_string()],
+        }
+        for error in &self.plugin_errors {
+            tracing::warn!("Plugin not loaded: {}", error);
+        }
+    }
+
+    fn handle_plugin_action(&mut self, action: PluginAction) {
+        match action {
+            PluginAction::Run { plugin, command, values } => self.run_plugin_command(plugin, command, values),
+            PluginAction::Reload => self.reload_plugins(),
+            PluginAction::OpenFolder => {
+                let opened = crate::plugins::plugins_dir().and_then(|dir| {
+                    std::fs::create_dir_all(&dir)?;
+                    crate::utils::open_folder(&dir)
+                });
+                if let Err(e) = opened {
+                    self.toasts.error(t!("open-folder-failed", error = e));
+                }
+            }
+        }
+    }
+
+    fn run_plugin_command(&mut self, plugin: usize, command: usize, values: std::collections::HashMap<String, String>) {
+        let (Some(adb_bridge), Some(device)) = (&self.adb_bridge, self.device_list.selected_device()) else {
+            self.toasts.warning(t!("no-device-adb"));
+            return;
+        };
+        if self.task_handles.contains_key("plugin") {
+            return;
+        }
+        let Some(plugin) = self.plugins.get(plugin) else {
+            return;
+        };
+        let dir = plugin.dir.clone();
+        let command = plugin.commands[command].clone();
+        let adb_path = adb_bridge.path().to_string();
+        let serial = device.identifier.clone();
+        self.plugins_panel.set_running();
+        self.run_background_task("plugin".to_string(), move || {
+            let result = crate::plugins::run(&dir, &command, &adb_path, &serial, &values).map_err(|e| e.to_string());
+            PluginResult(command.label, result)
+        });
+    }
+
+    fn pinned_scripts(&self) -> Vec<crate::config::UserScript> {
+        self.config
+            .try_lock()
+            .map(|config| config.scripts.iter().filter(|script| script.pinned).cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Run a script on `serial`, streaming what it prints to the Scripts window
+    fn run_script(&mut self, serial: String, name: String, source: String) {
+        let Some(adb_bridge) = &self.adb_bridge else {
+            self.scripts_window.finish(Err(t!("adb-not-configured")));
+            return;
+        };
+        if self.task_handles.contains_key("script") {
+            return;
+        }
+        let (screenshot_dir, screenshot_template) = match self.config.try_lock() {
+            Ok(config) => (config.screenshot_dir(), config.screenshot_template.clone()),
+            Err(_) => (dirs::desktop_dir().unwrap_or_default(), "screenshot_{timestamp}".to_string()),
+        };
+        let model = self
+            .devices
+            .iter()
+            .find(|device| device.identifier == serial)
+            .map(|device| device.model.clone())
+            .unwrap_or_default();
+        let context = crate::scripting::ScriptContext {
+            adb_path: adb_bridge.path().to_string(),
+            serial,
+            model,
+            screenshot_dir,
+            screenshot_template,
+        };
+        let sender = self.result_sender.clone();
+        let cancel = self.script_cancel.clone();
+        cancel.store(false, std::sync::atomic::Ordering::Relaxed);
+
+        let handle = tokio::task::spawn_blocking(move || {
+            let output = sender.clone();
+            let result = crate::scripting::run(context, &source, cancel, move |line| {
+                let _ = output.send(BackgroundTaskResult::ScriptOutput(line));
+            });
+            let _ = sender.send(BackgroundTaskResult::ScriptFinished(name, result));
+        });
+
+        self.task_handles.insert("script".to_string(), handle);
+    }
+
+    fn handle_bottom_panel_action(&mut self, action: BottomPanelAction) {
+        match action {
+            BottomPanelAction::RefreshDevices => self.refresh_devices(),
+            BottomPanelAction::RestartAdb => {
+                if let Some(adb_bridge) = &self.adb_bridge {
+                    if let Err(e) = crate::device::restart_adb_server(adb_bridge.path()) {
+                        error!("Failed to restart ADB: {}", e);
+                        self.toasts.error(t!("adb-restart-failed", error = e));
+                    } else {
+                        self.toasts.success(t!("adb-restarted"));
+                        self.refresh_devices();
+                    }
+                }
+            }
+            BottomPanelAction::OpenSettings => self.settings_window.open(),
+            BottomPanelAction::OpenGallery => {
+                let dir = self.config.try_lock().map(|config| config.screenshot_dir()).unwrap_or_default();
+                self.gallery_window.open(dir);
+            }
+            BottomPanelAction::OpenRecordings => {
+                let dir = self.config.try_lock().map(|config| config.screenshot_dir()).unwrap_or_default();
+                self.recording_library.open(dir);
+            }
+            BottomPanelAction::ToggleLogs => self.log_console.toggle(),
+            BottomPanelAction::ShowShortcuts => self.shortcuts_window.toggle(),
+            BottomPanelAction::OpenScripts => {
+                let scripts = self.config.try_lock().map(|config| config.scripts.clone()).unwrap_or_default();
+                let device = self.device_list.selected_device().map(|device| device.identifier.clone());
+                self.scripts_window.open(scripts, device);
+            }
+            BottomPanelAction::RunScript(_) | BottomPanelAction::None => {}
+        }
+    }
+
+    /// Remember where the main window is; while maximized or minimized the normal size and
+    /// position are kept from before
+    fn track_window_geometry(&mut self, ctx: &egui::Context) {
+        let (maximized, minimized, inner, outer) = ctx.input(|input| {
+            let viewport = input.viewport();
+            (
+                viewport.maximized.unwrap_or(false),
+                viewport.minimized.unwrap_or(false),
+                viewport.inner_rect,
+                viewport.outer_rect,
+            )
+        });
+        if minimized {
+            return;
+        }
+        if maximized {
+            if let Some(geometry) = &mut self.window_geometry {
+                geometry.maximized = true;
+            }
+            return;
+        }
+        let Some(inner) = inner else {
+            return;
+        };
+        // Rects are in points, which the UI scale setting zooms; the viewport builder takes logical pixels
+        let zoom = ctx.zoom_factor();
+        self.window_geometry = Some(WindowGeometry {
+            size: (inner.size() * zoom).into(),
+            position: outer.map(|outer| (outer.min.to_vec2() * zoom).into()),
+            maximized: false,
+        });
+    }
+
+    /// Point every adb and scrcpy process at the configured adb server
+    fn apply_adb_server(&self) {
+        if let Ok(config) = self.config.try_lock() {
+            crate::bridge::set_adb_server(config.remote_adb.active());
         }
     }
 
     fn apply_theme(&self, ctx: &egui::Context) {
         if let Ok(config) = self.config.try_lock() {
-            match config.theme.as_str() {
-                "dark" => ctx.set_visuals(egui::Visuals::dark()),
-                "light" => ctx.set_visuals(egui::Visuals::light()),
-                _ => ctx.set_visuals(egui::Visuals::default()),
-            }
+            ctx.set_visuals(crate::ui::theme::visuals(&config.theme, &config.custom_theme));
+        }
+    }
+
+    fn apply_scaling(&self, ctx: &egui::Context) {
+        if let Ok(config) = self.config.try_lock() {
+            crate::ui::theme::apply_scaling(ctx, config.ui_scale, config.font_size);
         }
     }
 
@@ -303,36 +3754,119 @@ impl DroidViewApp {
         self.task_handles.insert(task_id, handle);
     }
 
+    fn show_session_usage(&self, ui: &mut Ui, usage: &SessionUsage) {
+        ui.label(RichText::new(t!("session-usage")).strong());
+        egui::Grid::new("session_usage_grid").num_columns(3).show(ui, |ui| {
+            for (name, process) in [("scrcpy".to_string(), usage.scrcpy), (t!("session-usage-adb"), usage.adb)] {
+                ui.label(name);
+                match process {
+                    Some(process) => {
+                        ui.label(t!("session-usage-cpu", percent = format!("{:.0}", process.cpu_percent)));
+                        ui.label(t!("session-usage-ram", size = format_file_size(process.memory_bytes)));
+                    }
+                    None => {
+                        ui.label(RichText::new(t!("session-usage-not-running")).color(Color32::GRAY));
+                        ui.label("");
+                    }
+                }
+                ui.end_row();
+            }
+        });
+
+        if usage.scrcpy_saturated() {
+            ui.label(
+                RichText::new(format!("{} {}", egui_phosphor::fill::WARNING, t!("session-usage-saturated")))
+                .color(Color32::YELLOW),
+            );
+        }
+    }
+
     fn show_control_panel(&mut self, ui: &mut Ui) {
-        ui.heading("Control Panel");
+        ui.heading(t!("control-panel-title"));
 
         if let Some(device) = self.device_list.selected_device() {
             ui.group(|ui| {
-                ui.label(format!("Selected Device: {}", device.model));
-                ui.label(format!("ID: {}", device.identifier));
-                ui.label(format!("Status: {:?}", device.status));
+                let status = match &device.status {
+                    DeviceStatus::Device => t!("devices-status-connected"),
+                    DeviceStatus::Offline => t!("devices-status-offline"),
+                    DeviceStatus::Unauthorized => t!("devices-status-unauthorized"),
+                    DeviceStatus::NoPermission => t!("devices-status-no-permission"),
+                    DeviceStatus::Unknown(state) => state.clone(),
+                };
+                ui.label(t!("control-panel-selected", model = device.model));
+                ui.label(t!("control-panel-id", id = device.identifier));
+                ui.label(t!("control-panel-status", status = status));
             });
         } else {
-            ui.label(RichText::new("No device selected").color(Color32::GRAY));
+            ui.label(RichText::new(t!("control-panel-no-device")).color(Color32::GRAY));
         }
 
         ui.separator();
 
         ui.group(|ui| {
-            ui.heading("Scrcpy Controls");
+            ui.horizontal(|ui| {
+                ui.heading(t!("control-panel-scrcpy"));
+                if help_button(ui, HelpTopic::ScrcpyOptions) {
+                    self.help_window.open(HelpTopic::ScrcpyOptions);
+                }
+            });
 
             let mut start_scrcpy = false;
             let mut stop_scrcpy = false;
+            let mut open_stream = false;
+            let mut launch_template = None;
+            let mut open_remote_assist = false;
+            let template_names: Vec<String> = self
+                .config
+                .try_lock()
+                .map(|config| config.session_templates.iter().map(|template| template.name.clone()).collect())
+                .unwrap_or_default();
 
             ui.horizontal(|ui| {
-                if ui.button("▶ Start Scrcpy").clicked() {
+                if ui.button(t!("controls-start")).clicked() {
                     start_scrcpy = true;
                 }
-                if ui.button("■ Stop Scrcpy").clicked() {
+                if ui.button(t!("controls-stop")).clicked() {
                     stop_scrcpy = true;
                 }
+                let stream_label = if self.stream_session.is_some() {
+                    RichText::new(format!("{} {}", egui_phosphor::fill::BROADCAST, t!("control-panel-live"))).color(Color32::RED)
+                } else {
+                    RichText::new(format!("{} {}", egui_phosphor::fill::BROADCAST, t!("control-panel-stream")))
+                };
+                if ui.button(stream_label).clicked() {
+                    open_stream = true;
+                }
+                ui.menu_button(format!("{} {}", egui_phosphor::fill::BOOKMARKS, t!("control-panel-templates")), |ui| {
+                    if template_names.is_empty() {
+                        ui.label(RichText::new(t!("control-panel-no-templates")).color(Color32::GRAY));
+                    }
+                    for name in &template_names {
+                        if ui.button(name).clicked() {
+                            launch_template = Some(name.clone());
+                            ui.close();
+                        }
+                    }
+                    ui.separator();
+                    if ui
+                        .button(format!("{} {}", egui_phosphor::fill::HANDSHAKE, REMOTE_ASSISTANCE))
+                        .on_hover_text(t!("control-panel-remote-assist-hint"))
+                        .clicked()
+                    {
+                        launch_template = Some(REMOTE_ASSISTANCE.to_string());
+                        ui.close();
+                    }
+                    if ui.button(t!("control-panel-remote-assist-setup")).clicked() {
+                        open_remote_assist = true;
+                        ui.close();
+                    }
+                });
             });
 
+            if let Some(usage) = &self.session_usage {
+                self.show_session_usage(ui, usage);
+            }
+
             // --- Bitrate knob and quick settings ---
             {
                 let mut config = self.config.try_lock().unwrap();
@@ -359,13 +3893,13 @@ impl DroidViewApp {
                         .with_font_size(14.0)
                         .with_stroke_width(3.0)
                         .with_colors(Color32::GRAY, Color32::WHITE, Color32::WHITE)
-                        .with_label("Bitrate", LabelPosition::Top);
+                        .with_label(t!("control-panel-bitrate"), LabelPosition::Top);
                     let knob_resp = ui.add(knob);
                     if knob_resp.changed() {
                         knob_value = (knob_value / 100.0).round() * 100.0;
                         bitrate_value = knob_value as u32;
                     }
-                    egui::ComboBox::new("scrcpy_bitrate_unit_combo", "Unit")
+                    egui::ComboBox::new("scrcpy_bitrate_unit_combo", t!("control-panel-unit"))
                         .selected_text(bitrate_unit)
                         .show_ui(ui, |ui| {
                             ui.selectable_value(&mut bitrate_unit, "Kbps", "Kbps");
@@ -377,146 +3911,704 @@ impl DroidViewApp {
                         format!("{}K", bitrate_value)
                     };
                     config.bitrate = bitrate_str;
-                    ui.label(format!("Current: {}", config.bitrate));
+                    ui.label(t!("control-panel-current-bitrate", bitrate = config.bitrate));
+                });
+
+                // Video codec, greyed out where the device has no matching encoder
+                let supported_codecs = self
+                    .device_list
+                    .selected_device()
+                    .and_then(|device| self.video_codecs.get(&device.identifier))
+                    .filter(|codecs| !codecs.is_empty());
+                ui.horizontal(|ui| {
+                    ui.label(t!("control-panel-video-codec"));
+                    egui::ComboBox::from_id_salt("scrcpy_video_codec_combo")
+                        .selected_text(config.video_codec.clone())
+                        .show_ui(ui, |ui| {
+                            for codec in crate::config::VIDEO_CODECS {
+                                let supported = supported_codecs
+                                    .map(|codecs| codecs.iter().any(|c| c == codec))
+                                    .unwrap_or(true);
+                                ui.add_enabled_ui(supported, |ui| {
+                                    ui.selectable_value(&mut config.video_codec, codec.to_string(), codec)
+                                })
+                                .response
+                                .on_disabled_hover_text(t!("control-panel-codec-unsupported"));
+                            }
+                        });
+                    if let Some(codecs) = supported_codecs
+                        && !codecs.contains(&config.video_codec)
+                    {
+                        ui.label(
+                            RichText::new(format!("{} {}", egui_phosphor::fill::WARNING, t!("control-panel-codec-unsupported-here")))
+                                .color(Color32::YELLOW),
+                        );
+                    }
+                });
+
+                // Display selection, fed by the display watcher and remembered per device
+                if let Some(serial) = self.device_list.selected_device().map(|device| device.identifier.clone()) {
+                    let known_displays = self.displays.get(&serial);
+                    let mut display_id = config.display_ids.get(&serial).copied();
+                    ui.horizontal(|ui| {
+                        ui.label(t!("control-panel-display"));
+                        let selected_text = match display_id {
+                            Some(id) => known_displays
+                                .and_then(|displays| displays.iter().find(|d| d.id == id))
+                                .map(|d| format!("{} ({})", d.name, d.id))
+                                .unwrap_or_else(|| t!("control-panel-display-id", id = id)),
+                            None => t!("control-panel-display-default"),
+                        };
+                        egui::ComboBox::from_id_salt("scrcpy_display_combo")
+                            .selected_text(selected_text)
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut display_id, None, t!("control-panel-display-default"));
+                                for display in known_displays.into_iter().flatten() {
+                                    ui.selectable_value(
+                                        &mut display_id,
+                                        Some(display.id),
+                                        format!("{} ({})", display.name, display.id),
+                                    );
+                                }
+                            });
+                    });
+                    match display_id {
+                        Some(id) => config.display_ids.insert(serial, id),
+                        None => config.display_ids.remove(&serial),
+                    };
+                }
+
+                // Quick settings
+                ui.label(t!("control-panel-quick-settings"));
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut config.show_touches, t!("control-panel-show-touches"));
+                    ui.checkbox(&mut config.fullscreen, t!("control-panel-fullscreen"));
+                    ui.checkbox(&mut config.turn_screen_off, t!("control-panel-screen-off"));
+                });
+
+                // Max dimensions from settings (adjustable)
+                ui.horizontal(|ui| {
+                    let mut dim_val = config.dimension.unwrap_or(0);
+                    ui.label(t!("control-panel-max-dimensions"));
+                    if ui.add(egui::DragValue::new(&mut dim_val).range(0..=8192).speed(10)).changed() {
+                        if dim_val == 0 {
+                            config.dimension = None;
+                        } else {
+                            config.dimension = Some(dim_val);
+                        }
+                    }
+                    if ui.button(t!("control-panel-unlimited")).clicked() {
+                        config.dimension = None;
+                    }
+                    if let Some(dim) = config.dimension {
+                        ui.label(t!("control-panel-dimension", pixels = dim));
+                    } else {
+                        ui.label(t!("control-panel-dimension-unlimited"));
+                    }
                 });
+            }
+            // --- End config lock scope ---
+
+            if start_scrcpy {
+                self.start_scrcpy();
+            }
+            if let Some(name) = launch_template {
+                self.launch_template(&name);
+            }
+            if open_remote_assist {
+                self.remote_assist.open();
+            }
+            if stop_scrcpy {
+                self.stop_scrcpy();
+            }
+            if open_stream
+                && let Ok(config) = self.config.try_lock()
+            {
+                self.stream_window.open(config.stream.clone());
+            }
+        });
+
+        let gestures = self
+            .config
+            .try_lock()
+            .ok()
+            .filter(|config| config.panels.swipe)
+            .map(|config| config.gestures.clone());
+        if let Some(gestures) = gestures {
+            ui.separator();
+            if let Some(swipe_action) = self.swipe_panel.show(ui, &gestures) {
+                self.handle_swipe_action(swipe_action);
+            }
+        }
+    }
+
+    fn handle_swipe_action(&mut self, action: SwipeAction) {
+        // Start and end of the fixed swipes, in fractions of the screen
+        let (from, to) = match action {
+            SwipeAction::Up => ([0.5, 0.8], [0.5, 0.2]),
+            SwipeAction::Down => ([0.5, 0.2], [0.5, 0.8]),
+            SwipeAction::Left => ([0.8, 0.5], [0.2, 0.5]),
+            SwipeAction::Right => ([0.2, 0.5], [0.8, 0.5]),
+            SwipeAction::Gesture(gesture) => {
+                self.perform_gesture(gesture);
+                return;
+            }
+            SwipeAction::OpenDesigner => {
+                let gestures = self.config.try_lock().map(|config| config.gestures.clone()).unwrap_or_default();
+                self.gesture_designer.open(gestures);
+                return;
+            }
+        };
+        let (Some(adb_bridge), Some(device)) = (self.adb_bridge.as_ref(), self.device_list.selected_device()) else {
+            self.toasts.warning(t!("no-device-adb"));
+            return;
+        };
+        let (width, height) = match crate::gesture::screen_size(adb_bridge.path(), &device.identifier) {
+            Ok(size) => size,
+            Err(e) => {
+                self.toasts.error(t!("swipe-failed", error = e));
+                return;
+            }
+        };
+        let pixels = |[x, y]: [f32; 2]| ((x * width as f32).round() as u32, (y * height as f32).round() as u32);
+        let ((x1, y1), (x2, y2)) = (pixels(from), pixels(to));
+        let input = crate::config::MacroInput::Swipe { x1, y1, x2, y2, duration_ms: 300 };
+        match crate::macros::send(adb_bridge.path(), &device.identifier, &input) {
+            Ok(()) => {
+                self.macro_window.record(input);
+                self.toasts.success(t!("swipe-sent"));
+            }
+            Err(e) => self.toasts.error(t!("swipe-command-failed", error = e)),
+        }
+    }
+
+    /// Take a screenshot for the tap picker of the device it is open on, after `delay`
+    fn capture_tap_picker(&mut self, delay: std::time::Duration) {
+        let Some(adb_bridge) = &self.adb_bridge else {
+            return;
+        };
+        if self.task_handles.contains_key("tap_picker") {
+            return;
+        }
+        let adb_path = adb_bridge.path().to_string();
+        let device_id = self.tap_picker.device().to_string();
+        self.tap_picker.set_loading();
+        self.run_background_task("tap_picker".to_string(), move || {
+            std::thread::sleep(delay);
+            let result = crate::device::capture_screen(&adb_path, &device_id, PICKER_EDGE).map(|(image, screen)| {
+                let size = [image.width() as usize, image.height() as usize];
+                (egui::ColorImage::from_rgba_unmultiplied(size, &image), screen)
+            });
+            TapPickerResult(result.map_err(|e| e.to_string()))
+        });
+    }
+
+    /// Dump the view hierarchy of the device the inspector is open on, with a screenshot to match
+    fn capture_hierarchy(&mut self) {
+        let Some(adb_bridge) = &self.adb_bridge else {
+            return;
+        };
+        if self.task_handles.contains_key("hierarchy") {
+            return;
+        }
+        let adb_path = adb_bridge.path().to_string();
+        let device_id = self.hierarchy_window.device().to_string();
+        self.hierarchy_window.set_loading();
+        self.run_background_task("hierarchy".to_string(), move || {
+            // Dumped first, as the screenshot is quicker and so closer to what was dumped
+            let result = crate::hierarchy::dump(&adb_path, &device_id).and_then(|root| {
+                let (image, screen) = crate::device::capture_screen(&adb_path, &device_id, INSPECTOR_EDGE)?;
+                let size = [image.width() as usize, image.height() as usize];
+                Ok((root, egui::ColorImage::from_rgba_unmultiplied(size, &image), screen))
+            });
+            HierarchyResult(result.map_err(|e| e.to_string()))
+        });
+    }
+
+    /// Take a screenshot of `device` for one side of the screenshot comparison
+    fn capture_for_diff(&mut self, side: usize, device: String) {
+        let Some(adb_bridge) = &self.adb_bridge else {
+            return;
+        };
+        let task_id = format!("screenshot_diff_{}", side);
+        if self.task_handles.contains_key(&task_id) {
+            return;
+        }
+        let adb_path = adb_bridge.path().to_string();
+        self.screenshot_diff.set_loading(side);
+        self.run_background_task(task_id, move || {
+            let result = crate::device::capture_screen(&adb_path, &device, u32::MAX).map(|(image, _)| image);
+            ScreenshotDiffResult(side, device, result.map_err(|e| e.to_string()))
+        });
+    }
+
+    /// Take a full-resolution screenshot of the device the color picker is open on
+    fn capture_color_picker(&mut self) {
+        let Some(adb_bridge) = &self.adb_bridge else {
+            return;
+        };
+        if self.task_handles.contains_key("color_picker") {
+            return;
+        }
+        let adb_path = adb_bridge.path().to_string();
+        let device_id = self.color_picker.device().to_string();
+        self.color_picker.set_loading();
+        self.run_background_task("color_picker".to_string(), move || {
+            // Never scaled down, so every pixel keeps its exact color
+            let result = crate::device::capture_screen(&adb_path, &device_id, u32::MAX).map(|(image, _)| {
+                let size = [image.width() as usize, image.height() as usize];
+                egui::ColorImage::from_rgba_unmultiplied(size, &image)
+            });
+            ColorPickerResult(result.map_err(|e| e.to_string()))
+        });
+    }
+
+    /// Perform a custom gesture on the selected device
+    fn perform_gesture(&mut self, gesture: crate::config::Gesture) {
+        let (Some(adb_bridge), Some(device)) = (self.adb_bridge.as_ref(), self.device_list.selected_device()) else {
+            self.toasts.warning(t!("no-device-adb"));
+            return;
+        };
+        if self.task_handles.contains_key("gesture") {
+            return;
+        }
+        let adb_path = adb_bridge.path().to_string();
+        let serial = device.identifier.clone();
+        self.run_background_task("gesture".to_string(), move || {
+            let result = crate::gesture::perform(&adb_path, &serial, &gesture).map_err(|e| e.to_string());
+            GestureResult(gesture.name, result)
+        });
+    }
+
+    /// Re-register global hotkeys (and the tray menu) after templates or capture hotkeys changed
+    fn apply_session_templates(&mut self) {
+        let (templates, capture) = self
+            .config
+            .try_lock()
+            .map(|config| (config.session_templates.clone(), config.capture_hotkeys.clone()))
+            .unwrap_or_default();
+        let errors = self.global_hotkeys.bind(&templates, &capture);
+        if !errors.is_empty() {
+            tracing::warn!("Global hotkeys: {}", errors.join("; "));
+            self.toasts.warning(errors.join("; "));
+        }
+        #[cfg(feature = "tray")]
+        self.tray.set_templates(templates.iter().map(|template| template.name.clone()).collect());
+    }
+
+    fn apply_shortcuts(&mut self) {
+        let overrides = self.config.try_lock().map(|config| config.shortcuts.clone()).unwrap_or_default();
+        let (shortcuts, errors) = Shortcuts::from_config(&overrides);
+        self.shortcuts = shortcuts;
+        if !errors.is_empty() {
+            tracing::warn!("Keyboard shortcuts: {}", errors.join("; "));
+            self.toasts.warning(errors.join("; "));
+        }
+    }
 
-                // Quick settings
-                ui.label("Quick Settings:");
-                ui.horizontal(|ui| {
-                    ui.checkbox(&mut config.show_touches, "Show touches");
-                    ui.checkbox(&mut config.fullscreen, "Fullscreen");
-                    ui.checkbox(&mut config.turn_screen_off, "Turn screen off");
-                });
+    fn run_shortcut(&mut self, action: ShortcutAction) {
+        match action {
+            ShortcutAction::RefreshDevices => self.refresh_devices(),
+            ShortcutAction::Screenshot => self.handle_toolkit_action(crate::ui::panels::ToolkitAction::Screenshot),
+            ShortcutAction::StartScrcpy => self.start_scrcpy(),
+            ShortcutAction::OpenSettings => self.settings_window.open(),
+            ShortcutAction::ShowHelp => self.help_window.toggle(),
+            ShortcutAction::ShowShortcuts => self.shortcuts_window.toggle(),
+            ShortcutAction::CommandPalette => self.command_palette.toggle(),
+        }
+    }
 
-                // Max dimensions from settings (adjustable)
-                ui.horizontal(|ui| {
-                    let mut dim_val = config.dimension.unwrap_or(0);
-                    ui.label("Max dimensions:");
-                    if ui.add(egui::DragValue::new(&mut dim_val).range(0..=8192).speed(10)).changed() {
-                        if dim_val == 0 {
-                            config.dimension = None;
-                        } else {
-                            config.dimension = Some(dim_val);
-                        }
-                    }
-                    if ui.button("Unlimited").clicked() {
-                        config.dimension = None;
-                    }
-                    if let Some(dim) = config.dimension {
-                        ui.label(format!("({} px)", dim));
-                    } else {
-                        ui.label("(unlimited)");
-                    }
-                });
+    /// Everything the command palette offers right now
+    fn palette_commands(&self) -> Vec<(PaletteEntry, PaletteCommand)> {
+        use crate::ui::panels::ToolkitAction;
+        let entry = |category: String, label: String, detail: Option<String>| PaletteEntry {
+            category,
+            label,
+            detail,
+        };
+        let binding = |action: ShortcutAction| self.shortcuts.binding(action).map(|shortcut| crate::shortcuts::shortcut_to_string(&shortcut));
+        let mut commands = Vec::new();
+
+        let app = t!("palette-category-app");
+        for action in ShortcutAction::ALL {
+            if matches!(action, ShortcutAction::Screenshot | ShortcutAction::CommandPalette) {
+                continue;
             }
-            // --- End config lock scope ---
+            commands.push((entry(app.clone(), action.label(), binding(action)), PaletteCommand::Shortcut(action)));
+        }
+        if self.scrcpy_running {
+            commands.push((entry(app.clone(), t!("palette-stop-scrcpy"), None), PaletteCommand::StopScrcpy));
+        }
+        for action in [
+            BottomPanelAction::RestartAdb,
+            BottomPanelAction::OpenGallery,
+            BottomPanelAction::OpenRecordings,
+            BottomPanelAction::ToggleLogs,
+        ] {
+            let label = match action {
+                BottomPanelAction::RestartAdb => t!("palette-restart-adb"),
+                BottomPanelAction::OpenGallery => t!("palette-open-gallery"),
+                BottomPanelAction::OpenRecordings => t!("palette-open-recordings"),
+                _ => t!("palette-toggle-logs"),
+            };
+            commands.push((entry(app.clone(), label, None), PaletteCommand::QuickAction(action)));
+        }
+        commands.push((entry(app.clone(), t!("palette-toggle-theme"), None), PaletteCommand::ToggleTheme));
+        commands.push((entry(app.clone(), t!("tunnels-title"), None), PaletteCommand::OpenTunnels));
+        commands.push((entry(app, t!("palette-about"), None), PaletteCommand::About));
 
-            if start_scrcpy {
-                self.start_scrcpy();
+        let toolkit = t!("dock-panel-toolkit");
+        for action in ToolkitAction::ALL {
+            // Confirmations are drawn by the toolkit panel
+            if action.needs_confirmation() && !self.toolkit_panel.visible {
+                continue;
             }
-            if stop_scrcpy {
-                self.stop_scrcpy();
+            let detail = (action == ToolkitAction::Screenshot).then(|| binding(ShortcutAction::Screenshot)).flatten();
+            commands.push((entry(toolkit.clone(), action.label(), detail), PaletteCommand::Toolkit(action)));
+        }
+
+        let panels = t!("palette-category-panels");
+        let toggle = |visible: bool, name: String| {
+            if visible {
+                t!("palette-hide-panel", panel = name)
+            } else {
+                t!("palette-show-panel", panel = name)
             }
-        });
+        };
+        commands.push((
+            entry(panels.clone(), toggle(self.swipe_panel.visible, t!("settings-panel-swipe")), None),
+            PaletteCommand::ToggleSwipePanel,
+        ));
+        commands.push((
+            entry(panels.clone(), toggle(self.toolkit_panel.visible, DockPanel::Toolkit.label()), None),
+            PaletteCommand::TogglePanel(DockPanel::Toolkit),
+        ));
+        commands.push((
+            entry(panels.clone(), toggle(self.bottom_panel.visible, DockPanel::QuickActions.label()), None),
+            PaletteCommand::TogglePanel(DockPanel::QuickActions),
+        ));
+        commands.push((entry(panels, t!("dock-reset"), None), PaletteCommand::ResetLayout));
 
-        if let Ok(config) = self.config.try_lock() {
-            if config.panels.swipe {
-                ui.separator();
-                if let Some(swipe_action) = self.swipe_panel.show(ui) {
-                    if let (Some(adb_bridge), Some(device)) = (self.adb_bridge.as_ref(), self.device_list.selected_device()) {
-                        // Get screen size
-                        let output = std::process::Command::new(adb_bridge.path())
-                            .args(["-s", &device.identifier, "shell", "wm size"])
-                            .output();
-                        if let Ok(output) = output {
-                            if output.status.success() {
-                                let out = String::from_utf8_lossy(&output.stdout);
-                                if let Some(size_str) = out.split_whitespace().find(|s| s.contains('x')) {
-                                    let parts: Vec<&str> = size_str.split('x').collect();
-                                    if parts.len() == 2 {
-                                        if let (Ok(width), Ok(height)) = (parts[0].parse::<i32>(), parts[1].parse::<i32>()) {
-                                            // Calculate swipe coordinates
-                                            let (x1, y1, x2, y2) = match swipe_action {
-                                                crate::ui::panels::SwipeAction::Up => (width/2, (height*4)/5, width/2, height/5),
-                                                crate::ui::panels::SwipeAction::Down => (width/2, height/5, width/2, (height*4)/5),
-                                                crate::ui::panels::SwipeAction::Left => ((width*4)/5, height/2, width/5, height/2),
-                                                crate::ui::panels::SwipeAction::Right => (width/5, height/2, (width*4)/5, height/2),
-                                            };
-                                            let swipe_cmd = format!("input swipe {} {} {} {} 300", x1, y1, x2, y2);
-                                            let swipe_out = std::process::Command::new(adb_bridge.path())
-                                                .args(["-s", &device.identifier, "shell", &swipe_cmd])
-                                                .output();
-                                            if let Ok(swipe_out) = swipe_out {
-                                                if swipe_out.status.success() {
-                                                    self.status_message = "Swipe sent successfully".to_string();
-                                                } else {
-                                                    self.status_message = "Swipe command failed".to_string();
-                                                }
-                                            } else {
-                                                self.status_message = "Failed to send swipe command".to_string();
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    } else {
-                        self.status_message = "No device selected or ADB not configured".to_string();
+        let devices = t!("dock-panel-devices");
+        let selected = self.device_list.selected_device().map(|device| device.identifier.clone());
+        for device in self.device_list.devices() {
+            let detail = (selected.as_ref() == Some(&device.identifier)).then(|| t!("palette-selected"));
+            commands.push((
+                entry(
+                    devices.clone(),
+                    t!("palette-select-device", device = format!("{} ({})", self.device_list.display_name(device), device.identifier)),
+                    detail,
+                ),
+                PaletteCommand::SelectDevice(device.identifier.clone()),
+            ));
+        }
+        commands
+    }
+
+    fn run_palette_command(&mut self, ctx: &egui::Context, command: PaletteCommand) {
+        match command {
+            PaletteCommand::Shortcut(action) => self.run_shortcut(action),
+            PaletteCommand::Toolkit(action) if action.needs_confirmation() => self.toolkit_panel.confirm(action),
+            PaletteCommand::Toolkit(action) => self.handle_toolkit_action(action),
+            PaletteCommand::QuickAction(action) => self.handle_bottom_panel_action(action),
+            PaletteCommand::StopScrcpy => self.stop_scrcpy(),
+            PaletteCommand::ToggleSwipePanel | PaletteCommand::TogglePanel(_) => {
+                if let Ok(mut config) = self.config.try_lock() {
+                    match command {
+                        PaletteCommand::TogglePanel(DockPanel::Toolkit) => config.panels.toolkit = !config.panels.toolkit,
+                        PaletteCommand::TogglePanel(_) => config.panels.bottom = !config.panels.bottom,
+                        _ => config.panels.swipe = !config.panels.swipe,
                     }
+                    if let Err(e) = config.save() {
+                        error!("Failed to save panel visibility: {}", e);
+                    }
+                }
+                self.apply_panel_visibility_from_config();
+            }
+            PaletteCommand::ResetLayout => self.reset_layout(),
+            PaletteCommand::ToggleTheme => self.toggle_theme(ctx),
+            PaletteCommand::OpenTunnels => self.open_tunnels_window(),
+            PaletteCommand::About => self.about_dialog = true,
+            PaletteCommand::SelectDevice(identifier) => {
+                self.device_list.select(&identifier);
+            }
+        }
+    }
+
+    /// Store a binding from the shortcuts window and use it right away
+    fn rebind_shortcut(&mut self, action: ShortcutsAction) {
+        {
+            let Ok(mut config) = self.config.try_lock() else {
+                return;
+            };
+            match action {
+                ShortcutsAction::Set(shortcut, binding) => {
+                    config.shortcuts.insert(shortcut.id().to_string(), binding);
                 }
+                ShortcutsAction::Reset(shortcut) => {
+                    config.shortcuts.remove(shortcut.id());
+                }
+                ShortcutsAction::None => return,
+            }
+            if let Err(e) = config.save() {
+                self.toasts.error(t!("shortcuts-save-failed", error = e));
             }
         }
+        self.apply_shortcuts();
+    }
+
+    /// Start scrcpy with a session template, switching to its device first
+    fn launch_template(&mut self, name: &str) {
+        let template = self.config.try_lock().ok().and_then(|config| {
+            config
+                .session_templates
+                .iter()
+                .find(|template| template.name == name)
+                .cloned()
+                .or_else(|| (name == REMOTE_ASSISTANCE).then(SessionTemplate::remote_assistance))
+        });
+        let Some(template) = template else {
+            self.toasts.warning(t!("template-not-found", name = name));
+            return;
+        };
+        let device = template.device.trim();
+        if !device.is_empty() && !self.device_list.select(device) {
+            self.toasts.warning(t!("template-device-missing", template = template.name, device = device));
+            return;
+        }
+        info!("Launching session template '{}'", template.name);
+        self.start_scrcpy_with(Some(&template));
     }
 
     fn start_scrcpy(&mut self) {
+        self.start_scrcpy_with(None);
+    }
+
+    fn start_scrcpy_with(&mut self, template: Option<&SessionTemplate>) {
         if self.debug_disable_scrcpy {
-            self.status_message = "Scrcpy is disabled in debug mode".to_string();
+            self.toasts.warning(t!("scrcpy-debug-mode"));
             return;
         }
 
         if let (Some(scrcpy_bridge), Some(device)) =
             (&self.scrcpy_bridge, self.device_list.selected_device())
         {
-            let config = self.config.try_lock().unwrap();
+            let config = match template {
+                Some(template) => template.apply(&self.config.try_lock().unwrap()),
+                None => self.config.try_lock().unwrap().clone(),
+            };
 
             // Log configuration details
             info!("Starting scrcpy with configuration:");
             info!("  Device: {} ({})", device.model, device.identifier);
             info!("  Bitrate: {}", config.bitrate);
+            info!("  Video codec: {}", config.video_codec);
             info!("  Orientation: {:?}", config.orientation);
             info!("  Show touches: {}", config.show_touches);
             info!("  Display force on: {}", config.turn_screen_off);
             info!("  Fullscreen: {}", config.fullscreen);
             info!("  Dimension: {:?}", config.dimension);
+            info!("  Display id: {:?}", config.display_ids.get(&device.identifier));
+            info!("  Shortcut modifiers: {:?}", config.shortcut_mods);
             info!("  Extra args: '{}'", config.extra_args);
 
-            let args = scrcpy_bridge.build_args(
-                Some(&device.identifier),
-                &config.bitrate,
-                config.orientation.clone(),
-                config.show_touches,
-                config.fullscreen,
-                config.dimension,
-                &config.extra_args,
-                config.turn_screen_off,
-                config.force_adb_forward,
-            );
+            let mut args = scrcpy_bridge.build_args(Some(&device.identifier), &ScrcpyOptions::for_device(&config, &device.identifier));
+            let legacy_audio = config
+                .legacy_audio
+                .then(|| (device.identifier.clone(), device.details.api_level, config.sndcpy_apk.clone()));
+            let mut started = false;
 
             info!("Built scrcpy arguments: {:?}", args);
             info!("Scrcpy path: {}", scrcpy_bridge.path());
 
             match scrcpy_bridge.start(&args) {
                 Ok(_child) => {
+                    started = true;
                     info!("Scrcpy started successfully");
-                    self.status_message = "Scrcpy started".to_string();
+                    self.toasts.success(match template {
+                        Some(template) => t!("scrcpy-started-template", template = template.name),
+                        None => t!("scrcpy-started"),
+                    });
+                }
+                // Audio capture is unreliable before Android 11: retry once with video only
+                Err(e)
+                    if crate::bridge::is_audio_failure(&format!("{:#}", e))
+                        && !args.iter().any(|a| a == "--no-audio") =>
+                {
+                    tracing::warn!("Scrcpy failed while capturing audio, retrying with --no-audio: {:#}", e);
+                    args.push("--no-audio".to_string());
+                    match scrcpy_bridge.start(&args) {
+                        Ok(_child) => {
+                            started = true;
+                            self.toasts.warning(t!("scrcpy-started-without-audio"));
+                        }
+                        Err(e) => {
+                            error!("Failed to start scrcpy without audio: {}", e);
+                            self.toasts.error(t!("scrcpy-start-failed-without-audio", error = e));
+                            self.log_console.visible = true;
+                        }
+                    }
                 }
                 Err(e) => {
                     error!("Failed to start scrcpy: {}", e);
-                    self.status_message = format!("Failed to start scrcpy: {}", e);
+                    self.toasts.error(t!("scrcpy-start-failed", error = e));
+                    // scrcpy's own output is in the log
+                    self.log_console.visible = true;
                 }
             }
+            if started && let Some((device_id, api_level, apk)) = legacy_audio {
+                self.start_legacy_audio(device_id, api_level, apk);
+            }
         } else {
-            self.status_message = "No device selected or scrcpy not configured".to_string();
+            self.toasts.warning(t!("no-device-scrcpy"));
+        }
+    }
+
+    /// Forward audio with sndcpy when scrcpy cannot capture it from this device
+    fn start_legacy_audio(&mut self, device_id: String, api_level: Option<u32>, apk: Option<String>) {
+        if self.task_handles.contains_key("sndcpy") {
+            return;
+        }
+        if let Some(reason) = crate::sndcpy::unsupported_reason(api_level) {
+            self.toasts.warning(reason);
+            return;
+        }
+        let (Some(adb_bridge), Some(scrcpy_bridge)) = (&self.adb_bridge, &self.scrcpy_bridge) else {
+            return;
+        };
+        let adb_path = adb_bridge.path().to_string();
+        let scrcpy_path = scrcpy_bridge.path().to_string();
+        let Some(player) = crate::sndcpy::find_player() else {
+            self.toasts.error(t!("sndcpy-no-player"));
+            return;
+        };
+        // One device at a time, as the forwarded port is fixed
+        self.stop_legacy_audio();
+        let apk = crate::sndcpy::locate_apk(apk.as_deref());
+        self.run_background_task("sndcpy".to_string(), move || {
+            let scrcpy_major = crate::bridge::check_tool_version(&scrcpy_path, false)
+                .ok()
+                .and_then(|version| crate::bridge::major_version(&version.version));
+            if !crate::sndcpy::needed(api_level, scrcpy_major) {
+                return SndcpyResult(device_id, Ok(None));
+            }
+            let session = crate::sndcpy::AudioSession::start(&adb_path, &device_id, apk.as_deref(), &player);
+            SndcpyResult(device_id, session.map(Some).map_err(|e| e.to_string()))
+        });
+    }
+
+    /// Mirror the selected device inside DroidView, replacing any preview already running
+    fn start_native_preview(&mut self) {
+        if self.task_handles.contains_key("native_preview") {
+            return;
+        }
+        let (Some(adb_bridge), Some(scrcpy_bridge), Some(device)) =
+            (&self.adb_bridge, &self.scrcpy_bridge, self.device_list.selected_device())
+        else {
+            self.toasts.warning(t!("no-device-scrcpy"));
+            return;
+        };
+        let (adb_path, scrcpy_path) = (adb_bridge.path().to_string(), scrcpy_bridge.path().to_string());
+        let device_id = device.identifier.clone();
+        let Some(server) = crate::native_preview::locate_server(&scrcpy_path) else {
+            self.toasts.error(t!("native-preview-no-server", file = crate::native_preview::SERVER_FILE));
+            return;
+        };
+        let ffmpeg = self.config.try_lock().ok().and_then(|config| crate::stream::resolve_ffmpeg(&config.stream));
+        let Some(ffmpeg) = ffmpeg else {
+            self.toasts.error(t!("native-preview-no-ffmpeg"));
+            return;
+        };
+        self.stop_native_preview();
+        self.native_preview_window.set_starting(device_id.clone());
+        self.run_background_task("native_preview".to_string(), move || {
+            use crate::native_preview::PreviewSession;
+            let result = crate::bridge::check_tool_version(&scrcpy_path, false)
+                .map_err(anyhow::Error::msg)
+                .and_then(|version| PreviewSession::start(&adb_path, &device_id, &server, &version.version, &ffmpeg));
+            NativePreviewResult(result.map_err(|e| e.to_string()))
+        });
+    }
+
+    /// Run an interactive shell on the selected device in the shell console
+    fn start_shell_console(&mut self) {
+        let (Some(adb_bridge), Some(device)) = (&self.adb_bridge, self.device_list.selected_device()) else {
+            self.toasts.warning(t!("no-device-adb"));
+            return;
+        };
+        let device_id = device.identifier.clone();
+        let session = crate::shell_console::ShellSession::start(adb_bridge.path(), &device_id);
+        self.stop_shell_console();
+        match session {
+            Ok(session) => {
+                self.shell_console.open(device_id);
+                self.shell_session = Some(session);
+            }
+            Err(e) => self.toasts.error(t!("shell-console-failed", error = e.to_string())),
+        }
+    }
+
+    /// Run a command from the shell command box on the selected device and remember it in its history
+    fn run_shell_command(&mut self, command: String) {
+        let (Some(adb_bridge), Some(device)) = (&self.adb_bridge, self.device_list.selected_device()) else {
+            self.shell_command.set_output(Err(t!("no-device-adb")));
+            return;
+        };
+        let adb_path = adb_bridge.path().to_string();
+        let device_id = device.identifier.clone();
+        if let Ok(mut config) = self.config.try_lock() {
+            config.remember_shell_command(&device_id, &command);
+            let _ = config.save();
+        }
+        // One command at a time; the one not run stays in the history to run afterwards
+        if self.task_handles.contains_key("shell_command") {
+            self.toasts.warning(t!("shell-command-busy"));
+            return;
+        }
+        self.shell_command.set_running(command.clone());
+        self.run_background_task("shell_command".to_string(), move || {
+            ShellCommandResult(crate::shell_console::run(&adb_path, &device_id, &command).map_err(|e| e.to_string()))
+        });
+    }
+
+    /// Save a snippet for the shell command box, replacing one with the same name
+    fn save_shell_snippet(&mut self, snippet: ShellSnippet) {
+        if let Ok(mut config) = self.config.try_lock() {
+            match config.shell_snippets.iter_mut().find(|existing| existing.name == snippet.name) {
+                Some(existing) => *existing = snippet,
+                None => config.shell_snippets.push(snippet),
+            }
+            if let Err(e) = config.save() {
+                self.toasts.error(t!("snippet-save-failed", error = e));
+            }
+        }
+    }
+
+    fn delete_shell_snippet(&mut self, name: &str) {
+        if let Ok(mut config) = self.config.try_lock() {
+            config.shell_snippets.retain(|snippet| snippet.name != name);
+            if let Err(e) = config.save() {
+                self.toasts.error(t!("snippets-save-failed", error = e));
+            }
+        }
+    }
+
+    fn stop_shell_console(&mut self) {
+        if let Some(session) = self.shell_session.take() {
+            session.stop();
+        }
+    }
+
+    fn stop_native_preview(&mut self) {
+        if let Some(session) = self.native_preview.take() {
+            session.stop();
+        }
+        self.native_preview_window.close();
+    }
+
+    fn stop_legacy_audio(&mut self) {
+        if let Some(session) = self.audio_session.take() {
+            session.stop(self.adb_bridge.as_ref().map(|bridge| bridge.path()));
         }
     }
 
@@ -534,91 +4626,217 @@ impl DroidViewApp {
         {
             let _ = Command::new("pkill").arg("scrcpy").output();
         }
+        self.stop_legacy_audio();
 
-        self.status_message = "Scrcpy stopped".to_string();
+        self.toasts.info(t!("scrcpy-stopped"));
     }
 
     fn handle_wireless_adb_action(&mut self, action: crate::ui::panels::WirelessAdbAction) {
         use crate::ui::panels::WirelessAdbAction;
 
+        if matches!(action, WirelessAdbAction::OpenTunnels) {
+            self.open_tunnels_window();
+            return;
+        }
         if let Some(adb_bridge) = &self.adb_bridge {
             match action {
                 WirelessAdbAction::Connect { ip, port } => match adb_bridge.connect(&ip, port) {
-                    Ok(()) => {
+                    Ok(outcome) => {
                         info!("Successfully connected to {}:{}", ip, port);
-                        self.status_message = format!("Connected to {}:{}", ip, port);
+                        match outcome {
+                            ConnectOutcome::Connected(endpoint) => self.toasts.success(t!("wireless-connected", endpoint = endpoint)),
+                            ConnectOutcome::AlreadyConnected(endpoint) => {
+                                self.toasts.info(t!("wireless-already-connected", endpoint = endpoint))
+                            }
+                        }
+                        if let Ok(mut config) = self.config.try_lock() {
+                            config.remember_wireless_endpoint(&ip, port);
+                            let _ = config.save();
+                        }
                         self.refresh_devices();
                     }
                     Err(e) => {
                         error!("Failed to connect to {}:{}: {}", ip, port, e);
-                        self.status_message = format!("Connection failed: {}", e);
+                        self.toasts.error(t!("wireless-connect-failed", error = e));
                     }
                 },
                 WirelessAdbAction::EnableTcpip { device_id, port } => {
                     match adb_bridge.tcpip(port, Some(&device_id)) {
                         Ok(()) => {
                             info!("Enabled TCP/IP on device {}:{}", device_id, port);
-                            self.status_message =
-                                format!("TCP/IP enabled on {}:{}", device_id, port);
+                            self.toasts.success(t!("wireless-tcpip-enabled", device = device_id, port = port));
                         }
                         Err(e) => {
                             error!(
                                 "Failed to enable TCP/IP on device {}:{}: {}",
                                 device_id, port, e
                             );
-                            self.status_message = format!("TCP/IP enable failed: {}", e);
+                            self.toasts.error(t!("wireless-tcpip-failed", error = e));
                         }
                     }
                 }
+                WirelessAdbAction::OpenTunnels => {}
                 WirelessAdbAction::Pair { ip, port, code } => {
                     match adb_bridge.pair(&ip, port, &code) {
-                        Ok(()) => {
-                            info!("Successfully paired with {}:{}", ip, port);
-                            self.status_message = format!("Paired with {}:{}", ip, port);
+                        Ok(outcome) => {
+                            info!("Successfully paired with {} ({:?})", outcome.endpoint, outcome.guid);
+                            self.toasts.success(t!("wireless-paired", endpoint = outcome.endpoint));
+                            if let Ok(mut config) = self.config.try_lock() {
+                                config.mark_wireless_paired(&ip);
+                                let _ = config.save();
+                            }
                             self.refresh_devices();
                         }
                         Err(e) => {
                             error!("Failed to pair with {}:{}: {}", ip, port, e);
-                            self.status_message = format!("Pairing failed: {}", e);
+                            self.toasts.error(t!("wireless-pair-failed", error = e));
                         }
                     }
                 }
             }
         } else {
-            self.status_message = "ADB not configured".to_string();
+            self.toasts.warning(t!("adb-not-configured"));
         }
     }
 
     fn handle_toolkit_action(&mut self, action: crate::ui::panels::ToolkitAction) {
         use crate::ui::panels::ToolkitAction;
+        if action == ToolkitAction::BootloaderLock {
+            self.open_bootloader_wizard();
+            return;
+        }
+        if action == ToolkitAction::Sideload {
+            let device = self.device_list.selected_device().map(|device| device.identifier.clone());
+            self.sideload_window.open(device);
+            return;
+        }
+        if action == ToolkitAction::Automation {
+            let rules = self.automation_rules();
+            self.automation_window.open(rules);
+            return;
+        }
+        if action == ToolkitAction::Macros {
+            let macros = self.config.try_lock().map(|config| config.macros.clone()).unwrap_or_default();
+            let device = self.device_list.selected_device().map(|device| device.identifier.clone());
+            self.macro_window.open(macros, device);
+            return;
+        }
+        if action == ToolkitAction::TapPicker {
+            let Some(device) = self.device_list.selected_device() else {
+                self.toasts.warning(t!("no-device-adb"));
+                return;
+            };
+            self.tap_picker.open(device.identifier.clone());
+            self.capture_tap_picker(std::time::Duration::ZERO);
+            return;
+        }
+        if action == ToolkitAction::AdbKeys {
+            let device = self.device_list.selected_device().map(|device| device.identifier.clone());
+            self.open_adb_keys(device);
+            return;
+        }
         if let (Some(adb_bridge), Some(device)) =
             (&self.adb_bridge, self.device_list.selected_device())
         {
             match action {
-                ToolkitAction::Screenshot => {
-                    // Save screenshot to desktop with timestamp
-                    let desktop = dirs::desktop_dir().unwrap_or_default();
-                    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
-                    let file_path = desktop.join(format!("screenshot_{}.png", timestamp));
-                    let status = std::process::Command::new(adb_bridge.path())
+                ToolkitAction::AdbKeys
+                | ToolkitAction::Automation
+                | ToolkitAction::Macros
+                | ToolkitAction::TapPicker
+                | ToolkitAction::BootloaderLock
+                | ToolkitAction::Sideload => {}
+                ToolkitAction::Screenshot | ToolkitAction::CopyScreenshot => {
+                    let copy_only = matches!(action, ToolkitAction::CopyScreenshot);
+                    let (dir, template, also_copy) = match self.config.try_lock() {
+                        Ok(config) => (
+                            config.screenshot_dir(),
+                            config.screenshot_template.clone(),
+                            config.screenshot_to_clipboard,
+                        ),
+                        Err(_) => (dirs::desktop_dir().unwrap_or_default(), "screenshot_{timestamp}".to_string(), false),
+                    };
+                    let capture = crate::bridge::adb_command(adb_bridge.path())
                         .args(["-s", &device.identifier, "exec-out", "screencap", "-p"])
-                        .stdout(std::fs::File::create(&file_path).unwrap())
-                        .status();
-                    match status {
-                        Ok(s) if s.success() => {
-                            self.screenshot_success_dialog = Some(format!("Screenshot saved to {}", file_path.display()));
+                        .output();
+                    match capture {
+                        Ok(output) if output.status.success() && !output.stdout.is_empty() => {
+                            let png = output.stdout;
+                            if copy_only || also_copy {
+                                match crate::utils::copy_png_to_clipboard(&mut self.clipboard, &png) {
+                                    Ok(()) => self.toasts.success(t!("screenshot-copied")),
+                                    Err(e) => self.toasts.error(t!("screenshot-copy-failed", error = e)),
+                                }
+                            }
+                            if !copy_only {
+                                // Save to the configured folder, never overwriting an earlier capture
+                                let file_name = crate::utils::expand_filename_template(
+                                    &template,
+                                    &device.identifier,
+                                    &device.model,
+                                    chrono::Local::now(),
+                                    "png",
+                                );
+                                let file_path = crate::utils::unique_path(dir.join(file_name));
+                                match std::fs::create_dir_all(&dir).and_then(|_| std::fs::write(&file_path, &png)) {
+                                    Ok(()) => {
+                                        crate::gallery::record_capture(&file_path, crate::gallery::CaptureKind::Screenshot, &device.identifier, None);
+                                        self.screenshot_success_dialog = Some(file_path);
+                                    }
+                                    Err(e) => self.toasts.error(t!("screenshot-error", error = e)),
+                                }
+                            }
                         }
-                        Ok(s) => {
-                            self.status_message = format!("Screenshot failed: exit code {}", s);
+                        Ok(output) => {
+                            self.toasts.error(t!("screenshot-failed-status", status = output.status));
                         }
                         Err(e) => {
-                            self.status_message = format!("Screenshot error: {}", e);
+                            self.toasts.error(t!("screenshot-error", error = e));
                         }
                     }
                 }
+                ToolkitAction::LongScreenshot => {
+                    if !self.task_handles.contains_key("long_screenshot") {
+                        let (dir, template) = match self.config.try_lock() {
+                            Ok(config) => (config.screenshot_dir(), config.screenshot_template.clone()),
+                            Err(_) => (dirs::desktop_dir().unwrap_or_default(), "screenshot_{timestamp}".to_string()),
+                        };
+                        let adb_path = adb_bridge.path().to_string();
+                        let (device_id, model) = (device.identifier.clone(), device.model.clone());
+                        self.toasts.info(t!("long-screenshot-started"));
+                        self.run_background_task("long_screenshot".to_string(), move || {
+                            let result = crate::stitch::long_screenshot(&adb_path, &device_id, &model, &dir, &template);
+                            LongScreenshotResult(result.map_err(|e| e.to_string()))
+                        });
+                    }
+                }
                 ToolkitAction::RecordScreen => {
-                    // Show screen recording dialog
-                    self.screenrecord_dialog = true;
+                    if self.recording.is_some() {
+                        self.toasts.warning(t!("screenrecord-already-running"));
+                    } else {
+                        // Show screen recording dialog
+                        self.screenrecord_dialog = true;
+                    }
+                }
+                ToolkitAction::SendIntent => {
+                    self.intent_sender.open();
+                }
+                ToolkitAction::ShareView => {
+                    self.share_window.open();
+                }
+                ToolkitAction::Activities => {
+                    self.activity_viewer.open();
+                    self.load_activity_stack();
+                }
+                ToolkitAction::ClipboardHistory => {
+                    self.clipboard_history.open();
+                }
+                ToolkitAction::Benchmark => {
+                    let codecs = self.video_codecs.get(&device.identifier).cloned().unwrap_or_default();
+                    self.benchmark_window.open(crate::benchmark::default_cases(&codecs));
+                }
+                ToolkitAction::Capabilities => {
+                    self.capability_wizard.open(None);
+                    self.probe_capabilities();
                 }
                 ToolkitAction::InstallApk => {
                     // Open file picker (native dialog)
@@ -626,18 +4844,21 @@ impl DroidViewApp {
                         .add_filter("APK", &["apk"])
                         .pick_file()
                     {
-                        let status = std::process::Command::new(adb_bridge.path())
-                            .args(["-s", &device.identifier, "install", path.to_str().unwrap()])
-                            .status();
+                        let mut install = crate::bridge::adb_command(adb_bridge.path());
+                        install.args(["-s", &device.identifier, "install"]);
+                        if let Some(user) = self.package_user() {
+                            install.args(["--user", &user.to_string()]);
+                        }
+                        let status = install.arg(path.to_str().unwrap()).status();
                         match status {
                             Ok(s) if s.success() => {
-                                self.status_message = format!("Installed APK: {}", path.display());
+                                self.toasts.success(t!("apk-installed", path = path.display()));
                             }
                             Ok(s) => {
-                                self.status_message = format!("Install failed: exit code {}", s);
+                                self.toasts.error(t!("apk-install-failed-status", status = s));
                             }
                             Err(e) => {
-                                self.status_message = format!("Install error: {}", e);
+                                self.toasts.error(t!("apk-install-error", error = e));
                             }
                         }
                     }
@@ -669,8 +4890,10 @@ impl DroidViewApp {
                             .spawn();
                     }
 
+                    #[cfg(not(target_os = "linux"))]
+                    let opened = true;
                     #[cfg(target_os = "linux")]
-                    {
+                    let opened = {
                         // Try different terminal emulators on Linux
                         let terminals: &[(&str, &[&str])] = &[
                             ("gnome-terminal", &["--", "bash", "-c", &format!("{} -s {} shell; exec bash", adb_path, device_id)]),
@@ -690,15 +4913,57 @@ impl DroidViewApp {
 
                         if !opened {
                             // Fallback: try to open default terminal
-                            let _ = std::process::Command::new("x-terminal-emulator")
+                            opened = std::process::Command::new("x-terminal-emulator")
                                 .arg("-e")
                                 .arg(format!("{} -s {} shell", adb_path, device_id))
-                                .spawn();
+                                .spawn()
+                                .is_ok();
                         }
-                    }
+                        opened
+                    };
 
-                    self.status_message = "Opened ADB shell in terminal".to_string();
+                    if opened {
+                        self.toasts.info(t!("shell-opened"));
+                    } else {
+                        self.toasts.info(t!("shell-console-no-terminal"));
+                        self.start_shell_console();
+                    }
+                }
+                ToolkitAction::ShellConsole => self.start_shell_console(),
+                ToolkitAction::ShellCommand => self.shell_command.open(),
+                ToolkitAction::Bugreport => self.start_bugreport(),
+                ToolkitAction::PerfettoTrace => self.perfetto_window.open(),
+                ToolkitAction::FrameTiming => self.frame_profiler.open(),
+                ToolkitAction::WifiInfo => self.load_wifi_info(),
+                ToolkitAction::DeviceReport => self.export_device_report(),
+                ToolkitAction::Resolution => self.change_resolution(None, None),
+                ToolkitAction::Locale => {
+                    self.change_locale(LocaleAction::Refresh);
+                    self.check_clock(false);
+                }
+                ToolkitAction::Standby => self.change_standby(StandbyAction::Refresh(None)),
+                ToolkitAction::MockLocation => self.change_mock_location(MockLocationAction::Refresh),
+                ToolkitAction::Ocr => {
+                    if !self.task_handles.contains_key("ocr") {
+                        let adb_path = adb_bridge.path().to_string();
+                        let device_id = device.identifier.clone();
+                        self.run_background_task("ocr".to_string(), move || {
+                            OcrResult(crate::ocr::extract(&adb_path, &device_id).map_err(|e| e.to_string()))
+                        });
+                    }
                 }
+                ToolkitAction::LayoutInspector => {
+                    self.hierarchy_window.open(device.identifier.clone());
+                    self.capture_hierarchy();
+                }
+                ToolkitAction::ScreenshotDiff => self.screenshot_diff.open(&device.identifier),
+                ToolkitAction::NativePreview => self.start_native_preview(),
+                ToolkitAction::LivePreview => self.pip.toggle(),
+                ToolkitAction::ColorPicker => {
+                    self.color_picker.open(device.identifier.clone());
+                    self.capture_color_picker();
+                }
+                ToolkitAction::ReverseTether => self.toggle_reverse_tether(),
                 ToolkitAction::ShowImei => {
                     // Start async IMEI fetching if not already loading
                     if !self.loading_imei && !self.task_handles.contains_key("imei") {
@@ -713,7 +4978,7 @@ impl DroidViewApp {
                                 let mut imei_result = String::new();
                                 
                                 // Method 1: For Android 10+ (requires READ_PHONE_STATE permission)
-                                let output1 = std::process::Command::new(&adb_path)
+                                let output1 = crate::bridge::adb_command(&adb_path)
                                     .args([
                                         "-s",
                                         &device_id,
@@ -722,17 +4987,16 @@ impl DroidViewApp {
                                     ])
                                     .output();
                                 
-                                if let Ok(output) = output1 {
-                                    if output.status.success() {
-                                        let android_id = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                                        if !android_id.is_empty() {
-                                            imei_result.push_str(&format!("Android ID: {}\n", android_id));
-                                        }
+                                if let Ok(output) = output1
+                                    && output.status.success() {
+                                    let android_id = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                                    if !android_id.is_empty() {
+                                        imei_result.push_str(&format!("{}\n", t!("imei-android-id", value = android_id)));
                                     }
                                 }
                                 
                                 // Method 2: For dual-SIM devices (Android 5+)
-                                let output2 = std::process::Command::new(&adb_path)
+                                let output2 = crate::bridge::adb_command(&adb_path)
                                     .args([
                                         "-s",
                                         &device_id,
@@ -741,17 +5005,16 @@ impl DroidViewApp {
                                     ])
                                     .output();
                                 
-                                if let Ok(output) = output2 {
-                                    if output.status.success() {
-                                        let imei = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                                        if !imei.is_empty() && imei != "0" {
-                                            imei_result.push_str(&format!("IMEI: {}\n", imei));
-                                        }
+                                if let Ok(output) = output2
+                                    && output.status.success() {
+                                    let imei = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                                    if !imei.is_empty() && imei != "0" {
+                                        imei_result.push_str(&format!("{}\n", t!("imei-imei", value = imei)));
                                     }
                                 }
                                 
                                 // Method 3: For dual-SIM devices - IMEI1 and IMEI2
-                                let output3 = std::process::Command::new(&adb_path)
+                                let output3 = crate::bridge::adb_command(&adb_path)
                                     .args([
                                         "-s",
                                         &device_id,
@@ -760,16 +5023,15 @@ impl DroidViewApp {
                                     ])
                                     .output();
                                 
-                                if let Ok(output) = output3 {
-                                    if output.status.success() {
-                                        let imei1 = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                                        if !imei1.is_empty() && imei1 != "0" {
-                                            imei_result.push_str(&format!("IMEI1: {}\n", imei1));
-                                        }
+                                if let Ok(output) = output3
+                                    && output.status.success() {
+                                    let imei1 = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                                    if !imei1.is_empty() && imei1 != "0" {
+                                        imei_result.push_str(&format!("{}\n", t!("imei-imei1", value = imei1)));
                                     }
                                 }
                                 
-                                let output4 = std::process::Command::new(&adb_path)
+                                let output4 = crate::bridge::adb_command(&adb_path)
                                     .args([
                                         "-s",
                                         &device_id,
@@ -778,17 +5040,16 @@ impl DroidViewApp {
                                     ])
                                     .output();
                                 
-                                if let Ok(output) = output4 {
-                                    if output.status.success() {
-                                        let imei2 = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                                        if !imei2.is_empty() && imei2 != "0" {
-                                            imei_result.push_str(&format!("IMEI2: {}\n", imei2));
-                                        }
+                                if let Ok(output) = output4
+                                    && output.status.success() {
+                                    let imei2 = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                                    if !imei2.is_empty() && imei2 != "0" {
+                                        imei_result.push_str(&format!("{}\n", t!("imei-imei2", value = imei2)));
                                     }
                                 }
                                 
                                 // Method 4: Legacy method for older devices (deprecated but might work on some)
-                                let output5 = std::process::Command::new(&adb_path)
+                                let output5 = crate::bridge::adb_command(&adb_path)
                                     .args([
                                         "-s",
                                         &device_id,
@@ -797,17 +5058,16 @@ impl DroidViewApp {
                                     ])
                                     .output();
                                 
-                                if let Ok(output) = output5 {
-                                    if output.status.success() {
-                                        let imei = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                                        if !imei.is_empty() && imei.len() >= 14 {
-                                            imei_result.push_str(&format!("Legacy IMEI: {}\n", imei));
-                                        }
+                                if let Ok(output) = output5
+                                    && output.status.success() {
+                                    let imei = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                                    if !imei.is_empty() && imei.len() >= 14 {
+                                        imei_result.push_str(&format!("{}\n", t!("imei-legacy", value = imei)));
                                     }
                                 }
                                 
                                 // Method 5: Get device serial number as fallback
-                                let output6 = std::process::Command::new(&adb_path)
+                                let output6 = crate::bridge::adb_command(&adb_path)
                                     .args([
                                         "-s",
                                         &device_id,
@@ -816,25 +5076,24 @@ impl DroidViewApp {
                                     ])
                                     .output();
                                 
-                                if let Ok(output) = output6 {
-                                    if output.status.success() {
-                                        let serial = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                                        if !serial.is_empty() {
-                                            imei_result.push_str(&format!("Serial: {}\n", serial));
-                                        }
+                                if let Ok(output) = output6
+                                    && output.status.success() {
+                                    let serial = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                                    if !serial.is_empty() {
+                                        imei_result.push_str(&format!("{}\n", t!("imei-serial", value = serial)));
                                     }
                                 }
                                 
                                 if !imei_result.is_empty() {
                                     ImeiResult(imei_result.trim().to_string())
                                 } else {
-                                    ImeiResult("No IMEI/Device ID information available. This may be due to:\n• Android security restrictions (Android 10+)\n• Missing READ_PHONE_STATE permission\n• Device not supporting IMEI retrieval".to_string())
+                                    ImeiResult(t!("imei-unavailable"))
                                 }
                             });
                             
-                            self.status_message = "Loading IMEI...".to_string();
+                            self.toasts.info(t!("imei-loading"));
                         } else {
-                            self.status_message = "No device selected or ADB not configured".to_string();
+                            self.toasts.warning(t!("no-device-adb"));
                         }
                     }
                 }
@@ -851,7 +5110,7 @@ impl DroidViewApp {
                                 let mut display_info = String::new();
                                 
                                 // Get dumpsys display info
-                                let dumpsys_output = std::process::Command::new(&adb_path)
+                                let dumpsys_output = crate::bridge::adb_command(&adb_path)
                                     .args([
                                         "-s",
                                         &device_id,
@@ -860,16 +5119,15 @@ impl DroidViewApp {
                                     ])
                                     .output();
 
-                                if let Ok(output) = dumpsys_output {
-                                    if output.status.success() {
-                                        display_info.push_str("📱 Display Information:\n");
-                                        display_info.push_str(&String::from_utf8_lossy(&output.stdout));
-                                        display_info.push_str("\n\n");
-                                    }
+                                if let Ok(output) = dumpsys_output
+                                    && output.status.success() {
+                                    display_info.push_str(&format!("{}\n", t!("display-info-display")));
+                                    display_info.push_str(&String::from_utf8_lossy(&output.stdout));
+                                    display_info.push_str("\n\n");
                                 }
 
                                 // Get wm size info
-                                let wm_size_output = std::process::Command::new(&adb_path)
+                                let wm_size_output = crate::bridge::adb_command(&adb_path)
                                     .args([
                                         "-s",
                                         &device_id,
@@ -878,16 +5136,15 @@ impl DroidViewApp {
                                     ])
                                     .output();
 
-                                if let Ok(output) = wm_size_output {
-                                    if output.status.success() {
-                                        display_info.push_str("📐 Window Manager Size:\n");
-                                        display_info.push_str(&String::from_utf8_lossy(&output.stdout));
-                                        display_info.push_str("\n\n");
-                                    }
+                                if let Ok(output) = wm_size_output
+                                    && output.status.success() {
+                                    display_info.push_str(&format!("{}\n", t!("display-info-size")));
+                                    display_info.push_str(&String::from_utf8_lossy(&output.stdout));
+                                    display_info.push_str("\n\n");
                                 }
 
                                 // Get wm density info
-                                let wm_density_output = std::process::Command::new(&adb_path)
+                                let wm_density_output = crate::bridge::adb_command(&adb_path)
                                     .args([
                                         "-s",
                                         &device_id,
@@ -896,23 +5153,22 @@ impl DroidViewApp {
                                     ])
                                     .output();
 
-                                if let Ok(output) = wm_density_output {
-                                    if output.status.success() {
-                                        display_info.push_str("📊 Window Manager Density:\n");
-                                        display_info.push_str(&String::from_utf8_lossy(&output.stdout));
-                                    }
+                                if let Ok(output) = wm_density_output
+                                    && output.status.success() {
+                                    display_info.push_str(&format!("{}\n", t!("display-info-density")));
+                                    display_info.push_str(&String::from_utf8_lossy(&output.stdout));
                                 }
 
                                 if !display_info.is_empty() {
                                     display_info
                                 } else {
-                                    "Failed to retrieve display info".to_string()
+                                    t!("display-info-failed")
                                 }
                             });
                             
-                            self.status_message = "Loading display info...".to_string();
+                            self.toasts.info(t!("display-info-loading"));
                         } else {
-                            self.status_message = "No device selected or ADB not configured".to_string();
+                            self.toasts.warning(t!("no-device-adb"));
                         }
                     }
                 }
@@ -926,7 +5182,7 @@ impl DroidViewApp {
                             
                             // Spawn background task
                             self.run_background_task("battery_info".to_string(), move || {
-                                let output = std::process::Command::new(&adb_path)
+                                let output = crate::bridge::adb_command(&adb_path)
                                     .args([
                                         "-s",
                                         &device_id,
@@ -940,178 +5196,122 @@ impl DroidViewApp {
                                         let output_str = String::from_utf8_lossy(&output.stdout);
                                         BatteryInfoResult(output_str.to_string())
                                     }
-                                    _ => BatteryInfoResult("Failed to retrieve battery info".to_string()),
+                                    _ => BatteryInfoResult(t!("battery-info-failed")),
                                 }
                             });
                             
-                            self.status_message = "Loading battery info...".to_string();
+                            self.toasts.info(t!("battery-info-loading"));
                         } else {
-                            self.status_message = "No device selected or ADB not configured".to_string();
+                            self.toasts.warning(t!("no-device-adb"));
                         }
                     }
                 }
                 ToolkitAction::UninstallApp => {
-                    // Start async app list fetching if not already loading
-                    if !self.loading_apps && !self.task_handles.contains_key("app_list") {
-                        if let (Some(adb_bridge), Some(device)) = (self.adb_bridge.as_ref(), self.device_list.selected_device()) {
-                            self.loading_apps = true;
-                            let adb_path = adb_bridge.path().to_string();
-                            let device_id = device.identifier.clone();
-                            
-                            // Spawn background task
-                            self.run_background_task("app_list".to_string(), move || {
-                                let output = std::process::Command::new(&adb_path)
-                                    .args([
-                                        "-s",
-                                        &device_id,
-                                        "shell",
-                                        "pm list packages -3"
-                                    ])
-                                    .output();
-
-                                match output {
-                                    Ok(output) if output.status.success() => {
-                                        let mut apps = Vec::new();
-                                        for line in String::from_utf8_lossy(&output.stdout).lines() {
-                                            if line.starts_with("package:") {
-                                                let package_name = line.replace("package:", "").trim().to_string();
-                                                apps.push((package_name.clone(), package_name));
-                                            }
-                                        }
-                                        AppListResult(apps)
-                                    }
-                                    _ => AppListResult(Vec::new()),
-                                }
-                            });
-                            
-                            self.status_message = "Loading app list...".to_string();
-                        } else {
-                            self.status_message = "No device selected or ADB not configured".to_string();
-                        }
-                    }
+                    self.load_device_users();
+                    self.load_app_list(false);
                 }
                 ToolkitAction::DisableApp => {
-                    // Start async disable app list fetching if not already loading
-                    if !self.loading_disable_apps && !self.task_handles.contains_key("disable_app_list") {
-                        if let (Some(adb_bridge), Some(device)) = (self.adb_bridge.as_ref(), self.device_list.selected_device()) {
-                            self.loading_disable_apps = true;
-                            let adb_path = adb_bridge.path().to_string();
-                            let device_id = device.identifier.clone();
-                            
-                            // Spawn background task
-                            self.run_background_task("disable_app_list".to_string(), move || {
-                                let output = std::process::Command::new(&adb_path)
-                                    .args([
-                                        "-s",
-                                        &device_id,
-                                        "shell",
-                                        "pm list packages -e"
-                                    ])
-                                    .output();
-
-                                match output {
-                                    Ok(output) if output.status.success() => {
-                                        let mut apps = Vec::new();
-                                        for line in String::from_utf8_lossy(&output.stdout).lines() {
-                                            if line.starts_with("package:") {
-                                                let package_name = line.replace("package:", "").trim().to_string();
-                                                apps.push((package_name.clone(), package_name));
-                                            }
-                                        }
-                                        DisableAppListResult(apps)
-                                    }
-                                    _ => DisableAppListResult(Vec::new()),
-                                }
-                            });
-                            
-                            self.status_message = "Loading app list...".to_string();
-                        } else {
-                            self.status_message = "No device selected or ADB not configured".to_string();
-                        }
-                    }
+                    self.load_device_users();
+                    self.load_app_list(true);
                 }
                 ToolkitAction::Reboot => {
                     if let (Some(adb_bridge), Some(device)) = (self.adb_bridge.as_ref(), self.device_list.selected_device()) {
-                        let status = std::process::Command::new(adb_bridge.path())
+                        let status = crate::bridge::adb_command(adb_bridge.path())
                             .args(["-s", &device.identifier, "reboot"])
                             .status();
                         
                         match status {
                             Ok(s) if s.success() => {
-                                self.status_message = "Device reboot initiated".to_string();
+                                self.toasts.info(t!("reboot-started"));
                             }
                             Ok(s) => {
-                                self.status_message = format!("Reboot failed: exit code {}", s);
+                                self.toasts.error(t!("reboot-failed-status", status = s));
                             }
                             Err(e) => {
-                                self.status_message = format!("Reboot error: {}", e);
+                                self.toasts.error(t!("reboot-error", error = e));
                             }
                         }
                     } else {
-                        self.status_message = "No device selected or ADB not configured".to_string();
+                        self.toasts.warning(t!("no-device-adb"));
                     }
                 }
                 ToolkitAction::Shutdown => {
                     if let (Some(adb_bridge), Some(device)) = (self.adb_bridge.as_ref(), self.device_list.selected_device()) {
-                        let status = std::process::Command::new(adb_bridge.path())
+                        let status = crate::bridge::adb_command(adb_bridge.path())
                             .args(["-s", &device.identifier, "shell", "reboot", "-p"])
                             .status();
                         
                         match status {
                             Ok(s) if s.success() => {
-                                self.status_message = "Device shutdown initiated".to_string();
+                                self.toasts.info(t!("shutdown-started"));
                             }
                             Ok(s) => {
-                                self.status_message = format!("Shutdown failed: exit code {}", s);
+                                self.toasts.error(t!("shutdown-failed-status", status = s));
                             }
                             Err(e) => {
-                                self.status_message = format!("Shutdown error: {}", e);
+                                self.toasts.error(t!("shutdown-error", error = e));
                             }
                         }
                     } else {
-                        self.status_message = "No device selected or ADB not configured".to_string();
+                        self.toasts.warning(t!("no-device-adb"));
                     }
                 }
                 ToolkitAction::RebootRecovery => {
                     if let (Some(adb_bridge), Some(device)) = (self.adb_bridge.as_ref(), self.device_list.selected_device()) {
-                        let status = std::process::Command::new(adb_bridge.path())
+                        let status = crate::bridge::adb_command(adb_bridge.path())
                             .args(["-s", &device.identifier, "reboot", "recovery"])
                             .status();
                         
                         match status {
                             Ok(s) if s.success() => {
-                                self.status_message = "Device rebooting to recovery mode".to_string();
+                                self.toasts.info(t!("reboot-recovery-started"));
                             }
                             Ok(s) => {
-                                self.status_message = format!("Recovery reboot failed: exit code {}", s);
+                                self.toasts.error(t!("reboot-recovery-failed-status", status = s));
                             }
                             Err(e) => {
-                                self.status_message = format!("Recovery reboot error: {}", e);
+                                self.toasts.error(t!("reboot-recovery-error", error = e));
                             }
                         }
                     } else {
-                        self.status_message = "No device selected or ADB not configured".to_string();
+                        self.toasts.warning(t!("no-device-adb"));
                     }
                 }
                 ToolkitAction::RebootBootloader => {
                     if let (Some(adb_bridge), Some(device)) = (self.adb_bridge.as_ref(), self.device_list.selected_device()) {
-                        let status = std::process::Command::new(adb_bridge.path())
+                        let status = crate::bridge::adb_command(adb_bridge.path())
                             .args(["-s", &device.identifier, "reboot", "bootloader"])
                             .status();
                         
                         match status {
                             Ok(s) if s.success() => {
-                                self.status_message = "Device rebooting to bootloader".to_string();
+                                self.toasts.info(t!("reboot-bootloader-started"));
                             }
                             Ok(s) => {
-                                self.status_message = format!("Bootloader reboot failed: exit code {}", s);
+                                self.toasts.error(t!("reboot-bootloader-failed-status", status = s));
                             }
                             Err(e) => {
-                                self.status_message = format!("Bootloader reboot error: {}", e);
+                                self.toasts.error(t!("reboot-bootloader-error", error = e));
+                            }
+                        }
+                    } else {
+                        self.toasts.warning(t!("no-device-adb"));
+                    }
+                }
+                ToolkitAction::RebootTo(target) => {
+                    let device_id = device.identifier.clone();
+                    if self.reboot_targets.get(&device_id).is_some_and(|targets| !targets.contains(&target)) {
+                        self.toasts.warning(format!("{}: {}", target.label(), target.unsupported_reason()));
+                        return;
+                    }
+                    match crate::reboot::reboot(adb_bridge.path(), &device_id, target) {
+                        Ok(()) => {
+                            self.toasts.info(format!("{}…", target.label()));
+                            if target == RebootTarget::Sideload {
+                                self.sideload_window.open(Some(device_id));
                             }
                         }
-                    } else {
-                        self.status_message = "No device selected or ADB not configured".to_string();
+                        Err(e) => self.toasts.error(e.to_string()),
                     }
                 }
                 ToolkitAction::None => {}
@@ -1119,68 +5319,482 @@ impl DroidViewApp {
         } else if let ToolkitAction::None = action {
             // do nothing
         } else {
-            self.status_message = "No device selected or ADB not configured".to_string();
+            self.toasts.warning(t!("no-device-adb"));
         }
     }
 
     fn update_background_tasks(&mut self) {
         // Check for completed tasks
         while let Ok(result) = self.result_receiver.try_recv() {
+            if let Some((task, outcome)) = result.finished_task() {
+                let (ok, error) = (outcome.is_ok(), outcome.err());
+                self.emit_api_event(ApiEvent::TaskFinished { task: task.to_string(), ok, error });
+            }
             match result {
                 BackgroundTaskResult::AppList(apps) => {
                     self.loading_apps = false;
                     self.app_list = apps;
                     self.uninstall_dialog = true;
-                    self.status_message = "App list loaded successfully".to_string();
+                    self.toasts.success(t!("apps-loaded"));
                 }
                 BackgroundTaskResult::DisableAppList(apps) => {
                     self.loading_disable_apps = false;
                     self.disable_app_list = apps;
                     self.disable_dialog = true;
-                    self.status_message = "App list loaded successfully".to_string();
+                    self.toasts.success(t!("apps-loaded"));
+                }
+                BackgroundTaskResult::DeviceUsers(device_id, users) => {
+                    // The lists were loaded for the default user, so start from it on a new device
+                    let same_device = self.device_users.as_ref().is_some_and(|(id, _)| *id == device_id);
+                    if !same_device || !users.iter().any(|user| user.id == self.package_user) {
+                        self.package_user = 0;
+                    }
+                    self.device_users = Some((device_id, users));
                 }
                 BackgroundTaskResult::Imei(imei) => {
                     self.loading_imei = false;
                     self.imei_popup = Some(imei);
-                    self.status_message = "IMEI retrieved successfully".to_string();
+                    self.toasts.success(t!("imei-loaded"));
                 }
                 BackgroundTaskResult::DisplayInfo(info) => {
                     self.loading_display_info = false;
                     self.display_popup = Some(info);
-                    self.status_message = "Display info retrieved successfully".to_string();
+                    self.toasts.success(t!("display-info-loaded"));
                 }
                 BackgroundTaskResult::BatteryInfo(info) => {
                     self.loading_battery_info = false;
                     self.battery_popup = Some(info);
-                    self.status_message = "Battery info retrieved successfully".to_string();
+                    self.toasts.success(t!("battery-info-loaded"));
+                }
+                BackgroundTaskResult::VideoCodecs(device_id, codecs) => {
+                    info!("Video codecs for {}: {:?}", device_id, codecs);
+                    self.video_codecs.insert(device_id, codecs);
+                }
+                BackgroundTaskResult::Displays(device_id, displays) => {
+                    // Only compare against a previous snapshot, so the first poll doesn't prompt
+                    if let Some(previous) = self.displays.get(&device_id)
+                        && let Some(added) = displays
+                            .iter()
+                            .find(|d| !previous.iter().any(|p| p.id == d.id))
+                    {
+                        info!("New display on {}: {} ({})", device_id, added.name, added.id);
+                        self.toasts.info(t!("devices-new-display", name = added.name));
+                        self.new_display_prompt = Some((device_id.clone(), added.clone()));
+                    }
+                    self.displays.insert(device_id, displays);
+                }
+                BackgroundTaskResult::PushProgress(index, status) => {
+                    self.file_push_dialog.set_status(index, status);
+                }
+                BackgroundTaskResult::BenchmarkProgress(index, result) => {
+                    self.benchmark_window.set_progress(index, result);
+                }
+                BackgroundTaskResult::BenchmarkFinished => {
+                    self.benchmark_window.finish();
+                    self.toasts.info(t!("benchmark-finished"));
+                }
+                BackgroundTaskResult::Gfxinfo(package, frames) => match frames {
+                    Ok(frames) => self.frame_profiler.add_frames(&package, frames),
+                    Err(e) => self.frame_profiler.set_error(e),
+                },
+                BackgroundTaskResult::Wifi(info) => self.wifi_window.set_info(info),
+                BackgroundTaskResult::DeviceControls(serial, state) => self.device_controls.set_state(&serial, state),
+                BackgroundTaskResult::Resolution(info) => self.resolution_window.set_info(info),
+                BackgroundTaskResult::Locale(info, failure) => {
+                    self.locale_window.set_info(info);
+                    if let Some(e) = failure {
+                        self.toasts.error(e);
+                    }
+                }
+                BackgroundTaskResult::Standby(info, failure) => {
+                    self.standby_window.set_info(info);
+                    if let Some(e) = failure {
+                        self.toasts.error(e);
+                    }
+                }
+                BackgroundTaskResult::MockLocation(info, failure) => {
+                    self.mock_location_window.set_info(info);
+                    if let Some(e) = failure {
+                        self.toasts.error(e);
+                    }
+                }
+                BackgroundTaskResult::Clock(drift, synced) => {
+                    self.locale_window.set_drift(drift);
+                    match synced {
+                        Some(Ok(crate::clock::SyncOutcome::Set)) => self.toasts.success(t!("locale-clock-synced")),
+                        Some(Ok(crate::clock::SyncOutcome::NetworkTime)) => {
+                            self.toasts.warning(t!("locale-clock-network-time"))
+                        }
+                        Some(Err(e)) => self.toasts.error(t!("locale-clock-failed", error = e)),
+                        None => {}
+                    }
+                }
+                BackgroundTaskResult::Automation(rule, device, result) => match result {
+                    Ok(output) => {
+                        if !output.is_empty() {
+                            info!("Automation '{}' on {}: {}", rule, device, output);
+                        }
+                        self.toasts.info(t!("automation-ran", rule = rule, device = device));
+                    }
+                    Err(e) => self.toasts.error(t!("automation-failed", rule = rule, device = device, error = e)),
+                },
+                BackgroundTaskResult::AutomationBattery(levels) => {
+                    let rules = self.automation_rules();
+                    let firings = self.scheduler.on_battery(&rules, &levels);
+                    self.run_firings(firings);
+                }
+                BackgroundTaskResult::NativePreview(Ok(session)) => {
+                    // Closed while it was starting
+                    if !self.native_preview_window.is_open() {
+                        session.stop();
+                    } else {
+                        self.native_preview_window.set_started(&session.device_name);
+                        self.native_preview = Some(session);
+                    }
+                }
+                BackgroundTaskResult::NativePreview(Err(e)) => {
+                    self.native_preview_window.close();
+                    self.toasts.error(t!("native-preview-failed", error = e));
+                }
+                BackgroundTaskResult::Sndcpy(device, result) => match result {
+                    Ok(Some(session)) => {
+                        self.audio_session = Some(session);
+                        self.toasts.success(t!("sndcpy-started", device = device));
+                    }
+                    Ok(None) => info!("{} has native audio, sndcpy not needed", device),
+                    Err(e) => self.toasts.error(t!("sndcpy-failed", error = e)),
+                },
+                BackgroundTaskResult::Tether(device, enable, relay, result) => {
+                    if relay.is_some() {
+                        self.gnirehtet_relay = relay;
+                    }
+                    match (enable, result) {
+                        (true, Ok(())) => {
+                            self.tethered.push(device.clone());
+                            self.toasts.success(t!("tether-enabled", device = device));
+                        }
+                        (false, Ok(())) => self.toasts.info(t!("tether-disabled", device = device)),
+                        (_, Err(e)) => self.toasts.error(t!("tether-failed", error = e)),
+                    }
+                    if self.tethered.is_empty()
+                        && let Some(relay) = self.gnirehtet_relay.take()
+                    {
+                        relay.stop();
+                    }
+                }
+                BackgroundTaskResult::ForegroundPackage(package) => match package {
+                    Ok(package) => self.frame_profiler.set_package(package),
+                    Err(e) => self.frame_profiler.set_error(e),
+                },
+                BackgroundTaskResult::PerfettoFinished(result) => {
+                    self.perfetto_device = None;
+                    match &result {
+                        Ok(path) => self.toasts.success(t!("perfetto-trace-saved", path = path.display())),
+                        Err(e) => self.toasts.error(t!("perfetto-failed", error = e)),
+                    }
+                    self.perfetto_window.finish(result);
+                }
+                BackgroundTaskResult::BugreportProgress(percent) => self.bugreport_progress = Some(percent),
+                BackgroundTaskResult::BugreportFinished(result) => {
+                    self.bugreport_progress = None;
+                    match result {
+                        Ok(path) => {
+                            self.toasts.success(t!("bugreport-saved", path = path.display()));
+                            let open_folder = self.config.try_lock().map(|config| config.bugreport_open_folder).unwrap_or(false);
+                            if open_folder && let Err(e) = crate::utils::reveal_in_file_manager(&path) {
+                                self.toasts.error(t!("open-folder-failed", error = e));
+                            }
+                        }
+                        Err(e) => self.toasts.error(t!("bugreport-failed", error = e)),
+                    }
+                }
+                BackgroundTaskResult::SideloadProgress(stage) => self.sideload_window.set_stage(stage),
+                BackgroundTaskResult::MacroProgress(step) => self.macro_window.set_step(step),
+                BackgroundTaskResult::MockRouteProgress(point) => self.mock_location_window.set_point(point),
+                BackgroundTaskResult::MockRouteFinished(result) => {
+                    self.mock_location_window.set_playing(None);
+                    match result {
+                        Ok(()) => self.toasts.success(t!("mock-location-route-finished")),
+                        Err(e) => self.toasts.warning(t!("mock-location-route-failed", error = e)),
+                    }
+                }
+                BackgroundTaskResult::MacroFinished(result) => {
+                    let name = self.playing_macro.take().unwrap_or_default();
+                    match &result {
+                        Ok(()) => self.toasts.success(t!("macros-finished", name = name)),
+                        Err(e) => self.toasts.warning(t!("macros-failed", name = name, error = e.clone())),
+                    }
+                    self.macro_window.finish(result);
+                }
+                BackgroundTaskResult::Plugin(label, result) => {
+                    if let Err(e) = &result {
+                        self.toasts.error(t!("plugins-failed", command = label.clone(), error = e.clone()));
+                    }
+                    self.plugins_panel.finish(label, result);
+                }
+                BackgroundTaskResult::ScriptOutput(line) => self.scripts_window.append_output(line),
+                BackgroundTaskResult::ScriptFinished(name, result) => {
+                    match &result {
+                        Ok(()) => self.toasts.success(t!("scripts-done", name = name)),
+                        Err(e) => self.toasts.warning(t!("scripts-failed", name = name, error = e.clone())),
+                    }
+                    self.scripts_window.finish(result);
+                }
+                BackgroundTaskResult::TapPicker(result) => self.tap_picker.set_screenshot(result),
+                BackgroundTaskResult::Hierarchy(result) => self.hierarchy_window.set_dump(result),
+                BackgroundTaskResult::ColorPicker(result) => self.color_picker.set_screenshot(result),
+                BackgroundTaskResult::ScreenshotDiff(side, device, result) => {
+                    self.screenshot_diff.set_capture(side, &device, result);
+                }
+                BackgroundTaskResult::LongScreenshot(Ok((path, frames))) => {
+                    self.toasts.success(t!("long-screenshot-saved", frames = frames));
+                    self.screenshot_success_dialog = Some(path);
+                }
+                BackgroundTaskResult::LongScreenshot(Err(e)) => {
+                    self.toasts.error(t!("long-screenshot-failed", error = e));
+                }
+                BackgroundTaskResult::Ocr(Ok(text)) => {
+                    match crate::utils::copy_text_to_clipboard(&mut self.clipboard, &text) {
+                        Ok(()) => self.toasts.success(t!("ocr-copied", lines = text.lines().count())),
+                        Err(e) => self.toasts.error(t!("ocr-copy-failed", error = e.to_string())),
+                    }
+                }
+                BackgroundTaskResult::Ocr(Err(e)) => self.toasts.error(e),
+                BackgroundTaskResult::Gesture(name, result) => match result {
+                    Ok(()) if name.is_empty() => {}
+                    Ok(()) => self.toasts.success(t!("gestures-done", name = name)),
+                    Err(e) => self.toasts.error(t!("gestures-failed", error = e)),
+                },
+                BackgroundTaskResult::SideloadFinished(result) => {
+                    match &result {
+                        Ok(message) => self.toasts.success(message.clone()),
+                        Err(e) => self.toasts.error(t!("sideload-failed", error = e)),
+                    }
+                    self.sideload_window.finish(result);
+                }
+                BackgroundTaskResult::ExportFinished(result) => {
+                    match &result {
+                        Ok(path) => self.toasts.success(t!("recordings-exported", path = path.display())),
+                        Err(e) => self.toasts.error(e.clone()),
+                    }
+                    self.recording_library.set_export_result(result);
+                }
+                BackgroundTaskResult::DeviceReportFinished(result) => match result {
+                    Ok(path) => self.toasts.success(t!("device-report-saved", path = path.display())),
+                    Err(e) => self.toasts.error(t!("device-report-failed", error = e)),
+                },
+                BackgroundTaskResult::ScreenrecordFinished(result) => {
+                    self.recording = None;
+                    match result {
+                        Ok(path) => {
+                            self.toasts.success(t!("screenrecord-saved"));
+                            self.screenrecord_success_dialog = Some(t!("screenrecord-saved-to", path = path.display()));
+                        }
+                        Err(e) => {
+                            error!("Screen recording failed: {}", e);
+                            self.toasts.error(e);
+                        }
+                    }
+                }
+                BackgroundTaskResult::ScreenrecordStopped(result) => {
+                    if let Err(e) = result {
+                        error!("Could not stop the screen recording: {}", e);
+                        self.toasts.error(t!("screenrecord-stop-failed", error = e));
+                    }
+                }
+                BackgroundTaskResult::PushFinished { pushed, failed } => {
+                    self.file_push_dialog.finish();
+                    if failed == 0 {
+                        self.toasts.success(t!("push-done", count = pushed));
+                    } else {
+                        self.toasts.warning(t!("push-partly-failed", count = pushed, failed = failed));
+                    }
+                }
+                BackgroundTaskResult::Permissions { package, permissions, error } => {
+                    if let Some(error) = &error {
+                        self.toasts.error(error.clone());
+                    }
+                    self.permissions_window.set_permissions(&package, permissions, error);
+                }
+                BackgroundTaskResult::RebootTargets(device_id, targets) => match targets {
+                    Ok(targets) => {
+                        self.reboot_probe_failed.remove(&device_id);
+                        self.reboot_targets.insert(device_id, targets);
+                    }
+                    // Asked again a little later rather than hiding the targets until restart
+                    Err(e) => {
+                        tracing::warn!("{}", e);
+                        self.reboot_probe_failed.insert(device_id, std::time::Instant::now());
+                    }
+                },
+                BackgroundTaskResult::Capabilities(device_id, capabilities) => {
+                    let selected = self.device_list.selected_device().map(|d| d.identifier.clone());
+                    if selected.as_deref() == Some(device_id.as_str()) {
+                        self.capability_wizard.set_capabilities(Some(capabilities.clone()));
+                    }
+                    self.capabilities.insert(device_id, capabilities);
+                }
+                BackgroundTaskResult::StreamPreflight(checks) => {
+                    self.stream_window.set_checks(checks);
+                }
+                BackgroundTaskResult::StreamStarted(result) => match result {
+                    Ok(session) => {
+                        self.toasts.success(t!("stream-started"));
+                        self.stream_window.set_streaming(true, Ok(t!("stream-streaming-to", url = redact_stream_url(&session.url))));
+                        self.stream_session = Some(session);
+                    }
+                    Err(e) => {
+                        self.toasts.error(t!("stream-start-failed"));
+                        self.stream_window.set_streaming(false, Err(e));
+                    }
+                },
+                BackgroundTaskResult::DeviceClipboard(device_id, clip) => {
+                    self.clipboard_history.set_clip(&device_id, clip);
+                }
+                BackgroundTaskResult::DeviceStates(states) => {
+                    self.handle_device_states(states);
+                }
+                BackgroundTaskResult::Devices(result) => self.apply_devices(result),
+                BackgroundTaskResult::ScrcpyStatus(running) => self.apply_scrcpy_status(running),
+                BackgroundTaskResult::Health(samples) => {
+                    for (device_id, latency) in samples {
+                        self.device_list.set_health(device_id, latency);
+                    }
+                }
+                BackgroundTaskResult::AutoReconnect(endpoint, result) => {
+                    self.handle_auto_reconnect(endpoint, result);
+                }
+                BackgroundTaskResult::FastbootScan(devices) => self.bootloader_wizard.set_devices(devices),
+                BackgroundTaskResult::BootloaderFlash(result) => {
+                    match &result {
+                        Ok(message) => info!("{}", message),
+                        Err(e) => error!("Bootloader operation failed: {}", e),
+                    }
+                    self.bootloader_wizard.set_result(result);
+                }
+                BackgroundTaskResult::BootloaderManufacturer(manufacturer) => {
+                    self.bootloader_wizard.set_manufacturer(manufacturer);
+                }
+                BackgroundTaskResult::AdbKeys(host_key, device_keys) => {
+                    self.adb_keys_window.set_keys(host_key, device_keys);
+                }
+                BackgroundTaskResult::AdbKeyRegenerated(result) => match result {
+                    Ok(key) => {
+                        self.adb_keys_window
+                            .set_message(Ok(t!("adb-keys-regenerated", fingerprint = key.fingerprint.clone())));
+                        self.adb_keys_window.set_keys(Ok(key), None);
+                        self.refresh_devices();
+                    }
+                    Err(e) => {
+                        error!("Failed to regenerate adb keys: {}", e);
+                        self.adb_keys_window.set_message(Err(e));
+                    }
+                },
+                BackgroundTaskResult::AdbKeysRevoked(result) => {
+                    let message = result.map(|outcome| match outcome {
+                        RevokeOutcome::Revoked => t!("adb-keys-revoked"),
+                        RevokeOutcome::OpenedSettings => t!("adb-keys-revoke-manually"),
+                    });
+                    self.adb_keys_window.set_message(message);
+                }
+                BackgroundTaskResult::TunnelOpened(name, result) => {
+                    self.handle_tunnel_opened(name, result);
+                }
+                BackgroundTaskResult::Thumbnails(thumbnails) => {
+                    // Switched off while the capture ran
+                    let enabled = self.config.try_lock().map(|config| config.device_thumbnails).unwrap_or(true);
+                    if enabled {
+                        for (device_id, image) in thumbnails {
+                            self.device_list.set_thumbnail(device_id, image);
+                        }
+                    }
+                }
+                BackgroundTaskResult::Pip(device, result) => self.pip.set_frame(&device, result),
+                BackgroundTaskResult::ActivityStack(stack) => {
+                    self.activity_viewer.set_stack(stack);
+                }
+                BackgroundTaskResult::CompanionGranted(result) => {
+                    self.capability_wizard.set_message(result);
+                }
+                BackgroundTaskResult::IntentSent(output) => {
+                    match &output {
+                        Ok(_) => self.toasts.success(t!("intent-sent")),
+                        Err(e) => self.toasts.error(t!("intent-failed", error = e)),
+                    }
+                    self.intent_sender.set_output(output);
+                }
+                BackgroundTaskResult::ShellCommand(output) => self.shell_command.set_output(output),
+                BackgroundTaskResult::ToolInstallProgress(tool, stage) => {
+                    self.tool_install = Some((tool, stage));
+                }
+                BackgroundTaskResult::ToolInstalled(tool, result) => {
+                    self.apply_installed_tool(tool, result);
+                }
+                BackgroundTaskResult::UpdateChecked { result, manual } => {
+                    self.apply_update_check(result, manual);
+                }
+                BackgroundTaskResult::ToolsDetected { adb, scrcpy, redetect } => {
+                    self.apply_detected_tools(adb, scrcpy, redetect);
+                }
+                BackgroundTaskResult::AppDetails(package, details) => {
+                    self.app_details_window.set_details(&package, details);
+                }
+                BackgroundTaskResult::AppActionFinished(message) => match message {
+                    Ok(message) => self.toasts.success(message),
+                    Err(e) => self.toasts.error(e),
+                },
+                BackgroundTaskResult::SessionUsage(usage) => {
+                    // A late sample may arrive after the session ended
+                    self.session_usage = self.scrcpy_running.then_some(usage);
+                }
+                BackgroundTaskResult::DroppedApksInstalled(results) => {
+                    let failed: Vec<_> = results.iter().filter(|(_, r)| r.is_err()).collect();
+                    if failed.is_empty() {
+                        self.toasts.success(t!("apks-installed", count = results.len()));
+                    } else {
+                        let (name, error) = failed[0];
+                        self.toasts.error(t!(
+                            "apks-partly-installed",
+                            installed = results.len() - failed.len(),
+                            total = results.len(),
+                            name = name,
+                            error = error.as_ref().err().cloned().unwrap_or_default()
+                        ));
+                    }
+                }
+                BackgroundTaskResult::WirelessReconnect(results) => {
+                    self.loading_reconnect = false;
+                    let connected = results.iter().filter(|(_, r)| r.is_ok()).count();
+                    self.toasts.info(t!("wireless-reconnected", connected = connected, total = results.len()));
+                    self.reconnect_popup = Some(results);
+                    self.refresh_devices();
                 }
             }
         }
 
         // Clean up completed tasks
-        self.task_handles.retain(|_, handle| !handle.is_finished());
+        let finished: Vec<String> = self
+            .task_handles
+            .iter()
+            .filter(|(_, handle)| handle.is_finished())
+            .map(|(task, _)| task.clone())
+            .collect();
+        for task in finished {
+            self.task_handles.remove(&task);
+        }
     }
 
     fn is_processing(&self) -> bool {
-        self.loading_apps || self.loading_disable_apps || self.loading_imei || self.loading_display_info || self.loading_battery_info
+        self.loading_apps || self.loading_disable_apps || self.loading_imei || self.loading_display_info || self.loading_battery_info || self.loading_reconnect
     }
 
     fn toggle_theme(&mut self, ctx: &egui::Context) {
         if let Ok(mut config) = self.config.try_lock() {
-            match config.theme.as_str() {
-                "dark" => {
-                    config.theme = "light".to_string();
-                    ctx.set_visuals(egui::Visuals::light());
-                }
-                "light" => {
-                    config.theme = "dark".to_string();
-                    ctx.set_visuals(egui::Visuals::dark());
-                }
-                _ => {
-                    config.theme = "dark".to_string();
-                    ctx.set_visuals(egui::Visuals::dark());
-                }
-            }
+            config.theme = match config.theme.as_str() {
+                "dark" => "light".to_string(),
+                _ => "dark".to_string(),
+            };
+            ctx.set_visuals(crate::ui::theme::visuals(&config.theme, &config.custom_theme));
             // Save the theme change
             let _ = config.save();
         }
@@ -1189,181 +5803,213 @@ impl DroidViewApp {
 
 impl eframe::App for DroidViewApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.track_window_geometry(ctx);
+        if self.settings_window.take_redetect_requested() {
+            self.detect_tools(true);
+        }
+        if let Some(tool) = self.settings_window.take_install_requested() {
+            self.install_tool(tool);
+        }
+        if self.settings_window.take_update_check_requested() {
+            self.check_for_updates(true);
+        }
+        if let Some(topic) = self.settings_window.take_help_requested() {
+            self.help_window.open(topic);
+        }
+        if !self.shortcuts_window.is_capturing()
+            && let Some(action) = self.shortcuts.consume(ctx)
+        {
+            self.run_shortcut(action);
+        }
+
         if self.settings_window.take_just_saved() {
+            let language = self.config.try_lock().ok().and_then(|config| config.language.clone());
+            crate::i18n::set_language(language.as_deref());
+            let log_level = self.config.try_lock().map(|config| config.log_level.clone()).ok();
+            if let Some(level) = log_level
+                && let Err(e) = crate::logging::set_log_level(&level)
+            {
+                tracing::warn!("{}", e);
+            }
+            self.apply_poll_interval();
+            self.apply_session_templates();
+            self.apply_shortcuts();
+            self.apply_adb_server();
+            self.apply_control_api(ctx);
             self.update_bridges();
             self.refresh_devices();
-            self.status_message = "Settings saved and applied.".to_string();
+            self.toasts.success(t!("settings-saved"));
             self.apply_panel_visibility_from_config();
             self.apply_theme(ctx);
+            self.apply_scaling(ctx);
         }
         
+        match self.global_hotkeys.poll() {
+            Some(HotkeyTarget::Template(name)) => self.launch_template(&name),
+            Some(HotkeyTarget::Screenshot) => self.handle_toolkit_action(crate::ui::panels::ToolkitAction::Screenshot),
+            Some(HotkeyTarget::ToggleRecording) if self.recording.is_some() => self.stop_screenrecord(),
+            Some(HotkeyTarget::ToggleRecording) => self.start_screenrecord(),
+            None => {}
+        }
+        while let Some(request) = self.api_server.as_ref().and_then(ApiServer::poll) {
+            self.handle_api_request(request);
+        }
+        #[cfg(feature = "tray")]
+        match self.tray.poll() {
+            Some(crate::tray::TrayCommand::Show) => {
+                ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+                ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+                ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+            }
+            Some(crate::tray::TrayCommand::Launch(name)) => self.launch_template(&name),
+            None => {}
+        }
+
         // Performance optimization: Only update expensive operations periodically
         let now = std::time::Instant::now();
-        
-        // Update bridges every 2 seconds
-        if now.duration_since(self.last_bridge_update).as_secs() >= 2 {
+
+        // Every refresh period: rebuild the bridges if the paths changed, and look for scrcpy and
+        // devices in the background. The full device list is read on launch, on manual triggers
+        // and when the cheap `adb devices` check sees a device come, go or change state
+        if now.duration_since(self.last_device_watch) >= self.poll_interval {
             self.update_bridges();
-            self.last_bridge_update = now;
+            self.watch_devices();
+            self.check_scrcpy_process();
+            self.last_device_watch = now;
         }
+        self.run_due_auto_starts();
+        self.tick_automation();
+        self.run_due_reconnects();
+        self.device_list.set_reconnecting(self.reconnector.states());
         
-        // Note: Device refresh is now only done on first launch and manual triggers
-        // Removed automatic periodic refresh for better performance
-        
-        // Update scrcpy status every 500ms
-        if now.duration_since(self.last_scrcpy_status_update).as_millis() >= 500 {
-            self.update_scrcpy_status();
+        // Probe codecs for a newly selected device (cached per device)
+        self.probe_video_codecs();
+
+        // Watch for hot-plugged displays every 3 seconds
+        if now.duration_since(self.last_display_poll).as_secs() >= 3 {
+            self.poll_displays();
+            self.last_display_poll = now;
+        }
+
+        // Collect text copied on the device, when the user opted in
+        if self.clipboard_history.is_watching()
+            && now.duration_since(self.last_clipboard_poll) >= crate::device_clipboard::POLL_INTERVAL
+        {
+            self.poll_device_clipboard();
+            self.last_clipboard_poll = now;
+        }
+
+        // Measure how quickly each device answers
+        if now.duration_since(self.last_health_poll) >= crate::device::HEALTH_INTERVAL {
+            self.poll_health();
+            self.last_health_poll = now;
+        }
+
+        // Look for devices in fastboot mode while the bootloader wizard waits for one
+        if self.bootloader_wizard.wants_scan() && now.duration_since(self.last_fastboot_scan).as_secs() >= 2 {
+            self.scan_fastboot();
+        }
+
+        // Sample frame times for the frame profiler
+        if self.frame_profiler.sampling().is_some()
+            && now.duration_since(self.last_gfxinfo_poll) >= crate::gfxinfo::POLL_INTERVAL
+        {
+            self.poll_gfxinfo();
+            self.last_gfxinfo_poll = now;
+        }
+
+        // Refresh the device screen thumbnails
+
+        if now.duration_since(self.last_thumbnail_poll) >= THUMBNAIL_INTERVAL {
+            let enabled = self.config.try_lock().map(|config| config.device_thumbnails).unwrap_or(false);
+            if enabled {
+                self.poll_thumbnails();
+            }
+            self.last_thumbnail_poll = now;
+        }
+
+        // Refresh the live preview
+        if self.pip.is_visible() && now.duration_since(self.last_pip_poll) >= self.pip.interval() {
+            self.poll_pip();
+            self.last_pip_poll = now;
+        }
+
+        // Check on the processes DroidView started itself every 500ms
+        if now.duration_since(self.last_scrcpy_status_update) >= HOUSEKEEPING_INTERVAL {
+            self.check_legacy_audio();
+            self.check_stream();
+            self.reap_tunnels();
+            self.check_relay();
+            // Expired, or the opt-in was withdrawn in Settings
+            let share_opt_in = self.config.try_lock().map(|config| config.share_opt_in).unwrap_or(true);
+            if self.share_server.as_ref().is_some_and(|server| !server.is_running() || !share_opt_in) {
+                self.stop_share();
+            }
             self.last_scrcpy_status_update = now;
         }
+
+        // Sample host resource usage of the session every second
+        if !self.scrcpy_running {
+            self.session_usage = None;
+        } else if now.duration_since(self.last_usage_poll).as_secs() >= 1 {
+            self.sample_session_usage();
+            self.last_usage_poll = now;
+        }
         
-        // Request repaint only when needed for better performance
-        if self.is_processing() || self.scrcpy_running {
-            ctx.request_repaint();
-        } else {
-            // Reduce frame rate when idle for better performance
-            ctx.request_repaint_after(std::time::Duration::from_millis(100));
-        }
-
-        // Left panel (device list)
-        egui::SidePanel::left("device_panel")
-            .resizable(true)
-            .default_width(250.0)
-            .show(ctx, |ui| {
-                self.device_list.show(ui);
-                // Status bar below device list
-                ui.separator();
-                let status_color = if self.scrcpy_running {
-                    Color32::GREEN
-                } else {
-                    Color32::GRAY
-                };
-                ui.horizontal(|ui| {
-                    ui.label(RichText::new(&self.status_message).color(status_color));
-                    if self.scrcpy_running {
-                        ui.label(RichText::new("🟢 scrcpy running").color(Color32::GREEN));
-                    } else {
-                        ui.label(RichText::new("🔴 scrcpy stopped").color(Color32::RED));
-                    }
-                });
-                ui.separator();
-                ui.horizontal(|ui| {
-                    if ui.button("🔄 Refresh").clicked() {
-                        self.refresh_devices();
-                    }
-                    if ui.button("🔄 Restart ADB").clicked() {
-                        if let Some(adb_bridge) = &self.adb_bridge {
-                            if let Err(e) = crate::device::restart_adb_server(adb_bridge.path()) {
-                                error!("Failed to restart ADB: {}", e);
-                                self.status_message = format!("ADB restart failed: {}", e);
-                            } else {
-                                self.status_message = "ADB restarted".to_string();
-                                self.refresh_devices();
-                            }
-                        }
-                    }
-                });
-                ui.separator();
-                if let Some(action) = self.wireless_adb_panel.show(ui, self.adb_bridge.as_ref(), &self.devices) {
-                    self.handle_wireless_adb_action(action);
-                }
-            });
+        // Background results arrive without waking the UI: look for them often while tasks are
+        // out, and otherwise only come back for the checks above
+        let busy = self.is_processing() || !self.task_handles.is_empty() || !self.result_receiver.is_empty();
+        ctx.request_repaint_after(if busy { TASK_POLL_INTERVAL } else { HOUSEKEEPING_INTERVAL });
 
-        // Right panel (toolkit)
-        let available_width = ctx.available_rect().width();
-        let right_panel_default_width = available_width * 0.3;
-        let right_panel_width = right_panel_default_width.max(200.0);
-        if self.toolkit_panel.visible {
-            use crate::ui::panels::ToolkitLoadingState;
-            let loading = ToolkitLoadingState {
-                screenshot: false,
-                record_screen: false,
-                install_apk: false,
-                open_shell: false,
-                show_imei: self.loading_imei,
-                display_info: self.loading_display_info,
-                battery_info: self.loading_battery_info,
-                uninstall_app: self.loading_apps,
-                disable_app: self.loading_disable_apps,
-            };
-            egui::SidePanel::right("toolkit_panel")
-                .resizable(true)
-                .default_width(right_panel_width)
-                .min_width(180.0)
-                .show(ctx, |ui| {
-                    let toolkit_action = self.toolkit_panel.show(ui, &loading);
-                    self.handle_toolkit_action(toolkit_action);
-                    
-                    // Add processing status at the bottom of the right panel
-                    if self.is_processing() {
-                        ui.separator();
-                        ui.horizontal(|ui| {
-                            ui.add(egui::Spinner::new().size(16.0));
-                            ui.label(egui::RichText::new("Processing...").size(13.0).color(Color32::YELLOW));
-                        });
-                    }
-                    
-                    // Theme switch and About button
-                    ui.separator();
-                    ui.horizontal(|ui| {
-                        // Theme toggle button
-                        let current_theme = if let Ok(config) = self.config.try_lock() {
-                            config.theme.clone()
-                        } else {
-                            "default".to_string()
-                        };
-                        
-                        let theme_text = match current_theme.as_str() {
-                            "dark" => format!("{} Dark", egui_phosphor::fill::MOON),
-                            "light" => format!("{} Light", egui_phosphor::fill::SUN),
-                            _ => format!("{} Dark", egui_phosphor::fill::MOON)
-                        };
-                        
-                        if ui.button(egui::RichText::new(theme_text).size(12.0)).clicked() {
-                            self.toggle_theme(ctx);
-                        }
-                        
-                        ui.separator();
-                        
-                        // About button
-                        if ui.button(egui::RichText::new(format!("{} About", egui_phosphor::fill::INFO)).size(12.0)).clicked() {
-                            self.about_dialog = true;
-                        }
-                    });
-                });
+        // Missing dependency banner above everything else
+        let missing_tools = self.missing_tools.clone();
+        let installing = self.tool_install.map(|(tool, _)| tool);
+        match self.health_banner.show(ctx, &missing_tools, installing) {
+            HealthBannerAction::Locate(tool) => self.locate_tool(tool),
+            HealthBannerAction::Install(tool) => self.install_tool(tool),
+            HealthBannerAction::Download(tool) => {
+                if let Err(e) = crate::utils::open_url(tool.download_url()) {
+                    self.toasts.error(t!("browser-failed", error = e));
+                }
+            }
+            HealthBannerAction::None => {}
+        }
+        if let Some(device_id) = crate::ui::battery_simulation_banner(ctx, &self.battery_simulations) {
+            self.reset_battery_simulation(device_id);
         }
 
-        // Central panel (main content)
-        egui::CentralPanel::default().show(ctx, |ui| {
-            self.show_control_panel(ui);
-            if self.bottom_panel.visible {
-                egui::TopBottomPanel::bottom("bottom_panel")
-                    .resizable(true)
-                    .default_height(100.0)
-                    .show_inside(ui, |ui| {
-                        let action = self.bottom_panel.show(ui);
-                        match action {
-                            BottomPanelAction::RefreshDevices => self.refresh_devices(),
-                            BottomPanelAction::RestartAdb => {
-                                if let Some(adb_bridge) = &self.adb_bridge {
-                                    if let Err(e) = crate::device::restart_adb_server(adb_bridge.path()) {
-                                        error!("Failed to restart ADB: {}", e);
-                                        self.status_message = format!("ADB restart failed: {}", e);
-                                    } else {
-                                        self.status_message = "ADB restarted".to_string();
-                                        self.refresh_devices();
-                                    }
-                                }
-                            }
-                            BottomPanelAction::OpenSettings => self.settings_window.open(),
-                            BottomPanelAction::None => {}
-                        }
-                    });
+        self.log_console.show(ctx);
+
+        // Dockable panels fill the rest of the window, split, behind tabs or in windows of their own
+        let shown: Vec<DockPanel> = DockPanel::ALL.into_iter().filter(|panel| self.shows_panel(*panel)).collect();
+        crate::dock::sync(&mut self.dock, |panel| shown.contains(&panel));
+        let mut layout = std::mem::replace(&mut self.dock, DockLayout::new(Vec::new()));
+        let closed = crate::ui::dock::show(ctx, &mut layout, |ui, panel| self.show_dock_panel(ui, panel));
+        self.dock = layout;
+        if !closed.is_empty() {
+            if let Ok(mut config) = self.config.try_lock() {
+                for panel in closed {
+                    match panel {
+                        DockPanel::Toolkit => config.panels.toolkit = false,
+                        DockPanel::QuickActions => config.panels.bottom = false,
+                        _ => {}
+                    }
+                }
+                if let Err(e) = config.save() {
+                    error!("Failed to save panel visibility: {}", e);
+                }
             }
-        });
+            self.apply_panel_visibility_from_config();
+        }
+        // Dragging a tab or a divider ends with the pointer let go
+        if ctx.input(|input| input.pointer.any_released()) {
+            self.save_layout();
+        }
 
         // Show IMEI popup if available
         if let Some(imei) = &self.imei_popup {
             let imei_clone = imei.clone();
-            egui::Window::new(format!("{} Device IMEI", egui_phosphor::fill::PHONE))
+            egui::Window::new(format!("{} {}", egui_phosphor::fill::PHONE, t!("imei-title")))
                 .collapsible(false)
                 .resizable(false)
                 .fixed_size(egui::vec2(260.0, 120.0))
@@ -1375,7 +6021,7 @@ impl eframe::App for DroidViewApp {
                     // ui.separator();
                     ui.label(egui::RichText::new(&imei_clone).size(22.0).monospace());
                     ui.separator();
-                    if ui.add(egui::Button::new(egui::RichText::new("Close").size(12.0))).clicked() {
+                    if ui.add(egui::Button::new(egui::RichText::new(t!("dialog-close")).size(12.0))).clicked() {
                         self.imei_popup = None;
                     }
                 });
@@ -1384,7 +6030,7 @@ impl eframe::App for DroidViewApp {
         // Show Display Info popup if available
         if let Some(display_info) = &self.display_popup {
             let display_clone = display_info.clone();
-            egui::Window::new(format!("{} Display Information", egui_phosphor::fill::MONITOR))
+            egui::Window::new(format!("{} {}", egui_phosphor::fill::MONITOR, t!("display-info-title")))
                 .collapsible(false)
                 .resizable(true)
                 .default_size(egui::vec2(400.0, 300.0))
@@ -1398,7 +6044,7 @@ impl eframe::App for DroidViewApp {
                         ui.label(egui::RichText::new(&display_clone).size(11.0).monospace());
                     });
                     ui.separator();
-                    if ui.add(egui::Button::new(egui::RichText::new("Close").size(12.0))).clicked() {
+                    if ui.add(egui::Button::new(egui::RichText::new(t!("dialog-close")).size(12.0))).clicked() {
                         self.display_popup = None;
                     }
                 });
@@ -1407,29 +6053,97 @@ impl eframe::App for DroidViewApp {
         // Show Battery Info popup if available
         if let Some(battery_info) = &self.battery_popup {
             let battery_clone = battery_info.clone();
-            egui::Window::new(format!("{} Battery Information", egui_phosphor::fill::BATTERY_FULL))
+            egui::Window::new(format!("{} {}", egui_phosphor::fill::BATTERY_FULL, t!("battery-info-title")))
+                .collapsible(false)
+                .resizable(true)
+                .default_size(egui::vec2(350.0, 250.0))
+                .frame(egui::Frame::window(&egui::Style::default()).corner_radius(egui::CornerRadius::same(0)))
+                .pivot(egui::Align2::CENTER_CENTER)
+                .show(ctx, |ui| {
+                    // ui.add_space(4.0);
+                    // ui.label(egui::RichText::new("🔋 Battery Information").size(12.0));
+                    // ui.separator();
+                    egui::ScrollArea::vertical().max_height(150.0).show(ui, |ui| {
+                        ui.label(egui::RichText::new(&battery_clone).size(11.0).monospace());
+                    });
+                    ui.separator();
+                    if ui.add(egui::Button::new(egui::RichText::new(t!("dialog-close")).size(12.0))).clicked() {
+                        self.battery_popup = None;
+                    }
+                });
+        }
+
+        // Offer to mirror a newly detected display
+        if let Some((device_id, display)) = &self.new_display_prompt {
+            let device_id = device_id.clone();
+            let display = display.clone();
+            egui::Window::new(format!("{} {}", egui_phosphor::fill::MONITOR, t!("new-display-title")))
+                .collapsible(false)
+                .resizable(false)
+                .fixed_size(egui::vec2(320.0, 120.0))
+                .frame(egui::Frame::window(&egui::Style::default()).corner_radius(egui::CornerRadius::same(0)))
+                .pivot(egui::Align2::CENTER_CENTER)
+                .show(ctx, |ui| {
+                    ui.label(t!("new-display-appeared", name = display.name, id = display.id, device = device_id));
+                    ui.label(t!("new-display-question"));
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        if ui.add(egui::Button::new(egui::RichText::new(t!("new-display-mirror")).size(12.0))).clicked() {
+                            if let Ok(mut config) = self.config.try_lock() {
+                                config.display_ids.insert(device_id.clone(), display.id);
+                            }
+                            self.new_display_prompt = None;
+                            self.start_scrcpy();
+                        }
+                        if ui.add(egui::Button::new(egui::RichText::new(t!("dialog-dismiss")).size(12.0))).clicked() {
+                            self.new_display_prompt = None;
+                        }
+                    });
+                });
+        }
+
+        // Show wireless reconnect results if available
+        if let Some(results) = &self.reconnect_popup {
+            let results_clone = results.clone();
+            egui::Window::new(format!("{} {}", egui_phosphor::fill::WIFI_HIGH, t!("reconnect-title")))
                 .collapsible(false)
                 .resizable(true)
-                .default_size(egui::vec2(350.0, 250.0))
+                .default_size(egui::vec2(400.0, 250.0))
                 .frame(egui::Frame::window(&egui::Style::default()).corner_radius(egui::CornerRadius::same(0)))
                 .pivot(egui::Align2::CENTER_CENTER)
                 .show(ctx, |ui| {
-                    // ui.add_space(4.0);
-                    // ui.label(egui::RichText::new("🔋 Battery Information").size(12.0));
-                    // ui.separator();
-                    egui::ScrollArea::vertical().max_height(150.0).show(ui, |ui| {
-                        ui.label(egui::RichText::new(&battery_clone).size(11.0).monospace());
+                    egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                        for (endpoint, result) in &results_clone {
+                            ui.horizontal(|ui| {
+                                match result {
+                                    Ok(()) => {
+                                        ui.label(RichText::new(format!("{} {}", egui_phosphor::fill::CHECK_CIRCLE, endpoint)).color(Color32::GREEN));
+                                    }
+                                    Err(e) => {
+                                        ui.label(RichText::new(format!("{} {}", egui_phosphor::fill::X_CIRCLE, endpoint)).color(Color32::RED))
+                                            .on_hover_text(e);
+                                    }
+                                }
+                            });
+                        }
                     });
                     ui.separator();
-                    if ui.add(egui::Button::new(egui::RichText::new("Close").size(12.0))).clicked() {
-                        self.battery_popup = None;
-                    }
+                    ui.horizontal(|ui| {
+                        if ui.add(egui::Button::new(egui::RichText::new(t!("reconnect-retry")).size(12.0))).clicked() {
+                            self.reconnect_popup = None;
+                            self.reconnect_saved_devices();
+                        }
+                        if ui.add(egui::Button::new(egui::RichText::new(t!("dialog-close")).size(12.0))).clicked() {
+                            self.reconnect_popup = None;
+                        }
+                    });
                 });
         }
 
         // Show Screen Recording Dialog if available
         if self.screenrecord_dialog {
-            egui::Window::new(format!("{} Screen Recording Settings", egui_phosphor::fill::RECORD))
+            let mut start_recording = false;
+            egui::Window::new(format!("{} {}", egui_phosphor::fill::RECORD, t!("screenrecord-title")))
                 .collapsible(false)
                 .resizable(false)
                 .fixed_size(egui::vec2(300.0, 200.0))
@@ -1438,79 +6152,31 @@ impl eframe::App for DroidViewApp {
                 .show(ctx, |ui| {
 
                     ui.horizontal(|ui| {
-                        ui.label("Duration (seconds):");
+                        ui.label(t!("screenrecord-duration"));
                         ui.add(egui::DragValue::new(&mut self.screenrecord_duration).range(1..=180).speed(1));
                     });
                     
                     ui.horizontal(|ui| {
-                        ui.label("Bitrate (KB/s):");
+                        ui.label(t!("screenrecord-bitrate"));
                         ui.add(egui::DragValue::new(&mut self.screenrecord_bitrate).range(100..=10000).speed(100));
                     });
                     
                     ui.separator();
                     
                     ui.horizontal(|ui| {
-                        if ui.add(egui::Button::new(egui::RichText::new("Start Recording").size(12.0))).clicked() {
-                            if let (Some(adb_bridge), Some(device)) = (self.adb_bridge.as_ref(), self.device_list.selected_device()) {
-                                // Start screen recording with custom settings
-                                let status = std::process::Command::new(adb_bridge.path())
-                                    .args([
-                                        "-s",
-                                        &device.identifier,
-                                        "shell",
-                                        "screenrecord",
-                                        "/sdcard/video.mp4",
-                                        "--time-limit",
-                                        &self.screenrecord_duration.to_string(),
-                                        "--bit-rate",
-                                        &(self.screenrecord_bitrate * 1000).to_string(),
-                                    ])
-                                    .status();
-                                match status {
-                                    Ok(s) if s.success() => {
-                                        // Pull the file with timestamp
-                                        let desktop = dirs::desktop_dir().unwrap_or_default();
-                                        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
-                                        let file_path = desktop.join(format!("screenrecord_{}.mp4", timestamp));
-                                        let pull_status = std::process::Command::new(adb_bridge.path())
-                                            .args([
-                                                "-s",
-                                                &device.identifier,
-                                                "pull",
-                                                "/sdcard/video.mp4",
-                                                file_path.to_str().unwrap(),
-                                            ])
-                                            .status();
-                                        match pull_status {
-                                            Ok(ps) if ps.success() => {
-                                                self.screenrecord_success_dialog = Some(format!("Screen recording saved to {}", file_path.display()));
-                                            }
-                                            Ok(ps) => {
-                                                self.status_message = format!("Pull failed: exit code {}", ps);
-                                            }
-                                            Err(e) => {
-                                                self.status_message = format!("Pull error: {}", e);
-                                            }
-                                        }
-                                    }
-                                    Ok(s) => {
-                                        self.status_message = format!("Screenrecord failed: exit code {}", s);
-                                    }
-                                    Err(e) => {
-                                        self.status_message = format!("Screenrecord error: {}", e);
-                                    }
-                                }
-                                self.screenrecord_dialog = false;
-                            } else {
-                                self.status_message = "No device selected or ADB not configured".to_string();
-                            }
+                        if ui.add(egui::Button::new(egui::RichText::new(t!("screenrecord-start")).size(12.0))).clicked() {
+                            start_recording = true;
                         }
                         
-                        if ui.add(egui::Button::new(egui::RichText::new("Cancel").size(12.0))).clicked() {
+                        if ui.add(egui::Button::new(egui::RichText::new(t!("dialog-cancel")).size(12.0))).clicked() {
                             self.screenrecord_dialog = false;
                         }
                     });
                 });
+            if start_recording {
+                self.screenrecord_dialog = false;
+                self.start_screenrecord();
+            }
         }
 
         // Show About Dialog if available
@@ -1543,13 +6209,19 @@ impl eframe::App for DroidViewApp {
                                     ui.add_space(8.0);
                                 } else {
                                     // Fallback to Phosphor icon if icon not found
-                                    ui.label(egui::RichText::new(format!("{}", egui_phosphor::regular::ANDROID_LOGO)).size(32.0));
+                                    ui.label(egui::RichText::new(egui_phosphor::regular::ANDROID_LOGO.to_string()).size(32.0));
                                 }
                                 
                                 // App name and version
                                 ui.label(egui::RichText::new("DroidView").size(20.0).strong());
                                 ui.label(egui::RichText::new("(droid_view)").size(10.0).color(Color32::GRAY));
-                                ui.label(egui::RichText::new("Version 0.1.5").size(12.0));
+                                ui.label(egui::RichText::new(t!("about-version", version = "0.1.5")).size(12.0));
+                                if crate::config::is_portable() {
+                                    ui.label(egui::RichText::new(t!("about-portable")).size(10.0).color(Color32::GRAY));
+                                }
+                                if let Some(profile) = crate::config::active_profile() {
+                                    ui.label(egui::RichText::new(t!("about-profile", profile = profile)).size(10.0).color(Color32::GRAY));
+                                }
                                 
                                 ui.add_space(8.0);
                                 
@@ -1564,22 +6236,22 @@ impl eframe::App for DroidViewApp {
                                 ui.add_space(8.0);
                                 
                                 // License information
-                                ui.label(egui::RichText::new("License: GNU General Public License v3").size(10.0).color(Color32::GRAY));
+                                ui.label(egui::RichText::new(t!("about-license")).size(10.0).color(Color32::GRAY));
                                 
                                 ui.add_space(4.0);
                                 
                                 // License description
-                                ui.label(egui::RichText::new("This program is free software: you can redistribute it and/or modify it under the terms of the GNU General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.").size(9.0).color(Color32::GRAY));
+                                ui.label(egui::RichText::new(t!("about-license-free")).size(9.0).color(Color32::GRAY));
                                 
                                 ui.add_space(4.0);
                                 
-                                ui.label(egui::RichText::new("This program is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.").size(9.0).color(Color32::GRAY));
+                                ui.label(egui::RichText::new(t!("about-license-warranty")).size(9.0).color(Color32::GRAY));
                                 
                                 ui.add_space(8.0);
                                 
                                 // Website
                                 ui.vertical_centered(|ui| {
-                                    ui.label(egui::RichText::new("Website:").size(10.0));
+                                    ui.label(egui::RichText::new(t!("about-website")).size(10.0));
                                     if ui.link(egui::RichText::new("🐙/Genxster1998/DroidView").size(12.0).color(Color32::CYAN)).clicked() {
                                         // Open URL in default browser
                                         let _ = std::process::Command::new("open")
@@ -1592,8 +6264,8 @@ impl eframe::App for DroidViewApp {
                                 
                                 // License link
                                 ui.vertical_centered(|ui| {
-                                    ui.label(egui::RichText::new("License:").size(10.0));
-                                    if ui.link(egui::RichText::new("📄 View Full License").size(11.0).color(Color32::CYAN)).clicked() {
+                                    ui.label(egui::RichText::new(t!("about-license-link")).size(10.0));
+                                    if ui.link(egui::RichText::new(t!("about-view-license")).size(11.0).color(Color32::CYAN)).clicked() {
                                         // Open GPL v3 license in default browser
                                         let _ = std::process::Command::new("open")
                                             .arg("https://www.gnu.org/licenses/gpl-3.0.html")
@@ -1604,7 +6276,7 @@ impl eframe::App for DroidViewApp {
                                 ui.add_space(12.0);
                                 
                                 // Close button
-                                if ui.add(egui::Button::new(egui::RichText::new("Close").size(11.0)).min_size(egui::vec2(60.0, 24.0))).clicked() {
+                                if ui.add(egui::Button::new(egui::RichText::new(t!("dialog-close")).size(11.0)).min_size(egui::vec2(60.0, 24.0))).clicked() {
                                     self.about_dialog = false;
                                 }
                             });
@@ -1612,9 +6284,14 @@ impl eframe::App for DroidViewApp {
                 });
         }
 
+        // Context-menu action picked from either app dialog this frame
+        let mut requested_action: Option<(AppAction, String)> = None;
+        let package_user = self.package_user();
+        let mut reload_apps = false;
+
         // Show Uninstall App Dialog if available
         if self.uninstall_dialog {
-            egui::Window::new(format!("{} Uninstall Application", egui_phosphor::fill::TRASH))
+            egui::Window::new(format!("{} {}", egui_phosphor::fill::TRASH, t!("apps-uninstall-title")))
                 .collapsible(false)
                 .resizable(true)
                 .default_size(egui::vec2(400.0, 500.0))
@@ -1624,23 +6301,33 @@ impl eframe::App for DroidViewApp {
                     //ui.add_space(4.0);
                     //ui.label(egui::RichText::new(format!("{}", egui_phosphor::fill::TRASH)).size(32.0));
                     //ui.separator();
+
+                    if package_user.is_some()
+                        && let Some((_, users)) = &self.device_users
+                        && user_selector(ui, "uninstall_user", users, &mut self.package_user)
+                    {
+                        reload_apps = true;
+                    }
                     
                     if self.loading_apps {
                         ui.vertical_centered(|ui| {
                             ui.add_space(20.0);
-                            ui.label("Loading app list...");
+                            ui.label(t!("apps-loading"));
                             ui.add(egui::Spinner::new().size(20.0));
                             ui.add_space(20.0);
                         });
                     } else if self.app_list.is_empty() {
-                        ui.label("No apps found or failed to load app list.");
+                        ui.label(t!("apps-none"));
                     } else {
-                        ui.label(format!("Found {} apps:", self.app_list.len()));
+                        self.app_filter.show(ui, "uninstall_sort");
+                        let visible: Vec<PackageInfo> = self.app_filter.apply(&self.app_list).into_iter().cloned().collect();
+                        ui.label(t!("apps-showing", shown = visible.len(), total = self.app_list.len()));
                         ui.separator();
                         
                         // App selection with checkboxes
                         egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
-                            for (package_name, _) in &self.app_list {
+                            for app in &visible {
+                                let package_name = &app.package;
                                 let is_selected = self.selected_apps.contains(package_name);
                                 let mut checked = is_selected;
                                 
@@ -1653,7 +6340,22 @@ impl eframe::App for DroidViewApp {
                                         }
                                     }
                                     
-                                    ui.label(package_name);
+                                    let row = ui
+                                        .add(egui::Label::new(package_name).sense(egui::Sense::click()))
+                                        .on_hover_text(t!("apps-row-hint"));
+                                    if let Some(action) = app_context_menu(&row) {
+                                        requested_action = Some((action, package_name.clone()));
+                                    }
+                                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                        if ui
+                                            .add_enabled(app.enabled, egui::Button::new(egui_phosphor::fill::PLAY).small())
+                                            .on_hover_text(t!("apps-launch"))
+                                            .clicked()
+                                        {
+                                            requested_action = Some((AppAction::Launch, package_name.clone()));
+                                        }
+                                        ui.label(RichText::new(app_details(app)).weak().size(11.0));
+                                    });
                                 });
                             }
                         });
@@ -1661,35 +6363,44 @@ impl eframe::App for DroidViewApp {
                         ui.separator();
                         
                         // Selection summary
-                        if !self.selected_apps.is_empty() {
-                            ui.label(format!("Selected {} app(s)", self.selected_apps.len()));
+                        // Only the selected apps the filter shows are acted on
+                        let targets: Vec<String> = visible
+                            .iter()
+                            .filter(|app| self.selected_apps.contains(&app.package))
+                            .map(|app| app.package.clone())
+                            .collect();
+                        let hidden = self.selected_apps.len() - targets.len();
+                        if hidden > 0 {
+                            ui.label(t!("apps-selected-hidden", count = targets.len(), hidden = hidden));
+                        } else if !targets.is_empty() {
+                            ui.label(t!("apps-selected", count = targets.len()));
                         }
                         
                         // Uninstall buttons
                         ui.horizontal(|ui| {
-                            if ui.add(egui::Button::new(egui::RichText::new("Uninstall Selected").size(12.0))).clicked() {
-                                if !self.selected_apps.is_empty() {
+                            if ui.add(egui::Button::new(egui::RichText::new(t!("apps-uninstall-selected")).size(12.0))).clicked() {
+                                if !targets.is_empty() {
                                     if let (Some(adb_bridge), Some(device)) = (
                                         self.adb_bridge.as_ref(), 
                                         self.device_list.selected_device()
                                     ) {
                                         let mut success_count = 0;
                                         let mut failed_count = 0;
+                                        let mut done = std::collections::HashSet::new();
                                         
-                                        for package_name in &self.selected_apps {
-                                            // Uninstall the selected app
-                                            let status = std::process::Command::new(adb_bridge.path())
-                                                .args([
-                                                    "-s",
-                                                    &device.identifier,
-                                                    "uninstall",
-                                                    package_name,
-                                                ])
-                                                .status();
+                                        for package_name in &targets {
+                                            // Uninstall the selected app, only for the chosen user on multi-user devices
+                                            let mut uninstall = crate::bridge::adb_command(adb_bridge.path());
+                                            uninstall.args(["-s", &device.identifier, "uninstall"]);
+                                            if let Some(user) = package_user {
+                                                uninstall.args(["--user", &user.to_string()]);
+                                            }
+                                            let status = uninstall.arg(package_name).status();
                                             
                                             match status {
                                                 Ok(s) if s.success() => {
                                                     success_count += 1;
+                                                    done.insert(package_name.clone());
                                                 }
                                                 _ => {
                                                     failed_count += 1;
@@ -1698,35 +6409,35 @@ impl eframe::App for DroidViewApp {
                                         }
                                         
                                         // Remove successfully uninstalled apps from list
-                                        self.app_list.retain(|(package, _)| !self.selected_apps.contains(package));
+                                        self.app_list.retain(|app| !done.contains(&app.package));
                                         
                                         if failed_count == 0 {
-                                            self.status_message = format!("Successfully uninstalled {} app(s)", success_count);
+                                            self.toasts.success(t!("apps-uninstalled", count = success_count));
                                         } else {
-                                            self.status_message = format!("Uninstalled {} app(s), {} failed", success_count, failed_count);
+                                            self.toasts.warning(t!("apps-uninstalled-partly", count = success_count, failed = failed_count));
                                         }
                                         
-                                        self.selected_apps.clear();
+                                        self.selected_apps.retain(|package| !targets.contains(package));
                                     } else {
-                                        self.status_message = "No device selected or ADB not configured".to_string();
+                                        self.toasts.warning(t!("no-device-adb"));
                                     }
                                 } else {
-                                    self.status_message = "Please select at least one app to uninstall".to_string();
+                                    self.toasts.warning(t!("apps-uninstall-none"));
                                 }
                             }
                             
-                            if ui.add(egui::Button::new(egui::RichText::new("Select All").size(12.0))).clicked() {
+                            if ui.add(egui::Button::new(egui::RichText::new(t!("apps-select-all")).size(12.0))).clicked() {
                                 self.selected_apps.clear();
-                                for (package_name, _) in &self.app_list {
-                                    self.selected_apps.insert(package_name.clone());
+                                for app in &visible {
+                                    self.selected_apps.insert(app.package.clone());
                                 }
                             }
                             
-                            if ui.add(egui::Button::new(egui::RichText::new("Clear Selection").size(12.0))).clicked() {
+                            if ui.add(egui::Button::new(egui::RichText::new(t!("apps-clear-selection")).size(12.0))).clicked() {
                                 self.selected_apps.clear();
                             }
                             
-                            if ui.add(egui::Button::new(egui::RichText::new("Close").size(12.0))).clicked() {
+                            if ui.add(egui::Button::new(egui::RichText::new(t!("dialog-close")).size(12.0))).clicked() {
                                 self.uninstall_dialog = false;
                                 self.selected_apps.clear();
                             }
@@ -1737,7 +6448,7 @@ impl eframe::App for DroidViewApp {
 
         // Show Disable App Dialog if available
         if self.disable_dialog {
-            egui::Window::new(format!("{} Disable Application", egui_phosphor::fill::PROHIBIT))
+            egui::Window::new(format!("{} {}", egui_phosphor::fill::PROHIBIT, t!("apps-disable-title")))
                 .collapsible(false)
                 .resizable(true)
                 .default_size(egui::vec2(400.0, 500.0))
@@ -1747,23 +6458,33 @@ impl eframe::App for DroidViewApp {
                     //ui.add_space(4.0);
                     //ui.label(egui::RichText::new("🚫 Disable Application").size(12.0));
                     //ui.separator();
+
+                    if package_user.is_some()
+                        && let Some((_, users)) = &self.device_users
+                        && user_selector(ui, "disable_user", users, &mut self.package_user)
+                    {
+                        reload_apps = true;
+                    }
                     
                     if self.loading_disable_apps {
                         ui.vertical_centered(|ui| {
                             ui.add_space(20.0);
-                            ui.label("Loading app list...");
+                            ui.label(t!("apps-loading"));
                             ui.add(egui::Spinner::new().size(20.0));
                             ui.add_space(20.0);
                         });
                     } else if self.disable_app_list.is_empty() {
-                        ui.label("No apps found or failed to load app list.");
+                        ui.label(t!("apps-none"));
                     } else {
-                        ui.label(format!("Found {} enabled apps:", self.disable_app_list.len()));
+                        self.disable_app_filter.show(ui, "disable_sort");
+                        let visible: Vec<PackageInfo> = self.disable_app_filter.apply(&self.disable_app_list).into_iter().cloned().collect();
+                        ui.label(t!("apps-showing", shown = visible.len(), total = self.disable_app_list.len()));
                         ui.separator();
                         
                         // App selection with checkboxes
                         egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
-                            for (package_name, _) in &self.disable_app_list {
+                            for app in &visible {
+                                let package_name = &app.package;
                                 let is_selected = self.selected_disable_apps.contains(package_name);
                                 let mut checked = is_selected;
                                 
@@ -1776,7 +6497,22 @@ impl eframe::App for DroidViewApp {
                                         }
                                     }
                                     
-                                    ui.label(package_name);
+                                    let row = ui
+                                        .add(egui::Label::new(package_name).sense(egui::Sense::click()))
+                                        .on_hover_text(t!("apps-row-hint"));
+                                    if let Some(action) = app_context_menu(&row) {
+                                        requested_action = Some((action, package_name.clone()));
+                                    }
+                                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                        if ui
+                                            .add_enabled(app.enabled, egui::Button::new(egui_phosphor::fill::PLAY).small())
+                                            .on_hover_text(t!("apps-launch"))
+                                            .clicked()
+                                        {
+                                            requested_action = Some((AppAction::Launch, package_name.clone()));
+                                        }
+                                        ui.label(RichText::new(app_details(app)).weak().size(11.0));
+                                    });
                                 });
                             }
                         });
@@ -1784,29 +6520,39 @@ impl eframe::App for DroidViewApp {
                         ui.separator();
                         
                         // Selection summary
-                        if !self.selected_disable_apps.is_empty() {
-                            ui.label(format!("Selected {} app(s)", self.selected_disable_apps.len()));
+                        // Only the selected apps the filter shows are acted on
+                        let targets: Vec<String> = visible
+                            .iter()
+                            .filter(|app| self.selected_disable_apps.contains(&app.package))
+                            .map(|app| app.package.clone())
+                            .collect();
+                        let hidden = self.selected_disable_apps.len() - targets.len();
+                        if hidden > 0 {
+                            ui.label(t!("apps-selected-hidden", count = targets.len(), hidden = hidden));
+                        } else if !targets.is_empty() {
+                            ui.label(t!("apps-selected", count = targets.len()));
                         }
                         
                         // Disable buttons
                         ui.horizontal(|ui| {
-                            if ui.add(egui::Button::new(egui::RichText::new("Disable Selected").size(12.0))).clicked() {
-                                if !self.selected_disable_apps.is_empty() {
+                            if ui.add(egui::Button::new(egui::RichText::new(t!("apps-disable-selected")).size(12.0))).clicked() {
+                                if !targets.is_empty() {
                                     if let (Some(adb_bridge), Some(device)) = (
                                         self.adb_bridge.as_ref(), 
                                         self.device_list.selected_device()
                                     ) {
                                         let mut success_count = 0;
                                         let mut failed_count = 0;
+                                        let mut done = std::collections::HashSet::new();
                                         
-                                        for package_name in &self.selected_disable_apps {
-                                            // Disable the selected app for user 0
-                                            let status = std::process::Command::new(adb_bridge.path())
+                                        for package_name in &targets {
+                                            // Disable the selected app for the chosen user
+                                            let status = crate::bridge::adb_command(adb_bridge.path())
                                                 .args([
                                                     "-s",
                                                     &device.identifier,
                                                     "shell",
-                                                    "pm disable-user --user 0",
+                                                    &format!("pm disable-user --user {}", package_user.unwrap_or(0)),
                                                     package_name,
                                                 ])
                                                 .status();
@@ -1814,6 +6560,7 @@ impl eframe::App for DroidViewApp {
                                             match status {
                                                 Ok(s) if s.success() => {
                                                     success_count += 1;
+                                                    done.insert(package_name.clone());
                                                 }
                                                 _ => {
                                                     failed_count += 1;
@@ -1822,35 +6569,35 @@ impl eframe::App for DroidViewApp {
                                         }
                                         
                                         // Remove successfully disabled apps from list
-                                        self.disable_app_list.retain(|(package, _)| !self.selected_disable_apps.contains(package));
+                                        self.disable_app_list.retain(|app| !done.contains(&app.package));
                                         
                                         if failed_count == 0 {
-                                            self.status_message = format!("Successfully disabled {} app(s)", success_count);
+                                            self.toasts.success(t!("apps-disabled-all", count = success_count));
                                         } else {
-                                            self.status_message = format!("Disabled {} app(s), {} failed", success_count, failed_count);
+                                            self.toasts.warning(t!("apps-disabled-partly", count = success_count, failed = failed_count));
                                         }
                                         
-                                        self.selected_disable_apps.clear();
+                                        self.selected_disable_apps.retain(|package| !targets.contains(package));
                                     } else {
-                                        self.status_message = "No device selected or ADB not configured".to_string();
+                                        self.toasts.warning(t!("no-device-adb"));
                                     }
                                 } else {
-                                    self.status_message = "Please select at least one app to disable".to_string();
+                                    self.toasts.warning(t!("apps-disable-none"));
                                 }
                             }
                             
-                            if ui.add(egui::Button::new(egui::RichText::new("Select All").size(12.0))).clicked() {
+                            if ui.add(egui::Button::new(egui::RichText::new(t!("apps-select-all")).size(12.0))).clicked() {
                                 self.selected_disable_apps.clear();
-                                for (package_name, _) in &self.disable_app_list {
-                                    self.selected_disable_apps.insert(package_name.clone());
+                                for app in &visible {
+                                    self.selected_disable_apps.insert(app.package.clone());
                                 }
                             }
                             
-                            if ui.add(egui::Button::new(egui::RichText::new("Clear Selection").size(12.0))).clicked() {
+                            if ui.add(egui::Button::new(egui::RichText::new(t!("apps-clear-selection")).size(12.0))).clicked() {
                                 self.selected_disable_apps.clear();
                             }
                             
-                            if ui.add(egui::Button::new(egui::RichText::new("Close").size(12.0))).clicked() {
+                            if ui.add(egui::Button::new(egui::RichText::new(t!("dialog-close")).size(12.0))).clicked() {
                                 self.disable_dialog = false;
                                 self.selected_disable_apps.clear();
                             }
@@ -1859,26 +6606,486 @@ impl eframe::App for DroidViewApp {
                 });
         }
 
+        if reload_apps {
+            if self.uninstall_dialog {
+                self.load_app_list(false);
+            }
+            if self.disable_dialog {
+                self.load_app_list(true);
+            }
+        }
+
+        match requested_action {
+            Some((AppAction::ClearData, package)) => self.clear_data_confirm = Some(package),
+            Some((action, package)) => self.run_app_action(action, package),
+            None => {}
+        }
+
+        match self.bootloader_wizard.show(ctx) {
+            BootloaderAction::RebootToBootloader(device) => {
+                if let Some(adb_bridge) = &self.adb_bridge {
+                    let status = crate::bridge::adb_command(adb_bridge.path())
+                        .args(["-s", &device, "reboot", "bootloader"])
+                        .status();
+                    if !status.is_ok_and(|status| status.success()) {
+                        self.toasts.error(t!("bootloader-reboot-failed", device = device));
+                    }
+                }
+                self.scan_fastboot();
+            }
+            BootloaderAction::Scan => self.scan_fastboot(),
+            BootloaderAction::Run { serial, lock } => self.run_bootloader_flashing(serial, lock),
+            BootloaderAction::Reboot(serial) => {
+                let fastboot = FastbootBridge::locate(self.adb_bridge.as_ref().map(|bridge| bridge.path()));
+                match fastboot.map(|fastboot| fastboot.reboot(&serial)) {
+                    Some(Ok(())) => self.toasts.info(t!("device-rebooting")),
+                    Some(Err(e)) => self.toasts.error(t!("reboot-failed", error = e)),
+                    None => {}
+                }
+            }
+            BootloaderAction::None => {}
+        }
+
+        match self.wifi_window.show(ctx) {
+            WifiAction::Refresh => self.load_wifi_info(),
+            WifiAction::UseIp(ip) => {
+                ctx.copy_text(ip.clone());
+                self.wireless_adb_panel.set_tcpip_ip(ip.clone());
+                self.toasts.info(t!("wifi-ip-used", ip = ip));
+            }
+            WifiAction::None => {}
+        }
+
+        match self.resolution_window.show(ctx) {
+            ResolutionAction::Refresh => self.change_resolution(None, None),
+            ResolutionAction::Apply { size, density } => self.change_resolution(size, density),
+            ResolutionAction::Reset => {
+                if let (Some(adb_bridge), Some(device)) = (&self.adb_bridge, self.device_list.selected_device()) {
+                    let adb_path = adb_bridge.path().to_string();
+                    let device_id = device.identifier.clone();
+                    self.resolution_window.set_busy();
+                    self.run_background_task("resolution".to_string(), move || {
+                        let result = crate::resolution::reset(&adb_path, &device_id)
+                            .and_then(|()| crate::resolution::read(&adb_path, &device_id));
+                        ResolutionResult(result.map_err(|e| e.to_string()))
+                    });
+                }
+            }
+            ResolutionAction::None => {}
+        }
+
+        match self.locale_window.show(ctx) {
+            LocaleAction::None => {}
+            LocaleAction::CheckClock => self.check_clock(false),
+            LocaleAction::SyncClock => self.check_clock(true),
+            action => self.change_locale(action),
+        }
+
+        match self.standby_window.show(ctx) {
+            StandbyAction::None => {}
+            action => self.change_standby(action),
+        }
+
+        match self.mock_location_window.show(ctx) {
+            MockLocationAction::None => {}
+            MockLocationAction::StopRoute => self.route_cancel.store(true, std::sync::atomic::Ordering::Relaxed),
+            action => self.change_mock_location(action),
+        }
+
+        match self.frame_profiler.show(ctx) {
+            FrameProfilerAction::UseForeground => self.load_foreground_package(),
+            FrameProfilerAction::None => {}
+        }
+
+        match self.perfetto_window.show(ctx) {
+            PerfettoAction::Start { categories, duration_secs } => self.start_perfetto(categories, duration_secs),
+            PerfettoAction::Stop => self.stop_perfetto(),
+            PerfettoAction::None => {}
+        }
+
+        match self.sideload_window.show(ctx, &self.devices) {
+            SideloadAction::Start { serial, zip, reboot } => self.start_sideload(serial, zip, reboot),
+            SideloadAction::Cancel => {
+                self.sideload_cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+            }
+            SideloadAction::None => {}
+        }
+
+        match self.adb_keys_window.show(ctx, &self.devices) {
+            AdbKeysAction::Check(device) => self.check_adb_keys(device),
+            AdbKeysAction::Regenerate => self.regenerate_adb_keys(),
+            AdbKeysAction::Revoke(device) => self.revoke_adb_keys(device),
+            AdbKeysAction::None => {}
+        }
+
+        match self.macro_window.show(ctx, &self.devices) {
+            MacroAction::Send { serial, input } => {
+                if let Some(adb_bridge) = &self.adb_bridge
+                    && let Err(e) = crate::macros::send(adb_bridge.path(), &serial, &input)
+                {
+                    self.toasts.error(t!("input-failed", error = e));
+                }
+            }
+            MacroAction::Play { serial, name, steps, speed } => self.play_macro(serial, name, steps, speed),
+            MacroAction::Stop => self.macro_cancel.store(true, std::sync::atomic::Ordering::Relaxed),
+            MacroAction::Save(macros) => {
+                if let Ok(mut config) = self.config.try_lock() {
+                    config.macros = macros;
+                    if let Err(e) = config.save() {
+                        self.toasts.error(t!("macros-save-failed", error = e));
+                    }
+                }
+            }
+            MacroAction::None => {}
+        }
+
+        match self.gesture_designer.show(ctx) {
+            GestureDesignerAction::Try(gesture) => self.perform_gesture(gesture),
+            GestureDesignerAction::Save(gestures) => {
+                if let Ok(mut config) = self.config.try_lock() {
+                    config.gestures = gestures;
+                    if let Err(e) = config.save() {
+                        self.toasts.error(t!("gestures-save-failed", error = e));
+                    }
+                }
+            }
+            GestureDesignerAction::None => {}
+        }
+
+        match self.scripts_window.show(ctx, &self.devices) {
+            ScriptsAction::Run { serial, name, source } => self.run_script(serial, name, source),
+            ScriptsAction::Stop => self.script_cancel.store(true, std::sync::atomic::Ordering::Relaxed),
+            ScriptsAction::Save(scripts) => {
+                if let Ok(mut config) = self.config.try_lock() {
+                    config.scripts = scripts;
+                    if let Err(e) = config.save() {
+                        self.toasts.error(t!("scripts-save-failed", error = e));
+                    }
+                }
+            }
+            ScriptsAction::None => {}
+        }
+
+        match self.tap_picker.show(ctx) {
+            TapPickerAction::Refresh => self.capture_tap_picker(std::time::Duration::ZERO),
+            TapPickerAction::Send { serial, input, refresh } => {
+                let result = match &self.adb_bridge {
+                    Some(adb_bridge) => crate::macros::send(adb_bridge.path(), &serial, &input),
+                    None => Err(anyhow::anyhow!(t!("adb-not-configured"))),
+                };
+                match result {
+                    Ok(()) => {
+                        self.macro_window.record(input);
+                        if refresh {
+                            // Give the device a moment to react before looking again
+                            self.capture_tap_picker(std::time::Duration::from_millis(600));
+                        }
+                    }
+                    Err(e) => self.toasts.error(t!("input-failed", error = e)),
+                }
+            }
+            TapPickerAction::None => {}
+        }
+
+        match self.hierarchy_window.show(ctx) {
+            HierarchyAction::Refresh => self.capture_hierarchy(),
+            HierarchyAction::None => {}
+        }
+
+        match self.color_picker.show(ctx) {
+            ColorPickerAction::Refresh => self.capture_color_picker(),
+            ColorPickerAction::None => {}
+        }
+
+        self.pip.set_device(self.device_list.selected_device().map(|device| device.identifier.as_str()));
+        if self.pip.is_visible() {
+            ctx.request_repaint_after(self.pip.interval());
+        }
+        match self.pip.show(ctx) {
+            PipAction::Mirror => self.start_scrcpy(),
+            PipAction::None => {}
+        }
+
+        if let Some(session) = &mut self.native_preview {
+            if session.is_running() {
+                self.native_preview_window.set_frame(ctx, session.take_frame(), session.frame_count());
+                ctx.request_repaint_after(std::time::Duration::from_millis(16));
+            } else {
+                self.stop_native_preview();
+                self.toasts.warning(t!("native-preview-ended"));
+            }
+        }
+        match self.native_preview_window.show(ctx) {
+            NativePreviewAction::Stop => self.stop_native_preview(),
+            NativePreviewAction::None => {}
+        }
+
+        if let Some(session) = &mut self.shell_session {
+            self.shell_console.push_output(&session.take_output());
+            if session.is_running() {
+                ctx.request_repaint_after(std::time::Duration::from_millis(50));
+            } else {
+                self.stop_shell_console();
+                self.shell_console.set_ended();
+            }
+        }
+        match self.shell_console.show(ctx) {
+            ShellConsoleAction::Send(bytes) => {
+                if let Some(session) = &mut self.shell_session
+                    && let Err(e) = session.write(&bytes)
+                {
+                    self.toasts.error(t!("shell-write-failed", error = e));
+                }
+            }
+            ShellConsoleAction::Restart => self.start_shell_console(),
+            ShellConsoleAction::Close => self.stop_shell_console(),
+            ShellConsoleAction::None => {}
+        }
+
+        let selected = self.device_list.selected_device().map(|device| device.identifier.clone());
+        self.shell_command.set_device(selected.as_deref());
+        let (shell_history, shell_snippets) = self
+            .config
+            .try_lock()
+            .map(|config| {
+                let history = selected.as_ref().and_then(|serial| config.shell_history.get(serial)).cloned();
+                (history.unwrap_or_default(), config.shell_snippets.clone())
+            })
+            .unwrap_or_default();
+        match self.shell_command.show(ctx, &shell_history, &shell_snippets) {
+            ShellCommandAction::Run(command) => self.run_shell_command(command),
+            ShellCommandAction::SaveSnippet(snippet) => self.save_shell_snippet(snippet),
+            ShellCommandAction::DeleteSnippet(name) => self.delete_shell_snippet(&name),
+            ShellCommandAction::None => {}
+        }
+
+        self.screenshot_diff.set_devices(self.usable_serials());
+        match self.screenshot_diff.show(ctx) {
+            ScreenshotDiffAction::Capture { side, device } => self.capture_for_diff(side, device),
+            ScreenshotDiffAction::None => {}
+        }
+
+        self.automation_window.set_devices(self.usable_serials());
+        match self.automation_window.show(ctx) {
+            AutomationWindowAction::RunNow(rule) => {
+                let firings = crate::automation::fire_on(&rule, &self.usable_serials());
+                if firings.is_empty() {
+                    self.toasts.warning(t!("automation-no-device", rule = rule.name));
+                }
+                self.run_firings(firings);
+            }
+            AutomationWindowAction::Save(rules) => {
+                if let Ok(mut config) = self.config.try_lock() {
+                    config.automation_rules = rules;
+                    if let Err(e) = config.save() {
+                        self.toasts.error(t!("automation-save-failed", error = e));
+                    }
+                }
+            }
+            AutomationWindowAction::None => {}
+        }
+
+        self.tunnels_window.set_status(
+            self.tunnels.iter().map(|tunnel| tunnel.name.clone()).collect(),
+            self.opening_tunnels.clone(),
+        );
+        match self.tunnels_window.show(ctx) {
+            TunnelAction::Open(profile) => self.open_tunnel(profile),
+            TunnelAction::Close(name) => self.close_tunnel(&name),
+            TunnelAction::Save(profiles) => {
+                if let Ok(mut config) = self.config.try_lock() {
+                    config.ssh_tunnels = profiles;
+                    if let Err(e) = config.save() {
+                        self.toasts.error(t!("tunnels-save-failed", error = e));
+                    }
+                }
+            }
+            TunnelAction::None => {}
+        }
+
+        match self.stream_window.show(ctx) {
+            StreamAction::Preflight(stream_config) => self.preflight_stream(stream_config),
+            StreamAction::Start(stream_config) => self.start_stream(stream_config),
+            StreamAction::Stop => self.stop_stream(),
+            StreamAction::None => {}
+        }
+
+        match self.benchmark_window.show(ctx) {
+            BenchmarkAction::Start(cases) => self.start_benchmark(cases),
+            BenchmarkAction::Cancel => {
+                self.benchmark_cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+            }
+            BenchmarkAction::Apply(case) => {
+                if let Ok(mut config) = self.config.try_lock() {
+                    config.video_codec = case.codec.clone();
+                    config.bitrate = case.bitrate.clone();
+                    config.dimension = case.max_size;
+                    let _ = config.save();
+                }
+                self.toasts.success(t!("benchmark-applied", case = case.label()));
+            }
+            BenchmarkAction::None => {}
+        }
+
+        let share_opt_in = self.config.try_lock().map(|config| config.share_opt_in).unwrap_or(false);
+        match self.share_window.show(ctx, self.share_server.as_ref(), share_opt_in) {
+            ShareAction::Start { port, duration } => self.start_share(port, duration),
+            ShareAction::Stop => self.stop_share(),
+            ShareAction::None => {}
+        }
+
+        if let ActivityViewerAction::Refresh = self.activity_viewer.show(ctx) {
+            self.load_activity_stack();
+        }
+
+        self.clipboard_history.show(ctx);
+
+        let connected: Vec<String> = self
+            .device_list
+            .devices()
+            .iter()
+            .filter(|device| device.is_usable())
+            .map(|device| device.identifier.clone())
+            .collect();
+        match self.remote_assist.show(ctx, &connected) {
+            RemoteAssistAction::Wireless(action) => self.handle_wireless_adb_action(action),
+            RemoteAssistAction::Start(serial) => {
+                if self.device_list.select(&serial) {
+                    self.start_scrcpy_with(Some(&SessionTemplate::remote_assistance()));
+                }
+            }
+            RemoteAssistAction::None => {}
+        }
+
+        match self.capability_wizard.show(ctx) {
+            CapabilityAction::Recheck => self.probe_capabilities(),
+            CapabilityAction::GrantCompanion(package) => self.grant_companion(package),
+            CapabilityAction::None => {}
+        }
+
+        let intent_history = self
+            .config
+            .try_lock()
+            .map(|config| config.intent_history.clone())
+            .unwrap_or_default();
+        if let IntentSenderAction::Send(spec) = self.intent_sender.show(ctx, &intent_history) {
+            self.send_intent(spec);
+        }
+
+        match self.app_details_window.show(ctx) {
+            AppDetailsAction::Refresh(package) => self.load_app_details(package),
+            AppDetailsAction::Permissions(package) => self.run_app_action(AppAction::Permissions, package),
+            AppDetailsAction::None => {}
+        }
+
+        match self.permissions_window.show(ctx) {
+            PermissionsAction::Refresh(package) => self.load_permissions(package, None),
+            PermissionsAction::Set { package, permission, grant } => {
+                self.load_permissions(package, Some((permission, grant)))
+            }
+            PermissionsAction::None => {}
+        }
+
+        // Confirm before wiping an app's data
+        if let Some(package) = self.clear_data_confirm.clone() {
+            egui::Window::new(format!("{} {}", egui_phosphor::fill::WARNING, t!("apps-clear-data-title")))
+                .collapsible(false)
+                .resizable(false)
+                .frame(egui::Frame::window(&egui::Style::default()).corner_radius(egui::CornerRadius::same(0)))
+                .pivot(egui::Align2::CENTER_CENTER)
+                .show(ctx, |ui| {
+                    ui.label(t!("apps-clear-data-confirm", package = package));
+                    ui.label(
+                        RichText::new(t!("apps-clear-data-warning"))
+                            .color(Color32::YELLOW),
+                    );
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add(egui::Button::new(RichText::new(t!("apps-clear-data-button")).size(12.0).color(Color32::WHITE)).fill(Color32::DARK_RED))
+                            .clicked()
+                        {
+                            self.clear_data_confirm = None;
+                            self.run_app_action(AppAction::ClearData, package.clone());
+                        }
+                        if ui.add(egui::Button::new(RichText::new(t!("dialog-cancel")).size(12.0))).clicked() {
+                            self.clear_data_confirm = None;
+                        }
+                    });
+                });
+        }
+
+        // Offer the crash report left by a previous run
+        if let Some(report) = self.crash_reports.first().cloned() {
+            egui::Window::new(format!("{} {}", egui_phosphor::fill::BUG, t!("crash-title")))
+                .collapsible(false)
+                .resizable(false)
+                .frame(egui::Frame::window(&egui::Style::default()).corner_radius(egui::CornerRadius::same(0)))
+                .pivot(egui::Align2::CENTER_CENTER)
+                .show(ctx, |ui| {
+                    ui.label(t!("crash-saved"));
+                    ui.label(RichText::new(report.display().to_string()).monospace().size(11.0));
+                    if self.crash_reports.len() > 1 {
+                        ui.label(
+                            RichText::new(t!("crash-older", count = self.crash_reports.len() - 1))
+                                .color(Color32::GRAY),
+                        );
+                    }
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        if ui.add(egui::Button::new(RichText::new(t!("crash-open-report")).size(12.0))).clicked() {
+                            let _ = crate::utils::open_url(&report.display().to_string());
+                        }
+                        if ui.add(egui::Button::new(RichText::new(t!("crash-open-folder")).size(12.0))).clicked()
+                            && let Some(dir) = report.parent()
+                        {
+                            let _ = crate::utils::open_url(&dir.display().to_string());
+                        }
+                        if ui.add(egui::Button::new(RichText::new(t!("crash-report-issue")).size(12.0))).clicked()
+                            && let Some(url) = crate::crash::issue_url(&report)
+                        {
+                            // The browser gets the whole link; `cmd /C start` would cut it at the first `&`
+                            ui.ctx().open_url(egui::OpenUrl::new_tab(url));
+                        }
+                        if ui.add(egui::Button::new(RichText::new(t!("dialog-dismiss")).size(12.0))).clicked() {
+                            for report in self.crash_reports.drain(..) {
+                                crate::crash::mark_seen(&report);
+                            }
+                        }
+                    });
+                });
+        }
+
         // Show Screenshot Success Dialog
-        if let Some(success_message) = &self.screenshot_success_dialog {
-            let message_clone = success_message.clone();
-            egui::Window::new(format!("{} Screenshot Success", egui_phosphor::fill::CHECK_CIRCLE))
+        if let Some(file_path) = &self.screenshot_success_dialog {
+            let file_path = file_path.clone();
+            egui::Window::new(format!("{} {}", egui_phosphor::fill::CHECK_CIRCLE, t!("screenshot-success-title")))
                 .collapsible(false)
                 .resizable(false)
-                .fixed_size(egui::vec2(400.0, 120.0))
+                .fixed_size(egui::vec2(400.0, 140.0))
                 .frame(egui::Frame::window(&egui::Style::default()).corner_radius(egui::CornerRadius::same(0)))
                 .pivot(egui::Align2::CENTER_CENTER)
                 .show(ctx, |ui| {
                     ui.vertical_centered(|ui| {
-                        ui.label(egui::RichText::new(format!("{}", egui_phosphor::fill::CHECK_CIRCLE)).size(32.0).color(Color32::GREEN));
+                        ui.label(egui::RichText::new(egui_phosphor::fill::CHECK_CIRCLE.to_string()).size(32.0).color(Color32::GREEN));
                         ui.add_space(8.0);
-                        ui.label(egui::RichText::new("Screenshot Saved Successfully!").size(14.0).strong());
+                        ui.label(egui::RichText::new(t!("screenshot-saved")).size(14.0).strong());
                         ui.add_space(4.0);
-                        ui.label(egui::RichText::new(message_clone).size(11.0).monospace());
+                        ui.label(egui::RichText::new(t!("screenshot-saved-to", path = file_path.display())).size(11.0).monospace());
                         ui.add_space(12.0);
-                        if ui.add(egui::Button::new(egui::RichText::new("OK").size(12.0)).min_size(egui::vec2(60.0, 24.0))).clicked() {
-                            self.screenshot_success_dialog = None;
-                        }
+                        ui.horizontal(|ui| {
+                            if ui.add(egui::Button::new(egui::RichText::new(format!("{} {}", egui_phosphor::fill::FOLDER_OPEN, t!("screenshot-open-folder"))).size(12.0)).min_size(egui::vec2(60.0, 24.0))).clicked() {
+                                if let Some(dir) = file_path.parent()
+                                    && let Err(e) = crate::utils::open_folder(dir)
+                                {
+                                    self.toasts.error(t!("open-folder-failed", error = e));
+                                }
+                                self.screenshot_success_dialog = None;
+                            }
+                            if ui.add(egui::Button::new(egui::RichText::new(t!("dialog-ok")).size(12.0)).min_size(egui::vec2(60.0, 24.0))).clicked() {
+                                self.screenshot_success_dialog = None;
+                            }
+                        });
                     });
                 });
         }
@@ -1886,7 +7093,7 @@ impl eframe::App for DroidViewApp {
         // Show Screen Recording Success Dialog
         if let Some(success_message) = &self.screenrecord_success_dialog {
             let message_clone = success_message.clone();
-            egui::Window::new(format!("{} Screen Recording Success", egui_phosphor::fill::CHECK_CIRCLE))
+            egui::Window::new(format!("{} {}", egui_phosphor::fill::CHECK_CIRCLE, t!("screenrecord-success-title")))
                 .collapsible(false)
                 .resizable(false)
                 .fixed_size(egui::vec2(400.0, 120.0))
@@ -1894,20 +7101,197 @@ impl eframe::App for DroidViewApp {
                 .pivot(egui::Align2::CENTER_CENTER)
                 .show(ctx, |ui| {
                     ui.vertical_centered(|ui| {
-                        ui.label(egui::RichText::new(format!("{}", egui_phosphor::fill::CHECK_CIRCLE)).size(32.0).color(Color32::GREEN));
+                        ui.label(egui::RichText::new(egui_phosphor::fill::CHECK_CIRCLE.to_string()).size(32.0).color(Color32::GREEN));
                         ui.add_space(8.0);
-                        ui.label(egui::RichText::new("Screen Recording Saved Successfully!").size(14.0).strong());
+                        ui.label(egui::RichText::new(t!("screenrecord-saved-dialog")).size(14.0).strong());
                         ui.add_space(4.0);
                         ui.label(egui::RichText::new(message_clone).size(11.0).monospace());
                         ui.add_space(12.0);
-                        if ui.add(egui::Button::new(egui::RichText::new("OK").size(12.0)).min_size(egui::vec2(60.0, 24.0))).clicked() {
+                        if ui.add(egui::Button::new(egui::RichText::new(t!("dialog-ok")).size(12.0)).min_size(egui::vec2(60.0, 24.0))).clicked() {
                             self.screenrecord_success_dialog = None;
                         }
                     });
                 });
         }
 
+        // Drag-and-drop: highlight the window while files hover over it
+        if ctx.input(|i| !i.raw.hovered_files.is_empty()) {
+            let screen = ctx.screen_rect();
+            let painter = ctx.layer_painter(egui::LayerId::new(
+                egui::Order::Foreground,
+                egui::Id::new("file_drop_overlay"),
+            ));
+            painter.rect_filled(screen, 0.0, Color32::from_black_alpha(160));
+            painter.text(
+                screen.center(),
+                egui::Align2::CENTER_CENTER,
+                format!("{} {}", egui_phosphor::fill::UPLOAD_SIMPLE, t!("drop-to-push")),
+                egui::FontId::proportional(20.0),
+                Color32::WHITE,
+            );
+        }
+        self.handle_dropped_files(ctx);
+
+        if let FilePushAction::Start { files, destination } = self.file_push_dialog.show(ctx) {
+            self.start_file_push(files, destination);
+        }
+
         self.update_background_tasks();
         self.settings_window.show(ctx);
+        self.help_window.show(ctx);
+        self.toasts.show(ctx);
+        if self.command_palette.is_open() {
+            let (entries, mut commands): (Vec<PaletteEntry>, Vec<PaletteCommand>) =
+                self.palette_commands().into_iter().unzip();
+            if let Some(index) = self.command_palette.show(ctx, &entries) {
+                let command = commands.swap_remove(index);
+                self.run_palette_command(ctx, command);
+            }
+        }
+        let shortcuts_action = self.shortcuts_window.show(ctx, &self.shortcuts);
+        self.rebind_shortcut(shortcuts_action);
+        match self.update_notice.show(ctx) {
+            UpdateNoticeAction::OpenChangelog(url) => {
+                if let Err(e) = crate::utils::open_url(&url) {
+                    self.toasts.error(t!("browser-failed", error = e));
+                }
+            }
+            UpdateNoticeAction::Skip(version) => {
+                let mut config = self.config.try_lock().unwrap();
+                config.skipped_update = Some(version);
+                let _ = config.save();
+            }
+            UpdateNoticeAction::None => {}
+        }
+        self.gallery_window.show(ctx);
+        if let RecordingLibraryAction::Export { path, format, preset } = self.recording_library.show(ctx) {
+            self.export_recording(path, format, preset);
+        }
+    }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        if let Some(geometry) = self.window_geometry
+            && let Ok(mut config) = self.config.try_lock()
+        {
+            config.window = Some(geometry);
+            if let Err(e) = config.save() {
+                error!("Failed to save window geometry: {}", e);
+            }
+        }
+        if let Some(session) = self.stream_session.take() {
+            session.stop();
+        }
+        if let Some(session) = self.native_preview.take() {
+            session.stop();
+        }
+        self.stop_shell_console();
+        self.stop_share();
+        if let Some(server) = self.api_server.take() {
+            server.stop();
+        }
+        for tunnel in self.tunnels.drain(..) {
+            tunnel.close(self.adb_bridge.as_ref());
+        }
+        if let Some(adb_bridge) = &self.adb_bridge {
+            for device in self.tethered.drain(..) {
+                let _ = crate::gnirehtet::disable(adb_bridge.path(), &device);
+            }
+            for device in self.battery_simulations.drain(..) {
+                let _ = crate::automation::shell(adb_bridge.path(), &device, "dumpsys battery reset");
+            }
+        }
+        if let Some(relay) = self.gnirehtet_relay.take() {
+            relay.stop();
+        }
+        self.stop_legacy_audio();
+        self.route_cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+}
+
+/// `rtmp://host/app/<key>` → `rtmp://host/…`, for showing where a stream goes without its key
+fn redact_stream_url(url: &str) -> String {
+    match url.split_once("://") {
+        Some((scheme, rest)) => format!("{}://{}/…", scheme, rest.split(['/', '?']).next().unwrap_or_default()),
+        None => url.to_string(),
+    }
+}
+
+/// Unset or blank, as left behind by clearing the field in Settings
+fn tool_unset(path: Option<&str>) -> bool {
+    path.map(str::trim).unwrap_or_default().is_empty()
+}
+
+/// Send screenrecord SIGINT, with pkill where toybox has no killall
+fn interrupt_screenrecord(adb_path: &str, device_id: &str) -> Result<(), String> {
+    let output = crate::bridge::adb_command(adb_path)
+        .args(["-s", device_id, "shell", "killall -2 screenrecord || pkill -2 screenrecord"])
+        .output()
+        .map_err(|e| e.to_string())?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    match stderr.lines().map(str::trim).find(|line| !line.is_empty()) {
+        Some(message) => Err(message.to_string()),
+        None => Err(t!("screenrecord-not-running")),
+    }
+}
+
+/// Run screenrecord to completion (time limit or SIGINT), then pull and index the result
+fn record_screen(
+    adb_path: &str,
+    device: &Device,
+    time_limit: u32,
+    bit_rate: u32,
+    dir: &std::path::Path,
+) -> Result<std::path::PathBuf, String> {
+    const REMOTE_PATH: &str = "/sdcard/droidview_screenrecord.mp4";
+    let device_id = device.identifier.as_str();
+    // screenrecord captures at the display's native size
+    let resolution = device
+        .get_dimensions(adb_path)
+        .ok()
+        .flatten()
+        .map(|(width, height)| format!("{}x{}", width, height));
+
+    // An interrupted screenrecord exits non-zero but still finalizes the file, so only spawn errors matter here
+    let started = std::time::Instant::now();
+    crate::bridge::adb_command(adb_path)
+        .args(["-s", device_id, "shell", "screenrecord", REMOTE_PATH])
+        .args(["--time-limit", &time_limit.to_string(), "--bit-rate", &bit_rate.to_string()])
+        .output()
+        .map_err(|e| t!("screenrecord-error", error = e))?;
+    let duration_secs = started.elapsed().as_secs().min(time_limit as u64);
+
+    let _ = std::fs::create_dir_all(dir);
+    let file_name = crate::utils::expand_filename_template(
+        "screenrecord_{serial}_{timestamp}",
+        device_id,
+        &device.model,
+        chrono::Local::now(),
+        "mp4",
+    );
+    let file_path = crate::utils::unique_path(dir.join(file_name));
+    let pull = crate::bridge::adb_command(adb_path)
+        .arg("-s")
+        .arg(device_id)
+        .arg("pull")
+        .arg(REMOTE_PATH)
+        .arg(&file_path)
+        .output()
+        .map_err(|e| t!("screenrecord-pull-error", error = e))?;
+    let _ = crate::bridge::adb_command(adb_path)
+        .args(["-s", device_id, "shell", "rm", "-f", REMOTE_PATH])
+        .status();
+    if !pull.status.success() {
+        return Err(t!("screenrecord-pull-failed", error = String::from_utf8_lossy(&pull.stderr).trim()));
     }
+
+    let details = crate::gallery::RecordingDetails {
+        duration_secs,
+        resolution,
+        bitrate: bit_rate,
+    };
+    crate::gallery::record_capture(&file_path, crate::gallery::CaptureKind::Recording, device_id, Some(details));
+    Ok(file_path)
 }
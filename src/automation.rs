@@ -0,0 +1,240 @@
+//! Runs the automation rules of `AppConfig::automation_rules`
+//!
+//! The app feeds the scheduler what it sees: devices that just connected, the clock every
+//! `TICK`, and battery levels read every `BATTERY_POLL` while a battery rule is enabled. The
+//! scheduler answers with the rules to run on which device; the app carries them out.
+
+use crate::bridge::adb_command;
+use crate::config::{AutomationAction, AutomationRule, AutomationTrigger};
+use anyhow::Result;
+use chrono::{NaiveDate, NaiveDateTime, Timelike};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How often time-of-day rules are checked; well under a minute so none is missed
+pub const TICK: Duration = Duration::from_secs(15);
+pub const BATTERY_POLL: Duration = Duration::from_secs(60);
+
+/// A rule to run on a device
+#[derive(Debug, Clone, PartialEq)]
+pub struct Firing {
+    pub rule: String,
+    pub serial: String,
+    pub action: AutomationAction,
+}
+
+impl Firing {
+    fn new(rule: &AutomationRule, serial: &str) -> Self {
+        Self {
+            rule: rule.name.clone(),
+            serial: serial.to_string(),
+            action: rule.action.clone(),
+        }
+    }
+}
+
+/// Whether `rule` is on and covers the device `serial`
+pub fn applies(rule: &AutomationRule, serial: &str) -> bool {
+    let device = rule.device.trim();
+    rule.enabled && (device.is_empty() || device == serial)
+}
+
+/// Firings of `rule` on every connected device it covers, e.g. for Run now
+pub fn fire_on(rule: &AutomationRule, serials: &[String]) -> Vec<Firing> {
+    let device = rule.device.trim();
+    serials
+        .iter()
+        .filter(|serial| device.is_empty() || device == serial.as_str())
+        .map(|serial| Firing::new(rule, serial))
+        .collect()
+}
+
+/// Check a rule from the editor; names have to be unique, which the editor checks
+pub fn validate(rule: &AutomationRule) -> Result<(), String> {
+    if rule.name.trim().is_empty() {
+        return Err("Give the rule a name".to_string());
+    }
+    match &rule.trigger {
+        AutomationTrigger::TimeOfDay { hour, minute } if *hour > 23 || *minute > 59 => {
+            return Err("Not a valid time".to_string());
+        }
+        AutomationTrigger::BatteryBelow { percent } if !(1..=100).contains(percent) => {
+            return Err("The battery level has to be between 1 and 100%".to_string());
+        }
+        _ => {}
+    }
+    if let AutomationAction::Shell { command } = &rule.action
+        && command.trim().is_empty()
+    {
+        return Err("Enter the shell command to run".to_string());
+    }
+    Ok(())
+}
+
+/// What has fired already, so each rule runs once per occasion
+#[derive(Debug, Default)]
+pub struct Scheduler {
+    /// Day each time-of-day rule last ran
+    ran_on: HashMap<String, NaiveDate>,
+    /// Rule and device pairs whose battery is below the rule's level
+    below: HashSet<(String, String)>,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rules set off by `serial` becoming usable
+    pub fn on_connected(&self, rules: &[AutomationRule], serial: &str) -> Vec<Firing> {
+        rules
+            .iter()
+            .filter(|rule| rule.trigger == AutomationTrigger::DeviceConnected && applies(rule, serial))
+            .map(|rule| Firing::new(rule, serial))
+            .collect()
+    }
+
+    /// Time-of-day rules due at `now`, on the connected devices they cover
+    pub fn on_time(&mut self, rules: &[AutomationRule], serials: &[String], now: NaiveDateTime) -> Vec<Firing> {
+        let mut firings = Vec::new();
+        for rule in rules.iter().filter(|rule| rule.enabled) {
+            let AutomationTrigger::TimeOfDay { hour, minute } = rule.trigger else {
+                continue;
+            };
+            let due = now.hour() == hour as u32 && now.minute() == minute as u32;
+            if !due || self.ran_on.get(&rule.name) == Some(&now.date()) {
+                continue;
+            }
+            self.ran_on.insert(rule.name.clone(), now.date());
+            firings.extend(fire_on(rule, serials));
+        }
+        firings
+    }
+
+    /// Battery rules whose device just dropped below their level
+    pub fn on_battery(&mut self, rules: &[AutomationRule], levels: &[(String, Option<u8>)]) -> Vec<Firing> {
+        let mut firings = Vec::new();
+        for rule in rules {
+            let AutomationTrigger::BatteryBelow { percent } = rule.trigger else {
+                continue;
+            };
+            for (serial, level) in levels {
+                let Some(level) = level else {
+                    continue;
+                };
+                let key = (rule.name.clone(), serial.clone());
+                if *level >= percent {
+                    self.below.remove(&key);
+                } else if self.below.insert(key) && applies(rule, serial) {
+                    firings.push(Firing::new(rule, serial));
+                }
+            }
+        }
+        firings
+    }
+
+    /// Whether battery levels need to be read at all
+    pub fn watches_battery(rules: &[AutomationRule]) -> bool {
+        rules
+            .iter()
+            .any(|rule| rule.enabled && matches!(rule.trigger, AutomationTrigger::BatteryBelow { .. }))
+    }
+}
+
+/// The device's screen as PNG bytes
+pub fn screencap(adb_path: &str, serial: &str) -> Result<Vec<u8>> {
+    let output = adb_command(adb_path).args(["-s", serial, "exec-out", "screencap", "-p"]).output()?;
+    if !output.status.success() || output.stdout.is_empty() {
+        return Err(anyhow::anyhow!("Screenshot failed: exit code {}", output.status));
+    }
+    Ok(output.stdout)
+}
+
+/// Save a screenshot of the device to `dir`, named after `template`
+pub fn screenshot(adb_path: &str, serial: &str, model: &str, dir: &Path, template: &str) -> Result<PathBuf> {
+    let png = screencap(adb_path, serial)?;
+    let file_name = crate::utils::expand_filename_template(template, serial, model, chrono::Local::now(), "png");
+    let path = crate::utils::unique_path(dir.join(file_name));
+    std::fs::create_dir_all(dir)?;
+    std::fs::write(&path, &png)?;
+    crate::gallery::record_capture(&path, crate::gallery::CaptureKind::Screenshot, serial, None);
+    Ok(path)
+}
+
+/// Run a shell command on the device and return what it printed
+pub fn shell(adb_path: &str, serial: &str, command: &str) -> Result<String> {
+    let output = adb_command(adb_path).args(["-s", serial, "shell", command]).output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(anyhow::anyhow!(if stderr.is_empty() { stdout } else { stderr }));
+    }
+    Ok(stdout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(name: &str, device: &str, trigger: AutomationTrigger) -> AutomationRule {
+        AutomationRule {
+            name: name.to_string(),
+            device: device.to_string(),
+            trigger,
+            ..AutomationRule::default()
+        }
+    }
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 5, day).unwrap().and_hms_opt(hour, minute, 30).unwrap()
+    }
+
+    #[test]
+    fn fires_on_connect_for_matching_devices() {
+        let rules = vec![
+            rule("any", "", AutomationTrigger::DeviceConnected),
+            rule("pixel", "PIXEL", AutomationTrigger::DeviceConnected),
+            AutomationRule { enabled: false, ..rule("off", "", AutomationTrigger::DeviceConnected) },
+        ];
+        let fired: Vec<String> = Scheduler::new().on_connected(&rules, "OTHER").into_iter().map(|f| f.rule).collect();
+        assert_eq!(fired, ["any"]);
+        assert_eq!(Scheduler::new().on_connected(&rules, "PIXEL").len(), 2);
+    }
+
+    #[test]
+    fn fires_time_of_day_once_a_day() {
+        let rules = vec![rule("morning", "", AutomationTrigger::TimeOfDay { hour: 9, minute: 0 })];
+        let serials = vec!["A".to_string(), "B".to_string()];
+        let mut scheduler = Scheduler::new();
+        assert!(scheduler.on_time(&rules, &serials, at(1, 8, 59)).is_empty());
+        assert_eq!(scheduler.on_time(&rules, &serials, at(1, 9, 0)).len(), 2);
+        assert!(scheduler.on_time(&rules, &serials, at(1, 9, 0)).is_empty());
+        assert_eq!(scheduler.on_time(&rules, &serials, at(2, 9, 0)).len(), 2);
+    }
+
+    #[test]
+    fn fires_battery_once_per_drop() {
+        let rules = vec![rule("low", "", AutomationTrigger::BatteryBelow { percent: 20 })];
+        let mut scheduler = Scheduler::new();
+        let level = |percent| vec![("A".to_string(), Some(percent))];
+        assert!(scheduler.on_battery(&rules, &level(50)).is_empty());
+        assert_eq!(scheduler.on_battery(&rules, &level(19)).len(), 1);
+        assert!(scheduler.on_battery(&rules, &level(15)).is_empty());
+        assert!(scheduler.on_battery(&rules, &level(80)).is_empty());
+        assert_eq!(scheduler.on_battery(&rules, &level(10)).len(), 1);
+        assert!(Scheduler::watches_battery(&rules));
+    }
+
+    #[test]
+    fn validates_rules() {
+        assert!(validate(&rule("", "", AutomationTrigger::DeviceConnected)).is_err());
+        assert!(validate(&rule("late", "", AutomationTrigger::TimeOfDay { hour: 24, minute: 0 })).is_err());
+        let empty_shell = AutomationRule {
+            action: AutomationAction::Shell { command: " ".to_string() },
+            ..rule("shell", "", AutomationTrigger::DeviceConnected)
+        };
+        assert!(validate(&empty_shell).is_err());
+        assert!(validate(&rule("ok", "", AutomationTrigger::BatteryBelow { percent: 15 })).is_ok());
+    }
+}
@@ -0,0 +1,272 @@
+use std::io::{BufRead, BufReader, Read};
+use crate::bridge::adb_command;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// How long each benchmarked scrcpy session runs
+pub const CASE_DURATION: Duration = Duration::from_secs(8);
+
+/// Bitrates and max sizes combined with every supported codec
+const BITRATES: [&str; 2] = ["4M", "8M"];
+const MAX_SIZES: [Option<u32>; 2] = [Some(1024), None];
+
+/// One scrcpy configuration under test
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkCase {
+    pub codec: String,
+    pub bitrate: String,
+    pub max_size: Option<u32>,
+}
+
+impl BenchmarkCase {
+    pub fn label(&self) -> String {
+        let size = self.max_size.map(|size| size.to_string()).unwrap_or_else(|| "native".to_string());
+        format!("{} · {} · {}", self.codec, self.bitrate, size)
+    }
+}
+
+/// Measured outcome of one case
+#[derive(Debug, Clone)]
+pub struct BenchmarkResult {
+    pub case: BenchmarkCase,
+    pub avg_fps: f32,
+    pub min_fps: u32,
+    pub skipped_frames: u32,
+    pub error: Option<String>,
+}
+
+impl BenchmarkResult {
+    /// Higher is better: frame rate, minus a penalty for frames scrcpy had to drop
+    pub fn score(&self) -> f32 {
+        if self.error.is_some() {
+            return f32::MIN;
+        }
+        self.avg_fps - self.skipped_frames as f32 / CASE_DURATION.as_secs_f32()
+    }
+}
+
+/// Every codec × bitrate × max size combination, codecs the device cannot encode left out
+pub fn default_cases(codecs: &[String]) -> Vec<BenchmarkCase> {
+    let codecs: Vec<String> = if codecs.is_empty() {
+        vec!["h264".to_string()]
+    } else {
+        crate::config::VIDEO_CODECS
+            .iter()
+            .filter(|codec| codecs.iter().any(|c| c == *codec))
+            .map(|codec| codec.to_string())
+            .collect()
+    };
+
+    let mut cases = Vec::new();
+    for codec in &codecs {
+        for bitrate in BITRATES {
+            for max_size in MAX_SIZES {
+                cases.push(BenchmarkCase {
+                    codec: codec.clone(),
+                    bitrate: bitrate.to_string(),
+                    max_size,
+                });
+            }
+        }
+    }
+    cases
+}
+
+/// Parse `INFO: 58 fps` or `INFO: 58 fps (+3 frames skipped)` from `scrcpy --print-fps`
+pub fn parse_fps_line(line: &str) -> Option<(u32, u32)> {
+    let (before, after) = line.split_once(" fps")?;
+    let fps = before.split_whitespace().last()?.parse().ok()?;
+    let skipped = after
+        .split_once('+')
+        .and_then(|(_, rest)| rest.split_whitespace().next())
+        .and_then(|count| count.parse().ok())
+        .unwrap_or(0);
+    Some((fps, skipped))
+}
+
+/// Run one short scrcpy session and collect its fps counter
+pub fn run_case(scrcpy_path: &str, device_id: &str, case: &BenchmarkCase, cancel: &AtomicBool) -> BenchmarkResult {
+    let mut result = BenchmarkResult {
+        case: case.clone(),
+        avg_fps: 0.0,
+        min_fps: 0,
+        skipped_frames: 0,
+        error: None,
+    };
+
+    let mut args = vec![
+        "-s".to_string(),
+        device_id.to_string(),
+        format!("--video-codec={}", case.codec),
+        format!("--video-bit-rate={}", case.bitrate),
+        "--print-fps".to_string(),
+        "--no-audio".to_string(),
+        "--window-title=DroidView benchmark".to_string(),
+    ];
+    if let Some(max_size) = case.max_size {
+        args.push(format!("--max-size={}", max_size));
+    }
+
+    let mut child = match adb_command(scrcpy_path)
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            result.error = Some(e.to_string());
+            return result;
+        }
+    };
+
+    // scrcpy logs to either stream depending on version and platform
+    let (sender, receiver) = mpsc::channel();
+    let streams: [Option<Box<dyn Read + Send>>; 2] = [
+        child.stdout.take().map(|s| Box::new(s) as Box<dyn Read + Send>),
+        child.stderr.take().map(|s| Box::new(s) as Box<dyn Read + Send>),
+    ];
+    for stream in streams.into_iter().flatten() {
+        let sender = sender.clone();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stream).lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    drop(sender);
+
+    let started = Instant::now();
+    let mut samples = Vec::new();
+    let mut last_error = None;
+    while started.elapsed() < CASE_DURATION && !cancel.load(Ordering::Relaxed) {
+        match receiver.recv_timeout(Duration::from_millis(200)) {
+            Ok(line) => {
+                if let Some(sample) = parse_fps_line(&line) {
+                    samples.push(sample);
+                } else if line.contains("ERROR") {
+                    last_error = Some(line.trim().to_string());
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+        if let Ok(Some(_)) = child.try_wait() {
+            break;
+        }
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+
+    // The first second includes encoder start-up
+    let steady: Vec<(u32, u32)> = samples.iter().skip(1).copied().collect();
+    let samples = if steady.is_empty() { samples } else { steady };
+    if samples.is_empty() {
+        result.error = Some(last_error.unwrap_or_else(|| "No frames reported".to_string()));
+        return result;
+    }
+
+    result.avg_fps = samples.iter().map(|(fps, _)| *fps as f32).sum::<f32>() / samples.len() as f32;
+    result.min_fps = samples.iter().map(|(fps, _)| *fps).min().unwrap_or(0);
+    result.skipped_frames = samples.iter().map(|(_, skipped)| *skipped).sum();
+    result
+}
+
+/// `8M`, `800K` or `2000000` in bits per second
+fn bitrate_bps(bitrate: &str) -> Option<u64> {
+    let bitrate = bitrate.trim();
+    let (number, factor) = match bitrate.char_indices().last()? {
+        (index, 'K' | 'k') => (&bitrate[..index], 1_000),
+        (index, 'M' | 'm') => (&bitrate[..index], 1_000_000),
+        _ => (bitrate, 1),
+    };
+    number.parse::<u64>().ok().map(|number| number * factor)
+}
+
+/// The best successful result. Scores within a frame of each other are ties, settled by the
+/// steadier frame rate, then fewer skipped frames, then the larger size and higher bitrate
+pub fn best_result(results: &[BenchmarkResult]) -> Option<&BenchmarkResult> {
+    results
+        .iter()
+        .filter(|result| result.error.is_none())
+        .max_by(|a, b| {
+            a.score()
+                .round()
+                .total_cmp(&b.score().round())
+                .then(a.min_fps.cmp(&b.min_fps))
+                .then(b.skipped_frames.cmp(&a.skipped_frames))
+                .then(a.case.max_size.unwrap_or(u32::MAX).cmp(&b.case.max_size.unwrap_or(u32::MAX)))
+                .then(bitrate_bps(&a.case.bitrate).cmp(&bitrate_bps(&b.case.bitrate)))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(bitrate: &str, max_size: Option<u32>, avg_fps: f32, min_fps: u32, skipped_frames: u32) -> BenchmarkResult {
+        BenchmarkResult {
+            case: BenchmarkCase { codec: "h264".to_string(), bitrate: bitrate.to_string(), max_size },
+            avg_fps,
+            min_fps,
+            skipped_frames,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn parses_fps_lines() {
+        for (line, expected) in [
+            ("INFO: 58 fps", Some((58, 0))),
+            ("INFO: 60 fps (+3 frames skipped)", Some((60, 3))),
+            ("[server] INFO: 24 fps (+12 frames skipped)", Some((24, 12))),
+            ("INFO: Renderer: opengl", None),
+            ("INFO: many fps", None),
+            ("", None),
+        ] {
+            assert_eq!(parse_fps_line(line), expected, "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn parses_bitrates() {
+        assert_eq!(bitrate_bps("8M"), Some(8_000_000));
+        assert_eq!(bitrate_bps("16M"), Some(16_000_000));
+        assert_eq!(bitrate_bps("800k"), Some(800_000));
+        assert_eq!(bitrate_bps("2000000"), Some(2_000_000));
+        assert_eq!(bitrate_bps("fast"), None);
+    }
+
+    #[test]
+    fn builds_cases_for_supported_codecs() {
+        let cases = default_cases(&["h265".to_string(), "vp9".to_string(), "h264".to_string()]);
+        assert_eq!(cases.len(), 8);
+        assert!(cases.iter().all(|case| case.codec == "h264" || case.codec == "h265"));
+        assert!(default_cases(&[]).iter().all(|case| case.codec == "h264"));
+    }
+
+    #[test]
+    fn best_result_settles_ties_on_measurements() {
+        let failed = BenchmarkResult { error: Some("No frames reported".to_string()), ..result("8M", None, 90.0, 90, 0) };
+        let results = [failed, result("4M", Some(1024), 58.0, 50, 0), result("8M", Some(1024), 40.0, 38, 0)];
+        assert_eq!(best_result(&results).unwrap().case.bitrate, "4M");
+
+        // Same rounded score: the steadier one wins, then the one that skipped less
+        let results = [result("8M", None, 58.2, 40, 0), result("4M", Some(1024), 57.9, 52, 0)];
+        assert_eq!(best_result(&results).unwrap().case.bitrate, "4M");
+        let results = [result("8M", None, 60.0, 55, 8), result("4M", Some(1024), 59.0, 55, 0)];
+        assert_eq!(best_result(&results).unwrap().case.bitrate, "4M");
+
+        // Identical measurements: larger size, then higher bitrate, compared as numbers
+        let results = [result("8M", Some(1024), 60.0, 60, 0), result("16M", Some(1024), 60.0, 60, 0)];
+        assert_eq!(best_result(&results).unwrap().case.bitrate, "16M");
+        let results = [result("16M", Some(1024), 60.0, 60, 0), result("4M", None, 60.0, 60, 0)];
+        assert_eq!(best_result(&results).unwrap().case.bitrate, "4M");
+
+        assert!(best_result(&[]).is_none());
+    }
+}
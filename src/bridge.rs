@@ -1,8 +1,43 @@
+use crate::config::AppConfig;
+use crate::packages::user_flag;
 use anyhow::Result;
 use std::io::{BufRead, BufReader};
+use std::ffi::OsStr;
 use std::process::{Child, Command, Stdio};
+use std::sync::RwLock;
 use tokio::process::Command as TokioCommand;
 
+/// Environment variable adb (and scrcpy, through adb) reads the server address from
+const ADB_SERVER_SOCKET: &str = "ADB_SERVER_SOCKET";
+
+/// `tcp:host:port` of the remote adb server in use, `None` for the local one
+static REMOTE_SERVER: RwLock<Option<String>> = RwLock::new(None);
+
+/// Talk to the adb server at `host:port` instead of the local one; `None` goes back to local
+pub fn set_adb_server(server: Option<(&str, u16)>) {
+    let socket = server.map(|(host, port)| format!("tcp:{}:{}", host, port));
+    match &socket {
+        Some(socket) => tracing::info!("Using the adb server at {}", socket),
+        None => tracing::debug!("Using the local adb server"),
+    }
+    if let Ok(mut current) = REMOTE_SERVER.write() {
+        *current = socket;
+    }
+}
+
+pub fn adb_server_socket() -> Option<String> {
+    REMOTE_SERVER.read().ok().and_then(|socket| socket.clone())
+}
+
+/// A command for adb, or for scrcpy which runs adb itself, aimed at the configured server
+pub fn adb_command(program: impl AsRef<OsStr>) -> Command {
+    let mut command = Command::new(program);
+    if let Some(socket) = adb_server_socket() {
+        command.env(ADB_SERVER_SOCKET, socket);
+    }
+    command
+}
+
 pub struct AdbBridge {
     path: String,
 }
@@ -11,6 +46,108 @@ pub struct ScrcpyBridge {
     path: String,
 }
 
+/// Everything needed to build a scrcpy command line
+#[derive(Debug, Clone, Default)]
+pub struct ScrcpyOptions {
+    pub bitrate: String,
+    pub video_codec: String,
+    pub orientation: Option<String>,
+    pub show_touches: bool,
+    pub fullscreen: bool,
+    pub dimension: Option<u32>,
+    pub display_id: Option<u32>,
+    pub extra_args: String,
+    pub turn_screen_off: bool,
+    pub force_adb_forward: bool,
+    pub shortcut_mods: Vec<String>,
+    /// Host of a remote adb server; the video tunnel then has to go through it
+    pub tunnel_host: Option<String>,
+}
+
+impl ScrcpyOptions {
+    /// The configured options for mirroring the device `serial`
+    pub fn for_device(config: &AppConfig, serial: &str) -> Self {
+        Self {
+            bitrate: config.bitrate.clone(),
+            video_codec: config.video_codec.clone(),
+            orientation: config.orientation.clone(),
+            show_touches: config.show_touches,
+            fullscreen: config.fullscreen,
+            dimension: config.dimension,
+            display_id: config.display_ids.get(serial).copied(),
+            extra_args: config.extra_args.clone(),
+            turn_screen_off: config.turn_screen_off,
+            force_adb_forward: config.force_adb_forward,
+            shortcut_mods: config
+                .shortcut_mods
+                .iter()
+                .filter(|combo| !combo.is_empty())
+                .cloned()
+                .collect(),
+            tunnel_host: config.remote_adb.active().map(|(host, _)| host.to_string()),
+        }
+    }
+}
+
+/// Successful result of `adb connect`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectOutcome {
+    Connected(String),
+    AlreadyConnected(String),
+}
+
+/// Successful result of `adb pair`, with the device GUID when adb reports one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PairOutcome {
+    pub endpoint: String,
+    pub guid: Option<String>,
+}
+
+/// Failures of the wireless adb commands (`connect`, `pair`, `tcpip`)
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum WirelessError {
+    #[error(
+        "Connection refused: Unable to connect to {0}. Please check if:\n\
+        • The device is powered on and connected to the same network\n\
+        • The IP address and port are correct\n\
+        • The port is not blocked by a firewall\n\
+        • ADB TCP/IP is enabled on the device (run 'adb tcpip 5555' on USB first)"
+    )]
+    ConnectionRefused(String),
+    #[error(
+        "No route to host: Cannot reach {0}. Please check if:\n\
+        • The IP address is correct\n\
+        • The device is on the same network\n\
+        • Your network allows the connection"
+    )]
+    NoRoute(String),
+    #[error(
+        "Connection timeout: Unable to reach {0}. Please check if:\n\
+        • The device is powered on\n\
+        • The IP address is correct\n\
+        • The device is on the same network"
+    )]
+    Timeout(String),
+    #[error(
+        "Wrong pairing code for {0}. Check the six-digit code shown under \
+        'Pair device with pairing code' and note that it changes every time the dialog opens"
+    )]
+    WrongPairingCode(String),
+    #[error(
+        "Protocol fault while talking to {0}: {1}. The pairing port and code expire quickly; \
+        reopen the pairing dialog on the device and try again"
+    )]
+    ProtocolFault(String, String),
+    #[error("No device available for this command: {0}")]
+    NoDevice(String),
+    #[error("Invalid endpoint: {0}")]
+    InvalidEndpoint(String),
+    #[error("Failed to run adb: {0}")]
+    Io(String),
+    #[error("{0}")]
+    Other(String),
+}
+
 impl AdbBridge {
     pub fn new(path: String) -> Self {
         Self { path }
@@ -21,7 +158,7 @@ impl AdbBridge {
     }
 
     pub fn get_devices(&self) -> Result<Vec<String>> {
-        let output = Command::new(&self.path).args(["devices"]).output()?;
+        let output = adb_command(&self.path).args(["devices"]).output()?;
 
         if !output.status.success() {
             return Err(anyhow::anyhow!("Failed to execute adb devices"));
@@ -45,7 +182,7 @@ impl AdbBridge {
     }
 
     pub fn shell(&self, command: &str, device_id: Option<&str>) -> Result<String> {
-        let mut cmd = Command::new(&self.path);
+        let mut cmd = adb_command(&self.path);
 
         if let Some(device) = device_id {
             cmd.args(["-s", device]);
@@ -62,94 +199,201 @@ impl AdbBridge {
         Ok(String::from_utf8_lossy(&output.stdout).to_string())
     }
 
-    pub fn tcpip(&self, port: u16, device_id: Option<&str>) -> Result<()> {
-        let mut cmd = Command::new(&self.path);
+    /// Copy a local file or folder to the device with `adb push`
+    pub fn push(&self, local: &std::path::Path, remote: &str, device_id: Option<&str>) -> Result<()> {
+        let mut cmd = adb_command(&self.path);
 
         if let Some(device) = device_id {
             cmd.args(["-s", device]);
         }
 
-        cmd.args(["-d", "tcpip", &port.to_string()]);
+        let output = cmd.arg("push").arg(local).arg(remote).output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let message = stderr
+                .lines()
+                .rev()
+                .find(|line| !line.trim().is_empty())
+                .unwrap_or("Push failed")
+                .trim()
+                .to_string();
+            return Err(anyhow::anyhow!(message));
+        }
 
-        let status = cmd.status()?;
+        Ok(())
+    }
 
-        if !status.success() {
-            return Err(anyhow::anyhow!("TCP/IP command failed"));
+    /// Install an APK with `adb install -r`, for one user when `user` is set
+    pub fn install(&self, apk: &std::path::Path, device_id: Option<&str>, user: Option<u32>) -> Result<()> {
+        let mut cmd = adb_command(&self.path);
+
+        if let Some(device) = device_id {
+            cmd.args(["-s", device]);
+        }
+
+        cmd.args(["install", "-r"]);
+        if let Some(user) = user {
+            cmd.args(["--user", &user.to_string()]);
+        }
+        let output = cmd.arg(apk).output()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        if !output.status.success() || !stdout.contains("Success") {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let message = stderr
+                .lines()
+                .chain(stdout.lines())
+                .find(|line| line.contains("Failure") || line.contains("error"))
+                .unwrap_or("Install failed")
+                .trim()
+                .to_string();
+            return Err(anyhow::anyhow!(message));
         }
 
         Ok(())
     }
 
-    pub fn connect(&self, ip: &str, port: u16) -> Result<()> {
-        let output = Command::new(&self.path)
-            .args(["connect", &format!("{}:{}", ip, port)])
-            .output()?;
+    /// Kill every process of a package with `am force-stop`
+    pub fn force_stop(&self, package: &str, device_id: Option<&str>, user: Option<u32>) -> Result<()> {
+        self.shell(&format!("am force-stop{} {}", user_flag(user), package), device_id)?;
+        Ok(())
+    }
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr).to_lowercase();
-            let stdout = String::from_utf8_lossy(&output.stdout).to_lowercase();
-            
-            // Check for specific error patterns
-            if stderr.contains("connection refused") || stdout.contains("connection refused") {
-                return Err(anyhow::anyhow!(
-                    "Connection refused: Unable to connect to {}:{}. Please check if:\n\
-                    • The device is powered on and connected to the same network\n\
-                    • The IP address {} is correct\n\
-                    • Port {} is not blocked by firewall\n\
-                    • ADB TCP/IP is enabled on the device (run 'adb tcpip 5555' on USB first)",
-                    ip, port, ip, port
-                ));
-            } else if stderr.contains("no route to host") || stdout.contains("no route to host") {
-                return Err(anyhow::anyhow!(
-                    "No route to host: Cannot reach {}:{}. Please check if:\n\
-                    • The IP address {} is correct\n\
-                    • The device is on the same network\n\
-                    • Your network allows the connection",
-                    ip, port, ip
-                ));
-            } else if stderr.contains("timeout") || stdout.contains("timeout") {
-                return Err(anyhow::anyhow!(
-                    "Connection timeout: Unable to reach {}:{}. Please check if:\n\
-                    • The device is powered on\n\
-                    • The IP address {} is correct\n\
-                    • The device is on the same network",
-                    ip, port, ip
-                ));
-            } else if stderr.contains("already connected") || stdout.contains("already connected") {
-                return Err(anyhow::anyhow!(
-                    "Already connected to {}:{}",
-                    ip, port
-                ));
-            } else {
-                // Generic error with captured output for debugging
-                let error_msg = if !stderr.is_empty() {
-                    stderr
-                } else if !stdout.is_empty() {
-                    stdout
-                } else {
-                    "Unknown connection error".to_string()
-                };
-                
-                return Err(anyhow::anyhow!(
-                    "Failed to connect to {}:{} - {}",
-                    ip, port, error_msg.trim()
-                ));
+    /// Start a package's launcher activity, falling back to monkey when it can't be resolved
+    pub fn launch_app(&self, package: &str, device_id: Option<&str>, user: Option<u32>) -> Result<()> {
+        let resolved = self.shell(
+            &format!(
+                "cmd package resolve-activity --brief{} -c android.intent.category.LAUNCHER {}",
+                user_flag(user),
+                package
+            ),
+            device_id,
+        )?;
+        // The component is the last line, e.g. com.example/.MainActivity
+        if let Some(component) = resolved.lines().map(str::trim).rfind(|line| line.contains('/')) {
+            let output = self.shell(&format!("am start{} -n {}", user_flag(user), component), device_id)?;
+            if !output.contains("Error") {
+                return Ok(());
             }
         }
 
+        // monkey only starts apps of the foreground user
+        if let Some(user) = user.filter(|&user| user != 0) {
+            return Err(anyhow::anyhow!("{} has no launcher activity for user {}", package, user));
+        }
+        let output = self.shell(
+            &format!("monkey -p {} -c android.intent.category.LAUNCHER 1", package),
+            device_id,
+        )?;
+        if output.contains("No activities found") || output.contains("aborted") {
+            return Err(anyhow::anyhow!("{} has no launcher activity", package));
+        }
         Ok(())
     }
 
-    pub fn pair(&self, ip: &str, port: u16, pairing_code: &str) -> Result<()> {
-        let status = Command::new(&self.path)
-            .args(["pair", &format!("{}:{}", ip, port), pairing_code])
-            .status()?;
+    /// Wipe a package's data and cache with `pm clear`
+    pub fn clear_data(&self, package: &str, device_id: Option<&str>, user: Option<u32>) -> Result<()> {
+        let output = self.shell(&format!("pm clear{} {}", user_flag(user), package), device_id)?;
+        if !output.contains("Success") {
+            let message = output.trim();
+            return Err(anyhow::anyhow!(if message.is_empty() { "pm clear failed" } else { message }.to_string()));
+        }
+        Ok(())
+    }
+
+    pub fn tcpip(&self, port: u16, device_id: Option<&str>) -> Result<(), WirelessError> {
+        let mut cmd = adb_command(&self.path);
 
-        if !status.success() {
-            return Err(anyhow::anyhow!("Pairing command failed"));
+        if let Some(device) = device_id {
+            cmd.args(["-s", device]);
         }
 
-        Ok(())
+        let output = cmd
+            .args(["tcpip", &port.to_string()])
+            .output()
+            .map_err(|e| WirelessError::Io(e.to_string()))?;
+
+        parse_tcpip_output(
+            output.status.success(),
+            &String::from_utf8_lossy(&output.stdout),
+            &String::from_utf8_lossy(&output.stderr),
+        )
+    }
+
+    pub fn connect(&self, ip: &str, port: u16) -> Result<ConnectOutcome, WirelessError> {
+        let endpoint = format!("{}:{}", ip, port);
+        let output = adb_command(&self.path)
+            .args(["connect", &endpoint])
+            .output()
+            .map_err(|e| WirelessError::Io(e.to_string()))?;
+
+        // adb connect often exits 0 on failure, so the output decides
+        parse_connect_output(
+            &endpoint,
+            &String::from_utf8_lossy(&output.stdout),
+            &String::from_utf8_lossy(&output.stderr),
+        )
+    }
+
+    /// Connect to several `ip:port` endpoints in parallel, returning the result for each one
+    pub fn connect_all(
+        &self,
+        endpoints: &[String],
+    ) -> Vec<(String, Result<ConnectOutcome, WirelessError>)> {
+        let handles: Vec<_> = endpoints
+            .iter()
+            .map(|endpoint| {
+                let bridge = AdbBridge::new(self.path.clone());
+                let endpoint = endpoint.clone();
+                std::thread::spawn(move || bridge.connect_endpoint(&endpoint))
+            })
+            .collect();
+
+        // An attempt that panicked still gets its row, as a failure
+        endpoints
+            .iter()
+            .cloned()
+            .zip(handles)
+            .map(|(endpoint, handle)| {
+                let result = handle
+                    .join()
+                    .unwrap_or_else(|_| Err(WirelessError::Other(format!("Connecting to {} failed unexpectedly", endpoint))));
+                (endpoint, result)
+            })
+            .collect()
+    }
+
+    /// Connect to `ip:port`, or to `ip` on the default port 5555
+    pub fn connect_endpoint(&self, endpoint: &str) -> Result<ConnectOutcome, WirelessError> {
+        match endpoint.rsplit_once(':') {
+            Some((ip, port)) => match port.parse::<u16>() {
+                Ok(port) => self.connect(ip, port),
+                Err(_) => Err(WirelessError::InvalidEndpoint(endpoint.to_string())),
+            },
+            None => self.connect(endpoint, 5555),
+        }
+    }
+
+    /// Drop a device stuck offline and connect to it again
+    pub fn reconnect(&self, endpoint: &str) -> Result<ConnectOutcome, WirelessError> {
+        // Fails harmlessly when adb no longer lists the device
+        let _ = adb_command(&self.path).args(["disconnect", endpoint]).output();
+        self.connect_endpoint(endpoint)
+    }
+
+    pub fn pair(&self, ip: &str, port: u16, pairing_code: &str) -> Result<PairOutcome, WirelessError> {
+        let endpoint = format!("{}:{}", ip, port);
+        let output = adb_command(&self.path)
+            .args(["pair", &endpoint, pairing_code])
+            .output()
+            .map_err(|e| WirelessError::Io(e.to_string()))?;
+
+        parse_pair_output(
+            &endpoint,
+            &String::from_utf8_lossy(&output.stdout),
+            &String::from_utf8_lossy(&output.stderr),
+        )
     }
 }
 
@@ -163,7 +407,7 @@ impl ScrcpyBridge {
     }
 
     pub fn start(&self, args: &[String]) -> Result<Child> {
-        let mut cmd = Command::new(&self.path);
+        let mut cmd = adb_command(&self.path);
         cmd.args(args);
         cmd.stdout(Stdio::piped());
         cmd.stderr(Stdio::piped());
@@ -197,26 +441,25 @@ impl ScrcpyBridge {
                 );
 
                 // Try to capture any stderr output that might explain the exit
+                let mut stderr_lines = Vec::new();
                 if let Some(stderr) = child.stderr.take() {
                     let reader = BufReader::new(stderr);
-                    let mut stderr_lines = Vec::new();
-                    for line in reader.lines() {
-                        if let Ok(line) = line {
-                            stderr_lines.push(line);
-                        }
+                    for line in reader.lines().map_while(Result::ok) {
+                        stderr_lines.push(line);
                     }
                     if !stderr_lines.is_empty() {
                         tracing::error!("Scrcpy stderr output:");
-                        for line in stderr_lines {
+                        for line in &stderr_lines {
                             tracing::error!("  {}", line);
                         }
                     }
                 }
 
-                return Err(anyhow::anyhow!(
+                // Keep the output in the error chain (shown with `{:#}`) so callers can classify it
+                return Err(anyhow::anyhow!(stderr_lines.join("\n")).context(format!(
                     "Scrcpy process exited immediately with status: {:?}",
                     status
-                ));
+                )));
             }
             Ok(None) => {
                 tracing::info!("Scrcpy process started successfully and is still running");
@@ -225,10 +468,8 @@ impl ScrcpyBridge {
                 if let Some(stderr) = child.stderr.take() {
                     let reader = BufReader::new(stderr);
                     std::thread::spawn(move || {
-                        for line in reader.lines() {
-                            if let Ok(line) = line {
-                                tracing::info!("Scrcpy stderr: {}", line);
-                            }
+                        for line in reader.lines().map_while(Result::ok) {
+                            tracing::info!("Scrcpy stderr: {}", line);
                         }
                     });
                 }
@@ -241,8 +482,31 @@ impl ScrcpyBridge {
         Ok(child)
     }
 
+    /// Ask scrcpy which video codecs the device can encode (`scrcpy --list-encoders`)
+    pub fn list_video_codecs(&self, device_id: &str) -> Result<Vec<String>> {
+        let output = adb_command(&self.path)
+            .args(["-s", device_id, "--list-encoders"])
+            .output()?;
+
+        // scrcpy logs the encoder list, which may end up on either stream
+        let mut combined = String::from_utf8_lossy(&output.stdout).to_string();
+        combined.push_str(&String::from_utf8_lossy(&output.stderr));
+
+        let codecs = parse_video_codecs(&combined);
+        if codecs.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Could not list encoders (requires scrcpy 2.0 or newer)"
+            ));
+        }
+
+        Ok(codecs)
+    }
+
     pub async fn start_async(&self, args: &[String]) -> Result<tokio::process::Child> {
         let mut cmd = TokioCommand::new(&self.path);
+        if let Some(socket) = adb_server_socket() {
+            cmd.env(ADB_SERVER_SOCKET, socket);
+        }
         cmd.args(args);
         cmd.stdout(Stdio::piped());
         cmd.stderr(Stdio::piped());
@@ -254,55 +518,62 @@ impl ScrcpyBridge {
         Ok(child)
     }
 
-    pub fn build_args(
-        &self,
-        device_id: Option<&str>,
-        bitrate: &str,
-        orientation: Option<String>,
-        show_touches: bool,
-        fullscreen: bool,
-        dimension: Option<u32>,
-        extra_args: &str,
-        turn_screen_off: bool,
-        force_adb_forward: bool,
-    ) -> Vec<String> {
+    pub fn build_args(&self, device_id: Option<&str>, options: &ScrcpyOptions) -> Vec<String> {
         let mut args = Vec::new();
 
         if let Some(device) = device_id {
             args.extend_from_slice(&["-s".to_string(), device.to_string()]);
         }
 
-        args.extend_from_slice(&["-b".to_string(), bitrate.to_string()]);
+        args.extend_from_slice(&["-b".to_string(), options.bitrate.clone()]);
 
-        if let Some(orientation) = orientation {
-            if !orientation.is_empty() {
-                args.extend_from_slice(&["--orientation".to_string(), orientation]);
-            }
+        // h264 is scrcpy's default, only pass the flag for other codecs
+        if !options.video_codec.is_empty() && options.video_codec != "h264" {
+            args.push(format!("--video-codec={}", options.video_codec));
+        }
+
+        if let Some(orientation) = &options.orientation
+            && !orientation.is_empty()
+        {
+            args.extend_from_slice(&["--orientation".to_string(), orientation.clone()]);
         }
 
-        if show_touches {
+        if options.show_touches {
             args.push("--show-touches".to_string());
         }
 
-        if fullscreen {
+        if options.fullscreen {
             args.push("--fullscreen".to_string());
         }
 
-        if let Some(dim) = dimension {
+        if let Some(dim) = options.dimension {
             args.extend_from_slice(&["--max-size".to_string(), dim.to_string()]);
         }
 
-        if turn_screen_off {
+        if let Some(display_id) = options.display_id {
+            args.push(format!("--display-id={}", display_id));
+        }
+
+        if options.turn_screen_off {
             args.push("-S".to_string());
         }
 
-        if force_adb_forward {
+        // The device can only reach a remote server's machine, so tunnel forward to that host
+        if let Some(host) = &options.tunnel_host {
             args.push("--force-adb-forward".to_string());
+            args.push(format!("--tunnel-host={}", host));
+        } else if options.force_adb_forward {
+            args.push("--force-adb-forward".to_string());
+        }
+
+        if !options.shortcut_mods.is_empty() {
+            args.push(format!("--shortcut-mod={}", options.shortcut_mods.join(",")));
         }
 
         // Parse extra arguments
-        if !extra_args.is_empty() {
-            let extra: Vec<String> = extra_args
+        if !options.extra_args.is_empty() {
+            let extra: Vec<String> = options
+                .extra_args
                 .split_whitespace()
                 .map(|s| s.to_string())
                 .collect();
@@ -312,3 +583,423 @@ impl ScrcpyBridge {
         args
     }
 }
+
+/// Extract the distinct `--video-codec=` values from `scrcpy --list-encoders` output
+pub fn parse_video_codecs(output: &str) -> Vec<String> {
+    let mut codecs: Vec<String> = Vec::new();
+    for line in output.lines() {
+        for token in line.split_whitespace() {
+            if let Some(codec) = token.strip_prefix("--video-codec=")
+                && !codecs.iter().any(|c| c == codec)
+            {
+                codecs.push(codec.to_string());
+            }
+        }
+    }
+    codecs
+}
+
+fn combined_output(stdout: &str, stderr: &str) -> String {
+    format!("{}\n{}", stdout, stderr).trim().to_string()
+}
+
+fn first_meaningful_line(text: &str, fallback: &str) -> String {
+    text.lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or(fallback)
+        .to_string()
+}
+
+/// Classify a network failure message shared by `connect` and `pair`
+fn classify_network_error(endpoint: &str, lower: &str) -> Option<WirelessError> {
+    if lower.contains("connection refused") {
+        Some(WirelessError::ConnectionRefused(endpoint.to_string()))
+    } else if lower.contains("no route to host") || lower.contains("network is unreachable") {
+        Some(WirelessError::NoRoute(endpoint.to_string()))
+    } else if lower.contains("timed out") || lower.contains("timeout") {
+        Some(WirelessError::Timeout(endpoint.to_string()))
+    } else {
+        None
+    }
+}
+
+/// Parse the output of `adb connect <endpoint>`
+pub fn parse_connect_output(
+    endpoint: &str,
+    stdout: &str,
+    stderr: &str,
+) -> Result<ConnectOutcome, WirelessError> {
+    let output = combined_output(stdout, stderr);
+    let lower = output.to_lowercase();
+
+    if lower.contains("already connected to") {
+        return Ok(ConnectOutcome::AlreadyConnected(endpoint.to_string()));
+    }
+    if let Some(error) = classify_network_error(endpoint, &lower) {
+        return Err(error);
+    }
+    if lower.contains("failed to connect") || lower.contains("cannot connect") || lower.contains("error") {
+        return Err(WirelessError::Other(first_meaningful_line(
+            &output,
+            "Unknown connection error",
+        )));
+    }
+    if lower.contains("connected to") {
+        return Ok(ConnectOutcome::Connected(endpoint.to_string()));
+    }
+
+    Err(WirelessError::Other(first_meaningful_line(
+        &output,
+        "Unknown connection error",
+    )))
+}
+
+/// Parse the output of `adb pair <endpoint> <code>`
+pub fn parse_pair_output(
+    endpoint: &str,
+    stdout: &str,
+    stderr: &str,
+) -> Result<PairOutcome, WirelessError> {
+    let output = combined_output(stdout, stderr);
+    let lower = output.to_lowercase();
+
+    if lower.contains("successfully paired") {
+        let guid = output
+            .split("[guid=")
+            .nth(1)
+            .and_then(|rest| rest.split(']').next())
+            .map(|guid| guid.to_string());
+        return Ok(PairOutcome {
+            endpoint: endpoint.to_string(),
+            guid,
+        });
+    }
+    if lower.contains("wrong password") {
+        return Err(WirelessError::WrongPairingCode(endpoint.to_string()));
+    }
+    if lower.contains("protocol fault") {
+        let detail = output
+            .lines()
+            .find(|line| line.to_lowercase().contains("protocol fault"))
+            .map(|line| line.trim().trim_start_matches("error:").trim().to_string())
+            .unwrap_or_default();
+        return Err(WirelessError::ProtocolFault(endpoint.to_string(), detail));
+    }
+    if let Some(error) = classify_network_error(endpoint, &lower) {
+        return Err(error);
+    }
+
+    Err(WirelessError::Other(first_meaningful_line(
+        &output,
+        "Pairing failed",
+    )))
+}
+
+/// Whether scrcpy output shows the session died while capturing audio, which `--no-audio` avoids
+pub fn is_audio_failure(output: &str) -> bool {
+    output.lines().any(|line| {
+        let lower = line.to_lowercase();
+        lower.contains("audio")
+            && (lower.contains("error") || lower.contains("exception") || lower.contains("failed"))
+    })
+}
+
+/// scrcpy 2.0 introduced `--video-codec`, `--video-bit-rate` and audio forwarding
+const MIN_SCRCPY_MAJOR: u32 = 2;
+/// platform-tools 30 added `adb pair` for Android 11 wireless debugging
+const MIN_PLATFORM_TOOLS_MAJOR: u32 = 30;
+
+/// Version of an adb or scrcpy executable, with what DroidView cannot do with it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolVersion {
+    pub version: String,
+    pub problem: Option<String>,
+}
+
+pub fn major_version(version: &str) -> Option<u32> {
+    version.trim_start_matches('v').split(['.', '-']).next()?.parse().ok()
+}
+
+/// Parse `adb version`: `Android Debug Bridge version 1.0.41` then `Version 34.0.5-10900879`
+pub fn parse_adb_version(output: &str) -> Option<ToolVersion> {
+    let protocol = output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Android Debug Bridge version "))?
+        .trim();
+    let tools = output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Version "))
+        .map(|version| version.split_whitespace().next().unwrap_or(version).to_string());
+
+    let version = match &tools {
+        Some(tools) => format!("{} (platform-tools {})", protocol, tools),
+        None => protocol.to_string(),
+    };
+    let problem = tools
+        .as_deref()
+        .and_then(major_version)
+        .filter(|major| *major < MIN_PLATFORM_TOOLS_MAJOR)
+        .map(|_| format!("platform-tools older than {} cannot pair with Android 11+ devices", MIN_PLATFORM_TOOLS_MAJOR));
+    Some(ToolVersion { version, problem })
+}
+
+/// Parse `scrcpy --version`, whose first line is `scrcpy 2.4 <https://github.com/Genymobile/scrcpy>`
+pub fn parse_scrcpy_version(output: &str) -> Option<ToolVersion> {
+    let version = output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("scrcpy "))?
+        .split_whitespace()
+        .next()?
+        .to_string();
+    let problem = major_version(&version)
+        .filter(|major| *major < MIN_SCRCPY_MAJOR)
+        .map(|_| format!("DroidView needs scrcpy {}.0 or newer", MIN_SCRCPY_MAJOR));
+    Some(ToolVersion { version, problem })
+}
+
+/// Run `adb version` or `scrcpy --version` at `path` and report what it is
+pub fn check_tool_version(path: &str, adb: bool) -> Result<ToolVersion, String> {
+    let path = path.trim();
+    if path.is_empty() {
+        return Err("No path set".to_string());
+    }
+    let file = std::path::Path::new(path);
+    if file.components().count() > 1 {
+        if !file.exists() {
+            return Err("File not found".to_string());
+        }
+        if file.is_dir() {
+            return Err("This is a folder, not the executable".to_string());
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            if file.metadata().map(|m| m.permissions().mode() & 0o111 == 0).unwrap_or(false) {
+                return Err("File is not executable (chmod +x)".to_string());
+            }
+        }
+    }
+
+    let arg = if adb { "version" } else { "--version" };
+    let output = Command::new(path).arg(arg).output().map_err(|e| format!("Could not run: {}", e))?;
+    let text = combined_output(&String::from_utf8_lossy(&output.stdout), &String::from_utf8_lossy(&output.stderr));
+    let parsed = if adb { parse_adb_version(&text) } else { parse_scrcpy_version(&text) };
+    parsed.ok_or_else(|| {
+        format!(
+            "Not {}: {}",
+            if adb { "adb" } else { "scrcpy" },
+            first_meaningful_line(&text, "no version output")
+        )
+    })
+}
+
+/// Parse the output of `adb tcpip <port>`
+pub fn parse_tcpip_output(success: bool, stdout: &str, stderr: &str) -> Result<(), WirelessError> {
+    let output = combined_output(stdout, stderr);
+    let lower = output.to_lowercase();
+
+    if lower.contains("no devices/emulators found") || lower.contains("device not found") {
+        return Err(WirelessError::NoDevice(first_meaningful_line(&output, "no device")));
+    }
+    if lower.contains("more than one device") {
+        return Err(WirelessError::NoDevice(
+            "more than one device connected, select one first".to_string(),
+        ));
+    }
+    if !success || lower.starts_with("error") {
+        return Err(WirelessError::Other(first_meaningful_line(
+            &output,
+            "TCP/IP command failed",
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remote_server_tunnels_through_its_host() {
+        let bridge = ScrcpyBridge::new("scrcpy".to_string());
+        let options = ScrcpyOptions {
+            tunnel_host: Some("192.168.1.2".to_string()),
+            ..Default::default()
+        };
+        let args = bridge.build_args(Some("R58M123ABC"), &options);
+        assert!(args.contains(&"--tunnel-host=192.168.1.2".to_string()));
+        assert_eq!(args.iter().filter(|arg| *arg == "--force-adb-forward").count(), 1);
+    }
+
+    #[test]
+    fn connect_success() {
+        let result = parse_connect_output("192.168.1.20:5555", "connected to 192.168.1.20:5555\n", "");
+        assert_eq!(result, Ok(ConnectOutcome::Connected("192.168.1.20:5555".to_string())));
+    }
+
+    #[test]
+    fn connect_already_connected() {
+        let result = parse_connect_output(
+            "192.168.1.20:5555",
+            "already connected to 192.168.1.20:5555\n",
+            "",
+        );
+        assert_eq!(
+            result,
+            Ok(ConnectOutcome::AlreadyConnected("192.168.1.20:5555".to_string()))
+        );
+    }
+
+    #[test]
+    fn connect_refused() {
+        let result = parse_connect_output(
+            "192.168.1.20:5555",
+            "failed to connect to '192.168.1.20:5555': Connection refused\n",
+            "",
+        );
+        assert_eq!(
+            result,
+            Err(WirelessError::ConnectionRefused("192.168.1.20:5555".to_string()))
+        );
+    }
+
+    #[test]
+    fn connect_no_route_and_timeout() {
+        let no_route = parse_connect_output(
+            "10.0.0.9:5555",
+            "failed to connect to '10.0.0.9:5555': No route to host\n",
+            "",
+        );
+        assert_eq!(no_route, Err(WirelessError::NoRoute("10.0.0.9:5555".to_string())));
+
+        let timeout = parse_connect_output(
+            "10.0.0.9:5555",
+            "failed to connect to '10.0.0.9:5555': Operation timed out\n",
+            "",
+        );
+        assert_eq!(timeout, Err(WirelessError::Timeout("10.0.0.9:5555".to_string())));
+    }
+
+    #[test]
+    fn connect_unknown_failure_keeps_message() {
+        let result = parse_connect_output(
+            "host:5555",
+            "",
+            "failed to resolve host: 'host': Name or service not known\n",
+        );
+        assert_eq!(
+            result,
+            Err(WirelessError::Other(
+                "failed to resolve host: 'host': Name or service not known".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn pair_success_with_guid() {
+        let result = parse_pair_output(
+            "192.168.1.20:37123",
+            "Successfully paired to 192.168.1.20:37123 [guid=adb-R58M123ABC-XyZ12a]\n",
+            "",
+        );
+        assert_eq!(
+            result,
+            Ok(PairOutcome {
+                endpoint: "192.168.1.20:37123".to_string(),
+                guid: Some("adb-R58M123ABC-XyZ12a".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn pair_wrong_code() {
+        let result = parse_pair_output(
+            "192.168.1.20:37123",
+            "Failed: Wrong password or connection was dropped.\n",
+            "",
+        );
+        assert_eq!(
+            result,
+            Err(WirelessError::WrongPairingCode("192.168.1.20:37123".to_string()))
+        );
+    }
+
+    #[test]
+    fn pair_protocol_fault() {
+        let result = parse_pair_output(
+            "192.168.1.20:37123",
+            "",
+            "error: protocol fault (couldn't read status message): Success\n",
+        );
+        assert_eq!(
+            result,
+            Err(WirelessError::ProtocolFault(
+                "192.168.1.20:37123".to_string(),
+                "protocol fault (couldn't read status message): Success".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn pair_connection_refused() {
+        let result = parse_pair_output(
+            "192.168.1.20:37123",
+            "",
+            "error: unable to connect to 192.168.1.20:37123: Connection refused\n",
+        );
+        assert_eq!(
+            result,
+            Err(WirelessError::ConnectionRefused("192.168.1.20:37123".to_string()))
+        );
+    }
+
+    #[test]
+    fn tcpip_results() {
+        assert_eq!(parse_tcpip_output(true, "restarting in TCP mode port: 5555\n", ""), Ok(()));
+        assert!(matches!(
+            parse_tcpip_output(false, "", "error: no devices/emulators found\n"),
+            Err(WirelessError::NoDevice(_))
+        ));
+        assert!(matches!(
+            parse_tcpip_output(false, "", "error: more than one device/emulator\n"),
+            Err(WirelessError::NoDevice(_))
+        ));
+    }
+
+    #[test]
+    fn audio_failures() {
+        assert!(is_audio_failure(
+            "INFO: Renderer: opengl\nERROR: Audio capture failed: AudioRecord start failed\nERROR: Demuxer error"
+        ));
+        assert!(is_audio_failure("java.lang.UnsupportedOperationException: Could not start audio capture"));
+        assert!(!is_audio_failure("WARN: Audio disabled: it is not supported before Android 11"));
+        assert!(!is_audio_failure("ERROR: Could not find any ADB device"));
+    }
+
+    #[test]
+    fn adb_versions() {
+        let current = parse_adb_version(
+            "Android Debug Bridge version 1.0.41\nVersion 34.0.5-10900879\nInstalled as /usr/bin/adb\n",
+        )
+        .unwrap();
+        assert_eq!(current.version, "1.0.41 (platform-tools 34.0.5-10900879)");
+        assert_eq!(current.problem, None);
+
+        let old = parse_adb_version("Android Debug Bridge version 1.0.39\nVersion 0.0.1-4500957\n").unwrap();
+        assert!(old.problem.is_some());
+
+        assert_eq!(parse_adb_version("bash: adb: command not found"), None);
+    }
+
+    #[test]
+    fn scrcpy_versions() {
+        let current = parse_scrcpy_version("scrcpy 2.4 <https://github.com/Genymobile/scrcpy>\n\nDependencies").unwrap();
+        assert_eq!(current.version, "2.4");
+        assert_eq!(current.problem, None);
+
+        assert!(parse_scrcpy_version("scrcpy 1.25 <https://github.com/Genymobile/scrcpy>").unwrap().problem.is_some());
+        assert_eq!(parse_scrcpy_version("Android Debug Bridge version 1.0.41"), None);
+    }
+}
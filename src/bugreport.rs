@@ -0,0 +1,94 @@
+//! `adb bugreport`, which zips logs, dumpsys and tombstones on the device and pulls the result
+//!
+//! Generating the report takes from one to several minutes. adb prints progress as
+//! `[ 42%] generating bugreport-<name>.zip` lines separated by `\r`.
+
+use crate::bridge::adb_command;
+use crate::sideload::{follow_progress, Finished};
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::AtomicBool;
+
+/// The last percentage in a chunk of `adb bugreport` output
+pub fn parse_progress(output: &str) -> Option<u8> {
+    output.rsplit('[').find_map(|rest| {
+        let (percent, _) = rest.split_once("%]")?;
+        percent.trim().parse::<u8>().ok().map(|percent| percent.min(100))
+    })
+}
+
+/// `bugreport-<device>-<timestamp>.zip`, with characters file systems dislike replaced
+pub fn file_name(device: &str, now: chrono::DateTime<chrono::Local>) -> String {
+    let device: String = device
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    format!("bugreport-{}-{}.zip", device, now.format("%Y-%m-%d-%H-%M-%S"))
+}
+
+/// Generate a bug report on `serial` and save it in `dir`, reporting each new percentage.
+/// Returns the saved file.
+pub fn capture(
+    adb_path: &str,
+    serial: &str,
+    dir: &Path,
+    cancel: &AtomicBool,
+    on_progress: impl FnMut(u8),
+) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(file_name(serial, chrono::Local::now()));
+    let child = adb_command(adb_path)
+        .args(["-s", serial, "bugreport"])
+        .arg(&path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let Finished { status, output, errors } = follow_progress(child, cancel, parse_progress, on_progress)
+        .inspect_err(|_| {
+            let _ = std::fs::remove_file(&path);
+        })?;
+    if !status.success() {
+        let reason = errors
+            .lines()
+            .chain(output.lines())
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with('['))
+            .unwrap_or("adb bugreport failed");
+        return Err(anyhow::anyhow!(reason.to_string()));
+    }
+    // Before Android 7 the device produces a plain text report
+    let text = path.with_extension("txt");
+    if !path.exists() && text.exists() {
+        return Ok(text);
+    }
+    if !path.exists() {
+        return Err(anyhow::anyhow!("adb did not save {}", path.display()));
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn progress() {
+        assert_eq!(parse_progress("[  5%] generating bugreport-x.zip\r"), Some(5));
+        assert_eq!(
+            parse_progress("[ 41%] generating bugreport-x.zip\r[ 42%] generating bugreport-x.zip\r"),
+            Some(42)
+        );
+        assert_eq!(parse_progress("[100%] generating bugreport-x.zip"), Some(100));
+        assert_eq!(parse_progress("/data/user_de/0/com.android.shell/files/bugreports/x.zip: 1 file pulled"), None);
+    }
+
+    #[test]
+    fn names_files_after_the_device() {
+        let now = chrono::Local.with_ymd_and_hms(2024, 3, 9, 14, 5, 0).unwrap();
+        assert_eq!(file_name("192.168.1.20:5555", now), "bugreport-192_168_1_20_5555-2024-03-09-14-05-00.zip");
+    }
+}
@@ -0,0 +1,210 @@
+use serde::{Deserialize, Serialize};
+use crate::bridge::adb_command;
+
+/// Privileged operations some tools rely on, which OEM builds may block for the shell user
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Capability {
+    SecureSettings,
+    GlobalSettings,
+    NotificationPolicy,
+}
+
+impl Capability {
+    pub const ALL: [Capability; 3] = [
+        Capability::SecureSettings,
+        Capability::GlobalSettings,
+        Capability::NotificationPolicy,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Capability::SecureSettings => "Write secure settings",
+            Capability::GlobalSettings => "Write global settings",
+            Capability::NotificationPolicy => "Notification policy (cmd notification)",
+        }
+    }
+
+    /// Tools that stop working without this capability
+    pub fn features(self) -> &'static [&'static str] {
+        match self {
+            Capability::SecureSettings => &["Accessibility toggles", "Show taps / pointer location", "Default keyboard"],
+            Capability::GlobalSettings => &["Demo mode", "Animation scales", "Stay awake"],
+            Capability::NotificationPolicy => &["Do Not Disturb toggle"],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CapabilityStatus {
+    Available,
+    Denied(String),
+    Unknown(String),
+}
+
+/// What the shell user may do on one device
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DeviceCapabilities {
+    pub manufacturer: String,
+    pub statuses: Vec<(Capability, CapabilityStatus)>,
+}
+
+impl DeviceCapabilities {
+    pub fn status(&self, capability: Capability) -> Option<&CapabilityStatus> {
+        self.statuses
+            .iter()
+            .find(|(c, _)| *c == capability)
+            .map(|(_, status)| status)
+    }
+
+    pub fn is_available(&self, capability: Capability) -> bool {
+        matches!(self.status(capability), Some(CapabilityStatus::Available))
+    }
+}
+
+/// Check each capability without changing anything on the device
+///
+/// Settings are read back, which OEM restrictions block along with writes, and the shell user's
+/// grant of WRITE_SECURE_SETTINGS is looked up in `dumpsys package`.
+pub fn probe_capabilities(adb_path: &str, device_id: &str) -> DeviceCapabilities {
+    let shell = |command: &str| -> Result<String, String> {
+        adb_command(adb_path)
+            .args(["-s", device_id, "shell", command])
+            .output()
+            .map(|output| {
+                let mut text = String::from_utf8_lossy(&output.stdout).to_string();
+                text.push_str(&String::from_utf8_lossy(&output.stderr));
+                text
+            })
+            .map_err(|e| e.to_string())
+    };
+
+    let manufacturer = shell("getprop ro.product.manufacturer")
+        .map(|m| m.trim().to_string())
+        .unwrap_or_default();
+    let write_settings = shell("dumpsys package com.android.shell")
+        .ok()
+        .and_then(|dump| permission_granted(&dump, "android.permission.WRITE_SECURE_SETTINGS"));
+
+    let statuses = vec![
+        (
+            Capability::SecureSettings,
+            settings_status(shell("settings get secure android_id"), write_settings),
+        ),
+        (
+            Capability::GlobalSettings,
+            settings_status(shell("settings get global adb_enabled"), write_settings),
+        ),
+        (
+            Capability::NotificationPolicy,
+            classify_probe(shell("cmd notification get_dnd 2>&1 || cmd notification 2>&1")),
+        ),
+    ];
+
+    DeviceCapabilities { manufacturer, statuses }
+}
+
+/// Whether `dumpsys package` lists `permission` as granted, `None` when it is not listed
+fn permission_granted(dump: &str, permission: &str) -> Option<bool> {
+    dump.lines()
+        .filter_map(|line| line.trim().strip_prefix(permission)?.strip_prefix(':'))
+        .find_map(|rest| rest.split_whitespace().find_map(|field| field.strip_prefix("granted=")))
+        .map(|granted| granted == "true")
+}
+
+/// A settings namespace can be changed when it can be read and the shell holds the permission
+fn settings_status(read: Result<String, String>, write_granted: Option<bool>) -> CapabilityStatus {
+    match (classify_probe(read), write_granted) {
+        (CapabilityStatus::Available, Some(true)) => CapabilityStatus::Available,
+        (CapabilityStatus::Available, Some(false)) => {
+            CapabilityStatus::Denied("The shell user does not hold WRITE_SECURE_SETTINGS".to_string())
+        }
+        (CapabilityStatus::Available, None) => {
+            CapabilityStatus::Unknown("Could not read the shell user's permissions".to_string())
+        }
+        (status, _) => status,
+    }
+}
+
+fn classify_probe(result: Result<String, String>) -> CapabilityStatus {
+    match result {
+        Err(e) => CapabilityStatus::Unknown(e),
+        Ok(output) => {
+            if let Some(line) = output.lines().find(|line| line.contains("SecurityException")) {
+                CapabilityStatus::Denied(line.trim().to_string())
+            } else if output.contains("Unknown command") || output.contains("not found") {
+                CapabilityStatus::Unknown("Not supported on this Android version".to_string())
+            } else {
+                CapabilityStatus::Available
+            }
+        }
+    }
+}
+
+/// Step-by-step instructions to lift the shell restrictions for a manufacturer
+pub fn grant_guide(manufacturer: &str) -> Vec<&'static str> {
+    let manufacturer = manufacturer.to_lowercase();
+    let mut steps = vec!["Open Settings › About phone and tap Build number seven times to unlock Developer options."];
+
+    if manufacturer.contains("xiaomi") || manufacturer.contains("redmi") || manufacturer.contains("poco") {
+        steps.push("In Developer options enable \"USB debugging (Security settings)\". MIUI/HyperOS may ask you to sign in to a Mi account and insert a SIM.");
+        steps.push("Also enable \"Install via USB\" if you plan to install APKs.");
+    } else if manufacturer.contains("oppo") || manufacturer.contains("realme") || manufacturer.contains("oneplus") {
+        steps.push("In Developer options turn on \"Disable permission monitoring\".");
+    } else if manufacturer.contains("huawei") || manufacturer.contains("honor") {
+        steps.push("In Developer options enable \"Allow ADB debugging in charge only mode\".");
+    } else {
+        steps.push("Make sure USB debugging is enabled in Developer options.");
+    }
+
+    steps.push("Unplug and reconnect the device (or restart the ADB server), then press Re-check.");
+    steps
+}
+
+/// Grant WRITE_SECURE_SETTINGS to a companion app so it can perform the changes itself
+pub fn grant_to_companion(adb_path: &str, device_id: &str, package: &str) -> Result<(), String> {
+    let output = adb_command(adb_path)
+        .args([
+            "-s",
+            device_id,
+            "shell",
+            "pm",
+            "grant",
+            package,
+            "android.permission.WRITE_SECURE_SETTINGS",
+        ])
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    match stderr.lines().chain(stdout.lines()).find(|line| line.contains("Exception")) {
+        Some(line) => Err(line.trim().to_string()),
+        None if output.status.success() => Ok(()),
+        None => Err(format!("pm grant failed for {}", package)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_shell_permission_grant() {
+        let dump = "\
+    install permissions:
+      android.permission.WRITE_SETTINGS: granted=true
+      android.permission.WRITE_SECURE_SETTINGS: granted=false, flags=[ USER_FIXED ]
+";
+        assert_eq!(permission_granted(dump, "android.permission.WRITE_SECURE_SETTINGS"), Some(false));
+        assert_eq!(permission_granted(dump, "android.permission.WRITE_SETTINGS"), Some(true));
+        assert_eq!(permission_granted(dump, "android.permission.DUMP"), None);
+    }
+
+    #[test]
+    fn settings_need_the_read_and_the_grant() {
+        assert_eq!(settings_status(Ok("1\n".to_string()), Some(true)), CapabilityStatus::Available);
+        assert!(matches!(settings_status(Ok("1\n".to_string()), Some(false)), CapabilityStatus::Denied(_)));
+        let denied = Ok("java.lang.SecurityException: Permission denial\n".to_string());
+        assert!(matches!(settings_status(denied, Some(true)), CapabilityStatus::Denied(_)));
+    }
+}
@@ -0,0 +1,294 @@
+//! Subcommands that work without opening a window, for terminals and scripts
+//!
+//! They use the adb and scrcpy paths, capture folder and session templates from the config, so
+//! `droidview mirror --preset work` starts the same session as the template does in the app.
+
+use crate::bridge::{AdbBridge, ConnectOutcome, ScrcpyBridge, ScrcpyOptions};
+use crate::config::{AppConfig, SessionTemplate};
+use anyhow::Result;
+use clap::Subcommand;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::Child;
+
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum Command {
+    /// List the devices adb can use
+    Devices,
+    /// Save a screenshot of a device as PNG
+    Screenshot {
+        /// Device serial; can be left out when only one device is connected
+        #[arg(short, long)]
+        serial: Option<String>,
+        /// File to write; a new file in the capture folder from Settings when left out
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Record the screen of a device to an MP4 file with scrcpy, until the time limit or Ctrl+C
+    Record {
+        /// Device serial; can be left out when only one device is connected
+        #[arg(short, long)]
+        serial: Option<String>,
+        /// File to write; a new file in the capture folder from Settings when left out
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Stop after this many seconds
+        #[arg(short, long)]
+        time_limit: Option<u32>,
+    },
+    /// Connect to a device over wireless adb
+    Connect {
+        /// `ip:port`, or just the address for port 5555
+        endpoint: String,
+    },
+    /// Mirror a device with scrcpy using the video settings from Settings or a preset
+    Mirror {
+        /// Device serial; the preset's device or the only connected one when left out
+        #[arg(short, long)]
+        serial: Option<String>,
+        /// A session template by name, or `low-latency` or `remote-assistance`
+        #[arg(short, long)]
+        preset: Option<String>,
+    },
+}
+
+/// Run `command`, printing its results; errors are left for the caller to report
+pub fn run(command: Command, config: &AppConfig) -> Result<()> {
+    crate::bridge::set_adb_server(config.remote_adb.active());
+    let adb = AdbBridge::new(tool_path(config.adb_path.as_deref(), crate::utils::find_adb, "adb")?);
+
+    match command {
+        Command::Devices => {
+            for device in adb.get_devices()? {
+                println!("{}", device);
+            }
+        }
+        Command::Screenshot { serial, output } => {
+            let serial = choose_device(serial, &adb.get_devices()?)?;
+            let path = match output {
+                Some(path) => {
+                    std::fs::write(&path, crate::automation::screencap(adb.path(), &serial)?)?;
+                    path
+                }
+                None => crate::automation::screenshot(
+                    adb.path(),
+                    &serial,
+                    &model(&adb, &serial),
+                    &config.screenshot_dir(),
+                    &config.screenshot_template,
+                )?,
+            };
+            println!("{}", path.display());
+        }
+        Command::Record { serial, output, time_limit } => {
+            let scrcpy = scrcpy(config)?;
+            let serial = choose_device(serial, &adb.get_devices()?)?;
+            let path = match output {
+                Some(path) => path,
+                None => {
+                    let dir = config.screenshot_dir();
+                    std::fs::create_dir_all(&dir)?;
+                    let file_name = crate::utils::expand_filename_template(
+                        "screenrecord_{serial}_{timestamp}",
+                        &serial,
+                        &model(&adb, &serial),
+                        chrono::Local::now(),
+                        "mp4",
+                    );
+                    crate::utils::unique_path(dir.join(file_name))
+                }
+            };
+            let args = record_args(&scrcpy, &serial, &ScrcpyOptions::for_device(config, &serial), &path, time_limit);
+            eprintln!("Recording {} to {}; press Ctrl+C to stop", serial, path.display());
+            wait(scrcpy.start(&args)?)?;
+            crate::gallery::record_capture(&path, crate::gallery::CaptureKind::Recording, &serial, None);
+            println!("{}", path.display());
+        }
+        Command::Connect { endpoint } => match adb.connect_endpoint(&endpoint)? {
+            ConnectOutcome::Connected(device) => println!("Connected to {}", device),
+            ConnectOutcome::AlreadyConnected(device) => println!("Already connected to {}", device),
+        },
+        Command::Mirror { serial, preset } => {
+            let scrcpy = scrcpy(config)?;
+            let (config, preset_device) = match preset {
+                Some(name) => {
+                    let template = find_preset(config, &name)
+                        .ok_or_else(|| anyhow::anyhow!("No preset or session template named '{}'", name))?;
+                    let device = Some(template.device.trim().to_string()).filter(|device| !device.is_empty());
+                    (template.apply(config), device)
+                }
+                None => (config.clone(), None),
+            };
+            let serial = choose_device(serial.or(preset_device), &adb.get_devices()?)?;
+            let args = scrcpy.build_args(Some(&serial), &ScrcpyOptions::for_device(&config, &serial));
+            wait(scrcpy.start(&args)?)?;
+        }
+    }
+    Ok(())
+}
+
+/// Let a command started from a terminal print there; the release build has no console of its own
+#[cfg(windows)]
+pub fn attach_console() {
+    // Fails harmlessly when started from Explorer, with no console to attach to
+    unsafe {
+        winapi::um::wincon::AttachConsole(winapi::um::wincon::ATTACH_PARENT_PROCESS);
+    }
+}
+
+/// The configured tool, or the one found on this machine when that setting is empty or stale
+fn tool_path(configured: Option<&str>, find: fn() -> Option<PathBuf>, name: &str) -> Result<String> {
+    if !crate::utils::tool_missing(configured) {
+        return Ok(configured.unwrap_or_default().trim().to_string());
+    }
+    find()
+        .map(|path| path.display().to_string())
+        .ok_or_else(|| anyhow::anyhow!("{} was not found; install it or set its path in Settings → Paths", name))
+}
+
+/// `serial` when given, otherwise the only device connected
+fn choose_device(serial: Option<String>, devices: &[String]) -> Result<String> {
+    if let Some(serial) = serial {
+        return Ok(serial);
+    }
+    match devices {
+        [device] => Ok(device.clone()),
+        [] => Err(anyhow::anyhow!("No device connected")),
+        _ => Err(anyhow::anyhow!(
+            "{} devices connected, choose one with --serial: {}",
+            devices.len(),
+            devices.join(", ")
+        )),
+    }
+}
+
+/// A session template by name, or a built-in one, ignoring case and spaces versus dashes
+pub fn find_preset(config: &AppConfig, name: &str) -> Option<SessionTemplate> {
+    let key = |name: &str| name.trim().to_lowercase().replace([' ', '_'], "-");
+    let wanted = key(name);
+    config
+        .session_templates
+        .iter()
+        .find(|template| key(&template.name) == wanted)
+        .cloned()
+        .or_else(|| {
+            [SessionTemplate::low_latency(), SessionTemplate::remote_assistance()]
+                .into_iter()
+                .find(|template| key(&template.name) == wanted)
+        })
+}
+
+fn scrcpy(config: &AppConfig) -> Result<ScrcpyBridge> {
+    let path = tool_path(config.scrcpy_path.as_deref(), crate::utils::find_scrcpy, "scrcpy")?;
+    Ok(ScrcpyBridge::new(path))
+}
+
+fn model(adb: &AdbBridge, serial: &str) -> String {
+    adb.shell("getprop ro.product.model", Some(serial))
+        .map(|model| model.trim().to_string())
+        .unwrap_or_default()
+}
+
+/// scrcpy arguments that record `serial` to `path` without opening a mirror window
+fn record_args(
+    scrcpy: &ScrcpyBridge,
+    serial: &str,
+    options: &ScrcpyOptions,
+    path: &Path,
+    time_limit: Option<u32>,
+) -> Vec<String> {
+    let options = ScrcpyOptions {
+        fullscreen: false,
+        ..options.clone()
+    };
+    let mut args = scrcpy.build_args(Some(serial), &options);
+    args.push("--no-playback".to_string());
+    args.push(format!("--record={}", path.display()));
+    if let Some(seconds) = time_limit {
+        args.push(format!("--time-limit={}", seconds));
+    }
+    args
+}
+
+/// Wait for scrcpy to exit, passing on what it prints
+fn wait(mut child: Child) -> Result<()> {
+    if let Some(stdout) = child.stdout.take() {
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                println!("{}", line);
+            }
+        });
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(anyhow::anyhow!("scrcpy exited with {}", status));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(subcommand)]
+        command: Command,
+    }
+
+    fn parse(args: &[&str]) -> Command {
+        Cli::try_parse_from(std::iter::once("droidview").chain(args.iter().copied())).unwrap().command
+    }
+
+    #[test]
+    fn parses_subcommands() {
+        assert_eq!(
+            parse(&["screenshot", "-s", "ABC", "-o", "shot.png"]),
+            Command::Screenshot { serial: Some("ABC".to_string()), output: Some(PathBuf::from("shot.png")) }
+        );
+        assert_eq!(
+            parse(&["mirror", "--preset", "low-latency"]),
+            Command::Mirror { serial: None, preset: Some("low-latency".to_string()) }
+        );
+        assert_eq!(
+            parse(&["connect", "192.168.1.20:5555"]),
+            Command::Connect { endpoint: "192.168.1.20:5555".to_string() }
+        );
+        assert!(Cli::try_parse_from(["droidview", "connect"]).is_err());
+    }
+
+    #[test]
+    fn chooses_the_only_device() {
+        let one = vec!["ABC".to_string()];
+        let two = vec!["ABC".to_string(), "DEF".to_string()];
+        assert_eq!(choose_device(None, &one).unwrap(), "ABC");
+        assert_eq!(choose_device(Some("DEF".to_string()), &one).unwrap(), "DEF");
+        assert!(choose_device(None, &[]).is_err());
+        assert!(choose_device(None, &two).unwrap_err().to_string().contains("ABC, DEF"));
+    }
+
+    #[test]
+    fn finds_presets_by_name() {
+        let mut config = AppConfig::default();
+        assert_eq!(find_preset(&config, "low-latency").unwrap().bitrate, "4M");
+        assert_eq!(find_preset(&config, "Remote Assistance").unwrap().bitrate, "1M");
+        assert!(find_preset(&config, "work").is_none());
+
+        // A template with a built-in's name takes its place
+        let mut template = SessionTemplate::from_config("Low latency", "ABC", &config);
+        template.bitrate = "2M".to_string();
+        config.session_templates.push(template);
+        assert_eq!(find_preset(&config, "low_latency").unwrap().bitrate, "2M");
+    }
+
+    #[test]
+    fn records_without_a_window() {
+        let scrcpy = ScrcpyBridge::new("scrcpy".to_string());
+        let options = ScrcpyOptions { bitrate: "8M".to_string(), fullscreen: true, ..Default::default() };
+        let args = record_args(&scrcpy, "ABC", &options, Path::new("out.mp4"), Some(30));
+        assert!(!args.contains(&"--fullscreen".to_string()));
+        let tail = ["--no-playback", "--record=out.mp4", "--time-limit=30"].map(String::from);
+        assert!(args.ends_with(&tail));
+    }
+}
@@ -0,0 +1,132 @@
+//! Comparing the device clock with this computer's and setting it from there
+//!
+//! Drift is measured against the middle of the adb round trip. Setting the clock takes
+//! `cmd alarm set-time` on recent Android versions, or `date` as root on emulators and rooted
+//! devices; when neither works, network time is switched on so the device corrects itself.
+
+use crate::automation::shell;
+use anyhow::Result;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Drift small enough to leave alone, about what the adb round trip can measure
+pub const TOLERANCE_MS: i64 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncOutcome {
+    /// The clock now matches this computer's
+    Set,
+    /// The clock could not be set, so automatic network time was switched on instead
+    NetworkTime,
+}
+
+/// How far the device clock is ahead of this computer's, in milliseconds; negative when behind
+pub fn drift(adb_path: &str, serial: &str) -> Result<i64> {
+    let before = now_ms();
+    let output = shell(adb_path, serial, "date +%s.%N")?;
+    let after = now_ms();
+    let device = parse_device_time(&output).ok_or_else(|| anyhow::anyhow!("Could not read the device time"))?;
+    Ok(device - (before + after) / 2)
+}
+
+pub fn sync(adb_path: &str, serial: &str) -> Result<SyncOutcome> {
+    // Network time would move the clock again
+    shell(adb_path, serial, "settings put global auto_time 0")?;
+    match set_clock(adb_path, serial) {
+        Ok(true) => Ok(SyncOutcome::Set),
+        Ok(false) => {
+            shell(adb_path, serial, "settings put global auto_time 1")?;
+            Ok(SyncOutcome::NetworkTime)
+        }
+        Err(e) => {
+            // Don't leave the device without network time after a failure
+            let _ = shell(adb_path, serial, "settings put global auto_time 1");
+            Err(e)
+        }
+    }
+}
+
+/// Try each way of setting the clock until the drift is within `TOLERANCE_MS`
+fn set_clock(adb_path: &str, serial: &str) -> Result<bool> {
+    // Built just before running, so the time is current on each attempt
+    let commands: [fn() -> String; 3] = [
+        || format!("cmd alarm set-time {}", now_ms()),
+        || format!("date -u {}", date_stamp(now_ms())),
+        || format!("su 0 date -u {}", date_stamp(now_ms())),
+    ];
+    for command in commands {
+        if shell(adb_path, serial, &command()).is_ok() && drift(adb_path, serial)?.abs() <= TOLERANCE_MS {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or(Duration::ZERO).as_millis() as i64
+}
+
+/// Milliseconds since the epoch from `date +%s.%N`; older toybox prints `%N` as it is
+pub fn parse_device_time(output: &str) -> Option<i64> {
+    let (seconds, fraction) = output.trim().split_once('.').unwrap_or((output.trim(), ""));
+    let seconds: i64 = seconds.parse().ok()?;
+    let millis = fraction
+        .get(..3)
+        .filter(|digits| digits.chars().all(|c| c.is_ascii_digit()))
+        .and_then(|digits| digits.parse::<i64>().ok())
+        .unwrap_or(0);
+    Some(seconds * 1000 + millis)
+}
+
+/// `MMDDhhmmYYYY.ss` in UTC, the form toybox `date -u` sets the clock from
+pub fn date_stamp(millis: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(millis)
+        .unwrap_or_default()
+        .format("%m%d%H%M%Y.%S")
+        .to_string()
+}
+
+/// Size of a drift in its two largest units, leaving the wording to the UI
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DriftSize {
+    InSync,
+    Seconds(f64),
+    Minutes(u64, u64),
+    Hours(u64, u64),
+    Days(u64, u64),
+}
+
+pub fn drift_size(drift_ms: i64) -> DriftSize {
+    if drift_ms.abs() <= TOLERANCE_MS {
+        return DriftSize::InSync;
+    }
+    let seconds = drift_ms.unsigned_abs() as f64 / 1000.0;
+    match seconds {
+        s if s < 60.0 => DriftSize::Seconds(s),
+        s if s < 3600.0 => DriftSize::Minutes((s / 60.0) as u64, (s % 60.0) as u64),
+        s if s < 86400.0 => DriftSize::Hours((s / 3600.0) as u64, (s % 3600.0 / 60.0) as u64),
+        s => DriftSize::Days((s / 86400.0) as u64, (s % 86400.0 / 3600.0) as u64),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_device_times() {
+        assert_eq!(parse_device_time("1760608800.123456789\n"), Some(1_760_608_800_123));
+        assert_eq!(parse_device_time("1760608800.N"), Some(1_760_608_800_000));
+        assert_eq!(parse_device_time("1760608800"), Some(1_760_608_800_000));
+        assert_eq!(parse_device_time("date: bad format"), None);
+    }
+
+    #[test]
+    fn formats_stamps_and_drift() {
+        assert_eq!(date_stamp(1_760_608_805_500), "101610002025.05");
+        assert_eq!(drift_size(400), DriftSize::InSync);
+        assert_eq!(drift_size(3_240), DriftSize::Seconds(3.24));
+        assert_eq!(drift_size(-65_000), DriftSize::Minutes(1, 5));
+        assert_eq!(drift_size(-(5 * 3_600_000 + 61_000)), DriftSize::Hours(5, 1));
+        assert_eq!(drift_size(2 * 86_400_000 + 3 * 3_600_000), DriftSize::Days(2, 3));
+    }
+}
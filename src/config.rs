@@ -1,24 +1,244 @@
+use crate::dock::{default_layout, deserialize_layout, DockLayout};
+use crate::intent::{IntentSpec, INTENT_HISTORY_LIMIT};
+use crate::shell_console::HISTORY_LIMIT as SHELL_HISTORY_LIMIT;
 use anyhow::Result;
 use dirs::config_dir;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::OnceLock;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
     pub adb_path: Option<String>,
     pub scrcpy_path: Option<String>,
+    /// gnirehtet for reverse tethering; a bundled copy or the one on the PATH when unset
+    #[serde(default)]
+    pub gnirehtet_path: Option<String>,
     pub bitrate: String,
+    #[serde(default = "default_video_codec")]
+    pub video_codec: String,
     pub orientation: Option<String>,
     pub show_touches: bool,
     pub turn_screen_off: bool,
     pub fullscreen: bool,
     pub dimension: Option<u32>,
+    /// Display to mirror by device serial; other devices mirror their default display
+    #[serde(default)]
+    pub display_ids: BTreeMap<String, u32>,
     pub extra_args: String,
     pub force_adb_forward: bool,
+    /// scrcpy `--shortcut-mod` entries such as `lalt` or `lctrl+lshift` (empty = scrcpy default)
+    #[serde(default)]
+    pub shortcut_mods: Vec<String>,
+    /// Global hotkeys for captures, which also work while the mirror window has the focus
+    #[serde(default)]
+    pub capture_hotkeys: CaptureHotkeys,
     pub panels: PanelConfig,
+    /// Where the device list, toolkit and other panels are docked
+    #[serde(default = "default_layout", deserialize_with = "deserialize_layout")]
+    pub dock: DockLayout,
     pub theme: String,
+    #[serde(default)]
+    pub custom_theme: CustomTheme,
+    /// Zoom on top of the monitor's own scale factor
+    #[serde(default = "default_ui_scale")]
+    pub ui_scale: f32,
+    /// Body text size in points; other text styles scale along
+    #[serde(default = "default_font_size")]
+    pub font_size: f32,
+    /// Main window size and place when DroidView last closed
+    #[serde(default)]
+    pub window: Option<WindowGeometry>,
+    /// adb server on another machine to use instead of a local one
+    #[serde(default)]
+    pub remote_adb: RemoteAdbServer,
+    /// Saved SSH tunnels to devices on other machines
+    #[serde(default)]
+    pub ssh_tunnels: Vec<SshTunnelProfile>,
+    /// Rules that act on devices by themselves, see `automation`
+    #[serde(default)]
+    pub automation_rules: Vec<AutomationRule>,
+    /// Recorded input sequences, see `macros`
+    #[serde(default)]
+    pub macros: Vec<InputMacro>,
+    /// Gestures saved in the gesture designer, see `gesture`
+    #[serde(default)]
+    pub gestures: Vec<Gesture>,
+    /// Rhai scripts from the Scripts window, see `scripting`
+    #[serde(default)]
+    pub scripts: Vec<UserScript>,
+    /// Named commands for the shell command box
+    #[serde(default)]
+    pub shell_snippets: Vec<ShellSnippet>,
+    /// Commands run from the shell command box by device serial, newest first
+    #[serde(default)]
+    pub shell_history: BTreeMap<String, Vec<String>>,
+    /// Local HTTP API for test tools and stream decks, see `api`
+    #[serde(default)]
+    pub control_api: ControlApi,
     pub wireless_adb: WirelessAdbConfig,
+    /// Intents sent from the intent sender, newest first
+    #[serde(default)]
+    pub intent_history: Vec<IntentSpec>,
+    #[serde(default)]
+    pub stream: StreamConfig,
+    /// Folder screenshots and recordings are saved to; the desktop when unset
+    #[serde(default)]
+    pub screenshot_dir: Option<String>,
+    /// Screenshot file name without extension, see `utils::FILENAME_PLACEHOLDERS`
+    #[serde(default = "default_screenshot_template")]
+    pub screenshot_template: String,
+    /// Also put every screenshot on the clipboard
+    #[serde(default)]
+    pub screenshot_to_clipboard: bool,
+    /// Folder bug reports are saved to; the capture folder when unset
+    #[serde(default)]
+    pub bugreport_dir: Option<String>,
+    /// Open the folder once a bug report is saved
+    #[serde(default = "default_true")]
+    pub bugreport_open_folder: bool,
+    /// Experimental: allow the view-only screen share on the local network
+    #[serde(default)]
+    pub share_opt_in: bool,
+    /// Saved scrcpy sessions, launchable from the controls, hotkeys and the tray
+    #[serde(default)]
+    pub session_templates: Vec<SessionTemplate>,
+    /// One of `logging::LOG_LEVELS`; `--log-level` overrides it for a single run
+    #[serde(default = "default_log_level")]
+    pub log_level: String,
+    /// Ask GitHub for a newer release at startup
+    #[serde(default)]
+    pub check_for_updates: bool,
+    /// Release the user chose not to be reminded about
+    #[serde(default)]
+    pub skipped_update: Option<String>,
+    /// In-app shortcut overrides by `ShortcutAction::id`; an empty binding disables one
+    #[serde(default)]
+    pub shortcuts: BTreeMap<String, String>,
+    /// Nicknames and favorites by device serial
+    #[serde(default)]
+    pub device_labels: BTreeMap<String, DeviceLabel>,
+    /// Show Android version, battery and connection under each device
+    #[serde(default)]
+    pub device_list_detailed: bool,
+    /// Keep a small live screenshot of each device in the device list. Off unless asked for:
+    /// it takes a screencap of every device every few seconds
+    #[serde(default)]
+    pub device_thumbnails: bool,
+    /// Seconds between the background checks for devices coming and going and for scrcpy
+    #[serde(default = "default_device_poll_secs")]
+    pub device_poll_secs: u64,
+    /// Master switch for the devices set to start mirroring when they connect
+    #[serde(default = "default_true")]
+    pub auto_start_enabled: bool,
+    /// Forward audio with sndcpy when mirroring a device scrcpy cannot capture audio from
+    #[serde(default)]
+    pub legacy_audio: bool,
+    /// sndcpy's APK; a copy shipped next to DroidView when unset
+    #[serde(default)]
+    pub sndcpy_apk: Option<String>,
+    /// UI language code such as `en`; the system language when unset
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+/// Global hotkeys such as `Ctrl+Alt+S`; empty for none
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CaptureHotkeys {
+    /// Take a screenshot of the selected device
+    pub screenshot: String,
+    /// Start a screen recording of the selected device, or stop the running one
+    pub record: String,
+}
+
+/// A named scrcpy session: target device and video settings, optionally bound to a global hotkey
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionTemplate {
+    pub name: String,
+    /// Serial to launch on; the selected device when empty
+    #[serde(default)]
+    pub device: String,
+    pub bitrate: String,
+    pub video_codec: String,
+    pub dimension: Option<u32>,
+    pub orientation: Option<String>,
+    pub fullscreen: bool,
+    pub turn_screen_off: bool,
+    pub extra_args: String,
+    /// Global hotkey such as `Ctrl+Alt+1`; empty for none
+    #[serde(default)]
+    pub hotkey: String,
+}
+
+/// Name of the built-in template tuned for helping someone over a slow connection
+pub const REMOTE_ASSISTANCE: &str = "Remote assistance";
+/// Name of the built-in template that gives up picture quality for a quicker response
+pub const LOW_LATENCY: &str = "Low latency";
+
+impl SessionTemplate {
+    /// Low bitrate, 15 fps, 800px and no audio: usable over a VPN or a weak uplink
+    pub fn remote_assistance() -> Self {
+        Self {
+            name: REMOTE_ASSISTANCE.to_string(),
+            device: String::new(),
+            bitrate: "1M".to_string(),
+            video_codec: "h264".to_string(),
+            dimension: Some(800),
+            orientation: None,
+            fullscreen: false,
+            turn_screen_off: false,
+            extra_args: "--max-fps=15 --no-audio".to_string(),
+            hotkey: String::new(),
+        }
+    }
+
+    /// 4 Mbps h264 at 1024px and 60 fps with a short audio buffer: less for the encoder to queue
+    pub fn low_latency() -> Self {
+        Self {
+            name: LOW_LATENCY.to_string(),
+            device: String::new(),
+            bitrate: "4M".to_string(),
+            video_codec: "h264".to_string(),
+            dimension: Some(1024),
+            orientation: None,
+            fullscreen: false,
+            turn_screen_off: false,
+            extra_args: "--max-fps=60 --audio-buffer=40".to_string(),
+            hotkey: String::new(),
+        }
+    }
+
+    /// Capture the current video settings under `name`
+    pub fn from_config(name: &str, device: &str, config: &AppConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            device: device.to_string(),
+            bitrate: config.bitrate.clone(),
+            video_codec: config.video_codec.clone(),
+            dimension: config.dimension,
+            orientation: config.orientation.clone(),
+            fullscreen: config.fullscreen,
+            turn_screen_off: config.turn_screen_off,
+            extra_args: config.extra_args.clone(),
+            hotkey: String::new(),
+        }
+    }
+
+    /// `config` with this template's settings in place, for launching
+    pub fn apply(&self, config: &AppConfig) -> AppConfig {
+        let mut config = config.clone();
+        config.bitrate = self.bitrate.clone();
+        config.video_codec = self.video_codec.clone();
+        config.dimension = self.dimension;
+        config.orientation = self.orientation.clone();
+        config.fullscreen = self.fullscreen;
+        config.turn_screen_off = self.turn_screen_off;
+        config.extra_args = self.extra_args.clone();
+        config
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,12 +248,422 @@ pub struct PanelConfig {
     pub bottom: bool,
 }
 
+/// User adjustments on top of the default, dark or light theme
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomTheme {
+    /// Selections, links and focused widgets; the theme's own blue when unset
+    #[serde(default)]
+    pub accent: Option<[u8; 3]>,
+    /// Color mixed into panel and window backgrounds
+    #[serde(default)]
+    pub background_tint: Option<[u8; 3]>,
+    /// How much of the tint goes into the backgrounds, 0.0 to 0.5
+    #[serde(default = "default_tint_strength")]
+    pub tint_strength: f32,
+    /// Corner radius of windows, menus and buttons in points
+    #[serde(default)]
+    pub corner_radius: Option<u8>,
+}
+
+fn default_ui_scale() -> f32 {
+    1.0
+}
+
+/// egui's own body text size
+pub fn default_font_size() -> f32 {
+    12.5
+}
+
+fn default_tint_strength() -> f32 {
+    0.15
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for CustomTheme {
+    fn default() -> Self {
+        Self {
+            accent: None,
+            background_tint: None,
+            tint_strength: default_tint_strength(),
+            corner_radius: None,
+        }
+    }
+}
+
+/// How the user tagged one device
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DeviceLabel {
+    /// Shown instead of the model name
+    #[serde(default)]
+    pub nickname: Option<String>,
+    /// Listed before the other devices
+    #[serde(default)]
+    pub favorite: bool,
+    /// Start mirroring as soon as the device connects
+    #[serde(default)]
+    pub auto_start: bool,
+}
+
+/// Main window geometry in logical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    /// Size before maximizing, so un-maximizing after a restart goes back to it
+    pub size: [f32; 2],
+    /// Outer top-left corner; unknown on Wayland, where the compositor places windows
+    #[serde(default)]
+    pub position: Option<[f32; 2]>,
+    #[serde(default)]
+    pub maximized: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WirelessAdbConfig {
     pub last_tcpip_ip: String,
     pub last_tcpip_port: String,
     pub last_pairing_ip: String,
     pub last_pairing_port: String,
+    /// Wireless endpoints that were successfully connected before
+    #[serde(default)]
+    pub saved_endpoints: Vec<WirelessEndpoint>,
+    /// Attempt `adb connect` to every saved endpoint when DroidView starts
+    #[serde(default)]
+    pub reconnect_on_startup: bool,
+    /// Retry wireless devices that drop off until they are back
+    #[serde(default = "default_true")]
+    pub auto_reconnect: bool,
+}
+
+/// An adb server on another machine, used instead of the local one when enabled
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemoteAdbServer {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub host: String,
+    #[serde(default = "default_adb_server_port")]
+    pub port: u16,
+}
+
+impl Default for RemoteAdbServer {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: String::new(),
+            port: default_adb_server_port(),
+        }
+    }
+}
+
+impl RemoteAdbServer {
+    /// Host and port to use, when enabled and filled in
+    pub fn active(&self) -> Option<(&str, u16)> {
+        let host = self.host.trim();
+        (self.enabled && !host.is_empty()).then_some((host, self.port))
+    }
+}
+
+fn default_adb_server_port() -> u16 {
+    5037
+}
+
+/// The HTTP control API, which only listens on this machine
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ControlApi {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_control_api_port")]
+    pub port: u16,
+    /// Secret every request has to send; made up when the API is first turned on
+    #[serde(default)]
+    pub token: String,
+}
+
+impl Default for ControlApi {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: default_control_api_port(),
+            token: String::new(),
+        }
+    }
+}
+
+impl ControlApi {
+    pub fn new_token() -> String {
+        uuid::Uuid::new_v4().simple().to_string()
+    }
+}
+
+fn default_control_api_port() -> u16 {
+    8788
+}
+
+/// An SSH port forward to a device that only another machine can reach
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SshTunnelProfile {
+    pub name: String,
+    /// `user@host` to log in to
+    pub ssh_host: String,
+    #[serde(default = "default_ssh_port")]
+    pub ssh_port: u16,
+    /// The device's adb address as seen from the SSH host, e.g. `localhost:5555`
+    pub target: String,
+    /// Local end of the forward; adb connects to `localhost:<local_port>`
+    pub local_port: u16,
+    /// Private key to log in with, when not the default one or the agent's
+    #[serde(default)]
+    pub identity_file: Option<String>,
+}
+
+impl Default for SshTunnelProfile {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            ssh_host: String::new(),
+            ssh_port: default_ssh_port(),
+            target: "localhost:5555".to_string(),
+            local_port: 15555,
+            identity_file: None,
+        }
+    }
+}
+
+fn default_ssh_port() -> u16 {
+    22
+}
+
+/// What sets off an automation rule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AutomationTrigger {
+    DeviceConnected,
+    /// Every day at this local time
+    TimeOfDay { hour: u8, minute: u8 },
+    /// Once each time the charge drops below `percent`
+    BatteryBelow { percent: u8 },
+}
+
+/// What an automation rule does to the device
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AutomationAction {
+    StartMirroring,
+    /// Saved like a toolkit screenshot, to the capture folder
+    Screenshot,
+    Shell { command: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutomationRule {
+    pub name: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Serial the rule is limited to; every device when empty
+    #[serde(default)]
+    pub device: String,
+    pub trigger: AutomationTrigger,
+    pub action: AutomationAction,
+}
+
+/// One input event sent with `adb shell input`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MacroInput {
+    Tap { x: u32, y: u32 },
+    Swipe { x1: u32, y1: u32, x2: u32, y2: u32, duration_ms: u32 },
+    /// A keycode name such as `KEYCODE_BACK`, or its number
+    Key { code: String },
+    Text { text: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MacroStep {
+    /// Pause before this step, as recorded
+    #[serde(default)]
+    pub delay_ms: u64,
+    pub input: MacroInput,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputMacro {
+    pub name: String,
+    pub steps: Vec<MacroStep>,
+}
+
+/// Shape of a custom gesture; points are fractions of the screen width and height
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GestureKind {
+    /// One finger through each point in turn
+    Path { points: Vec<[f32; 2]> },
+    /// Two fingers side by side around `center`, going from `from` apart to `to` apart
+    Pinch { center: [f32; 2], from: f32, to: f32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserScript {
+    pub name: String,
+    pub source: String,
+    /// Has a button in the quick actions
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShellSnippet {
+    pub name: String,
+    pub command: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Gesture {
+    pub name: String,
+    pub kind: GestureKind,
+    pub duration_ms: u32,
+}
+
+impl Default for AutomationRule {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            enabled: true,
+            device: String::new(),
+            trigger: AutomationTrigger::DeviceConnected,
+            action: AutomationAction::StartMirroring,
+        }
+    }
+}
+
+/// A wireless device DroidView connected to before
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "SavedEndpoint")]
+pub struct WirelessEndpoint {
+    /// `ip:port`
+    pub endpoint: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nickname: Option<String>,
+    /// Last time it was connected, or seen connected in the device list
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_seen: Option<chrono::DateTime<chrono::Local>>,
+    /// Paired with a code (Android 11+ wireless debugging)
+    #[serde(default)]
+    pub paired: bool,
+}
+
+impl WirelessEndpoint {
+    pub fn new(endpoint: String) -> Self {
+        Self {
+            endpoint,
+            nickname: None,
+            last_seen: None,
+            paired: false,
+        }
+    }
+
+    /// The nickname if it has one, otherwise the endpoint
+    pub fn display_name(&self) -> &str {
+        self.nickname.as_deref().unwrap_or(&self.endpoint)
+    }
+
+    pub fn host(&self) -> &str {
+        self.endpoint.rsplit_once(':').map_or(&self.endpoint, |(host, _)| host)
+    }
+}
+
+/// Saved endpoints as written by any version: older configs list plain `ip:port` strings
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SavedEndpoint {
+    Plain(String),
+    Full {
+        endpoint: String,
+        #[serde(default)]
+        nickname: Option<String>,
+        #[serde(default)]
+        last_seen: Option<chrono::DateTime<chrono::Local>>,
+        #[serde(default)]
+        paired: bool,
+    },
+}
+
+impl From<SavedEndpoint> for WirelessEndpoint {
+    fn from(saved: SavedEndpoint) -> Self {
+        match saved {
+            SavedEndpoint::Plain(endpoint) => WirelessEndpoint::new(endpoint),
+            SavedEndpoint::Full {
+                endpoint,
+                nickname,
+                last_seen,
+                paired,
+            } => WirelessEndpoint {
+                endpoint,
+                nickname,
+                last_seen,
+                paired,
+            },
+        }
+    }
+}
+
+/// Settings of the network stream (scrcpy remuxed by ffmpeg to RTMP/SRT)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StreamConfig {
+    /// `rtmp://`, `rtmps://` or `srt://` endpoint, including any stream key
+    pub url: String,
+    pub ffmpeg_path: Option<String>,
+    pub video_bitrate: String,
+    pub audio_bitrate: String,
+    pub max_size: Option<u32>,
+    /// Keep the scrcpy window open while streaming
+    pub show_mirror: bool,
+}
+
+impl Default for StreamConfig {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            ffmpeg_path: None,
+            video_bitrate: "6M".to_string(),
+            audio_bitrate: "128K".to_string(),
+            max_size: Some(1280),
+            show_mirror: true,
+        }
+    }
+}
+
+/// Video codecs scrcpy can be asked to use, in order of preference
+pub const VIDEO_CODECS: [&str; 3] = ["h264", "h265", "av1"];
+
+/// Modifier keys scrcpy accepts in `--shortcut-mod`
+pub const SHORTCUT_MODIFIERS: [(&str, &str); 6] = [
+    ("lctrl", "Left Ctrl"),
+    ("rctrl", "Right Ctrl"),
+    ("lalt", "Left Alt"),
+    ("ralt", "Right Alt"),
+    ("lsuper", "Left Super"),
+    ("rsuper", "Right Super"),
+];
+
+fn default_video_codec() -> String {
+    "h264".to_string()
+}
+
+/// Seconds between device and scrcpy checks unless configured otherwise
+pub const DEFAULT_DEVICE_POLL_SECS: u64 = 2;
+
+fn default_device_poll_secs() -> u64 {
+    DEFAULT_DEVICE_POLL_SECS
+}
+
+fn default_log_level() -> String {
+    "info".to_string()
+}
+
+fn default_screenshot_template() -> String {
+    "screenshot_{serial}_{timestamp}".to_string()
 }
 
 impl Default for AppConfig {
@@ -41,33 +671,81 @@ impl Default for AppConfig {
         Self {
             adb_path: None,
             scrcpy_path: None,
+            gnirehtet_path: None,
             bitrate: "8M".to_string(),
+            video_codec: default_video_codec(),
             orientation: None,
             show_touches: false,
             turn_screen_off: false,
             fullscreen: false,
             dimension: None,
+            display_ids: BTreeMap::new(),
             extra_args: String::new(),
             force_adb_forward: false,
+            shortcut_mods: Vec::new(),
+            capture_hotkeys: CaptureHotkeys::default(),
             panels: PanelConfig {
                 swipe: true,
                 toolkit: true,
                 bottom: true,
             },
+            dock: default_layout(),
             theme: "default".to_string(),
+            custom_theme: CustomTheme::default(),
+            ui_scale: default_ui_scale(),
+            font_size: default_font_size(),
+            window: None,
+            remote_adb: RemoteAdbServer::default(),
+            ssh_tunnels: Vec::new(),
+            automation_rules: Vec::new(),
+            macros: Vec::new(),
+            gestures: Vec::new(),
+            scripts: Vec::new(),
+            shell_snippets: Vec::new(),
+            shell_history: BTreeMap::new(),
+            control_api: ControlApi::default(),
             wireless_adb: WirelessAdbConfig {
                 last_tcpip_ip: String::new(),
                 last_tcpip_port: "5555".to_string(),
                 last_pairing_ip: String::new(),
                 last_pairing_port: "5555".to_string(),
+                saved_endpoints: Vec::new(),
+                reconnect_on_startup: false,
+                auto_reconnect: true,
             },
+            intent_history: Vec::new(),
+            stream: StreamConfig::default(),
+            screenshot_dir: None,
+            screenshot_template: default_screenshot_template(),
+            screenshot_to_clipboard: false,
+            bugreport_dir: None,
+            bugreport_open_folder: true,
+            share_opt_in: false,
+            session_templates: Vec::new(),
+            log_level: default_log_level(),
+            check_for_updates: false,
+            skipped_update: None,
+            shortcuts: BTreeMap::new(),
+            device_labels: BTreeMap::new(),
+            device_list_detailed: false,
+            device_thumbnails: false,
+            device_poll_secs: DEFAULT_DEVICE_POLL_SECS,
+            auto_start_enabled: true,
+            legacy_audio: false,
+            sndcpy_apk: None,
+            language: None,
         }
     }
 }
 
 impl AppConfig {
     pub fn load() -> Result<Self> {
-        let config_path = Self::config_path()?;
+        Self::load_profile(active_profile())
+    }
+
+    /// Load the config of a named profile (`None` for the default one) without switching to it
+    pub fn load_profile(profile: Option<&str>) -> Result<Self> {
+        let config_path = profile_config_path(profile)?;
 
         if config_path.exists() {
             let content = fs::read_to_string(config_path)?;
@@ -79,7 +757,11 @@ impl AppConfig {
     }
 
     pub fn save(&self) -> Result<()> {
-        let config_path = Self::config_path()?;
+        self.save_profile(active_profile())
+    }
+
+    pub fn save_profile(&self, profile: Option<&str>) -> Result<()> {
+        let config_path = profile_config_path(profile)?;
 
         // Ensure config directory exists
         if let Some(parent) = config_path.parent() {
@@ -91,11 +773,218 @@ impl AppConfig {
         Ok(())
     }
 
-    fn config_path() -> Result<PathBuf> {
-        let mut path =
-            config_dir().ok_or_else(|| anyhow::anyhow!("Could not determine config directory"))?;
-        path.push("DroidView");
-        path.push("config.toml");
-        Ok(path)
+    /// Remember a wireless endpoint for later reconnects, or mark a known one as seen now
+    pub fn remember_wireless_endpoint(&mut self, ip: &str, port: u16) {
+        let endpoint = format!("{}:{}", ip, port);
+        let saved = &mut self.wireless_adb.saved_endpoints;
+        let index = match saved.iter().position(|saved| saved.endpoint == endpoint) {
+            Some(index) => index,
+            None => {
+                saved.push(WirelessEndpoint::new(endpoint));
+                saved.len() - 1
+            }
+        };
+        saved[index].last_seen = Some(chrono::Local::now());
+    }
+
+    /// Update the last-seen time of a saved endpoint; false when it is not saved
+    pub fn wireless_endpoint_seen(&mut self, endpoint: &str) -> bool {
+        match self
+            .wireless_adb
+            .saved_endpoints
+            .iter_mut()
+            .find(|saved| saved.endpoint == endpoint)
+        {
+            Some(saved) => {
+                saved.last_seen = Some(chrono::Local::now());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Mark the saved endpoints on `host` as paired
+    pub fn mark_wireless_paired(&mut self, host: &str) {
+        for saved in &mut self.wireless_adb.saved_endpoints {
+            if saved.host() == host {
+                saved.paired = true;
+            }
+        }
+    }
+
+    /// Move an intent to the top of the history, dropping the oldest beyond the limit
+    pub fn remember_intent(&mut self, spec: &IntentSpec) {
+        self.intent_history.retain(|existing| existing != spec);
+        self.intent_history.insert(0, spec.clone());
+        self.intent_history.truncate(INTENT_HISTORY_LIMIT);
+    }
+
+    /// Move a command to the top of a device's shell history, dropping the oldest beyond the limit
+    pub fn remember_shell_command(&mut self, serial: &str, command: &str) {
+        let history = self.shell_history.entry(serial.to_string()).or_default();
+        history.retain(|existing| existing != command);
+        history.insert(0, command.to_string());
+        history.truncate(SHELL_HISTORY_LIMIT);
+    }
+
+    /// The configured capture folder, falling back to the desktop
+    pub fn screenshot_dir(&self) -> PathBuf {
+        self.screenshot_dir
+            .as_deref()
+            .map(str::trim)
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(dirs::desktop_dir)
+            .unwrap_or_default()
+    }
+
+    /// The configured bug report folder, falling back to the capture folder
+    pub fn bugreport_dir(&self) -> PathBuf {
+        self.bugreport_dir
+            .as_deref()
+            .map(str::trim)
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| self.screenshot_dir())
+    }
+}
+
+fn profile_config_path(profile: Option<&str>) -> Result<PathBuf> {
+    match profile {
+        Some(profile) => Ok(app_dir()?.join(PROFILES_DIR).join(profile).join("config.toml")),
+        None => Ok(app_dir()?.join("config.toml")),
+    }
+}
+
+/// Named profiles live in `<app dir>/profiles/<name>/`; the default profile keeps the top-level config.toml
+const PROFILES_DIR: &str = "profiles";
+
+static ACTIVE_PROFILE: OnceLock<String> = OnceLock::new();
+
+/// Select the profile (`--profile work`) before the config is loaded; only the first call has an effect
+pub fn set_profile(name: &str) -> Result<()> {
+    let name = name.trim();
+    validate_profile_name(name)?;
+    if name != "default" {
+        let _ = ACTIVE_PROFILE.set(name.to_string());
+    }
+    Ok(())
+}
+
+pub fn validate_profile_name(name: &str) -> Result<()> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(anyhow::anyhow!(
+            "Invalid profile name '{}': use letters, digits, '-' and '_'",
+            name
+        ));
+    }
+    Ok(())
+}
+
+/// The named profile in use, `None` for the default one
+pub fn active_profile() -> Option<&'static str> {
+    ACTIVE_PROFILE.get().map(String::as_str)
+}
+
+/// Names of the profiles that have been created, sorted
+pub fn list_profiles() -> Vec<String> {
+    let mut profiles: Vec<String> = app_dir()
+        .ok()
+        .and_then(|dir| fs::read_dir(dir.join(PROFILES_DIR)).ok())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().join("config.toml").is_file())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect();
+    profiles.sort();
+    profiles
+}
+
+/// Marker file that, placed next to the executable, keeps all settings beside it
+pub const PORTABLE_MARKER: &str = "portable.txt";
+
+/// Directory of the executable when it carries a `portable.txt` marker
+pub fn portable_dir() -> Option<PathBuf> {
+    let exe_dir = std::env::current_exe().ok()?.parent()?.to_path_buf();
+    exe_dir.join(PORTABLE_MARKER).is_file().then_some(exe_dir)
+}
+
+pub fn is_portable() -> bool {
+    portable_dir().is_some()
+}
+
+#[cfg(feature = "test-harness")]
+static APP_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Point every DroidView file at `dir`, so harness runs never touch the real settings
+#[cfg(feature = "test-harness")]
+pub fn set_app_dir_override(dir: PathBuf) {
+    let _ = APP_DIR_OVERRIDE.set(dir);
+}
+
+/// Where DroidView keeps its files: next to the executable in portable mode, else the user config dir
+pub fn app_dir() -> Result<PathBuf> {
+    #[cfg(feature = "test-harness")]
+    if let Some(dir) = APP_DIR_OVERRIDE.get() {
+        return Ok(dir.clone());
+    }
+    if let Some(dir) = portable_dir() {
+        return Ok(dir);
+    }
+    let mut path =
+        config_dir().ok_or_else(|| anyhow::anyhow!("Could not determine config directory"))?;
+    path.push("DroidView");
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saved_endpoints_read_old_and_new_configs() {
+        let old: WirelessAdbConfig = toml::from_str(
+            "last_tcpip_ip = \"\"\nlast_tcpip_port = \"5555\"\nlast_pairing_ip = \"\"\nlast_pairing_port = \"5555\"\nsaved_endpoints = [\"192.168.1.20:5555\"]\n",
+        )
+        .unwrap();
+        assert_eq!(old.saved_endpoints, vec![WirelessEndpoint::new("192.168.1.20:5555".to_string())]);
+
+        let mut config = AppConfig::default();
+        config.wireless_adb.saved_endpoints = old.saved_endpoints;
+        config.remember_wireless_endpoint("192.168.1.20", 5555);
+        config.remember_wireless_endpoint("192.168.1.21", 41234);
+        config.mark_wireless_paired("192.168.1.21");
+        config.wireless_adb.saved_endpoints[0].nickname = Some("Pixel".to_string());
+
+        let saved = toml::to_string_pretty(&config).unwrap();
+        let loaded: AppConfig = toml::from_str(&saved).unwrap();
+        let endpoints = &loaded.wireless_adb.saved_endpoints;
+        assert_eq!(endpoints.len(), 2);
+        assert_eq!(endpoints[0].display_name(), "Pixel");
+        assert!(endpoints[0].last_seen.is_some());
+        assert!(!endpoints[0].paired);
+        assert_eq!(endpoints[1].host(), "192.168.1.21");
+        assert!(endpoints[1].paired);
+    }
+
+    #[test]
+    fn remembers_shell_commands_per_device() {
+        let mut config = AppConfig::default();
+        config.remember_shell_command("emulator-5554", "getprop ro.build.version.release");
+        config.remember_shell_command("emulator-5554", "pm list packages");
+        config.remember_shell_command("emulator-5554", "getprop ro.build.version.release");
+        config.remember_shell_command("R58M", "dumpsys battery");
+        assert_eq!(
+            config.shell_history["emulator-5554"],
+            ["getprop ro.build.version.release", "pm list packages"]
+        );
+        assert_eq!(config.shell_history["R58M"], ["dumpsys battery"]);
+
+        for index in 0..SHELL_HISTORY_LIMIT + 5 {
+            config.remember_shell_command("R58M", &format!("echo {}", index));
+        }
+        assert_eq!(config.shell_history["R58M"].len(), SHELL_HISTORY_LIMIT);
+        assert_eq!(config.shell_history["R58M"][0], format!("echo {}", SHELL_HISTORY_LIMIT + 4));
     }
 }
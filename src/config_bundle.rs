@@ -0,0 +1,375 @@
+use crate::config::{self, AppConfig, VIDEO_CODECS};
+use crate::intent::INTENT_HISTORY_LIMIT;
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// Bumped when the bundle layout changes in a way older versions cannot read
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Key of the default profile in `ConfigBundle::profiles`
+const DEFAULT_PROFILE: &str = "default";
+
+/// Settings exported to move DroidView to another machine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigBundle {
+    pub version: u32,
+    pub exported_at: DateTime<Local>,
+    /// Settings of the profile that was in use when exporting
+    pub config: AppConfig,
+    /// The other profiles, by name; `default` is the regular configuration
+    #[serde(default)]
+    pub profiles: BTreeMap<String, AppConfig>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportMode {
+    /// Keep current settings, adding templates, endpoints, intents and profiles that are missing
+    Merge,
+    /// Take over every imported setting and overwrite profiles of the same name
+    Replace,
+}
+
+fn current_profile_key() -> &'static str {
+    config::active_profile().unwrap_or(DEFAULT_PROFILE)
+}
+
+fn profile_arg(name: &str) -> Option<&str> {
+    (name != DEFAULT_PROFILE).then_some(name)
+}
+
+/// Write `config` and every other profile on disk to `path`
+pub fn export_bundle(config: &AppConfig, path: &Path) -> Result<()> {
+    let mut names = config::list_profiles();
+    names.insert(0, DEFAULT_PROFILE.to_string());
+
+    let mut profiles = BTreeMap::new();
+    for name in names.into_iter().filter(|name| name != current_profile_key()) {
+        match AppConfig::load_profile(profile_arg(&name)) {
+            Ok(profile) => {
+                profiles.insert(name, profile);
+            }
+            Err(e) => tracing::warn!("Not exporting profile {}: {}", name, e),
+        }
+    }
+
+    let bundle = ConfigBundle {
+        version: BUNDLE_VERSION,
+        exported_at: Local::now(),
+        config: config.clone(),
+        profiles,
+    };
+    fs::write(path, toml::to_string_pretty(&bundle)?)?;
+    Ok(())
+}
+
+/// Parse a bundle, failing on files DroidView cannot use and warning about settings that will not carry over
+pub fn read_bundle(path: &Path) -> Result<(ConfigBundle, Vec<String>)> {
+    let content = fs::read_to_string(path)?;
+    let bundle: ConfigBundle =
+        toml::from_str(&content).map_err(|e| anyhow::anyhow!("{} is not a DroidView settings export: {}", path.display(), e))?;
+    if bundle.version > BUNDLE_VERSION {
+        return Err(anyhow::anyhow!(
+            "This export was made by a newer DroidView (format {}); update before importing it",
+            bundle.version
+        ));
+    }
+    for name in bundle.profiles.keys() {
+        config::validate_profile_name(name)?;
+    }
+
+    let mut warnings = validate(&bundle.config, "Settings");
+    for (name, profile) in &bundle.profiles {
+        warnings.extend(validate(profile, &format!("Profile {}", name)));
+    }
+    Ok((bundle, warnings))
+}
+
+fn validate(config: &AppConfig, label: &str) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if !VIDEO_CODECS.contains(&config.video_codec.as_str()) {
+        warnings.push(format!("{}: unknown video codec '{}'", label, config.video_codec));
+    }
+    for (name, path) in [("adb", &config.adb_path), ("scrcpy", &config.scrcpy_path)] {
+        if let Some(path) = path
+            && !Path::new(path).exists()
+        {
+            warnings.push(format!("{}: {} path {} does not exist here; the local one is kept", label, name, path));
+        }
+    }
+
+    let mut names = HashSet::new();
+    for template in &config.session_templates {
+        if template.name.trim().is_empty() || !names.insert(template.name.as_str()) {
+            warnings.push(format!("{}: session template '{}' has an empty or duplicate name", label, template.name));
+        }
+        if !template.hotkey.trim().is_empty()
+            && let Err(e) = crate::hotkeys::parse_hotkey(&template.hotkey)
+        {
+            warnings.push(format!("{}: {}", label, e));
+        }
+    }
+    warnings
+}
+
+/// Tool paths and folders only make sense on the machine they were set on
+fn keep_local_paths(imported: &mut AppConfig, current: &AppConfig) {
+    let missing = |path: &Option<String>| path.as_deref().is_some_and(|path| !Path::new(path).exists());
+    if missing(&imported.adb_path) {
+        imported.adb_path = current.adb_path.clone();
+    }
+    if missing(&imported.scrcpy_path) {
+        imported.scrcpy_path = current.scrcpy_path.clone();
+    }
+    if missing(&imported.screenshot_dir) {
+        imported.screenshot_dir = current.screenshot_dir.clone();
+    }
+}
+
+fn merge_into(current: &mut AppConfig, imported: &AppConfig) -> Vec<String> {
+    let mut summary = Vec::new();
+
+    let templates: Vec<_> = imported
+        .session_templates
+        .iter()
+        .filter(|template| !current.session_templates.iter().any(|existing| existing.name == template.name))
+        .cloned()
+        .collect();
+    if !templates.is_empty() {
+        summary.push(format!("Added {} session template(s)", templates.len()));
+        current.session_templates.extend(templates);
+    }
+
+    let snippets: Vec<_> = imported
+        .shell_snippets
+        .iter()
+        .filter(|snippet| !current.shell_snippets.iter().any(|existing| existing.name == snippet.name))
+        .cloned()
+        .collect();
+    if !snippets.is_empty() {
+        summary.push(format!("Added {} shell snippet(s)", snippets.len()));
+        current.shell_snippets.extend(snippets);
+    }
+
+    let endpoints: Vec<_> = imported
+        .wireless_adb
+        .saved_endpoints
+        .iter()
+        .filter(|saved| {
+            !current
+                .wireless_adb
+                .saved_endpoints
+                .iter()
+                .any(|existing| existing.endpoint == saved.endpoint)
+        })
+        .cloned()
+        .collect();
+    if !endpoints.is_empty() {
+        summary.push(format!("Added {} wireless endpoint(s)", endpoints.len()));
+        current.wireless_adb.saved_endpoints.extend(endpoints);
+    }
+
+    let before = current.intent_history.len();
+    for intent in &imported.intent_history {
+        if current.intent_history.len() < INTENT_HISTORY_LIMIT && !current.intent_history.contains(intent) {
+            current.intent_history.push(intent.clone());
+        }
+    }
+    if current.intent_history.len() > before {
+        summary.push(format!("Added {} intent(s) to the history", current.intent_history.len() - before));
+    }
+    summary
+}
+
+/// Apply an imported bundle to `current` and write its profiles; returns what changed
+pub fn apply_bundle(bundle: &ConfigBundle, mode: ImportMode, current: &mut AppConfig) -> Result<Vec<String>> {
+    let mut summary = match mode {
+        ImportMode::Merge => merge_into(current, &bundle.config),
+        ImportMode::Replace => {
+            let mut imported = bundle.config.clone();
+            keep_local_paths(&mut imported, current);
+            *current = imported;
+            vec!["Replaced the current settings".to_string()]
+        }
+    };
+
+    let existing = config::list_profiles();
+    for (name, profile) in &bundle.profiles {
+        // The current profile already received `bundle.config`
+        if name == current_profile_key() {
+            continue;
+        }
+        let exists = name == DEFAULT_PROFILE || existing.contains(name);
+        let mut profile = profile.clone();
+        match (mode, exists) {
+            (ImportMode::Merge, true) => {
+                let mut on_disk = AppConfig::load_profile(profile_arg(name))?;
+                if !merge_into(&mut on_disk, &profile).is_empty() {
+                    on_disk.save_profile(profile_arg(name))?;
+                    summary.push(format!("Merged into profile {}", name));
+                }
+            }
+            (ImportMode::Replace, true) => {
+                keep_local_paths(&mut profile, &AppConfig::load_profile(profile_arg(name))?);
+                profile.save_profile(profile_arg(name))?;
+                summary.push(format!("Replaced profile {}", name));
+            }
+            (_, false) => {
+                keep_local_paths(&mut profile, current);
+                profile.save_profile(profile_arg(name))?;
+                summary.push(format!("Created profile {}", name));
+            }
+        }
+    }
+
+    if summary.is_empty() {
+        summary.push("Nothing new to import".to_string());
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{SessionTemplate, ShellSnippet, WirelessEndpoint};
+    use crate::intent::IntentSpec;
+
+    fn bundle(config: AppConfig) -> ConfigBundle {
+        ConfigBundle {
+            version: BUNDLE_VERSION,
+            exported_at: Local::now(),
+            config,
+            profiles: BTreeMap::new(),
+        }
+    }
+
+    fn snippet(name: &str, command: &str) -> ShellSnippet {
+        ShellSnippet { name: name.to_string(), command: command.to_string() }
+    }
+
+    fn intent(action: &str) -> IntentSpec {
+        IntentSpec { action: action.to_string(), ..IntentSpec::default() }
+    }
+
+    #[test]
+    fn merge_keeps_current_entries_on_conflict() {
+        let mut current = AppConfig::default();
+        current.session_templates.push(SessionTemplate::from_config("Work", "R58M", &current.clone()));
+        current.shell_snippets.push(snippet("Battery", "dumpsys battery"));
+        current.wireless_adb.saved_endpoints.push(WirelessEndpoint::new("192.168.1.20:5555".to_string()));
+        current.intent_history.push(intent("android.intent.action.VIEW"));
+        current.bitrate = "8M".to_string();
+
+        let mut imported = AppConfig::default();
+        imported.session_templates.push(SessionTemplate::from_config("Work", "OTHER", &imported.clone()));
+        imported.session_templates.push(SessionTemplate::low_latency());
+        imported.shell_snippets.push(snippet("Battery", "dumpsys battery --reset"));
+        imported.shell_snippets.push(snippet("Packages", "pm list packages"));
+        imported.wireless_adb.saved_endpoints.push(WirelessEndpoint::new("192.168.1.20:5555".to_string()));
+        imported.wireless_adb.saved_endpoints.push(WirelessEndpoint::new("192.168.1.21:5555".to_string()));
+        imported.intent_history.push(intent("android.intent.action.VIEW"));
+        imported.intent_history.push(intent("android.intent.action.DIAL"));
+        imported.bitrate = "2M".to_string();
+
+        let summary = merge_into(&mut current, &imported);
+        assert_eq!(
+            summary,
+            [
+                "Added 1 session template(s)",
+                "Added 1 shell snippet(s)",
+                "Added 1 wireless endpoint(s)",
+                "Added 1 intent(s) to the history",
+            ]
+        );
+        // Same-named entries and plain settings stay as they were
+        assert_eq!(current.session_templates[0].device, "R58M");
+        assert_eq!(current.shell_snippets[0].command, "dumpsys battery");
+        assert_eq!(current.bitrate, "8M");
+        assert_eq!(current.session_templates.len(), 2);
+        assert_eq!(current.wireless_adb.saved_endpoints.len(), 2);
+        assert_eq!(current.intent_history.len(), 2);
+
+        // A second merge finds nothing new
+        assert!(merge_into(&mut current, &imported).is_empty());
+    }
+
+    #[test]
+    fn merge_stops_at_the_intent_history_limit() {
+        let mut current = AppConfig {
+            intent_history: (0..INTENT_HISTORY_LIMIT).map(|index| intent(&format!("action.{}", index))).collect(),
+            ..AppConfig::default()
+        };
+        let imported = AppConfig { intent_history: vec![intent("action.new")], ..AppConfig::default() };
+
+        assert!(merge_into(&mut current, &imported).is_empty());
+        assert_eq!(current.intent_history.len(), INTENT_HISTORY_LIMIT);
+    }
+
+    #[test]
+    fn replace_takes_imported_settings_but_keeps_local_paths() {
+        let local = std::env::temp_dir().display().to_string();
+        let mut current = AppConfig {
+            adb_path: Some(local.clone()),
+            screenshot_dir: Some(local.clone()),
+            shell_snippets: vec![snippet("Battery", "dumpsys battery")],
+            ..AppConfig::default()
+        };
+        let imported = AppConfig {
+            adb_path: Some("/nonexistent/droidview/adb".to_string()),
+            scrcpy_path: Some(local.clone()),
+            screenshot_dir: None,
+            bitrate: "2M".to_string(),
+            ..AppConfig::default()
+        };
+
+        let summary = apply_bundle(&bundle(imported), ImportMode::Replace, &mut current).unwrap();
+        assert_eq!(summary, ["Replaced the current settings"]);
+        assert_eq!(current.bitrate, "2M");
+        assert!(current.shell_snippets.is_empty());
+        // A path missing on this machine falls back to the local one; paths that exist, or are unset, are taken over
+        assert_eq!(current.adb_path, Some(local.clone()));
+        assert_eq!(current.scrcpy_path, Some(local));
+        assert_eq!(current.screenshot_dir, None);
+    }
+
+    #[test]
+    fn merge_without_anything_new_says_so() {
+        let mut current = AppConfig::default();
+        let summary = apply_bundle(&bundle(AppConfig::default()), ImportMode::Merge, &mut current).unwrap();
+        assert_eq!(summary, ["Nothing new to import"]);
+    }
+
+    #[test]
+    fn reads_bundles_with_missing_fields() {
+        let dir = std::env::temp_dir().join(format!("droidview-bundle-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("export.toml");
+
+        // Fields with defaults may be left out, as older exports do
+        let mut value = toml::Value::try_from(bundle(AppConfig::default())).unwrap();
+        value.as_table_mut().unwrap().remove("profiles");
+        value["config"].as_table_mut().unwrap().remove("video_codec");
+        fs::write(&path, toml::to_string(&value).unwrap()).unwrap();
+        let (read, warnings) = read_bundle(&path).unwrap();
+        assert!(read.profiles.is_empty());
+        assert_eq!(read.config.video_codec, AppConfig::default().video_codec);
+        assert!(warnings.is_empty());
+
+        // Required ones may not
+        value["config"].as_table_mut().unwrap().remove("bitrate");
+        fs::write(&path, toml::to_string(&value).unwrap()).unwrap();
+        let error = read_bundle(&path).unwrap_err().to_string();
+        assert!(error.contains("is not a DroidView settings export"), "{}", error);
+
+        // Nor may a newer format
+        let mut newer = toml::Value::try_from(bundle(AppConfig::default())).unwrap();
+        newer.as_table_mut().unwrap().insert("version".to_string(), toml::Value::Integer(BUNDLE_VERSION as i64 + 1));
+        fs::write(&path, toml::to_string(&newer).unwrap()).unwrap();
+        assert!(read_bundle(&path).unwrap_err().to_string().contains("newer DroidView"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
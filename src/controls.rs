@@ -0,0 +1,450 @@
+//! Device settings changed from the Device Controls panel
+//!
+//! Brightness is the `screen_brightness` system setting, 0 to 255, which only sticks while
+//! automatic brightness is off. Volumes go through `cmd media_session volume`, or the older
+//! `media volume` on devices without it; the volume keys work everywhere but move whichever
+//! stream is active.
+//!
+//! Quick-settings toggles are read back from the settings that hold their state and switched
+//! with `svc`, `cmd` or `settings`. Android has no shell command for the torch, so the flashlight
+//! toggle opens the Quick Settings shade, where its tile can be tapped in the mirror.
+//!
+//! Battery simulation overrides what the battery service reports with `dumpsys battery set`,
+//! so apps see the chosen level and charging state until `dumpsys battery reset`.
+//!
+//! Developer options that live in system properties only reach running apps when they are told
+//! to read their properties again, which `service call activity` with `SYSPROPS_TRANSACTION` does.
+
+use crate::automation::shell;
+use crate::capabilities::Capability;
+use anyhow::Result;
+
+pub const BRIGHTNESS_MAX: u8 = 255;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VolumeStream {
+    Media,
+    Ring,
+}
+
+impl VolumeStream {
+    pub const ALL: [VolumeStream; 2] = [VolumeStream::Media, VolumeStream::Ring];
+
+    /// Stream number of `AudioManager`
+    fn id(self) -> u8 {
+        match self {
+            VolumeStream::Media => 3,
+            VolumeStream::Ring => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VolumeKey {
+    Up,
+    Down,
+    Mute,
+}
+
+impl VolumeKey {
+    fn keycode(self) -> &'static str {
+        match self {
+            VolumeKey::Up => "KEYCODE_VOLUME_UP",
+            VolumeKey::Down => "KEYCODE_VOLUME_DOWN",
+            VolumeKey::Mute => "KEYCODE_VOLUME_MUTE",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Toggle {
+    Wifi,
+    MobileData,
+    AirplaneMode,
+    DoNotDisturb,
+    AutoRotate,
+    Flashlight,
+}
+
+impl Toggle {
+    pub const ALL: [Toggle; 6] = [
+        Toggle::Wifi,
+        Toggle::MobileData,
+        Toggle::AirplaneMode,
+        Toggle::DoNotDisturb,
+        Toggle::AutoRotate,
+        Toggle::Flashlight,
+    ];
+
+    /// Namespace and name of the setting holding the state; anything but 0 is on
+    fn setting(self) -> &'static str {
+        match self {
+            Toggle::Wifi => "global wifi_on",
+            Toggle::MobileData => "global mobile_data",
+            Toggle::AirplaneMode => "global airplane_mode_on",
+            Toggle::DoNotDisturb => "global zen_mode",
+            Toggle::AutoRotate => "system accelerometer_rotation",
+            Toggle::Flashlight => "secure flashlight_enabled",
+        }
+    }
+
+    /// Commands that switch the toggle, tried in order until one works
+    fn commands(self, on: bool) -> Vec<String> {
+        let (enable, flag) = if on { ("enable", 1) } else { ("disable", 0) };
+        match self {
+            Toggle::Wifi => vec![
+                format!("svc wifi {}", enable),
+                format!("cmd wifi set-wifi-enabled {}d", enable),
+            ],
+            Toggle::MobileData => vec![format!("svc data {}", enable)],
+            Toggle::AirplaneMode => vec![
+                format!("cmd connectivity airplane-mode {}", enable),
+                format!(
+                    "settings put global airplane_mode_on {} && \
+                     am broadcast -a android.intent.action.AIRPLANE_MODE --ez state {}",
+                    flag, on
+                ),
+            ],
+            Toggle::DoNotDisturb => vec![format!("cmd notification set_dnd {}", if on { "on" } else { "off" })],
+            Toggle::AutoRotate => vec![format!("settings put system accelerometer_rotation {}", flag)],
+            Toggle::Flashlight => vec!["cmd statusbar expand-settings".to_string()],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DevOption {
+    LayoutBounds,
+    PointerLocation,
+    GpuOverdraw,
+    StrictModeFlash,
+}
+
+impl DevOption {
+    pub const ALL: [DevOption; 4] = [
+        DevOption::LayoutBounds,
+        DevOption::PointerLocation,
+        DevOption::GpuOverdraw,
+        DevOption::StrictModeFlash,
+    ];
+
+    /// Prints the current value on one line, with a default when it was never set
+    fn read_command(self) -> &'static str {
+        match self {
+            DevOption::LayoutBounds => "getprop debug.layout false",
+            DevOption::PointerLocation => "settings get system pointer_location",
+            DevOption::GpuOverdraw => "getprop debug.hwui.overdraw false",
+            DevOption::StrictModeFlash => "getprop persist.sys.strictmode.visual 0",
+        }
+    }
+
+    fn is_on(self, value: &str) -> bool {
+        match self {
+            DevOption::GpuOverdraw => value == "show",
+            _ => value == "1" || value == "true",
+        }
+    }
+
+    fn command(self, on: bool) -> String {
+        match self {
+            DevOption::LayoutBounds => format!("setprop debug.layout {}", on),
+            DevOption::PointerLocation => format!("settings put system pointer_location {}", u8::from(on)),
+            DevOption::GpuOverdraw => format!("setprop debug.hwui.overdraw {}", if on { "show" } else { "false" }),
+            DevOption::StrictModeFlash => format!("setprop persist.sys.strictmode.visual {}", u8::from(on)),
+        }
+    }
+
+    /// A system property, which running apps only notice when poked
+    fn needs_poke(self) -> bool {
+        self != DevOption::PointerLocation
+    }
+}
+
+/// Window, transition and animator duration scales, which the panel sets together
+pub const ANIMATION_SCALES: [f32; 3] = [0.0, 0.5, 1.0];
+
+/// Tells running apps to read the debug system properties again
+const SYSPROPS_TRANSACTION: &str = "service call activity 1599295570";
+
+/// Charging states of `BatteryManager` that can be simulated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatteryStatus {
+    Charging,
+    Discharging,
+    NotCharging,
+    Full,
+}
+
+impl BatteryStatus {
+    pub const ALL: [BatteryStatus; 4] = [
+        BatteryStatus::Charging,
+        BatteryStatus::Discharging,
+        BatteryStatus::NotCharging,
+        BatteryStatus::Full,
+    ];
+
+    fn code(self) -> u8 {
+        match self {
+            BatteryStatus::Charging => 2,
+            BatteryStatus::Discharging => 3,
+            BatteryStatus::NotCharging => 4,
+            BatteryStatus::Full => 5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Volume {
+    pub level: u32,
+    pub min: u32,
+    pub max: u32,
+}
+
+/// What the panel shows; a value is None when the device would not report it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ControlState {
+    pub brightness: Option<u8>,
+    pub auto_brightness: bool,
+    pub media_volume: Option<Volume>,
+    pub ring_volume: Option<Volume>,
+    /// Toggles whose state the device reports
+    pub toggles: Vec<(Toggle, bool)>,
+    pub dev_options: Vec<(DevOption, bool)>,
+    /// Window animation scale, 1 when never changed
+    pub animation_scale: Option<f32>,
+}
+
+impl ControlState {
+    pub fn volume(&self, stream: VolumeStream) -> Option<Volume> {
+        match stream {
+            VolumeStream::Media => self.media_volume,
+            VolumeStream::Ring => self.ring_volume,
+        }
+    }
+
+    pub fn toggle(&self, toggle: Toggle) -> Option<bool> {
+        self.toggles.iter().find(|(known, _)| *known == toggle).map(|(_, on)| *on)
+    }
+
+    pub fn dev_option(&self, option: DevOption) -> Option<bool> {
+        self.dev_options.iter().find(|(known, _)| *known == option).map(|(_, on)| *on)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ControlChange {
+    Brightness(u8),
+    AutoBrightness(bool),
+    Volume(VolumeStream, u32),
+    VolumeKey(VolumeKey),
+    Toggle(Toggle, bool),
+    DevOption(DevOption, bool),
+    AnimationScale(f32),
+    SimulateBattery { level: u8, status: BatteryStatus },
+    ResetBattery,
+}
+
+impl ControlChange {
+    /// What the shell user has to be allowed for this change, on builds that restrict it
+    pub fn capability(self) -> Option<Capability> {
+        match self {
+            ControlChange::Toggle(Toggle::DoNotDisturb, _) => Some(Capability::NotificationPolicy),
+            ControlChange::AnimationScale(_) => Some(Capability::GlobalSettings),
+            _ => None,
+        }
+    }
+}
+
+pub fn read(adb_path: &str, serial: &str) -> Result<ControlState> {
+    let brightness = shell(adb_path, serial, "settings get system screen_brightness")?;
+    let mode = shell(adb_path, serial, "settings get system screen_brightness_mode").unwrap_or_default();
+    Ok(ControlState {
+        brightness: brightness.trim().parse::<u32>().ok().map(|level| level.min(BRIGHTNESS_MAX as u32) as u8),
+        auto_brightness: mode.trim() == "1",
+        media_volume: read_volume(adb_path, serial, VolumeStream::Media),
+        ring_volume: read_volume(adb_path, serial, VolumeStream::Ring),
+        toggles: parse_toggles(&shell(adb_path, serial, &toggles_command()).unwrap_or_default()),
+        dev_options: parse_dev_options(&shell(adb_path, serial, &dev_options_command()).unwrap_or_default()),
+        animation_scale: shell(adb_path, serial, "settings get global window_animation_scale")
+            .ok()
+            .map(|scale| parse_animation_scale(&scale)),
+    })
+}
+
+pub fn apply(adb_path: &str, serial: &str, change: ControlChange) -> Result<()> {
+    match change {
+        ControlChange::Brightness(level) => {
+            // Automatic brightness would move the level again right away
+            shell(adb_path, serial, "settings put system screen_brightness_mode 0")?;
+            shell(adb_path, serial, &format!("settings put system screen_brightness {}", level))?;
+        }
+        ControlChange::AutoBrightness(auto) => {
+            shell(adb_path, serial, &format!("settings put system screen_brightness_mode {}", u8::from(auto)))?;
+        }
+        ControlChange::Volume(stream, level) => {
+            let args = format!("volume --stream {} --set {}", stream.id(), level);
+            let output = shell(adb_path, serial, &format!("cmd media_session {}", args))
+                .or_else(|_| shell(adb_path, serial, &format!("media {}", args)))?;
+            if parse_volume(&output).is_none() && output.to_lowercase().contains("error") {
+                return Err(anyhow::anyhow!(output));
+            }
+        }
+        ControlChange::VolumeKey(key) => {
+            shell(adb_path, serial, &format!("input keyevent {}", key.keycode()))?;
+        }
+        ControlChange::Toggle(toggle, on) => {
+            let mut result = Err(anyhow::anyhow!("Nothing to run"));
+            for command in toggle.commands(on) {
+                result = shell(adb_path, serial, &command);
+                if result.is_ok() {
+                    break;
+                }
+            }
+            result?;
+            // Radios take a moment to change the setting that is read back
+            std::thread::sleep(std::time::Duration::from_millis(500));
+        }
+        ControlChange::DevOption(option, on) => {
+            shell(adb_path, serial, &option.command(on))?;
+            if option.needs_poke() {
+                shell(adb_path, serial, SYSPROPS_TRANSACTION)?;
+            }
+        }
+        ControlChange::SimulateBattery { level, status } => {
+            shell(adb_path, serial, &battery_command(level, status))?;
+        }
+        ControlChange::ResetBattery => {
+            shell(adb_path, serial, "dumpsys battery reset")?;
+        }
+        ControlChange::AnimationScale(scale) => {
+            let command = ["window_animation_scale", "transition_animation_scale", "animator_duration_scale"]
+                .map(|setting| format!("settings put global {} {}", setting, scale))
+                .join(" && ");
+            shell(adb_path, serial, &command)?;
+        }
+    }
+    Ok(())
+}
+
+/// One shell command printing the setting of every toggle, a line each
+fn toggles_command() -> String {
+    Toggle::ALL
+        .iter()
+        .map(|toggle| format!("settings get {}", toggle.setting()))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Pair the lines printed by `toggles_command` with their toggles, leaving out unset ones
+pub fn parse_toggles(output: &str) -> Vec<(Toggle, bool)> {
+    Toggle::ALL
+        .into_iter()
+        .zip(output.lines())
+        .filter_map(|(toggle, value)| value.trim().parse::<i64>().ok().map(|value| (toggle, value != 0)))
+        .collect()
+}
+
+/// Report `level` percent and `status`, with a charger connected unless discharging
+pub fn battery_command(level: u8, status: BatteryStatus) -> String {
+    let power = match status {
+        BatteryStatus::Discharging => "dumpsys battery unplug",
+        _ => "dumpsys battery set ac 1",
+    };
+    format!(
+        "{} && dumpsys battery set status {} && dumpsys battery set level {}",
+        power,
+        status.code(),
+        level.min(100)
+    )
+}
+
+fn dev_options_command() -> String {
+    DevOption::ALL.map(DevOption::read_command).join("; ")
+}
+
+/// Pair the lines printed by `dev_options_command` with their options
+pub fn parse_dev_options(output: &str) -> Vec<(DevOption, bool)> {
+    DevOption::ALL
+        .into_iter()
+        .zip(output.lines())
+        .map(|(option, value)| (option, option.is_on(value.trim())))
+        .collect()
+}
+
+/// An unset scale, printed as `null`, is the default of 1
+pub fn parse_animation_scale(output: &str) -> f32 {
+    output.trim().parse().unwrap_or(1.0)
+}
+
+fn read_volume(adb_path: &str, serial: &str, stream: VolumeStream) -> Option<Volume> {
+    let args = format!("volume --stream {} --get", stream.id());
+    [format!("cmd media_session {}", args), format!("media {}", args)]
+        .iter()
+        .find_map(|command| shell(adb_path, serial, command).ok().and_then(|output| parse_volume(&output)))
+}
+
+/// Parse `volume is 7 in range [0..15]` out of the volume command's output
+pub fn parse_volume(output: &str) -> Option<Volume> {
+    let line = output.lines().find_map(|line| line.split_once("volume is ").map(|(_, rest)| rest))?;
+    let (level, range) = line.split_once(" in range [")?;
+    let (min, max) = range.trim_end_matches(']').split_once("..")?;
+    Some(Volume {
+        level: level.trim().parse().ok()?,
+        min: min.trim().parse().ok()?,
+        max: max.trim().parse().ok()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_volume_output() {
+        let output = "[V] will get volume\n[V] volume is 7 in range [0..15]\n";
+        assert_eq!(parse_volume(output), Some(Volume { level: 7, min: 0, max: 15 }));
+        assert_eq!(
+            parse_volume("[v] volume is 1 in range [1..7]"),
+            Some(Volume { level: 1, min: 1, max: 7 })
+        );
+        assert_eq!(parse_volume("Error: unknown command 'volume'"), None);
+    }
+
+    #[test]
+    fn reads_toggle_states() {
+        assert!(toggles_command().starts_with("settings get global wifi_on; settings get global mobile_data;"));
+        let toggles = parse_toggles("1\n0\n0\n3\n1\nnull\n");
+        assert_eq!(toggles.len(), 5);
+        let state = ControlState { toggles, ..ControlState::default() };
+        assert_eq!(state.toggle(Toggle::Wifi), Some(true));
+        assert_eq!(state.toggle(Toggle::MobileData), Some(false));
+        // Any Do Not Disturb mode counts as on
+        assert_eq!(state.toggle(Toggle::DoNotDisturb), Some(true));
+        assert_eq!(state.toggle(Toggle::Flashlight), None);
+    }
+
+    #[test]
+    fn simulates_battery_states() {
+        assert_eq!(
+            battery_command(5, BatteryStatus::Discharging),
+            "dumpsys battery unplug && dumpsys battery set status 3 && dumpsys battery set level 5"
+        );
+        assert_eq!(
+            battery_command(120, BatteryStatus::Charging),
+            "dumpsys battery set ac 1 && dumpsys battery set status 2 && dumpsys battery set level 100"
+        );
+    }
+
+    #[test]
+    fn reads_developer_options() {
+        let options = parse_dev_options("true\nnull\nshow\n0\n");
+        let state = ControlState { dev_options: options, ..ControlState::default() };
+        assert_eq!(state.dev_option(DevOption::LayoutBounds), Some(true));
+        assert_eq!(state.dev_option(DevOption::PointerLocation), Some(false));
+        assert_eq!(state.dev_option(DevOption::GpuOverdraw), Some(true));
+        assert_eq!(state.dev_option(DevOption::StrictModeFlash), Some(false));
+        assert_eq!(DevOption::GpuOverdraw.command(false), "setprop debug.hwui.overdraw false");
+        assert_eq!(parse_animation_scale("0.5"), 0.5);
+        assert_eq!(parse_animation_scale("null"), 1.0);
+    }
+}
@@ -0,0 +1,142 @@
+//! Crash reports for panics, written to the `crashes` folder of the config directory
+//!
+//! The panic hook saves the message, location and a backtrace as `crash-<time>.txt`. On the next
+//! start the app offers the newest unseen report with a prefilled GitHub issue, then renames it to
+//! `*.seen.txt`. Release builds keep their symbols so the backtrace still names functions.
+
+use crate::config::app_dir;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const ISSUES_URL: &str = "https://github.com/Genxster1998/DroidView/issues/new";
+
+/// Folder holding crash reports, inside the config directory
+pub fn crash_dir() -> Option<PathBuf> {
+    app_dir().ok().map(|dir| dir.join("crashes"))
+}
+
+/// Write a crash report for every panic, then defer to the default hook
+///
+/// GUI panics otherwise disappear with the window, and release builds abort on panic.
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic payload".to_string());
+        let location = info
+            .location()
+            .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
+            .unwrap_or_else(|| "unknown location".to_string());
+        let thread = std::thread::current().name().unwrap_or("unnamed").to_string();
+        let backtrace = std::backtrace::Backtrace::force_capture();
+
+        let report = format!(
+            "DroidView {}\nOS: {}\nTime: {}\nThread: {}\nLocation: {}\n\nPanic: {}\n\nBacktrace:\n{}\n",
+            env!("CARGO_PKG_VERSION"),
+            os_info::get(),
+            chrono::Local::now().to_rfc3339(),
+            thread,
+            location,
+            message,
+            backtrace
+        );
+
+        if let Some(dir) = crash_dir()
+            && fs::create_dir_all(&dir).is_ok()
+        {
+            // Milliseconds and a counter, so panics on several threads at once don't overwrite each other
+            let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S%.3f").to_string();
+            let written = (0..100).find_map(|n| {
+                let name = if n == 0 { format!("crash-{}.txt", stamp) } else { format!("crash-{}-{}.txt", stamp, n) };
+                let path = dir.join(name);
+                let mut file = fs::OpenOptions::new().write(true).create_new(true).open(&path).ok()?;
+                file.write_all(report.as_bytes()).ok().map(|_| path)
+            });
+            if let Some(path) = written {
+                eprintln!("Crash report written to {}", path.display());
+            }
+        }
+
+        default_hook(info);
+    }));
+}
+
+/// Reports not yet shown to the user, newest first
+pub fn pending_reports() -> Vec<PathBuf> {
+    let Some(dir) = crash_dir() else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut reports: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            name.starts_with("crash-") && name.ends_with(".txt") && !name.ends_with(".seen.txt")
+        })
+        .collect();
+    reports.sort();
+    reports.reverse();
+    reports
+}
+
+/// Rename a report to `*.seen.txt` so it isn't offered again, keeping it on disk
+pub fn mark_seen(report: &Path) {
+    let seen = report.with_extension("seen.txt");
+    if let Err(e) = fs::rename(report, &seen) {
+        tracing::warn!("Failed to mark crash report {} as seen: {}", report.display(), e);
+    }
+}
+
+/// Prefilled "new issue" link with the panic summary; the full report is attached by hand
+pub fn issue_url(report: &Path) -> Option<String> {
+    let content = fs::read_to_string(report).ok()?;
+    let summary: String = content
+        .lines()
+        .take_while(|line| !line.starts_with("Backtrace:"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let panic_line = content
+        .lines()
+        .find_map(|line| line.strip_prefix("Panic: "))
+        .unwrap_or("crash");
+    // The report's path would publish the user's home folder, and often their name
+    let body = format!(
+        "**Crash report**\n\n```\n{}\n```\n\nPlease attach the full crash report file.",
+        summary.trim()
+    );
+
+    reqwest::Url::parse_with_params(
+        ISSUES_URL,
+        &[("title", format!("Crash: {}", panic_line)), ("body", body)],
+    )
+    .ok()
+    .map(|url| url.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn issue_links_leave_out_the_report_path() {
+        let dir = std::env::temp_dir().join(format!("droidview-crash-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let report = dir.join("crash-20260101-120000.txt");
+        fs::write(&report, "DroidView 0.1\nPanic: index out of bounds\n\nBacktrace:\n  0: main\n").unwrap();
+        let url = issue_url(&report).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(url.contains("index+out+of+bounds") || url.contains("index%20out%20of%20bounds"));
+        assert!(!url.contains("droidview-crash"));
+        assert!(!url.contains("Backtrace"));
+    }
+}
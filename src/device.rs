@@ -1,6 +1,13 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::process::Command;
+use crate::bridge::adb_command;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+/// How often each device's connection health is measured
+pub const HEALTH_INTERVAL: Duration = Duration::from_secs(10);
+/// A device that takes longer than this to answer counts as unresponsive
+pub const HEALTH_TIMEOUT: Duration = Duration::from_secs(5);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Device {
@@ -10,9 +17,68 @@ pub struct Device {
     pub model: String,
     pub device: String,
     pub transport_id: String,
+    #[serde(default)]
+    pub details: DeviceDetails,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Facts read from a connected device; each is `None` when it could not be read
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeviceDetails {
+    /// e.g. `14`
+    pub android_version: Option<String>,
+    pub api_level: Option<u32>,
+    /// Charge in percent
+    pub battery: Option<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionType {
+    Usb,
+    Wifi,
+    Emulator,
+}
+
+impl ConnectionType {
+    pub fn label(self) -> &'static str {
+        match self {
+            ConnectionType::Usb => "USB",
+            ConnectionType::Wifi => "Wi-Fi",
+            ConnectionType::Emulator => "Emulator",
+        }
+    }
+
+    pub fn icon(self) -> &'static str {
+        match self {
+            ConnectionType::Usb => egui_phosphor::fill::USB,
+            ConnectionType::Wifi => egui_phosphor::fill::WIFI_HIGH,
+            ConnectionType::Emulator => egui_phosphor::fill::DESKTOP,
+        }
+    }
+}
+
+/// How quickly a device answers over its connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkHealth {
+    Good,
+    Slow,
+    Poor,
+    /// Failed or timed out
+    Unresponsive,
+}
+
+impl LinkHealth {
+    /// Classify the round trip of a trivial shell command; `None` when it never answered
+    pub fn from_latency(latency: Option<Duration>) -> Self {
+        match latency {
+            Some(latency) if latency <= Duration::from_millis(250) => LinkHealth::Good,
+            Some(latency) if latency <= Duration::from_secs(1) => LinkHealth::Slow,
+            Some(_) => LinkHealth::Poor,
+            None => LinkHealth::Unresponsive,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DeviceStatus {
     Device,
     Offline,
@@ -38,8 +104,19 @@ impl Device {
         matches!(self.status, DeviceStatus::Device)
     }
 
+    /// Told from the serial: `ip:port` and mDNS names are wireless, `emulator-N` local
+    pub fn connection_type(&self) -> ConnectionType {
+        if self.identifier.starts_with("emulator-") {
+            ConnectionType::Emulator
+        } else if self.identifier.contains(':') || self.identifier.contains("._adb-tls-") {
+            ConnectionType::Wifi
+        } else {
+            ConnectionType::Usb
+        }
+    }
+
     pub fn get_dimensions(&self, adb_path: &str) -> Result<Option<(u32, u32)>> {
-        let output = Command::new(adb_path)
+        let output = adb_command(adb_path)
             .args(["-s", &self.identifier, "shell", "wm", "size"])
             .output()?;
 
@@ -56,12 +133,11 @@ impl Device {
                 if parts.len() == 2 {
                     let size_str = parts[1].trim();
                     let dimensions: Vec<&str> = size_str.split('x').collect();
-                    if dimensions.len() == 2 {
-                        if let (Ok(width), Ok(height)) =
+                    if dimensions.len() == 2
+                        && let (Ok(width), Ok(height)) =
                             (dimensions[0].parse::<u32>(), dimensions[1].parse::<u32>())
-                        {
-                            return Ok(Some((width, height)));
-                        }
+                    {
+                        return Ok(Some((width, height)));
                     }
                 }
             }
@@ -71,8 +147,73 @@ impl Device {
     }
 }
 
+/// A logical display reported by `dumpsys display`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DisplayEntry {
+    pub id: u32,
+    pub name: String,
+}
+
+pub fn get_displays(adb_path: &str, device_id: &str) -> Result<Vec<DisplayEntry>> {
+    let output = adb_command(adb_path)
+        .args(["-s", device_id, "shell", "dumpsys", "display"])
+        .output()?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!("Failed to execute dumpsys display"));
+    }
+
+    Ok(parse_displays(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse entries like `DisplayInfo{"Built-in Screen", displayId 0, ...}` (or `"Built-in Screen, displayId 0"` before Android 13)
+pub fn parse_displays(output: &str) -> Vec<DisplayEntry> {
+    let mut displays: Vec<DisplayEntry> = Vec::new();
+
+    for line in output.lines() {
+        let mut rest = line;
+        while let Some(start) = rest.find("DisplayInfo{\"") {
+            rest = &rest[start + "DisplayInfo{\"".len()..];
+            let Some(name_end) = rest.find('"') else {
+                break;
+            };
+            let quoted = &rest[..name_end];
+            rest = &rest[name_end..];
+            // Only this entry's fields; older Androids print no displayId, and the next entry's is not ours
+            let entry = &rest[..rest.find("DisplayInfo{\"").unwrap_or(rest.len())];
+
+            // Android 10-12 put the id inside the quotes: `"Built-in Screen, displayId 0"`
+            let (name, entry) = match quoted.rfind(", displayId ") {
+                Some(pos) => (&quoted[..pos], &quoted[pos..]),
+                None => (quoted, entry),
+            };
+            let name = name.to_string();
+
+            let id = entry
+                .find("displayId ")
+                .map(|pos| &entry[pos + "displayId ".len()..])
+                .and_then(|s| {
+                    s.split(|c: char| !c.is_ascii_digit())
+                        .next()
+                        .and_then(|n| n.parse::<u32>().ok())
+                });
+
+            if let Some(id) = id
+                && !displays.iter().any(|d| d.id == id)
+            {
+                displays.push(DisplayEntry { id, name });
+            }
+        }
+    }
+
+    displays.sort_by_key(|d| d.id);
+    displays
+}
+
+/// Attached devices with their Android version and battery; this asks every device, so call it
+/// off the UI thread
 pub fn get_devices(adb_path: &str) -> Result<Vec<Device>> {
-    let output = Command::new(adb_path).args(["devices", "-l"]).output()?;
+    let output = adb_command(adb_path).args(["devices", "-l"]).output()?;
 
     if !output.status.success() {
         return Err(anyhow::anyhow!("Failed to execute adb devices"));
@@ -129,22 +270,229 @@ pub fn get_devices(adb_path: &str) -> Result<Vec<Device>> {
             model,
             device,
             transport_id,
+            details: DeviceDetails::default(),
         });
     }
 
+    // Asked all at once, so one slow device does not hold up the others
+    std::thread::scope(|scope| {
+        for device in devices.iter_mut().filter(|device| device.status == DeviceStatus::Device) {
+            scope.spawn(move || device.details = get_device_details(adb_path, &device.identifier));
+        }
+    });
+
     Ok(devices)
 }
 
+/// Serial and state of each attached device, without asking the devices anything; cheap
+/// enough to poll for devices coming and going
+pub fn get_device_states(adb_path: &str) -> Result<Vec<(String, DeviceStatus)>> {
+    let output = adb_command(adb_path).arg("devices").output()?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!("Failed to execute adb devices"));
+    }
+    Ok(parse_device_states(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse `adb devices` output
+pub fn parse_device_states(output: &str) -> Vec<(String, DeviceStatus)> {
+    output
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            Some((parts.next()?.to_string(), DeviceStatus::from(parts.next()?)))
+        })
+        .collect()
+}
+
+/// Time `adb shell echo ok` takes on the device, or `None` when it fails or takes longer
+/// than `timeout`
+pub fn ping(adb_path: &str, device_id: &str, timeout: Duration) -> Option<Duration> {
+    let started = Instant::now();
+    let mut child = adb_command(adb_path)
+        .args(["-s", device_id, "shell", "echo", "ok"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if started.elapsed() < timeout => std::thread::sleep(Duration::from_millis(10)),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    }
+    let latency = started.elapsed();
+    let output = child.wait_with_output().ok()?;
+    (output.status.success() && String::from_utf8_lossy(&output.stdout).trim() == "ok").then_some(latency)
+}
+
+/// Android version, API level and battery charge, in a single shell round trip
+pub fn get_device_details(adb_path: &str, device_id: &str) -> DeviceDetails {
+    let output = adb_command(adb_path)
+        .args([
+            "-s",
+            device_id,
+            "shell",
+            "getprop ro.build.version.release; getprop ro.build.version.sdk; dumpsys battery",
+        ])
+        .output();
+    match output {
+        Ok(output) if output.status.success() => parse_device_details(&String::from_utf8_lossy(&output.stdout)),
+        _ => DeviceDetails::default(),
+    }
+}
+
+/// Parse the release and SDK lines followed by `dumpsys battery` output
+pub fn parse_device_details(output: &str) -> DeviceDetails {
+    let mut lines = output.lines().map(str::trim);
+    let android_version = lines.next().filter(|line| !line.is_empty() && !line.contains(':')).map(str::to_string);
+    let api_level = lines.next().and_then(|line| line.parse().ok());
+
+    let mut level = None;
+    let mut scale = 100;
+    for line in lines {
+        if let Some(value) = line.strip_prefix("level:") {
+            level = value.trim().parse::<u32>().ok();
+        } else if let Some(value) = line.strip_prefix("scale:") {
+            scale = value.trim().parse::<u32>().ok().filter(|scale| *scale > 0).unwrap_or(100);
+        }
+    }
+    let battery = level.map(|level| (level * 100 / scale).min(100) as u8);
+
+    DeviceDetails {
+        android_version,
+        api_level,
+        battery,
+    }
+}
+
+/// A screenshot of the device scaled down to fit `max_edge` pixels on its longer side
+pub fn capture_thumbnail(adb_path: &str, device_id: &str, max_edge: u32) -> Result<image::RgbaImage> {
+    Ok(capture_screen(adb_path, device_id, max_edge)?.0)
+}
+
+/// A screenshot no larger than `max_edge` pixels on its longer side, and the size of the screen it shows
+pub fn capture_screen(adb_path: &str, device_id: &str, max_edge: u32) -> Result<(image::RgbaImage, (u32, u32))> {
+    let output = adb_command(adb_path)
+        .args(["-s", device_id, "exec-out", "screencap", "-p"])
+        .output()?;
+    if !output.status.success() || output.stdout.is_empty() {
+        return Err(anyhow::anyhow!("screencap failed"));
+    }
+    let image = image::load_from_memory(&output.stdout)?;
+    let size = (image.width(), image.height());
+    let image = if size.0.max(size.1) > max_edge { image.thumbnail(max_edge, max_edge) } else { image };
+    Ok((image.to_rgba8(), size))
+}
+
 pub fn restart_adb_server(adb_path: &str) -> Result<()> {
-    let status = Command::new(adb_path).arg("kill-server").status()?;
+    if let Some(socket) = crate::bridge::adb_server_socket() {
+        return Err(anyhow::anyhow!("The adb server at {} has to be restarted on its own machine", socket));
+    }
+    let status = adb_command(adb_path).arg("kill-server").status()?;
     if !status.success() {
         return Err(anyhow::anyhow!("Failed to kill ADB server"));
     }
 
-    let status = Command::new(adb_path).arg("start-server").status()?;
+    let status = adb_command(adb_path).arg("start-server").status()?;
     if !status.success() {
         return Err(anyhow::anyhow!("Failed to start ADB server"));
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn device_details() {
+        let details = parse_device_details(
+            "14\n34\nCurrent Battery Service state:\n  AC powered: false\n  USB powered: true\n  level: 85\n  scale: 100\n",
+        );
+        assert_eq!(details.android_version.as_deref(), Some("14"));
+        assert_eq!(details.api_level, Some(34));
+        assert_eq!(details.battery, Some(85));
+
+        // No battery service, e.g. on some TV boxes
+        let details = parse_device_details("8.1.0\n27\n");
+        assert_eq!(details.api_level, Some(27));
+        assert_eq!(details.battery, None);
+
+        assert_eq!(parse_device_details(""), DeviceDetails::default());
+    }
+
+    #[test]
+    fn link_health() {
+        assert_eq!(LinkHealth::from_latency(Some(Duration::from_millis(40))), LinkHealth::Good);
+        assert_eq!(LinkHealth::from_latency(Some(Duration::from_millis(600))), LinkHealth::Slow);
+        assert_eq!(LinkHealth::from_latency(Some(Duration::from_secs(3))), LinkHealth::Poor);
+        assert_eq!(LinkHealth::from_latency(None), LinkHealth::Unresponsive);
+    }
+
+    #[test]
+    fn device_states() {
+        let states = parse_device_states(
+            "List of devices attached\nR58M123ABC\tdevice\n192.168.1.20:5555\tunauthorized\n\nemulator-5554\toffline\n",
+        );
+        assert_eq!(
+            states,
+            vec![
+                ("R58M123ABC".to_string(), DeviceStatus::Device),
+                ("192.168.1.20:5555".to_string(), DeviceStatus::Unauthorized),
+                ("emulator-5554".to_string(), DeviceStatus::Offline),
+            ]
+        );
+        assert!(parse_device_states("List of devices attached\n\n").is_empty());
+    }
+
+    #[test]
+    fn displays() {
+        // Trimmed from `dumpsys display` on Android 13 with an overlay display
+        let output = r#"DISPLAY MANAGER (dumpsys display)
+Display Devices: size=2
+  DisplayDeviceInfo{"Built-in Screen": uniqueId="local:4619827259835644672", 1080 x 2400, modeId 1, defaultModeId 1, type INTERNAL}
+  DisplayDeviceInfo{"Overlay #1": uniqueId="overlay:com.android.shell,0", 1920 x 1080, modeId 2, type OVERLAY}
+Logical Displays: size=2
+  Display 0:
+    mDisplayId=0
+    mBaseDisplayInfo=DisplayInfo{"Built-in Screen", displayId 0", displayGroupId 0, FLAG_SECURE, FLAG_SUPPORTS_PROTECTED_BUFFERS, FLAG_TRUSTED, real 1080 x 2400, largest app 1080 x 2400, smallest app 1080 x 2400, state ON, type INTERNAL, uniqueId "local:4619827259835644672", app 1080 x 2400, density 420 (409.432 x 411.891) dpi, layerStack 0}
+    mOverrideDisplayInfo=DisplayInfo{"Built-in Screen", displayId 0", displayGroupId 0, FLAG_SECURE, real 1080 x 2400, state ON, type INTERNAL, layerStack 0}
+  Display 2:
+    mDisplayId=2
+    mBaseDisplayInfo=DisplayInfo{"Overlay #1", displayId 2", displayGroupId 0, FLAG_PRESENTATION, FLAG_TRUSTED, real 1920 x 1080, state ON, type OVERLAY, layerStack 2}
+"#;
+        assert_eq!(
+            parse_displays(output),
+            vec![
+                DisplayEntry { id: 0, name: "Built-in Screen".to_string() },
+                DisplayEntry { id: 2, name: "Overlay #1".to_string() },
+            ]
+        );
+
+        // Android 9 prints no displayId; on a line with several entries, one without an id must not take the next one's
+        let output = r#"  mBaseDisplayInfo=DisplayInfo{"Built-in Screen", uniqueId "local:0", app 1080 x 1920, real 1080 x 1920, layerStack 0}
+  mViewports=[DisplayInfo{"HDMI Screen", uniqueId "local:1", app 1920 x 1080}, DisplayInfo{"Overlay #1", displayId 3", layerStack 3}]
+"#;
+        assert_eq!(parse_displays(output), vec![DisplayEntry { id: 3, name: "Overlay #1".to_string() }]);
+
+        // Android 10-12 close the quotes after the id
+        let output = r#"    mBaseDisplayInfo=DisplayInfo{"Built-in Screen, displayId 0", uniqueId "local:4630946213010562946", app 1080 x 2340, real 1080 x 2340, largest app 2340 x 2272, smallest app 1080 x 1012, mode 1, defaultMode 1, modes [{id=1, width=1080, height=2340, fps=60.0}], type BUILT_IN, state ON, FLAG_SECURE, layerStack 0}
+    mBaseDisplayInfo=DisplayInfo{"Overlay #1, displayId 4", uniqueId "overlay:1", app 1920 x 1080, real 1920 x 1080, type OVERLAY, state ON, FLAG_PRESENTATION, layerStack 4}
+"#;
+        assert_eq!(
+            parse_displays(output),
+            vec![
+                DisplayEntry { id: 0, name: "Built-in Screen".to_string() },
+                DisplayEntry { id: 4, name: "Overlay #1".to_string() },
+            ]
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
+use crate::bridge::adb_command;
+use std::time::Duration;
+
+/// How many clips the history keeps before dropping the oldest
+pub const HISTORY_LIMIT: usize = 25;
+
+/// How often the device clipboard is read while watching
+pub const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Text copied on the device, as first seen by DroidView
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClipEntry {
+    pub text: String,
+    pub device: String,
+    pub seen_at: DateTime<Local>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ClipboardHistory {
+    /// Newest first
+    pub entries: VecDeque<ClipEntry>,
+}
+
+impl ClipboardHistory {
+    /// Record a clip unless it is the one already on top; true when it was new
+    pub fn push(&mut self, device: &str, text: String) -> bool {
+        if text.is_empty() || self.entries.front().is_some_and(|entry| entry.text == text) {
+            return false;
+        }
+        // Copying an older clip again moves it back to the top
+        self.entries.retain(|entry| entry.text != text);
+        self.entries.push_front(ClipEntry {
+            text,
+            device: device.to_string(),
+            seen_at: Local::now(),
+        });
+        self.entries.truncate(HISTORY_LIMIT);
+        true
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Read the primary clip with `cmd clipboard` (Android 13+); `None` when the clipboard is empty
+pub fn read_clipboard(adb_path: &str, device_id: &str) -> Result<Option<String>> {
+    let output = adb_command(adb_path)
+        .args(["-s", device_id, "shell", "cmd", "clipboard", "get-primary-clip"])
+        .output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lower = stdout.to_lowercase();
+    if !output.status.success() || lower.contains("unknown command") || lower.contains("can't find service") {
+        return Err(anyhow::anyhow!(
+            "Reading the clipboard over ADB needs Android 13 or newer"
+        ));
+    }
+    Ok(parse_primary_clip(&stdout))
+}
+
+/// Extract the text item from a `ClipData { text/plain "label" {T:hello} }` dump
+pub fn parse_primary_clip(output: &str) -> Option<String> {
+    let start = output.find("{T:")? + 3;
+    let rest = &output[start..];
+    // The dump closes the item with "}" and the clip with " }"
+    let end = rest.rfind("} }").or_else(|| rest.rfind('}'))?;
+    Some(rest[..end].to_string()).filter(|text| !text.is_empty())
+}
@@ -0,0 +1,188 @@
+//! Screenshots placed in a generic device frame, for store listings and docs
+//!
+//! The frames are drawn rather than bundled as images, so they fit a screenshot of any size and
+//! aspect ratio: every measure is a share of the screenshot width. Shapes are rounded rectangles
+//! and circles drawn with anti-aliased edges from their signed distance.
+
+use anyhow::Result;
+use image::{Rgba, RgbaImage};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameStyle {
+    /// Rounded corners and a punch-hole camera
+    Phone,
+    /// Wider bezels with the camera in them
+    Tablet,
+    /// A thin border only
+    Minimal,
+}
+
+impl FrameStyle {
+    pub const ALL: [FrameStyle; 3] = [FrameStyle::Phone, FrameStyle::Tablet, FrameStyle::Minimal];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            FrameStyle::Phone => "Phone",
+            FrameStyle::Tablet => "Tablet",
+            FrameStyle::Minimal => "Minimal",
+        }
+    }
+
+    /// Bezel width and screen corner radius as shares of the screenshot width
+    fn measures(self) -> (f32, f32) {
+        match self {
+            FrameStyle::Phone => (0.045, 0.08),
+            FrameStyle::Tablet => (0.07, 0.03),
+            FrameStyle::Minimal => (0.018, 0.05),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameOptions {
+    pub style: FrameStyle,
+    pub bezel: [u8; 4],
+    pub shadow: bool,
+    /// Fill around the device; transparent when `None`
+    pub background: Option<[u8; 4]>,
+}
+
+impl Default for FrameOptions {
+    fn default() -> Self {
+        Self {
+            style: FrameStyle::Phone,
+            bezel: [24, 24, 27, 255],
+            shadow: true,
+            background: None,
+        }
+    }
+}
+
+/// A rounded rectangle by its corners
+#[derive(Clone, Copy)]
+struct Shape {
+    min: (f32, f32),
+    max: (f32, f32),
+    radius: f32,
+}
+
+impl Shape {
+    /// Distance of a point from the edge, negative inside
+    fn distance(&self, x: f32, y: f32) -> f32 {
+        let half = ((self.max.0 - self.min.0) / 2.0, (self.max.1 - self.min.1) / 2.0);
+        let radius = self.radius.min(half.0).min(half.1);
+        let center = (self.min.0 + half.0, self.min.1 + half.1);
+        let qx = (x - center.0).abs() - half.0 + radius;
+        let qy = (y - center.1).abs() - half.1 + radius;
+        qx.max(0.0).hypot(qy.max(0.0)) + qx.max(qy).min(0.0) - radius
+    }
+
+    /// Share of the pixel at `x`, `y` inside the shape, or the shadow strength `softness` pixels out
+    fn coverage(&self, x: u32, y: u32, softness: f32) -> f32 {
+        let distance = self.distance(x as f32 + 0.5, y as f32 + 0.5);
+        (0.5 - distance / softness.max(1.0)).clamp(0.0, 1.0)
+    }
+}
+
+/// `color` drawn over `pixel` with `coverage` of its alpha
+fn blend(pixel: &mut Rgba<u8>, color: [u8; 4], coverage: f32) {
+    let source = color[3] as f32 / 255.0 * coverage;
+    if source <= 0.0 {
+        return;
+    }
+    let destination = pixel[3] as f32 / 255.0 * (1.0 - source);
+    let alpha = source + destination;
+    for channel in 0..3 {
+        let mixed = (color[channel] as f32 * source + pixel[channel] as f32 * destination) / alpha;
+        pixel[channel] = mixed.round() as u8;
+    }
+    pixel[3] = (alpha * 255.0).round() as u8;
+}
+
+/// `screenshot` inside a device frame as `options` describe
+pub fn compose(screenshot: &RgbaImage, options: &FrameOptions) -> RgbaImage {
+    let (width, height) = screenshot.dimensions();
+    let unit = width as f32;
+    let (bezel_share, radius_share) = options.style.measures();
+    let bezel = (unit * bezel_share).round().max(1.0);
+    let margin = if options.shadow || options.background.is_some() { (unit * 0.12).round() } else { 0.0 };
+    let canvas = (width + 2 * (bezel + margin) as u32, height + 2 * (bezel + margin) as u32);
+
+    let screen_min = (margin + bezel, margin + bezel);
+    let screen = Shape {
+        min: screen_min,
+        max: (screen_min.0 + width as f32, screen_min.1 + height as f32),
+        radius: unit * radius_share,
+    };
+    let body = Shape {
+        min: (margin, margin),
+        max: (canvas.0 as f32 - margin, canvas.1 as f32 - margin),
+        radius: screen.radius + bezel,
+    };
+    let drop = unit * 0.02;
+    let shadow = Shape { min: (body.min.0, body.min.1 + drop), max: (body.max.0, body.max.1 + drop), ..body };
+    let camera = match options.style {
+        FrameStyle::Phone => Some((canvas.0 as f32 / 2.0, screen.min.1 + unit * 0.035, unit * 0.018)),
+        FrameStyle::Tablet => Some((canvas.0 as f32 / 2.0, margin + bezel / 2.0, bezel * 0.18)),
+        FrameStyle::Minimal => None,
+    };
+
+    let mut framed = RgbaImage::from_pixel(canvas.0, canvas.1, Rgba(options.background.unwrap_or([0, 0, 0, 0])));
+    for (x, y, pixel) in framed.enumerate_pixels_mut() {
+        if options.shadow {
+            blend(pixel, [0, 0, 0, 110], shadow.coverage(x, y, unit * 0.05));
+        }
+        blend(pixel, options.bezel, body.coverage(x, y, 1.0));
+        let inside = screen.coverage(x, y, 1.0);
+        if inside > 0.0 {
+            let source = (x as f32 - screen.min.0, y as f32 - screen.min.1);
+            let source = ((source.0 as u32).min(width - 1), (source.1 as u32).min(height - 1));
+            blend(pixel, screenshot.get_pixel(source.0, source.1).0, inside);
+        }
+        if let Some((cx, cy, radius)) = camera {
+            let distance = (x as f32 + 0.5 - cx).hypot(y as f32 + 0.5 - cy) - radius;
+            blend(pixel, [8, 8, 10, 255], (0.5 - distance).clamp(0.0, 1.0));
+        }
+    }
+    framed
+}
+
+/// Frame the screenshot at `source` and save it next to it, recorded in the gallery for `device`
+pub fn export(source: &Path, device: &str, options: &FrameOptions) -> Result<PathBuf> {
+    let screenshot = image::open(source)?.to_rgba8();
+    let framed = compose(&screenshot, options);
+    let stem = source.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+    let path = crate::utils::unique_path(source.with_file_name(format!("{}_framed.png", stem)));
+    framed.save(&path)?;
+    crate::gallery::record_capture(&path, crate::gallery::CaptureKind::Screenshot, device, None);
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_screenshots() {
+        let screenshot = RgbaImage::from_pixel(200, 400, Rgba([200, 30, 30, 255]));
+        let options = FrameOptions { style: FrameStyle::Minimal, shadow: false, ..FrameOptions::default() };
+        let framed = compose(&screenshot, &options);
+        // A 4 pixel bezel on each side, with nothing around it
+        assert_eq!(framed.dimensions(), (208, 408));
+        assert_eq!(framed.get_pixel(104, 204).0, [200, 30, 30, 255]);
+        assert_eq!(framed.get_pixel(1, 204).0, options.bezel);
+        // Rounded away at the corner
+        assert_eq!(framed.get_pixel(0, 0).0[3], 0);
+
+        let background = [240, 240, 250, 255];
+        let options = FrameOptions { background: Some(background), ..FrameOptions::default() };
+        let framed = compose(&screenshot, &options);
+        assert_eq!(framed.dimensions(), (266, 466));
+        assert_eq!(framed.get_pixel(1, 1).0, background);
+        // The shadow falls below the device
+        assert!(framed.get_pixel(133, 450).0[0] < 240);
+        // The punch-hole camera at the top of the screen
+        assert_eq!(framed.get_pixel(133, 24 + 9 + 7).0, [8, 8, 10, 255]);
+    }
+}
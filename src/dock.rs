@@ -0,0 +1,222 @@
+//! Arrangement of the dockable panels, persisted as `AppConfig::dock`
+//!
+//! The panels are tabs of an [`egui_dock::DockState`], so they can be split, stacked behind
+//! tabs or torn off into windows of their own. The control panel is a tab as well, but stays
+//! in the main window. Panels that are switched off are left out of the state and come back
+//! beside the panel they share a corner with.
+
+use crate::t;
+use egui_dock::{DockState, Node, NodeIndex, Split, SurfaceIndex, TabIndex};
+use serde::{Deserialize, Deserializer, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DockPanel {
+    /// Selected device and the scrcpy controls, in the middle of the window
+    Session,
+    Devices,
+    Wireless,
+    Toolkit,
+    QuickActions,
+    /// Forms of the installed command plugins, see `plugins`
+    Plugins,
+    /// Brightness and volume of the selected device
+    DeviceControls,
+}
+
+pub type DockLayout = DockState<DockPanel>;
+
+impl DockPanel {
+    pub const ALL: [DockPanel; 7] = [
+        DockPanel::Session,
+        DockPanel::Devices,
+        DockPanel::Wireless,
+        DockPanel::Toolkit,
+        DockPanel::QuickActions,
+        DockPanel::Plugins,
+        DockPanel::DeviceControls,
+    ];
+
+    pub fn id(self) -> &'static str {
+        match self {
+            DockPanel::Session => "session",
+            DockPanel::Devices => "devices",
+            DockPanel::Wireless => "wireless",
+            DockPanel::Toolkit => "toolkit",
+            DockPanel::QuickActions => "quick_actions",
+            DockPanel::Plugins => "plugins",
+            DockPanel::DeviceControls => "device_controls",
+        }
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            DockPanel::Session => t!("dock-panel-session"),
+            DockPanel::Devices => t!("dock-panel-devices"),
+            DockPanel::Wireless => t!("dock-panel-wireless"),
+            DockPanel::Toolkit => t!("dock-panel-toolkit"),
+            DockPanel::QuickActions => t!("dock-panel-quick-actions"),
+            DockPanel::Plugins => t!("dock-panel-plugins"),
+            DockPanel::DeviceControls => t!("dock-panel-device-controls"),
+        }
+    }
+
+    pub fn icon(self) -> &'static str {
+        match self {
+            DockPanel::Session => egui_phosphor::fill::PLAY_CIRCLE,
+            DockPanel::Devices => egui_phosphor::fill::DEVICE_MOBILE,
+            DockPanel::Wireless => egui_phosphor::fill::WIFI_HIGH,
+            DockPanel::Toolkit => egui_phosphor::fill::TOOLBOX,
+            DockPanel::QuickActions => egui_phosphor::fill::LIGHTNING,
+            DockPanel::Plugins => egui_phosphor::fill::PUZZLE_PIECE,
+            DockPanel::DeviceControls => egui_phosphor::fill::SLIDERS_HORIZONTAL,
+        }
+    }
+
+    /// The panel this one sits with in the default layout
+    fn neighbour(self) -> DockPanel {
+        match self {
+            DockPanel::Session => DockPanel::Session,
+            DockPanel::Devices => DockPanel::Wireless,
+            DockPanel::Wireless => DockPanel::Devices,
+            DockPanel::Toolkit => DockPanel::DeviceControls,
+            DockPanel::DeviceControls => DockPanel::Toolkit,
+            DockPanel::QuickActions => DockPanel::Plugins,
+            DockPanel::Plugins => DockPanel::QuickActions,
+        }
+    }
+
+    /// Side of the control panel the panel opens on when its neighbour is not shown, and the
+    /// share of the space the control panel keeps
+    fn home(self) -> (Split, f32) {
+        match self {
+            DockPanel::Session | DockPanel::Devices | DockPanel::Wireless => (Split::Left, 0.75),
+            DockPanel::Toolkit | DockPanel::DeviceControls => (Split::Right, 0.65),
+            DockPanel::QuickActions | DockPanel::Plugins => (Split::Below, 0.8),
+        }
+    }
+}
+
+/// Device list and wireless ADB on the left, the toolkit and device controls on the right,
+/// quick actions and plugins under the control panel
+pub fn default_layout() -> DockLayout {
+    let mut layout = DockState::new(vec![DockPanel::Session]);
+    let tree = layout.main_surface_mut();
+    let [session, devices] = tree.split_left(NodeIndex::root(), 0.25, vec![DockPanel::Devices]);
+    tree.split_below(devices, 0.6, vec![DockPanel::Wireless]);
+    let [session, toolkit] = tree.split_right(session, 0.65, vec![DockPanel::Toolkit]);
+    tree.split_below(toolkit, 0.7, vec![DockPanel::DeviceControls]);
+    tree.split_below(session, 0.8, vec![DockPanel::QuickActions, DockPanel::Plugins]);
+    layout
+}
+
+/// Keep the panels for which `shown` holds once each, adding the ones missing from the layout
+pub fn sync(layout: &mut DockLayout, shown: impl Fn(DockPanel) -> bool) {
+    if layout.find_main_surface_tab(&DockPanel::Session).is_none() {
+        *layout = default_layout();
+    }
+    // One tab at a time: `remove_tab` moves the sibling subtree up in place of an emptied leaf,
+    // which `retain_tabs` does not do for nested splits
+    while let Some((surface, node, panel)) = unwanted(layout, &shown) {
+        let tabs = layout[surface][node].tabs().unwrap_or_default();
+        if let Some(index) = tabs.iter().rposition(|tab| *tab == panel) {
+            layout.remove_tab((surface, node, TabIndex(index)));
+        }
+    }
+    for panel in DockPanel::ALL {
+        if shown(panel) && layout.find_tab(&panel).is_none() {
+            restore(layout, panel);
+        }
+    }
+}
+
+/// The first tab of a hidden panel, or of a panel already shown earlier in the layout
+fn unwanted(layout: &DockLayout, shown: impl Fn(DockPanel) -> bool) -> Option<(SurfaceIndex, NodeIndex, DockPanel)> {
+    let mut seen = Vec::new();
+    layout.iter_all_tabs().find_map(|((surface, node), panel)| {
+        let drop = (*panel != DockPanel::Session && !shown(*panel)) || seen.contains(panel);
+        seen.push(*panel);
+        drop.then_some((surface, node, *panel))
+    })
+}
+
+fn restore(layout: &mut DockLayout, panel: DockPanel) {
+    if let Some((surface, node, _)) = layout.find_tab(&panel.neighbour()) {
+        layout[surface][node].append_tab(panel);
+        return;
+    }
+    let Some((node, _)) = layout.find_main_surface_tab(&DockPanel::Session) else {
+        return;
+    };
+    // The fraction egui_dock takes is the share of the left or upper node
+    let (split, kept) = panel.home();
+    let fraction = if matches!(split, Split::Left | Split::Above) { 1.0 - kept } else { kept };
+    layout[SurfaceIndex::main()].split(node, split, fraction, Node::leaf(panel));
+}
+
+/// Read a saved layout, falling back to the default one for layouts this version cannot read
+pub fn deserialize_layout<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DockLayout, D::Error> {
+    let value = toml::Value::deserialize(deserializer)?;
+    Ok(value.try_into().unwrap_or_else(|e| {
+        tracing::warn!("Resetting the panel layout, the saved one could not be read: {}", e);
+        default_layout()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn panels(layout: &DockLayout) -> Vec<DockPanel> {
+        layout.iter_all_tabs().map(|(_, panel)| *panel).collect()
+    }
+
+    #[test]
+    fn sync_hides_and_restores_panels() {
+        let mut layout = default_layout();
+        sync(&mut layout, |panel| panel != DockPanel::Plugins && panel != DockPanel::Toolkit);
+        assert!(!panels(&layout).contains(&DockPanel::Plugins));
+        assert!(!panels(&layout).contains(&DockPanel::Toolkit));
+
+        // Back beside its neighbour
+        sync(&mut layout, |_| true);
+        let (_, controls, _) = layout.find_tab(&DockPanel::DeviceControls).unwrap();
+        let (_, toolkit, _) = layout.find_tab(&DockPanel::Toolkit).unwrap();
+        assert_eq!(toolkit, controls);
+        assert_eq!(panels(&layout).len(), DockPanel::ALL.len());
+
+        // A panel whose neighbour is hidden as well opens beside the control panel
+        sync(&mut layout, |panel| panel != DockPanel::Devices && panel != DockPanel::Wireless);
+        sync(&mut layout, |panel| panel != DockPanel::Wireless);
+        assert!(layout.find_main_surface_tab(&DockPanel::Devices).is_some());
+    }
+
+    #[test]
+    fn sync_drops_repeated_panels() {
+        let mut layout = default_layout();
+        layout.add_window(vec![DockPanel::Toolkit]);
+        sync(&mut layout, |_| true);
+        assert_eq!(panels(&layout).len(), DockPanel::ALL.len());
+    }
+
+    #[test]
+    fn layouts_survive_the_config_file() {
+        #[derive(Serialize, Deserialize)]
+        struct Config {
+            #[serde(deserialize_with = "deserialize_layout")]
+            dock: DockLayout,
+        }
+
+        let mut layout = default_layout();
+        sync(&mut layout, |panel| panel != DockPanel::QuickActions);
+        layout.add_window(vec![DockPanel::QuickActions]);
+        let text = toml::to_string_pretty(&Config { dock: layout }).unwrap();
+        let read: Config = toml::from_str(&text).unwrap();
+        assert!(read.dock.find_tab(&DockPanel::QuickActions).is_some_and(|(surface, _, _)| !surface.is_main()));
+
+        // The slots of earlier versions
+        let old = "[dock]\nslots = [{ panel = \"toolkit\", area = \"left\" }]\n";
+        let read: Config = toml::from_str(old).unwrap();
+        assert_eq!(panels(&read.dock).len(), DockPanel::ALL.len());
+    }
+}
@@ -0,0 +1,114 @@
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Formats recordings can be converted to for bug reports and PR descriptions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Gif,
+    WebM,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 2] = [ExportFormat::Gif, ExportFormat::WebM];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ExportFormat::Gif => "GIF",
+            ExportFormat::WebM => "WebM",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Gif => "gif",
+            ExportFormat::WebM => "webm",
+        }
+    }
+}
+
+/// Output width and frame rate; GIFs grow quickly, so the small preset is the default
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportPreset {
+    Small,
+    Medium,
+    Large,
+}
+
+impl ExportPreset {
+    pub const ALL: [ExportPreset; 3] = [ExportPreset::Small, ExportPreset::Medium, ExportPreset::Large];
+
+    pub fn width(self) -> u32 {
+        match self {
+            ExportPreset::Small => 360,
+            ExportPreset::Medium => 540,
+            ExportPreset::Large => 720,
+        }
+    }
+
+    pub fn fps(self) -> u32 {
+        match self {
+            ExportPreset::Small => 10,
+            ExportPreset::Medium => 15,
+            ExportPreset::Large => 24,
+        }
+    }
+
+    pub fn label(self) -> String {
+        let name = match self {
+            ExportPreset::Small => "Small",
+            ExportPreset::Medium => "Medium",
+            ExportPreset::Large => "Large",
+        };
+        format!("{} ({} px, {} fps)", name, self.width(), self.fps())
+    }
+}
+
+/// ffmpeg arguments converting `input` to `output`
+pub fn ffmpeg_args(input: &Path, output: &Path, format: ExportFormat, preset: ExportPreset) -> Vec<String> {
+    // Never upscale, and keep the height even for the VP9 encoder
+    let scale = format!("fps={},scale='min({},iw)':-2:flags=lanczos", preset.fps(), preset.width());
+    let mut args = vec![
+        "-hide_banner".to_string(),
+        "-y".to_string(),
+        "-i".to_string(),
+        input.to_string_lossy().to_string(),
+    ];
+    match format {
+        // A palette generated from the clip itself looks far better than the default 256 colours
+        ExportFormat::Gif => args.extend([
+            "-vf".to_string(),
+            format!("{},split[a][b];[a]palettegen[p];[b][p]paletteuse", scale),
+            "-loop".to_string(),
+            "0".to_string(),
+        ]),
+        ExportFormat::WebM => args.extend([
+            "-vf".to_string(),
+            scale,
+            "-c:v".to_string(),
+            "libvpx-vp9".to_string(),
+            "-b:v".to_string(),
+            "0".to_string(),
+            "-crf".to_string(),
+            "36".to_string(),
+            "-an".to_string(),
+        ]),
+    }
+    args.push(output.to_string_lossy().to_string());
+    args
+}
+
+/// Convert a recording, writing the result next to it; returns the new file
+pub fn export_recording(ffmpeg_path: &str, input: &Path, format: ExportFormat, preset: ExportPreset) -> Result<PathBuf> {
+    let output_path = crate::utils::unique_path(input.with_extension(format.extension()));
+    let output = Command::new(ffmpeg_path)
+        .args(ffmpeg_args(input, &output_path, format, preset))
+        .output()?;
+    if !output.status.success() {
+        let _ = std::fs::remove_file(&output_path);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().rev().find(|line| !line.trim().is_empty()).unwrap_or("unknown error");
+        return Err(anyhow::anyhow!("ffmpeg failed: {}", reason.trim()));
+    }
+    Ok(output_path)
+}
@@ -0,0 +1,199 @@
+//! fastboot, for devices sitting in the bootloader where adb cannot see them
+//!
+//! fastboot ships in platform-tools next to adb, so it is looked for there first. Most of its
+//! output, including `getvar` answers, goes to stderr.
+
+use anyhow::Result;
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// What the bootloader reports about its lock state
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BootloaderInfo {
+    pub product: Option<String>,
+    pub unlocked: Option<bool>,
+    /// `flashing get_unlock_ability`: false while "OEM unlocking" is off in developer options
+    pub unlock_ability: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FastbootDevice {
+    pub serial: String,
+    pub info: BootloaderInfo,
+}
+
+#[derive(Debug, Clone)]
+pub struct FastbootBridge {
+    path: String,
+}
+
+impl FastbootBridge {
+    pub fn new(path: String) -> Self {
+        Self { path }
+    }
+
+    /// fastboot next to `adb_path`, or wherever else it is installed
+    pub fn locate(adb_path: Option<&str>) -> Option<Self> {
+        let sibling = adb_path
+            .map(|adb| Path::new(adb).with_file_name(format!("fastboot{}", std::env::consts::EXE_SUFFIX)))
+            .filter(|path| path.is_file());
+        sibling
+            .or_else(|| crate::utils::find_executable("fastboot"))
+            .map(|path| Self::new(path.to_string_lossy().to_string()))
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    fn run(&self, serial: Option<&str>, args: &[&str]) -> Result<Output> {
+        let mut cmd = Command::new(&self.path);
+        if let Some(serial) = serial {
+            cmd.args(["-s", serial]);
+        }
+        Ok(cmd.args(args).output()?)
+    }
+
+    /// Serials of the devices in fastboot mode
+    pub fn devices(&self) -> Result<Vec<String>> {
+        let output = self.run(None, &["devices"])?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!("fastboot devices failed"));
+        }
+        Ok(parse_devices(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    pub fn getvar(&self, serial: &str, name: &str) -> Option<String> {
+        let output = self.run(Some(serial), &["getvar", name]).ok()?;
+        parse_getvar(&combined(&output), name)
+    }
+
+    pub fn bootloader_info(&self, serial: &str) -> BootloaderInfo {
+        let unlock_ability = self
+            .run(Some(serial), &["flashing", "get_unlock_ability"])
+            .ok()
+            .and_then(|output| parse_getvar(&combined(&output), "get_unlock_ability"))
+            .map(|ability| ability == "1");
+        BootloaderInfo {
+            product: self.getvar(serial, "product"),
+            unlocked: self.getvar(serial, "unlocked").map(|unlocked| unlocked == "yes"),
+            unlock_ability,
+        }
+    }
+
+    /// Every device in fastboot mode with its lock state
+    pub fn scan(&self) -> Result<Vec<FastbootDevice>> {
+        Ok(self
+            .devices()?
+            .into_iter()
+            .map(|serial| FastbootDevice {
+                info: self.bootloader_info(&serial),
+                serial,
+            })
+            .collect())
+    }
+
+    /// `fastboot flashing unlock` or `lock`
+    ///
+    /// Success only means the bootloader took the command: many return before the user answered
+    /// the prompt on the device, and declining it is not reported. Read `unlocked` back to learn
+    /// the outcome.
+    pub fn flashing(&self, serial: &str, lock: bool) -> Result<()> {
+        let output = self.run(Some(serial), &["flashing", if lock { "lock" } else { "unlock" }])?;
+        let text = combined(&output);
+        if !output.status.success() || text.contains("FAILED") {
+            let reason = text
+                .lines()
+                .find(|line| line.contains("FAILED"))
+                .unwrap_or("fastboot flashing failed")
+                .trim();
+            return Err(anyhow::anyhow!(reason.to_string()));
+        }
+        Ok(())
+    }
+
+    pub fn reboot(&self, serial: &str) -> Result<()> {
+        let output = self.run(Some(serial), &["reboot"])?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!("fastboot reboot failed"));
+        }
+        Ok(())
+    }
+}
+
+fn combined(output: &Output) -> String {
+    format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    )
+}
+
+/// Parse `fastboot devices`: `<serial>\tfastboot` per line
+pub fn parse_devices(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let serial = fields.next()?;
+            (fields.next() == Some("fastboot")).then(|| serial.to_string())
+        })
+        .collect()
+}
+
+/// Value of `name` in `getvar` output, e.g. `unlocked: yes`
+pub fn parse_getvar(output: &str, name: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let line = line.trim().trim_start_matches("(bootloader)").trim();
+        let (key, value) = line.split_once(':')?;
+        (key.trim() == name).then(|| value.trim().to_string())
+    })
+}
+
+/// What differs about unlocking on devices from `manufacturer`
+pub fn oem_notes(manufacturer: &str) -> &'static [&'static str] {
+    match manufacturer.trim().to_lowercase().as_str() {
+        "google" => &[
+            "Turn on \"OEM unlocking\" in developer options first; it is greyed out on carrier-locked Pixels.",
+        ],
+        "xiaomi" | "redmi" | "poco" => &[
+            "Xiaomi refuses `fastboot flashing unlock`: unlocking needs the Mi Unlock tool, a bound Mi account and a waiting period.",
+        ],
+        "samsung" => &[
+            "Samsung devices have no fastboot. Unlocking happens in Download mode after enabling \"OEM unlocking\", which this wizard cannot do.",
+        ],
+        "oneplus" => &[
+            "Turn on \"OEM unlocking\" in developer options first.",
+            "Older OnePlus models use `fastboot oem unlock` instead.",
+        ],
+        "motorola" | "sony" => &[
+            "An unlock code from the manufacturer's website is needed, entered with `fastboot oem unlock <code>`.",
+        ],
+        "huawei" | "honor" => &["Huawei and Honor no longer issue bootloader unlock codes."],
+        _ => &["Turn on \"OEM unlocking\" in developer options first, if the device has it."],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_fastboot_output() {
+        assert_eq!(
+            parse_devices("8A2X0KXYZ\tfastboot\nemulator-5554\tdevice\n\n"),
+            vec!["8A2X0KXYZ".to_string()]
+        );
+        let getvar = "unlocked: no\nFinished. Total time: 0.001s\n";
+        assert_eq!(parse_getvar(getvar, "unlocked").as_deref(), Some("no"));
+        assert_eq!(parse_getvar("(bootloader) get_unlock_ability: 1\nOKAY", "get_unlock_ability").as_deref(), Some("1"));
+        assert_eq!(parse_getvar(getvar, "product"), None);
+    }
+
+    #[test]
+    fn notes_by_manufacturer() {
+        assert!(oem_notes("Xiaomi")[0].contains("Mi Unlock"));
+        assert!(oem_notes(" samsung ")[0].contains("Download mode"));
+        assert_eq!(oem_notes("Fairphone").len(), 1);
+    }
+}
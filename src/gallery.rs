@@ -0,0 +1,117 @@
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Index of captures, kept inside the capture directory
+pub const INDEX_FILE: &str = ".droidview-captures.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CaptureKind {
+    Screenshot,
+    Recording,
+}
+
+/// Settings and outcome of a screen recording
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordingDetails {
+    pub duration_secs: u64,
+    /// `WIDTHxHEIGHT` of the device display, when it could be read
+    pub resolution: Option<String>,
+    /// Bits per second passed to screenrecord
+    pub bitrate: u32,
+}
+
+/// A screenshot or recording DroidView saved
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CaptureEntry {
+    pub path: PathBuf,
+    pub kind: CaptureKind,
+    pub device: String,
+    pub captured_at: DateTime<Local>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<RecordingDetails>,
+}
+
+impl CaptureEntry {
+    pub fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CaptureIndex {
+    pub entries: Vec<CaptureEntry>,
+}
+
+impl CaptureIndex {
+    /// Load the index of `dir`, dropping entries whose files were removed outside DroidView
+    pub fn load(dir: &Path) -> Self {
+        let mut index: Self = fs::read_to_string(dir.join(INDEX_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        index.entries.retain(|entry| entry.path.is_file());
+        index
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<()> {
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join(INDEX_FILE), serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Rename a capture on disk, keeping its extension
+    pub fn rename(&mut self, index: usize, new_stem: &str) -> Result<()> {
+        let entry = self
+            .entries
+            .get_mut(index)
+            .ok_or_else(|| anyhow::anyhow!("No such capture"))?;
+        let stem = crate::utils::sanitize_filename(new_stem.trim());
+        if stem.is_empty() {
+            return Err(anyhow::anyhow!("Name cannot be empty"));
+        }
+        let extension = entry
+            .path
+            .extension()
+            .map(|e| format!(".{}", e.to_string_lossy()))
+            .unwrap_or_default();
+        let target = entry.path.with_file_name(format!("{}{}", stem, extension));
+        if target.exists() {
+            return Err(anyhow::anyhow!("{} already exists", target.display()));
+        }
+        fs::rename(&entry.path, &target)?;
+        entry.path = target;
+        Ok(())
+    }
+
+    pub fn delete(&mut self, index: usize) -> Result<()> {
+        if let Some(entry) = self.entries.get(index) {
+            fs::remove_file(&entry.path)?;
+            self.entries.remove(index);
+        }
+        Ok(())
+    }
+}
+
+/// Add a freshly saved capture to the index next to it
+pub fn record_capture(path: &Path, kind: CaptureKind, device: &str, details: Option<RecordingDetails>) {
+    let Some(dir) = path.parent() else {
+        return;
+    };
+    let mut index = CaptureIndex::load(dir);
+    index.entries.push(CaptureEntry {
+        path: path.to_path_buf(),
+        kind,
+        device: device.to_string(),
+        captured_at: Local::now(),
+        details,
+    });
+    if let Err(e) = index.save(dir) {
+        tracing::warn!("Could not update the capture index: {}", e);
+    }
+}
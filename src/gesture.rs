@@ -0,0 +1,244 @@
+//! Custom gestures: multi-point paths and two-finger pinches sent to the device
+//!
+//! Gestures are stored in fractions of the screen so one drawn for a phone also fits a tablet.
+//! A straight swipe goes through `input swipe` and a path through `input motionevent`, one
+//! process per point. `input` has no second pointer, so pinches write multitouch events straight
+//! to the touchscreen with `sendevent`, which needs the shell user to be allowed to open it.
+
+use crate::bridge::adb_command;
+use crate::config::{Gesture, GestureKind};
+use anyhow::Result;
+
+/// Steps a pinch is split into; each one is a frame of both fingers moving
+const PINCH_STEPS: u32 = 12;
+
+// Linux input event types and codes used by `sendevent`
+const EV_SYN: u32 = 0;
+const EV_KEY: u32 = 1;
+const EV_ABS: u32 = 3;
+const BTN_TOUCH: u32 = 0x14a;
+const ABS_MT_SLOT: u32 = 0x2f;
+const ABS_MT_POSITION_X: u32 = 0x35;
+const ABS_MT_POSITION_Y: u32 = 0x36;
+const ABS_MT_TRACKING_ID: u32 = 0x39;
+/// -1 as the unsigned value `sendevent` expects; lifts a finger
+const NO_TRACKING_ID: u32 = u32::MAX;
+
+/// A multitouch touchscreen and its coordinate range
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Touchscreen {
+    pub path: String,
+    pub max_x: u32,
+    pub max_y: u32,
+}
+
+/// Parse `wm size`, preferring the override size when one is set
+pub fn parse_wm_size(output: &str) -> Option<(u32, u32)> {
+    let size = |prefix: &str| {
+        output.lines().find_map(|line| {
+            let (width, height) = line.trim().strip_prefix(prefix)?.trim().split_once('x')?;
+            Some((width.trim().parse().ok()?, height.trim().parse().ok()?))
+        })
+    };
+    size("Override size:").or_else(|| size("Physical size:"))
+}
+
+pub fn screen_size(adb_path: &str, serial: &str) -> Result<(u32, u32)> {
+    let output = adb_command(adb_path).args(["-s", serial, "shell", "wm", "size"]).output()?;
+    parse_wm_size(&String::from_utf8_lossy(&output.stdout))
+        .ok_or_else(|| anyhow::anyhow!("Could not read the screen size"))
+}
+
+/// The first device in `getevent -lp` output that reports multitouch slots and positions
+pub fn parse_touchscreen(output: &str) -> Option<Touchscreen> {
+    let max_of = |line: &str| line.split("max ").nth(1)?.split(',').next()?.trim().parse::<u32>().ok();
+    let mut path = None;
+    let (mut slots, mut max_x, mut max_y) = (false, None, None);
+    for line in output.lines() {
+        if let Some(device) = line.strip_prefix("add device ") {
+            if let (Some(path), true, Some(max_x), Some(max_y)) = (path.take(), slots, max_x, max_y) {
+                return Some(Touchscreen { path, max_x, max_y });
+            }
+            path = device.split_once(": ").map(|(_, path)| path.trim().to_string());
+            (slots, max_x, max_y) = (false, None, None);
+        } else if line.contains("ABS_MT_SLOT") {
+            slots = true;
+        } else if line.contains("ABS_MT_POSITION_X") {
+            max_x = max_of(line);
+        } else if line.contains("ABS_MT_POSITION_Y") {
+            max_y = max_of(line);
+        }
+    }
+    match (path, slots, max_x, max_y) {
+        (Some(path), true, Some(max_x), Some(max_y)) => Some(Touchscreen { path, max_x, max_y }),
+        _ => None,
+    }
+}
+
+/// Screen pixels of a point given in fractions of the screen
+pub fn to_pixels(point: [f32; 2], width: u32, height: u32) -> (u32, u32) {
+    let scale = |fraction: f32, size: u32| (fraction.clamp(0.0, 1.0) * size.saturating_sub(1) as f32).round() as u32;
+    (scale(point[0], width), scale(point[1], height))
+}
+
+/// Shell script that draws a path of at least two points over `duration_ms`
+pub fn path_script(points: &[[f32; 2]], duration_ms: u32, width: u32, height: u32) -> Option<String> {
+    let pixels: Vec<(u32, u32)> = points.iter().map(|point| to_pixels(*point, width, height)).collect();
+    match pixels.as_slice() {
+        [] | [_] => None,
+        [(x1, y1), (x2, y2)] => Some(format!("input swipe {} {} {} {} {}", x1, y1, x2, y2, duration_ms.max(1))),
+        [(x, y), middle @ .., (last_x, last_y)] => {
+            let pause = duration_ms as f32 / 1000.0 / (middle.len() + 1) as f32;
+            let mut commands = vec![format!("input motionevent DOWN {} {}", x, y)];
+            for (x, y) in middle {
+                commands.push(format!("sleep {:.3}", pause));
+                commands.push(format!("input motionevent MOVE {} {}", x, y));
+            }
+            commands.push(format!("sleep {:.3}", pause));
+            commands.push(format!("input motionevent UP {} {}", last_x, last_y));
+            Some(commands.join("; "))
+        }
+    }
+}
+
+/// Shell script of `sendevent` calls moving two fingers apart or together around `center`.
+/// `from` and `to` are the distances between the fingers, in fractions of the screen width.
+pub fn pinch_script(touchscreen: &Touchscreen, center: [f32; 2], from: f32, to: f32, duration_ms: u32) -> String {
+    let device = &touchscreen.path;
+    let event = |kind: u32, code: u32, value: u32| format!("sendevent {} {} {} {}", device, kind, code, value);
+    let position = |x: f32, y: f32| {
+        let x = (x.clamp(0.0, 1.0) * touchscreen.max_x as f32).round() as u32;
+        let y = (y.clamp(0.0, 1.0) * touchscreen.max_y as f32).round() as u32;
+        [event(EV_ABS, ABS_MT_POSITION_X, x), event(EV_ABS, ABS_MT_POSITION_Y, y)]
+    };
+    let fingers = |spread: f32| [(center[0] - spread / 2.0, center[1]), (center[0] + spread / 2.0, center[1])];
+    let sync = event(EV_SYN, 0, 0);
+
+    let mut commands = Vec::new();
+    for (slot, (x, y)) in fingers(from).into_iter().enumerate() {
+        commands.push(event(EV_ABS, ABS_MT_SLOT, slot as u32));
+        commands.push(event(EV_ABS, ABS_MT_TRACKING_ID, 100 + slot as u32));
+        commands.extend(position(x, y));
+    }
+    commands.push(event(EV_KEY, BTN_TOUCH, 1));
+    commands.push(sync.clone());
+
+    let pause = duration_ms as f32 / 1000.0 / PINCH_STEPS as f32;
+    for step in 1..=PINCH_STEPS {
+        let spread = from + (to - from) * step as f32 / PINCH_STEPS as f32;
+        commands.push(format!("sleep {:.3}", pause));
+        for (slot, (x, y)) in fingers(spread).into_iter().enumerate() {
+            commands.push(event(EV_ABS, ABS_MT_SLOT, slot as u32));
+            commands.extend(position(x, y));
+        }
+        commands.push(sync.clone());
+    }
+
+    for slot in 0..2 {
+        commands.push(event(EV_ABS, ABS_MT_SLOT, slot));
+        commands.push(event(EV_ABS, ABS_MT_TRACKING_ID, NO_TRACKING_ID));
+    }
+    commands.push(event(EV_KEY, BTN_TOUCH, 0));
+    commands.push(sync);
+    commands.join("; ")
+}
+
+/// Perform `gesture` on the device
+pub fn perform(adb_path: &str, serial: &str, gesture: &Gesture) -> Result<()> {
+    let script = match &gesture.kind {
+        GestureKind::Path { points } => {
+            let (width, height) = screen_size(adb_path, serial)?;
+            path_script(points, gesture.duration_ms, width, height)
+                .ok_or_else(|| anyhow::anyhow!("A path needs at least two points"))?
+        }
+        GestureKind::Pinch { center, from, to } => {
+            let output = adb_command(adb_path).args(["-s", serial, "shell", "getevent", "-lp"]).output()?;
+            let touchscreen = parse_touchscreen(&String::from_utf8_lossy(&output.stdout))
+                .ok_or_else(|| anyhow::anyhow!("No multitouch screen found, or the shell may not read input devices"))?;
+            pinch_script(&touchscreen, *center, *from, *to, gesture.duration_ms)
+        }
+    };
+    let output = adb_command(adb_path).args(["-s", serial, "shell", &script]).output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() || stderr.contains("Permission denied") || stderr.contains("Exception") {
+        let reason = stderr.lines().map(str::trim).find(|line| !line.is_empty()).unwrap_or("gesture failed");
+        return Err(anyhow::anyhow!(reason.to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_wm_size() {
+        assert_eq!(parse_wm_size("Physical size: 1080x2400\n"), Some((1080, 2400)));
+        assert_eq!(parse_wm_size("Physical size: 1080x2400\nOverride size: 720x1600\n"), Some((720, 1600)));
+        assert_eq!(parse_wm_size("error"), None);
+    }
+
+    #[test]
+    fn converts_fractions_to_pixels() {
+        assert_eq!(to_pixels([0.0, 0.0], 1080, 2400), (0, 0));
+        assert_eq!(to_pixels([0.5, 0.25], 1081, 2401), (540, 600));
+        // Points off the screen land on its edge
+        assert_eq!(to_pixels([1.5, -0.2], 1080, 2400), (1079, 0));
+    }
+
+    #[test]
+    fn finds_touchscreen() {
+        let output = "\
+add device 1: /dev/input/event0
+  name:     \"gpio-keys\"
+  events:
+    KEY (0001): KEY_VOLUMEDOWN
+add device 2: /dev/input/event3
+  name:     \"fts_ts\"
+  events:
+    ABS (0003): ABS_MT_SLOT           : value 0, min 0, max 9, fuzz 0, flat 0, resolution 0
+                ABS_MT_POSITION_X     : value 0, min 0, max 1079, fuzz 0, flat 0, resolution 0
+                ABS_MT_POSITION_Y     : value 0, min 0, max 2399, fuzz 0, flat 0, resolution 0
+";
+        assert_eq!(
+            parse_touchscreen(output),
+            Some(Touchscreen { path: "/dev/input/event3".to_string(), max_x: 1079, max_y: 2399 })
+        );
+        assert_eq!(parse_touchscreen("add device 1: /dev/input/event0\n"), None);
+    }
+
+    #[test]
+    fn builds_path_scripts() {
+        assert_eq!(path_script(&[[0.5, 0.5]], 300, 1000, 2000), None);
+        assert_eq!(
+            path_script(&[[0.0, 0.0], [1.0, 1.0]], 300, 1001, 2001).as_deref(),
+            Some("input swipe 0 0 1000 2000 300")
+        );
+        let script = path_script(&[[0.0, 0.0], [0.5, 0.5], [1.0, 0.0]], 400, 1001, 2001).unwrap();
+        assert_eq!(
+            script,
+            "input motionevent DOWN 0 0; sleep 0.200; input motionevent MOVE 500 1000; sleep 0.200; \
+             input motionevent UP 1000 0"
+        );
+    }
+
+    #[test]
+    fn builds_pinch_scripts() {
+        let touchscreen = Touchscreen { path: "/dev/input/event3".to_string(), max_x: 1000, max_y: 2000 };
+        let script = pinch_script(&touchscreen, [0.5, 0.5], 0.2, 0.6, 240);
+        let commands: Vec<&str> = script.split("; ").collect();
+        // Both fingers go down 20% of the width apart and end up 60% apart
+        assert!(commands.starts_with(&[
+            "sendevent /dev/input/event3 3 47 0",
+            "sendevent /dev/input/event3 3 57 100",
+            "sendevent /dev/input/event3 3 53 400",
+            "sendevent /dev/input/event3 3 54 1000",
+        ]));
+        assert!(script.contains(
+            "sendevent /dev/input/event3 3 53 200; sendevent /dev/input/event3 3 54 1000; \
+             sendevent /dev/input/event3 3 47 1; sendevent /dev/input/event3 3 53 800"
+        ));
+        assert!(commands.ends_with(&["sendevent /dev/input/event3 1 330 0", "sendevent /dev/input/event3 0 0 0"]));
+        assert_eq!(commands.iter().filter(|command| command.starts_with("sleep")).count(), PINCH_STEPS as usize);
+    }
+}
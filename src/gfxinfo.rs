@@ -0,0 +1,158 @@
+//! Frame timing of an app from `dumpsys gfxinfo <package> framestats`
+//!
+//! Each call reports the frames rendered in roughly the last two seconds as CSV rows between
+//! `---PROFILEDATA---` markers, with nanosecond timestamps per stage. A frame's time is from
+//! its intended vsync to completion; rows with non-zero flags are startup or resize frames that
+//! would skew the numbers and are left out.
+
+use crate::bridge::adb_command;
+use anyhow::Result;
+use std::time::Duration;
+
+pub const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+const MARKER: &str = "---PROFILEDATA---";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frame {
+    /// Identifies the frame across overlapping samples
+    pub intended_vsync: u64,
+    pub duration_ms: f32,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FrameSummary {
+    pub frames: usize,
+    pub p50: f32,
+    pub p90: f32,
+    pub p95: f32,
+    pub p99: f32,
+    /// Frames that took longer than the budget
+    pub janky: usize,
+    /// Vsyncs that passed without a new frame because of slow ones
+    pub dropped: usize,
+}
+
+/// Frames in `framestats` output, oldest first
+pub fn parse_framestats(output: &str) -> Vec<Frame> {
+    let mut frames = Vec::new();
+    let mut columns: Option<(usize, usize, usize)> = None;
+    let mut inside = false;
+    for line in output.lines().map(str::trim) {
+        if line == MARKER {
+            inside = !inside;
+            columns = None;
+            continue;
+        }
+        if !inside || line.is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').collect();
+        let Some((flags, intended, completed)) = columns else {
+            let index = |name: &str| fields.iter().position(|field| *field == name);
+            columns = index("Flags").zip(index("IntendedVsync")).zip(index("FrameCompleted")).map(
+                |((flags, intended), completed)| (flags, intended, completed),
+            );
+            continue;
+        };
+        let value = |index: usize| fields.get(index).and_then(|field| field.parse::<u64>().ok());
+        if let (Some(0), Some(start), Some(end)) = (value(flags), value(intended), value(completed))
+            && end > start
+        {
+            frames.push(Frame {
+                intended_vsync: start,
+                duration_ms: (end - start) as f32 / 1_000_000.0,
+            });
+        }
+    }
+    frames.sort_by_key(|frame| frame.intended_vsync);
+    frames
+}
+
+/// Value at `percent` of `sorted`, by nearest rank
+pub fn percentile(sorted: &[f32], percent: f32) -> f32 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((percent / 100.0) * sorted.len() as f32).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Percentiles and jank of `durations` against a frame budget such as 16.7 ms at 60 Hz
+pub fn summarize(durations: &[f32], budget_ms: f32) -> FrameSummary {
+    let mut sorted = durations.to_vec();
+    sorted.sort_by(f32::total_cmp);
+    let janky: Vec<f32> = sorted.iter().copied().filter(|duration| *duration > budget_ms).collect();
+    FrameSummary {
+        frames: sorted.len(),
+        p50: percentile(&sorted, 50.0),
+        p90: percentile(&sorted, 90.0),
+        p95: percentile(&sorted, 95.0),
+        p99: percentile(&sorted, 99.0),
+        janky: janky.len(),
+        dropped: janky.iter().map(|duration| (duration / budget_ms).ceil() as usize - 1).sum(),
+    }
+}
+
+pub fn sample(adb_path: &str, serial: &str, package: &str) -> Result<Vec<Frame>> {
+    let output = adb_command(adb_path)
+        .args(["-s", serial, "shell", "dumpsys", "gfxinfo", package, "framestats"])
+        .output()?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!("dumpsys gfxinfo failed"));
+    }
+    let text = String::from_utf8_lossy(&output.stdout);
+    if text.contains("No process found") {
+        return Err(anyhow::anyhow!("{} is not running", package));
+    }
+    Ok(parse_framestats(&text))
+}
+
+/// Package of the activity in the foreground
+pub fn foreground_package(adb_path: &str, serial: &str) -> Result<String> {
+    let stack = crate::activity_stack::get_activity_stack(adb_path, serial)?;
+    stack
+        .resumed
+        .and_then(|component| component.split('/').next().map(str::to_string))
+        .ok_or_else(|| anyhow::anyhow!("No app in the foreground"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAMESTATS: &str = "\
+Applications Graphics Acceleration Info:
+Window: com.example/com.example.MainActivity
+---PROFILEDATA---
+Flags,IntendedVsync,Vsync,FrameCompleted,
+0,2000000000,2000000000,2008000000,
+1,1000000000,1000000000,1900000000,
+0,2016000000,2016000000,2056000000,
+---PROFILEDATA---
+";
+
+    #[test]
+    fn parses_framestats() {
+        let frames = parse_framestats(FRAMESTATS);
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].intended_vsync, 2_000_000_000);
+        assert!((frames[0].duration_ms - 8.0).abs() < 0.001);
+        assert!((frames[1].duration_ms - 40.0).abs() < 0.001);
+        assert!(parse_framestats("No process found for: com.example").is_empty());
+    }
+
+    #[test]
+    fn summarizes_durations() {
+        let durations: Vec<f32> = (1..=100).map(|ms| ms as f32).collect();
+        let summary = summarize(&durations, 16.0);
+        assert_eq!(summary.frames, 100);
+        assert_eq!((summary.p50, summary.p90, summary.p99), (50.0, 90.0, 99.0));
+        assert_eq!(summary.janky, 84);
+
+        let summary = summarize(&[10.0, 20.0, 50.0], 16.0);
+        // 20 ms misses one vsync, 50 ms misses three
+        assert_eq!((summary.janky, summary.dropped), (2, 4));
+        assert_eq!(summarize(&[], 16.0), FrameSummary::default());
+    }
+}
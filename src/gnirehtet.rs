@@ -0,0 +1,160 @@
+//! Reverse tethering with gnirehtet: the device reaches the internet through this computer
+//!
+//! The gnirehtet relay runs here and listens on port 31416. On the device, the gnirehtet app
+//! opens a VPN whose traffic goes through `adb reverse localabstract:gnirehtet tcp:31416` to the
+//! relay. One relay serves every device. The app's APK ships next to the gnirehtet binary.
+
+use crate::bridge::adb_command;
+use anyhow::Result;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::time::Duration;
+
+pub const PACKAGE: &str = "com.genymobile.gnirehtet";
+const ACTIVITY: &str = "com.genymobile.gnirehtet/.GnirehtetActivity";
+const RELAY_PORT: u16 = 31416;
+const REVERSE_SOCKET: &str = "localabstract:gnirehtet";
+pub const DOWNLOAD_URL: &str = "https://github.com/Genymobile/gnirehtet/releases/latest";
+
+/// The configured binary, else one bundled, downloaded or on the PATH
+pub fn locate(configured: Option<&str>) -> Option<PathBuf> {
+    configured
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .filter(|path| path.is_file())
+        .or_else(|| crate::utils::find_executable("gnirehtet"))
+}
+
+/// `gnirehtet.apk`, which releases put next to the binary
+pub fn apk_path(binary: &Path) -> PathBuf {
+    binary.with_file_name("gnirehtet.apk")
+}
+
+/// Arguments for `adb shell` that start or stop the VPN on the device
+pub fn client_command(start: bool) -> String {
+    let action = if start { "START" } else { "STOP" };
+    format!("am start -a {}.{} -n {}", PACKAGE, action, ACTIVITY)
+}
+
+/// The relay process; every tethered device talks to it
+#[derive(Debug)]
+pub struct Relay {
+    child: Child,
+}
+
+impl Relay {
+    pub fn start(binary: &Path) -> Result<Self> {
+        let mut child = Command::new(binary)
+            .arg("relay")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        // A relay that cannot bind its port exits right away
+        std::thread::sleep(Duration::from_millis(300));
+        if child.try_wait()?.is_some() {
+            let mut errors = String::new();
+            if let Some(mut stderr) = child.stderr.take() {
+                let _ = stderr.read_to_string(&mut errors);
+            }
+            let reason = errors
+                .lines()
+                .map(str::trim)
+                .rfind(|line| !line.is_empty())
+                .unwrap_or("gnirehtet relay exited");
+            return Err(anyhow::anyhow!(reason.to_string()));
+        }
+        // Keep reading, a relay that fills the pipe blocks on its next write
+        if let Some(stderr) = child.stderr.take() {
+            std::thread::spawn(move || {
+                for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                    tracing::info!("gnirehtet relay: {}", line);
+                }
+            });
+        }
+        Ok(Self { child })
+    }
+
+    /// Why the relay stopped, once it has
+    pub fn exit_reason(&mut self) -> Option<String> {
+        match self.child.try_wait() {
+            Ok(Some(status)) => Some(format!("gnirehtet relay exited ({})", status)),
+            Ok(None) => None,
+            Err(e) => Some(e.to_string()),
+        }
+    }
+
+    pub fn stop(mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Install the gnirehtet app when missing, set up the reverse forward and start the VPN.
+/// The user has to accept the VPN prompt on the device the first time.
+pub fn enable(adb_path: &str, serial: &str, binary: &Path) -> Result<()> {
+    let installed = adb_command(adb_path)
+        .args(["-s", serial, "shell", "pm", "path", PACKAGE])
+        .output()?;
+    if !String::from_utf8_lossy(&installed.stdout).contains("package:") {
+        let apk = apk_path(binary);
+        if !apk.is_file() {
+            return Err(anyhow::anyhow!("{} not found; it comes with gnirehtet", apk.display()));
+        }
+        let output = adb_command(adb_path).args(["-s", serial, "install", "-r"]).arg(&apk).output()?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "Could not install gnirehtet: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+    }
+
+    let status = adb_command(adb_path)
+        .args(["-s", serial, "reverse", REVERSE_SOCKET, &format!("tcp:{}", RELAY_PORT)])
+        .status()?;
+    if !status.success() {
+        return Err(anyhow::anyhow!("adb reverse failed"));
+    }
+    let status = adb_command(adb_path)
+        .args(["-s", serial, "shell", &client_command(true)])
+        .status()?;
+    if !status.success() {
+        return Err(anyhow::anyhow!("Could not start gnirehtet on the device"));
+    }
+    Ok(())
+}
+
+/// Stop the VPN and remove the reverse forward
+pub fn disable(adb_path: &str, serial: &str) -> Result<()> {
+    let stopped = adb_command(adb_path)
+        .args(["-s", serial, "shell", &client_command(false)])
+        .status();
+    let _ = adb_command(adb_path)
+        .args(["-s", serial, "reverse", "--remove", REVERSE_SOCKET])
+        .status();
+    if !stopped?.success() {
+        return Err(anyhow::anyhow!("Could not stop gnirehtet on the device"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn client_commands() {
+        assert_eq!(
+            client_command(true),
+            "am start -a com.genymobile.gnirehtet.START -n com.genymobile.gnirehtet/.GnirehtetActivity"
+        );
+        assert!(client_command(false).contains("gnirehtet.STOP"));
+        assert_eq!(
+            apk_path(Path::new("/opt/gnirehtet/gnirehtet")),
+            Path::new("/opt/gnirehtet/gnirehtet.apk")
+        );
+    }
+}
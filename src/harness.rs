@@ -0,0 +1,291 @@
+//! Headless driver for `DroidViewApp`, for regression tests of dialogs and action flows
+//!
+//! Build with `--features test-harness`. The app talks to adb and scrcpy only through their
+//! executables, so the harness substitutes small scripts (`MockTool`) that log every call and
+//! print canned output. Frames run through `egui_kittest` at a fixed size and time step, so
+//! layouts repeat exactly.
+
+use crate::app::DroidViewApp;
+use crate::config::AppConfig;
+use eframe::egui;
+use egui::accesskit;
+use egui_kittest::kittest::{by, NodeT, Queryable};
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// Screen size every harness frame is laid out at
+const SCREEN_SIZE: egui::Vec2 = egui::vec2(1280.0, 800.0);
+/// Simulated time between frames
+const FRAME_TIME: f32 = 1.0 / 60.0;
+
+fn sh_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+/// Stand-in for the adb or scrcpy executable: a shell script answering from a table of canned outputs
+pub struct MockTool {
+    dir: PathBuf,
+    name: String,
+    /// `(pattern, stdout, exit code)`, matched in order against the space-joined arguments
+    responses: Vec<(String, String, i32)>,
+    /// Patterns whose calls take this long to answer
+    delays: Vec<(String, Duration)>,
+}
+
+impl MockTool {
+    fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("droidview-harness-{}", uuid::Uuid::new_v4()));
+        Self {
+            dir,
+            name: name.to_string(),
+            responses: Vec::new(),
+            delays: Vec::new(),
+        }
+    }
+
+    /// An adb that lists `devices`, given as `(serial, model)`, as connected
+    pub fn adb(devices: &[(&str, &str)]) -> Self {
+        let mut listing = String::from("List of devices attached\n");
+        for (serial, model) in devices {
+            listing.push_str(&format!(
+                "{} device product:{} model:{} device:{} transport_id:1\n",
+                serial,
+                model.to_lowercase(),
+                model,
+                model.to_lowercase()
+            ));
+        }
+        Self::new("adb").respond("devices", &listing)
+    }
+
+    /// A scrcpy that logs its arguments and exits successfully
+    pub fn scrcpy() -> Self {
+        Self::new("scrcpy")
+    }
+
+    /// Print `stdout` for calls whose arguments contain `pattern`; earlier responses win
+    pub fn respond(self, pattern: &str, stdout: &str) -> Self {
+        self.respond_with_status(pattern, stdout, 0)
+    }
+
+    pub fn respond_with_status(mut self, pattern: &str, stdout: &str, status: i32) -> Self {
+        self.responses.push((pattern.to_string(), stdout.to_string(), status));
+        self
+    }
+
+    /// Answer calls whose arguments contain `pattern` only after `delay`, like a slow device
+    pub fn delay(mut self, pattern: &str, delay: Duration) -> Self {
+        self.delays.push((pattern.to_string(), delay));
+        self
+    }
+
+    fn log_path(&self) -> PathBuf {
+        self.dir.join("calls.log")
+    }
+
+    /// Write the script and return its path
+    fn install(&self) -> std::io::Result<PathBuf> {
+        fs::create_dir_all(&self.dir)?;
+        let mut script = format!("#!/bin/sh\necho \"$*\" >> {}\ncase \"$*\" in\n", sh_quote(&self.log_path().to_string_lossy()));
+        for (index, (pattern, stdout, status)) in self.responses.iter().enumerate() {
+            let output = self.dir.join(format!("response-{}.txt", index));
+            fs::write(&output, stdout)?;
+            let delay = self
+                .delays
+                .iter()
+                .find(|(delayed, _)| pattern.contains(delayed.as_str()))
+                .map(|(_, delay)| format!("sleep {:.3}; ", delay.as_secs_f64()))
+                .unwrap_or_default();
+            script.push_str(&format!(
+                "  *{}*) {}cat {}; exit {};;\n",
+                sh_quote(pattern),
+                delay,
+                sh_quote(&output.to_string_lossy()),
+                status
+            ));
+        }
+        script.push_str("esac\nexit 0\n");
+
+        let path = self.dir.join(&self.name);
+        fs::write(&path, script)?;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+        Ok(path)
+    }
+
+    /// Every invocation so far, as its space-joined arguments
+    pub fn calls(&self) -> Vec<String> {
+        fs::read_to_string(self.log_path())
+            .map(|log| log.lines().map(str::to_string).collect())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for MockTool {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// A `DroidViewApp` running without a window
+pub struct Harness {
+    kittest: egui_kittest::Harness<'static, DroidViewApp>,
+    config: Arc<Mutex<AppConfig>>,
+    runtime: tokio::runtime::Runtime,
+    adb: MockTool,
+    scrcpy: Option<MockTool>,
+}
+
+impl Harness {
+    /// Start the app against `adb`; without a mock scrcpy, launches stay in debug mode (disabled)
+    pub fn new(adb: MockTool, scrcpy: Option<MockTool>) -> anyhow::Result<Self> {
+        // Keep config, logs and captures out of the user's own DroidView folder
+        crate::config::set_app_dir_override(
+            std::env::temp_dir().join(format!("droidview-harness-config-{}", std::process::id())),
+        );
+
+        let scrcpy_path = match &scrcpy {
+            Some(scrcpy) => Some(scrcpy.install()?.display().to_string()),
+            None => None,
+        };
+        let config = AppConfig {
+            adb_path: Some(adb.install()?.display().to_string()),
+            scrcpy_path,
+            screenshot_dir: Some(adb.dir.join("captures").display().to_string()),
+            ..AppConfig::default()
+        };
+        let config = Arc::new(Mutex::new(config));
+
+        let runtime = tokio::runtime::Builder::new_multi_thread().enable_all().build()?;
+        let kittest = {
+            let _guard = runtime.enter();
+            let config = config.clone();
+            let debug_mode = scrcpy.is_none();
+            egui_kittest::Harness::builder()
+                .with_size(SCREEN_SIZE)
+                .with_step_dt(FRAME_TIME)
+                .build_eframe(move |cc| {
+                    let mut fonts = egui::FontDefinitions::default();
+                    egui_phosphor::add_to_fonts(&mut fonts, egui_phosphor::Variant::Fill);
+                    cc.egui_ctx.set_fonts(fonts);
+                    DroidViewApp::new(cc, config, debug_mode)
+                })
+        };
+
+        let mut harness = Self {
+            kittest,
+            config,
+            runtime,
+            adb,
+            scrcpy,
+        };
+        harness.step();
+        Ok(harness)
+    }
+
+    /// Run a frame for each queued input event, or a single one when none are queued
+    pub fn step(&mut self) {
+        let _guard = self.runtime.enter();
+        self.kittest.step();
+    }
+
+    pub fn run(&mut self, frames: usize) {
+        for _ in 0..frames {
+            self.step();
+        }
+    }
+
+    /// Step until background tasks have delivered their results, or `timeout` passes
+    pub fn settle(&mut self, timeout: Duration) -> bool {
+        let started = Instant::now();
+        loop {
+            self.step();
+            if self.kittest.state().is_idle() {
+                // One more frame to render what the last result changed
+                self.step();
+                return true;
+            }
+            if started.elapsed() > timeout {
+                return false;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    /// Click the first enabled widget whose label contains `label`
+    pub fn click(&mut self, label: &str) -> anyhow::Result<()> {
+        // The label proper: kittest's label filters also match the text of plain labels
+        let clickable = |node: &egui_kittest::kittest::AccessKitNode<'_>| {
+            node.label().is_some_and(|text| text.contains(label))
+                && node.supports_action(accesskit::Action::Click)
+                && !node.is_disabled()
+        };
+        self.kittest
+            .query_all(by().predicate(clickable))
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("No clickable widget labelled '{}'", label))?
+            .click();
+        self.step();
+        // Let windows opened by the click lay themselves out
+        self.step();
+        Ok(())
+    }
+
+    /// Give the keyboard focus to the text field whose hint contains `hint`
+    pub fn focus(&mut self, hint: &str) -> anyhow::Result<()> {
+        let field = |node: &egui_kittest::kittest::AccessKitNode<'_>| {
+            node.placeholder().is_some_and(|text| text.contains(hint))
+        };
+        self.kittest
+            .query_all(by().predicate(field))
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("No text field with the hint '{}'", hint))?
+            .focus();
+        self.step();
+        Ok(())
+    }
+
+    /// Type into the focused text field
+    pub fn type_text(&mut self, text: &str) {
+        self.kittest.input_mut().events.push(egui::Event::Text(text.to_string()));
+        self.step();
+    }
+
+    pub fn press_key(&mut self, key: egui::Key, modifiers: egui::Modifiers) {
+        self.kittest.key_press_modifiers(modifiers, key);
+        self.step();
+    }
+
+    /// Every label and value rendered in the last frame
+    pub fn texts(&self) -> Vec<String> {
+        self.kittest
+            .query_all(by().include_labels())
+            .flat_map(|node| [node.accesskit_node().label(), node.accesskit_node().value()])
+            .flatten()
+            .collect()
+    }
+
+    /// Whether any rendered label or value contains `needle`
+    pub fn shows(&self, needle: &str) -> bool {
+        self.texts().iter().any(|text| text.contains(needle))
+    }
+
+    pub fn adb_calls(&self) -> Vec<String> {
+        self.adb.calls()
+    }
+
+    pub fn scrcpy_calls(&self) -> Vec<String> {
+        self.scrcpy.as_ref().map(MockTool::calls).unwrap_or_default()
+    }
+
+    pub fn selected_device(&self) -> Option<String> {
+        self.kittest.state().selected_serial()
+    }
+
+    pub fn config(&self) -> AppConfig {
+        self.config.blocking_lock().clone()
+    }
+}
@@ -0,0 +1,182 @@
+//! The view hierarchy on screen, as `uiautomator dump` writes it
+//!
+//! The dump is an XML file of nested `node` elements whose attributes describe each view: its
+//! class, resource id, text, state flags and bounds in screen pixels. It is read back with
+//! `exec-out cat` and removed again. uiautomator waits for the screen to be idle, so a dump of an
+//! endless animation fails.
+
+use crate::automation::shell;
+use crate::bridge::adb_command;
+use crate::utils::xml_attributes;
+use anyhow::Result;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+const DUMP_PATH: &str = "/data/local/tmp/droidview_window_dump.xml";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Node {
+    /// Attributes in the order of the dump
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Node>,
+}
+
+impl Node {
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.attributes.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+    }
+
+    /// Left, top, right and bottom edge in screen pixels
+    pub fn bounds(&self) -> Option<[u32; 4]> {
+        self.get("bounds").and_then(parse_bounds)
+    }
+
+    /// Class without its package, the id without the app's, and the text if any
+    pub fn label(&self) -> String {
+        let class = self.get("class").unwrap_or("hierarchy");
+        let mut label = class.rsplit('.').next().unwrap_or(class).to_string();
+        if let Some(id) = self.get("resource-id").filter(|id| !id.is_empty()) {
+            label.push_str(&format!(" #{}", id.rsplit(":id/").next().unwrap_or(id)));
+        }
+        let text = ["text", "content-desc"].into_iter().filter_map(|name| self.get(name)).find(|text| !text.is_empty());
+        if let Some(text) = text {
+            let short: String = text.chars().take(32).collect();
+            let ellipsis = if short.len() < text.len() { "…" } else { "" };
+            label.push_str(&format!(" \"{}{}\"", short, ellipsis));
+        }
+        label
+    }
+
+    /// The node at `path`, a list of child indices from this one
+    pub fn at(&self, path: &[usize]) -> Option<&Node> {
+        path.iter().try_fold(self, |node, &index| node.children.get(index))
+    }
+
+    /// Path of the smallest node whose bounds contain the point, which is the one drawn on top
+    pub fn deepest_at(&self, x: u32, y: u32) -> Option<Vec<usize>> {
+        let mut best: Option<(u64, Vec<usize>)> = None;
+        let mut stack = vec![(self, Vec::new())];
+        while let Some((node, path)) = stack.pop() {
+            if let Some([left, top, right, bottom]) = node.bounds()
+                && (left..right).contains(&x)
+                && (top..bottom).contains(&y)
+            {
+                let area = u64::from(right - left) * u64::from(bottom - top);
+                if best.as_ref().is_none_or(|(smallest, _)| area <= *smallest) {
+                    best = Some((area, path.clone()));
+                }
+            }
+            for (index, child) in node.children.iter().enumerate() {
+                let mut child_path = path.clone();
+                child_path.push(index);
+                stack.push((child, child_path));
+            }
+        }
+        best.map(|(_, path)| path)
+    }
+}
+
+/// Dump the view hierarchy of what is on screen
+pub fn dump(adb_path: &str, serial: &str) -> Result<Node> {
+    let output = shell(adb_path, serial, &format!("uiautomator dump {}", DUMP_PATH))?;
+    if output.contains("ERROR") {
+        return Err(anyhow::anyhow!(output));
+    }
+    let xml = adb_command(adb_path).args(["-s", serial, "exec-out", "cat", DUMP_PATH]).output()?;
+    let _ = shell(adb_path, serial, &format!("rm {}", DUMP_PATH));
+    if !xml.status.success() {
+        return Err(anyhow::anyhow!("Could not read the dump: {}", String::from_utf8_lossy(&xml.stderr).trim()));
+    }
+    parse(&String::from_utf8_lossy(&xml.stdout))
+}
+
+/// The `hierarchy` element with its nodes
+pub fn parse(xml: &str) -> Result<Node> {
+    // Open elements, the outermost first; a pseudo-root collects the top level. The element
+    // names are not kept, uiautomator only writes `hierarchy` and `node`
+    let mut stack = vec![Node::default()];
+    let mut reader = Reader::from_str(xml);
+    loop {
+        match reader.read_event()? {
+            Event::Start(element) => stack.push(Node { attributes: xml_attributes(&element)?, children: Vec::new() }),
+            Event::Empty(element) => {
+                let node = Node { attributes: xml_attributes(&element)?, children: Vec::new() };
+                if let Some(parent) = stack.last_mut() {
+                    parent.children.push(node);
+                }
+            }
+            Event::End(_) => {
+                if stack.len() < 2 {
+                    return Err(anyhow::anyhow!("Unbalanced closing tag in the dump"));
+                }
+                let node = stack.pop().unwrap_or_default();
+                if let Some(parent) = stack.last_mut() {
+                    parent.children.push(node);
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    if stack.len() != 1 {
+        return Err(anyhow::anyhow!("The dump ended in the middle of a node"));
+    }
+    let mut root = stack.pop().unwrap_or_default();
+    match root.children.len() {
+        1 => Ok(root.children.remove(0)),
+        _ => Err(anyhow::anyhow!("The dump has no view hierarchy")),
+    }
+}
+
+/// `[0,63][1080,210]` as left, top, right and bottom
+pub fn parse_bounds(bounds: &str) -> Option<[u32; 4]> {
+    let (first, second) = bounds.trim().strip_prefix('[')?.strip_suffix(']')?.split_once("][")?;
+    let (left, top) = first.split_once(',')?;
+    let (right, bottom) = second.split_once(',')?;
+    Some([left.parse().ok()?, top.parse().ok()?, right.parse().ok()?, bottom.parse().ok()?])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DUMP: &str = concat!(
+        r#"<?xml version='1.0' encoding='UTF-8' standalone='yes' ?><hierarchy rotation="0">"#,
+        r#"<node index="0" text="" resource-id="" class="android.widget.FrameLayout" "#,
+        r#"package="com.example" bounds="[0,0][1080,2400]">"#,
+        r#"<node index="0" text="Tom &amp; Jerry &lt;3&#10;&#x2764;" resource-id="com.example:id/title" "#,
+        r#"class="android.widget.TextView" package="com.example" clickable="true" bounds="[0,100][1080,300]" />"#,
+        r#"<node index="1" text="" resource-id="" class="android.widget.Button" content-desc="Send" "#,
+        r#"package="com.example" bounds="[900,2200][1060,2380]" />"#,
+        r#"</node></hierarchy>"#,
+    );
+
+    #[test]
+    fn parses_dumps() {
+        let root = parse(DUMP).unwrap();
+        assert_eq!(root.get("rotation"), Some("0"));
+        let frame = &root.children[0];
+        assert_eq!(frame.children.len(), 2);
+        let title = &frame.children[0];
+        assert_eq!(title.get("text"), Some("Tom & Jerry <3\n❤"));
+        assert_eq!(title.get("clickable"), Some("true"));
+        assert_eq!(title.bounds(), Some([0, 100, 1080, 300]));
+        assert_eq!(title.label(), "TextView #title \"Tom & Jerry <3\n❤\"");
+        assert_eq!(frame.children[1].label(), "Button \"Send\"");
+        assert_eq!(root.at(&[0, 1]), Some(&frame.children[1]));
+
+        assert!(parse("<hierarchy><node bounds=\"[0,0][1,1]\">").is_err());
+        assert!(parse("uiautomator: not found").is_err());
+    }
+
+    #[test]
+    fn finds_the_node_under_a_point() {
+        let root = parse(DUMP).unwrap();
+        assert_eq!(root.deepest_at(500, 200), Some(vec![0, 0]));
+        assert_eq!(root.deepest_at(1000, 2300), Some(vec![0, 1]));
+        assert_eq!(root.deepest_at(500, 1000), Some(vec![0]));
+        assert_eq!(root.deepest_at(5000, 1000), None);
+        assert_eq!(parse_bounds("[0,63][1080,210]"), Some([0, 63, 1080, 210]));
+        assert_eq!(parse_bounds("0,0,1,1"), None);
+    }
+}
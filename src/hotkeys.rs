@@ -0,0 +1,110 @@
+use crate::config::{CaptureHotkeys, SessionTemplate};
+use global_hotkey::hotkey::HotKey;
+use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::mpsc;
+
+/// Parse a binding such as `Ctrl+Alt+1` or `Shift+Super+F5`
+pub fn parse_hotkey(text: &str) -> Result<HotKey, String> {
+    HotKey::from_str(text.trim()).map_err(|e| format!("Invalid hotkey '{}': {}", text.trim(), e))
+}
+
+/// What a global hotkey does
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HotkeyTarget {
+    /// Launch the session template with this name
+    Template(String),
+    Screenshot,
+    /// Start a screen recording, or stop the running one
+    ToggleRecording,
+}
+
+/// System-wide hotkeys, which also work while the scrcpy window or another app has the focus
+pub struct GlobalHotkeys {
+    manager: Option<GlobalHotKeyManager>,
+    registered: Vec<HotKey>,
+    bindings: HashMap<u32, HotkeyTarget>, // hotkey id -> what it does
+    receiver: mpsc::Receiver<u32>,
+}
+
+impl GlobalHotkeys {
+    /// Must be created on the main thread; `ctx` is woken when a hotkey fires
+    pub fn new(ctx: egui::Context) -> Self {
+        let manager = match GlobalHotKeyManager::new() {
+            Ok(manager) => Some(manager),
+            Err(e) => {
+                // e.g. a Wayland session without XWayland
+                tracing::warn!("Global hotkeys unavailable: {}", e);
+                None
+            }
+        };
+
+        let (sender, receiver) = mpsc::channel();
+        GlobalHotKeyEvent::set_event_handler(Some(move |event: GlobalHotKeyEvent| {
+            if event.state() == HotKeyState::Pressed {
+                let _ = sender.send(event.id());
+                ctx.request_repaint();
+            }
+        }));
+
+        Self {
+            manager,
+            registered: Vec::new(),
+            bindings: HashMap::new(),
+            receiver,
+        }
+    }
+
+    /// Replace all bindings; returns a message for every hotkey that could not be registered
+    pub fn bind(&mut self, templates: &[SessionTemplate], capture: &CaptureHotkeys) -> Vec<String> {
+        let Some(manager) = &self.manager else {
+            return Vec::new();
+        };
+        for hotkey in self.registered.drain(..) {
+            let _ = manager.unregister(hotkey);
+        }
+        self.bindings.clear();
+
+        let wanted = templates
+            .iter()
+            .map(|template| (template.hotkey.as_str(), HotkeyTarget::Template(template.name.clone())))
+            .chain([
+                (capture.screenshot.as_str(), HotkeyTarget::Screenshot),
+                (capture.record.as_str(), HotkeyTarget::ToggleRecording),
+            ]);
+        let mut errors = Vec::new();
+        for (text, target) in wanted.filter(|(text, _)| !text.trim().is_empty()) {
+            let hotkey = match parse_hotkey(text) {
+                Ok(hotkey) => hotkey,
+                Err(e) => {
+                    errors.push(e);
+                    continue;
+                }
+            };
+            if self.bindings.contains_key(&hotkey.id()) {
+                errors.push(format!("{} is bound more than once", text.trim()));
+                continue;
+            }
+            match manager.register(hotkey) {
+                Ok(()) => {
+                    self.registered.push(hotkey);
+                    self.bindings.insert(hotkey.id(), target);
+                }
+                // Usually taken by another application
+                Err(e) => errors.push(format!("Could not register {}: {}", text.trim(), e)),
+            }
+        }
+        errors
+    }
+
+    /// What the hotkey pressed since the last call does
+    pub fn poll(&self) -> Option<HotkeyTarget> {
+        while let Ok(id) = self.receiver.try_recv() {
+            if let Some(name) = self.bindings.get(&id) {
+                return Some(name.clone());
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,311 @@
+//! Translations of the UI strings
+//!
+//! Catalogs use a small subset of the Fluent syntax: `key = text` messages, indented
+//! continuation lines, `#` comments and `{ $name }` placeholders. English is built in and
+//! fills in every key a translation lacks. More languages are read from `<lang>.ftl` files
+//! in `assets/i18n` (compiled in) or in the `i18n` folder of the config directory, so a
+//! community translation needs no code change.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Mutex, RwLock};
+
+pub const FALLBACK_LANGUAGE: &str = "en";
+
+/// Catalogs compiled into the binary, by language code
+const BUILT_IN: &[(&str, &str)] = &[(FALLBACK_LANGUAGE, include_str!("../assets/i18n/en.ftl"))];
+
+/// Message holding a catalog's own name for its language, shown in the selector
+const LANGUAGE_NAME_KEY: &str = "language-name";
+
+struct Active {
+    language: String,
+    messages: HashMap<String, String>,
+    fallback: HashMap<String, String>,
+}
+
+static ACTIVE: RwLock<Option<Active>> = RwLock::new(None);
+
+/// `available_languages` result, refreshed whenever the language is set
+static LANGUAGES: Mutex<Option<Vec<(String, String)>>> = Mutex::new(None);
+
+/// Parse a catalog into its messages; returns the messages and one error per unreadable line
+pub fn parse_catalog(text: &str) -> (HashMap<String, String>, Vec<String>) {
+    let mut messages = HashMap::new();
+    let mut errors = Vec::new();
+    let mut current: Option<(String, String)> = None;
+
+    for (number, line) in text.lines().enumerate() {
+        if line.trim_start().starts_with('#') {
+            continue;
+        }
+        if line.starts_with([' ', '\t']) && !line.trim().is_empty() {
+            match &mut current {
+                Some((_, value)) => {
+                    if !value.is_empty() {
+                        value.push('\n');
+                    }
+                    value.push_str(line.trim());
+                }
+                None => errors.push(format!("line {}: continuation without a message", number + 1)),
+            }
+            continue;
+        }
+        if let Some((key, value)) = current.take() {
+            messages.insert(key, value);
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        match line.split_once('=') {
+            Some((key, value))
+                if !key.trim().is_empty()
+                    && key.trim().chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') =>
+            {
+                current = Some((key.trim().to_string(), value.trim().to_string()));
+            }
+            _ => errors.push(format!("line {}: expected `key = text`", number + 1)),
+        }
+    }
+    if let Some((key, value)) = current {
+        messages.insert(key, value);
+    }
+    (messages, errors)
+}
+
+/// Replace `{ $name }` placeholders with `args`; unknown ones are left as they are
+fn format_message(message: &str, args: &[(&str, String)]) -> String {
+    let mut result = String::with_capacity(message.len());
+    let mut rest = message;
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}') else {
+            result.push_str(&rest[start..]);
+            return result;
+        };
+        let placeholder = &rest[start..start + end + 1];
+        let name = placeholder[1..placeholder.len() - 1].trim().trim_start_matches('$');
+        match args.iter().find(|(arg, _)| *arg == name) {
+            Some((_, value)) => result.push_str(value),
+            None => result.push_str(placeholder),
+        }
+        rest = &rest[start + end + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Folder for translations added without rebuilding
+pub fn user_catalog_dir() -> Option<PathBuf> {
+    crate::config::app_dir().ok().map(|dir| dir.join("i18n"))
+}
+
+fn catalog_source(language: &str) -> Option<String> {
+    if let Some(dir) = user_catalog_dir()
+        && let Ok(text) = fs::read_to_string(dir.join(format!("{}.ftl", language)))
+    {
+        return Some(text);
+    }
+    BUILT_IN
+        .iter()
+        .find(|(code, _)| *code == language)
+        .map(|(_, text)| text.to_string())
+}
+
+fn load_catalog(language: &str) -> Option<HashMap<String, String>> {
+    let (messages, errors) = parse_catalog(&catalog_source(language)?);
+    for error in errors {
+        tracing::warn!("Translation {}: {}", language, error);
+    }
+    Some(messages)
+}
+
+/// Language codes with a catalog and each catalog's name for itself, English first
+pub fn available_languages() -> Vec<(String, String)> {
+    let Ok(mut cached) = LANGUAGES.lock() else {
+        return scan_languages();
+    };
+    cached.get_or_insert_with(scan_languages).clone()
+}
+
+fn scan_languages() -> Vec<(String, String)> {
+    let mut codes: Vec<String> = BUILT_IN.iter().map(|(code, _)| code.to_string()).collect();
+    if let Some(dir) = user_catalog_dir()
+        && let Ok(entries) = fs::read_dir(dir)
+    {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "ftl")
+                && let Some(code) = path.file_stem().map(|stem| stem.to_string_lossy().to_string())
+                && !codes.contains(&code)
+            {
+                codes.push(code);
+            }
+        }
+    }
+    codes[1..].sort();
+
+    codes
+        .into_iter()
+        .map(|code| {
+            let name = load_catalog(&code)
+                .and_then(|messages| messages.get(LANGUAGE_NAME_KEY).cloned())
+                .unwrap_or_else(|| code.clone());
+            (code, name)
+        })
+        .collect()
+}
+
+/// The system language as a catalog code, e.g. `de` for `de_DE.UTF-8`
+pub fn system_language() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty() && value != "C" && value != "POSIX")
+        .and_then(|value| value.split(['_', '.', '-', '@']).next().map(str::to_lowercase))
+}
+
+/// Switch the UI language; `None` follows the system. Unknown languages fall back to English.
+pub fn set_language(language: Option<&str>) {
+    let requested = language
+        .map(str::to_string)
+        .or_else(system_language)
+        .unwrap_or_else(|| FALLBACK_LANGUAGE.to_string());
+    let fallback = load_catalog(FALLBACK_LANGUAGE).unwrap_or_default();
+    let (language, messages) = match load_catalog(&requested) {
+        Some(messages) => (requested, messages),
+        None => {
+            if language.is_some() {
+                tracing::warn!("No translation for '{}', using English", requested);
+            }
+            (FALLBACK_LANGUAGE.to_string(), HashMap::new())
+        }
+    };
+    tracing::debug!("UI language set to {}", language);
+    if let Ok(mut cached) = LANGUAGES.lock() {
+        *cached = None;
+    }
+
+    if let Ok(mut active) = ACTIVE.write() {
+        *active = Some(Active {
+            language,
+            messages,
+            fallback,
+        });
+    }
+}
+
+/// Code of the language in use
+pub fn current_language() -> String {
+    ACTIVE
+        .read()
+        .ok()
+        .and_then(|active| active.as_ref().map(|active| active.language.clone()))
+        .unwrap_or_else(|| FALLBACK_LANGUAGE.to_string())
+}
+
+/// The message for `key` in the current language, falling back to English and then to the key
+pub fn tr(key: &str, args: &[(&str, String)]) -> String {
+    let lookup = |active: &Active| {
+        active
+            .messages
+            .get(key)
+            .or_else(|| active.fallback.get(key))
+            .map(|message| format_message(message, args))
+            .unwrap_or_else(|| key.to_string())
+    };
+    if let Ok(guard) = ACTIVE.read()
+        && let Some(active) = guard.as_ref()
+    {
+        return lookup(active);
+    }
+
+    // Used before `set_language`: English only
+    let english = Active {
+        language: FALLBACK_LANGUAGE.to_string(),
+        messages: HashMap::new(),
+        fallback: load_catalog(FALLBACK_LANGUAGE).unwrap_or_default(),
+    };
+    let text = lookup(&english);
+    if let Ok(mut guard) = ACTIVE.write()
+        && guard.is_none()
+    {
+        *guard = Some(english);
+    }
+    text
+}
+
+/// Translate a message: `t!("settings-title")` or `t!("devices-found", count = devices.len())`
+#[macro_export]
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::tr($key, &[])
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::tr($key, &[$((stringify!($name), $value.to_string())),+])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalogs() {
+        let (messages, errors) = parse_catalog(
+            "# Comment\nlanguage-name = English\nsettings-title = Application Settings\n\nlong =\n    First line\n    second line\nnot a message\n",
+        );
+        assert_eq!(messages.get("language-name").map(String::as_str), Some("English"));
+        assert_eq!(messages.get("settings-title").map(String::as_str), Some("Application Settings"));
+        assert_eq!(messages.get("long").map(String::as_str), Some("First line\nsecond line"));
+        assert_eq!(errors.len(), 1);
+
+        assert_eq!(
+            format_message("Found { $count } device(s) on {$host}", &[("count", "3".to_string()), ("host", "pc".to_string())]),
+            "Found 3 device(s) on pc"
+        );
+        assert_eq!(format_message("Keep { $unknown } and {", &[]), "Keep { $unknown } and {");
+    }
+
+    #[test]
+    fn english_catalog_parses_cleanly() {
+        let (messages, errors) = parse_catalog(BUILT_IN[0].1);
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert!(messages.contains_key(LANGUAGE_NAME_KEY));
+    }
+
+    #[test]
+    fn english_catalog_has_every_key_in_use() {
+        fn sources(dir: &std::path::Path, files: &mut Vec<PathBuf>) {
+            for entry in fs::read_dir(dir).unwrap().flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    sources(&path, files);
+                } else if path.extension().is_some_and(|ext| ext == "rs") {
+                    files.push(path);
+                }
+            }
+        }
+        let mut files = Vec::new();
+        sources(&std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src"), &mut files);
+
+        let (messages, _) = parse_catalog(BUILT_IN[0].1);
+        let mut missing = Vec::new();
+        for file in files {
+            let text = fs::read_to_string(&file).unwrap();
+            for (start, _) in text.match_indices("t!(\"") {
+                // Not the end of `format!(` and the like, nor an example in a comment
+                let line = &text[text[..start].rfind('\n').map_or(0, |end| end + 1)..start];
+                if line.ends_with(|c: char| c.is_alphanumeric() || c == '_') || line.trim_start().starts_with("//") {
+                    continue;
+                }
+                let key = text[start + 4..].split('"').next().unwrap_or_default();
+                if !messages.contains_key(key) {
+                    missing.push(format!("{}: {}", file.display(), key));
+                }
+            }
+        }
+        assert!(missing.is_empty(), "{:#?}", missing);
+    }
+}
@@ -0,0 +1,86 @@
+//! Pixel comparison of two screenshots
+//!
+//! A pixel counts as changed when any of its channels differs by more than the threshold, which
+//! lets compression noise and subpixel rendering pass. The heatmap is the first image dimmed to
+//! gray with the changes on top, from red for small differences to yellow for large ones.
+//! Screenshots of different sizes are compared after scaling the second to the first.
+
+use image::{imageops, Rgba, RgbaImage};
+
+pub const DEFAULT_THRESHOLD: u8 = 16;
+
+pub struct Diff {
+    pub heatmap: RgbaImage,
+    pub changed: u64,
+    pub total: u64,
+    /// The second image had to be scaled to the size of the first
+    pub scaled: bool,
+}
+
+impl Diff {
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.changed as f64 * 100.0 / self.total as f64
+    }
+}
+
+/// Compare `after` with `before`, counting pixels that differ by more than `threshold`
+pub fn compare(before: &RgbaImage, after: &RgbaImage, threshold: u8) -> Diff {
+    let (width, height) = before.dimensions();
+    let scaled = after.dimensions() != before.dimensions();
+    let resized;
+    let after = if scaled {
+        resized = imageops::resize(after, width, height, imageops::FilterType::Triangle);
+        &resized
+    } else {
+        after
+    };
+
+    let mut changed = 0;
+    let heatmap = RgbaImage::from_fn(width, height, |x, y| {
+        let (a, b) = (before.get_pixel(x, y), after.get_pixel(x, y));
+        let difference = a.0.iter().zip(b.0).map(|(a, b)| a.abs_diff(b)).max().unwrap_or(0);
+        if difference > threshold {
+            changed += 1;
+            Rgba([255, difference, 0, 255])
+        } else {
+            let luma = (a[0] as u32 * 299 + a[1] as u32 * 587 + a[2] as u32 * 114) / 1000;
+            let dimmed = (luma / 3 + 20) as u8;
+            Rgba([dimmed, dimmed, dimmed, 255])
+        }
+    });
+    Diff { heatmap, changed, total: u64::from(width) * u64::from(height), scaled }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_changed_pixels() {
+        let before = RgbaImage::from_pixel(20, 10, Rgba([90, 120, 200, 255]));
+        let diff = compare(&before, &before, DEFAULT_THRESHOLD);
+        assert_eq!((diff.changed, diff.percent()), (0, 0.0));
+
+        let mut after = before.clone();
+        for x in 0..5 {
+            for y in 0..4 {
+                after.put_pixel(x, y, Rgba([250, 120, 200, 255]));
+            }
+        }
+        // Below the threshold
+        after.put_pixel(19, 9, Rgba([95, 125, 195, 255]));
+        let diff = compare(&before, &after, DEFAULT_THRESHOLD);
+        assert_eq!(diff.changed, 20);
+        assert_eq!(diff.percent(), 10.0);
+        assert!(!diff.scaled);
+        assert_eq!(diff.heatmap.get_pixel(0, 0).0, [255, 160, 0, 255]);
+        assert_eq!(diff.heatmap.get_pixel(19, 9).0[0], diff.heatmap.get_pixel(10, 5).0[0]);
+
+        let diff = compare(&before, &RgbaImage::from_pixel(40, 20, Rgba([90, 120, 200, 255])), DEFAULT_THRESHOLD);
+        assert!(diff.scaled);
+        assert_eq!(diff.changed, 0);
+    }
+}
@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+
+/// How many sent intents are kept in the history
+pub const INTENT_HISTORY_LIMIT: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum IntentKind {
+    #[default]
+    Activity,
+    Broadcast,
+}
+
+impl IntentKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            IntentKind::Activity => "am start",
+            IntentKind::Broadcast => "am broadcast",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ExtraType {
+    #[default]
+    String,
+    Int,
+    Long,
+    Float,
+    Bool,
+    Uri,
+}
+
+impl ExtraType {
+    pub const ALL: [ExtraType; 6] = [
+        ExtraType::String,
+        ExtraType::Int,
+        ExtraType::Long,
+        ExtraType::Float,
+        ExtraType::Bool,
+        ExtraType::Uri,
+    ];
+
+    /// The `am` flag for this extra type
+    pub fn flag(self) -> &'static str {
+        match self {
+            ExtraType::String => "--es",
+            ExtraType::Int => "--ei",
+            ExtraType::Long => "--el",
+            ExtraType::Float => "--ef",
+            ExtraType::Bool => "--ez",
+            ExtraType::Uri => "--eu",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ExtraType::String => "string",
+            ExtraType::Int => "int",
+            ExtraType::Long => "long",
+            ExtraType::Float => "float",
+            ExtraType::Bool => "bool",
+            ExtraType::Uri => "uri",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IntentExtra {
+    pub key: String,
+    pub kind: ExtraType,
+    pub value: String,
+}
+
+/// An `am start` / `am broadcast` invocation as composed in the intent sender
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IntentSpec {
+    pub kind: IntentKind,
+    pub action: String,
+    pub data: String,
+    pub component: String,
+    pub category: String,
+    pub extras: Vec<IntentExtra>,
+}
+
+impl IntentSpec {
+    pub fn is_empty(&self) -> bool {
+        self.action.trim().is_empty() && self.data.trim().is_empty() && self.component.trim().is_empty()
+    }
+
+    /// The shell command run on the device, with every value quoted for `sh`
+    pub fn to_command(&self) -> String {
+        let mut parts = vec![self.kind.label().to_string()];
+        if self.kind == IntentKind::Activity {
+            // Wait for the launch so errors and timing come back in the output
+            parts.push("-W".to_string());
+        }
+
+        let mut push = |flag: &str, value: &str| {
+            let value = value.trim();
+            if !value.is_empty() {
+                parts.push(flag.to_string());
+                parts.push(shell_quote(value));
+            }
+        };
+        push("-a", &self.action);
+        push("-d", &self.data);
+        push("-c", &self.category);
+        push("-n", &self.component);
+
+        for extra in &self.extras {
+            if extra.key.trim().is_empty() {
+                continue;
+            }
+            parts.push(extra.kind.flag().to_string());
+            parts.push(shell_quote(extra.key.trim()));
+            parts.push(shell_quote(&extra.value));
+        }
+
+        parts.join(" ")
+    }
+
+    /// One-line summary for the history list
+    pub fn summary(&self) -> String {
+        let target = [&self.data, &self.action, &self.component]
+            .into_iter()
+            .map(|s| s.trim())
+            .find(|s| !s.is_empty())
+            .unwrap_or("(empty)");
+        format!("{}: {}", self.kind.label(), target)
+    }
+}
+
+/// Wrap a value in single quotes for the device shell
+pub fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
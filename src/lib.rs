@@ -16,13 +16,69 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
+pub mod activity_stack;
+pub mod adb_keys;
+pub mod api;
 pub mod app;
+pub mod automation;
+pub mod benchmark;
 pub mod bridge;
+pub mod bugreport;
+pub mod capabilities;
+pub mod clock;
+pub mod cli;
 pub mod config;
+pub mod config_bundle;
+pub mod controls;
+pub mod crash;
 pub mod device;
+pub mod device_clipboard;
+pub mod device_frame;
+pub mod dock;
+pub mod export;
+pub mod fastboot;
+pub mod gallery;
+pub mod gesture;
+pub mod gfxinfo;
+pub mod gnirehtet;
+pub mod hierarchy;
+#[cfg(all(feature = "test-harness", unix))]
+pub mod harness;
+pub mod hotkeys;
+pub mod i18n;
+pub mod image_diff;
+pub mod intent;
+pub mod locale;
 pub mod logging;
+pub mod macros;
+pub mod mock_location;
+pub mod monitor;
+pub mod native_preview;
+pub mod ocr;
+pub mod packages;
+pub mod perfetto;
+pub mod plugins;
+pub mod reboot;
+pub mod reconnect;
+pub mod report;
+pub mod resolution;
+pub mod scripting;
+pub mod share;
+pub mod shell_console;
+pub mod shortcuts;
+pub mod sideload;
+pub mod sndcpy;
+pub mod standby;
+pub mod stitch;
+pub mod stream;
+pub mod tools;
+pub mod tunnel;
+#[cfg(feature = "tray")]
+pub mod tray;
 pub mod ui;
+pub mod update;
 pub mod utils;
+pub mod wifi;
 
 pub use app::DroidViewApp;
 pub use config::AppConfig;
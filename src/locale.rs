@@ -0,0 +1,219 @@
+//! The device's locale and time zone, for localization testing
+//!
+//! Android 13 and later take the locale list from the `system_locales` setting; older versions
+//! only read `persist.sys.locale`, which the shell user can set on emulators and rooted devices.
+//! The time zone goes through `cmd alarm set-timezone` where it exists and the alarm service's
+//! binder call before that, with automatic time zone detection switched off first.
+
+use crate::automation::shell;
+use anyhow::Result;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LocaleInfo {
+    /// BCP 47 tag such as `en-US`
+    pub locale: String,
+    /// IANA name such as `Europe/Berlin`
+    pub timezone: String,
+    pub auto_timezone: bool,
+}
+
+/// Locales offered in the list, by tag and English name; others can be typed in
+pub const LOCALES: [(&str, &str); 48] = [
+    ("ar-EG", "Arabic (Egypt)"),
+    ("ar-SA", "Arabic (Saudi Arabia)"),
+    ("bg-BG", "Bulgarian"),
+    ("bn-BD", "Bengali"),
+    ("ca-ES", "Catalan"),
+    ("cs-CZ", "Czech"),
+    ("da-DK", "Danish"),
+    ("de-AT", "German (Austria)"),
+    ("de-DE", "German (Germany)"),
+    ("el-GR", "Greek"),
+    ("en-AU", "English (Australia)"),
+    ("en-GB", "English (United Kingdom)"),
+    ("en-IN", "English (India)"),
+    ("en-US", "English (United States)"),
+    ("es-ES", "Spanish (Spain)"),
+    ("es-MX", "Spanish (Mexico)"),
+    ("fa-IR", "Persian"),
+    ("fi-FI", "Finnish"),
+    ("fil-PH", "Filipino"),
+    ("fr-CA", "French (Canada)"),
+    ("fr-FR", "French (France)"),
+    ("he-IL", "Hebrew"),
+    ("hi-IN", "Hindi"),
+    ("hr-HR", "Croatian"),
+    ("hu-HU", "Hungarian"),
+    ("id-ID", "Indonesian"),
+    ("it-IT", "Italian"),
+    ("ja-JP", "Japanese"),
+    ("ko-KR", "Korean"),
+    ("ms-MY", "Malay"),
+    ("nb-NO", "Norwegian Bokmål"),
+    ("nl-NL", "Dutch"),
+    ("pl-PL", "Polish"),
+    ("pt-BR", "Portuguese (Brazil)"),
+    ("pt-PT", "Portuguese (Portugal)"),
+    ("ro-RO", "Romanian"),
+    ("ru-RU", "Russian"),
+    ("sk-SK", "Slovak"),
+    ("sr-RS", "Serbian"),
+    ("sv-SE", "Swedish"),
+    ("sw-KE", "Swahili"),
+    ("ta-IN", "Tamil"),
+    ("th-TH", "Thai"),
+    ("tr-TR", "Turkish"),
+    ("uk-UA", "Ukrainian"),
+    ("vi-VN", "Vietnamese"),
+    ("zh-CN", "Chinese (Simplified)"),
+    ("zh-TW", "Chinese (Traditional)"),
+];
+
+/// Time zones offered in the list; others can be typed in
+pub const TIMEZONES: [&str; 36] = [
+    "Pacific/Honolulu",
+    "America/Anchorage",
+    "America/Los_Angeles",
+    "America/Denver",
+    "America/Chicago",
+    "America/New_York",
+    "America/Sao_Paulo",
+    "America/Argentina/Buenos_Aires",
+    "Atlantic/Azores",
+    "UTC",
+    "Europe/London",
+    "Europe/Lisbon",
+    "Europe/Paris",
+    "Europe/Berlin",
+    "Europe/Madrid",
+    "Europe/Rome",
+    "Europe/Warsaw",
+    "Europe/Athens",
+    "Europe/Kyiv",
+    "Europe/Istanbul",
+    "Europe/Moscow",
+    "Africa/Cairo",
+    "Africa/Lagos",
+    "Africa/Johannesburg",
+    "Asia/Dubai",
+    "Asia/Tehran",
+    "Asia/Karachi",
+    "Asia/Kolkata",
+    "Asia/Kathmandu",
+    "Asia/Bangkok",
+    "Asia/Shanghai",
+    "Asia/Singapore",
+    "Asia/Tokyo",
+    "Asia/Seoul",
+    "Australia/Sydney",
+    "Pacific/Auckland",
+];
+
+pub fn read(adb_path: &str, serial: &str) -> Result<LocaleInfo> {
+    let locales = shell(
+        adb_path,
+        serial,
+        "settings get system system_locales; getprop persist.sys.locale; getprop ro.product.locale",
+    )?;
+    let timezone = shell(adb_path, serial, "getprop persist.sys.timezone")?;
+    let auto_timezone = shell(adb_path, serial, "settings get global auto_time_zone").unwrap_or_default();
+    Ok(LocaleInfo {
+        locale: first_locale(&locales).unwrap_or_default(),
+        timezone: timezone.trim().to_string(),
+        auto_timezone: auto_timezone.trim() == "1",
+    })
+}
+
+/// Switch the device to `tag`; fails when the device kept its locale
+pub fn set_locale(adb_path: &str, serial: &str, tag: &str) -> Result<()> {
+    if !is_locale_tag(tag) {
+        return Err(anyhow::anyhow!("'{}' is not a locale tag such as en-US", tag));
+    }
+    let _ = shell(adb_path, serial, &format!("settings put system system_locales {}", tag));
+    // Apps still running pick the new locale up when they are restarted
+    if read(adb_path, serial)?.locale != tag {
+        let _ = shell(adb_path, serial, &format!("setprop persist.sys.locale {}", tag));
+    }
+    if read(adb_path, serial)?.locale != tag {
+        return Err(anyhow::anyhow!(
+            "The device kept its locale; changing it over adb needs Android 13 or later, an emulator or root"
+        ));
+    }
+    Ok(())
+}
+
+pub fn set_timezone(adb_path: &str, serial: &str, timezone: &str) -> Result<()> {
+    if timezone.is_empty() || timezone.contains(char::is_whitespace) || timezone.contains(['\'', '"', ';']) {
+        return Err(anyhow::anyhow!("'{}' is not a time zone such as Europe/Berlin", timezone));
+    }
+    // Automatic detection would put the old zone back
+    shell(adb_path, serial, "settings put global auto_time_zone 0")?;
+    let commands = [
+        format!("cmd alarm set-timezone {}", timezone),
+        format!("service call alarm 3 s16 {}", timezone),
+        format!("setprop persist.sys.timezone {}", timezone),
+    ];
+    for command in commands {
+        if shell(adb_path, serial, &command).is_ok() && read(adb_path, serial)?.timezone == timezone {
+            return Ok(());
+        }
+    }
+    Err(anyhow::anyhow!("The device did not accept the time zone {}", timezone))
+}
+
+/// The first locale of the first line that has one; `system_locales` can list several
+pub fn first_locale(output: &str) -> Option<String> {
+    output
+        .lines()
+        .map(|line| line.trim().split(',').next().unwrap_or_default().trim())
+        .find(|locale| !locale.is_empty() && *locale != "null")
+        .map(str::to_string)
+}
+
+/// Letters, digits and dashes, starting with a language
+pub fn is_locale_tag(tag: &str) -> bool {
+    let mut parts = tag.split('-');
+    let language = parts.next().unwrap_or_default();
+    (2..=3).contains(&language.len())
+        && language.chars().all(|c| c.is_ascii_alphabetic())
+        && parts.all(|part| !part.is_empty() && part.len() <= 8 && part.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+/// Entries of `LOCALES` whose tag or name contains `query`, ignoring case
+pub fn search_locales(query: &str) -> Vec<(&'static str, &'static str)> {
+    let query = query.trim().to_lowercase();
+    LOCALES
+        .into_iter()
+        .filter(|(tag, name)| tag.to_lowercase().contains(&query) || name.to_lowercase().contains(&query))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_first_locale() {
+        assert_eq!(first_locale("fr-FR,en-US\nen-US\nen-US"), Some("fr-FR".to_string()));
+        assert_eq!(first_locale("null\n\nde-DE"), Some("de-DE".to_string()));
+        assert_eq!(first_locale("null"), None);
+    }
+
+    #[test]
+    fn checks_locale_tags() {
+        assert!(is_locale_tag("en-US"));
+        assert!(is_locale_tag("fil-PH"));
+        assert!(is_locale_tag("sr-Latn-RS"));
+        assert!(!is_locale_tag("english"));
+        assert!(!is_locale_tag("en-"));
+        assert!(!is_locale_tag("en-US; reboot"));
+    }
+
+    #[test]
+    fn searches_locales() {
+        let german: Vec<_> = search_locales("GERMAN").into_iter().map(|(tag, _)| tag).collect();
+        assert_eq!(german, ["de-AT", "de-DE"]);
+        assert_eq!(search_locales("pt-br"), [("pt-BR", "Portuguese (Brazil)")]);
+        assert_eq!(search_locales("").len(), LOCALES.len());
+    }
+}
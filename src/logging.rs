@@ -1,15 +1,237 @@
-use tracing::{info, Level};
-use tracing_subscriber::FmtSubscriber;
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use tracing::field::{Field, Visit};
+use tracing::{info, Event, Level, Subscriber};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{reload, Layer, Registry};
 
-pub fn init_logging() {
-    FmtSubscriber::builder()
-        .with_max_level(Level::INFO)
+/// How many log lines the in-app console keeps
+const LOG_CAPACITY: usize = 5000;
+
+/// Levels accepted by `--log-level` and the settings, most severe first
+pub const LOG_LEVELS: [&str; 5] = ["error", "warn", "info", "debug", "trace"];
+
+const LOG_FILE: &str = "droidview.log";
+/// The log file is rotated once it grows past this size
+const MAX_LOG_SIZE: u64 = 5 * 1024 * 1024;
+/// Rotated files kept next to the current one: droidview.1.log is the newest
+const KEEP_ROTATED: usize = 3;
+
+static LEVEL_HANDLE: OnceLock<reload::Handle<LevelFilter, Registry>> = OnceLock::new();
+/// Level given with `--log-level`, which holds for the whole run whatever Settings say
+static CLI_LEVEL: OnceLock<LevelFilter> = OnceLock::new();
+
+/// One line of DroidView's own log, as shown in the log console
+#[derive(Debug, Clone)]
+pub struct LogRecord {
+    /// Increases by one per record, so readers can ask for what they have not seen
+    pub seq: u64,
+    pub at: DateTime<Local>,
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+}
+
+impl LogRecord {
+    pub fn to_line(&self) -> String {
+        format!("{} {:>5} {}: {}", self.at.format("%Y-%m-%d %H:%M:%S%.3f"), self.level, self.target, self.message)
+    }
+}
+
+struct LogBuffer {
+    records: VecDeque<LogRecord>,
+    next_seq: u64,
+}
+
+static LOG_BUFFER: Mutex<LogBuffer> = Mutex::new(LogBuffer {
+    records: VecDeque::new(),
+    next_seq: 0,
+});
+
+/// Records logged after `seq` (all of them for `None`) that are still in the buffer
+pub fn records_after(seq: Option<u64>) -> Vec<LogRecord> {
+    let Ok(buffer) = LOG_BUFFER.lock() else {
+        return Vec::new();
+    };
+    let start = match seq {
+        Some(seq) => buffer.records.partition_point(|record| record.seq <= seq),
+        None => 0,
+    };
+    buffer.records.range(start..).cloned().collect()
+}
+
+/// Tracing layer copying every event into the ring buffer read by the log console
+struct RingBufferLayer;
+
+impl<S: Subscriber> Layer<S> for RingBufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+
+        let Ok(mut buffer) = LOG_BUFFER.lock() else {
+            return;
+        };
+        let seq = buffer.next_seq;
+        buffer.next_seq += 1;
+        if buffer.records.len() >= LOG_CAPACITY {
+            buffer.records.pop_front();
+        }
+        buffer.records.push_back(LogRecord {
+            seq,
+            at: Local::now(),
+            level: *event.metadata().level(),
+            target: event.metadata().target().to_string(),
+            message: visitor.message,
+        });
+    }
+}
+
+/// Formats the `message` field followed by any other fields as `name=value`
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let fields = std::mem::take(&mut self.message);
+            let _ = write!(self.message, "{:?}{}", value, fields);
+        } else {
+            let _ = write!(self.message, " {}={:?}", field.name(), value);
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.insert_str(0, value);
+        } else {
+            let _ = write!(self.message, " {}={}", field.name(), value);
+        }
+    }
+}
+
+/// Folder holding the log files, inside the config directory
+pub fn log_dir() -> Option<PathBuf> {
+    crate::config::app_dir().ok().map(|dir| dir.join("logs"))
+}
+
+/// Log file that moves aside to `droidview.N.log` when it gets too big
+struct RotatingFile {
+    dir: PathBuf,
+    file: File,
+    size: u64,
+}
+
+impl RotatingFile {
+    fn open(dir: PathBuf) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        let file = OpenOptions::new().create(true).append(true).open(dir.join(LOG_FILE))?;
+        let size = file.metadata()?.len();
+        Ok(Self { dir, file, size })
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        self.dir.join(format!("droidview.{}.log", index))
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        let _ = fs::remove_file(self.rotated_path(KEEP_ROTATED));
+        for index in (1..KEEP_ROTATED).rev() {
+            let _ = fs::rename(self.rotated_path(index), self.rotated_path(index + 1));
+        }
+        fs::rename(self.dir.join(LOG_FILE), self.rotated_path(1))?;
+        self.file = OpenOptions::new().create(true).append(true).open(self.dir.join(LOG_FILE))?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.size > 0 && self.size + buf.len() as u64 > MAX_LOG_SIZE {
+            self.rotate()?;
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// `info`, `DEBUG`, ... as a filter; `None` for anything not in `LOG_LEVELS`
+pub fn parse_level(level: &str) -> Option<LevelFilter> {
+    let level = level.trim().to_lowercase();
+    LOG_LEVELS.contains(&level.as_str()).then(|| level.parse().ok()).flatten()
+}
+
+/// Log to stderr, the in-app console and a rotating file in `log_dir()`, at `cli_level` when
+/// `--log-level` was given and at `level` (INFO when unknown) until [`set_log_level`] otherwise.
+/// Called before the configuration is read, so nothing logged while reading it is lost
+pub fn init_logging(cli_level: Option<&str>, level: &str) {
+    let cli_level = cli_level.and_then(parse_level);
+    if let Some(filter) = cli_level {
+        let _ = CLI_LEVEL.set(filter);
+    }
+    let (filter, handle) = reload::Layer::new(cli_level.or_else(|| parse_level(level)).unwrap_or(LevelFilter::INFO));
+
+    let stderr = tracing_subscriber::fmt::layer()
         .with_target(false)
         .with_thread_ids(false)
         .with_thread_names(false)
         .with_file(false)
-        .with_line_number(false)
+        .with_line_number(false);
+
+    let file_error;
+    let file = match log_dir().map(RotatingFile::open) {
+        Some(Ok(file)) => {
+            file_error = None;
+            Some(tracing_subscriber::fmt::layer().with_ansi(false).with_writer(Mutex::new(file)))
+        }
+        Some(Err(e)) => {
+            file_error = Some(e.to_string());
+            None
+        }
+        None => {
+            file_error = Some("Could not determine the config directory".to_string());
+            None
+        }
+    };
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(stderr)
+        .with(file)
+        .with(RingBufferLayer)
         .init();
+    let _ = LEVEL_HANDLE.set(handle);
 
     info!("DroidView logging initialized");
+    if let Some(e) = file_error {
+        tracing::warn!("Not writing a log file: {}", e);
+    }
+}
+
+/// Change the level of every log output while running, unless `--log-level` chose it
+pub fn set_log_level(level: &str) -> Result<()> {
+    let filter = parse_level(level).ok_or_else(|| anyhow::anyhow!("Unknown log level '{}'", level))?;
+    let handle = LEVEL_HANDLE.get().ok_or_else(|| anyhow::anyhow!("Logging is not initialized"))?;
+    if CLI_LEVEL.get().is_some() || handle.clone_current() == Some(filter) {
+        return Ok(());
+    }
+    handle.reload(filter)?;
+    info!("Log level set to {}", filter);
+    Ok(())
 }
@@ -0,0 +1,133 @@
+//! Recorded input macros: taps, swipes, key events and text replayed with `adb shell input`
+//!
+//! Each step keeps the pause before it as recorded. Playback divides pauses and swipe
+//! durations by the speed factor, so 2.0 replays twice as fast.
+
+use crate::bridge::adb_command;
+use crate::config::{MacroInput, MacroStep};
+use crate::utils::sleep_unless_cancelled;
+use anyhow::Result;
+use std::sync::atomic::AtomicBool;
+use std::time::Duration;
+
+pub const MIN_SPEED: f32 = 0.25;
+pub const MAX_SPEED: f32 = 4.0;
+/// Keys offered in the recorder; any other keycode can be typed in
+pub const COMMON_KEYS: [(&str, &str); 9] = [
+    ("KEYCODE_BACK", "Back"),
+    ("KEYCODE_HOME", "Home"),
+    ("KEYCODE_APP_SWITCH", "Recent apps"),
+    ("KEYCODE_POWER", "Power"),
+    ("KEYCODE_VOLUME_UP", "Volume up"),
+    ("KEYCODE_VOLUME_DOWN", "Volume down"),
+    ("KEYCODE_ENTER", "Enter"),
+    ("KEYCODE_DEL", "Backspace"),
+    ("KEYCODE_TAB", "Tab"),
+];
+
+/// Quote text for `input text`, which takes `%s` for a space and runs through the device shell
+pub fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            ' ' => escaped.push_str("%s"),
+            '\\' | '"' | '\'' | '`' | '$' | '&' | '|' | ';' | '<' | '>' | '(' | ')' | '*' | '~' | '?' | '#' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// The `adb shell` command for one input, with swipes slowed down or sped up by `speed`
+pub fn input_command(input: &MacroInput, speed: f32) -> String {
+    match input {
+        MacroInput::Tap { x, y } => format!("input tap {} {}", x, y),
+        MacroInput::Swipe { x1, y1, x2, y2, duration_ms } => {
+            let duration = (*duration_ms as f32 / speed.clamp(MIN_SPEED, MAX_SPEED)).round().max(1.0) as u32;
+            format!("input swipe {} {} {} {} {}", x1, y1, x2, y2, duration)
+        }
+        MacroInput::Key { code } => format!("input keyevent {}", code.trim()),
+        MacroInput::Text { text } => format!("input text {}", escape_text(text)),
+    }
+}
+
+/// Short description of a step for the step list
+pub fn describe(input: &MacroInput) -> String {
+    match input {
+        MacroInput::Tap { x, y } => format!("Tap {}, {}", x, y),
+        MacroInput::Swipe { x1, y1, x2, y2, duration_ms } => {
+            format!("Swipe {}, {} → {}, {} ({} ms)", x1, y1, x2, y2, duration_ms)
+        }
+        MacroInput::Key { code } => format!("Key {}", code.trim().trim_start_matches("KEYCODE_")),
+        MacroInput::Text { text } => format!("Text \"{}\"", text),
+    }
+}
+
+pub fn scaled_delay(delay_ms: u64, speed: f32) -> Duration {
+    Duration::from_millis((delay_ms as f32 / speed.clamp(MIN_SPEED, MAX_SPEED)) as u64)
+}
+
+/// Send one input to the device
+pub fn send(adb_path: &str, serial: &str, input: &MacroInput) -> Result<()> {
+    let output = adb_command(adb_path)
+        .args(["-s", serial, "shell", &input_command(input, 1.0)])
+        .output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() || stderr.contains("Exception") {
+        return Err(anyhow::anyhow!("{}", stderr.lines().next().unwrap_or("input failed").trim()));
+    }
+    Ok(())
+}
+
+/// Replay `steps`, reporting the index of each step before it runs; stops early once `cancel` is set
+pub fn play(
+    adb_path: &str,
+    serial: &str,
+    steps: &[MacroStep],
+    speed: f32,
+    cancel: &AtomicBool,
+    mut on_step: impl FnMut(usize),
+) -> Result<()> {
+    let speed = speed.clamp(MIN_SPEED, MAX_SPEED);
+    for (index, step) in steps.iter().enumerate() {
+        if !sleep_unless_cancelled(scaled_delay(step.delay_ms, speed), cancel) {
+            return Err(anyhow::anyhow!("Stopped"));
+        }
+        on_step(index);
+        let output = adb_command(adb_path)
+            .args(["-s", serial, "shell", &input_command(&step.input, speed)])
+            .output()?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!("Step {} failed: {}", index + 1, describe(&step.input)));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_input_commands() {
+        assert_eq!(input_command(&MacroInput::Tap { x: 10, y: 20 }, 1.0), "input tap 10 20");
+        let swipe = MacroInput::Swipe { x1: 0, y1: 0, x2: 100, y2: 200, duration_ms: 300 };
+        assert_eq!(input_command(&swipe, 2.0), "input swipe 0 0 100 200 150");
+        assert_eq!(input_command(&swipe, 0.0), "input swipe 0 0 100 200 1200");
+        let key = MacroInput::Key { code: "KEYCODE_HOME".to_string() };
+        assert_eq!(input_command(&key, 1.0), "input keyevent KEYCODE_HOME");
+        let text = MacroInput::Text { text: "it's a $5 test".to_string() };
+        assert_eq!(input_command(&text, 1.0), "input text it\\'s%sa%s\\$5%stest");
+    }
+
+    #[test]
+    fn scales_delays() {
+        assert_eq!(scaled_delay(1000, 2.0), Duration::from_millis(500));
+        assert_eq!(scaled_delay(1000, 0.5), Duration::from_millis(2000));
+        // Clamped to the slider range
+        assert_eq!(scaled_delay(1000, 100.0), Duration::from_millis(250));
+    }
+}
@@ -20,14 +20,16 @@
 
 use clap::Parser;
 use droid_view::app::DroidViewApp;
+use droid_view::cli::Command;
 use droid_view::config::AppConfig;
-use droid_view::logging::init_logging;
+use droid_view::logging::{init_logging, set_log_level};
 use eframe::{egui, NativeOptions};
 use egui::IconData;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use droid_view::app::ICON_PNG;
-use egui_phosphor;
+
+const MIN_WINDOW_SIZE: [f32; 2] = [500.0, 400.0];
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -51,30 +53,101 @@ struct Args {
     /// Reset configuration files
     #[arg(short, long)]
     reset_config: bool,
+
+    /// Use a separate named configuration (e.g. `work`); "default" is the regular one
+    #[arg(short, long)]
+    profile: Option<String>,
+
+    /// List the named configuration profiles and exit
+    #[arg(long)]
+    list_profiles: bool,
+
+    /// Log verbosity for this run, overriding the one in Settings
+    #[arg(long, value_parser = droid_view::logging::LOG_LEVELS)]
+    log_level: Option<String>,
+
+    /// Run a single task without opening the window
+    #[command(subcommand)]
+    command: Option<Command>,
 }
 
 #[tokio::main]
 async fn main() -> Result<(), eframe::Error> {
     let args = Args::parse();
 
-    // Initialize logging
-    init_logging();
+    if args.list_profiles {
+        #[cfg(windows)]
+        droid_view::cli::attach_console();
+        println!("default");
+        for profile in droid_view::config::list_profiles() {
+            println!("{}", profile);
+        }
+        return Ok(());
+    }
+
+    if let Some(profile) = &args.profile
+        && let Err(e) = droid_view::config::set_profile(profile)
+    {
+        #[cfg(windows)]
+        droid_view::cli::attach_console();
+        eprintln!("{}", e);
+        std::process::exit(2);
+    }
+
+    // Keep a report of panics, which otherwise vanish with the window
+    droid_view::crash::install_panic_hook();
+
+    // Initialize logging before anything can log; the file goes next to the configuration.
+    // Subcommands keep their output to what they print unless asked for more
+    init_logging(args.log_level.as_deref(), if args.command.is_some() { "warn" } else { "info" });
 
     // Load or create configuration
     let config = if args.reset_config {
         AppConfig::default()
     } else {
-        AppConfig::load().unwrap_or_default()
+        AppConfig::load().unwrap_or_else(|e| {
+            tracing::warn!("Using the default settings, the config file could not be read: {}", e);
+            AppConfig::default()
+        })
     };
+    if args.command.is_none()
+        && let Err(e) = set_log_level(&config.log_level)
+    {
+        tracing::warn!("{}", e);
+    }
+
+    if let Some(command) = args.command {
+        #[cfg(windows)]
+        droid_view::cli::attach_console();
+        if let Err(e) = droid_view::cli::run(command, &config) {
+            eprintln!("{:#}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
 
     // Create shared configuration
     let config = Arc::new(Mutex::new(config));
 
     // Set up native options
+    let title = match droid_view::config::active_profile() {
+        Some(profile) => format!("DroidView ({})", profile),
+        None => "DroidView".to_string(),
+    };
+    let geometry = config.try_lock().ok().and_then(|config| config.window);
     let mut viewport = egui::ViewportBuilder::default()
-        .with_inner_size([800.0, 600.0])
-        .with_min_inner_size([500.0, 400.0])
+        .with_title(title)
+        .with_inner_size(geometry.map_or([800.0, 600.0], |geometry| {
+            [geometry.size[0].max(MIN_WINDOW_SIZE[0]), geometry.size[1].max(MIN_WINDOW_SIZE[1])]
+        }))
+        .with_min_inner_size(MIN_WINDOW_SIZE)
         .with_decorations(!args.hide_wm_frame);
+    if let Some(geometry) = geometry {
+        if let Some(position) = geometry.position {
+            viewport = viewport.with_position(position);
+        }
+        viewport = viewport.with_maximized(geometry.maximized);
+    }
 
     if args.always_on_top {
         viewport = viewport.with_always_on_top();
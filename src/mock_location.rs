@@ -0,0 +1,314 @@
+//! Mock locations, set by hand, from presets or played back along a GPX route
+//!
+//! Android only takes mock locations from the app picked as mock location app, which is the
+//! `android:mock_location` app op. Emulators take coordinates straight from their console with
+//! `adb emu geo fix`; other devices need a helper app that publishes what it is sent. That is
+//! Appium's settings app, whose location service takes coordinates as intent extras; its APK
+//! ships next to DroidView as `appium-settings.apk`.
+
+use crate::automation::shell;
+use crate::bridge::adb_command;
+use crate::packages::is_package_name;
+use crate::utils::{sleep_unless_cancelled, xml_attributes};
+use anyhow::Result;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::sync::atomic::AtomicBool;
+use std::time::Duration;
+
+pub const HELPER_PACKAGE: &str = "io.appium.settings";
+pub const HELPER_APK: &str = "appium-settings.apk";
+pub const HELPER_DOWNLOAD_URL: &str = "https://github.com/appium/io.appium.settings/releases/latest";
+const HELPER_SERVICE: &str = "io.appium.settings/.LocationService";
+const MOCK_LOCATION_OP: &str = "android:mock_location";
+
+/// Pause between route points that have no timestamps
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(1);
+/// Longest pause between route points, so breaks in a recording are skipped
+const MAX_INTERVAL: Duration = Duration::from_secs(30);
+pub const MIN_SPEED: f32 = 0.5;
+pub const MAX_SPEED: f32 = 20.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Position {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl Position {
+    pub fn is_valid(&self) -> bool {
+        (-90.0..=90.0).contains(&self.latitude) && (-180.0..=180.0).contains(&self.longitude)
+    }
+}
+
+/// Places offered as presets, by name, latitude and longitude
+pub const PRESETS: [(&str, f64, f64); 12] = [
+    ("London", 51.5074, -0.1278),
+    ("Paris", 48.8566, 2.3522),
+    ("Berlin", 52.5200, 13.4050),
+    ("New York", 40.7128, -74.0060),
+    ("San Francisco", 37.7749, -122.4194),
+    ("São Paulo", -23.5505, -46.6333),
+    ("Cairo", 30.0444, 31.2357),
+    ("Mumbai", 19.0760, 72.8777),
+    ("Beijing", 39.9042, 116.4074),
+    ("Tokyo", 35.6762, 139.6503),
+    ("Sydney", -33.8688, 151.2093),
+    ("Null Island", 0.0, 0.0),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoutePoint {
+    pub position: Position,
+    /// Milliseconds since the epoch, when the GPX file has times
+    pub time_ms: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MockLocationInfo {
+    /// Emulators are driven through their console and need no helper
+    pub emulator: bool,
+    pub helper_installed: bool,
+    /// Packages allowed to provide mock locations
+    pub providers: Vec<String>,
+}
+
+pub fn is_emulator(serial: &str) -> bool {
+    serial.starts_with("emulator-")
+}
+
+pub fn read(adb_path: &str, serial: &str) -> Result<MockLocationInfo> {
+    let installed = shell(adb_path, serial, &format!("pm path {}", HELPER_PACKAGE)).unwrap_or_default();
+    let providers = shell(adb_path, serial, &format!("appops query-op {} allow", MOCK_LOCATION_OP))?;
+    Ok(MockLocationInfo {
+        emulator: is_emulator(serial),
+        helper_installed: installed.starts_with("package:"),
+        providers: parse_providers(&providers),
+    })
+}
+
+/// Allow `package` to provide mock locations, which makes it the mock location app
+pub fn set_provider(adb_path: &str, serial: &str, package: &str) -> Result<()> {
+    if !is_package_name(package) {
+        return Err(anyhow::anyhow!("'{}' is not a package name such as com.example.app", package));
+    }
+    shell(adb_path, serial, &format!("appops set {} {} allow", package, MOCK_LOCATION_OP))?;
+    Ok(())
+}
+
+/// Install the helper app when it is missing and make it the mock location app
+pub fn prepare_helper(adb_path: &str, serial: &str) -> Result<()> {
+    let installed = shell(adb_path, serial, &format!("pm path {}", HELPER_PACKAGE)).unwrap_or_default();
+    if !installed.starts_with("package:") {
+        let apk = crate::utils::find_bundled_file(HELPER_APK).ok_or_else(|| {
+            anyhow::anyhow!("{} not found next to DroidView; get it from {}", HELPER_APK, HELPER_DOWNLOAD_URL)
+        })?;
+        // -g grants the location permissions the service needs
+        let output = adb_command(adb_path).args(["-s", serial, "install", "-r", "-g"]).arg(&apk).output()?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+        }
+    }
+    set_provider(adb_path, serial, HELPER_PACKAGE)
+}
+
+/// Report `position` as the device's location until it is set again or stopped
+pub fn set(adb_path: &str, serial: &str, position: Position) -> Result<()> {
+    if !position.is_valid() {
+        return Err(anyhow::anyhow!("{}, {} is not a position on Earth", position.latitude, position.longitude));
+    }
+    if is_emulator(serial) {
+        // The console takes longitude first
+        let (longitude, latitude) = (position.longitude.to_string(), position.latitude.to_string());
+        let output = adb_command(adb_path)
+            .args(["-s", serial, "emu", "geo", "fix", &longitude, &latitude])
+            .output()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if !output.status.success() || stdout.contains("KO") {
+            return Err(anyhow::anyhow!("The emulator refused the location: {}", stdout.trim()));
+        }
+        return Ok(());
+    }
+    // Background services have to be started in the foreground from Android 8
+    let result = shell(adb_path, serial, &helper_command("start-foreground-service", position))
+        .or_else(|_| shell(adb_path, serial, &helper_command("startservice", position)))?;
+    if result.contains("Error") {
+        return Err(anyhow::anyhow!("{}; is the helper app installed?", result));
+    }
+    Ok(())
+}
+
+/// Stop the helper publishing; an emulator keeps its last fix
+pub fn stop(adb_path: &str, serial: &str) -> Result<()> {
+    if !is_emulator(serial) {
+        shell(adb_path, serial, &format!("am stopservice {}", HELPER_SERVICE))?;
+    }
+    Ok(())
+}
+
+pub fn helper_command(am: &str, position: Position) -> String {
+    format!(
+        "am {} --user 0 -n {} --es longitude {} --es latitude {}",
+        am, HELPER_SERVICE, position.longitude, position.latitude
+    )
+}
+
+/// Set each point of `route` in turn, keeping the recorded pace sped up by `speed`, and report the
+/// index of each point once it is set; stops early once `cancel` is set
+pub fn play_route(
+    adb_path: &str,
+    serial: &str,
+    route: &[RoutePoint],
+    speed: f32,
+    cancel: &AtomicBool,
+    mut on_point: impl FnMut(usize),
+) -> Result<()> {
+    for (index, (point, delay)) in route.iter().zip(route_delays(route, speed)).enumerate() {
+        if !sleep_unless_cancelled(delay, cancel) {
+            return Err(anyhow::anyhow!("Stopped"));
+        }
+        set(adb_path, serial, point.position)?;
+        on_point(index);
+    }
+    Ok(())
+}
+
+/// Pause before each point: the recorded time since the previous one, sped up by `speed`
+pub fn route_delays(route: &[RoutePoint], speed: f32) -> Vec<Duration> {
+    let speed = speed.clamp(MIN_SPEED, MAX_SPEED);
+    let mut delays = vec![Duration::ZERO; route.len().min(1)];
+    for pair in route.windows(2) {
+        let recorded = match (pair[0].time_ms, pair[1].time_ms) {
+            (Some(from), Some(to)) if to > from => Duration::from_millis((to - from) as u64),
+            _ => DEFAULT_INTERVAL,
+        };
+        delays.push(recorded.min(MAX_INTERVAL).div_f32(speed));
+    }
+    delays
+}
+
+/// Track points of a GPX file, or its route points when it has no track
+pub fn parse_gpx(text: &str) -> Result<Vec<RoutePoint>> {
+    let mut reader = Reader::from_str(text);
+    let mut track = Vec::new();
+    let mut route = Vec::new();
+    // The open `trkpt` or `rtept`, whether it is a track point, and the text of its open `time`
+    let mut point: Option<(RoutePoint, bool)> = None;
+    let mut time: Option<String> = None;
+    loop {
+        match reader.read_event()? {
+            Event::Start(element) if is_point(&element) => {
+                let number = track.len() + route.len() + 1;
+                point = Some((gpx_point(&element, number)?, element.local_name().as_ref() == b"trkpt"));
+            }
+            Event::Empty(element) if is_point(&element) => {
+                let number = track.len() + route.len() + 1;
+                let read = gpx_point(&element, number)?;
+                match element.local_name().as_ref() {
+                    b"trkpt" => track.push(read),
+                    _ => route.push(read),
+                }
+            }
+            Event::Start(element) if point.is_some() && element.local_name().as_ref() == b"time" => {
+                time = Some(String::new());
+            }
+            Event::Text(text) if time.is_some() => time.get_or_insert_default().push_str(&text.unescape()?),
+            Event::CData(data) if time.is_some() => time.get_or_insert_default().push_str(&data.decode()?),
+            Event::End(element) if element.local_name().as_ref() == b"time" => {
+                if let (Some((read, _)), Some(text)) = (point.as_mut(), time.take()) {
+                    read.time_ms = chrono::DateTime::parse_from_rfc3339(text.trim())
+                        .ok()
+                        .map(|time| time.timestamp_millis());
+                }
+            }
+            Event::End(element) if matches!(element.local_name().as_ref(), b"trkpt" | b"rtept") => {
+                match point.take() {
+                    Some((read, true)) => track.push(read),
+                    Some((read, false)) => route.push(read),
+                    None => {}
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    [track, route]
+        .into_iter()
+        .find(|points| !points.is_empty())
+        .ok_or_else(|| anyhow::anyhow!("The file has no track or route points"))
+}
+
+fn is_point(element: &BytesStart) -> bool {
+    matches!(element.local_name().as_ref(), b"trkpt" | b"rtept")
+}
+
+/// Position of the `number`th point, read from its `lat` and `lon` attributes
+fn gpx_point(element: &BytesStart, number: usize) -> Result<RoutePoint> {
+    let attributes = xml_attributes(element)?;
+    let coordinate = |name: &str| -> Result<f64> {
+        attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .and_then(|(_, value)| value.trim().parse().ok())
+            .ok_or_else(|| anyhow::anyhow!("Point {} has no valid {}", number, name))
+    };
+    let position = Position { latitude: coordinate("lat")?, longitude: coordinate("lon")? };
+    Ok(RoutePoint { position, time_ms: None })
+}
+
+/// Package names printed by `appops query-op`, one a line
+pub fn parse_providers(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| is_package_name(line))
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GPX: &str = r#"<?xml version="1.0"?>
+<gpx version="1.1" creator="test">
+  <trk><name>Walk</name><trkseg>
+    <trkpt lat="52.5200" lon="13.4050"><ele>34</ele><time>2024-05-01T10:00:00Z</time></trkpt>
+    <trkpt lon='13.4060' lat='52.5210'><time><![CDATA[2024-05-01T10:00:04Z]]></time></trkpt>
+    <trkpt lat="52.5220" lon="13.4070"/>
+  </trkseg></trk>
+</gpx>"#;
+
+    #[test]
+    fn parses_gpx_tracks() {
+        let route = parse_gpx(GPX).unwrap();
+        assert_eq!(route.len(), 3);
+        assert_eq!(route[1].position, Position { latitude: 52.521, longitude: 13.406 });
+        assert_eq!(route[1].time_ms.zip(route[0].time_ms).map(|(to, from)| to - from), Some(4000));
+        assert_eq!(route[2].time_ms, None);
+
+        let route = parse_gpx(r#"<gpx><rte><rtept lat="1.5" lon="&#45;2"></rtept></rte></gpx>"#).unwrap();
+        assert_eq!(route[0].position, Position { latitude: 1.5, longitude: -2.0 });
+        assert!(parse_gpx("<gpx></gpx>").is_err());
+        assert!(parse_gpx(r#"<gpx><trkpt lat="x" lon="1"/></gpx>"#).is_err());
+    }
+
+    #[test]
+    fn paces_routes() {
+        let route = parse_gpx(GPX).unwrap();
+        let delays = route_delays(&route, 2.0);
+        assert_eq!(delays, [Duration::ZERO, Duration::from_secs(2), Duration::from_millis(500)]);
+        assert!(route_delays(&[], 1.0).is_empty());
+    }
+
+    #[test]
+    fn builds_helper_commands() {
+        let berlin = Position { latitude: 52.52, longitude: 13.405 };
+        assert_eq!(
+            helper_command("startservice", berlin),
+            "am startservice --user 0 -n io.appium.settings/.LocationService --es longitude 13.405 --es latitude 52.52"
+        );
+        assert!(!Position { latitude: 91.0, longitude: 0.0 }.is_valid());
+        assert_eq!(parse_providers("io.appium.settings\nNo operations.\n"), ["io.appium.settings"]);
+    }
+}
@@ -0,0 +1,75 @@
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+
+/// Per-process CPU use at or above this share of one core counts as saturated
+pub const SATURATION_THRESHOLD: f32 = 90.0;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ProcessUsage {
+    /// Percent of a single core; can exceed 100 on multi-threaded processes
+    pub cpu_percent: f32,
+    pub memory_bytes: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionUsage {
+    pub scrcpy: Option<ProcessUsage>,
+    pub adb: Option<ProcessUsage>,
+}
+
+impl SessionUsage {
+    /// Whether scrcpy is burning a whole core, usually the video decoder falling behind
+    pub fn scrcpy_saturated(&self) -> bool {
+        self.scrcpy
+            .map(|usage| usage.cpu_percent >= SATURATION_THRESHOLD)
+            .unwrap_or(false)
+    }
+}
+
+/// Samples host CPU and memory of the scrcpy and adb server processes
+///
+/// CPU usage is a delta between refreshes, so keep one monitor alive and sample it periodically.
+pub struct ResourceMonitor {
+    system: System,
+}
+
+impl Default for ResourceMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResourceMonitor {
+    pub fn new() -> Self {
+        Self { system: System::new() }
+    }
+
+    pub fn sample(&mut self) -> SessionUsage {
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing().with_cpu().with_memory(),
+        );
+
+        SessionUsage {
+            scrcpy: self.usage_of("scrcpy"),
+            adb: self.usage_of("adb"),
+        }
+    }
+
+    /// Sum usage over every process with this executable name (with or without `.exe`)
+    fn usage_of(&self, name: &str) -> Option<ProcessUsage> {
+        let exe_name = format!("{}.exe", name);
+        let mut total: Option<ProcessUsage> = None;
+
+        for process in self.system.processes().values() {
+            let process_name = process.name().to_string_lossy();
+            if process_name.eq_ignore_ascii_case(name) || process_name.eq_ignore_ascii_case(&exe_name) {
+                let usage = total.get_or_insert_with(ProcessUsage::default);
+                usage.cpu_percent += process.cpu_usage();
+                usage.memory_bytes += process.memory();
+            }
+        }
+
+        total
+    }
+}
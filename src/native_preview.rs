@@ -0,0 +1,352 @@
+//! Experimental: the device screen inside DroidView, without the scrcpy client
+//!
+//! DroidView pushes the scrcpy server that came with scrcpy and starts it the way the client
+//! does, with the video socket forwarded through adb. After a dummy byte, the server sends the
+//! device name and a codec header, then the raw H.264 stream. ffmpeg decodes that stream and
+//! writes every frame as an uncompressed BMP, which carries its own size, so rotations need no
+//! special handling. The server only accepts a client of its exact version, so the version of
+//! the configured scrcpy is passed along with its server. The preview is view-only: input and
+//! audio stay with the scrcpy window.
+
+use crate::bridge::adb_command;
+use anyhow::Result;
+use image::RgbaImage;
+use std::io::{Read, Write};
+use std::net::{Shutdown, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub const SERVER_FILE: &str = "scrcpy-server";
+const DEVICE_PATH: &str = "/data/local/tmp/droidview-scrcpy-server.jar";
+/// Longer side of the mirrored screen, which keeps decoding and uploading cheap
+pub const MAX_SIZE: u32 = 1024;
+/// How long the server gets to start listening, in tries a tenth of a second apart
+const CONNECT_ATTEMPTS: u32 = 50;
+const DEVICE_NAME_LENGTH: usize = 64;
+/// `h264` in ASCII, as the codec header names it
+const CODEC_H264: u32 = 0x6832_3634;
+
+/// The scrcpy server matching `scrcpy_path`: where `SCRCPY_SERVER_PATH` points, else next to it,
+/// in the `share/scrcpy` of its prefix, or shipped with DroidView
+pub fn locate_server(scrcpy_path: &str) -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os("SCRCPY_SERVER_PATH").map(PathBuf::from).filter(|path| path.is_file()) {
+        return Some(path);
+    }
+    let scrcpy = Path::new(scrcpy_path);
+    let scrcpy = if scrcpy.components().count() > 1 { Some(scrcpy.to_path_buf()) } else { crate::utils::find_scrcpy() };
+    let scrcpy = scrcpy.map(|path| path.canonicalize().unwrap_or(path));
+    if let Some(bin) = scrcpy.as_deref().and_then(Path::parent) {
+        let beside = bin.join(SERVER_FILE);
+        if beside.is_file() {
+            return Some(beside);
+        }
+        if let Some(prefix) = bin.parent() {
+            let shared = prefix.join("share").join("scrcpy").join(SERVER_FILE);
+            if shared.is_file() {
+                return Some(shared);
+            }
+        }
+    }
+    crate::utils::find_bundled_file(SERVER_FILE)
+}
+
+/// Arguments after the server class: the version, then `key=value` options
+pub fn server_args(version: &str, scid: u32, max_size: u32) -> Vec<String> {
+    let mut args = vec![
+        version.to_string(),
+        format!("scid={:08x}", scid),
+        "log_level=info".to_string(),
+        "video_codec=h264".to_string(),
+        format!("max_size={}", max_size),
+    ];
+    for flag in [
+        "audio=false",
+        "control=false",
+        "tunnel_forward=true",
+        "send_device_meta=true",
+        "send_frame_meta=false",
+        "send_dummy_byte=true",
+        "send_codec_meta=true",
+        "cleanup=true",
+    ] {
+        args.push(flag.to_string());
+    }
+    args
+}
+
+/// A frame as ffmpeg writes it with `-c:v bmp -pix_fmt bgr24`, or with 32-bit pixels
+pub fn parse_bmp(data: &[u8]) -> Option<RgbaImage> {
+    let u32_at = |offset: usize| data.get(offset..offset + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]));
+    let u16_at = |offset: usize| data.get(offset..offset + 2).map(|b| u16::from_le_bytes([b[0], b[1]]));
+    if !data.starts_with(b"BM") {
+        return None;
+    }
+    let pixels = u32_at(10)? as usize;
+    let width = u32_at(18)? as i32;
+    let height = u32_at(22)? as i32;
+    let bits = u16_at(28)?;
+    let compression = u32_at(30)?;
+    let bytes = match (bits, compression) {
+        (24, 0) => 3,
+        (32, 0 | 3) => 4,
+        _ => return None,
+    };
+    if width <= 0 || height == 0 {
+        return None;
+    }
+    let (width, rows) = (width as u32, height.unsigned_abs());
+    let stride = (width as usize * bytes).div_ceil(4) * 4;
+    if data.len() < pixels + stride * rows as usize {
+        return None;
+    }
+    // Rows run bottom-up unless the height is negative
+    let bottom_up = height > 0;
+    Some(RgbaImage::from_fn(width, rows, |x, y| {
+        let row = if bottom_up { rows - 1 - y } else { y };
+        let start = pixels + row as usize * stride + x as usize * bytes;
+        let [blue, green, red] = [data[start], data[start + 1], data[start + 2]];
+        image::Rgba([red, green, blue, 255])
+    }))
+}
+
+/// The scrcpy server running on a device and ffmpeg decoding what it sends
+pub struct PreviewSession {
+    pub serial: String,
+    pub device_name: String,
+    adb_path: String,
+    port: u16,
+    server: Child,
+    decoder: Child,
+    socket: TcpStream,
+    frame: Arc<Mutex<Option<RgbaImage>>>,
+    frames: Arc<AtomicU64>,
+    running: Arc<AtomicBool>,
+}
+
+impl std::fmt::Debug for PreviewSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PreviewSession").field("serial", &self.serial).field("port", &self.port).finish()
+    }
+}
+
+impl PreviewSession {
+    /// Push and start the server, connect to it and start decoding
+    pub fn start(adb_path: &str, serial: &str, server: &Path, version: &str, ffmpeg: &str) -> Result<Self> {
+        let output = adb_command(adb_path).args(["-s", serial, "push"]).arg(server).arg(DEVICE_PATH).output()?;
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow::anyhow!("Could not push the scrcpy server: {}", error.trim()));
+        }
+
+        // A 31-bit id, so that several servers on one device have their own socket
+        let scid = (uuid::Uuid::new_v4().as_u128() as u32) & 0x7fff_ffff;
+        let socket_name = format!("localabstract:scrcpy_{:08x}", scid);
+        let output = adb_command(adb_path).args(["-s", serial, "forward", "tcp:0", &socket_name]).output()?;
+        let port: u16 = String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse()
+            .map_err(|_| anyhow::anyhow!("adb forward failed: {}", String::from_utf8_lossy(&output.stderr).trim()))?;
+        let remove_forward = || {
+            let forward = format!("tcp:{}", port);
+            let _ = adb_command(adb_path).args(["-s", serial, "forward", "--remove", &forward]).status();
+        };
+
+        let mut server = match adb_command(adb_path)
+            .args(["-s", serial, "shell", &format!("CLASSPATH={}", DEVICE_PATH), "app_process", "/"])
+            .arg("com.genymobile.scrcpy.Server")
+            .args(server_args(version, scid, MAX_SIZE))
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        {
+            Ok(child) => child,
+            Err(e) => {
+                remove_forward();
+                return Err(e.into());
+            }
+        };
+        let fail = |server: &mut Child, error: anyhow::Error| {
+            let _ = server.kill();
+            let _ = server.wait();
+            remove_forward();
+            Err(error)
+        };
+
+        let (socket, device_name, (codec, width, height)) = match connect(port, &mut server) {
+            Ok(connected) => connected,
+            Err(e) => return fail(&mut server, e),
+        };
+        if codec != CODEC_H264 {
+            return fail(&mut server, anyhow::anyhow!("The scrcpy server sent codec {:08x}, not H.264", codec));
+        }
+        tracing::info!("Native preview of {} ({}x{}) on port {}", device_name, width, height, port);
+
+        let decoder = Command::new(ffmpeg)
+            .args(["-loglevel", "error", "-fflags", "nobuffer", "-flags", "low_delay"])
+            .args(["-probesize", "32", "-analyzeduration", "0", "-f", "h264", "-i", "pipe:0"])
+            .args(["-f", "image2pipe", "-c:v", "bmp", "-pix_fmt", "bgr24", "pipe:1"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn();
+        let mut decoder = match decoder {
+            Ok(child) => child,
+            Err(e) => return fail(&mut server, anyhow::anyhow!("Could not start ffmpeg: {}", e)),
+        };
+
+        let frame = Arc::new(Mutex::new(None));
+        let frames = Arc::new(AtomicU64::new(0));
+        let running = Arc::new(AtomicBool::new(true));
+        let (Some(mut input), Some(mut output), Ok(mut stream)) =
+            (decoder.stdin.take(), decoder.stdout.take(), socket.try_clone())
+        else {
+            let _ = decoder.kill();
+            return fail(&mut server, anyhow::anyhow!("Could not connect ffmpeg to the stream"));
+        };
+
+        // The stream into ffmpeg; closing its input lets it finish
+        let pump_running = running.clone();
+        std::thread::spawn(move || {
+            let _ = std::io::copy(&mut stream, &mut input);
+            let _ = input.flush();
+            pump_running.store(false, Ordering::Relaxed);
+        });
+        // Decoded frames out of it, keeping only the newest
+        let (latest, count, reader_running) = (frame.clone(), frames.clone(), running.clone());
+        std::thread::spawn(move || {
+            let mut header = [0u8; 14];
+            while output.read_exact(&mut header).is_ok() {
+                let size = u32::from_le_bytes([header[2], header[3], header[4], header[5]]) as usize;
+                let mut data = header.to_vec();
+                data.resize(size.max(header.len()), 0);
+                if output.read_exact(&mut data[header.len()..]).is_err() {
+                    break;
+                }
+                if let Some(image) = parse_bmp(&data) {
+                    if let Ok(mut latest) = latest.lock() {
+                        *latest = Some(image);
+                    }
+                    count.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            reader_running.store(false, Ordering::Relaxed);
+        });
+        socket.set_read_timeout(None)?;
+
+        Ok(Self {
+            serial: serial.to_string(),
+            device_name,
+            adb_path: adb_path.to_string(),
+            port,
+            server,
+            decoder,
+            socket,
+            frame,
+            frames,
+            running,
+        })
+    }
+
+    /// The newest frame, once
+    pub fn take_frame(&self) -> Option<RgbaImage> {
+        self.frame.lock().ok().and_then(|mut frame| frame.take())
+    }
+
+    /// Frames decoded so far
+    pub fn frame_count(&self) -> u64 {
+        self.frames.load(Ordering::Relaxed)
+    }
+
+    /// Whether the stream is still coming in
+    pub fn is_running(&mut self) -> bool {
+        self.running.load(Ordering::Relaxed) && matches!(self.decoder.try_wait(), Ok(None))
+    }
+
+    pub fn stop(mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        let _ = self.socket.shutdown(Shutdown::Both);
+        for child in [&mut self.decoder, &mut self.server] {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        let _ = adb_command(&self.adb_path)
+            .args(["-s", &self.serial, "forward", "--remove", &format!("tcp:{}", self.port)])
+            .status();
+    }
+}
+
+/// Connect once the server listens, and read the device name and the codec header
+fn connect(port: u16, server: &mut Child) -> Result<(TcpStream, String, (u32, u32, u32))> {
+    // adb accepts the connection before the server listens, then closes it: the dummy byte
+    // tells the two apart
+    let mut connected = None;
+    for _ in 0..CONNECT_ATTEMPTS {
+        if let Ok(Some(status)) = server.try_wait() {
+            return Err(anyhow::anyhow!("The scrcpy server exited ({}); is it the same version as scrcpy?", status));
+        }
+        if let Ok(mut socket) = TcpStream::connect(("127.0.0.1", port)) {
+            socket.set_read_timeout(Some(Duration::from_secs(5)))?;
+            let mut dummy = [0u8; 1];
+            if socket.read_exact(&mut dummy).is_ok() {
+                connected = Some(socket);
+                break;
+            }
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    let mut socket = connected.ok_or_else(|| anyhow::anyhow!("The scrcpy server did not start"))?;
+
+    let mut name = [0u8; DEVICE_NAME_LENGTH];
+    socket.read_exact(&mut name)?;
+    let end = name.iter().position(|byte| *byte == 0).unwrap_or(name.len());
+    let device_name = String::from_utf8_lossy(&name[..end]).to_string();
+    let mut codec = [0u8; 12];
+    socket.read_exact(&mut codec)?;
+    let field = |index: usize| u32::from_be_bytes([codec[index], codec[index + 1], codec[index + 2], codec[index + 3]]);
+    Ok((socket, device_name, (field(0), field(4), field(8))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_server_args() {
+        let args = server_args("3.1", 0x1234abc, 1024);
+        assert_eq!(args[0], "3.1");
+        assert!(args.contains(&"scid=01234abc".to_string()));
+        assert!(args.contains(&"max_size=1024".to_string()));
+        assert!(args.contains(&"tunnel_forward=true".to_string()));
+        assert!(args.contains(&"audio=false".to_string()));
+    }
+
+    #[test]
+    fn parses_bmp_frames() {
+        // 2x2, 24-bit, bottom-up, each row padded from 6 to 8 bytes
+        let mut bmp = b"BM".to_vec();
+        bmp.extend(70u32.to_le_bytes());
+        bmp.extend([0; 4]);
+        bmp.extend(54u32.to_le_bytes());
+        bmp.extend(40u32.to_le_bytes());
+        bmp.extend(2i32.to_le_bytes());
+        bmp.extend(2i32.to_le_bytes());
+        bmp.extend(1u16.to_le_bytes());
+        bmp.extend(24u16.to_le_bytes());
+        bmp.extend([0; 24]);
+        bmp.extend([0, 0, 255, 0, 255, 0, 0, 0]);
+        bmp.extend([255, 0, 0, 255, 255, 255, 0, 0]);
+        let image = parse_bmp(&bmp).unwrap();
+        assert_eq!(image.dimensions(), (2, 2));
+        assert_eq!(image.get_pixel(0, 0).0, [0, 0, 255, 255]);
+        assert_eq!(image.get_pixel(1, 0).0, [255, 255, 255, 255]);
+        assert_eq!(image.get_pixel(0, 1).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(1, 1).0, [0, 255, 0, 255]);
+
+        assert!(parse_bmp(&bmp[..60]).is_none());
+        assert!(parse_bmp(b"PNG").is_none());
+    }
+}
@@ -0,0 +1,79 @@
+//! Reading the text on the device screen with tesseract
+//!
+//! The screenshot goes to a temporary file that tesseract reads and prints the text of. Phone
+//! screens are small for their pixel count, so text comes out well without any preprocessing.
+
+use crate::automation::screencap;
+use anyhow::Result;
+use std::path::PathBuf;
+use std::process::Command;
+
+pub const INSTALL_URL: &str = "https://tesseract-ocr.github.io/tessdoc/Installation.html";
+
+/// tesseract on the PATH, or where its Windows installer puts it
+pub fn find_tesseract() -> Option<PathBuf> {
+    #[cfg(target_os = "windows")]
+    {
+        for dir in ["C:\\Program Files\\Tesseract-OCR", "C:\\Program Files (x86)\\Tesseract-OCR"] {
+            let path = PathBuf::from(dir).join("tesseract.exe");
+            if path.is_file() {
+                return Some(path);
+            }
+        }
+    }
+    crate::utils::find_executable("tesseract")
+}
+
+/// The text on the screen of `serial`
+pub fn extract(adb_path: &str, serial: &str) -> Result<String> {
+    let tesseract = find_tesseract()
+        .ok_or_else(|| anyhow::anyhow!("Text recognition needs tesseract; see {} to install it", INSTALL_URL))?;
+    let png = screencap(adb_path, serial)?;
+    let file_name = format!("droidview_ocr_{}.png", serial.replace(|c: char| !c.is_ascii_alphanumeric(), "_"));
+    let path = std::env::temp_dir().join(file_name);
+    std::fs::write(&path, png)?;
+    let output = Command::new(&tesseract).arg(&path).arg("stdout").output();
+    let _ = std::fs::remove_file(&path);
+    let output = output?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!("tesseract failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    let text = clean_text(&String::from_utf8_lossy(&output.stdout));
+    if text.is_empty() {
+        return Err(anyhow::anyhow!("No text found on the screen"));
+    }
+    Ok(text)
+}
+
+/// Trailing spaces, page breaks and runs of blank lines removed
+pub fn clean_text(raw: &str) -> String {
+    let mut text = String::new();
+    let mut blank = false;
+    for line in raw.replace('\u{c}', "").lines().map(str::trim_end) {
+        if line.trim().is_empty() {
+            blank = !text.is_empty();
+            continue;
+        }
+        if blank {
+            text.push('\n');
+            blank = false;
+        }
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        text.push_str(line);
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cleans_recognized_text() {
+        let raw = "\n12:30  \n\n\n\nUnfortunately, App has stopped.\nOK\n\n\u{c}";
+        assert_eq!(clean_text(raw), "12:30\n\nUnfortunately, App has stopped.\nOK");
+        assert_eq!(clean_text("  \n\u{c}"), "");
+    }
+}
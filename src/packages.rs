@@ -0,0 +1,551 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use crate::bridge::adb_command;
+
+/// An installed package as reported by `dumpsys package packages`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PackageInfo {
+    pub package: String,
+    pub system: bool,
+    pub enabled: bool,
+    pub code_path: String,
+    pub first_install_time: Option<String>,
+    pub last_update_time: Option<String>,
+    pub size_kb: Option<u64>,
+}
+
+impl PackageInfo {
+    pub fn new(package: &str) -> Self {
+        Self {
+            package: package.to_string(),
+            enabled: true,
+            ..Default::default()
+        }
+    }
+}
+
+/// A user or profile on the device, as listed by `pm list users`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeviceUser {
+    pub id: u32,
+    pub name: String,
+    pub running: bool,
+    /// A managed (work) profile rather than a full user
+    pub work_profile: bool,
+}
+
+impl DeviceUser {
+    pub fn label(&self) -> String {
+        if self.work_profile {
+            format!("{} ({}, work profile)", self.name, self.id)
+        } else {
+            format!("{} ({})", self.name, self.id)
+        }
+    }
+}
+
+/// `UserInfo` flag of managed profiles
+const FLAG_MANAGED_PROFILE: u32 = 0x20;
+
+/// Letters, digits and underscores in at least two dot-separated parts, as in `com.example.app`
+pub fn is_package_name(package: &str) -> bool {
+    package.split('.').count() >= 2
+        && package.split('.').all(|part| {
+            part.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+                && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+}
+
+/// ` --user <id>` for pm and am commands scoped to a user, empty for the default
+pub fn user_flag(user: Option<u32>) -> String {
+    user.map(|id| format!(" --user {}", id)).unwrap_or_default()
+}
+
+/// Users and profiles on the device
+pub fn list_users(adb_path: &str, device_id: &str) -> Result<Vec<DeviceUser>> {
+    let output = adb_command(adb_path)
+        .args(["-s", device_id, "shell", "pm", "list", "users"])
+        .output()?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!("Failed to list users"));
+    }
+    Ok(parse_users(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse lines like `UserInfo{10:Work profile:1030} running`
+pub fn parse_users(output: &str) -> Vec<DeviceUser> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (_, rest) = line.split_once("UserInfo{")?;
+            let (info, state) = rest.split_once('}')?;
+            let (id, rest) = info.split_once(':')?;
+            // Names may contain colons, the flags never do
+            let (name, flags) = rest.rsplit_once(':')?;
+            let flags = u32::from_str_radix(flags.trim(), 16).unwrap_or(0);
+            Some(DeviceUser {
+                id: id.trim().parse().ok()?,
+                name: name.to_string(),
+                running: state.contains("running"),
+                work_profile: flags & FLAG_MANAGED_PROFILE != 0,
+            })
+        })
+        .collect()
+}
+
+/// List every package installed for `user` (the default user when `None`) with flags,
+/// install times and code size
+pub fn list_packages(adb_path: &str, device_id: &str, user: Option<u32>) -> Result<Vec<PackageInfo>> {
+    let output = adb_command(adb_path)
+        .args(["-s", device_id, "shell", "dumpsys", "package", "packages"])
+        .output()?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!("Failed to execute dumpsys package"));
+    }
+
+    let mut packages = parse_dumpsys_packages(&String::from_utf8_lossy(&output.stdout), user.unwrap_or(0));
+
+    // Older Androids may not print a usable dump; fall back to the plain package list
+    if packages.is_empty() {
+        packages = list_packages_fallback(adb_path, device_id, user)?;
+    }
+
+    fill_sizes(adb_path, device_id, &mut packages);
+    packages.sort_by(|a, b| a.package.cmp(&b.package));
+    Ok(packages)
+}
+
+fn list_packages_fallback(adb_path: &str, device_id: &str, user: Option<u32>) -> Result<Vec<PackageInfo>> {
+    let list = |flag: &str| -> Result<Vec<String>> {
+        let command = format!("pm list packages{} {}", user_flag(user), flag);
+        let output = adb_command(adb_path)
+            .args(["-s", device_id, "shell", command.trim_end()])
+            .output()?;
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.strip_prefix("package:"))
+            .map(|p| p.trim().to_string())
+            .collect())
+    };
+
+    let system = list("-s")?;
+    let disabled = list("-d")?;
+    let all = list("")?;
+
+    Ok(all
+        .iter()
+        .map(|package| PackageInfo {
+            system: system.contains(package),
+            enabled: !disabled.contains(package),
+            ..PackageInfo::new(package)
+        })
+        .collect())
+}
+
+/// Code sizes from `dumpsys diskstats`, which unlike `du` on the code paths needs no root;
+/// packages the last storage scan has not seen stay without a size
+fn fill_sizes(adb_path: &str, device_id: &str, packages: &mut [PackageInfo]) {
+    let Ok(output) = adb_command(adb_path)
+        .args(["-s", device_id, "shell", "dumpsys", "diskstats"])
+        .output()
+    else {
+        return;
+    };
+    let sizes = parse_diskstats_sizes(&String::from_utf8_lossy(&output.stdout), "App Sizes:");
+    for package in packages.iter_mut() {
+        package.size_kb = sizes.get(&package.package).map(|bytes| bytes / 1024);
+    }
+}
+
+/// Parse the `Packages:` section of `dumpsys package packages`, keeping the packages
+/// installed for `user` with their enabled state for that user
+pub fn parse_dumpsys_packages(output: &str, user: u32) -> Vec<PackageInfo> {
+    let mut packages: Vec<PackageInfo> = Vec::new();
+    let mut current: Option<PackageInfo> = None;
+    let mut installed = true;
+    let mut in_packages = false;
+    let user_prefix = format!("User {}:", user);
+
+    for line in output.lines() {
+        if line.trim_end() == "Packages:" {
+            in_packages = true;
+            continue;
+        }
+        if !in_packages {
+            continue;
+        }
+        // Any other top-level section ends the package list
+        if !line.starts_with(' ') && !line.trim().is_empty() {
+            break;
+        }
+
+        let trimmed = line.trim();
+        if let Some(rest) = trimmed.strip_prefix("Package [") {
+            if let Some(done) = current.take()
+                && installed
+            {
+                packages.push(done);
+            }
+            let name = rest.split(']').next().unwrap_or_default();
+            current = Some(PackageInfo::new(name));
+            installed = true;
+            continue;
+        }
+
+        let Some(package) = current.as_mut() else {
+            continue;
+        };
+
+        if let Some(path) = trimmed.strip_prefix("codePath=") {
+            package.code_path = path.to_string();
+        } else if let Some(flags) = trimmed.strip_prefix("flags=[") {
+            package.system = flags.split_whitespace().any(|f| f == "SYSTEM");
+        } else if let Some(time) = trimmed.strip_prefix("firstInstallTime=") {
+            package.first_install_time = Some(time.to_string());
+        } else if let Some(time) = trimmed.strip_prefix("lastUpdateTime=") {
+            package.last_update_time = Some(time.to_string());
+        } else if trimmed.starts_with(&user_prefix) {
+            let field = |name: &str| trimmed.split_whitespace().find_map(|field| field.strip_prefix(name));
+            // enabled=0 (default) and 1 mean enabled, 2-4 are the disabled states
+            if let Some(state) = field("enabled=") {
+                package.enabled = matches!(state, "0" | "1");
+            }
+            if field("installed=") == Some("false") {
+                installed = false;
+            }
+        }
+    }
+
+    if let Some(done) = current
+        && installed
+    {
+        packages.push(done);
+    }
+    packages
+}
+
+/// Permissions whose grant exposes personal data or sensors, highlighted in the editor
+pub const DANGEROUS_PERMISSIONS: &[&str] = &[
+    "android.permission.ACCESS_FINE_LOCATION",
+    "android.permission.ACCESS_COARSE_LOCATION",
+    "android.permission.ACCESS_BACKGROUND_LOCATION",
+    "android.permission.CAMERA",
+    "android.permission.RECORD_AUDIO",
+    "android.permission.READ_CONTACTS",
+    "android.permission.WRITE_CONTACTS",
+    "android.permission.GET_ACCOUNTS",
+    "android.permission.READ_CALENDAR",
+    "android.permission.WRITE_CALENDAR",
+    "android.permission.READ_SMS",
+    "android.permission.SEND_SMS",
+    "android.permission.RECEIVE_SMS",
+    "android.permission.READ_CALL_LOG",
+    "android.permission.WRITE_CALL_LOG",
+    "android.permission.READ_PHONE_STATE",
+    "android.permission.READ_PHONE_NUMBERS",
+    "android.permission.CALL_PHONE",
+    "android.permission.BODY_SENSORS",
+    "android.permission.READ_EXTERNAL_STORAGE",
+    "android.permission.WRITE_EXTERNAL_STORAGE",
+    "android.permission.READ_MEDIA_IMAGES",
+    "android.permission.READ_MEDIA_VIDEO",
+    "android.permission.READ_MEDIA_AUDIO",
+];
+
+/// A runtime permission of a package for user 0
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuntimePermission {
+    pub name: String,
+    pub granted: bool,
+    pub flags: Vec<String>,
+}
+
+impl RuntimePermission {
+    pub fn is_dangerous(&self) -> bool {
+        DANGEROUS_PERMISSIONS.contains(&self.name.as_str())
+    }
+
+    /// Fixed by the system or device policy, so `pm grant`/`pm revoke` will be refused
+    pub fn is_fixed(&self) -> bool {
+        self.flags
+            .iter()
+            .any(|flag| flag == "SYSTEM_FIXED" || flag == "POLICY_FIXED")
+    }
+}
+
+/// Read the runtime permissions of one package via `dumpsys package <pkg>`
+pub fn get_runtime_permissions(adb_path: &str, device_id: &str, package: &str) -> Result<Vec<RuntimePermission>> {
+    let output = adb_command(adb_path)
+        .args(["-s", device_id, "shell", "dumpsys", "package", package])
+        .output()?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!("Failed to execute dumpsys package {}", package));
+    }
+
+    Ok(parse_runtime_permissions(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Grant or revoke a runtime permission with `pm grant` / `pm revoke`
+pub fn set_permission(adb_path: &str, device_id: &str, package: &str, permission: &str, grant: bool) -> Result<()> {
+    let verb = if grant { "grant" } else { "revoke" };
+    let output = adb_command(adb_path)
+        .args(["-s", device_id, "shell", "pm", verb, package, permission])
+        .output()?;
+
+    // pm prints nothing on success and an exception message on failure
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let message = stderr.lines().chain(stdout.lines()).find(|line| !line.trim().is_empty());
+    match message {
+        Some(message) if !output.status.success() || message.contains("Exception") => {
+            Err(anyhow::anyhow!(message.trim().to_string()))
+        }
+        _ if !output.status.success() => Err(anyhow::anyhow!("pm {} failed", verb)),
+        _ => Ok(()),
+    }
+}
+
+/// Parse the first `runtime permissions:` block (user 0) of `dumpsys package <pkg>`
+pub fn parse_runtime_permissions(output: &str) -> Vec<RuntimePermission> {
+    let mut permissions = Vec::new();
+    let mut header_indent: Option<usize> = None;
+
+    for line in output.lines() {
+        let indent = line.len() - line.trim_start().len();
+        let trimmed = line.trim();
+
+        match header_indent {
+            None => {
+                if trimmed == "runtime permissions:" {
+                    header_indent = Some(indent);
+                }
+            }
+            Some(header) => {
+                if indent <= header || trimmed.is_empty() {
+                    break;
+                }
+                // android.permission.CAMERA: granted=false, flags=[ USER_SET|USER_FIXED]
+                let Some((name, rest)) = trimmed.split_once(": ") else {
+                    continue;
+                };
+                let granted = rest.contains("granted=true");
+                let flags = rest
+                    .split_once("flags=[")
+                    .map(|(_, flags)| {
+                        flags
+                            .trim_end_matches(']')
+                            .split('|')
+                            .map(|flag| flag.trim().to_string())
+                            .filter(|flag| !flag.is_empty())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                permissions.push(RuntimePermission {
+                    name: name.to_string(),
+                    granted,
+                    flags,
+                });
+            }
+        }
+    }
+
+    permissions
+}
+
+/// Version, install and size details of one package
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AppDetails {
+    pub package: String,
+    pub version_name: Option<String>,
+    pub version_code: Option<String>,
+    pub min_sdk: Option<u32>,
+    pub target_sdk: Option<u32>,
+    pub first_install_time: Option<String>,
+    pub last_update_time: Option<String>,
+    pub installer: Option<String>,
+    pub apk_paths: Vec<String>,
+    pub apk_size_bytes: Option<u64>,
+    pub data_size_bytes: Option<u64>,
+}
+
+/// Gather details for one package from `dumpsys package`, `pm path` and `dumpsys diskstats`
+pub fn get_app_details(adb_path: &str, device_id: &str, package: &str) -> Result<AppDetails> {
+    let shell = |args: &[&str]| -> Result<String> {
+        let output = adb_command(adb_path)
+            .args(["-s", device_id, "shell"])
+            .args(args)
+            .output()?;
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    };
+
+    let dumpsys = shell(&["dumpsys", "package", package])?;
+    let mut details = parse_app_details(&dumpsys, package);
+    if details.version_name.is_none() && details.version_code.is_none() {
+        return Err(anyhow::anyhow!("Package {} not found", package));
+    }
+
+    details.apk_paths = shell(&["pm", "path", package])?
+        .lines()
+        .filter_map(|line| line.strip_prefix("package:"))
+        .map(|path| path.trim().to_string())
+        .collect();
+
+    if !details.apk_paths.is_empty() {
+        let mut args = vec!["stat", "-c", "%s"];
+        args.extend(details.apk_paths.iter().map(String::as_str));
+        let sizes: Vec<u64> = shell(&args)?
+            .lines()
+            .filter_map(|line| line.trim().parse().ok())
+            .collect();
+        if !sizes.is_empty() {
+            details.apk_size_bytes = Some(sizes.iter().sum());
+        }
+    }
+
+    // diskstats is readable without root, unlike the app's data directory
+    if let Ok(diskstats) = shell(&["dumpsys", "diskstats"]) {
+        details.data_size_bytes = parse_diskstats_data_size(&diskstats, package);
+    }
+
+    Ok(details)
+}
+
+/// Parse the first `Package [pkg]` block of `dumpsys package <pkg>`
+pub fn parse_app_details(output: &str, package: &str) -> AppDetails {
+    let mut details = AppDetails {
+        package: package.to_string(),
+        ..Default::default()
+    };
+    let header = format!("Package [{}]", package);
+    let mut block_indent: Option<usize> = None;
+
+    for line in output.lines() {
+        let indent = line.len() - line.trim_start().len();
+        let trimmed = line.trim();
+
+        match block_indent {
+            None => {
+                if trimmed.starts_with(&header) {
+                    block_indent = Some(indent);
+                }
+                continue;
+            }
+            Some(header_indent) if indent <= header_indent && !trimmed.is_empty() => break,
+            Some(_) => {}
+        }
+
+        // Values that may contain spaces take the rest of the line
+        if let Some(value) = trimmed.strip_prefix("versionName=") {
+            details.version_name = Some(value.to_string());
+        } else if let Some(value) = trimmed.strip_prefix("firstInstallTime=") {
+            details.first_install_time = Some(value.to_string());
+        } else if let Some(value) = trimmed.strip_prefix("lastUpdateTime=") {
+            details.last_update_time = Some(value.to_string());
+        } else if let Some(value) = trimmed.strip_prefix("installerPackageName=") {
+            if value != "null" {
+                details.installer = Some(value.to_string());
+            }
+        } else if trimmed.starts_with("versionCode=") {
+            // versionCode=123 minSdk=21 targetSdk=34
+            for token in trimmed.split_whitespace() {
+                if let Some((key, value)) = token.split_once('=') {
+                    match key {
+                        "versionCode" => details.version_code = Some(value.to_string()),
+                        "minSdk" => details.min_sdk = value.parse().ok(),
+                        "targetSdk" => details.target_sdk = value.parse().ok(),
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    details
+}
+
+/// Look up a package's data size in `dumpsys diskstats`
+pub fn parse_diskstats_data_size(output: &str, package: &str) -> Option<u64> {
+    parse_diskstats_sizes(output, "App Data Sizes:").get(package).copied()
+}
+
+/// Sizes in bytes by package from one of the arrays of `dumpsys diskstats`, such as
+/// `App Sizes:`, which run parallel to `Package Names:`
+pub fn parse_diskstats_sizes(output: &str, sizes: &str) -> std::collections::HashMap<String, u64> {
+    let array = |prefix: &str| -> Vec<String> {
+        output
+            .lines()
+            .find_map(|line| line.trim().strip_prefix(prefix))
+            .map(|line| {
+                line.trim()
+                    .trim_start_matches('[')
+                    .trim_end_matches(']')
+                    .split(',')
+                    .map(|item| item.trim().trim_matches('"').to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    array("Package Names:")
+        .into_iter()
+        .zip(array(sizes))
+        .filter_map(|(name, size)| Some((name, size.parse().ok()?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn users() {
+        let users = parse_users(
+            "Users:\n\tUserInfo{0:Owner:c13} running\n\tUserInfo{10:Work: Acme:1030} running\n\tUserInfo{11:Guest:404}\n",
+        );
+        assert_eq!(users.len(), 3);
+        assert_eq!(users[0].id, 0);
+        assert!(users[0].running && !users[0].work_profile);
+        assert_eq!(users[1].name, "Work: Acme");
+        assert!(users[1].work_profile);
+        assert!(!users[2].running);
+        assert_eq!(user_flag(Some(10)), " --user 10");
+        assert_eq!(user_flag(None), "");
+    }
+
+    #[test]
+    fn packages_for_a_user() {
+        let dump = "Packages:\n  Package [com.example.mail] (1a2b):\n    codePath=/data/app/mail\n    User 0: ceDataInode=1 installed=true hidden=false enabled=0\n    User 10: ceDataInode=2 installed=true hidden=false enabled=3\n  Package [com.example.chat] (3c4d):\n    codePath=/data/app/chat\n    User 0: ceDataInode=3 installed=true hidden=false enabled=0\n    User 10: ceDataInode=0 installed=false hidden=false enabled=0\n\nShared users:\n";
+
+        let owner = parse_dumpsys_packages(dump, 0);
+        assert_eq!(owner.len(), 2);
+        assert!(owner.iter().all(|package| package.enabled));
+
+        let work = parse_dumpsys_packages(dump, 10);
+        assert_eq!(work.len(), 1);
+        assert_eq!(work[0].package, "com.example.mail");
+        assert!(!work[0].enabled);
+    }
+
+    #[test]
+    fn sizes_from_diskstats() {
+        let diskstats = "Latency: 0ms [512B Data Write]\nPackage Names: [\"com.example.mail\",\"com.example.chat\"]\nApp Sizes: [4096000,20480]\nApp Data Sizes: [1024,2048]\nCache Sizes: [0,0]\n";
+
+        let sizes = parse_diskstats_sizes(diskstats, "App Sizes:");
+        assert_eq!(sizes.get("com.example.mail"), Some(&4096000));
+        assert_eq!(sizes.get("com.example.chat"), Some(&20480));
+        assert_eq!(parse_diskstats_data_size(diskstats, "com.example.chat"), Some(2048));
+        assert_eq!(parse_diskstats_data_size(diskstats, "com.example.camera"), None);
+        assert!(parse_diskstats_sizes("Latency: 0ms\n", "App Sizes:").is_empty());
+    }
+
+    #[test]
+    fn checks_package_names() {
+        assert!(is_package_name("com.example.app"));
+        assert!(is_package_name("org.mozilla.firefox_beta"));
+        assert!(!is_package_name("example"));
+        assert!(!is_package_name("com.example.app; reboot"));
+        assert!(!is_package_name("com..app"));
+    }
+}
@@ -0,0 +1,145 @@
+//! Perfetto traces recorded on the device with `perfetto`'s lightweight command line mode
+//!
+//! The categories are atrace categories plus `sched`, `freq` and the other ftrace groups the
+//! `perfetto` binary expands itself. Traces are written to `/data/misc/perfetto-traces`, the
+//! one place the shell user may write that `traced` can also reach, then pulled and removed.
+
+use crate::bridge::adb_command;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+const REMOTE_DIR: &str = "/data/misc/perfetto-traces";
+/// Where traces are opened; it reads files dropped onto the page
+pub const PERFETTO_UI: &str = "https://ui.perfetto.dev";
+
+/// Category, description, and whether it is on by default
+pub const CATEGORIES: &[(&str, &str, bool)] = &[
+    ("sched", "CPU scheduling", true),
+    ("freq", "CPU frequency", true),
+    ("idle", "CPU idle states", false),
+    ("gfx", "Graphics and SurfaceFlinger", true),
+    ("view", "View system", true),
+    ("input", "Input dispatch", true),
+    ("wm", "Window manager", true),
+    ("am", "Activity manager", true),
+    ("binder_driver", "Binder transactions", false),
+    ("dalvik", "ART runtime and GC", false),
+    ("hal", "Hardware modules", false),
+    ("res", "Resource loading", false),
+    ("camera", "Camera", false),
+    ("audio", "Audio", false),
+    ("video", "Video codecs", false),
+    ("memory", "Memory", false),
+];
+
+pub const MAX_DURATION_SECS: u32 = 120;
+
+/// Arguments after `perfetto` for a trace of `categories` lasting `duration_secs`
+pub fn perfetto_args(categories: &[&str], duration_secs: u32, remote_path: &str) -> Vec<String> {
+    let mut args = vec![
+        "-o".to_string(),
+        remote_path.to_string(),
+        "-t".to_string(),
+        format!("{}s", duration_secs.clamp(1, MAX_DURATION_SECS)),
+        "-b".to_string(),
+        "64mb".to_string(),
+    ];
+    args.extend(categories.iter().map(|category| category.to_string()));
+    args
+}
+
+/// Record a trace on `serial` and pull it into `dir`; returns the saved file.
+/// Blocks for the duration of the trace, or until [`stop`] ends it early.
+pub fn record(
+    adb_path: &str,
+    serial: &str,
+    model: &str,
+    categories: &[&str],
+    duration_secs: u32,
+    dir: &Path,
+) -> Result<PathBuf> {
+    if categories.is_empty() {
+        return Err(anyhow::anyhow!("Choose at least one category"));
+    }
+    let now = chrono::Local::now();
+    let remote_path = format!("{}/droidview-{}.perfetto-trace", REMOTE_DIR, now.format("%Y%m%d-%H%M%S"));
+
+    // traced only runs when enabled on Android 9 and 10; later versions ignore the property.
+    // It is persistent, so the value the device had is put back once perfetto is done
+    let previous = adb_command(adb_path)
+        .args(["-s", serial, "shell", "getprop persist.traced.enable"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+    let enable = previous.as_deref().is_some_and(|value| value != "1");
+    if enable {
+        set_traced_enable(adb_path, serial, "1");
+    }
+    let output = adb_command(adb_path)
+        .args(["-s", serial, "shell", "perfetto"])
+        .args(perfetto_args(categories, duration_secs, &remote_path))
+        .output();
+    if let Some(previous) = previous.filter(|_| enable) {
+        set_traced_enable(adb_path, serial, &previous);
+    }
+    let output = output?;
+    // Stopping early interrupts perfetto, which still writes the trace
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() && !stderr.contains("Trace written") {
+        let reason = stderr
+            .lines()
+            .map(str::trim)
+            .rfind(|line| !line.is_empty())
+            .unwrap_or("perfetto failed");
+        return Err(anyhow::anyhow!(reason.to_string()));
+    }
+
+    std::fs::create_dir_all(dir)?;
+    let file_name =
+        crate::utils::expand_filename_template("trace_{model}_{timestamp}", serial, model, now, "perfetto-trace");
+    let path = crate::utils::unique_path(dir.join(file_name));
+    let pull = adb_command(adb_path)
+        .args(["-s", serial, "pull", &remote_path])
+        .arg(&path)
+        .output()?;
+    let _ = adb_command(adb_path)
+        .args(["-s", serial, "shell", "rm", "-f", &remote_path])
+        .status();
+    if !pull.status.success() {
+        return Err(anyhow::anyhow!(
+            "Pull failed: {}",
+            String::from_utf8_lossy(&pull.stderr).trim()
+        ));
+    }
+    Ok(path)
+}
+
+fn set_traced_enable(adb_path: &str, serial: &str, value: &str) {
+    let _ = adb_command(adb_path)
+        .args(["-s", serial, "shell", &format!("setprop persist.traced.enable {}", crate::intent::shell_quote(value))])
+        .status();
+}
+
+/// End a running trace early; perfetto saves what it has so far
+pub fn stop(adb_path: &str, serial: &str) -> Result<()> {
+    adb_command(adb_path)
+        .args(["-s", serial, "shell", "pkill -INT perfetto"])
+        .status()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_perfetto_arguments() {
+        assert_eq!(
+            perfetto_args(&["sched", "gfx"], 10, "/data/misc/perfetto-traces/t"),
+            ["-o", "/data/misc/perfetto-traces/t", "-t", "10s", "-b", "64mb", "sched", "gfx"]
+        );
+        assert_eq!(perfetto_args(&[], 0, "t")[3], "1s");
+        assert_eq!(perfetto_args(&[], 9999, "t")[3], "120s");
+    }
+}
@@ -0,0 +1,337 @@
+//! Command plugins: folders in `<app dir>/plugins` described by a `plugin.toml`
+//!
+//! A plugin declares commands, each either adb arguments or a program to run, with an optional
+//! form whose values fill `{name}` placeholders in the arguments; `{serial}` is the selected
+//! device. Commands show in the Plugins panel, and with `toolkit = true` also as toolkit buttons.
+//! Values in `shell` and `exec-out` commands are quoted, so each stays one word on the device.
+//!
+//! ```toml
+//! name = "Battery saver"
+//! description = "Toggle battery saver"
+//!
+//! [[command]]
+//! label = "Battery saver"
+//! adb = ["shell", "settings", "put", "global", "low_power", "{enabled}"]
+//! toolkit = true
+//!
+//! [[command.field]]
+//! name = "enabled"
+//! kind = "checkbox"
+//! default = "1"
+//! ```
+//!
+//! Programs run in the plugin folder with `DROIDVIEW_ADB` and `DROIDVIEW_SERIAL` set.
+
+use crate::bridge::adb_command;
+use crate::intent::shell_quote;
+use anyhow::Result;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+pub const MANIFEST: &str = "plugin.toml";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Plugin {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default, rename = "command")]
+    pub commands: Vec<PluginCommand>,
+    /// Folder the manifest was read from
+    #[serde(skip)]
+    pub dir: PathBuf,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PluginCommand {
+    pub label: String,
+    #[serde(default)]
+    pub description: String,
+    /// Arguments for adb after `-s <serial>`
+    #[serde(default)]
+    pub adb: Vec<String>,
+    /// Program and arguments; a relative program is looked for in the plugin folder first
+    #[serde(default)]
+    pub exec: Vec<String>,
+    /// Also show a button in the toolkit, which runs with the form's current values
+    #[serde(default)]
+    pub toolkit: bool,
+    #[serde(default, rename = "field")]
+    pub fields: Vec<PluginField>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PluginField {
+    pub name: String,
+    /// Shown next to the input; the name when empty
+    #[serde(default)]
+    pub label: String,
+    #[serde(default)]
+    pub kind: FieldKind,
+    #[serde(default)]
+    pub default: String,
+    /// Values offered by a `choice` field
+    #[serde(default)]
+    pub options: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldKind {
+    #[default]
+    Text,
+    Number,
+    /// "1" when ticked and "0" when not
+    Checkbox,
+    Choice,
+}
+
+impl PluginField {
+    pub fn label(&self) -> &str {
+        if self.label.is_empty() { &self.name } else { &self.label }
+    }
+}
+
+impl PluginCommand {
+    /// Form values before the user changes anything
+    pub fn defaults(&self) -> HashMap<String, String> {
+        self.fields
+            .iter()
+            .map(|field| {
+                let value = match field.kind {
+                    FieldKind::Choice if field.default.is_empty() => field.options.first().cloned().unwrap_or_default(),
+                    FieldKind::Checkbox if field.default.is_empty() => "0".to_string(),
+                    _ => field.default.clone(),
+                };
+                (field.name.clone(), value)
+            })
+            .collect()
+    }
+}
+
+pub fn plugins_dir() -> Result<PathBuf> {
+    Ok(crate::config::app_dir()?.join("plugins"))
+}
+
+/// Read and check the manifest in `dir`
+pub fn load(dir: &Path) -> Result<Plugin> {
+    let text = std::fs::read_to_string(dir.join(MANIFEST))?;
+    let mut plugin: Plugin = toml::from_str(&text)?;
+    plugin.dir = dir.to_path_buf();
+    validate(&plugin)?;
+    Ok(plugin)
+}
+
+fn validate(plugin: &Plugin) -> Result<()> {
+    if plugin.name.trim().is_empty() {
+        return Err(anyhow::anyhow!("The plugin has no name"));
+    }
+    for command in &plugin.commands {
+        if command.adb.is_empty() == command.exec.is_empty() {
+            return Err(anyhow::anyhow!("Command '{}' needs either `adb` or `exec`", command.label));
+        }
+        for (index, field) in command.fields.iter().enumerate() {
+            if field.name.is_empty() || field.name == "serial" {
+                return Err(anyhow::anyhow!("Command '{}' has a field without a usable name", command.label));
+            }
+            if command.fields[..index].iter().any(|other| other.name == field.name) {
+                return Err(anyhow::anyhow!("Command '{}' has two fields named '{}'", command.label, field.name));
+            }
+            if field.kind == FieldKind::Choice && field.options.is_empty() {
+                return Err(anyhow::anyhow!("Field '{}' is a choice without options", field.name));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Every plugin in `dir` sorted by name, and a message for each folder that could not be loaded
+pub fn discover(dir: &Path) -> (Vec<Plugin>, Vec<String>) {
+    let mut plugins = Vec::new();
+    let mut errors = Vec::new();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return (plugins, errors);
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.join(MANIFEST).is_file() {
+            continue;
+        }
+        match load(&path) {
+            Ok(plugin) => plugins.push(plugin),
+            Err(e) => errors.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+    plugins.sort_by_key(|plugin| plugin.name.to_lowercase());
+    (plugins, errors)
+}
+
+/// Fill `{serial}` and `{field}` placeholders in one pass over each argument, so a value that
+/// contains a placeholder is left as typed; unknown ones stay as they are. With `quote` every
+/// value is quoted for the device shell.
+pub fn expand(args: &[String], serial: &str, values: &HashMap<String, String>, quote: bool) -> Vec<String> {
+    let value = |name: &str| {
+        let value = if name == "serial" { Some(serial) } else { values.get(name).map(String::as_str) }?;
+        Some(if quote { shell_quote(value) } else { value.to_string() })
+    };
+    args.iter()
+        .map(|arg| {
+            let mut filled = String::with_capacity(arg.len());
+            let mut rest = arg.as_str();
+            while let Some(start) = rest.find('{') {
+                filled.push_str(&rest[..start]);
+                let after = &rest[start + 1..];
+                match after.find('}').and_then(|end| Some((end, value(&after[..end])?))) {
+                    Some((end, value)) => {
+                        filled.push_str(&value);
+                        rest = &after[end + 1..];
+                    }
+                    None => {
+                        filled.push('{');
+                        rest = after;
+                    }
+                }
+            }
+            filled.push_str(rest);
+            filled
+        })
+        .collect()
+}
+
+/// The process for `command` of the plugin in `plugin_dir`
+pub fn command_for(
+    plugin_dir: &Path,
+    command: &PluginCommand,
+    adb_path: &str,
+    serial: &str,
+    values: &HashMap<String, String>,
+) -> Result<Command> {
+    if !command.adb.is_empty() {
+        let mut process = adb_command(adb_path);
+        // adb hands `shell` and `exec-out` arguments to the device shell as one line
+        let quote = matches!(command.adb[0].as_str(), "shell" | "exec-out");
+        process.args(["-s", serial]).args(expand(&command.adb, serial, values, quote));
+        return Ok(process);
+    }
+    let args = expand(&command.exec, serial, values, false);
+    let Some((program, args)) = args.split_first() else {
+        return Err(anyhow::anyhow!("Command '{}' has nothing to run", command.label));
+    };
+    let bundled = plugin_dir.join(program);
+    let mut process = if Path::new(program).is_relative() && bundled.is_file() {
+        Command::new(bundled)
+    } else {
+        Command::new(program)
+    };
+    process
+        .args(args)
+        .current_dir(plugin_dir)
+        .env("DROIDVIEW_ADB", adb_path)
+        .env("DROIDVIEW_SERIAL", serial);
+    Ok(process)
+}
+
+/// Run `command` and return what it printed
+pub fn run(
+    plugin_dir: &Path,
+    command: &PluginCommand,
+    adb_path: &str,
+    serial: &str,
+    values: &HashMap<String, String>,
+) -> Result<String> {
+    let output = command_for(plugin_dir, command, adb_path, serial, values)?.output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(if stderr.is_empty() { format!("exit code {}", output.status) } else { stderr }));
+    }
+    Ok(if stdout.is_empty() { stderr } else { stdout })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST_TEXT: &str = r#"
+name = "Battery saver"
+
+[[command]]
+label = "Battery saver"
+adb = ["shell", "settings", "put", "global", "low_power", "{enabled}"]
+toolkit = true
+
+[[command.field]]
+name = "enabled"
+kind = "checkbox"
+
+[[command]]
+label = "Report"
+exec = ["./report.sh", "{serial}", "{format}"]
+
+[[command.field]]
+name = "format"
+kind = "choice"
+options = ["txt", "json"]
+"#;
+
+    #[test]
+    fn reads_manifests() {
+        let mut plugin: Plugin = toml::from_str(MANIFEST_TEXT).unwrap();
+        plugin.dir = PathBuf::from("plugins/battery");
+        assert!(validate(&plugin).is_ok());
+        assert_eq!(plugin.commands.len(), 2);
+        assert!(plugin.commands[0].toolkit);
+        assert_eq!(plugin.commands[0].defaults()["enabled"], "0");
+        assert_eq!(plugin.commands[1].defaults()["format"], "txt");
+        assert_eq!(plugin.commands[1].fields[0].label(), "format");
+    }
+
+    #[test]
+    fn rejects_bad_manifests() {
+        let both = "name = \"x\"\n[[command]]\nlabel = \"y\"\nadb = [\"shell\"]\nexec = [\"ls\"]\n";
+        assert!(validate(&toml::from_str(both).unwrap()).is_err());
+        let neither = "name = \"x\"\n[[command]]\nlabel = \"y\"\n";
+        assert!(validate(&toml::from_str(neither).unwrap()).is_err());
+        let choice = "name = \"x\"\n[[command]]\nlabel = \"y\"\nadb = [\"shell\"]\n\
+                      [[command.field]]\nname = \"z\"\nkind = \"choice\"\n";
+        assert!(validate(&toml::from_str(choice).unwrap()).is_err());
+    }
+
+    #[test]
+    fn fills_placeholders() {
+        let args = vec!["{serial}".to_string(), "--format={format}".to_string(), "{other}".to_string()];
+        let values = HashMap::from([("format".to_string(), "json".to_string())]);
+        assert_eq!(expand(&args, "ABC", &values, false), ["ABC", "--format=json", "{other}"]);
+
+        // Values are not expanded again, and quoted for the device shell
+        let values = HashMap::from([
+            ("format".to_string(), "{serial}; reboot".to_string()),
+            ("serial".to_string(), "not this".to_string()),
+        ]);
+        assert_eq!(expand(&args, "ABC", &values, false), ["ABC", "--format={serial}; reboot", "{other}"]);
+        assert_eq!(expand(&args, "ABC", &values, true), ["'ABC'", "--format='{serial}; reboot'", "{other}"]);
+        let args = vec!["{{serial}}".to_string(), "{".to_string()];
+        assert_eq!(expand(&args, "ABC", &values, false), ["{ABC}", "{"]);
+    }
+
+    #[test]
+    fn discovers_plugin_folders() {
+        let dir = std::env::temp_dir().join(format!("droidview-plugins-{}", std::process::id()));
+        let good = dir.join("battery");
+        let bad = dir.join("broken");
+        std::fs::create_dir_all(&good).unwrap();
+        std::fs::create_dir_all(&bad).unwrap();
+        std::fs::create_dir_all(dir.join("not-a-plugin")).unwrap();
+        std::fs::write(good.join(MANIFEST), MANIFEST_TEXT).unwrap();
+        std::fs::write(bad.join(MANIFEST), "name = ").unwrap();
+
+        let (plugins, errors) = discover(&dir);
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(plugins.len(), 1);
+        assert_eq!(plugins[0].dir, good);
+        assert_eq!(errors.len(), 1);
+        assert!(discover(&dir).0.is_empty());
+    }
+}
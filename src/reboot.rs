@@ -0,0 +1,168 @@
+//! Reboot targets beyond the normal restart, recovery and bootloader, and which of them a
+//! device supports
+//!
+//! Support is read from the device's properties: fastbootd only exists with dynamic partitions
+//! (Android 10 and later), and emergency download mode is a Qualcomm feature.
+
+use crate::bridge::adb_command;
+use crate::t;
+use anyhow::Result;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RebootTarget {
+    /// Userspace fastboot, for flashing logical partitions
+    Fastbootd,
+    /// Android with third-party apps disabled
+    SafeMode,
+    /// Recovery waiting for `adb sideload`
+    Sideload,
+    /// Qualcomm emergency download mode
+    Edl,
+}
+
+impl RebootTarget {
+    pub const ALL: [RebootTarget; 4] = [
+        RebootTarget::Fastbootd,
+        RebootTarget::SafeMode,
+        RebootTarget::Sideload,
+        RebootTarget::Edl,
+    ];
+
+    pub fn label(self) -> String {
+        match self {
+            RebootTarget::Fastbootd => t!("toolkit-reboot-fastbootd"),
+            RebootTarget::SafeMode => t!("toolkit-reboot-safe-mode"),
+            RebootTarget::Sideload => t!("toolkit-reboot-sideload"),
+            RebootTarget::Edl => t!("toolkit-reboot-edl"),
+        }
+    }
+
+    /// Why a device without support for this target does not offer it
+    pub fn unsupported_reason(self) -> String {
+        match self {
+            RebootTarget::Fastbootd => t!("toolkit-reboot-fastbootd-unsupported"),
+            RebootTarget::Edl => t!("toolkit-reboot-edl-unsupported"),
+            RebootTarget::SafeMode | RebootTarget::Sideload => String::new(),
+        }
+    }
+}
+
+/// Parse `getprop` output: `[key]: [value]` per line
+pub fn parse_getprop(output: &str) -> HashMap<String, String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.trim().split_once("]: [")?;
+            let key = key.strip_prefix('[')?;
+            let value = value.strip_suffix(']')?;
+            Some((key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+fn is_qualcomm(props: &HashMap<String, String>) -> bool {
+    let prop = |key: &str| props.get(key).map(|value| value.to_lowercase()).unwrap_or_default();
+    let soc = prop("ro.soc.manufacturer");
+    if soc == "qti" || soc == "qualcomm" {
+        return true;
+    }
+    let platform = prop("ro.board.platform");
+    prop("ro.hardware").contains("qcom")
+        || ["msm", "sdm", "sm", "apq", "qcs"].iter().any(|prefix| {
+            platform
+                .strip_prefix(prefix)
+                .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+        })
+        || [
+            "kona", "lahaina", "taro", "kalama", "pineapple", "lito", "bengal", "holi", "trinket", "atoll",
+        ]
+        .contains(&platform.as_str())
+}
+
+/// The targets a device with `props` can reboot into
+pub fn supported_targets(props: &HashMap<String, String>) -> Vec<RebootTarget> {
+    RebootTarget::ALL
+        .into_iter()
+        .filter(|target| match target {
+            RebootTarget::Fastbootd => props.get("ro.boot.dynamic_partitions").is_some_and(|value| value == "true"),
+            RebootTarget::Edl => is_qualcomm(props),
+            RebootTarget::SafeMode | RebootTarget::Sideload => true,
+        })
+        .collect()
+}
+
+/// The targets the device supports; an error when its properties could not be read, which is
+/// no answer to keep
+pub fn probe(adb_path: &str, serial: &str) -> Result<Vec<RebootTarget>> {
+    let output = adb_command(adb_path).args(["-s", serial, "shell", "getprop"]).output()?;
+    let props = parse_getprop(&String::from_utf8_lossy(&output.stdout));
+    if !output.status.success() || props.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Could not read the properties of {}: {}",
+            serial,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(supported_targets(&props))
+}
+
+pub fn reboot(adb_path: &str, serial: &str, target: RebootTarget) -> Result<()> {
+    let mode = match target {
+        RebootTarget::Fastbootd => "fastboot",
+        RebootTarget::Sideload => return crate::sideload::reboot_to_sideload(adb_path, serial),
+        RebootTarget::Edl => "edl",
+        RebootTarget::SafeMode => {
+            // Read once by the next boot, which clears it again
+            let output = adb_command(adb_path)
+                .args(["-s", serial, "shell", "setprop persist.sys.safemode 1"])
+                .output()?;
+            if !output.status.success() || !output.stderr.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "The device refused to boot into safe mode: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ));
+            }
+            ""
+        }
+    };
+    let mut cmd = adb_command(adb_path);
+    cmd.args(["-s", serial, "reboot"]);
+    if !mode.is_empty() {
+        cmd.arg(mode);
+    }
+    let status = cmd.status()?;
+    if !status.success() {
+        return Err(anyhow::anyhow!("Reboot failed: exit code {}", status));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_getprop() {
+        let props = parse_getprop("[ro.hardware]: [qcom]\n[ro.boot.dynamic_partitions]: [true]\n[empty]: []\ngarbage\n");
+        assert_eq!(props.get("ro.hardware").map(String::as_str), Some("qcom"));
+        assert_eq!(props.get("empty").map(String::as_str), Some(""));
+        assert_eq!(props.len(), 3);
+    }
+
+    #[test]
+    fn detects_targets() {
+        let pixel = parse_getprop("[ro.boot.dynamic_partitions]: [true]\n[ro.soc.manufacturer]: [Google]\n[ro.board.platform]: [gs201]\n");
+        assert_eq!(
+            supported_targets(&pixel),
+            vec![RebootTarget::Fastbootd, RebootTarget::SafeMode, RebootTarget::Sideload]
+        );
+
+        let qualcomm = parse_getprop("[ro.board.platform]: [sdm845]\n");
+        assert!(supported_targets(&qualcomm).contains(&RebootTarget::Edl));
+        assert!(!supported_targets(&qualcomm).contains(&RebootTarget::Fastbootd));
+        assert!(supported_targets(&parse_getprop("[ro.board.platform]: [kona]\n")).contains(&RebootTarget::Edl));
+        // Samsung Exynos platforms start with "s" too
+        assert!(!supported_targets(&parse_getprop("[ro.board.platform]: [smdk]\n")).contains(&RebootTarget::Edl));
+    }
+}
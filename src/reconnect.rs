@@ -0,0 +1,142 @@
+//! Reconnection of wireless devices that dropped off, retried with exponential backoff
+//!
+//! The app tracks an `ip:port` device here when it stops being usable and runs `adb connect`
+//! for every endpoint `due` returns. Tracking ends when the device is usable again, the user
+//! cancels it, or the attempts run out.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+/// Wait before the first attempt, doubled after every failed one
+pub const FIRST_DELAY: Duration = Duration::from_secs(2);
+pub const MAX_DELAY: Duration = Duration::from_secs(60);
+/// Attempts before giving up on an endpoint, roughly five minutes in all
+pub const MAX_ATTEMPTS: u32 = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReconnectState {
+    /// Attempts made so far
+    pub attempts: u32,
+    pub next_attempt: Instant,
+    /// An `adb connect` is running
+    pub in_flight: bool,
+}
+
+/// Wait before the attempt following `attempts` earlier ones
+pub fn delay(attempts: u32) -> Duration {
+    FIRST_DELAY
+        .checked_mul(1 << attempts.min(16))
+        .map_or(MAX_DELAY, |delay| delay.min(MAX_DELAY))
+}
+
+/// True for `ip:port` serials, the only kind `adb connect` can bring back
+pub fn is_endpoint(serial: &str) -> bool {
+    serial
+        .rsplit_once(':')
+        .is_some_and(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok())
+}
+
+#[derive(Debug, Default)]
+pub struct Reconnector {
+    pending: BTreeMap<String, ReconnectState>,
+}
+
+impl Reconnector {
+    /// Start retrying `endpoint` unless it already is
+    pub fn track(&mut self, endpoint: &str, now: Instant) {
+        self.pending.entry(endpoint.to_string()).or_insert(ReconnectState {
+            attempts: 0,
+            next_attempt: now + delay(0),
+            in_flight: false,
+        });
+    }
+
+    /// Stop retrying; true when the endpoint was tracked
+    pub fn cancel(&mut self, endpoint: &str) -> bool {
+        self.pending.remove(endpoint).is_some()
+    }
+
+    /// Endpoints whose next attempt should start now; they stay in flight until `finished`
+    pub fn due(&mut self, now: Instant) -> Vec<String> {
+        self.pending
+            .iter_mut()
+            .filter(|(_, state)| !state.in_flight && state.next_attempt <= now)
+            .map(|(endpoint, state)| {
+                state.in_flight = true;
+                endpoint.clone()
+            })
+            .collect()
+    }
+
+    /// Record an attempt that did not bring the device back; false once it is given up
+    pub fn finished(&mut self, endpoint: &str, now: Instant) -> bool {
+        let Some(state) = self.pending.get_mut(endpoint) else {
+            return false;
+        };
+        state.attempts += 1;
+        if state.attempts >= MAX_ATTEMPTS {
+            self.pending.remove(endpoint);
+            return false;
+        }
+        state.in_flight = false;
+        state.next_attempt = now + delay(state.attempts);
+        true
+    }
+
+    pub fn states(&self) -> &BTreeMap<String, ReconnectState> {
+        &self.pending
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff() {
+        assert_eq!(delay(0), Duration::from_secs(2));
+        assert_eq!(delay(1), Duration::from_secs(4));
+        assert_eq!(delay(4), Duration::from_secs(32));
+        assert_eq!(delay(5), MAX_DELAY);
+        assert_eq!(delay(u32::MAX), MAX_DELAY);
+
+        assert!(is_endpoint("192.168.1.20:5555"));
+        assert!(is_endpoint("[fe80::1]:5555"));
+        assert!(!is_endpoint("R58M123ABC"));
+        assert!(!is_endpoint("adb-R58M123ABC-xyz._adb-tls-connect._tcp"));
+    }
+
+    #[test]
+    fn retries_until_cancelled_or_given_up() {
+        let start = Instant::now();
+        let mut reconnector = Reconnector::default();
+        reconnector.track("10.0.0.5:5555", start);
+        assert!(reconnector.due(start).is_empty());
+
+        let first = start + FIRST_DELAY;
+        assert_eq!(reconnector.due(first), vec!["10.0.0.5:5555".to_string()]);
+        // Still running, so not handed out twice
+        assert!(reconnector.due(first).is_empty());
+        assert!(reconnector.finished("10.0.0.5:5555", first));
+        assert!(reconnector.due(first + FIRST_DELAY).is_empty());
+        assert_eq!(reconnector.due(first + delay(1)).len(), 1);
+
+        // Tracking again keeps the backoff where it is
+        reconnector.track("10.0.0.5:5555", first);
+        assert_eq!(reconnector.states()["10.0.0.5:5555"].attempts, 1);
+
+        let mut now = first;
+        while reconnector.finished("10.0.0.5:5555", now) {
+            now += MAX_DELAY;
+        }
+        assert!(reconnector.is_empty());
+
+        reconnector.track("10.0.0.6:5555", now);
+        assert!(reconnector.cancel("10.0.0.6:5555"));
+        assert!(!reconnector.cancel("10.0.0.6:5555"));
+    }
+}
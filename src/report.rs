@@ -0,0 +1,427 @@
+//! Device reports for support tickets and inventories
+//!
+//! A report collects the build properties, display, battery, storage, memory and installed apps
+//! of one device, and is saved as JSON for tools or as a single HTML page for people.
+
+use crate::bridge::adb_command;
+use crate::packages::PackageInfo;
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Json,
+    Html,
+}
+
+impl ReportFormat {
+    /// JSON for a `.json` file, HTML for anything else
+    pub fn for_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("json") => ReportFormat::Json,
+            _ => ReportFormat::Html,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DeviceReport {
+    /// When the report was taken, RFC 3339
+    pub generated: String,
+    pub serial: String,
+    pub build: BuildInfo,
+    pub display: DisplayInfo,
+    pub battery: BatteryInfo,
+    pub storage: Vec<StorageVolume>,
+    pub memory_total_kb: Option<u64>,
+    pub apps: Vec<PackageInfo>,
+}
+
+/// Build properties; empty when the device does not set them
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct BuildInfo {
+    pub manufacturer: String,
+    pub brand: String,
+    pub model: String,
+    pub device: String,
+    pub android_version: String,
+    pub api_level: Option<u32>,
+    pub security_patch: String,
+    pub build_id: String,
+    pub fingerprint: String,
+    pub abi: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DisplayInfo {
+    /// e.g. `1080x2400`
+    pub physical_size: Option<String>,
+    pub override_size: Option<String>,
+    pub physical_density: Option<u32>,
+    pub override_density: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct BatteryInfo {
+    /// Charge in percent
+    pub level: Option<u8>,
+    /// e.g. `Good` or `Overheat`
+    pub health: Option<String>,
+    /// e.g. `Charging`
+    pub status: Option<String>,
+    pub temperature_c: Option<f32>,
+    pub voltage_mv: Option<u32>,
+    pub technology: Option<String>,
+    /// Only reported by newer Android versions
+    pub cycle_count: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StorageVolume {
+    pub mount: String,
+    pub size_kb: u64,
+    pub used_kb: u64,
+    pub available_kb: u64,
+}
+
+/// Mount points included in the report, when the device has them
+const VOLUMES: &str = "/data /system /storage/emulated";
+
+/// Read everything for the report from the device; the app list can take a while on large installs
+pub fn gather(adb_path: &str, serial: &str) -> Result<DeviceReport> {
+    // Commands such as df exit non-zero when one path is missing but still print the others
+    let run = |command: &str| -> Result<String> {
+        let output = adb_command(adb_path).args(["-s", serial, "shell", command]).output()?;
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    };
+
+    let props = crate::reboot::parse_getprop(&run("getprop")?);
+    if props.is_empty() {
+        return Err(anyhow::anyhow!("Could not read the properties of {}", serial));
+    }
+    let apps = crate::packages::list_packages(adb_path, serial, None).unwrap_or_else(|e| {
+        tracing::warn!("Device report without the app list: {}", e);
+        Vec::new()
+    });
+
+    Ok(DeviceReport {
+        generated: chrono::Local::now().to_rfc3339(),
+        serial: serial.to_string(),
+        build: build_info(&props),
+        display: parse_display(&run("wm size; wm density")?),
+        battery: parse_battery(&run("dumpsys battery")?),
+        storage: parse_df(&run(&format!("df -k {} 2>/dev/null", VOLUMES))?),
+        memory_total_kb: parse_meminfo(&run("cat /proc/meminfo")?),
+        apps,
+    })
+}
+
+/// Gather the report and save it to `path`, as JSON or HTML by the file's extension
+pub fn export(adb_path: &str, serial: &str, path: &Path) -> Result<PathBuf> {
+    let report = gather(adb_path, serial)?;
+    let text = match ReportFormat::for_path(path) {
+        ReportFormat::Json => serde_json::to_string_pretty(&report)?,
+        ReportFormat::Html => to_html(&report),
+    };
+    std::fs::write(path, text)?;
+    Ok(path.to_path_buf())
+}
+
+pub fn build_info(props: &HashMap<String, String>) -> BuildInfo {
+    let prop = |key: &str| props.get(key).cloned().unwrap_or_default();
+    BuildInfo {
+        manufacturer: prop("ro.product.manufacturer"),
+        brand: prop("ro.product.brand"),
+        model: prop("ro.product.model"),
+        device: prop("ro.product.device"),
+        android_version: prop("ro.build.version.release"),
+        api_level: props.get("ro.build.version.sdk").and_then(|sdk| sdk.parse().ok()),
+        security_patch: prop("ro.build.version.security_patch"),
+        build_id: prop("ro.build.display.id"),
+        fingerprint: prop("ro.build.fingerprint"),
+        abi: prop("ro.product.cpu.abi"),
+    }
+}
+
+/// Parse `wm size` followed by `wm density`
+pub fn parse_display(output: &str) -> DisplayInfo {
+    let value = |prefix: &str| {
+        output
+            .lines()
+            .find_map(|line| line.trim().strip_prefix(prefix))
+            .map(|value| value.trim().to_string())
+    };
+    DisplayInfo {
+        physical_size: value("Physical size:"),
+        override_size: value("Override size:"),
+        physical_density: value("Physical density:").and_then(|density| density.parse().ok()),
+        override_density: value("Override density:").and_then(|density| density.parse().ok()),
+    }
+}
+
+/// Parse `dumpsys battery`, naming the health and status codes of `BatteryManager`
+pub fn parse_battery(output: &str) -> BatteryInfo {
+    let values: HashMap<&str, &str> = output
+        .lines()
+        .filter_map(|line| line.trim().split_once(':'))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .collect();
+    let number = |key: &str| values.get(key).and_then(|value| value.parse::<i64>().ok());
+
+    let scale = number("scale").filter(|scale| *scale > 0).unwrap_or(100);
+    let health = number("health").map(|code| {
+        match code {
+            2 => "Good",
+            3 => "Overheat",
+            4 => "Dead",
+            5 => "Over voltage",
+            6 => "Failure",
+            7 => "Cold",
+            _ => "Unknown",
+        }
+        .to_string()
+    });
+    let status = number("status").map(|code| {
+        match code {
+            2 => "Charging",
+            3 => "Discharging",
+            4 => "Not charging",
+            5 => "Full",
+            _ => "Unknown",
+        }
+        .to_string()
+    });
+
+    BatteryInfo {
+        level: number("level").map(|level| (level * 100 / scale).clamp(0, 100) as u8),
+        health,
+        status,
+        // Reported in tenths of a degree
+        temperature_c: number("temperature").map(|tenths| tenths as f32 / 10.0),
+        voltage_mv: number("voltage").and_then(|voltage| u32::try_from(voltage).ok()),
+        technology: values.get("technology").map(|technology| technology.to_string()),
+        cycle_count: number("cycle count")
+            .or_else(|| number("Charge cycle count"))
+            .and_then(|count| u32::try_from(count).ok()),
+    }
+}
+
+/// Parse `df -k`: filesystem, 1K blocks, used, available, use% and mount point
+pub fn parse_df(output: &str) -> Vec<StorageVolume> {
+    output
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [_, size, used, available, _, mount] = fields.as_slice() else {
+                return None;
+            };
+            Some(StorageVolume {
+                mount: mount.to_string(),
+                size_kb: size.parse().ok()?,
+                used_kb: used.parse().ok()?,
+                available_kb: available.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+pub fn parse_meminfo(output: &str) -> Option<u64> {
+    output
+        .lines()
+        .find_map(|line| line.strip_prefix("MemTotal:"))
+        .and_then(|value| value.trim().trim_end_matches("kB").trim().parse().ok())
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn size_kb(kb: u64) -> String {
+    crate::utils::format_file_size(kb * 1024)
+}
+
+/// The report as a single page that needs no other files
+pub fn to_html(report: &DeviceReport) -> String {
+    let build = &report.build;
+    let title = format!("{} {} ({})", build.manufacturer, build.model, report.serial);
+    let or_dash = |value: Option<String>| value.filter(|value| !value.is_empty()).unwrap_or_else(|| "—".to_string());
+
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!doctype html>\n<html><head><meta charset=\"utf-8\"><title>{}</title>\n<style>\
+         body{{font-family:sans-serif;margin:2em;color:#222}}table{{border-collapse:collapse;margin-bottom:1.5em}}\
+         th,td{{border:1px solid #ccc;padding:4px 10px;text-align:left}}th{{background:#f2f2f2}}\
+         </style></head><body>\n<h1>{}</h1>\n<p>Generated by DroidView on {}</p>\n",
+        escape(&title),
+        escape(&title),
+        escape(&report.generated)
+    );
+
+    let section = |html: &mut String, heading: &str, rows: &[(&str, String)]| {
+        let _ = write!(html, "<h2>{}</h2>\n<table>\n", heading);
+        for (name, value) in rows {
+            let _ = writeln!(html, "<tr><th>{}</th><td>{}</td></tr>", name, escape(value));
+        }
+        html.push_str("</table>\n");
+    };
+
+    section(
+        &mut html,
+        "Device",
+        &[
+            ("Manufacturer", or_dash(Some(build.manufacturer.clone()))),
+            ("Brand", or_dash(Some(build.brand.clone()))),
+            ("Model", or_dash(Some(build.model.clone()))),
+            ("Device", or_dash(Some(build.device.clone()))),
+            ("Serial", report.serial.clone()),
+            ("Android", or_dash(Some(build.android_version.clone()))),
+            ("API level", or_dash(build.api_level.map(|level| level.to_string()))),
+            ("Security patch", or_dash(Some(build.security_patch.clone()))),
+            ("Build", or_dash(Some(build.build_id.clone()))),
+            ("Fingerprint", or_dash(Some(build.fingerprint.clone()))),
+            ("ABI", or_dash(Some(build.abi.clone()))),
+            ("Memory", or_dash(report.memory_total_kb.map(size_kb))),
+        ],
+    );
+
+    let display = &report.display;
+    section(
+        &mut html,
+        "Display",
+        &[
+            ("Size", or_dash(display.physical_size.clone())),
+            ("Size override", or_dash(display.override_size.clone())),
+            ("Density", or_dash(display.physical_density.map(|density| format!("{} dpi", density)))),
+            ("Density override", or_dash(display.override_density.map(|density| format!("{} dpi", density)))),
+        ],
+    );
+
+    let battery = &report.battery;
+    section(
+        &mut html,
+        "Battery",
+        &[
+            ("Level", or_dash(battery.level.map(|level| format!("{}%", level)))),
+            ("Health", or_dash(battery.health.clone())),
+            ("Status", or_dash(battery.status.clone())),
+            ("Temperature", or_dash(battery.temperature_c.map(|celsius| format!("{:.1} °C", celsius)))),
+            ("Voltage", or_dash(battery.voltage_mv.map(|voltage| format!("{} mV", voltage)))),
+            ("Technology", or_dash(battery.technology.clone())),
+            ("Charge cycles", or_dash(battery.cycle_count.map(|count| count.to_string()))),
+        ],
+    );
+
+    html.push_str("<h2>Storage</h2>\n<table>\n<tr><th>Mount</th><th>Size</th><th>Used</th><th>Free</th></tr>\n");
+    for volume in &report.storage {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape(&volume.mount),
+            size_kb(volume.size_kb),
+            size_kb(volume.used_kb),
+            size_kb(volume.available_kb)
+        );
+    }
+    html.push_str("</table>\n");
+
+    let _ = write!(
+        html,
+        "<h2>Installed apps ({})</h2>\n<table>\n\
+         <tr><th>Package</th><th>Type</th><th>Enabled</th><th>Installed</th><th>Updated</th><th>Size</th></tr>\n",
+        report.apps.len()
+    );
+    for app in &report.apps {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape(&app.package),
+            if app.system { "System" } else { "User" },
+            if app.enabled { "Yes" } else { "No" },
+            escape(&or_dash(app.first_install_time.clone())),
+            escape(&or_dash(app.last_update_time.clone())),
+            or_dash(app.size_kb.map(size_kb))
+        );
+    }
+    html.push_str("</table>\n</body></html>\n");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BATTERY: &str = "Current Battery Service state:
+  AC powered: false
+  USB powered: true
+  status: 2
+  health: 2
+  present: true
+  level: 87
+  scale: 100
+  voltage: 4312
+  temperature: 284
+  technology: Li-ion
+";
+
+    #[test]
+    fn parses_battery_state() {
+        let battery = parse_battery(BATTERY);
+        assert_eq!(battery.level, Some(87));
+        assert_eq!(battery.health.as_deref(), Some("Good"));
+        assert_eq!(battery.status.as_deref(), Some("Charging"));
+        assert_eq!(battery.temperature_c, Some(28.4));
+        assert_eq!(battery.voltage_mv, Some(4312));
+        assert_eq!(battery.technology.as_deref(), Some("Li-ion"));
+        assert_eq!(battery.cycle_count, None);
+    }
+
+    #[test]
+    fn parses_display_and_storage() {
+        let display = parse_display("Physical size: 1080x2400\nOverride size: 720x1600\nPhysical density: 420\n");
+        assert_eq!(display.physical_size.as_deref(), Some("1080x2400"));
+        assert_eq!(display.override_size.as_deref(), Some("720x1600"));
+        assert_eq!(display.physical_density, Some(420));
+        assert_eq!(display.override_density, None);
+
+        let df = "Filesystem      1K-blocks     Used Available Use% Mounted on\n\
+                  /dev/block/dm-8 115249700 40213764  75035936  35% /data\n\
+                  /dev/fuse       115249700 40213764  75035936  35% /storage/emulated\n";
+        let storage = parse_df(df);
+        assert_eq!(storage.len(), 2);
+        assert_eq!(storage[0].mount, "/data");
+        assert_eq!(storage[0].available_kb, 75035936);
+        assert_eq!(parse_meminfo("MemTotal:        7754644 kB\nMemFree: 1 kB\n"), Some(7754644));
+    }
+
+    #[test]
+    fn writes_escaped_html() {
+        let props = crate::reboot::parse_getprop(
+            "[ro.product.manufacturer]: [Google]\n[ro.product.model]: [Pixel <7>]\n[ro.build.version.sdk]: [34]\n",
+        );
+        let report = DeviceReport {
+            serial: "ABC".to_string(),
+            build: build_info(&props),
+            battery: parse_battery(BATTERY),
+            apps: vec![PackageInfo::new("com.example.app")],
+            ..Default::default()
+        };
+        assert_eq!(report.build.api_level, Some(34));
+        let html = to_html(&report);
+        assert!(html.contains("<title>Google Pixel &lt;7&gt; (ABC)</title>"));
+        assert!(html.contains("<td>87%</td>"));
+        assert!(html.contains("Installed apps (1)"));
+        assert!(html.contains("<td>com.example.app</td><td>User</td><td>Yes</td>"));
+    }
+
+    #[test]
+    fn picks_the_format_by_extension() {
+        assert_eq!(ReportFormat::for_path(Path::new("report.JSON")), ReportFormat::Json);
+        assert_eq!(ReportFormat::for_path(Path::new("report.html")), ReportFormat::Html);
+        assert_eq!(ReportFormat::for_path(Path::new("report")), ReportFormat::Html);
+    }
+}
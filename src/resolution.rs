@@ -0,0 +1,127 @@
+//! Overriding the screen resolution and density with `wm size` and `wm density`
+//!
+//! Overrides survive reboots. Values far from the panel's own can leave the screen unreadable
+//! or untouchable, so they are checked before being applied; `wm size reset` and
+//! `wm density reset` from a terminal always bring the device back.
+
+use crate::automation::shell;
+use crate::report::DisplayInfo;
+use anyhow::Result;
+
+/// Portrait resolutions offered as presets
+pub const SIZE_PRESETS: [(&str, u32, u32); 6] = [
+    ("HD", 720, 1280),
+    ("HD+", 720, 1600),
+    ("FHD", 1080, 1920),
+    ("FHD+", 1080, 2400),
+    ("QHD", 1440, 2560),
+    ("Tablet", 1600, 2560),
+];
+
+pub const DENSITY_PRESETS: [u32; 6] = [240, 320, 400, 420, 480, 560];
+
+/// Narrowest width that still fits Android's own dialogs
+const MIN_WIDTH: u32 = 320;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Risk {
+    /// Narrower than Android's layouts are made for
+    TooSmall,
+    /// More than twice the panel's pixels, which slows rendering to a crawl
+    TooLarge,
+    /// A different shape than the panel, which leaves parts of the screen unused or unreachable
+    AspectRatio,
+    /// Text and buttons become too small to hit
+    DensityTooLow,
+    /// Too little fits on screen to reach Settings again
+    DensityTooHigh,
+}
+
+pub fn read(adb_path: &str, serial: &str) -> Result<DisplayInfo> {
+    let info = crate::report::parse_display(&shell(adb_path, serial, "wm size; wm density")?);
+    if info.physical_size.is_none() {
+        return Err(anyhow::anyhow!("Could not read the screen size"));
+    }
+    Ok(info)
+}
+
+/// Override the size and density; None leaves that value as it is
+pub fn apply(adb_path: &str, serial: &str, size: Option<(u32, u32)>, density: Option<u32>) -> Result<()> {
+    if let Some((width, height)) = size {
+        shell(adb_path, serial, &format!("wm size {}x{}", width, height))?;
+    }
+    if let Some(density) = density {
+        shell(adb_path, serial, &format!("wm density {}", density))?;
+    }
+    Ok(())
+}
+
+pub fn reset(adb_path: &str, serial: &str) -> Result<()> {
+    shell(adb_path, serial, "wm size reset; wm density reset")?;
+    Ok(())
+}
+
+/// `1080x2400` as width and height
+pub fn parse_size(size: &str) -> Option<(u32, u32)> {
+    let (width, height) = size.trim().split_once('x')?;
+    Some((width.trim().parse().ok()?, height.trim().parse().ok()?))
+}
+
+/// Ways the new values could make the device hard to use, compared to the panel's own
+pub fn risks(physical: (u32, u32), physical_density: u32, size: (u32, u32), density: u32) -> Vec<Risk> {
+    let mut risks = Vec::new();
+    // Orientation does not matter, only the shorter and longer edges
+    let edges = |(width, height): (u32, u32)| (width.min(height) as f32, width.max(height) as f32);
+    let (panel_short, panel_long) = edges(physical);
+    let (short, long) = edges(size);
+
+    if short < MIN_WIDTH as f32 {
+        risks.push(Risk::TooSmall);
+    }
+    if short * long > panel_short * panel_long * 2.0 {
+        risks.push(Risk::TooLarge);
+    }
+    if short > 0.0 && panel_short > 0.0 {
+        let ratio = (long / short) / (panel_long / panel_short);
+        if !(0.8..=1.25).contains(&ratio) {
+            risks.push(Risk::AspectRatio);
+        }
+    }
+
+    // What matters is the density relative to the resolution actually used
+    let scaled_density = physical_density as f32 * short / panel_short.max(1.0);
+    if (density as f32) < scaled_density * 0.5 {
+        risks.push(Risk::DensityTooLow);
+    }
+    if density as f32 > scaled_density * 2.0 {
+        risks.push(Risk::DensityTooHigh);
+    }
+    risks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sizes() {
+        assert_eq!(parse_size("1080x2400"), Some((1080, 2400)));
+        assert_eq!(parse_size(" 720 x 1280 "), Some((720, 1280)));
+        assert_eq!(parse_size("1080"), None);
+    }
+
+    #[test]
+    fn flags_risky_values() {
+        let panel = (1080, 2400);
+        assert!(risks(panel, 420, (1080, 2400), 420).is_empty());
+        assert!(risks(panel, 420, (720, 1600), 280).is_empty());
+        // Landscape values for a portrait panel are the same shape
+        assert!(risks(panel, 420, (2400, 1080), 420).is_empty());
+
+        assert_eq!(risks(panel, 420, (240, 533), 93), vec![Risk::TooSmall]);
+        assert_eq!(risks(panel, 420, (2160, 4800), 840), vec![Risk::TooLarge]);
+        assert_eq!(risks(panel, 420, (1080, 1080), 420), vec![Risk::AspectRatio]);
+        assert_eq!(risks(panel, 420, (1080, 2400), 160), vec![Risk::DensityTooLow]);
+        assert_eq!(risks(panel, 420, (1080, 2400), 960), vec![Risk::DensityTooHigh]);
+    }
+}
@@ -0,0 +1,222 @@
+//! User scripts written in Rhai and run against one device
+//!
+//! Scripts only get what is registered here: `device.shell()`, `device.tap()` and the other
+//! input methods, `screenshot()` and `sleep()`. Rhai has no file or network access of its own,
+//! so a script can do no more than the device's adb shell allows.
+
+use crate::config::MacroInput;
+use crate::utils::sleep_unless_cancelled;
+use rhai::{Dynamic, Engine, EvalAltResult, Scope};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// How long `device.long_press()` holds
+const LONG_PRESS_MS: u32 = 800;
+/// Operations a script may run in all; plenty for a loop that taps and sleeps for hours
+const MAX_OPERATIONS: u64 = 50_000_000;
+/// Longest string a script may build, which leaves room for large `dumpsys` output
+const MAX_STRING_SIZE: usize = 16 * 1024 * 1024;
+const MAX_COLLECTION_SIZE: usize = 100_000;
+const MAX_CALL_LEVELS: usize = 64;
+const MAX_EXPR_DEPTH: usize = 64;
+
+/// Where a script runs and where its screenshots go
+#[derive(Debug, Clone)]
+pub struct ScriptContext {
+    pub adb_path: String,
+    pub serial: String,
+    pub model: String,
+    pub screenshot_dir: PathBuf,
+    pub screenshot_template: String,
+}
+
+/// The device a script runs on, given to it as `device`
+#[derive(Debug, Clone)]
+struct ScriptDevice {
+    adb_path: String,
+    serial: String,
+}
+
+type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+fn script_error(e: impl std::fmt::Display) -> Box<EvalAltResult> {
+    e.to_string().into()
+}
+
+fn coordinate(value: i64) -> ScriptResult<u32> {
+    u32::try_from(value).map_err(|_| script_error(format!("{} is not a screen coordinate", value)))
+}
+
+impl ScriptDevice {
+    fn shell(&mut self, command: &str) -> ScriptResult<String> {
+        crate::automation::shell(&self.adb_path, &self.serial, command).map_err(script_error)
+    }
+
+    fn send(&mut self, input: MacroInput) -> ScriptResult<()> {
+        crate::macros::send(&self.adb_path, &self.serial, &input).map_err(script_error)
+    }
+
+    fn tap(&mut self, x: i64, y: i64) -> ScriptResult<()> {
+        self.send(MacroInput::Tap { x: coordinate(x)?, y: coordinate(y)? })
+    }
+
+    fn swipe(&mut self, x1: i64, y1: i64, x2: i64, y2: i64, duration_ms: i64) -> ScriptResult<()> {
+        self.send(MacroInput::Swipe {
+            x1: coordinate(x1)?,
+            y1: coordinate(y1)?,
+            x2: coordinate(x2)?,
+            y2: coordinate(y2)?,
+            duration_ms: duration_ms.clamp(1, 60_000) as u32,
+        })
+    }
+}
+
+/// Check a script for syntax errors without running it
+pub fn check(source: &str) -> Result<(), String> {
+    engine().compile(source).map(|_| ()).map_err(|e| e.to_string())
+}
+
+/// An engine with `eval` turned off, so scripts are exactly what is in the editor, and with
+/// limits that stop a runaway script before it takes the app's memory or stack
+fn engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.disable_symbol("eval");
+    engine
+        .set_max_operations(MAX_OPERATIONS)
+        .set_max_string_size(MAX_STRING_SIZE)
+        .set_max_array_size(MAX_COLLECTION_SIZE)
+        .set_max_map_size(MAX_COLLECTION_SIZE)
+        .set_max_call_levels(MAX_CALL_LEVELS)
+        .set_max_expr_depths(MAX_EXPR_DEPTH, MAX_EXPR_DEPTH);
+    engine
+}
+
+/// Run `source` on the device of `context`, passing everything it prints to `on_output`.
+/// Setting `cancel` stops the script at its next statement or during `sleep()`.
+pub fn run(
+    context: ScriptContext,
+    source: &str,
+    cancel: Arc<AtomicBool>,
+    on_output: impl Fn(String) + 'static,
+) -> Result<(), String> {
+    let mut engine = engine();
+    let on_output = Rc::new(on_output);
+
+    let output = on_output.clone();
+    engine.on_print(move |text| output(text.to_string()));
+    let output = on_output.clone();
+    engine.on_debug(move |text, _, position| output(format!("[{}] {}", position, text)));
+    let stop = cancel.clone();
+    engine.on_progress(move |_| stop.load(Ordering::Relaxed).then_some(Dynamic::UNIT));
+
+    engine
+        .register_type_with_name::<ScriptDevice>("Device")
+        .register_get("serial", |device: &mut ScriptDevice| device.serial.clone())
+        .register_fn("shell", ScriptDevice::shell)
+        .register_fn("tap", ScriptDevice::tap)
+        .register_fn("long_press", |device: &mut ScriptDevice, x: i64, y: i64| {
+            device.swipe(x, y, x, y, LONG_PRESS_MS as i64)
+        })
+        .register_fn("swipe", ScriptDevice::swipe)
+        .register_fn("key", |device: &mut ScriptDevice, code: &str| {
+            device.send(MacroInput::Key { code: code.to_string() })
+        })
+        .register_fn("text", |device: &mut ScriptDevice, text: &str| {
+            device.send(MacroInput::Text { text: text.to_string() })
+        });
+
+    let stop = cancel.clone();
+    engine.register_fn("sleep", move |ms: i64| -> ScriptResult<()> {
+        if !sleep_unless_cancelled(Duration::from_millis(ms.max(0) as u64), &stop) {
+            return Err(script_error("Stopped"));
+        }
+        Ok(())
+    });
+
+    let shot = context.clone();
+    engine.register_fn("screenshot", move || -> ScriptResult<String> {
+        let path = crate::automation::screenshot(
+            &shot.adb_path,
+            &shot.serial,
+            &shot.model,
+            &shot.screenshot_dir,
+            &shot.screenshot_template,
+        )
+        .map_err(script_error)?;
+        Ok(path.display().to_string())
+    });
+
+    let mut scope = Scope::new();
+    scope.push_constant(
+        "device",
+        ScriptDevice {
+            adb_path: context.adb_path,
+            serial: context.serial,
+        },
+    );
+    match engine.run_with_scope(&mut scope, source) {
+        Ok(()) => Ok(()),
+        Err(_) if cancel.load(Ordering::Relaxed) => Err("Stopped".to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    fn context() -> ScriptContext {
+        ScriptContext {
+            adb_path: "adb".to_string(),
+            serial: "SERIAL".to_string(),
+            model: "Pixel".to_string(),
+            screenshot_dir: std::env::temp_dir(),
+            screenshot_template: "screenshot_{timestamp}".to_string(),
+        }
+    }
+
+    #[test]
+    fn reports_syntax_errors() {
+        assert!(check("let x = 1; print(x);").is_ok());
+        assert!(check("let x = ;").is_err());
+        // `eval` is switched off
+        assert!(check("eval(\"1\")").is_err());
+    }
+
+    #[test]
+    fn collects_printed_output() {
+        let lines = Rc::new(RefCell::new(Vec::new()));
+        let sink = lines.clone();
+        let source = "for i in 0..2 { print(`${device.serial} ${i}`); } sleep(1);";
+        let result = run(context(), source, Arc::new(AtomicBool::new(false)), move |line| {
+            sink.borrow_mut().push(line)
+        });
+        assert_eq!(result, Ok(()));
+        assert_eq!(*lines.borrow(), ["SERIAL 0", "SERIAL 1"]);
+    }
+
+    #[test]
+    fn stops_when_cancelled() {
+        let cancel = Arc::new(AtomicBool::new(true));
+        assert_eq!(run(context(), "loop { }", cancel.clone(), |_| {}), Err("Stopped".to_string()));
+        assert_eq!(run(context(), "sleep(60000);", cancel, |_| {}), Err("Stopped".to_string()));
+    }
+
+    #[test]
+    fn stops_runaway_scripts() {
+        let never_cancel = || Arc::new(AtomicBool::new(false));
+        assert!(run(context(), "fn down(n) { down(n + 1) } down(0);", never_cancel(), |_| {}).is_err());
+        assert!(run(context(), "let s = \"x\"; loop { s += s; }", never_cancel(), |_| {}).is_err());
+        assert!(run(context(), "let a = [0]; loop { a += a; }", never_cancel(), |_| {}).is_err());
+    }
+
+    #[test]
+    fn rejects_bad_coordinates() {
+        let result = run(context(), "device.tap(-1, 5);", Arc::new(AtomicBool::new(false)), |_| {});
+        assert!(result.unwrap_err().contains("not a screen coordinate"));
+    }
+}
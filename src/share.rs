@@ -0,0 +1,231 @@
+//! View-only sharing of the device screen with other machines on the local network
+//!
+//! One thread grabs a screenshot every `FRAME_INTERVAL` while someone watches; another serves
+//! them as MJPEG on `0.0.0.0`. Every request needs the random token from the share link, and the
+//! share ends by itself once its duration runs out.
+
+use crate::bridge::adb_command;
+use anyhow::Result;
+use constant_time_eq::constant_time_eq;
+use std::io::{Cursor, Write};
+use std::net::{IpAddr, UdpSocket};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_SHARE_PORT: u16 = 8787;
+
+/// Share durations offered in the UI, in minutes
+pub const SHARE_DURATIONS: [u64; 4] = [5, 15, 30, 60];
+
+/// Pause between screen captures while someone is watching
+const FRAME_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Longest edge of the shared frames
+const MAX_FRAME_EDGE: u32 = 1280;
+
+/// Latest JPEG frame and its sequence number
+#[derive(Default)]
+struct FrameSlot {
+    seq: u64,
+    jpeg: Arc<Vec<u8>>,
+}
+
+/// State shared by the capture thread, the HTTP thread and each viewer
+struct Shared {
+    token: String,
+    expires_at: Instant,
+    stop: AtomicBool,
+    viewers: AtomicUsize,
+    frame: Mutex<FrameSlot>,
+}
+
+impl Shared {
+    fn active(&self) -> bool {
+        !self.stop.load(Ordering::Relaxed) && Instant::now() < self.expires_at
+    }
+}
+
+/// A time-limited, view-only MJPEG relay of the device screen on the local network
+pub struct ShareServer {
+    shared: Arc<Shared>,
+    port: u16,
+    threads: Vec<JoinHandle<()>>,
+}
+
+impl ShareServer {
+    pub fn start(adb_path: String, device_id: String, port: u16, duration: Duration) -> Result<Self> {
+        let server = tiny_http::Server::http(("0.0.0.0", port))
+            .map_err(|e| anyhow::anyhow!("Could not listen on port {}: {}", port, e))?;
+
+        let shared = Arc::new(Shared {
+            // 122 random bits, never logged
+            token: uuid::Uuid::new_v4().simple().to_string(),
+            expires_at: Instant::now() + duration,
+            stop: AtomicBool::new(false),
+            viewers: AtomicUsize::new(0),
+            frame: Mutex::new(FrameSlot::default()),
+        });
+
+        let capture = {
+            let shared = shared.clone();
+            std::thread::spawn(move || capture_loop(&shared, &adb_path, &device_id))
+        };
+        let http = {
+            let shared = shared.clone();
+            std::thread::spawn(move || serve(&shared, server))
+        };
+
+        tracing::info!("View-only share listening on port {}", port);
+        Ok(Self {
+            shared,
+            port,
+            threads: vec![capture, http],
+        })
+    }
+
+    /// Link to hand out, using this machine's LAN address
+    pub fn url(&self) -> String {
+        let host = local_ip().map(|ip| ip.to_string()).unwrap_or_else(|| "localhost".to_string());
+        format!("http://{}:{}/?token={}", host, self.port, self.shared.token)
+    }
+
+    pub fn remaining(&self) -> Duration {
+        self.shared.expires_at.saturating_duration_since(Instant::now())
+    }
+
+    pub fn viewer_count(&self) -> usize {
+        self.shared.viewers.load(Ordering::Relaxed)
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.shared.active()
+    }
+
+    pub fn stop(self) {
+        self.shared.stop.store(true, Ordering::Relaxed);
+        for thread in self.threads {
+            let _ = thread.join();
+        }
+        tracing::info!("View-only share stopped");
+    }
+}
+
+/// The address other machines on the LAN reach this one at
+fn local_ip() -> Option<IpAddr> {
+    // Connecting a UDP socket sends nothing but picks the outgoing interface
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("192.0.2.1:80").ok()?;
+    socket.local_addr().ok().map(|addr| addr.ip())
+}
+
+fn capture_frame(adb_path: &str, device_id: &str) -> Result<Vec<u8>> {
+    let output = adb_command(adb_path)
+        .args(["-s", device_id, "exec-out", "screencap", "-p"])
+        .output()?;
+    if !output.status.success() || output.stdout.is_empty() {
+        return Err(anyhow::anyhow!("screencap failed"));
+    }
+
+    let image = image::load_from_memory(&output.stdout)?.thumbnail(MAX_FRAME_EDGE, MAX_FRAME_EDGE);
+    let mut jpeg = Vec::new();
+    image::DynamicImage::ImageRgb8(image.to_rgb8())
+        .write_to(&mut Cursor::new(&mut jpeg), image::ImageOutputFormat::Jpeg(70))?;
+    Ok(jpeg)
+}
+
+fn capture_loop(shared: &Shared, adb_path: &str, device_id: &str) {
+    while shared.active() {
+        // Nobody watching, nothing to capture
+        if shared.viewers.load(Ordering::Relaxed) > 0 {
+            match capture_frame(adb_path, device_id) {
+                Ok(jpeg) => {
+                    let mut slot = shared.frame.lock().unwrap();
+                    slot.seq += 1;
+                    slot.jpeg = Arc::new(jpeg);
+                }
+                Err(e) => tracing::warn!("Share capture failed: {}", e),
+            }
+        }
+        std::thread::sleep(FRAME_INTERVAL);
+    }
+}
+
+const VIEWER_PAGE: &str = r#"<!doctype html>
+<html><head><meta charset="utf-8"><meta name="viewport" content="width=device-width, initial-scale=1">
+<title>DroidView - shared screen</title>
+<style>body{margin:0;background:#111;display:flex;justify-content:center;align-items:center;height:100vh}
+img{max-width:100%;max-height:100vh}</style></head>
+<body><img src="/stream?token=TOKEN" alt="Device screen"></body></html>"#;
+
+fn serve(shared: &Arc<Shared>, server: tiny_http::Server) {
+    while shared.active() {
+        let request = match server.recv_timeout(Duration::from_millis(250)) {
+            Ok(Some(request)) => request,
+            Ok(None) => continue,
+            Err(e) => {
+                tracing::warn!("Share server error: {}", e);
+                break;
+            }
+        };
+
+        let (path, query) = request.url().split_once('?').unwrap_or((request.url(), ""));
+        let authorized = query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .any(|(key, value)| key == "token" && constant_time_eq(value.as_bytes(), shared.token.as_bytes()));
+
+        if !authorized {
+            let _ = request.respond(tiny_http::Response::from_string("Forbidden").with_status_code(403));
+            continue;
+        }
+
+        match path {
+            "/" => {
+                let page = VIEWER_PAGE.replace("TOKEN", &shared.token);
+                let header = tiny_http::Header::from_bytes("Content-Type", "text/html; charset=utf-8").unwrap();
+                let _ = request.respond(tiny_http::Response::from_string(page).with_header(header));
+            }
+            "/stream" => {
+                let shared = shared.clone();
+                std::thread::spawn(move || stream_to_viewer(&shared, request.into_writer()));
+            }
+            _ => {
+                let _ = request.respond(tiny_http::Response::from_string("Not found").with_status_code(404));
+            }
+        }
+    }
+}
+
+fn stream_to_viewer(shared: &Shared, mut writer: Box<dyn Write + Send>) {
+    shared.viewers.fetch_add(1, Ordering::Relaxed);
+
+    let mut last_seq = 0;
+    let mut write_frames = || -> std::io::Result<()> {
+        writer.write_all(
+            b"HTTP/1.1 200 OK\r\nContent-Type: multipart/x-mixed-replace; boundary=frame\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+        )?;
+        while shared.active() {
+            let frame = {
+                let slot = shared.frame.lock().unwrap();
+                (slot.seq != last_seq).then(|| (slot.seq, slot.jpeg.clone()))
+            };
+            match frame {
+                Some((seq, jpeg)) => {
+                    last_seq = seq;
+                    write!(writer, "--frame\r\nContent-Type: image/jpeg\r\nContent-Length: {}\r\n\r\n", jpeg.len())?;
+                    writer.write_all(&jpeg)?;
+                    writer.write_all(b"\r\n")?;
+                    writer.flush()?;
+                }
+                None => std::thread::sleep(Duration::from_millis(100)),
+            }
+        }
+        Ok(())
+    };
+    // A write error just means the viewer went away
+    let _ = write_frames();
+
+    shared.viewers.fetch_sub(1, Ordering::Relaxed);
+}
@@ -0,0 +1,266 @@
+//! An interactive `adb shell` inside DroidView
+//!
+//! `adb shell -tt` makes the device allocate a terminal for the shell even though adb's own
+//! input is a pipe, so the shell prompts, echoes what it is sent and turns Ctrl+C into an
+//! interrupt, the same as in a terminal emulator. What comes back is terminal output:
+//! [`Scrollback`] drops escape sequences and applies carriage returns and backspaces, which is
+//! enough for a shell, but not for full-screen programs like `top` or `vi`.
+//!
+//! One-off commands from the shell command box go through [`run`] instead, without a terminal,
+//! so their output comes back as plain text along with the exit code.
+
+use crate::bridge::adb_command;
+use anyhow::Result;
+use std::io::{Read, Write};
+use std::process::{Child, ChildStdin, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Lines kept in the scrollback before the oldest are dropped
+pub const MAX_LINES: usize = 5000;
+/// What Ctrl+C sends through the terminal
+pub const INTERRUPT: u8 = 0x03;
+/// Commands kept in each device's history in the shell command box
+pub const HISTORY_LIMIT: usize = 50;
+
+/// What a command from the shell command box printed and how it ended
+#[derive(Debug, Clone)]
+pub struct CommandOutput {
+    /// Standard output, then standard error
+    pub text: String,
+    /// `None` when adb could not tell, as with devices older than Android 7
+    pub exit_code: Option<i32>,
+    pub elapsed: Duration,
+}
+
+/// Run `command` in a shell on the device and wait for it to finish
+pub fn run(adb_path: &str, serial: &str, command: &str) -> Result<CommandOutput> {
+    let started = Instant::now();
+    let output = adb_command(adb_path).args(["-s", serial, "shell", command]).stdin(Stdio::null()).output()?;
+    let mut text = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !stderr.trim().is_empty() {
+        if !text.is_empty() && !text.ends_with('\n') {
+            text.push('\n');
+        }
+        text.push_str(&stderr);
+    }
+    Ok(CommandOutput { text, exit_code: output.status.code(), elapsed: started.elapsed() })
+}
+
+/// Text of a shell session, as a terminal would leave it on screen
+#[derive(Debug, Clone)]
+pub struct Scrollback {
+    lines: Vec<String>,
+    /// Characters of the current line written from here overwrite it, after a carriage return
+    column: Option<usize>,
+    escape: Escape,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Escape {
+    None,
+    /// Just after ESC
+    Start,
+    /// Inside `ESC [`, until its final byte
+    Csi,
+    /// Inside `ESC ]`, until BEL or ST
+    Osc,
+    /// ESC inside an OSC, the start of ST
+    OscEnd,
+}
+
+impl Default for Scrollback {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scrollback {
+    pub fn new() -> Self {
+        Self { lines: vec![String::new()], column: None, escape: Escape::None }
+    }
+
+    /// Add output from the shell; escape sequences may be split across calls
+    pub fn push(&mut self, text: &str) {
+        for c in text.chars() {
+            self.escape = match (self.escape, c) {
+                (Escape::None, '\x1b') => Escape::Start,
+                (Escape::None, c) => {
+                    self.put(c);
+                    Escape::None
+                }
+                (Escape::Start, '[') => Escape::Csi,
+                (Escape::Start, ']') => Escape::Osc,
+                (Escape::Start, _) => Escape::None,
+                (Escape::Csi, '\x40'..='\x7e') => Escape::None,
+                (Escape::Csi, _) => Escape::Csi,
+                (Escape::Osc | Escape::OscEnd, '\x07') => Escape::None,
+                (Escape::Osc | Escape::OscEnd, '\x1b') => Escape::OscEnd,
+                (Escape::OscEnd, '\\') => Escape::None,
+                (Escape::Osc | Escape::OscEnd, _) => Escape::Osc,
+            };
+        }
+        if self.lines.len() > MAX_LINES {
+            let excess = self.lines.len() - MAX_LINES;
+            self.lines.drain(..excess);
+        }
+    }
+
+    fn put(&mut self, c: char) {
+        let line = self.lines.last_mut().expect("the scrollback always has a line");
+        match c {
+            '\n' => {
+                self.column = None;
+                self.lines.push(String::new());
+            }
+            '\r' => self.column = Some(0),
+            '\x08' => {
+                let column = self.column.unwrap_or_else(|| line.chars().count());
+                self.column = Some(column.saturating_sub(1));
+            }
+            '\t' => line.push_str("    "),
+            c if c.is_control() => {}
+            c => match self.column {
+                Some(column) => {
+                    let mut chars: Vec<char> = line.chars().collect();
+                    if column < chars.len() {
+                        chars[column] = c;
+                    } else {
+                        chars.push(c);
+                    }
+                    *line = chars.into_iter().collect();
+                    self.column = Some(column + 1);
+                }
+                None => line.push(c),
+            },
+        }
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Everything in the scrollback, one line after the other
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+}
+
+/// A shell running on a device, with its output collected in the background
+pub struct ShellSession {
+    pub serial: String,
+    child: Child,
+    input: ChildStdin,
+    output: Arc<Mutex<String>>,
+    running: Arc<AtomicBool>,
+}
+
+impl std::fmt::Debug for ShellSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShellSession").field("serial", &self.serial).finish()
+    }
+}
+
+impl ShellSession {
+    pub fn start(adb_path: &str, serial: &str) -> Result<Self> {
+        let mut child = adb_command(adb_path)
+            .args(["-s", serial, "shell", "-tt"])
+            // Passed on to the device: keeps programs from coloring their output
+            .env("TERM", "dumb")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let (Some(input), Some(stdout), Some(stderr)) = (child.stdin.take(), child.stdout.take(), child.stderr.take())
+        else {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(anyhow::anyhow!("Could not connect to the adb shell"));
+        };
+
+        let output = Arc::new(Mutex::new(String::new()));
+        let running = Arc::new(AtomicBool::new(true));
+        spawn_reader(stdout, output.clone(), Some(running.clone()));
+        // With a terminal the shell's errors come through stdout; adb's own land here
+        spawn_reader(stderr, output.clone(), None);
+
+        Ok(Self { serial: serial.to_string(), child, input, output, running })
+    }
+
+    /// Send keystrokes to the shell
+    pub fn write(&mut self, bytes: &[u8]) -> Result<()> {
+        self.input.write_all(bytes)?;
+        self.input.flush()?;
+        Ok(())
+    }
+
+    /// Output received since the last call
+    pub fn take_output(&self) -> String {
+        self.output.lock().map(|mut output| std::mem::take(&mut *output)).unwrap_or_default()
+    }
+
+    pub fn is_running(&mut self) -> bool {
+        self.running.load(Ordering::Relaxed) && matches!(self.child.try_wait(), Ok(None))
+    }
+
+    pub fn stop(mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Collect what `source` sends as text, keeping characters split between reads whole
+fn spawn_reader(mut source: impl Read + Send + 'static, output: Arc<Mutex<String>>, running: Option<Arc<AtomicBool>>) {
+    std::thread::spawn(move || {
+        let mut buffer = [0u8; 4096];
+        let mut pending = Vec::new();
+        while let Ok(read) = source.read(&mut buffer) {
+            if read == 0 {
+                break;
+            }
+            pending.extend_from_slice(&buffer[..read]);
+            let complete = match std::str::from_utf8(&pending) {
+                Ok(_) => pending.len(),
+                Err(e) if e.error_len().is_none() => e.valid_up_to(),
+                Err(_) => pending.len(),
+            };
+            let text = String::from_utf8_lossy(&pending[..complete]).to_string();
+            pending.drain(..complete);
+            if let Ok(mut output) = output.lock() {
+                output.push_str(&text);
+            }
+        }
+        if let Some(running) = running {
+            running.store(false, Ordering::Relaxed);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_escape_sequences() {
+        let mut scrollback = Scrollback::new();
+        scrollback.push("\x1b]0;title\x07\x1b[1;32mpix");
+        scrollback.push("el:/ $\x1b[0m ls\r\n");
+        scrollback.push("\x1b[");
+        scrollback.push("01;34msdcard\x1b[0m\r\n");
+        assert_eq!(scrollback.lines(), ["pixel:/ $ ls", "sdcard", ""]);
+    }
+
+    #[test]
+    fn applies_carriage_returns_and_backspaces() {
+        let mut scrollback = Scrollback::new();
+        scrollback.push("10%\r50%\r100%\n");
+        scrollback.push("lss\x08 \x08\n");
+        assert_eq!(scrollback.text(), "100%\nls \n");
+    }
+}
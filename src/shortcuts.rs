@@ -0,0 +1,252 @@
+//! In-app keyboard shortcuts, remappable through `AppConfig::shortcuts`
+//!
+//! Bindings are stored as text like `Ctrl+Enter`. `Ctrl` means the platform command key,
+//! so the same config gives Cmd+Enter on macOS.
+
+use crate::t;
+use egui::{Key, KeyboardShortcut, Modifiers};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortcutAction {
+    RefreshDevices,
+    Screenshot,
+    StartScrcpy,
+    OpenSettings,
+    ShowHelp,
+    ShowShortcuts,
+    CommandPalette,
+}
+
+impl ShortcutAction {
+    pub const ALL: [ShortcutAction; 7] = [
+        ShortcutAction::RefreshDevices,
+        ShortcutAction::Screenshot,
+        ShortcutAction::StartScrcpy,
+        ShortcutAction::OpenSettings,
+        ShortcutAction::ShowHelp,
+        ShortcutAction::ShowShortcuts,
+        ShortcutAction::CommandPalette,
+    ];
+
+    /// Key in `AppConfig::shortcuts`
+    pub fn id(self) -> &'static str {
+        match self {
+            ShortcutAction::RefreshDevices => "refresh_devices",
+            ShortcutAction::Screenshot => "screenshot",
+            ShortcutAction::StartScrcpy => "start_scrcpy",
+            ShortcutAction::OpenSettings => "open_settings",
+            ShortcutAction::ShowHelp => "show_help",
+            ShortcutAction::ShowShortcuts => "show_shortcuts",
+            ShortcutAction::CommandPalette => "command_palette",
+        }
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            ShortcutAction::RefreshDevices => t!("shortcut-refresh-devices"),
+            ShortcutAction::Screenshot => t!("shortcut-screenshot"),
+            ShortcutAction::StartScrcpy => t!("shortcut-start-scrcpy"),
+            ShortcutAction::OpenSettings => t!("shortcut-open-settings"),
+            ShortcutAction::ShowHelp => t!("shortcut-show-help"),
+            ShortcutAction::ShowShortcuts => t!("shortcut-show-shortcuts"),
+            ShortcutAction::CommandPalette => t!("shortcut-command-palette"),
+        }
+    }
+
+    pub fn default_binding(self) -> &'static str {
+        match self {
+            ShortcutAction::RefreshDevices => "F5",
+            ShortcutAction::Screenshot => "Ctrl+S",
+            ShortcutAction::StartScrcpy => "Ctrl+Enter",
+            ShortcutAction::OpenSettings => "Ctrl+,",
+            ShortcutAction::ShowHelp => "F1",
+            ShortcutAction::ShowShortcuts => "Ctrl+/",
+            ShortcutAction::CommandPalette => "Ctrl+P",
+        }
+    }
+}
+
+/// Parse a binding such as `F5`, `Ctrl+S` or `Ctrl+Shift+Enter`
+pub fn parse_shortcut(text: &str) -> Result<KeyboardShortcut, String> {
+    let text = text.trim();
+    let (modifier_names, key_name) = match text.rsplit_once('+') {
+        Some((modifiers, key)) => (modifiers, key.trim()),
+        None => ("", text),
+    };
+    let key = Key::from_name(key_name).ok_or_else(|| format!("Unknown key '{}' in shortcut '{}'", key_name, text))?;
+
+    let mut modifiers = Modifiers::NONE;
+    for name in modifier_names.split('+').map(str::trim).filter(|name| !name.is_empty()) {
+        modifiers |= match name.to_lowercase().as_str() {
+            "ctrl" | "control" | "cmd" | "command" => Modifiers::COMMAND,
+            "alt" | "option" => Modifiers::ALT,
+            "shift" => Modifiers::SHIFT,
+            _ => return Err(format!("Unknown modifier '{}' in shortcut '{}'", name, text)),
+        };
+    }
+    Ok(KeyboardShortcut::new(modifiers, key))
+}
+
+/// The text form `parse_shortcut` reads back
+pub fn shortcut_to_string(shortcut: &KeyboardShortcut) -> String {
+    let mut text = String::new();
+    if shortcut.modifiers.command || shortcut.modifiers.ctrl {
+        text.push_str("Ctrl+");
+    }
+    if shortcut.modifiers.alt {
+        text.push_str("Alt+");
+    }
+    if shortcut.modifiers.shift {
+        text.push_str("Shift+");
+    }
+    text.push_str(shortcut.logical_key.symbol_or_name());
+    text
+}
+
+/// The shortcut for a key press, with Ctrl and Cmd both meaning the command key
+pub fn shortcut_from_press(modifiers: Modifiers, key: Key) -> KeyboardShortcut {
+    let mut normalized = Modifiers::NONE;
+    if modifiers.command || modifiers.ctrl {
+        normalized |= Modifiers::COMMAND;
+    }
+    if modifiers.alt {
+        normalized |= Modifiers::ALT;
+    }
+    if modifiers.shift {
+        normalized |= Modifiers::SHIFT;
+    }
+    KeyboardShortcut::new(normalized, key)
+}
+
+/// The active binding of every action
+#[derive(Debug, Clone)]
+pub struct Shortcuts {
+    bindings: Vec<(ShortcutAction, Option<KeyboardShortcut>)>,
+}
+
+impl Default for Shortcuts {
+    fn default() -> Self {
+        Self::from_config(&BTreeMap::new()).0
+    }
+}
+
+impl Shortcuts {
+    /// Defaults overridden by `overrides` (action id to binding, empty for none); also returns
+    /// a message for every override that could not be used
+    pub fn from_config(overrides: &BTreeMap<String, String>) -> (Self, Vec<String>) {
+        let mut errors = Vec::new();
+        let mut bindings: Vec<(ShortcutAction, Option<KeyboardShortcut>)> = Vec::new();
+        for action in ShortcutAction::ALL {
+            let text = overrides.get(action.id()).map(String::as_str).unwrap_or(action.default_binding());
+            let shortcut = if text.trim().is_empty() {
+                None
+            } else {
+                match parse_shortcut(text) {
+                    Ok(shortcut) => Some(shortcut),
+                    Err(e) => {
+                        errors.push(e);
+                        parse_shortcut(action.default_binding()).ok()
+                    }
+                }
+            };
+            if let Some(shortcut) = shortcut
+                && let Some((other, _)) = bindings.iter().find(|(_, bound)| *bound == Some(shortcut))
+            {
+                errors.push(format!(
+                    "{} is bound to both \"{}\" and \"{}\"; keeping the first",
+                    shortcut_to_string(&shortcut),
+                    other.label(),
+                    action.label()
+                ));
+                bindings.push((action, None));
+                continue;
+            }
+            bindings.push((action, shortcut));
+        }
+        (Self { bindings }, errors)
+    }
+
+    pub fn binding(&self, action: ShortcutAction) -> Option<KeyboardShortcut> {
+        self.bindings
+            .iter()
+            .find(|(bound, _)| *bound == action)
+            .and_then(|(_, shortcut)| *shortcut)
+    }
+
+    /// The action bound to `shortcut`, if any
+    pub fn action_for(&self, shortcut: &KeyboardShortcut) -> Option<ShortcutAction> {
+        self.bindings
+            .iter()
+            .find(|(_, bound)| bound.as_ref() == Some(shortcut))
+            .map(|(action, _)| *action)
+    }
+
+    /// Take the first shortcut pressed this frame out of the input; none while a text field
+    /// has the focus, so typing and its editing keys stay with the field
+    pub fn consume(&self, ctx: &egui::Context) -> Option<ShortcutAction> {
+        if ctx.wants_keyboard_input() {
+            return None;
+        }
+        // Bindings with more modifiers first, so Ctrl+Shift+S is not read as Ctrl+S
+        let mut bound: Vec<(ShortcutAction, KeyboardShortcut)> = self
+            .bindings
+            .iter()
+            .filter_map(|(action, shortcut)| shortcut.map(|shortcut| (*action, shortcut)))
+            .collect();
+        bound.sort_by_key(|(_, shortcut)| std::cmp::Reverse(modifier_count(shortcut.modifiers)));
+
+        ctx.input_mut(|input| {
+            bound
+                .into_iter()
+                .find(|(_, shortcut)| input.consume_shortcut(shortcut))
+                .map(|(action, _)| action)
+        })
+    }
+}
+
+fn modifier_count(modifiers: Modifiers) -> usize {
+    [modifiers.command || modifiers.ctrl, modifiers.alt, modifiers.shift]
+        .into_iter()
+        .filter(|held| *held)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shortcut_text() {
+        assert_eq!(parse_shortcut("F5"), Ok(KeyboardShortcut::new(Modifiers::NONE, Key::F5)));
+        assert_eq!(parse_shortcut("ctrl + ,"), Ok(KeyboardShortcut::new(Modifiers::COMMAND, Key::Comma)));
+        assert_eq!(
+            parse_shortcut("Ctrl+Shift+Enter"),
+            Ok(KeyboardShortcut::new(Modifiers::COMMAND | Modifiers::SHIFT, Key::Enter))
+        );
+        assert!(parse_shortcut("Hyper+S").is_err());
+        assert!(parse_shortcut("Ctrl+Nope").is_err());
+
+        for action in ShortcutAction::ALL {
+            let shortcut = parse_shortcut(action.default_binding()).unwrap();
+            assert_eq!(shortcut_to_string(&shortcut), action.default_binding());
+        }
+    }
+
+    #[test]
+    fn overrides_and_conflicts() {
+        let overrides = BTreeMap::from([
+            ("screenshot".to_string(), "Ctrl+J".to_string()),
+            ("refresh_devices".to_string(), String::new()),
+            ("open_settings".to_string(), "Ctrl+J".to_string()),
+        ]);
+        let (shortcuts, errors) = Shortcuts::from_config(&overrides);
+        assert_eq!(shortcuts.binding(ShortcutAction::RefreshDevices), None);
+        assert_eq!(
+            shortcuts.binding(ShortcutAction::Screenshot),
+            Some(KeyboardShortcut::new(Modifiers::COMMAND, Key::J))
+        );
+        assert_eq!(shortcuts.binding(ShortcutAction::OpenSettings), None);
+        assert_eq!(errors.len(), 1);
+    }
+}
@@ -0,0 +1,163 @@
+//! `adb sideload` of OTA and ROM zips through recovery
+//!
+//! The device is rebooted into recovery's sideload mode, which adb lists with the state
+//! `sideload`, and the zip is streamed to it. adb reports progress as `serving: 'x.zip'  (~42%)`
+//! lines ending in `\r`; the recovery installs the package once the transfer is done.
+
+use crate::bridge::adb_command;
+use crate::device::{get_device_states, DeviceStatus};
+use anyhow::Result;
+use std::io::Read;
+use std::path::Path;
+use std::process::{Child, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// How long the device gets to come back in sideload mode
+pub const WAIT_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SideloadStage {
+    Rebooting,
+    Waiting,
+    /// Percent of the zip sent
+    Transferring(u8),
+}
+
+/// The last percentage in a chunk of `adb sideload` output
+pub fn parse_progress(output: &str) -> Option<u8> {
+    output.rsplit("(~").find_map(|rest| {
+        let (percent, _) = rest.split_once("%)")?;
+        percent.trim().parse::<u8>().ok().map(|percent| percent.min(100))
+    })
+}
+
+pub fn in_sideload_mode(adb_path: &str, serial: &str) -> bool {
+    get_device_states(adb_path).is_ok_and(|states| {
+        states
+            .iter()
+            .any(|(id, status)| id == serial && matches!(status, DeviceStatus::Unknown(state) if state == "sideload"))
+    })
+}
+
+/// `adb reboot sideload`, which takes the device straight to recovery's sideload mode
+pub fn reboot_to_sideload(adb_path: &str, serial: &str) -> Result<()> {
+    let status = adb_command(adb_path).args(["-s", serial, "reboot", "sideload"]).status()?;
+    if !status.success() {
+        return Err(anyhow::anyhow!("Could not reboot {} into sideload mode", serial));
+    }
+    Ok(())
+}
+
+pub fn wait_for_sideload(adb_path: &str, serial: &str, cancel: &AtomicBool) -> Result<()> {
+    let started = Instant::now();
+    while !in_sideload_mode(adb_path, serial) {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(anyhow::anyhow!("Cancelled"));
+        }
+        if started.elapsed() > WAIT_TIMEOUT {
+            return Err(anyhow::anyhow!(
+                "{} did not show up in sideload mode; choose \"Apply update from ADB\" in recovery",
+                serial
+            ));
+        }
+        std::thread::sleep(Duration::from_secs(1));
+    }
+    Ok(())
+}
+
+/// What an adb command that reports progress printed, once it exited
+pub struct Finished {
+    pub status: ExitStatus,
+    pub output: String,
+    pub errors: String,
+}
+
+/// Read the output of `child` as it comes, passing each new percentage `parse` finds in it to
+/// `on_progress`, until it exits. Setting `cancel` kills it.
+pub fn follow_progress(
+    mut child: Child,
+    cancel: &AtomicBool,
+    parse: impl Fn(&str) -> Option<u8>,
+    mut on_progress: impl FnMut(u8),
+) -> Result<Finished> {
+    let mut stdout = child.stdout.take().ok_or_else(|| anyhow::anyhow!("No output from adb"))?;
+    let mut output = String::new();
+    let mut buffer = [0u8; 512];
+    let mut last = None;
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(anyhow::anyhow!("Cancelled"));
+        }
+        let read = stdout.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        let chunk = String::from_utf8_lossy(&buffer[..read]);
+        output.push_str(&chunk);
+        if let Some(percent) = parse(&chunk)
+            && last != Some(percent)
+        {
+            last = Some(percent);
+            on_progress(percent);
+        }
+    }
+
+    let status = child.wait()?;
+    let mut errors = String::new();
+    if let Some(mut stderr) = child.stderr.take() {
+        let _ = stderr.read_to_string(&mut errors);
+    }
+    Ok(Finished { status, output, errors })
+}
+
+/// Stream `zip` to a device in sideload mode, reporting each new percentage
+pub fn sideload(
+    adb_path: &str,
+    serial: &str,
+    zip: &Path,
+    cancel: &AtomicBool,
+    on_progress: impl FnMut(u8),
+) -> Result<()> {
+    let child = adb_command(adb_path)
+        .args(["-s", serial, "sideload"])
+        .arg(zip)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let Finished { status, output, errors } = follow_progress(child, cancel, parse_progress, on_progress)?;
+    // Some recoveries close the connection once they have everything, which adb reports as an error
+    if status.success() || output.contains("Total xfer") {
+        return Ok(());
+    }
+    let last_message = |text: &str| {
+        text.split(['\r', '\n'])
+            .map(str::trim)
+            .rfind(|line| !line.is_empty() && !line.starts_with("serving:"))
+            .map(str::to_string)
+    };
+    let reason = last_message(&errors)
+        .or_else(|| last_message(&output))
+        .unwrap_or_else(|| "adb sideload failed".to_string());
+    Err(anyhow::anyhow!(reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress() {
+        assert_eq!(parse_progress("serving: 'ota.zip'  (~7%)    \r"), Some(7));
+        assert_eq!(
+            parse_progress("serving: 'ota.zip'  (~41%)    \rserving: 'ota.zip'  (~42%)    \r"),
+            Some(42)
+        );
+        assert_eq!(parse_progress("Total xfer: 1.00x\n"), None);
+        assert_eq!(parse_progress("loading: 'ota.zip'\n"), None);
+    }
+}
@@ -0,0 +1,196 @@
+//! Audio forwarding with sndcpy, for devices that scrcpy cannot capture audio from
+//!
+//! scrcpy forwards audio from Android 11 on, and only from version 2.0. sndcpy covers Android 10:
+//! its app captures the playback and serves it as raw 48 kHz stereo PCM on the `sndcpy` abstract
+//! socket, which is forwarded to port 28200 here and played with VLC or ffplay. The port is
+//! fixed, so one device can forward audio at a time.
+
+use crate::bridge::adb_command;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::time::Duration;
+
+pub const PACKAGE: &str = "com.rom1v.sndcpy";
+pub const DOWNLOAD_URL: &str = "https://github.com/rom1v/sndcpy/releases/latest";
+const PORT: u16 = 28200;
+const SAMPLE_RATE: u32 = 48000;
+/// Android 10, where capturing other apps' playback became possible
+const MIN_API_LEVEL: u32 = 29;
+/// Android 11, from which scrcpy 2.0 captures audio itself
+const NATIVE_API_LEVEL: u32 = 30;
+
+/// Whether a device needs sndcpy for audio, given its API level and the scrcpy major version
+pub fn needed(api_level: Option<u32>, scrcpy_major: Option<u32>) -> bool {
+    match api_level {
+        Some(level) if level >= NATIVE_API_LEVEL => scrcpy_major.is_some_and(|major| major < 2),
+        Some(level) => level >= MIN_API_LEVEL,
+        None => false,
+    }
+}
+
+/// Why sndcpy cannot help this device, if it cannot
+pub fn unsupported_reason(api_level: Option<u32>) -> Option<String> {
+    match api_level {
+        Some(level) if level < MIN_API_LEVEL => {
+            Some(format!("Audio forwarding needs Android 10 (API {}) or newer", MIN_API_LEVEL))
+        }
+        None => Some("The device's Android version is unknown".to_string()),
+        Some(_) => None,
+    }
+}
+
+/// The configured APK, else `sndcpy.apk` shipped next to DroidView or scrcpy
+pub fn locate_apk(configured: Option<&str>) -> Option<PathBuf> {
+    configured
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .filter(|path| path.is_file())
+        .or_else(|| crate::utils::find_bundled_file("sndcpy.apk"))
+}
+
+/// VLC, else ffplay
+pub fn find_player() -> Option<PathBuf> {
+    #[cfg(target_os = "macos")]
+    {
+        let app = Path::new("/Applications/VLC.app/Contents/MacOS/VLC");
+        if app.is_file() {
+            return Some(app.to_path_buf());
+        }
+    }
+    crate::utils::find_executable("vlc").or_else(|| crate::utils::find_executable("ffplay"))
+}
+
+/// Arguments that make `player` play the raw stream on the forwarded port
+pub fn player_args(player: &Path) -> Vec<String> {
+    let url = format!("tcp://localhost:{}", PORT);
+    let name = player.file_stem().map(|stem| stem.to_string_lossy().to_lowercase()).unwrap_or_default();
+    let rate = SAMPLE_RATE.to_string();
+    let args = if name == "ffplay" {
+        vec![
+            "-nodisp", "-autoexit", "-loglevel", "error", "-f", "s16le", "-ar", &rate, "-ch_layout", "stereo",
+            "-probesize", "32", "-fflags", "nobuffer", &url,
+        ]
+    } else {
+        vec!["-Idummy", "--demux", "rawaud", "--network-caching=50", "--play-and-exit", &url]
+    };
+    args.into_iter().map(str::to_string).collect()
+}
+
+/// Audio being forwarded from one device
+#[derive(Debug)]
+pub struct AudioSession {
+    pub serial: String,
+    player: Child,
+}
+
+impl AudioSession {
+    /// Install the app when missing, forward its socket, start capturing and start the player
+    pub fn start(adb_path: &str, serial: &str, apk: Option<&Path>, player: &Path) -> Result<Self> {
+        let shell = |args: &[&str]| adb_command(adb_path).args(["-s", serial, "shell"]).args(args).output();
+        let installed = shell(&["pm", "path", PACKAGE])?;
+        if !String::from_utf8_lossy(&installed.stdout).contains("package:") {
+            let apk = apk.ok_or_else(|| anyhow::anyhow!("sndcpy.apk not found; set its path in Settings"))?;
+            let output = adb_command(adb_path).args(["-s", serial, "install", "-t", "-r", "-g"]).arg(apk).output()?;
+            if !output.status.success() {
+                return Err(anyhow::anyhow!(
+                    "Could not install sndcpy: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ));
+            }
+        }
+        // Skips the screen capture prompt
+        shell(&["appops", "set", PACKAGE, "PROJECT_MEDIA", "allow"])?;
+
+        let status = adb_command(adb_path)
+            .args(["-s", serial, "forward", &format!("tcp:{}", PORT), "localabstract:sndcpy"])
+            .status()?;
+        if !status.success() {
+            return Err(anyhow::anyhow!("adb forward failed"));
+        }
+        let output = shell(&["am", "start", &format!("{}/.MainActivity", PACKAGE)])?;
+        if !output.status.success() {
+            remove_forward(adb_path, serial);
+            return Err(anyhow::anyhow!("Could not start sndcpy on the device"));
+        }
+        // The app needs a moment before it listens
+        std::thread::sleep(Duration::from_millis(1000));
+
+        let mut player = match Command::new(player)
+            .args(player_args(player))
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        {
+            Ok(child) => child,
+            Err(e) => {
+                stop_device(adb_path, serial);
+                return Err(anyhow::anyhow!("Could not start {}: {}", player.display(), e));
+            }
+        };
+        std::thread::sleep(Duration::from_millis(300));
+        if let Ok(Some(status)) = player.try_wait() {
+            stop_device(adb_path, serial);
+            return Err(anyhow::anyhow!("The audio player exited ({})", status));
+        }
+        Ok(Self {
+            serial: serial.to_string(),
+            player,
+        })
+    }
+
+    /// Whether the player is still playing
+    pub fn is_running(&mut self) -> bool {
+        matches!(self.player.try_wait(), Ok(None))
+    }
+
+    pub fn stop(mut self, adb_path: Option<&str>) {
+        let _ = self.player.kill();
+        let _ = self.player.wait();
+        if let Some(adb_path) = adb_path {
+            stop_device(adb_path, &self.serial);
+        }
+    }
+}
+
+fn remove_forward(adb_path: &str, serial: &str) {
+    let _ = adb_command(adb_path)
+        .args(["-s", serial, "forward", "--remove", &format!("tcp:{}", PORT)])
+        .status();
+}
+
+/// Stop capturing on the device and remove the forward
+fn stop_device(adb_path: &str, serial: &str) {
+    let _ = adb_command(adb_path)
+        .args(["-s", serial, "shell", "am", "force-stop", PACKAGE])
+        .status();
+    remove_forward(adb_path, serial);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn needed_only_without_native_audio() {
+        assert!(needed(Some(29), Some(3)));
+        assert!(!needed(Some(30), Some(2)));
+        assert!(needed(Some(33), Some(1)));
+        assert!(!needed(Some(28), Some(1)));
+        assert!(!needed(None, Some(1)));
+        assert!(unsupported_reason(Some(28)).is_some());
+        assert_eq!(unsupported_reason(Some(29)), None);
+    }
+
+    #[test]
+    fn builds_player_args() {
+        let vlc = player_args(Path::new("/usr/bin/vlc"));
+        assert_eq!(vlc.first().map(String::as_str), Some("-Idummy"));
+        assert_eq!(vlc.last().map(String::as_str), Some("tcp://localhost:28200"));
+        let ffplay = player_args(Path::new("/opt/ffmpeg/bin/ffplay"));
+        assert!(ffplay.windows(2).any(|pair| pair == ["-ar", "48000"]));
+        assert_eq!(ffplay.last().map(String::as_str), Some("tcp://localhost:28200"));
+    }
+}
@@ -0,0 +1,198 @@
+//! Doze and app standby, for testing how apps behave when the system holds back their work
+//!
+//! `dumpsys deviceidle force-idle` puts the device into deep Doze right away, whatever the screen
+//! and charger are doing, and it stays there until `unforce`. Whitelisted packages are exempt
+//! from Doze; the system's own entries cannot be removed. Standby buckets, from `active` to
+//! `restricted`, decide how often an app's jobs and alarms may run.
+
+use crate::automation::shell;
+use crate::packages::is_package_name;
+use anyhow::Result;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StandbyBucket {
+    /// Exempt from standby, for system apps
+    Exempted,
+    Active,
+    WorkingSet,
+    Frequent,
+    Rare,
+    Restricted,
+    /// Never used since being installed
+    Never,
+}
+
+impl StandbyBucket {
+    /// Buckets `am set-standby-bucket` accepts; `restricted` needs Android 11
+    pub const SETTABLE: [StandbyBucket; 5] = [
+        StandbyBucket::Active,
+        StandbyBucket::WorkingSet,
+        StandbyBucket::Frequent,
+        StandbyBucket::Rare,
+        StandbyBucket::Restricted,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            StandbyBucket::Exempted => "exempted",
+            StandbyBucket::Active => "active",
+            StandbyBucket::WorkingSet => "working_set",
+            StandbyBucket::Frequent => "frequent",
+            StandbyBucket::Rare => "rare",
+            StandbyBucket::Restricted => "restricted",
+            StandbyBucket::Never => "never",
+        }
+    }
+
+    /// Constant of `UsageStatsManager`, which `am get-standby-bucket` prints
+    fn from_code(code: u32) -> Option<StandbyBucket> {
+        match code {
+            5 => Some(StandbyBucket::Exempted),
+            10 => Some(StandbyBucket::Active),
+            20 => Some(StandbyBucket::WorkingSet),
+            30 => Some(StandbyBucket::Frequent),
+            40 => Some(StandbyBucket::Rare),
+            45 => Some(StandbyBucket::Restricted),
+            50 => Some(StandbyBucket::Never),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WhitelistEntry {
+    pub package: String,
+    /// Added by the user or over adb, so it can be removed again
+    pub user: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StandbyInfo {
+    /// Deep and light Doze states such as `ACTIVE` or `IDLE`
+    pub deep: String,
+    pub light: String,
+    pub whitelist: Vec<WhitelistEntry>,
+    /// Package whose bucket was read
+    pub package: Option<String>,
+    pub bucket: Option<StandbyBucket>,
+}
+
+impl StandbyInfo {
+    pub fn is_whitelisted(&self, package: &str) -> bool {
+        self.whitelist.iter().any(|entry| entry.package == package)
+    }
+}
+
+/// Doze states and the whitelist, plus the standby bucket of `package` when given
+pub fn read(adb_path: &str, serial: &str, package: Option<&str>) -> Result<StandbyInfo> {
+    let states = shell(adb_path, serial, "dumpsys deviceidle get deep; dumpsys deviceidle get light")?;
+    let mut states = states.lines().map(|line| line.trim().to_string());
+    let whitelist = shell(adb_path, serial, "dumpsys deviceidle whitelist").unwrap_or_default();
+    let bucket = match package {
+        Some(package) => {
+            check_package(package)?;
+            parse_bucket(&shell(adb_path, serial, &format!("am get-standby-bucket {}", package))?)
+        }
+        None => None,
+    };
+    Ok(StandbyInfo {
+        deep: states.next().unwrap_or_default(),
+        light: states.next().unwrap_or_default(),
+        whitelist: parse_whitelist(&whitelist),
+        package: package.map(str::to_string),
+        bucket,
+    })
+}
+
+/// Enter deep Doze now, or leave it again
+pub fn force_idle(adb_path: &str, serial: &str, idle: bool) -> Result<()> {
+    if !idle {
+        shell(adb_path, serial, "dumpsys deviceidle unforce")?;
+        return Ok(());
+    }
+    let mut output = shell(adb_path, serial, "dumpsys deviceidle force-idle")?;
+    // Doze can be switched off on emulators and in developer builds
+    if output.contains("Unable") {
+        shell(adb_path, serial, "dumpsys deviceidle enable")?;
+        output = shell(adb_path, serial, "dumpsys deviceidle force-idle")?;
+    }
+    if output.contains("Unable") {
+        return Err(anyhow::anyhow!(output));
+    }
+    Ok(())
+}
+
+pub fn set_whitelisted(adb_path: &str, serial: &str, package: &str, whitelisted: bool) -> Result<()> {
+    check_package(package)?;
+    let sign = if whitelisted { '+' } else { '-' };
+    let output = shell(adb_path, serial, &format!("dumpsys deviceidle whitelist {}{}", sign, package))?;
+    if output.contains("Unknown package") || output.contains("Failed") {
+        return Err(anyhow::anyhow!(output));
+    }
+    Ok(())
+}
+
+pub fn set_bucket(adb_path: &str, serial: &str, package: &str, bucket: StandbyBucket) -> Result<()> {
+    check_package(package)?;
+    shell(adb_path, serial, &format!("am set-standby-bucket {} {}", package, bucket.name()))?;
+    Ok(())
+}
+
+fn check_package(package: &str) -> Result<()> {
+    if !is_package_name(package) {
+        return Err(anyhow::anyhow!("'{}' is not a package name such as com.example.app", package));
+    }
+    Ok(())
+}
+
+/// `kind,package,uid` lines, each package once; a user entry wins over a system one
+pub fn parse_whitelist(output: &str) -> Vec<WhitelistEntry> {
+    let mut entries: Vec<WhitelistEntry> = Vec::new();
+    for line in output.lines() {
+        let mut fields = line.trim().split(',');
+        let (Some(kind), Some(package)) = (fields.next(), fields.next()) else {
+            continue;
+        };
+        let user = kind == "user";
+        match entries.iter_mut().find(|entry| entry.package == package) {
+            Some(entry) => entry.user |= user,
+            None => entries.push(WhitelistEntry { package: package.to_string(), user }),
+        }
+    }
+    entries
+}
+
+/// The bucket number, or a name on the few versions that print one
+pub fn parse_bucket(output: &str) -> Option<StandbyBucket> {
+    let output = output.trim();
+    match output.parse() {
+        Ok(code) => StandbyBucket::from_code(code),
+        Err(_) => StandbyBucket::SETTABLE.into_iter().find(|bucket| bucket.name().eq_ignore_ascii_case(output)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_whitelist() {
+        let output = "system-excidle,com.android.shell,2000\n\
+                      system,com.android.shell,2000\n\
+                      system,com.google.android.gms,10138\n\
+                      user,com.example.app,10234\n";
+        let whitelist = parse_whitelist(output);
+        assert_eq!(whitelist.len(), 3);
+        assert!(!whitelist[0].user);
+        assert_eq!(whitelist[2], WhitelistEntry { package: "com.example.app".to_string(), user: true });
+    }
+
+    #[test]
+    fn parses_buckets() {
+        assert_eq!(parse_bucket("10\n"), Some(StandbyBucket::Active));
+        assert_eq!(parse_bucket("45"), Some(StandbyBucket::Restricted));
+        assert_eq!(parse_bucket("5"), Some(StandbyBucket::Exempted));
+        assert_eq!(parse_bucket("rare"), Some(StandbyBucket::Rare));
+        assert_eq!(parse_bucket("Unknown package"), None);
+    }
+}
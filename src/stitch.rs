@@ -0,0 +1,210 @@
+//! Long screenshots of scrolling content, stitched from several screens
+//!
+//! Each screen is captured, scrolled up with a slow `input swipe` so it does not fling, and
+//! captured again until the content stops moving or `MAX_FRAMES` is reached. Rows that stay put
+//! between two captures at the top and bottom are the status bar, toolbars and navigation bar;
+//! between them the scroll distance is the shift that lines up the most rows of the two captures.
+//! Rows are compared by hash, leaving out the right edge where scroll bars fade in.
+
+use crate::automation::{screencap, shell};
+use anyhow::Result;
+use image::RgbaImage;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Screens captured at most for one long screenshot
+pub const MAX_FRAMES: usize = 10;
+/// Swipe length as a fraction of the screen height, leaving plenty of overlap to line up
+const SWIPE_FRACTION: f32 = 0.4;
+const SWIPE_DURATION_MS: u32 = 700;
+/// Time for the content to come to rest after a swipe
+const SETTLE: Duration = Duration::from_millis(500);
+/// Rows with content that have to line up for a shift to count
+const MIN_MATCHED_ROWS: usize = 24;
+/// Share of the compared content rows that have to line up
+const MIN_MATCH_RATIO: f32 = 0.9;
+
+/// How two consecutive captures line up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overlap {
+    /// Rows at the top and bottom that did not move
+    pub top: u32,
+    pub bottom: u32,
+    /// Pixels the content in between scrolled by
+    pub shift: u32,
+}
+
+/// Capture, scroll and capture again on `serial`, then save the stitched image to `dir`.
+/// Returns where it was saved and how many screens went into it.
+pub fn long_screenshot(
+    adb_path: &str,
+    serial: &str,
+    model: &str,
+    dir: &Path,
+    template: &str,
+) -> Result<(PathBuf, usize)> {
+    let first = capture(adb_path, serial)?;
+    let (width, height) = first.dimensions();
+    let x = width / 2;
+    let from = (height as f32 * (0.5 + SWIPE_FRACTION / 2.0)) as u32;
+    let to = (height as f32 * (0.5 - SWIPE_FRACTION / 2.0)) as u32;
+
+    let mut frames = vec![first];
+    let mut overlaps = Vec::new();
+    while frames.len() < MAX_FRAMES {
+        shell(adb_path, serial, &format!("input swipe {} {} {} {} {}", x, from, x, to, SWIPE_DURATION_MS))?;
+        std::thread::sleep(SETTLE);
+        let next = capture(adb_path, serial)?;
+        // The end of the content, or a jump too far to line up
+        let Some(overlap) = frames.last().and_then(|previous| find_overlap(previous, &next)) else {
+            break;
+        };
+        overlaps.push(overlap);
+        frames.push(next);
+    }
+    if overlaps.is_empty() {
+        return Err(anyhow::anyhow!("The screen did not scroll; open a scrollable list or page first"));
+    }
+
+    let image = stitch(&frames, &overlaps);
+    let file_name = crate::utils::expand_filename_template(
+        &format!("{}_long", template),
+        serial,
+        model,
+        chrono::Local::now(),
+        "png",
+    );
+    let path = crate::utils::unique_path(dir.join(file_name));
+    std::fs::create_dir_all(dir)?;
+    image.save(&path)?;
+    crate::gallery::record_capture(&path, crate::gallery::CaptureKind::Screenshot, serial, None);
+    Ok((path, frames.len()))
+}
+
+fn capture(adb_path: &str, serial: &str) -> Result<RgbaImage> {
+    Ok(image::load_from_memory(&screencap(adb_path, serial)?)?.to_rgba8())
+}
+
+/// Hash of each row, and whether it has more than one color
+fn row_hashes(image: &RgbaImage) -> Vec<(u64, bool)> {
+    let (width, _) = image.dimensions();
+    let compared = (width - width / 40) as usize * 4;
+    image
+        .as_raw()
+        .chunks_exact(width as usize * 4)
+        .map(|row| {
+            let row = &row[..compared];
+            let mut hasher = DefaultHasher::new();
+            row.hash(&mut hasher);
+            let varied = row.chunks_exact(4).any(|pixel| pixel != &row[..4]);
+            (hasher.finish(), varied)
+        })
+        .collect()
+}
+
+/// How `next` continues `previous`, or `None` when nothing scrolled or no shift lines them up
+pub fn find_overlap(previous: &RgbaImage, next: &RgbaImage) -> Option<Overlap> {
+    if previous.dimensions() != next.dimensions() {
+        return None;
+    }
+    let (before, after) = (row_hashes(previous), row_hashes(next));
+    let height = before.len();
+    let same = |row: &usize| before[*row].0 == after[*row].0;
+    let top = (0..height).take_while(same).count();
+    if top == height {
+        return None;
+    }
+    let bottom = (top..height).rev().take_while(same).count();
+    let content = top..height - bottom;
+
+    // The shift that lines up the most rows with content, preferring the smallest
+    let mut best: Option<(usize, usize)> = None;
+    for shift in 1..content.len() {
+        let (mut compared, mut matched) = (0, 0);
+        for row in content.start..content.end - shift {
+            let (hash, varied) = after[row];
+            if varied {
+                compared += 1;
+                if hash == before[row + shift].0 {
+                    matched += 1;
+                }
+            }
+        }
+        let enough = matched >= MIN_MATCHED_ROWS && matched as f32 >= compared as f32 * MIN_MATCH_RATIO;
+        if enough && best.is_none_or(|(_, most)| matched > most) {
+            best = Some((shift, matched));
+        }
+    }
+    best.map(|(shift, _)| Overlap { top: top as u32, bottom: bottom as u32, shift: shift as u32 })
+}
+
+/// One tall image of `frames`, where `overlaps[i]` lines up frame `i + 1` with frame `i`
+pub fn stitch(frames: &[RgbaImage], overlaps: &[Overlap]) -> RgbaImage {
+    let (width, height) = frames[0].dimensions();
+    // The tallest footer of any pair, so rows that only sometimes stayed put are not repeated
+    let footer = overlaps.iter().map(|overlap| overlap.bottom).max().unwrap_or(0);
+    let total = height + overlaps.iter().map(|overlap| overlap.shift).sum::<u32>();
+    let mut stitched = RgbaImage::new(width, total);
+    let mut copy = |frame: &RgbaImage, from: u32, rows: u32, to: u32| {
+        for y in 0..rows {
+            for x in 0..width {
+                stitched.put_pixel(x, to + y, *frame.get_pixel(x, from + y));
+            }
+        }
+    };
+
+    copy(&frames[0], 0, height - footer, 0);
+    let mut y = height - footer;
+    for (frame, overlap) in frames[1..].iter().zip(overlaps) {
+        copy(frame, (height - footer).saturating_sub(overlap.shift), overlap.shift, y);
+        y += overlap.shift;
+    }
+    if let Some(last) = frames.last() {
+        copy(last, height - footer, footer, y);
+    }
+    stitched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: u32 = 20;
+    const FOOTER: u32 = 16;
+
+    fn pixel(x: u32, row: u32) -> image::Rgba<u8> {
+        image::Rgba([(row * 37 % 251) as u8, (x * 3 + row * 11) as u8, (row / 7) as u8, 255])
+    }
+
+    /// A screen of a page scrolled down by `scroll`, with fixed bars at the top and bottom
+    fn screen(scroll: u32) -> RgbaImage {
+        RgbaImage::from_fn(80, 200, |x, y| {
+            pixel(x, if (HEADER..200 - FOOTER).contains(&y) { y + scroll } else { y * 1000 })
+        })
+    }
+
+    #[test]
+    fn finds_the_scroll_between_screens() {
+        let overlap = find_overlap(&screen(0), &screen(70)).unwrap();
+        assert_eq!(overlap, Overlap { top: HEADER, bottom: FOOTER, shift: 70 });
+        assert_eq!(find_overlap(&screen(0), &screen(0)), None);
+        // Nothing left in common
+        assert_eq!(find_overlap(&screen(0), &screen(500)), None);
+    }
+
+    #[test]
+    fn stitches_screens_into_the_page() {
+        let frames = [screen(0), screen(70), screen(130)];
+        let overlaps: Vec<Overlap> = frames.windows(2).map(|pair| find_overlap(&pair[0], &pair[1]).unwrap()).collect();
+        let stitched = stitch(&frames, &overlaps);
+        assert_eq!(stitched.dimensions(), (80, 330));
+        // The header, the whole page, then the footer
+        let page = RgbaImage::from_fn(80, 330, |x, y| match y {
+            0..HEADER => pixel(x, y * 1000),
+            314.. => pixel(x, (y - 130) * 1000),
+            _ => pixel(x, y),
+        });
+        assert_eq!(stitched, page);
+    }
+}
@@ -0,0 +1,405 @@
+use crate::bridge::adb_command;
+use crate::config::StreamConfig;
+use anyhow::Result;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// How long to wait for scrcpy to write the first bytes of the recording
+const RECORD_START_TIMEOUT: Duration = Duration::from_secs(15);
+/// Where named pipes are missing the recording is a regular file that only grows; the stream
+/// stops once it reaches this size, about an hour and a half at the default bitrates
+const MAX_RECORDING_SIZE: u64 = 4 * 1024 * 1024 * 1024;
+/// Recordings left behind by a session that never stopped are removed once this old
+const STALE_RECORDING_AGE: Duration = Duration::from_secs(60 * 60);
+const RECORDING_PREFIX: &str = "droidview-stream-";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamProtocol {
+    Rtmp,
+    Rtmps,
+    Srt,
+}
+
+impl StreamProtocol {
+    pub fn from_url(url: &str) -> Option<Self> {
+        let scheme = url.trim().split("://").next()?.to_lowercase();
+        match scheme.as_str() {
+            "rtmp" => Some(StreamProtocol::Rtmp),
+            "rtmps" => Some(StreamProtocol::Rtmps),
+            "srt" => Some(StreamProtocol::Srt),
+            _ => None,
+        }
+    }
+
+    /// Name of the protocol in `ffmpeg -protocols`
+    pub fn ffmpeg_name(self) -> &'static str {
+        match self {
+            StreamProtocol::Rtmp => "rtmp",
+            StreamProtocol::Rtmps => "rtmps",
+            StreamProtocol::Srt => "srt",
+        }
+    }
+
+    /// Container ffmpeg muxes into for this protocol
+    pub fn muxer(self) -> &'static str {
+        match self {
+            StreamProtocol::Rtmp | StreamProtocol::Rtmps => "flv",
+            StreamProtocol::Srt => "mpegts",
+        }
+    }
+}
+
+/// One line of the preflight checklist
+#[derive(Debug, Clone)]
+pub struct PreflightCheck {
+    pub name: &'static str,
+    pub result: Result<String, String>,
+}
+
+/// ffmpeg from the stream settings, else from PATH and the bundled tool dirs
+pub fn resolve_ffmpeg(config: &StreamConfig) -> Option<String> {
+    config
+        .ffmpeg_path
+        .as_ref()
+        .filter(|path| !path.trim().is_empty())
+        .cloned()
+        .or_else(|| crate::utils::find_executable("ffmpeg").map(|path| path.to_string_lossy().to_string()))
+}
+
+/// First line of `<tool> <flag>`, used as a version probe
+fn tool_version(path: &str, flag: &str) -> Result<String, String> {
+    let output = Command::new(path).arg(flag).output().map_err(|e| e.to_string())?;
+    let mut text = String::from_utf8_lossy(&output.stdout).to_string();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    text.lines()
+        .find(|line| !line.trim().is_empty())
+        .map(|line| line.trim().to_string())
+        .ok_or_else(|| format!("{} printed nothing", path))
+}
+
+/// Check everything a stream needs before anything is started
+pub fn preflight(scrcpy_path: Option<&str>, config: &StreamConfig) -> Vec<PreflightCheck> {
+    let mut checks = Vec::new();
+
+    let protocol = StreamProtocol::from_url(&config.url);
+    checks.push(PreflightCheck {
+        name: "Endpoint",
+        result: match protocol {
+            Some(protocol) => Ok(format!("{} endpoint", protocol.ffmpeg_name().to_uppercase())),
+            None if config.url.trim().is_empty() => Err("No stream URL set".to_string()),
+            None => Err("URL must start with rtmp://, rtmps:// or srt://".to_string()),
+        },
+    });
+
+    checks.push(PreflightCheck {
+        name: "scrcpy",
+        result: match scrcpy_path {
+            // Audio capture and --record-format need scrcpy 2.0
+            Some(path) => tool_version(path, "--version").and_then(|version| {
+                let major = version
+                    .split_whitespace()
+                    .nth(1)
+                    .and_then(|v| v.split('.').next())
+                    .and_then(|v| v.parse::<u32>().ok());
+                match major {
+                    Some(major) if major < 2 => Err(format!("{} is too old, streaming needs scrcpy 2.0+", version)),
+                    _ => Ok(version),
+                }
+            }),
+            None => Err("scrcpy not configured".to_string()),
+        },
+    });
+
+    let ffmpeg = resolve_ffmpeg(config);
+    checks.push(PreflightCheck {
+        name: "ffmpeg",
+        result: match &ffmpeg {
+            Some(path) => tool_version(path, "-version"),
+            None => Err("ffmpeg not found, install it or set its path".to_string()),
+        },
+    });
+
+    if let (Some(path), Some(protocol)) = (&ffmpeg, protocol) {
+        let result = Command::new(path)
+            .args(["-hide_banner", "-protocols"])
+            .output()
+            .map_err(|e| e.to_string())
+            .and_then(|output| {
+                let text = String::from_utf8_lossy(&output.stdout);
+                // Only the "Output:" half of the list matters for pushing a stream
+                let outputs = text.split("Output:").nth(1).unwrap_or(&text);
+                if outputs.lines().any(|line| line.trim() == protocol.ffmpeg_name()) {
+                    Ok(format!("{} output supported", protocol.ffmpeg_name()))
+                } else {
+                    Err(format!("This ffmpeg build cannot write {}", protocol.ffmpeg_name()))
+                }
+            });
+        checks.push(PreflightCheck {
+            name: "Protocol",
+            result,
+        });
+    }
+
+    checks
+}
+
+/// scrcpy arguments that record h264/aac Matroska to `record_path`
+pub fn scrcpy_args(device_id: &str, config: &StreamConfig, record_path: &Path) -> Vec<String> {
+    let mut args = vec![
+        "-s".to_string(),
+        device_id.to_string(),
+        // flv only carries h264 and aac, so ffmpeg can copy both without re-encoding
+        "--video-codec=h264".to_string(),
+        "--audio-codec=aac".to_string(),
+        format!("--video-bit-rate={}", config.video_bitrate),
+        format!("--audio-bit-rate={}", config.audio_bitrate),
+        format!("--record={}", record_path.display()),
+        "--record-format=mkv".to_string(),
+    ];
+    if let Some(max_size) = config.max_size {
+        args.push(format!("--max-size={}", max_size));
+    }
+    if !config.show_mirror {
+        // --no-display before scrcpy 2.5, --no-playback since; -N works with both
+        args.push("-N".to_string());
+    }
+    args
+}
+
+/// ffmpeg arguments that remux the recording to the endpoint; `follow` reads a growing regular file
+pub fn ffmpeg_args(config: &StreamConfig, record_path: &Path, follow: bool) -> Result<Vec<String>, String> {
+    let protocol = StreamProtocol::from_url(&config.url)
+        .ok_or_else(|| "URL must start with rtmp://, rtmps:// or srt://".to_string())?;
+
+    let mut args = vec!["-hide_banner".to_string(), "-loglevel".to_string(), "warning".to_string()];
+    if follow {
+        args.extend(["-follow".to_string(), "1".to_string()]);
+    }
+    args.extend([
+        "-fflags".to_string(),
+        "+genpts".to_string(),
+        "-i".to_string(),
+        format!("file:{}", record_path.display()),
+        "-map".to_string(),
+        "0".to_string(),
+        "-c".to_string(),
+        "copy".to_string(),
+        "-f".to_string(),
+        protocol.muxer().to_string(),
+        config.url.trim().to_string(),
+    ]);
+    Ok(args)
+}
+
+/// Create a named pipe so the recording never touches the disk; false where unsupported
+fn make_fifo(path: &Path) -> bool {
+    if cfg!(unix) {
+        Command::new("mkfifo")
+            .arg(path)
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false)
+    } else {
+        false
+    }
+}
+
+/// Delete recordings in `dir` that no running stream is writing to any more
+fn remove_stale_recordings(dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !name.starts_with(RECORDING_PREFIX) || !name.ends_with(".mkv") {
+            continue;
+        }
+        // A live recording is written to all the time; pipes are left to their session
+        let stale = entry.metadata().is_ok_and(|metadata| {
+            metadata.is_file()
+                && metadata.modified().is_ok_and(|modified| modified.elapsed().is_ok_and(|age| age > STALE_RECORDING_AGE))
+        });
+        if stale {
+            let _ = std::fs::remove_file(entry.path());
+        }
+    }
+}
+
+fn spawn_logged(program: &str, args: &[String], label: &'static str) -> Result<Child> {
+    // Stream URLs usually embed the stream key, keep it out of the log
+    let logged: Vec<&str> = args
+        .iter()
+        .map(|arg| if arg.contains("://") { "<url>" } else { arg.as_str() })
+        .collect();
+    tracing::info!("Starting {}: {} {}", label, program, logged.join(" "));
+    let mut child = adb_command(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    if let Some(stderr) = child.stderr.take() {
+        std::thread::spawn(move || {
+            for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                tracing::info!("{} stderr: {}", label, line);
+            }
+        });
+    }
+    Ok(child)
+}
+
+/// A running scrcpy → ffmpeg pipeline; dropping it stops both and deletes the recording
+#[derive(Debug)]
+pub struct StreamSession {
+    scrcpy: Child,
+    ffmpeg: Child,
+    record_path: PathBuf,
+    /// Whether the recording is a named pipe rather than a growing file
+    fifo: bool,
+    pub url: String,
+}
+
+impl StreamSession {
+    pub fn start(scrcpy_path: &str, device_id: &str, config: &StreamConfig) -> Result<Self> {
+        let ffmpeg_path = resolve_ffmpeg(config).ok_or_else(|| anyhow::anyhow!("ffmpeg not found"))?;
+        let temp_dir = std::env::temp_dir();
+        remove_stale_recordings(&temp_dir);
+        let record_path = temp_dir.join(format!("{}{}.mkv", RECORDING_PREFIX, std::process::id()));
+        let _ = std::fs::remove_file(&record_path);
+
+        let fifo = make_fifo(&record_path);
+        let ffmpeg_args = ffmpeg_args(config, &record_path, !fifo).map_err(|e| anyhow::anyhow!(e))?;
+        let scrcpy_args = scrcpy_args(device_id, config, &record_path);
+
+        let (scrcpy, ffmpeg) = if fifo {
+            // The reader has to be there first or scrcpy blocks opening the pipe
+            let ffmpeg = spawn_logged(&ffmpeg_path, &ffmpeg_args, "ffmpeg")?;
+            (spawn_logged(scrcpy_path, &scrcpy_args, "scrcpy")?, ffmpeg)
+        } else {
+            let mut scrcpy = spawn_logged(scrcpy_path, &scrcpy_args, "scrcpy")?;
+            // ffmpeg cannot follow a file that does not exist yet
+            let started = Instant::now();
+            while std::fs::metadata(&record_path).map(|m| m.len() == 0).unwrap_or(true) {
+                if let Ok(Some(status)) = scrcpy.try_wait() {
+                    return Err(anyhow::anyhow!("scrcpy exited before recording started ({})", status));
+                }
+                if started.elapsed() > RECORD_START_TIMEOUT {
+                    let _ = scrcpy.kill();
+                    return Err(anyhow::anyhow!("scrcpy did not start recording"));
+                }
+                std::thread::sleep(Duration::from_millis(200));
+            }
+            let ffmpeg = spawn_logged(&ffmpeg_path, &ffmpeg_args, "ffmpeg")?;
+            (scrcpy, ffmpeg)
+        };
+
+        let mut session = Self {
+            scrcpy,
+            ffmpeg,
+            record_path,
+            fifo,
+            url: config.url.trim().to_string(),
+        };
+
+        // Catch a refused connection or bad stream key straight away
+        std::thread::sleep(Duration::from_millis(1500));
+        if let Some(reason) = session.exit_reason() {
+            session.stop();
+            return Err(anyhow::anyhow!(reason));
+        }
+        Ok(session)
+    }
+
+    /// Why the pipeline stopped, once either process has exited
+    pub fn exit_reason(&mut self) -> Option<String> {
+        if let Ok(Some(status)) = self.ffmpeg.try_wait() {
+            return Some(format!("ffmpeg exited ({}), check the URL and stream key", status));
+        }
+        if let Ok(Some(status)) = self.scrcpy.try_wait() {
+            return Some(format!("scrcpy exited ({})", status));
+        }
+        let size = if self.fifo { 0 } else { std::fs::metadata(&self.record_path).map_or(0, |m| m.len()) };
+        if size >= MAX_RECORDING_SIZE {
+            return Some(format!(
+                "The temporary recording reached {} GB; start the stream again to continue",
+                MAX_RECORDING_SIZE / (1024 * 1024 * 1024)
+            ));
+        }
+        None
+    }
+
+    pub fn stop(self) {
+        drop(self);
+    }
+}
+
+impl Drop for StreamSession {
+    fn drop(&mut self) {
+        for child in [&mut self.scrcpy, &mut self.ffmpeg] {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        let _ = std::fs::remove_file(&self.record_path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(url: &str) -> StreamConfig {
+        StreamConfig { url: url.to_string(), ..StreamConfig::default() }
+    }
+
+    #[test]
+    fn reads_the_protocol_from_the_url() {
+        assert_eq!(StreamProtocol::from_url(" RTMP://live.example.com/app/key"), Some(StreamProtocol::Rtmp));
+        assert_eq!(StreamProtocol::from_url("rtmps://live.example.com:443/app"), Some(StreamProtocol::Rtmps));
+        assert_eq!(StreamProtocol::from_url("srt://127.0.0.1:9000"), Some(StreamProtocol::Srt));
+        assert_eq!(StreamProtocol::from_url("https://example.com"), None);
+        assert_eq!(StreamProtocol::from_url(""), None);
+    }
+
+    #[test]
+    fn builds_pipeline_arguments() {
+        let path = Path::new("/tmp/droidview-stream-1.mkv");
+        let args = ffmpeg_args(&config("srt://127.0.0.1:9000"), path, false).unwrap();
+        assert!(!args.contains(&"-follow".to_string()));
+        assert!(args.contains(&"file:/tmp/droidview-stream-1.mkv".to_string()));
+        assert_eq!(args[args.len() - 3..], ["-f", "mpegts", "srt://127.0.0.1:9000"]);
+
+        let args = ffmpeg_args(&config("rtmp://live.example.com/app/key "), path, true).unwrap();
+        assert!(args.windows(2).any(|pair| pair == ["-follow", "1"]));
+        assert_eq!(args[args.len() - 2..], ["flv", "rtmp://live.example.com/app/key"]);
+        assert!(ffmpeg_args(&config("live.example.com"), path, false).is_err());
+
+        let args = scrcpy_args("R58M", &config("srt://127.0.0.1:9000"), path);
+        assert!(args.contains(&"--record=/tmp/droidview-stream-1.mkv".to_string()));
+        assert!(args.contains(&"--record-format=mkv".to_string()));
+    }
+
+    #[test]
+    fn removes_only_stale_recordings() {
+        let dir = std::env::temp_dir().join(format!("droidview-stream-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let old = dir.join(format!("{}1.mkv", RECORDING_PREFIX));
+        let live = dir.join(format!("{}2.mkv", RECORDING_PREFIX));
+        let other = dir.join("holiday.mkv");
+        for path in [&old, &live, &other] {
+            std::fs::write(path, b"mkv").unwrap();
+        }
+        let long_ago = std::time::SystemTime::now() - STALE_RECORDING_AGE * 2;
+        for path in [&old, &other] {
+            std::fs::File::options().write(true).open(path).unwrap().set_modified(long_ago).unwrap();
+        }
+
+        remove_stale_recordings(&dir);
+        assert!(!old.exists());
+        assert!(live.exists());
+        assert!(other.exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
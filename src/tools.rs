@@ -0,0 +1,576 @@
+//! Downloads the official adb (platform-tools) and scrcpy builds into `<config dir>/tools`
+//!
+//! Archives are checked against the checksum their publisher lists before anything is
+//! unpacked: SHA-1 from Google's SDK repository manifest for platform-tools, SHA-256 from
+//! the GitHub release for scrcpy.
+
+use crate::bridge::{check_tool_version, ToolVersion};
+use crate::t;
+use crate::utils::format_file_size;
+use anyhow::Result;
+use serde::Deserialize;
+use sha1::Digest;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const SDK_REPOSITORY: &str = "https://dl.google.com/android/repository/";
+const SDK_MANIFEST: &str = "repository2-3.xml";
+const SCRCPY_LATEST_RELEASE: &str = "https://api.github.com/repos/Genymobile/scrcpy/releases/latest";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Package {
+    PlatformTools,
+    Scrcpy,
+}
+
+impl Package {
+    pub fn name(self) -> &'static str {
+        match self {
+            Package::PlatformTools => "platform-tools",
+            Package::Scrcpy => "scrcpy",
+        }
+    }
+
+    fn executable(self) -> &'static str {
+        match (self, cfg!(windows)) {
+            (Package::PlatformTools, false) => "adb",
+            (Package::PlatformTools, true) => "adb.exe",
+            (Package::Scrcpy, false) => "scrcpy",
+            (Package::Scrcpy, true) => "scrcpy.exe",
+        }
+    }
+}
+
+/// Where an install is at, reported while it runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstallStage {
+    Resolving,
+    Downloading { received: u64, total: Option<u64> },
+    Verifying,
+    Extracting,
+}
+
+impl InstallStage {
+    pub fn describe(self) -> String {
+        match self {
+            InstallStage::Resolving => t!("tools-resolving"),
+            InstallStage::Downloading { received, total: Some(total) } if total > 0 => t!(
+                "tools-downloading-of",
+                received = format_file_size(received),
+                total = format_file_size(total),
+                percent = received * 100 / total
+            ),
+            InstallStage::Downloading { received, .. } => t!("tools-downloading", received = format_file_size(received)),
+            InstallStage::Verifying => t!("tools-verifying"),
+            InstallStage::Extracting => t!("tools-extracting"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Checksum {
+    Sha1(String),
+    Sha256(String),
+}
+
+/// A downloadable archive for this machine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Archive {
+    pub url: String,
+    pub file_name: String,
+    pub size: Option<u64>,
+    pub checksum: Checksum,
+}
+
+/// A finished install
+#[derive(Debug, Clone)]
+pub struct Installed {
+    pub package: Package,
+    pub executable: PathBuf,
+    pub version: String,
+}
+
+/// Folder the downloaded tools are unpacked into, inside the config directory
+pub fn tools_dir() -> Result<PathBuf> {
+    Ok(crate::config::app_dir()?.join("tools"))
+}
+
+/// Unpacked package folders, for tool detection
+pub fn installed_dirs() -> Vec<PathBuf> {
+    let Ok(dir) = tools_dir() else {
+        return Vec::new();
+    };
+    [Package::PlatformTools, Package::Scrcpy]
+        .into_iter()
+        .map(|package| dir.join(package.name()))
+        .filter(|dir| dir.is_dir())
+        .collect()
+}
+
+/// `host-os` value of the SDK manifest for this machine
+fn sdk_host_os() -> Result<&'static str> {
+    sdk_host_os_for(std::env::consts::OS, std::env::consts::ARCH)
+}
+
+/// Google builds platform-tools for x86 Linux and Windows only; the macOS build is universal, and
+/// Windows on Arm runs the x86 build under emulation
+fn sdk_host_os_for(os: &str, arch: &str) -> Result<&'static str> {
+    match (os, arch) {
+        ("windows", "x86_64" | "x86" | "aarch64") => Ok("windows"),
+        ("linux", "x86_64") => Ok("linux"),
+        ("macos", "x86_64" | "aarch64") => Ok("macosx"),
+        (os, arch) => Err(anyhow::anyhow!("platform-tools are not published for {} {}", os, arch)),
+    }
+}
+
+/// Prefix of the scrcpy release asset for this machine; Windows on Arm takes the x64 build
+fn scrcpy_asset_prefix() -> Result<&'static str> {
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("windows", "x86_64" | "aarch64") => Ok("scrcpy-win64-"),
+        ("windows", "x86") => Ok("scrcpy-win32-"),
+        ("linux", "x86_64") => Ok("scrcpy-linux-x86_64-"),
+        ("macos", "aarch64") => Ok("scrcpy-macos-aarch64-"),
+        ("macos", "x86_64") => Ok("scrcpy-macos-x86_64-"),
+        (os, arch) => Err(anyhow::anyhow!("scrcpy does not publish a build for {} {}", os, arch)),
+    }
+}
+
+/// Text between `<name ...>` and `</name>`, starting the search at the front of `xml`
+fn xml_element<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    let open = format!("<{}", name);
+    let mut from = 0;
+    loop {
+        let start = from + xml[from..].find(&open)?;
+        let rest = &xml[start + open.len()..];
+        // Skip longer names sharing the prefix, like <checksums> for <checksum>
+        if rest.starts_with('>') || rest.starts_with(char::is_whitespace) {
+            let content = &rest[rest.find('>')? + 1..];
+            let end = content.find(&format!("</{}>", name))?;
+            return Some(content[..end].trim());
+        }
+        from = start + open.len();
+    }
+}
+
+/// Find the platform-tools archive for `host_os` in Google's SDK repository manifest
+pub fn parse_sdk_manifest(xml: &str, host_os: &str) -> Option<Archive> {
+    let start = xml.find("<remotePackage path=\"platform-tools\"")?;
+    let package = &xml[start..];
+    let package = &package[..package.find("</remotePackage>")?];
+
+    package.split("<archive>").skip(1).find_map(|archive| {
+        if xml_element(archive, "host-os")? != host_os {
+            return None;
+        }
+        let complete = xml_element(archive, "complete")?;
+        let file_name = xml_element(complete, "url")?.to_string();
+        Some(Archive {
+            url: format!("{}{}", SDK_REPOSITORY, file_name),
+            file_name,
+            size: xml_element(complete, "size").and_then(|size| size.parse().ok()),
+            checksum: Checksum::Sha1(xml_element(complete, "checksum")?.to_lowercase()),
+        })
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub body: Option<String>,
+    pub assets: Vec<ReleaseAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub browser_download_url: String,
+    pub size: u64,
+    /// `sha256:<hex>`, filled in by GitHub for newer uploads
+    #[serde(default)]
+    pub digest: Option<String>,
+}
+
+fn is_sha256(text: &str) -> bool {
+    text.len() == 64 && text.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Pick the scrcpy asset starting with `prefix`, taking its SHA-256 from the asset digest
+/// or, for older releases, from the release notes where it follows the file name
+pub fn select_scrcpy_asset(release: &Release, prefix: &str) -> Option<Archive> {
+    let asset = release
+        .assets
+        .iter()
+        .find(|asset| asset.name.starts_with(prefix) && (asset.name.ends_with(".zip") || asset.name.ends_with(".tar.gz")))?;
+
+    let from_digest = asset
+        .digest
+        .as_deref()
+        .and_then(|digest| digest.strip_prefix("sha256:"))
+        .filter(|hash| is_sha256(hash))
+        .map(str::to_string);
+    let from_notes = || {
+        let body = release.body.as_deref()?;
+        let after = &body[body.find(&asset.name)? + asset.name.len()..];
+        // Only look right behind the name, so another asset's hash is never picked up
+        after.get(..200).unwrap_or(after)
+            .split(|c: char| !c.is_ascii_hexdigit())
+            .find(|token| is_sha256(token))
+            .map(str::to_string)
+    };
+
+    Some(Archive {
+        url: asset.browser_download_url.clone(),
+        file_name: asset.name.clone(),
+        size: Some(asset.size),
+        checksum: Checksum::Sha256(from_digest.or_else(from_notes)?.to_lowercase()),
+    })
+}
+
+async fn resolve(client: &reqwest::Client, package: Package) -> Result<Archive> {
+    match package {
+        Package::PlatformTools => {
+            let host_os = sdk_host_os()?;
+            let xml = client
+                .get(format!("{}{}", SDK_REPOSITORY, SDK_MANIFEST))
+                .send()
+                .await?
+                .error_for_status()?
+                .text()
+                .await?;
+            parse_sdk_manifest(&xml, host_os)
+                .ok_or_else(|| anyhow::anyhow!("The SDK repository lists no platform-tools for {}", host_os))
+        }
+        Package::Scrcpy => {
+            let prefix = scrcpy_asset_prefix()?;
+            let release: Release = client
+                .get(SCRCPY_LATEST_RELEASE)
+                .header("Accept", "application/vnd.github+json")
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            select_scrcpy_asset(&release, prefix).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "scrcpy {} has no {}* archive with a published SHA-256",
+                    release.tag_name,
+                    prefix
+                )
+            })
+        }
+    }
+}
+
+enum Hasher {
+    Sha1(sha1::Sha1),
+    Sha256(sha2::Sha256),
+}
+
+impl Hasher {
+    fn for_checksum(checksum: &Checksum) -> Self {
+        match checksum {
+            Checksum::Sha1(_) => Hasher::Sha1(sha1::Sha1::new()),
+            Checksum::Sha256(_) => Hasher::Sha256(sha2::Sha256::new()),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Sha1(hasher) => hasher.update(data),
+            Hasher::Sha256(hasher) => hasher.update(data),
+        }
+    }
+
+    fn finish_hex(self) -> String {
+        match self {
+            Hasher::Sha1(hasher) => hex::encode(hasher.finalize()),
+            Hasher::Sha256(hasher) => hex::encode(hasher.finalize()),
+        }
+    }
+}
+
+/// Stream `archive` to `path`, hashing as it arrives, and fail unless the checksum matches
+async fn download(
+    client: &reqwest::Client,
+    archive: &Archive,
+    path: &Path,
+    progress: &(dyn Fn(InstallStage) + Send + Sync),
+) -> Result<()> {
+    let mut response = client.get(&archive.url).send().await?.error_for_status()?;
+    let total = response.content_length().or(archive.size);
+    let mut file = File::create(path)?;
+    let mut hasher = Hasher::for_checksum(&archive.checksum);
+    let mut received = 0u64;
+    let mut reported = 0u64;
+
+    progress(InstallStage::Downloading { received, total });
+    while let Some(chunk) = response.chunk().await? {
+        file.write_all(&chunk)?;
+        hasher.update(&chunk);
+        received += chunk.len() as u64;
+        // Report about every 256 KiB instead of per chunk
+        if received - reported >= 256 * 1024 {
+            reported = received;
+            progress(InstallStage::Downloading { received, total });
+        }
+    }
+    file.flush()?;
+    progress(InstallStage::Downloading { received, total });
+
+    progress(InstallStage::Verifying);
+    let expected = match &archive.checksum {
+        Checksum::Sha1(hash) | Checksum::Sha256(hash) => hash,
+    };
+    let actual = hasher.finish_hex();
+    if &actual != expected {
+        return Err(anyhow::anyhow!(
+            "Checksum mismatch for {}: expected {}, got {}",
+            archive.file_name,
+            expected,
+            actual
+        ));
+    }
+    Ok(())
+}
+
+/// Unpack with the system `tar` (bsdtar reads zip too on Windows and macOS), or `unzip` for zips on Linux
+fn extract(archive: &Path, into: &Path) -> Result<()> {
+    let is_zip = archive.extension().is_some_and(|ext| ext == "zip");
+    let mut command = if is_zip && cfg!(target_os = "linux") {
+        let mut command = Command::new("unzip");
+        command.arg("-q").arg(archive).arg("-d").arg(into);
+        command
+    } else {
+        let mut command = Command::new("tar");
+        command.arg("-xf").arg(archive).arg("-C").arg(into);
+        command
+    };
+    let program = command.get_program().to_string_lossy().to_string();
+    let output = command
+        .output()
+        .map_err(|e| anyhow::anyhow!("Could not run {} to unpack the archive: {}", program, e))?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "{} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+/// The single folder an archive unpacked into, or the staging folder itself
+fn unpacked_root(staging: &Path) -> Result<PathBuf> {
+    let entries: Vec<PathBuf> = fs::read_dir(staging)?.filter_map(|entry| entry.ok().map(|e| e.path())).collect();
+    match entries.as_slice() {
+        [only] if only.is_dir() => Ok(only.clone()),
+        _ => Ok(staging.to_path_buf()),
+    }
+}
+
+/// Make the unpacked executable runnable and return the version it reports
+fn verify_unpacked(package: Package, root: &Path) -> Result<String> {
+    let executable = root.join(package.executable());
+    if !executable.is_file() {
+        return Err(anyhow::anyhow!("The archive did not contain {}", package.executable()));
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut permissions = fs::metadata(&executable)?.permissions();
+        permissions.set_mode(permissions.mode() | 0o755);
+        fs::set_permissions(&executable, permissions)?;
+    }
+    match check_tool_version(&executable.to_string_lossy(), package == Package::PlatformTools) {
+        Ok(ToolVersion { version, .. }) => Ok(version),
+        Err(e) => Err(anyhow::anyhow!("The downloaded {} does not run: {}", package.executable(), e)),
+    }
+}
+
+/// Move `root` to `target`, keeping the earlier `target` aside until the move succeeded and
+/// putting it back if it did not
+fn swap_in(root: &Path, target: &Path) -> Result<()> {
+    let previous = target.with_extension("previous");
+    let _ = fs::remove_dir_all(&previous);
+    let had_previous = target.exists();
+    if had_previous {
+        fs::rename(target, &previous)?;
+    }
+    if let Err(e) = fs::rename(root, target) {
+        if had_previous {
+            let _ = fs::rename(&previous, target);
+        }
+        return Err(anyhow::anyhow!("Could not move the new {} into place: {}", target.display(), e));
+    }
+    if had_previous {
+        let _ = fs::remove_dir_all(&previous);
+    }
+    Ok(())
+}
+
+/// Download, verify and unpack `package` into `tools_dir()`, replacing an earlier install
+pub async fn install(package: Package, progress: &(dyn Fn(InstallStage) + Send + Sync)) -> Result<Installed> {
+    progress(InstallStage::Resolving);
+    let client = crate::utils::http_client()?;
+    let archive = resolve(&client, package).await?;
+    tracing::info!("Installing {} from {}", package.name(), archive.url);
+
+    let dir = tools_dir()?;
+    let downloads = dir.join("downloads");
+    fs::create_dir_all(&downloads)?;
+    let archive_path = downloads.join(&archive.file_name);
+    let result = download(&client, &archive, &archive_path, progress).await;
+    if result.is_err() {
+        let _ = fs::remove_file(&archive_path);
+    }
+    result?;
+
+    progress(InstallStage::Extracting);
+    let staging = dir.join(format!(".{}-staging", package.name()));
+    let _ = fs::remove_dir_all(&staging);
+    fs::create_dir_all(&staging)?;
+    let unpacked = extract(&archive_path, &staging).and_then(|_| unpacked_root(&staging));
+    let _ = fs::remove_file(&archive_path);
+    let root = match unpacked {
+        Ok(root) => root,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(e);
+        }
+    };
+
+    // The earlier install stays in place until the new one is known to run
+    let version = match verify_unpacked(package, &root) {
+        Ok(version) => version,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(e);
+        }
+    };
+    let target = dir.join(package.name());
+    let result = swap_in(&root, &target);
+    let _ = fs::remove_dir_all(&staging);
+    result?;
+
+    let executable = target.join(package.executable());
+    tracing::info!("Installed {} {} at {}", package.name(), version, executable.display());
+    Ok(Installed {
+        package,
+        executable,
+        version,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashes_in_pieces() {
+        let mut hasher = Hasher::for_checksum(&Checksum::Sha256(String::new()));
+        for chunk in b"abc".chunks(2) {
+            hasher.update(chunk);
+        }
+        assert_eq!(hasher.finish_hex(), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        let mut hasher = Hasher::for_checksum(&Checksum::Sha1(String::new()));
+        hasher.update(b"abc");
+        assert_eq!(hasher.finish_hex(), "a9993e364706816aba3e25717850c26c9cd0d89d");
+    }
+
+    #[test]
+    fn sdk_hosts() {
+        assert_eq!(sdk_host_os_for("linux", "x86_64").unwrap(), "linux");
+        assert_eq!(sdk_host_os_for("macos", "aarch64").unwrap(), "macosx");
+        assert_eq!(sdk_host_os_for("windows", "x86").unwrap(), "windows");
+        assert!(sdk_host_os_for("linux", "aarch64").is_err());
+        assert_eq!(sdk_host_os_for("windows", "aarch64").unwrap(), "windows");
+    }
+
+    #[test]
+    fn swaps_in_over_an_earlier_install() {
+        let dir = std::env::temp_dir().join(format!("droidview-swap-{}", uuid::Uuid::new_v4()));
+        let (root, target) = (dir.join("staging"), dir.join("scrcpy"));
+        fs::create_dir_all(&root).unwrap();
+        fs::create_dir_all(&target).unwrap();
+        fs::write(root.join("new"), "").unwrap();
+        fs::write(target.join("old"), "").unwrap();
+
+        swap_in(&root, &target).unwrap();
+        assert!(target.join("new").is_file());
+        assert!(!target.join("old").exists());
+        assert!(!target.with_extension("previous").exists());
+
+        // A failed move leaves the earlier install where it was
+        assert!(swap_in(&dir.join("missing"), &target).is_err());
+        assert!(target.join("new").is_file());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn sdk_manifest() {
+        let xml = r#"<sdk:sdk-repository>
+<remotePackage path="build-tools;35.0.0"><archives><archive><complete><size>1</size>
+<checksum type="sha1">aaaa</checksum><url>build-tools.zip</url></complete><host-os>linux</host-os></archive></archives></remotePackage>
+<remotePackage path="platform-tools">
+  <revision><major>35</major><minor>0</minor><micro>2</micro></revision>
+  <archives>
+    <archive>
+      <complete>
+        <size>6307914</size>
+        <checksum type="sha1">D6D72D006c5f3f2Ee6d6d4b6f4d2b8f6e1b1c2a3</checksum>
+        <url>platform-tools_r35.0.2-darwin.zip</url>
+      </complete>
+      <host-os>macosx</host-os>
+    </archive>
+    <archive>
+      <complete>
+        <size>7014543</size>
+        <checksum type="sha1">e0fd1bd7a3f2b6b9b7d2c6c7b3a5f2e4d1c0b9a8</checksum>
+        <url>platform-tools_r35.0.2-linux.zip</url>
+      </complete>
+      <host-os>linux</host-os>
+    </archive>
+  </archives>
+</remotePackage>
+</sdk:sdk-repository>"#;
+
+        let linux = parse_sdk_manifest(xml, "linux").unwrap();
+        assert_eq!(linux.url, "https://dl.google.com/android/repository/platform-tools_r35.0.2-linux.zip");
+        assert_eq!(linux.size, Some(7014543));
+        assert_eq!(linux.checksum, Checksum::Sha1("e0fd1bd7a3f2b6b9b7d2c6c7b3a5f2e4d1c0b9a8".to_string()));
+        assert_eq!(
+            parse_sdk_manifest(xml, "macosx").unwrap().checksum,
+            Checksum::Sha1("d6d72d006c5f3f2ee6d6d4b6f4d2b8f6e1b1c2a3".to_string())
+        );
+        assert!(parse_sdk_manifest(xml, "windows").is_none());
+    }
+
+    #[test]
+    fn scrcpy_release_assets() {
+        let hash = "a".repeat(64);
+        let release: Release = serde_json::from_value(serde_json::json!({
+            "tag_name": "v3.1",
+            "body": format!("- `scrcpy-linux-x86_64-v3.1.tar.gz`\n  SHA-256: `{}`\n", hash),
+            "assets": [
+                { "name": "scrcpy-server-v3.1", "browser_download_url": "https://example.com/server", "size": 1 },
+                { "name": "scrcpy-win64-v3.1.zip", "browser_download_url": "https://example.com/win64.zip", "size": 2,
+                  "digest": format!("sha256:{}", "B".repeat(64)) },
+                { "name": "scrcpy-linux-x86_64-v3.1.tar.gz", "browser_download_url": "https://example.com/linux.tar.gz", "size": 3 },
+                { "name": "scrcpy-macos-aarch64-v3.1.tar.gz", "browser_download_url": "https://example.com/mac.tar.gz", "size": 4 }
+            ]
+        }))
+        .unwrap();
+
+        let win = select_scrcpy_asset(&release, "scrcpy-win64-").unwrap();
+        assert_eq!(win.checksum, Checksum::Sha256("b".repeat(64)));
+        assert_eq!(win.file_name, "scrcpy-win64-v3.1.zip");
+        let linux = select_scrcpy_asset(&release, "scrcpy-linux-x86_64-").unwrap();
+        assert_eq!(linux.checksum, Checksum::Sha256(hash));
+        // Without a published checksum there is nothing to verify against
+        assert!(select_scrcpy_asset(&release, "scrcpy-macos-aarch64-").is_none());
+        assert!(select_scrcpy_asset(&release, "scrcpy-win32-").is_none());
+    }
+}
@@ -0,0 +1,106 @@
+//! Tray menu listing the session templates, built with the `tray` feature
+
+use std::cell::RefCell;
+use std::sync::mpsc;
+use tray_icon::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tray_icon::{Icon, TrayIcon, TrayIconBuilder};
+
+const SHOW_ID: &str = "show";
+const TEMPLATE_PREFIX: &str = "template:";
+
+pub enum TrayCommand {
+    Show,
+    Launch(String),
+}
+
+thread_local! {
+    // The icon lives on the thread running the platform event loop (GTK on Linux, the UI thread elsewhere)
+    static TRAY: RefCell<Option<TrayIcon>> = const { RefCell::new(None) };
+}
+
+fn build_menu(templates: &[String]) -> Menu {
+    let menu = Menu::new();
+    let _ = menu.append(&MenuItem::with_id(SHOW_ID, "Show DroidView", true, None));
+    let _ = menu.append(&PredefinedMenuItem::separator());
+    if templates.is_empty() {
+        let _ = menu.append(&MenuItem::new("No session templates", false, None));
+    }
+    for name in templates {
+        let _ = menu.append(&MenuItem::with_id(format!("{}{}", TEMPLATE_PREFIX, name), name, true, None));
+    }
+    menu
+}
+
+fn build_tray(templates: &[String]) {
+    let icon = image::load_from_memory(crate::app::ICON_PNG).ok().and_then(|image| {
+        let image = image.to_rgba8();
+        let (width, height) = image.dimensions();
+        Icon::from_rgba(image.into_raw(), width, height).ok()
+    });
+    let mut builder = TrayIconBuilder::new()
+        .with_menu(Box::new(build_menu(templates)))
+        .with_tooltip("DroidView");
+    if let Some(icon) = icon {
+        builder = builder.with_icon(icon);
+    }
+    match builder.build() {
+        Ok(tray) => TRAY.with(|cell| *cell.borrow_mut() = Some(tray)),
+        Err(e) => tracing::warn!("Could not create the tray icon: {}", e),
+    }
+}
+
+fn set_menu(templates: &[String]) {
+    TRAY.with(|cell| {
+        if let Some(tray) = cell.borrow().as_ref() {
+            tray.set_menu(Some(Box::new(build_menu(templates))));
+        }
+    });
+}
+
+/// Tray icon whose menu shows DroidView or launches a template
+pub struct Tray {
+    receiver: mpsc::Receiver<TrayCommand>,
+}
+
+impl Tray {
+    /// Call from the UI thread; `ctx` is woken when a menu entry is clicked
+    pub fn new(templates: Vec<String>, ctx: egui::Context) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        MenuEvent::set_event_handler(Some(move |event: MenuEvent| {
+            let id = event.id().0.as_str();
+            let command = match id.strip_prefix(TEMPLATE_PREFIX) {
+                Some(name) => TrayCommand::Launch(name.to_string()),
+                None if id == SHOW_ID => TrayCommand::Show,
+                None => return,
+            };
+            let _ = sender.send(command);
+            ctx.request_repaint();
+        }));
+
+        #[cfg(target_os = "linux")]
+        std::thread::spawn(move || {
+            if let Err(e) = gtk::init() {
+                tracing::warn!("Could not start GTK for the tray icon: {}", e);
+                return;
+            }
+            build_tray(&templates);
+            gtk::main();
+        });
+        #[cfg(not(target_os = "linux"))]
+        build_tray(&templates);
+
+        Self { receiver }
+    }
+
+    /// Rebuild the menu after templates were added, renamed or removed
+    pub fn set_templates(&self, templates: Vec<String>) {
+        #[cfg(target_os = "linux")]
+        gtk::glib::idle_add_once(move || set_menu(&templates));
+        #[cfg(not(target_os = "linux"))]
+        set_menu(&templates);
+    }
+
+    pub fn poll(&self) -> Option<TrayCommand> {
+        self.receiver.try_recv().ok()
+    }
+}
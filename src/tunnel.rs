@@ -0,0 +1,228 @@
+//! SSH port forwards to devices on another machine, e.g. in a remote device lab
+//!
+//! `ssh -N -L <local>:<target>` forwards a local port to the device's adb port as seen from
+//! the SSH host, and `adb connect localhost:<local>` then talks to the device through it.
+//! SSH runs non-interactively, so the host has to accept a key or agent login.
+
+use crate::bridge::{AdbBridge, ConnectOutcome};
+use crate::config::SshTunnelProfile;
+use crate::t;
+use anyhow::Result;
+use std::io::{BufRead, BufReader};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// How long ssh gets to log in and start listening on the local port
+const OPEN_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Arguments for `ssh` that hold the forward from `local_port` open without running a remote
+/// command
+pub fn ssh_args(profile: &SshTunnelProfile, local_port: u16) -> Vec<String> {
+    let mut args = vec![
+        "-N".to_string(),
+        "-L".to_string(),
+        format!("{}:{}", local_port, profile.target.trim()),
+        "-p".to_string(),
+        profile.ssh_port.to_string(),
+        // Fail instead of prompting for a password nobody can type
+        "-o".to_string(),
+        "BatchMode=yes".to_string(),
+        "-o".to_string(),
+        "ExitOnForwardFailure=yes".to_string(),
+        "-o".to_string(),
+        "ServerAliveInterval=15".to_string(),
+    ];
+    if let Some(identity) = profile.identity_file.as_deref().map(str::trim).filter(|path| !path.is_empty()) {
+        args.push("-i".to_string());
+        args.push(identity.to_string());
+    }
+    args.push(profile.ssh_host.trim().to_string());
+    args
+}
+
+/// What is wrong with the profile, if anything
+pub fn validate(profile: &SshTunnelProfile) -> Result<(), String> {
+    if profile.name.trim().is_empty() {
+        return Err(t!("tunnels-error-name"));
+    }
+    if profile.ssh_host.trim().is_empty() {
+        return Err(t!("tunnels-error-host"));
+    }
+    match profile.target.trim().rsplit_once(':') {
+        Some((host, port)) if !host.is_empty() && port.parse::<u16>().is_ok() => {}
+        _ => return Err(t!("tunnels-error-target")),
+    }
+    if profile.local_port == 0 {
+        return Err(t!("tunnels-error-port"));
+    }
+    Ok(())
+}
+
+/// The profile's local port when nothing listens on it yet, else one the system hands out
+fn free_port(preferred: u16) -> Result<u16> {
+    if TcpListener::bind(("127.0.0.1", preferred)).is_ok() {
+        return Ok(preferred);
+    }
+    let port = TcpListener::bind(("127.0.0.1", 0))?.local_addr()?.port();
+    tracing::info!("Port {} is taken, forwarding from port {} instead", preferred, port);
+    Ok(port)
+}
+
+/// An ssh process holding a forward open
+#[derive(Debug)]
+pub struct OpenTunnel {
+    pub name: String,
+    /// Local end of the forward, the profile's port unless that one was taken
+    pub local_port: u16,
+    ssh: Child,
+    /// Last line ssh wrote to stderr
+    last_error: Arc<Mutex<String>>,
+}
+
+impl OpenTunnel {
+    /// Start ssh and wait until the local end of the forward accepts connections
+    pub fn open(profile: &SshTunnelProfile) -> Result<Self> {
+        validate(profile).map_err(|e| anyhow::anyhow!(e))?;
+        let ssh_path = crate::utils::find_executable("ssh")
+            .map(|path| path.to_string_lossy().to_string())
+            .unwrap_or_else(|| "ssh".to_string());
+        let local_port = free_port(profile.local_port)?;
+        tracing::info!("Opening SSH tunnel '{}' via {}", profile.name, profile.ssh_host);
+        let mut ssh = Command::new(ssh_path)
+            .args(ssh_args(profile, local_port))
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        // Log everything ssh says for as long as it runs, a full pipe would block it
+        let last_error = Arc::new(Mutex::new(String::new()));
+        let mut drain = ssh.stderr.take().map(|stderr| {
+            let name = profile.name.clone();
+            let last_error = last_error.clone();
+            std::thread::spawn(move || {
+                for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                    let line = line.trim();
+                    if !line.is_empty() {
+                        tracing::info!("ssh ({}): {}", name, line);
+                        if let Ok(mut last) = last_error.lock() {
+                            *last = line.to_string();
+                        }
+                    }
+                }
+            })
+        });
+
+        let address = SocketAddr::from(([127, 0, 0, 1], local_port));
+        let started = Instant::now();
+        loop {
+            // Only a connection made while ssh is still running counts as its forward
+            let connected = TcpStream::connect_timeout(&address, Duration::from_millis(200)).is_ok();
+            if let Ok(Some(status)) = ssh.try_wait() {
+                if let Some(drain) = drain.take() {
+                    let _ = drain.join();
+                }
+                let reason = last_error.lock().map(|last| last.clone()).unwrap_or_default();
+                return Err(anyhow::anyhow!("ssh exited ({}) {}", status, reason));
+            }
+            if connected {
+                break;
+            }
+            if started.elapsed() > OPEN_TIMEOUT {
+                let _ = ssh.kill();
+                let _ = ssh.wait();
+                return Err(anyhow::anyhow!("ssh did not open the forward in time"));
+            }
+            std::thread::sleep(Duration::from_millis(200));
+        }
+
+        Ok(Self {
+            name: profile.name.clone(),
+            local_port,
+            ssh,
+            last_error,
+        })
+    }
+
+    /// `adb connect` to the local end of the forward
+    pub fn connect(&self, adb: &AdbBridge) -> Result<ConnectOutcome, String> {
+        adb.connect("localhost", self.local_port).map_err(|e| e.to_string())
+    }
+
+    pub fn endpoint(&self) -> String {
+        format!("localhost:{}", self.local_port)
+    }
+
+    /// Why ssh stopped, once it has
+    pub fn exit_reason(&mut self) -> Option<String> {
+        match self.ssh.try_wait() {
+            Ok(Some(status)) => {
+                let reason = self.last_error.lock().map(|last| last.clone()).unwrap_or_default();
+                Some(format!("ssh exited ({}) {}", status, reason).trim_end().to_string())
+            }
+            _ => None,
+        }
+    }
+
+    pub fn close(mut self, adb: Option<&AdbBridge>) {
+        if let Some(adb) = adb {
+            let _ = crate::bridge::adb_command(adb.path())
+                .args(["disconnect", &self.endpoint()])
+                .output();
+        }
+        let _ = self.ssh.kill();
+        let _ = self.ssh.wait();
+        tracing::info!("Closed SSH tunnel '{}'", self.name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile() -> SshTunnelProfile {
+        SshTunnelProfile {
+            name: "Lab Pixel".to_string(),
+            ssh_host: "ci@lab.example.com".to_string(),
+            ssh_port: 2222,
+            target: "10.0.0.7:5555".to_string(),
+            local_port: 15555,
+            identity_file: Some("~/.ssh/lab".to_string()),
+        }
+    }
+
+    #[test]
+    fn builds_ssh_arguments() {
+        let args = ssh_args(&profile(), 15555);
+        assert_eq!(&args[..3], ["-N", "-L", "15555:10.0.0.7:5555"]);
+        assert!(args.windows(2).any(|pair| pair == ["-p", "2222"]));
+        assert!(args.windows(2).any(|pair| pair == ["-i", "~/.ssh/lab"]));
+        assert_eq!(args.last().map(String::as_str), Some("ci@lab.example.com"));
+
+        let mut no_key = profile();
+        no_key.identity_file = Some("  ".to_string());
+        assert!(!ssh_args(&no_key, 15555).contains(&"-i".to_string()));
+    }
+
+    #[test]
+    fn validates_profiles() {
+        assert!(validate(&profile()).is_ok());
+        let mut bad = profile();
+        bad.target = "10.0.0.7".to_string();
+        assert!(validate(&bad).is_err());
+        let mut bad = profile();
+        bad.ssh_host = " ".to_string();
+        assert!(validate(&bad).is_err());
+    }
+
+    #[test]
+    fn forwards_from_a_free_port() {
+        let taken = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = taken.local_addr().unwrap().port();
+        let picked = free_port(port).unwrap();
+        assert_ne!(picked, port);
+        assert!(TcpListener::bind(("127.0.0.1", picked)).is_ok());
+    }
+}
@@ -0,0 +1,147 @@
+use crate::activity_stack::ActivityStack;
+use crate::t;
+use egui::{Color32, RichText, Window};
+
+pub enum ActivityViewerAction {
+    None,
+    Refresh,
+}
+
+/// Foreground activity, task stack and recents of the selected device
+pub struct ActivityViewer {
+    visible: bool,
+    stack: Option<ActivityStack>,
+    loading: bool,
+    error: Option<String>,
+}
+
+impl Default for ActivityViewer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ActivityViewer {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            stack: None,
+            loading: false,
+            error: None,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.visible = true;
+        self.loading = true;
+        self.error = None;
+    }
+
+    pub fn set_stack(&mut self, stack: Result<ActivityStack, String>) {
+        match stack {
+            Ok(stack) => {
+                self.stack = Some(stack);
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+        self.loading = false;
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> ActivityViewerAction {
+        if !self.visible {
+            return ActivityViewerAction::None;
+        }
+
+        let mut action = ActivityViewerAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::STACK, t!("activities-title")))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(480.0, 420.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(!self.loading, egui::Button::new(t!("activities-refresh"))).clicked() {
+                        self.loading = true;
+                        action = ActivityViewerAction::Refresh;
+                    }
+                    if let Some(stack) = &self.stack
+                        && ui.button(format!("{} {}", egui_phosphor::fill::COPY, t!("activities-copy-all"))).clicked()
+                    {
+                        ui.ctx().copy_text(stack.to_text());
+                    }
+                    if self.loading {
+                        ui.add(egui::Spinner::new().size(14.0));
+                    }
+                });
+
+                if let Some(error) = &self.error {
+                    ui.label(RichText::new(error).color(Color32::RED));
+                }
+
+                let Some(stack) = &self.stack else {
+                    return;
+                };
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(t!("activities-foreground")).strong());
+                    match &stack.resumed {
+                        Some(activity) => {
+                            ui.label(RichText::new(activity).monospace().color(Color32::GREEN));
+                            copy_button(ui, activity);
+                        }
+                        None => {
+                            ui.label(RichText::new(t!("activities-unknown")).color(Color32::GRAY));
+                        }
+                    }
+                });
+
+                ui.separator();
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::CollapsingHeader::new(t!("activities-tasks", count = stack.tasks.len()))
+                        .id_salt("activity_tasks")
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            for task in &stack.tasks {
+                                ui.label(RichText::new(format!("#{} {}", task.id, task.affinity)).strong());
+                                for activity in &task.activities {
+                                    ui.horizontal(|ui| {
+                                        ui.add_space(12.0);
+                                        ui.label(RichText::new(activity).monospace().size(11.0));
+                                        copy_button(ui, activity);
+                                    });
+                                }
+                            }
+                        });
+
+                    egui::CollapsingHeader::new(t!("activities-recents", count = stack.recents.len()))
+                        .id_salt("activity_recents")
+                        .show(ui, |ui| {
+                            for task in &stack.recents {
+                                ui.horizontal(|ui| {
+                                    ui.label(RichText::new(format!("#{}", task.id)).color(Color32::GRAY));
+                                    ui.label(RichText::new(&task.affinity).monospace().size(11.0));
+                                    copy_button(ui, &task.affinity);
+                                });
+                            }
+                        });
+                });
+            });
+
+        self.visible = open;
+        action
+    }
+}
+
+fn copy_button(ui: &mut egui::Ui, text: &str) {
+    if ui
+        .small_button(egui_phosphor::fill::COPY)
+        .on_hover_text(t!("activities-copy"))
+        .clicked()
+    {
+        ui.ctx().copy_text(text.to_string());
+    }
+}
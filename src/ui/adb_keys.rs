@@ -0,0 +1,283 @@
+use crate::adb_keys::{DeviceKeys, HostKey};
+use crate::device::{Device, DeviceStatus};
+use crate::t;
+use egui::{Color32, RichText, Window};
+
+pub enum AdbKeysAction {
+    None,
+    /// Read the host key again, and the trusted keys of the device if one is chosen
+    Check(Option<String>),
+    Regenerate,
+    Revoke(String),
+}
+
+/// This computer's adb key, the keys a device trusts, and what to do about "unauthorized"
+pub struct AdbKeysWindow {
+    visible: bool,
+    device: Option<String>,
+    host_key: Option<Result<HostKey, String>>,
+    /// Trusted keys of the device they were read from
+    device_keys: Option<(String, DeviceKeys)>,
+    checking: bool,
+    confirm_regenerate: bool,
+    confirm_revoke: bool,
+    message: Option<Result<String, String>>,
+}
+
+impl Default for AdbKeysWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AdbKeysWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            device: None,
+            host_key: None,
+            device_keys: None,
+            checking: false,
+            confirm_regenerate: false,
+            confirm_revoke: false,
+            message: None,
+        }
+    }
+
+    /// Open on `device`, or on the device chosen last time
+    pub fn open(&mut self, device: Option<String>) {
+        self.visible = true;
+        if device.is_some() {
+            self.device = device;
+        }
+        self.confirm_regenerate = false;
+        self.confirm_revoke = false;
+        self.message = None;
+    }
+
+    pub fn device(&self) -> Option<&str> {
+        self.device.as_deref()
+    }
+
+    pub fn set_checking(&mut self) {
+        self.checking = true;
+    }
+
+    pub fn set_keys(&mut self, host_key: Result<HostKey, String>, device_keys: Option<(String, DeviceKeys)>) {
+        self.host_key = Some(host_key);
+        self.device_keys = device_keys;
+        self.checking = false;
+    }
+
+    pub fn set_message(&mut self, message: Result<String, String>) {
+        self.message = Some(message);
+        self.checking = false;
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, devices: &[Device]) -> AdbKeysAction {
+        if !self.visible {
+            return AdbKeysAction::None;
+        }
+
+        let mut action = AdbKeysAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::KEY, t!("adb-keys-title")))
+            .id(egui::Id::new("adb_keys"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(500.0, 440.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(!self.checking, egui::Button::new(t!("adb-keys-recheck"))).clicked() {
+                        self.message = None;
+                        action = AdbKeysAction::Check(self.device.clone());
+                    }
+                    if self.checking {
+                        ui.add(egui::Spinner::new().size(14.0));
+                    }
+                });
+                ui.separator();
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    let host_fingerprint = self.show_host_key(ui, &mut action);
+                    ui.separator();
+                    self.show_device(ui, devices, host_fingerprint.as_deref(), &mut action);
+                });
+
+                match &self.message {
+                    Some(Ok(message)) => {
+                        ui.separator();
+                        ui.label(RichText::new(message).color(Color32::GREEN));
+                    }
+                    Some(Err(message)) => {
+                        ui.separator();
+                        ui.label(RichText::new(message).color(Color32::RED));
+                    }
+                    None => {}
+                }
+            });
+
+        self.visible = open;
+        action
+    }
+
+    /// Returns the host fingerprint when the key could be read
+    fn show_host_key(&mut self, ui: &mut egui::Ui, action: &mut AdbKeysAction) -> Option<String> {
+        ui.label(RichText::new(t!("adb-keys-host")).strong());
+        let fingerprint = match &self.host_key {
+            Some(Ok(key)) => {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(&key.fingerprint).monospace());
+                    if ui
+                        .small_button(egui_phosphor::fill::COPY)
+                        .on_hover_text(t!("adb-keys-copy"))
+                        .clicked()
+                    {
+                        ui.ctx().copy_text(key.fingerprint.clone());
+                    }
+                });
+                ui.label(RichText::new(format!("{}  {}", key.path.display(), key.comment)).size(11.0).weak());
+                Some(key.fingerprint.clone())
+            }
+            Some(Err(e)) => {
+                ui.label(RichText::new(e).color(Color32::RED));
+                None
+            }
+            None => None,
+        };
+
+        let remote = crate::bridge::adb_server_socket();
+        if self.confirm_regenerate {
+            ui.label(RichText::new(t!("adb-keys-regenerate-warning")).color(Color32::YELLOW));
+            ui.horizontal(|ui| {
+                if ui.button(t!("adb-keys-regenerate-confirm")).clicked() {
+                    *action = AdbKeysAction::Regenerate;
+                    self.confirm_regenerate = false;
+                    self.checking = true;
+                }
+                if ui.button(t!("adb-keys-cancel")).clicked() {
+                    self.confirm_regenerate = false;
+                }
+            });
+        } else {
+            let button = ui.add_enabled(
+                remote.is_none() && !self.checking,
+                egui::Button::new(format!("{} {}", egui_phosphor::fill::ARROWS_CLOCKWISE, t!("adb-keys-regenerate"))),
+            );
+            let button = match &remote {
+                Some(socket) => button.on_disabled_hover_text(t!("adb-keys-remote", socket = socket)),
+                None => button.on_hover_text(t!("adb-keys-regenerate-hint")),
+            };
+            if button.clicked() {
+                self.confirm_regenerate = true;
+            }
+        }
+        fingerprint
+    }
+
+    fn show_device(
+        &mut self,
+        ui: &mut egui::Ui,
+        devices: &[Device],
+        host_fingerprint: Option<&str>,
+        action: &mut AdbKeysAction,
+    ) {
+        ui.label(RichText::new(t!("adb-keys-device")).strong());
+        if devices.is_empty() {
+            ui.label(RichText::new(t!("adb-keys-no-devices")).color(Color32::GRAY));
+            return;
+        }
+
+        let selected = self.device.as_ref().and_then(|id| devices.iter().find(|device| device.identifier == *id));
+        let selected_text = selected.map_or_else(|| t!("adb-keys-choose-device"), device_name);
+        egui::ComboBox::from_id_salt("adb_keys_device")
+            .selected_text(selected_text)
+            .show_ui(ui, |ui| {
+                for device in devices {
+                    let chosen = self.device.as_deref() == Some(device.identifier.as_str());
+                    if ui.selectable_label(chosen, device_name(device)).clicked() && !chosen {
+                        self.device = Some(device.identifier.clone());
+                        self.confirm_revoke = false;
+                        *action = AdbKeysAction::Check(self.device.clone());
+                    }
+                }
+            });
+        let Some(device) = selected else {
+            return;
+        };
+
+        match &device.status {
+            DeviceStatus::Unauthorized => {
+                ui.label(RichText::new(t!("adb-keys-unauthorized")).color(Color32::YELLOW));
+                let fingerprint = host_fingerprint.unwrap_or("?");
+                let key_dir = crate::adb_keys::key_dir().map(|dir| dir.display().to_string()).unwrap_or_default();
+                let steps = [
+                    t!("adb-keys-step-unlock"),
+                    t!("adb-keys-step-allow", fingerprint = fingerprint),
+                    t!("adb-keys-step-replug"),
+                    t!("adb-keys-step-revoke"),
+                    t!("adb-keys-step-writable", dir = key_dir),
+                ];
+                for (index, step) in steps.iter().enumerate() {
+                    ui.label(format!("{}. {}", index + 1, step));
+                }
+            }
+            DeviceStatus::Device => {
+                match &self.device_keys {
+                    Some((id, DeviceKeys::Listed(keys))) if *id == device.identifier => {
+                        ui.label(t!("adb-keys-trusted", count = keys.len()));
+                        for key in keys {
+                            ui.horizontal(|ui| {
+                                ui.label(RichText::new(&key.fingerprint).monospace().size(11.0));
+                                ui.label(RichText::new(&key.comment).weak());
+                                if Some(key.fingerprint.as_str()) == host_fingerprint {
+                                    ui.label(RichText::new(t!("adb-keys-this-computer")).color(Color32::GREEN));
+                                }
+                            });
+                        }
+                    }
+                    Some((id, DeviceKeys::NeedsRoot)) if *id == device.identifier => {
+                        ui.label(RichText::new(t!("adb-keys-needs-root")).color(Color32::GRAY));
+                    }
+                    _ => {}
+                }
+
+                if self.confirm_revoke {
+                    ui.label(RichText::new(t!("adb-keys-revoke-warning")).color(Color32::YELLOW));
+                    ui.horizontal(|ui| {
+                        if ui.button(t!("adb-keys-revoke-confirm")).clicked() {
+                            *action = AdbKeysAction::Revoke(device.identifier.clone());
+                            self.confirm_revoke = false;
+                            self.checking = true;
+                        }
+                        if ui.button(t!("adb-keys-cancel")).clicked() {
+                            self.confirm_revoke = false;
+                        }
+                    });
+                } else if ui
+                    .add_enabled(!self.checking, egui::Button::new(t!("adb-keys-revoke")))
+                    .on_hover_text(t!("adb-keys-revoke-hint"))
+                    .clicked()
+                {
+                    self.confirm_revoke = true;
+                }
+            }
+            DeviceStatus::NoPermission => {
+                ui.label(RichText::new(t!("adb-keys-no-permission")).color(Color32::RED));
+            }
+            DeviceStatus::Offline | DeviceStatus::Unknown(_) => {
+                ui.label(RichText::new(t!("adb-keys-offline")).color(Color32::GRAY));
+            }
+        }
+    }
+}
+
+fn device_name(device: &Device) -> String {
+    if device.model.is_empty() {
+        device.identifier.clone()
+    } else {
+        format!("{} ({})", device.model, device.identifier)
+    }
+}
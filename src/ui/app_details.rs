@@ -0,0 +1,136 @@
+use crate::packages::AppDetails;
+use crate::t;
+use crate::utils::format_file_size;
+use egui::{Color32, RichText, Window};
+
+pub enum AppDetailsAction {
+    None,
+    Refresh(String),
+    Permissions(String),
+}
+
+/// Detail pane for a package picked in the app dialogs
+pub struct AppDetailsWindow {
+    visible: bool,
+    package: String,
+    details: Option<AppDetails>,
+    loading: bool,
+    error: Option<String>,
+}
+
+impl Default for AppDetailsWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AppDetailsWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            package: String::new(),
+            details: None,
+            loading: false,
+            error: None,
+        }
+    }
+
+    /// Open for a package; the caller starts loading and later calls `set_details`
+    pub fn open(&mut self, package: &str) {
+        self.visible = true;
+        self.package = package.to_string();
+        self.details = None;
+        self.error = None;
+        self.loading = true;
+    }
+
+    pub fn set_details(&mut self, package: &str, details: Result<AppDetails, String>) {
+        if package != self.package {
+            return;
+        }
+        match details {
+            Ok(details) => self.details = Some(details),
+            Err(e) => self.error = Some(e),
+        }
+        self.loading = false;
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> AppDetailsAction {
+        if !self.visible {
+            return AppDetailsAction::None;
+        }
+
+        let mut action = AppDetailsAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::INFO, t!("app-details-title")))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(420.0, 320.0))
+            .show(ctx, |ui| {
+                ui.label(RichText::new(&self.package).strong());
+                ui.separator();
+
+                if self.loading {
+                    ui.horizontal(|ui| {
+                        ui.add(egui::Spinner::new().size(14.0));
+                        ui.label(t!("app-details-loading"));
+                    });
+                } else if let Some(error) = &self.error {
+                    ui.label(RichText::new(error).color(Color32::RED));
+                } else if let Some(details) = &self.details {
+                    let unknown = || "—".to_string();
+                    let version = match (&details.version_name, &details.version_code) {
+                        (Some(name), Some(code)) => format!("{} ({})", name, code),
+                        (Some(name), None) => name.clone(),
+                        (None, Some(code)) => code.clone(),
+                        (None, None) => unknown(),
+                    };
+                    let rows = [
+                        (t!("app-details-version"), version),
+                        (t!("app-details-target-sdk"), details.target_sdk.map(|v| v.to_string()).unwrap_or_else(unknown)),
+                        (t!("app-details-min-sdk"), details.min_sdk.map(|v| v.to_string()).unwrap_or_else(unknown)),
+                        (t!("app-details-installed"), details.first_install_time.clone().unwrap_or_else(unknown)),
+                        (t!("app-details-updated"), details.last_update_time.clone().unwrap_or_else(unknown)),
+                        (t!("app-details-installer"), details.installer.clone().unwrap_or_else(unknown)),
+                        (t!("app-details-apk-size"), details.apk_size_bytes.map(format_file_size).unwrap_or_else(unknown)),
+                        (t!("app-details-data-size"), details.data_size_bytes.map(format_file_size).unwrap_or_else(unknown)),
+                    ];
+
+                    egui::Grid::new("app_details_grid").num_columns(2).striped(true).show(ui, |ui| {
+                        for (label, value) in rows {
+                            ui.label(RichText::new(label).color(Color32::GRAY));
+                            ui.label(value);
+                            ui.end_row();
+                        }
+                    });
+
+                    if !details.apk_paths.is_empty() {
+                        egui::CollapsingHeader::new(t!("app-details-apk-paths", count = details.apk_paths.len()))
+                            .id_salt("app_details_paths")
+                            .show(ui, |ui| {
+                                for path in &details.apk_paths {
+                                    ui.label(RichText::new(path).monospace().size(11.0));
+                                }
+                            });
+                    }
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(!self.loading, egui::Button::new(t!("app-details-refresh"))).clicked() {
+                        self.loading = true;
+                        self.error = None;
+                        action = AppDetailsAction::Refresh(self.package.clone());
+                    }
+                    if ui.button(format!("{} {}", egui_phosphor::fill::SHIELD_CHECK, t!("app-details-permissions"))).clicked() {
+                        action = AppDetailsAction::Permissions(self.package.clone());
+                    }
+                });
+            });
+
+        self.visible = open;
+        action
+    }
+}
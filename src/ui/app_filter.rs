@@ -0,0 +1,182 @@
+use crate::packages::{DeviceUser, PackageInfo};
+use crate::t;
+use crate::utils::format_file_size;
+use egui::Ui;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppSort {
+    Name,
+    InstallDate,
+    Size,
+}
+
+impl AppSort {
+    fn label(self) -> String {
+        match self {
+            AppSort::Name => t!("apps-sort-name"),
+            AppSort::InstallDate => t!("apps-sort-install-date"),
+            AppSort::Size => t!("apps-sort-size"),
+        }
+    }
+}
+
+/// Per-app troubleshooting actions offered from the app list dialogs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppAction {
+    Details,
+    Launch,
+    ForceStop,
+    ClearData,
+    Permissions,
+}
+
+/// Click and right-click handling for a package row
+pub fn app_context_menu(response: &egui::Response) -> Option<AppAction> {
+    let mut action = response.clicked().then_some(AppAction::Details);
+    response.context_menu(|ui| {
+        if ui.button(format!("{} {}", egui_phosphor::fill::INFO, t!("apps-details"))).clicked() {
+            action = Some(AppAction::Details);
+            ui.close();
+        }
+        if ui.button(format!("{} {}", egui_phosphor::fill::PLAY, t!("apps-launch"))).clicked() {
+            action = Some(AppAction::Launch);
+            ui.close();
+        }
+        if ui.button(format!("{} {}", egui_phosphor::fill::SHIELD_CHECK, t!("apps-permissions"))).clicked() {
+            action = Some(AppAction::Permissions);
+            ui.close();
+        }
+        if ui.button(format!("{} {}", egui_phosphor::fill::STOP_CIRCLE, t!("apps-force-stop"))).clicked() {
+            action = Some(AppAction::ForceStop);
+            ui.close();
+        }
+        if ui.button(format!("{} {}", egui_phosphor::fill::ERASER, t!("apps-clear-data"))).clicked() {
+            action = Some(AppAction::ClearData);
+            ui.close();
+        }
+    });
+    action
+}
+
+/// Picker for the user or work profile the app list dialogs act on; true when it changed
+pub fn user_selector(ui: &mut Ui, id_salt: &str, users: &[DeviceUser], selected: &mut u32) -> bool {
+    let mut changed = false;
+    ui.horizontal(|ui| {
+        ui.label(t!("apps-user"));
+        let current = users
+            .iter()
+            .find(|user| user.id == *selected)
+            .map_or_else(|| selected.to_string(), DeviceUser::label);
+        egui::ComboBox::from_id_salt(id_salt).selected_text(current).show_ui(ui, |ui| {
+            for user in users {
+                let label = if user.running {
+                    user.label()
+                } else {
+                    t!("apps-user-not-running", user = user.label())
+                };
+                if ui.selectable_value(selected, user.id, label).changed() {
+                    changed = true;
+                }
+            }
+        });
+    });
+    changed
+}
+
+/// Search box, kind filters and sort order shared by the app list dialogs
+pub struct AppListFilter {
+    pub search: String,
+    pub show_user: bool,
+    /// `None` when the list holds no system apps, so there is nothing to toggle
+    pub show_system: Option<bool>,
+    /// `None` when the list holds no disabled apps
+    pub show_disabled: Option<bool>,
+    pub sort: AppSort,
+}
+
+impl AppListFilter {
+    pub fn new(show_system: Option<bool>, show_disabled: Option<bool>) -> Self {
+        Self {
+            search: String::new(),
+            show_user: true,
+            show_system,
+            show_disabled,
+            sort: AppSort::Name,
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut Ui, id_salt: &str) {
+        ui.horizontal(|ui| {
+            ui.label(egui_phosphor::fill::MAGNIFYING_GLASS);
+            ui.add(
+                egui::TextEdit::singleline(&mut self.search)
+                    .hint_text(t!("apps-search"))
+                    .desired_width(200.0),
+            );
+            if !self.search.is_empty() && ui.small_button("✖").clicked() {
+                self.search.clear();
+            }
+        });
+        ui.horizontal(|ui| {
+            if let Some(show_system) = self.show_system.as_mut() {
+                ui.checkbox(&mut self.show_user, t!("apps-show-user"));
+                ui.checkbox(show_system, t!("apps-show-system"));
+            }
+            if let Some(show_disabled) = self.show_disabled.as_mut() {
+                ui.checkbox(show_disabled, t!("apps-show-disabled"));
+            }
+            egui::ComboBox::from_id_salt(id_salt)
+                .selected_text(self.sort.label())
+                .show_ui(ui, |ui| {
+                    for sort in [AppSort::Name, AppSort::InstallDate, AppSort::Size] {
+                        ui.selectable_value(&mut self.sort, sort, sort.label());
+                    }
+                });
+        });
+    }
+
+    pub fn matches(&self, app: &PackageInfo) -> bool {
+        if app.system && self.show_system == Some(false) {
+            return false;
+        }
+        if !app.system && self.show_system.is_some() && !self.show_user {
+            return false;
+        }
+        if !app.enabled && self.show_disabled == Some(false) {
+            return false;
+        }
+        let needle = self.search.trim().to_lowercase();
+        needle.is_empty() || app.package.to_lowercase().contains(&needle)
+    }
+
+    /// Filter and sort the list, returning references in display order
+    pub fn apply<'a>(&self, apps: &'a [PackageInfo]) -> Vec<&'a PackageInfo> {
+        let mut visible: Vec<&PackageInfo> = apps.iter().filter(|app| self.matches(app)).collect();
+        match self.sort {
+            AppSort::Name => visible.sort_by(|a, b| a.package.cmp(&b.package)),
+            AppSort::InstallDate => {
+                visible.sort_by(|a, b| b.first_install_time.cmp(&a.first_install_time))
+            }
+            AppSort::Size => visible.sort_by_key(|app| std::cmp::Reverse(app.size_kb)),
+        }
+        visible
+    }
+}
+
+/// Short "system · disabled · 12.3 MB · 2024-01-01" summary shown beside each package
+pub fn app_details(app: &PackageInfo) -> String {
+    let mut parts = Vec::new();
+    if app.system {
+        parts.push(t!("apps-system"));
+    }
+    if !app.enabled {
+        parts.push(t!("apps-disabled"));
+    }
+    if let Some(size) = app.size_kb {
+        parts.push(format_file_size(size * 1024));
+    }
+    if let Some(date) = app.first_install_time.as_deref().and_then(|t| t.split_whitespace().next()) {
+        parts.push(date.to_string());
+    }
+    parts.join(" · ")
+}
@@ -0,0 +1,281 @@
+use crate::config::{AutomationAction, AutomationRule, AutomationTrigger};
+use crate::t;
+use egui::{Color32, RichText, Window};
+
+pub enum AutomationWindowAction {
+    None,
+    /// Run a rule right away, on the devices it covers
+    RunNow(AutomationRule),
+    /// The edited list of rules to store in the config
+    Save(Vec<AutomationRule>),
+}
+
+/// Automation rules with a form to add or edit one
+pub struct AutomationWindow {
+    visible: bool,
+    rules: Vec<AutomationRule>,
+    /// Rule in the form, with its index when it is an edit
+    draft: Option<(Option<usize>, AutomationRule)>,
+    draft_error: Option<String>,
+    /// Connected serials, offered for the device field
+    devices: Vec<String>,
+}
+
+impl Default for AutomationWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AutomationWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            rules: Vec::new(),
+            draft: None,
+            draft_error: None,
+            devices: Vec::new(),
+        }
+    }
+
+    pub fn open(&mut self, rules: Vec<AutomationRule>) {
+        if !self.visible {
+            self.rules = rules;
+            self.draft = None;
+        }
+        self.visible = true;
+    }
+
+    pub fn set_devices(&mut self, devices: Vec<String>) {
+        self.devices = devices;
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> AutomationWindowAction {
+        if !self.visible {
+            return AutomationWindowAction::None;
+        }
+
+        let mut action = AutomationWindowAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::ROBOT, t!("automation-title")))
+            .id(egui::Id::new("automation"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(520.0, 380.0))
+            .show(ctx, |ui| {
+                ui.label(RichText::new(t!("automation-hint")).size(11.0).color(Color32::GRAY));
+                ui.separator();
+
+                if self.rules.is_empty() {
+                    ui.label(RichText::new(t!("automation-empty")).color(Color32::GRAY));
+                }
+                let mut remove = None;
+                let mut toggled = false;
+                for (index, rule) in self.rules.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        toggled |= ui.checkbox(&mut rule.enabled, "").changed();
+                        ui.label(RichText::new(&rule.name).strong());
+                        ui.label(RichText::new(describe(rule)).weak());
+
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui
+                                .small_button(egui_phosphor::fill::TRASH)
+                                .on_hover_text(t!("automation-delete"))
+                                .clicked()
+                            {
+                                remove = Some(index);
+                            }
+                            if ui
+                                .small_button(egui_phosphor::fill::PENCIL_SIMPLE)
+                                .on_hover_text(t!("automation-edit"))
+                                .clicked()
+                            {
+                                self.draft = Some((Some(index), rule.clone()));
+                                self.draft_error = None;
+                            }
+                            if ui
+                                .small_button(egui_phosphor::fill::PLAY)
+                                .on_hover_text(t!("automation-run-now"))
+                                .clicked()
+                            {
+                                action = AutomationWindowAction::RunNow(rule.clone());
+                            }
+                        });
+                    });
+                }
+                if let Some(index) = remove {
+                    self.rules.remove(index);
+                }
+                if remove.is_some() || toggled {
+                    action = AutomationWindowAction::Save(self.rules.clone());
+                }
+
+                ui.separator();
+                match &mut self.draft {
+                    None => {
+                        if ui.button(format!("{} {}", egui_phosphor::fill::PLUS, t!("automation-add"))).clicked() {
+                            self.draft = Some((None, AutomationRule::default()));
+                            self.draft_error = None;
+                        }
+                    }
+                    Some((editing, draft)) => {
+                        show_form(ui, draft, &self.devices);
+
+                        let mut done = false;
+                        ui.horizontal(|ui| {
+                            if ui.button(t!("automation-save")).clicked() {
+                                let duplicate = self.rules.iter().enumerate().any(|(index, rule)| {
+                                    rule.name.trim() == draft.name.trim() && Some(index) != *editing
+                                });
+                                match crate::automation::validate(draft) {
+                                    Err(e) => self.draft_error = Some(e),
+                                    Ok(()) if duplicate => self.draft_error = Some(t!("automation-duplicate")),
+                                    Ok(()) => {
+                                        draft.name = draft.name.trim().to_string();
+                                        draft.device = draft.device.trim().to_string();
+                                        match editing {
+                                            Some(index) => self.rules[*index] = draft.clone(),
+                                            None => self.rules.push(draft.clone()),
+                                        }
+                                        action = AutomationWindowAction::Save(self.rules.clone());
+                                        done = true;
+                                    }
+                                }
+                            }
+                            if ui.button(t!("automation-cancel")).clicked() {
+                                done = true;
+                            }
+                        });
+                        if let Some(error) = &self.draft_error {
+                            ui.label(RichText::new(error).color(Color32::RED));
+                        }
+                        if done {
+                            self.draft = None;
+                            self.draft_error = None;
+                        }
+                    }
+                }
+            });
+
+        self.visible = open;
+        action
+    }
+}
+
+fn show_form(ui: &mut egui::Ui, draft: &mut AutomationRule, devices: &[String]) {
+    egui::Grid::new("automation_form").num_columns(2).show(ui, |ui| {
+        ui.label(t!("automation-name"));
+        ui.text_edit_singleline(&mut draft.name);
+        ui.end_row();
+
+        ui.label(t!("automation-device"));
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut draft.device)
+                    .hint_text(t!("automation-any-device"))
+                    .desired_width(160.0),
+            );
+            egui::ComboBox::from_id_salt("automation_device")
+                .selected_text("")
+                .width(24.0)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut draft.device, String::new(), t!("automation-any-device"));
+                    for serial in devices {
+                        ui.selectable_value(&mut draft.device, serial.clone(), serial);
+                    }
+                });
+        });
+        ui.end_row();
+
+        ui.label(t!("automation-when"));
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_salt("automation_trigger")
+                .selected_text(trigger_label(&draft.trigger))
+                .show_ui(ui, |ui| {
+                    for trigger in [
+                        AutomationTrigger::DeviceConnected,
+                        AutomationTrigger::TimeOfDay { hour: 9, minute: 0 },
+                        AutomationTrigger::BatteryBelow { percent: 20 },
+                    ] {
+                        let selected = std::mem::discriminant(&trigger) == std::mem::discriminant(&draft.trigger);
+                        if ui.selectable_label(selected, trigger_label(&trigger)).clicked() && !selected {
+                            draft.trigger = trigger;
+                        }
+                    }
+                });
+            match &mut draft.trigger {
+                AutomationTrigger::DeviceConnected => {}
+                AutomationTrigger::TimeOfDay { hour, minute } => {
+                    ui.add(egui::DragValue::new(hour).range(0..=23).custom_formatter(|n, _| format!("{:02}", n)));
+                    ui.label(":");
+                    ui.add(egui::DragValue::new(minute).range(0..=59).custom_formatter(|n, _| format!("{:02}", n)));
+                }
+                AutomationTrigger::BatteryBelow { percent } => {
+                    ui.add(egui::DragValue::new(percent).range(1..=100).suffix("%"));
+                }
+            }
+        });
+        ui.end_row();
+
+        ui.label(t!("automation-then"));
+        ui.vertical(|ui| {
+            egui::ComboBox::from_id_salt("automation_action")
+                .selected_text(action_label(&draft.action))
+                .show_ui(ui, |ui| {
+                    for action in [
+                        AutomationAction::StartMirroring,
+                        AutomationAction::Screenshot,
+                        AutomationAction::Shell { command: String::new() },
+                    ] {
+                        let selected = std::mem::discriminant(&action) == std::mem::discriminant(&draft.action);
+                        if ui.selectable_label(selected, action_label(&action)).clicked() && !selected {
+                            draft.action = action;
+                        }
+                    }
+                });
+            if let AutomationAction::Shell { command } = &mut draft.action {
+                ui.add(
+                    egui::TextEdit::singleline(command)
+                        .hint_text("svc wifi enable")
+                        .font(egui::TextStyle::Monospace),
+                );
+            }
+        });
+        ui.end_row();
+    });
+}
+
+fn trigger_label(trigger: &AutomationTrigger) -> String {
+    match trigger {
+        AutomationTrigger::DeviceConnected => t!("automation-trigger-connected"),
+        AutomationTrigger::TimeOfDay { .. } => t!("automation-trigger-time"),
+        AutomationTrigger::BatteryBelow { .. } => t!("automation-trigger-battery"),
+    }
+}
+
+fn action_label(action: &AutomationAction) -> String {
+    match action {
+        AutomationAction::StartMirroring => t!("automation-action-mirror"),
+        AutomationAction::Screenshot => t!("automation-action-screenshot"),
+        AutomationAction::Shell { .. } => t!("automation-action-shell"),
+    }
+}
+
+/// One line such as "At 09:00 → Take a screenshot"
+fn describe(rule: &AutomationRule) -> String {
+    let when = match &rule.trigger {
+        AutomationTrigger::TimeOfDay { hour, minute } => {
+            format!("{} {:02}:{:02}", trigger_label(&rule.trigger), hour, minute)
+        }
+        AutomationTrigger::BatteryBelow { percent } => format!("{} {}%", trigger_label(&rule.trigger), percent),
+        AutomationTrigger::DeviceConnected => trigger_label(&rule.trigger),
+    };
+    let then = match &rule.action {
+        AutomationAction::Shell { command } => format!("adb shell {}", command),
+        action => action_label(action),
+    };
+    let device = if rule.device.is_empty() { t!("automation-any-device") } else { rule.device.clone() };
+    format!("{} → {} ({})", when, then, device)
+}
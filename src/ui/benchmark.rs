@@ -0,0 +1,171 @@
+use crate::benchmark::{best_result, BenchmarkCase, BenchmarkResult, CASE_DURATION};
+use crate::t;
+use egui::{Color32, RichText, Window};
+
+pub enum BenchmarkAction {
+    None,
+    Start(Vec<BenchmarkCase>),
+    Cancel,
+    Apply(BenchmarkCase),
+}
+
+/// Runs short scrcpy sessions per configuration and compares their frame rates
+pub struct BenchmarkWindow {
+    visible: bool,
+    cases: Vec<(BenchmarkCase, bool)>,
+    results: Vec<BenchmarkResult>,
+    running: Option<usize>, // index into the selected cases of the one being measured
+    total: usize,
+}
+
+impl Default for BenchmarkWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BenchmarkWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            cases: Vec::new(),
+            results: Vec::new(),
+            running: None,
+            total: 0,
+        }
+    }
+
+    pub fn open(&mut self, cases: Vec<BenchmarkCase>) {
+        self.visible = true;
+        if self.running.is_none() {
+            self.cases = cases.into_iter().map(|case| (case, true)).collect();
+        }
+    }
+
+    pub fn set_progress(&mut self, index: usize, result: Option<BenchmarkResult>) {
+        self.running = Some(index);
+        if let Some(result) = result {
+            self.results.push(result);
+        }
+    }
+
+    pub fn finish(&mut self) {
+        self.running = None;
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> BenchmarkAction {
+        if !self.visible {
+            return BenchmarkAction::None;
+        }
+
+        let mut action = BenchmarkAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::GAUGE, t!("benchmark-title")))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(520.0, 460.0))
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new(t!("benchmark-intro", seconds = CASE_DURATION.as_secs()))
+                    .size(11.0)
+                    .color(Color32::GRAY),
+                );
+                ui.separator();
+
+                ui.add_enabled_ui(self.running.is_none(), |ui| {
+                    ui.horizontal_wrapped(|ui| {
+                        for (case, enabled) in &mut self.cases {
+                            ui.checkbox(enabled, case.label());
+                        }
+                    });
+                });
+
+                ui.horizontal(|ui| {
+                    match self.running {
+                        Some(index) => {
+                            ui.add(egui::Spinner::new().size(14.0));
+                            ui.label(t!("benchmark-measuring", current = index + 1, total = self.total));
+                            if ui.button(t!("benchmark-cancel")).clicked() {
+                                action = BenchmarkAction::Cancel;
+                            }
+                        }
+                        None => {
+                            let selected: Vec<BenchmarkCase> = self
+                                .cases
+                                .iter()
+                                .filter(|(_, enabled)| *enabled)
+                                .map(|(case, _)| case.clone())
+                                .collect();
+                            if ui
+                                .add_enabled(!selected.is_empty(), egui::Button::new(format!("{} {}", egui_phosphor::fill::PLAY, t!("benchmark-run"))))
+                                .clicked()
+                            {
+                                self.results.clear();
+                                self.total = selected.len();
+                                self.running = Some(0);
+                                action = BenchmarkAction::Start(selected);
+                            }
+                        }
+                    }
+                });
+
+                if self.results.is_empty() {
+                    return;
+                }
+                ui.separator();
+
+                let best = best_result(&self.results).map(|best| best.case.clone());
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::Grid::new("benchmark_results_grid")
+                        .num_columns(5)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for header in ["benchmark-configuration", "benchmark-avg-fps", "benchmark-min-fps", "benchmark-skipped"] {
+                                ui.label(RichText::new(t!(header)).strong());
+                            }
+                            ui.label("");
+                            ui.end_row();
+
+                            for result in &self.results {
+                                let is_best = best.as_ref() == Some(&result.case);
+                                let mut label = RichText::new(result.case.label());
+                                if is_best {
+                                    label = label.strong().color(Color32::GREEN);
+                                }
+                                ui.label(label);
+                                match &result.error {
+                                    Some(error) => {
+                                        ui.label("-");
+                                        ui.label("-");
+                                        ui.label("-");
+                                        ui.label(RichText::new(egui_phosphor::fill::X_CIRCLE).color(Color32::RED))
+                                            .on_hover_text(error);
+                                    }
+                                    None => {
+                                        ui.label(format!("{:.1}", result.avg_fps));
+                                        ui.label(result.min_fps.to_string());
+                                        ui.label(result.skipped_frames.to_string());
+                                        ui.label(if is_best { t!("benchmark-best") } else { String::new() });
+                                    }
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+
+                if let Some(best) = best
+                    && self.running.is_none()
+                    && ui
+                        .button(format!("{} {}", egui_phosphor::fill::CHECK, t!("benchmark-apply-best", case = best.label())))
+                        .clicked()
+                {
+                    action = BenchmarkAction::Apply(best);
+                }
+            });
+
+        self.visible = open;
+        action
+    }
+}
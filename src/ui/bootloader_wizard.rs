@@ -0,0 +1,284 @@
+use crate::fastboot::{oem_notes, FastbootDevice};
+use crate::t;
+use egui::{Color32, RichText, Window};
+
+pub enum BootloaderAction {
+    None,
+    /// `adb reboot bootloader` on the device the wizard was opened for
+    RebootToBootloader(String),
+    Scan,
+    Run { serial: String, lock: bool },
+    Reboot(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Choose,
+    Connect,
+    Confirm,
+    Running,
+    Done,
+}
+
+/// Walks through `fastboot flashing unlock` / `lock`: pick the operation, get the device into
+/// the bootloader, confirm twice, then let the user answer on the device
+pub struct BootloaderWizard {
+    visible: bool,
+    step: Step,
+    lock: bool,
+    /// Device seen by adb that gets rebooted into the bootloader
+    adb_device: Option<String>,
+    manufacturer: String,
+    fastboot_found: bool,
+    devices: Result<Vec<FastbootDevice>, String>,
+    chosen: Option<String>,
+    backed_up: bool,
+    understood: bool,
+    typed: String,
+    result: Option<Result<String, String>>,
+}
+
+impl Default for BootloaderWizard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BootloaderWizard {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            step: Step::Choose,
+            lock: false,
+            adb_device: None,
+            manufacturer: String::new(),
+            fastboot_found: false,
+            devices: Ok(Vec::new()),
+            chosen: None,
+            backed_up: false,
+            understood: false,
+            typed: String::new(),
+            result: None,
+        }
+    }
+
+    pub fn open(&mut self, adb_device: Option<String>, fastboot_found: bool) {
+        if self.visible && matches!(self.step, Step::Running) {
+            return;
+        }
+        *self = Self {
+            visible: true,
+            adb_device,
+            fastboot_found,
+            ..Self::new()
+        };
+    }
+
+    pub fn set_manufacturer(&mut self, manufacturer: String) {
+        self.manufacturer = manufacturer;
+    }
+
+    /// The devices now in fastboot mode; the choice is kept once the user is confirming it
+    pub fn set_devices(&mut self, devices: Result<Vec<FastbootDevice>, String>) {
+        if let Ok(found) = &devices
+            && self.step != Step::Confirm
+            && self.chosen.as_ref().is_none_or(|chosen| !found.iter().any(|device| device.serial == *chosen))
+        {
+            self.chosen = found.first().map(|device| device.serial.clone());
+        }
+        self.devices = devices;
+    }
+
+    pub fn set_result(&mut self, result: Result<String, String>) {
+        self.result = Some(result);
+        self.step = Step::Done;
+    }
+
+    /// Fastboot devices should be polled while the wizard waits for one
+    pub fn wants_scan(&self) -> bool {
+        self.visible && matches!(self.step, Step::Connect | Step::Confirm)
+    }
+
+    fn word(&self) -> &'static str {
+        if self.lock { "LOCK" } else { "UNLOCK" }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> BootloaderAction {
+        if !self.visible {
+            return BootloaderAction::None;
+        }
+
+        let mut action = BootloaderAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::LOCK_KEY, t!("bootloader-title")))
+            .id(egui::Id::new("bootloader_wizard"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(480.0, 420.0))
+            .show(ctx, |ui| {
+                if !self.fastboot_found {
+                    ui.label(RichText::new(t!("bootloader-no-fastboot")).color(Color32::RED));
+                    return;
+                }
+                match self.step {
+                    Step::Choose => self.show_choose(ui, &mut action),
+                    Step::Connect => self.show_connect(ui, &mut action),
+                    Step::Confirm => self.show_confirm(ui, &mut action),
+                    Step::Running => {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label(t!("bootloader-running"));
+                        });
+                        ui.label(RichText::new(t!("bootloader-answer-on-device")).strong());
+                    }
+                    Step::Done => {
+                        match &self.result {
+                            Some(Ok(message)) => {
+                                ui.label(RichText::new(message).color(Color32::GREEN));
+                            }
+                            Some(Err(message)) => {
+                                ui.label(RichText::new(message).color(Color32::RED));
+                            }
+                            None => {}
+                        }
+                        ui.horizontal(|ui| {
+                            if let Some(serial) = &self.chosen
+                                && ui.button(t!("bootloader-reboot")).clicked()
+                            {
+                                action = BootloaderAction::Reboot(serial.clone());
+                            }
+                            if ui.button(t!("bootloader-close")).clicked() {
+                                self.visible = false;
+                            }
+                        });
+                    }
+                }
+            });
+
+        self.visible = open && self.visible;
+        action
+    }
+
+    fn show_choose(&mut self, ui: &mut egui::Ui, action: &mut BootloaderAction) {
+        ui.radio_value(&mut self.lock, false, t!("bootloader-unlock"));
+        ui.radio_value(&mut self.lock, true, t!("bootloader-lock"));
+        ui.add_space(4.0);
+        let warning = if self.lock { t!("bootloader-lock-warning") } else { t!("bootloader-unlock-warning") };
+        ui.label(RichText::new(warning).color(Color32::YELLOW));
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.label(t!("bootloader-manufacturer"));
+            ui.add(egui::TextEdit::singleline(&mut self.manufacturer).hint_text("Google").desired_width(140.0));
+        });
+        for note in oem_notes(&self.manufacturer) {
+            ui.label(RichText::new(format!("• {}", note)).size(11.0));
+        }
+
+        ui.separator();
+        let next = match &self.adb_device {
+            Some(device) => t!("bootloader-next-reboot", device = device),
+            None => t!("bootloader-next"),
+        };
+        if ui.button(next).clicked() {
+            // Only once: after the reboot adb no longer sees the device
+            if let Some(device) = self.adb_device.take() {
+                *action = BootloaderAction::RebootToBootloader(device);
+            } else {
+                *action = BootloaderAction::Scan;
+            }
+            self.step = Step::Connect;
+        }
+    }
+
+    fn show_connect(&mut self, ui: &mut egui::Ui, action: &mut BootloaderAction) {
+        match &self.devices {
+            Ok(devices) if devices.is_empty() => {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(t!("bootloader-waiting"));
+                });
+                ui.label(RichText::new(t!("bootloader-manual-hint")).size(11.0).color(Color32::GRAY));
+            }
+            Ok(devices) => {
+                for device in devices {
+                    let state = match device.info.unlocked {
+                        Some(true) => t!("bootloader-state-unlocked"),
+                        Some(false) => t!("bootloader-state-locked"),
+                        None => t!("bootloader-state-unknown"),
+                    };
+                    let product = device.info.product.as_deref().unwrap_or("?");
+                    ui.radio_value(
+                        &mut self.chosen,
+                        Some(device.serial.clone()),
+                        format!("{} ({}) – {}", device.serial, product, state),
+                    );
+                }
+                if let Some(device) = devices.iter().find(|device| Some(&device.serial) == self.chosen.as_ref()) {
+                    if device.info.unlocked == Some(!self.lock) {
+                        ui.label(RichText::new(t!("bootloader-already")).color(Color32::GRAY));
+                    }
+                    if !self.lock && device.info.unlock_ability == Some(false) {
+                        ui.label(RichText::new(t!("bootloader-no-unlock-ability")).color(Color32::YELLOW));
+                    }
+                }
+            }
+            Err(e) => {
+                ui.label(RichText::new(e).color(Color32::RED));
+            }
+        }
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            if ui.button(t!("bootloader-back")).clicked() {
+                self.step = Step::Choose;
+            }
+            if ui.button(t!("bootloader-rescan")).clicked() {
+                *action = BootloaderAction::Scan;
+            }
+            if ui.add_enabled(self.chosen.is_some(), egui::Button::new(t!("bootloader-next"))).clicked() {
+                self.step = Step::Confirm;
+            }
+        });
+    }
+
+    fn show_confirm(&mut self, ui: &mut egui::Ui, action: &mut BootloaderAction) {
+        let Some(serial) = self.chosen.clone() else {
+            self.step = Step::Connect;
+            return;
+        };
+        ui.label(RichText::new(t!("bootloader-confirm-device", device = serial.clone())).strong());
+        let present = self.devices.as_ref().is_ok_and(|found| found.iter().any(|device| device.serial == serial));
+        if !present {
+            ui.label(RichText::new(t!("bootloader-device-gone", device = serial.clone())).color(Color32::YELLOW));
+        }
+        ui.checkbox(&mut self.backed_up, t!("bootloader-confirm-backup"));
+        let understood = if self.lock { t!("bootloader-confirm-lock") } else { t!("bootloader-confirm-wipe") };
+        ui.checkbox(&mut self.understood, understood);
+        ui.horizontal(|ui| {
+            ui.label(t!("bootloader-confirm-type", word = self.word()));
+            ui.add(egui::TextEdit::singleline(&mut self.typed).desired_width(100.0));
+        });
+
+        let ready = present && self.backed_up && self.understood && self.typed.trim() == self.word();
+        ui.separator();
+        ui.horizontal(|ui| {
+            if ui.button(t!("bootloader-back")).clicked() {
+                self.step = Step::Connect;
+            }
+            let label = if self.lock { t!("bootloader-run-lock") } else { t!("bootloader-run-unlock") };
+            if ui
+                .add_enabled(
+                    ready,
+                    egui::Button::new(RichText::new(label).color(Color32::WHITE)).fill(Color32::DARK_RED),
+                )
+                .clicked()
+            {
+                *action = BootloaderAction::Run { serial, lock: self.lock };
+                self.step = Step::Running;
+            }
+        });
+    }
+}
@@ -0,0 +1,161 @@
+use crate::capabilities::{grant_guide, Capability, CapabilityStatus, DeviceCapabilities};
+use crate::t;
+use egui::{Color32, RichText, Window};
+
+pub enum CapabilityAction {
+    None,
+    Recheck,
+    GrantCompanion(String),
+}
+
+/// Shows which privileged tools work on the device and walks through lifting OEM restrictions
+pub struct CapabilityWizard {
+    visible: bool,
+    capabilities: Option<DeviceCapabilities>,
+    probing: bool,
+    focus: Option<Capability>,
+    companion_package: String,
+    message: Option<Result<String, String>>,
+}
+
+impl Default for CapabilityWizard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CapabilityWizard {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            capabilities: None,
+            probing: false,
+            focus: None,
+            companion_package: String::new(),
+            message: None,
+        }
+    }
+
+    /// Open the wizard, optionally highlighting the capability a tool was missing
+    pub fn open(&mut self, focus: Option<Capability>) {
+        self.visible = true;
+        self.focus = focus;
+        self.message = None;
+    }
+
+    pub fn set_probing(&mut self) {
+        self.probing = true;
+    }
+
+    pub fn set_capabilities(&mut self, capabilities: Option<DeviceCapabilities>) {
+        self.capabilities = capabilities;
+        self.probing = false;
+    }
+
+    pub fn set_message(&mut self, message: Result<String, String>) {
+        self.message = Some(message);
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> CapabilityAction {
+        if !self.visible {
+            return CapabilityAction::None;
+        }
+
+        let mut action = CapabilityAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::KEY, t!("capabilities-title")))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(480.0, 420.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(!self.probing, egui::Button::new(t!("capabilities-recheck"))).clicked() {
+                        self.message = None;
+                        action = CapabilityAction::Recheck;
+                    }
+                    if self.probing {
+                        ui.add(egui::Spinner::new().size(14.0));
+                        ui.label(t!("capabilities-checking"));
+                    }
+                });
+                ui.separator();
+
+                let Some(capabilities) = &self.capabilities else {
+                    if !self.probing {
+                        ui.label(t!("capabilities-no-device"));
+                    }
+                    return;
+                };
+
+                if !capabilities.manufacturer.is_empty() {
+                    ui.label(RichText::new(t!("capabilities-manufacturer", name = capabilities.manufacturer)).color(Color32::GRAY));
+                }
+
+                let mut any_denied = false;
+                for (capability, status) in &capabilities.statuses {
+                    let (icon, color, detail) = match status {
+                        CapabilityStatus::Available => (egui_phosphor::fill::CHECK_CIRCLE, Color32::GREEN, None),
+                        CapabilityStatus::Denied(reason) => {
+                            any_denied = true;
+                            (egui_phosphor::fill::X_CIRCLE, Color32::RED, Some(reason))
+                        }
+                        CapabilityStatus::Unknown(reason) => (egui_phosphor::fill::QUESTION, Color32::GRAY, Some(reason)),
+                    };
+
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(icon).color(color));
+                        let mut label = RichText::new(capability.label());
+                        if self.focus == Some(*capability) {
+                            label = label.strong();
+                        }
+                        let response = ui.label(label);
+                        if let Some(detail) = detail {
+                            response.on_hover_text(detail);
+                        }
+                    });
+                    ui.label(
+                        RichText::new(t!("capabilities-used-by", features = capability.features().join(", ")))
+                            .size(11.0)
+                            .color(Color32::GRAY),
+                    );
+                }
+
+                if any_denied {
+                    ui.separator();
+                    ui.label(RichText::new(t!("capabilities-how-to")).strong());
+                    for (index, step) in grant_guide(&capabilities.manufacturer).iter().enumerate() {
+                        ui.label(format!("{}. {}", index + 1, step));
+                    }
+
+                    ui.add_space(6.0);
+                    ui.label(t!("capabilities-companion"));
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.companion_package)
+                                .hint_text("com.example.companion")
+                                .desired_width(220.0),
+                        );
+                        let package = self.companion_package.trim().to_string();
+                        if ui.add_enabled(!package.is_empty(), egui::Button::new(t!("capabilities-grant"))).clicked() {
+                            action = CapabilityAction::GrantCompanion(package);
+                        }
+                    });
+                }
+
+                match &self.message {
+                    Some(Ok(message)) => {
+                        ui.label(RichText::new(message).color(Color32::GREEN));
+                    }
+                    Some(Err(message)) => {
+                        ui.label(RichText::new(message).color(Color32::RED));
+                    }
+                    None => {}
+                }
+            });
+
+        self.visible = open;
+        action
+    }
+}
@@ -0,0 +1,118 @@
+use crate::device_clipboard::ClipboardHistory;
+use crate::t;
+use egui::{Color32, RichText, Window};
+
+/// Clips copied on the device while watching, each copyable to the host
+pub struct ClipboardHistoryWindow {
+    visible: bool,
+    watching: bool,
+    history: ClipboardHistory,
+    error: Option<String>,
+}
+
+impl Default for ClipboardHistoryWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClipboardHistoryWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            watching: false,
+            history: ClipboardHistory::default(),
+            error: None,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.visible = true;
+    }
+
+    /// Whether the device clipboard should be polled; keeps going when the window is closed
+    pub fn is_watching(&self) -> bool {
+        self.watching
+    }
+
+    pub fn set_clip(&mut self, device: &str, clip: Result<Option<String>, String>) {
+        match clip {
+            Ok(Some(text)) => {
+                self.history.push(device, text);
+                self.error = None;
+            }
+            Ok(None) => self.error = None,
+            Err(e) => {
+                // Not supported on this device: stop polling instead of failing every interval
+                self.watching = false;
+                self.error = Some(e);
+            }
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        if !self.visible {
+            return;
+        }
+
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::CLIPBOARD_TEXT, t!("clipboard-title")))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(460.0, 400.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.checkbox(&mut self.watching, t!("clipboard-watch")).changed() && self.watching {
+                        self.error = None;
+                    }
+                    if ui
+                        .add_enabled(!self.history.entries.is_empty(), egui::Button::new(t!("clipboard-clear")))
+                        .clicked()
+                    {
+                        self.history.clear();
+                    }
+                });
+                ui.label(
+                    RichText::new(t!("clipboard-session-only"))
+                        .size(11.0)
+                        .color(Color32::GRAY),
+                );
+                if let Some(error) = &self.error {
+                    ui.label(RichText::new(error).color(Color32::RED));
+                }
+                ui.separator();
+
+                if self.history.entries.is_empty() {
+                    ui.label(RichText::new(t!("clipboard-empty")).color(Color32::GRAY));
+                    return;
+                }
+
+                egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                    for (index, entry) in self.history.entries.iter().enumerate() {
+                        ui.push_id(index, |ui| {
+                            ui.horizontal(|ui| {
+                                if ui
+                                    .small_button(egui_phosphor::fill::COPY)
+                                    .on_hover_text(t!("clipboard-copy"))
+                                    .clicked()
+                                {
+                                    ui.ctx().copy_text(entry.text.clone());
+                                }
+                                ui.label(
+                                    RichText::new(format!("{} · {}", entry.seen_at.format("%H:%M:%S"), entry.device))
+                                        .size(10.0)
+                                        .color(Color32::GRAY),
+                                );
+                            });
+                            ui.label(RichText::new(&entry.text).monospace());
+                        });
+                        ui.separator();
+                    }
+                });
+            });
+
+        self.visible = open;
+    }
+}
@@ -0,0 +1,265 @@
+use crate::t;
+use egui::{Color32, RichText, Sense, Stroke, TextureHandle, Window};
+
+pub enum ColorPickerAction {
+    None,
+    /// Take the screenshot again
+    Refresh,
+}
+
+/// Pixels across the magnifier next to the screenshot
+const MAGNIFIER_PIXELS: i64 = 15;
+/// Colors kept in the picked list
+const MAX_PICKED: usize = 12;
+
+/// A color clicked on the screenshot and where it was
+#[derive(Clone, Copy)]
+struct Pick {
+    color: Color32,
+    x: u32,
+    y: u32,
+}
+
+/// A full-resolution screenshot to hover or click for the exact color of a pixel, in hex and RGB
+pub struct ColorPickerWindow {
+    visible: bool,
+    device: String,
+    image: Option<egui::ColorImage>,
+    texture: Option<TextureHandle>,
+    loading: bool,
+    error: Option<String>,
+    hovered: Option<Pick>,
+    picked: Vec<Pick>,
+}
+
+impl Default for ColorPickerWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ColorPickerWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            device: String::new(),
+            image: None,
+            texture: None,
+            loading: false,
+            error: None,
+            hovered: None,
+            picked: Vec::new(),
+        }
+    }
+
+    pub fn open(&mut self, device: String) {
+        if device != self.device {
+            self.image = None;
+            self.texture = None;
+        }
+        self.visible = true;
+        self.device = device;
+    }
+
+    pub fn device(&self) -> &str {
+        &self.device
+    }
+
+    pub fn set_loading(&mut self) {
+        self.loading = true;
+    }
+
+    pub fn set_screenshot(&mut self, result: Result<egui::ColorImage, String>) {
+        self.loading = false;
+        match result {
+            Ok(image) => {
+                self.image = Some(image);
+                self.texture = None;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> ColorPickerAction {
+        if !self.visible {
+            return ColorPickerAction::None;
+        }
+        if self.texture.is_none()
+            && let Some(image) = &self.image
+        {
+            // Nearest filtering keeps single pixels sharp when the screenshot is shown larger
+            self.texture = Some(ctx.load_texture("color_picker", image.clone(), egui::TextureOptions::NEAREST));
+        }
+
+        let mut action = ColorPickerAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::EYEDROPPER, t!("color-picker-title")))
+            .id(egui::Id::new("color_picker"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(640.0, 680.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(&self.device).weak());
+                    if ui
+                        .add_enabled(!self.loading, egui::Button::new(egui_phosphor::fill::CAMERA))
+                        .on_hover_text(t!("color-picker-refresh"))
+                        .clicked()
+                    {
+                        action = ColorPickerAction::Refresh;
+                    }
+                    if self.loading {
+                        ui.add(egui::Spinner::new().size(14.0));
+                    }
+                });
+                ui.label(RichText::new(t!("color-picker-hint")).size(11.0).color(Color32::GRAY));
+                if let Some(error) = &self.error {
+                    ui.label(RichText::new(error).color(Color32::RED));
+                }
+                ui.separator();
+
+                if self.texture.is_none() {
+                    if !self.loading {
+                        ui.label(RichText::new(t!("color-picker-empty")).color(Color32::GRAY));
+                    }
+                    return;
+                }
+                ui.horizontal_top(|ui| {
+                    self.show_screenshot(ui);
+                    ui.separator();
+                    ui.vertical(|ui| {
+                        self.show_magnifier(ui);
+                        ui.add_space(6.0);
+                        match self.hovered {
+                            Some(pick) => show_pick(ui, pick),
+                            None => {
+                                ui.label(RichText::new(t!("color-picker-hover")).color(Color32::GRAY));
+                            }
+                        }
+                        ui.separator();
+                        self.show_picked(ui);
+                    });
+                });
+            });
+
+        self.visible = open;
+        action
+    }
+
+    /// The screenshot fitted to the window; hovering it reads the pixel below and clicking keeps it
+    fn show_screenshot(&mut self, ui: &mut egui::Ui) {
+        let (Some(texture), Some(image)) = (&self.texture, &self.image) else {
+            return;
+        };
+        let image_size = texture.size_vec2();
+        let available = egui::vec2(ui.available_width() - 240.0, ui.available_height()).max(egui::vec2(120.0, 120.0));
+        let scale = (available.x / image_size.x).min(available.y / image_size.y).min(1.0);
+        let response = ui.add(
+            egui::Image::new(texture)
+                .fit_to_exact_size(image_size * scale)
+                .sense(Sense::click()),
+        );
+        let rect = response.rect;
+
+        self.hovered = response.hover_pos().and_then(|position| {
+            let offset = position - rect.min;
+            let x = (offset.x / rect.width() * image.size[0] as f32) as u32;
+            let y = (offset.y / rect.height() * image.size[1] as f32) as u32;
+            pixel(image, x as i64, y as i64).map(|color| Pick { color, x, y })
+        });
+        if response.clicked()
+            && let Some(pick) = self.hovered
+        {
+            self.picked.retain(|picked| picked.color != pick.color);
+            self.picked.insert(0, pick);
+            self.picked.truncate(MAX_PICKED);
+        }
+        if self.hovered.is_some() {
+            ui.ctx().set_cursor_icon(egui::CursorIcon::Crosshair);
+        }
+    }
+
+    /// The pixels around the hovered one, enlarged, with the hovered pixel outlined
+    fn show_magnifier(&self, ui: &mut egui::Ui) {
+        let cell = 12.0;
+        let side = cell * MAGNIFIER_PIXELS as f32;
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(side, side), Sense::hover());
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 0.0, ui.visuals().extreme_bg_color);
+        let (Some(image), Some(hovered)) = (&self.image, self.hovered) else {
+            return;
+        };
+        let radius = MAGNIFIER_PIXELS / 2;
+        for row in 0..MAGNIFIER_PIXELS {
+            for column in 0..MAGNIFIER_PIXELS {
+                let x = i64::from(hovered.x) + column - radius;
+                let y = i64::from(hovered.y) + row - radius;
+                if let Some(color) = pixel(image, x, y) {
+                    let min = rect.min + egui::vec2(column as f32 * cell, row as f32 * cell);
+                    painter.rect_filled(egui::Rect::from_min_size(min, egui::vec2(cell, cell)), 0.0, color);
+                }
+            }
+        }
+        let center = rect.min + egui::vec2(radius as f32 * cell, radius as f32 * cell);
+        let center = egui::Rect::from_min_size(center, egui::vec2(cell, cell));
+        // Dark inside light so the outline shows on any color
+        painter.rect_stroke(center, 0.0, Stroke::new(1.0, Color32::BLACK), egui::StrokeKind::Inside);
+        painter.rect_stroke(center, 0.0, Stroke::new(1.0, Color32::WHITE), egui::StrokeKind::Outside);
+        painter.rect_stroke(rect, 0.0, Stroke::new(1.0, Color32::GRAY), egui::StrokeKind::Inside);
+    }
+
+    /// Colors clicked so far, newest first
+    fn show_picked(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(t!("color-picker-picked")).strong());
+            if !self.picked.is_empty() && ui.small_button(t!("color-picker-clear")).clicked() {
+                self.picked.clear();
+            }
+        });
+        if self.picked.is_empty() {
+            ui.label(RichText::new(t!("color-picker-click")).size(11.0).color(Color32::GRAY));
+            return;
+        }
+        egui::ScrollArea::vertical().id_salt("color_picker_picked").show(ui, |ui| {
+            for pick in &self.picked {
+                show_pick(ui, *pick);
+                ui.add_space(4.0);
+            }
+        });
+    }
+}
+
+/// The color at `x`, `y`, if that is on the image
+fn pixel(image: &egui::ColorImage, x: i64, y: i64) -> Option<Color32> {
+    let [width, height] = image.size;
+    if x < 0 || y < 0 || x >= width as i64 || y >= height as i64 {
+        return None;
+    }
+    image.pixels.get(y as usize * width + x as usize).copied()
+}
+
+/// A swatch with the hex and RGB values of a color, each with a copy button
+fn show_pick(ui: &mut egui::Ui, pick: Pick) {
+    let [r, g, b, _] = pick.color.to_array();
+    let hex = format!("#{:02X}{:02X}{:02X}", r, g, b);
+    let rgb = format!("rgb({}, {}, {})", r, g, b);
+    ui.horizontal(|ui| {
+        let (swatch, _) = ui.allocate_exact_size(egui::vec2(36.0, 36.0), Sense::hover());
+        ui.painter().rect_filled(swatch, 4.0, pick.color);
+        ui.painter().rect_stroke(swatch, 4.0, Stroke::new(1.0, Color32::GRAY), egui::StrokeKind::Inside);
+        ui.vertical(|ui| {
+            for value in [hex, rgb] {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(&value).monospace());
+                    if ui.small_button(egui_phosphor::fill::COPY).on_hover_text(t!("color-picker-copy")).clicked() {
+                        ui.ctx().copy_text(value.clone());
+                    }
+                });
+            }
+            ui.label(RichText::new(format!("{}, {}", pick.x, pick.y)).size(11.0).weak());
+        });
+    });
+}
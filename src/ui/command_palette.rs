@@ -0,0 +1,142 @@
+use crate::t;
+use crate::utils::fuzzy_score;
+use egui::{Color32, Key, Modifiers, RichText};
+
+const MAX_RESULTS: usize = 50;
+
+/// One runnable line of the palette
+pub struct PaletteEntry {
+    /// Group shown before the label, e.g. "Toolkit"
+    pub category: String,
+    pub label: String,
+    /// Shortcut or other detail shown on the right
+    pub detail: Option<String>,
+}
+
+/// Keyboard-driven list of every action, filtered by fuzzy search as you type
+#[derive(Default)]
+pub struct CommandPalette {
+    visible: bool,
+    query: String,
+    /// Highlighted row among the current matches
+    highlighted: usize,
+}
+
+impl CommandPalette {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+        self.query.clear();
+        self.highlighted = 0;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.visible
+    }
+
+    /// Indices of `entries` matching the query, best first
+    fn matches(&self, entries: &[PaletteEntry]) -> Vec<usize> {
+        let mut scored: Vec<(i32, usize)> = entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                fuzzy_score(&self.query, &format!("{} {}", entry.category, entry.label)).map(|score| (score, index))
+            })
+            .collect();
+        // Stable, so equal scores keep the order the commands were listed in
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(_, index)| index).take(MAX_RESULTS).collect()
+    }
+
+    /// Returns the index into `entries` of the command to run
+    pub fn show(&mut self, ctx: &egui::Context, entries: &[PaletteEntry]) -> Option<usize> {
+        if !self.visible {
+            return None;
+        }
+
+        let (up, down, enter, escape) = ctx.input_mut(|input| {
+            (
+                input.consume_key(Modifiers::NONE, Key::ArrowUp),
+                input.consume_key(Modifiers::NONE, Key::ArrowDown),
+                input.consume_key(Modifiers::NONE, Key::Enter),
+                input.consume_key(Modifiers::NONE, Key::Escape),
+            )
+        });
+        if escape {
+            self.visible = false;
+            return None;
+        }
+
+        let mut matches = self.matches(entries);
+        if down {
+            self.highlighted += 1;
+        }
+        if up {
+            self.highlighted = self.highlighted.saturating_sub(1);
+        }
+        self.highlighted = self.highlighted.min(matches.len().saturating_sub(1));
+
+        let mut chosen = enter.then(|| matches.get(self.highlighted).copied()).flatten();
+        let window = egui::Window::new(t!("palette-title"))
+            .id(egui::Id::new("command_palette"))
+            .title_bar(false)
+            .collapsible(false)
+            .resizable(false)
+            .fixed_size(egui::vec2(440.0, 0.0))
+            .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 60.0))
+            .show(ctx, |ui| {
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut self.query)
+                        .hint_text(t!("palette-hint"))
+                        .desired_width(f32::INFINITY),
+                );
+                response.request_focus();
+                if response.changed() {
+                    self.highlighted = 0;
+                    matches = self.matches(entries);
+                }
+                ui.separator();
+
+                if matches.is_empty() {
+                    ui.label(RichText::new(t!("palette-no-matches")).color(Color32::GRAY));
+                    return;
+                }
+                egui::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                    for (row, &index) in matches.iter().enumerate() {
+                        let entry = &entries[index];
+                        let highlighted = row == self.highlighted;
+                        let response = ui
+                            .horizontal(|ui| {
+                                let label = ui.selectable_label(
+                                    highlighted,
+                                    RichText::new(format!("{}: {}", entry.category, entry.label)),
+                                );
+                                if let Some(detail) = &entry.detail {
+                                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                        ui.label(RichText::new(detail).monospace().weak());
+                                    });
+                                }
+                                label
+                            })
+                            .inner;
+                        if highlighted && (up || down) {
+                            response.scroll_to_me(None);
+                        }
+                        if response.clicked() {
+                            chosen = Some(index);
+                        }
+                    }
+                });
+            });
+
+        // Clicking outside closes it, as with a menu
+        let clicked_elsewhere = window.is_some_and(|window| window.response.clicked_elsewhere());
+        if chosen.is_some() || clicked_elsewhere {
+            self.visible = false;
+        }
+        chosen
+    }
+}
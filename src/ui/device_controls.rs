@@ -0,0 +1,305 @@
+use crate::controls::{
+    BatteryStatus, ControlChange, ControlState, DevOption, Toggle, VolumeKey, VolumeStream, ANIMATION_SCALES,
+    BRIGHTNESS_MAX,
+};
+use crate::t;
+use egui::{Color32, RichText, Ui};
+
+pub enum DeviceControlsAction {
+    Refresh,
+    Change(ControlChange),
+}
+
+/// Brightness, volume, quick-settings toggles, developer options and battery simulation of the selected device
+pub struct DeviceControlsPanel {
+    device: Option<String>,
+    state: Option<ControlState>,
+    error: Option<String>,
+    loading: bool,
+    /// Slider positions, sent to the device when a drag ends
+    brightness: u8,
+    volumes: [u32; 2],
+    battery_level: u8,
+    battery_status: BatteryStatus,
+    /// The shown device reports a simulated battery
+    simulating: bool,
+}
+
+impl Default for DeviceControlsPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeviceControlsPanel {
+    pub fn new() -> Self {
+        Self {
+            device: None,
+            state: None,
+            error: None,
+            loading: false,
+            brightness: 0,
+            volumes: [0; 2],
+            battery_level: 15,
+            battery_status: BatteryStatus::Discharging,
+            simulating: false,
+        }
+    }
+
+    pub fn device(&self) -> Option<&str> {
+        self.device.as_deref()
+    }
+
+    /// Show another device; its values are loaded by the caller
+    pub fn set_device(&mut self, device: Option<String>) {
+        self.device = device;
+        self.state = None;
+        self.error = None;
+    }
+
+    pub fn set_simulating(&mut self, simulating: bool) {
+        self.simulating = simulating;
+    }
+
+    pub fn set_loading(&mut self) {
+        self.loading = true;
+    }
+
+    /// Results for a device that is no longer shown are dropped
+    pub fn set_state(&mut self, device: &str, state: Result<ControlState, String>) {
+        if self.device.as_deref() != Some(device) {
+            return;
+        }
+        self.loading = false;
+        match state {
+            Ok(state) => {
+                self.brightness = state.brightness.unwrap_or(0);
+                for (slot, stream) in self.volumes.iter_mut().zip(VolumeStream::ALL) {
+                    *slot = state.volume(stream).map_or(0, |volume| volume.level);
+                }
+                self.state = Some(state);
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut Ui) -> Option<DeviceControlsAction> {
+        let mut action = None;
+
+        ui.horizontal(|ui| {
+            ui.heading(t!("controls-title"));
+            let refresh = egui::Button::new(egui_phosphor::fill::ARROWS_CLOCKWISE).small();
+            if ui
+                .add_enabled(self.device.is_some() && !self.loading, refresh)
+                .on_hover_text(t!("controls-refresh"))
+                .clicked()
+            {
+                action = Some(DeviceControlsAction::Refresh);
+            }
+            if self.loading {
+                ui.spinner();
+            }
+        });
+        if self.device.is_none() {
+            ui.label(RichText::new(t!("controls-no-device")).color(Color32::GRAY));
+            return action;
+        }
+        if let Some(error) = &self.error {
+            ui.label(RichText::new(error).size(11.0).color(Color32::RED));
+        }
+        let Some(state) = &self.state else {
+            return action;
+        };
+        ui.horizontal_wrapped(|ui| {
+            for toggle in Toggle::ALL {
+                let on = state.toggle(toggle);
+                let (icon, label) = match toggle {
+                    Toggle::Wifi => (egui_phosphor::fill::WIFI_HIGH, t!("controls-wifi")),
+                    Toggle::MobileData => (egui_phosphor::fill::CELL_SIGNAL_FULL, t!("controls-mobile-data")),
+                    Toggle::AirplaneMode => (egui_phosphor::fill::AIRPLANE, t!("controls-airplane-mode")),
+                    Toggle::DoNotDisturb => (egui_phosphor::fill::MOON, t!("controls-do-not-disturb")),
+                    Toggle::AutoRotate => (egui_phosphor::fill::DEVICE_ROTATE, t!("controls-auto-rotate")),
+                    Toggle::Flashlight => (egui_phosphor::fill::FLASHLIGHT, t!("controls-flashlight")),
+                };
+                let hint = match (toggle, on) {
+                    (Toggle::Flashlight, _) => t!("controls-flashlight-hint"),
+                    (_, Some(true)) => t!("controls-toggle-on", name = label),
+                    (_, Some(false)) => t!("controls-toggle-off", name = label),
+                    (_, None) => t!("controls-toggle-unknown", name = label),
+                };
+                // The flashlight's state is often unknown, but opening Quick Settings always works
+                let enabled = on.is_some() || toggle == Toggle::Flashlight;
+                let button = egui::Button::new(RichText::new(icon).size(16.0))
+                    .selected(on == Some(true))
+                    .min_size(egui::vec2(32.0, 28.0));
+                if ui.add_enabled(enabled, button).on_hover_text(&hint).on_disabled_hover_text(&hint).clicked() {
+                    action = Some(DeviceControlsAction::Change(ControlChange::Toggle(toggle, on != Some(true))));
+                }
+            }
+        });
+        ui.add_space(4.0);
+
+        let change = |action: &mut Option<DeviceControlsAction>, response: egui::Response, change: ControlChange| {
+            // Sliders change the device once, when let go or clicked, not on every step of a drag
+            if response.drag_stopped() || (response.changed() && !response.dragged()) {
+                *action = Some(DeviceControlsAction::Change(change));
+            }
+        };
+
+        egui::Grid::new("device_controls").num_columns(2).show(ui, |ui| {
+            ui.label(RichText::new(egui_phosphor::fill::SUN).size(16.0)).on_hover_text(t!("controls-brightness"));
+            ui.horizontal(|ui| {
+                if state.brightness.is_some() {
+                    let slider = egui::Slider::new(&mut self.brightness, 0..=BRIGHTNESS_MAX).show_value(false);
+                    let response = ui.add(slider);
+                    change(&mut action, response, ControlChange::Brightness(self.brightness));
+                    let mut auto = state.auto_brightness;
+                    if ui.checkbox(&mut auto, t!("controls-auto-brightness")).changed() {
+                        action = Some(DeviceControlsAction::Change(ControlChange::AutoBrightness(auto)));
+                    }
+                } else {
+                    ui.label(RichText::new(t!("controls-unavailable")).weak());
+                }
+            });
+            ui.end_row();
+
+            for (index, stream) in VolumeStream::ALL.into_iter().enumerate() {
+                let (icon, label) = match stream {
+                    VolumeStream::Media => (egui_phosphor::fill::MUSIC_NOTES, t!("controls-media-volume")),
+                    VolumeStream::Ring => (egui_phosphor::fill::BELL_RINGING, t!("controls-ring-volume")),
+                };
+                ui.label(RichText::new(icon).size(16.0)).on_hover_text(label);
+                match state.volume(stream) {
+                    Some(volume) => {
+                        let level = &mut self.volumes[index];
+                        let response = ui.add(egui::Slider::new(level, volume.min..=volume.max));
+                        change(&mut action, response, ControlChange::Volume(stream, *level));
+                    }
+                    None => {
+                        ui.label(RichText::new(t!("controls-unavailable")).weak());
+                    }
+                }
+                ui.end_row();
+            }
+        });
+
+        ui.horizontal(|ui| {
+            for (key, icon, hint) in [
+                (VolumeKey::Down, egui_phosphor::fill::SPEAKER_LOW, t!("controls-volume-down")),
+                (VolumeKey::Up, egui_phosphor::fill::SPEAKER_HIGH, t!("controls-volume-up")),
+                (VolumeKey::Mute, egui_phosphor::fill::SPEAKER_X, t!("controls-mute")),
+            ] {
+                if ui.button(icon).on_hover_text(hint).clicked() {
+                    action = Some(DeviceControlsAction::Change(ControlChange::VolumeKey(key)));
+                }
+            }
+        });
+
+        egui::CollapsingHeader::new(t!("controls-developer-options"))
+            .id_salt("device_controls_developer")
+            .show(ui, |ui| {
+                for option in DevOption::ALL {
+                    let label = match option {
+                        DevOption::LayoutBounds => t!("controls-layout-bounds"),
+                        DevOption::PointerLocation => t!("controls-pointer-location"),
+                        DevOption::GpuOverdraw => t!("controls-gpu-overdraw"),
+                        DevOption::StrictModeFlash => t!("controls-strict-mode"),
+                    };
+                    let mut on = state.dev_option(option).unwrap_or(false);
+                    if ui.checkbox(&mut on, label).changed() {
+                        action = Some(DeviceControlsAction::Change(ControlChange::DevOption(option, on)));
+                    }
+                }
+                ui.horizontal(|ui| {
+                    ui.label(t!("controls-animation-scale"));
+                    for scale in ANIMATION_SCALES {
+                        let text = if scale == 0.0 { t!("controls-animation-off") } else { format!("{}x", scale) };
+                        if ui.selectable_label(state.animation_scale == Some(scale), text).clicked() {
+                            action = Some(DeviceControlsAction::Change(ControlChange::AnimationScale(scale)));
+                        }
+                    }
+                });
+            });
+
+        egui::CollapsingHeader::new(t!("controls-battery-simulation"))
+            .id_salt("device_controls_battery")
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(t!("controls-battery-level"));
+                    ui.add(egui::Slider::new(&mut self.battery_level, 0..=100).suffix("%"));
+                });
+                ui.horizontal(|ui| {
+                    ui.label(t!("controls-battery-status"));
+                    egui::ComboBox::from_id_salt("battery_status")
+                        .selected_text(status_label(self.battery_status))
+                        .show_ui(ui, |ui| {
+                            for status in BatteryStatus::ALL {
+                                ui.selectable_value(&mut self.battery_status, status, status_label(status));
+                            }
+                        });
+                });
+                ui.horizontal(|ui| {
+                    if ui.button(t!("controls-battery-simulate")).clicked() {
+                        action = Some(DeviceControlsAction::Change(ControlChange::SimulateBattery {
+                            level: self.battery_level,
+                            status: self.battery_status,
+                        }));
+                    }
+                    if ui
+                        .add_enabled(self.simulating, egui::Button::new(t!("controls-battery-reset")))
+                        .on_hover_text(t!("controls-battery-reset-hint"))
+                        .clicked()
+                    {
+                        action = Some(DeviceControlsAction::Change(ControlChange::ResetBattery));
+                    }
+                });
+                if self.simulating {
+                    let active = format!("{} {}", egui_phosphor::fill::WARNING, t!("controls-battery-active"));
+                    ui.label(RichText::new(active).color(Color32::ORANGE));
+                }
+            });
+
+        action
+    }
+}
+
+fn status_label(status: BatteryStatus) -> String {
+    match status {
+        BatteryStatus::Charging => t!("controls-battery-charging"),
+        BatteryStatus::Discharging => t!("controls-battery-discharging"),
+        BatteryStatus::NotCharging => t!("controls-battery-not-charging"),
+        BatteryStatus::Full => t!("controls-battery-full"),
+    }
+}
+
+/// Banner across the top of the main window while devices report a simulated battery; returns the
+/// device to reset
+pub fn battery_simulation_banner(ctx: &egui::Context, devices: &[String]) -> Option<String> {
+    if devices.is_empty() {
+        return None;
+    }
+    let mut reset = None;
+    egui::TopBottomPanel::top("battery_simulation_banner")
+        .frame(
+            egui::Frame::new()
+                .fill(Color32::from_rgb(120, 60, 0))
+                .inner_margin(egui::Margin::symmetric(8, 6)),
+        )
+        .show(ctx, |ui| {
+            for device in devices {
+                ui.horizontal(|ui| {
+                    let text = format!(
+                        "{} {}",
+                        egui_phosphor::fill::BATTERY_WARNING,
+                        t!("banner-battery-simulation", device = device.as_str())
+                    );
+                    ui.label(RichText::new(text).color(Color32::WHITE).strong());
+                    if ui.button(t!("controls-battery-reset")).clicked() {
+                        reset = Some(device.clone());
+                    }
+                });
+            }
+        });
+    reset
+}
@@ -1,9 +1,54 @@
-use crate::device::{Device, DeviceStatus};
-use egui::{Color32, RichText, Ui};
+use crate::config::DeviceLabel;
+use crate::device::{Device, DeviceDetails, DeviceStatus, LinkHealth};
+use crate::reconnect::{ReconnectState, MAX_ATTEMPTS};
+use crate::t;
+use egui::{Color32, Key, Modifiers, RichText, TextureHandle, Ui};
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+/// The search box appears once more than this many devices are listed
+const SEARCH_THRESHOLD: usize = 3;
+
+/// How often the screen thumbnails are captured again
+pub const THUMBNAIL_INTERVAL: Duration = Duration::from_secs(5);
+/// Longer side of a captured thumbnail, large enough for the hover preview
+pub const THUMBNAIL_EDGE: u32 = 240;
+/// Height of the thumbnail in a compact and a detailed row
+const COMPACT_THUMBNAIL_HEIGHT: f32 = 20.0;
+const DETAILED_THUMBNAIL_HEIGHT: f32 = 48.0;
+
+pub enum DeviceListAction {
+    None,
+    /// Serial and new nickname; `None` goes back to the model name
+    Rename(String, Option<String>),
+    SetFavorite(String, bool),
+    SetAutoStart(String, bool),
+    CancelReconnect(String),
+    /// Open the ADB keys window on an unauthorized device
+    FixAuthorization(String),
+    /// Switch between one line per device and a second line of details
+    SetDetailed(bool),
+}
 
 pub struct DeviceList {
     devices: Vec<Device>,
     selected_device: Option<usize>,
+    filter: String,
+    /// Position in the filtered list picked with the arrow keys
+    highlighted: usize,
+    labels: BTreeMap<String, DeviceLabel>,
+    /// Serial and text of the nickname being edited
+    renaming: Option<(String, String)>,
+    detailed: bool,
+    /// The Settings switch for auto-start, so the marker can say when it is off
+    auto_start_enabled: bool,
+    /// Wireless devices being reconnected, by endpoint
+    reconnecting: BTreeMap<String, ReconnectState>,
+    /// Latest round trip of each device; `None` when it did not answer
+    health: HashMap<String, Option<Duration>>,
+    /// Screenshots received since the last frame, uploaded as textures in `show`
+    pending_thumbnails: Vec<(String, egui::ColorImage)>,
+    thumbnails: HashMap<String, TextureHandle>,
 }
 
 impl Default for DeviceList {
@@ -17,11 +62,88 @@ impl DeviceList {
         Self {
             devices: Vec::new(),
             selected_device: None,
+            filter: String::new(),
+            highlighted: 0,
+            labels: BTreeMap::new(),
+            renaming: None,
+            detailed: false,
+            auto_start_enabled: true,
+            reconnecting: BTreeMap::new(),
+            health: HashMap::new(),
+            pending_thumbnails: Vec::new(),
+            thumbnails: HashMap::new(),
+        }
+    }
+
+    pub fn set_thumbnail(&mut self, identifier: String, image: egui::ColorImage) {
+        self.pending_thumbnails.push((identifier, image));
+    }
+
+    pub fn set_health(&mut self, identifier: String, latency: Option<Duration>) {
+        self.health.insert(identifier, latency);
+    }
+
+    pub fn clear_thumbnails(&mut self) {
+        self.pending_thumbnails.clear();
+        self.thumbnails.clear();
+    }
+
+    /// Upload the screenshots received since the last frame
+    fn load_thumbnails(&mut self, ctx: &egui::Context) {
+        for (identifier, image) in self.pending_thumbnails.drain(..) {
+            match self.thumbnails.get_mut(&identifier) {
+                Some(texture) => texture.set(image, egui::TextureOptions::LINEAR),
+                None => {
+                    let texture = ctx.load_texture(
+                        format!("device_thumbnail_{}", identifier),
+                        image,
+                        egui::TextureOptions::LINEAR,
+                    );
+                    self.thumbnails.insert(identifier, texture);
+                }
+            }
+        }
+    }
+
+    pub fn set_detailed(&mut self, detailed: bool) {
+        self.detailed = detailed;
+    }
+
+    pub fn set_auto_start_enabled(&mut self, enabled: bool) {
+        self.auto_start_enabled = enabled;
+    }
+
+    pub fn set_reconnecting(&mut self, reconnecting: &BTreeMap<String, ReconnectState>) {
+        if self.reconnecting != *reconnecting {
+            self.reconnecting = reconnecting.clone();
         }
     }
 
+    pub fn set_labels(&mut self, labels: BTreeMap<String, DeviceLabel>) {
+        self.labels = labels;
+    }
+
+    /// The nickname if the device has one, otherwise its model
+    pub fn display_name(&self, device: &Device) -> String {
+        self.labels
+            .get(&device.identifier)
+            .and_then(|label| label.nickname.clone())
+            .unwrap_or_else(|| device.model.clone())
+    }
+
+    fn is_favorite(&self, device: &Device) -> bool {
+        self.labels.get(&device.identifier).is_some_and(|label| label.favorite)
+    }
+
     pub fn update_devices(&mut self, devices: Vec<Device>) {
         self.devices = devices;
+        // Forget the screens of devices that went away or can no longer be captured
+        let usable = &self.devices;
+        let is_usable = |identifier: &String| {
+            usable.iter().any(|device| device.identifier == *identifier && device.is_usable())
+        };
+        self.thumbnails.retain(|identifier, _| is_usable(identifier));
+        self.health.retain(|identifier, _| is_usable(identifier));
 
         // Reset selection if device list is empty
         if self.devices.is_empty() {
@@ -41,6 +163,30 @@ impl DeviceList {
         }
     }
 
+    /// Select the device with this serial; false when it is not in the list
+    pub fn select(&mut self, identifier: &str) -> bool {
+        match self.devices.iter().position(|d| d.identifier == identifier) {
+            Some(index) => {
+                self.selected_device = Some(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Which device is selected, to put back later with `restore_selection`
+    pub fn selection(&self) -> Option<usize> {
+        self.selected_device
+    }
+
+    pub fn restore_selection(&mut self, selection: Option<usize>) {
+        self.selected_device = selection.filter(|&index| index < self.devices.len());
+    }
+
+    pub fn devices(&self) -> &[Device] {
+        &self.devices
+    }
+
     pub fn selected_device(&self) -> Option<&Device> {
         match self.selected_device {
             Some(i) if i < self.devices.len() => Some(&self.devices[i]),
@@ -48,56 +194,360 @@ impl DeviceList {
         }
     }
 
-    pub fn show(&mut self, ui: &mut Ui) {
-        ui.heading("Connected Devices");
+    /// Indices of all devices, favorites first
+    fn ordered(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.devices.len()).collect();
+        order.sort_by_key(|&index| !self.is_favorite(&self.devices[index]));
+        order
+    }
+
+    /// Indices of the devices matching the search box, best match first
+    fn filtered(&self) -> Vec<usize> {
+        if self.filter.trim().is_empty() {
+            return self.ordered();
+        }
+        let mut matches: Vec<(usize, i32)> = self
+            .devices
+            .iter()
+            .enumerate()
+            .filter_map(|(index, device)| {
+                let nickname = self.labels.get(&device.identifier).and_then(|label| label.nickname.as_ref());
+                // Wireless serials are `ip:port`, so the serial covers IP searches
+                [&device.identifier, &device.model, &device.product, &device.device]
+                    .into_iter()
+                    .chain(nickname)
+                    .filter_map(|field| crate::utils::fuzzy_score(&self.filter, field))
+                    .max()
+                    .map(|score| (index, score))
+            })
+            .collect();
+        matches.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+        matches.into_iter().map(|(index, _)| index).collect()
+    }
+
+    /// Search box with arrow key navigation; Enter selects the highlighted device
+    fn show_search(&mut self, ui: &mut Ui) -> Vec<usize> {
+        let response = ui.add(
+            egui::TextEdit::singleline(&mut self.filter)
+                .hint_text(format!("{} {}", egui_phosphor::fill::MAGNIFYING_GLASS, t!("devices-search")))
+                .desired_width(f32::INFINITY),
+        );
+        if response.changed() {
+            self.highlighted = 0;
+        }
+
+        let visible = self.filtered();
+        if response.has_focus() || response.lost_focus() {
+            let (down, up, enter, escape) = ui.input_mut(|i| {
+                (
+                    i.consume_key(Modifiers::NONE, Key::ArrowDown),
+                    i.consume_key(Modifiers::NONE, Key::ArrowUp),
+                    i.key_pressed(Key::Enter),
+                    i.key_pressed(Key::Escape),
+                )
+            });
+            if down && self.highlighted + 1 < visible.len() {
+                self.highlighted += 1;
+            }
+            if up {
+                self.highlighted = self.highlighted.saturating_sub(1);
+            }
+            if enter
+                && response.lost_focus()
+                && let Some(&index) = visible.get(self.highlighted)
+                && self.devices[index].is_usable()
+            {
+                self.selected_device = Some(index);
+            }
+            if escape {
+                self.filter.clear();
+                self.highlighted = 0;
+                return self.filtered();
+            }
+            // Keep typing after Enter without clicking back into the box
+            if enter {
+                response.request_focus();
+            }
+        }
+        self.highlighted = self.highlighted.min(visible.len().saturating_sub(1));
+        visible
+    }
+
+    pub fn show(&mut self, ui: &mut Ui) -> DeviceListAction {
+        let mut action = DeviceListAction::None;
+        self.load_thumbnails(ui.ctx());
+        ui.horizontal(|ui| {
+            ui.heading(t!("devices-heading"));
+            let (icon, hint) = if self.detailed {
+                (egui_phosphor::fill::LIST, t!("devices-compact"))
+            } else {
+                (egui_phosphor::fill::ROWS, t!("devices-detailed"))
+            };
+            if ui.small_button(icon).on_hover_text(hint).clicked() {
+                action = DeviceListAction::SetDetailed(!self.detailed);
+            }
+        });
+
+        // Dropped devices adb no longer lists; offline ones get their state in their own row
+        for (endpoint, state) in &self.reconnecting {
+            if self.devices.iter().any(|device| device.identifier == *endpoint) {
+                continue;
+            }
+            ui.horizontal(|ui| {
+                let name = self
+                    .labels
+                    .get(endpoint)
+                    .and_then(|label| label.nickname.clone())
+                    .unwrap_or_else(|| endpoint.clone());
+                ui.label(RichText::new(name).color(Color32::GRAY));
+                reconnect_status(ui, state);
+                if ui
+                    .small_button(egui_phosphor::fill::X)
+                    .on_hover_text(t!("devices-reconnect-cancel"))
+                    .clicked()
+                {
+                    action = DeviceListAction::CancelReconnect(endpoint.clone());
+                }
+            });
+        }
 
         if self.devices.is_empty() {
-            ui.label(RichText::new("No devices found").color(Color32::GRAY));
-            return;
+            if self.reconnecting.is_empty() {
+                ui.label(RichText::new(t!("devices-none")).color(Color32::GRAY));
+            }
+            return action;
+        }
+
+        let searching = self.devices.len() > SEARCH_THRESHOLD || !self.filter.is_empty();
+        let visible = if searching {
+            self.show_search(ui)
+        } else {
+            self.ordered()
+        };
+        if visible.is_empty() {
+            ui.label(RichText::new(t!("devices-no-match")).color(Color32::GRAY));
+            return action;
         }
 
         egui::ScrollArea::vertical().show(ui, |ui| {
-            for (index, device) in self.devices.iter().enumerate() {
+            for (position, &index) in visible.iter().enumerate() {
+                let device = &self.devices[index];
                 let is_selected = self.selected_device == Some(index);
+                let is_highlighted = searching && !self.filter.is_empty() && position == self.highlighted;
                 let is_usable = device.is_usable();
+                let is_favorite = self.is_favorite(device);
+                let auto_start = self.labels.get(&device.identifier).is_some_and(|label| label.auto_start);
+                let name = self.display_name(device);
 
                 let text = if is_usable {
-                    RichText::new(&device.model)
+                    RichText::new(&name)
                 } else {
-                    RichText::new(&device.model).color(Color32::GRAY)
+                    RichText::new(&name).color(Color32::GRAY)
                 };
 
                 let status_text = match &device.status {
-                    DeviceStatus::Device => RichText::new("✅ Connected").color(Color32::GREEN),
-                    DeviceStatus::Offline => RichText::new("❌ Offline").color(Color32::RED),
+                    DeviceStatus::Device => RichText::new(format!("✅ {}", t!("devices-status-connected"))).color(Color32::GREEN),
+                    DeviceStatus::Offline => RichText::new(format!("❌ {}", t!("devices-status-offline"))).color(Color32::RED),
                     DeviceStatus::Unauthorized => {
-                        RichText::new("⚠️ Unauthorized").color(Color32::YELLOW)
+                        RichText::new(format!("⚠️ {}", t!("devices-status-unauthorized"))).color(Color32::YELLOW)
                     }
                     DeviceStatus::NoPermission => {
-                        RichText::new("🚫 No Permission").color(Color32::RED)
+                        RichText::new(format!("🚫 {}", t!("devices-status-no-permission"))).color(Color32::RED)
                     }
                     DeviceStatus::Unknown(s) => {
                         RichText::new(format!("❓ {}", s)).color(Color32::GRAY)
                     }
                 };
 
+                let identifier = device.identifier.clone();
                 ui.horizontal(|ui| {
-                    if ui.selectable_label(is_selected, text).clicked() && is_usable {
-                        self.selected_device = Some(index);
+                    let star = if is_favorite {
+                        RichText::new(egui_phosphor::fill::STAR).color(Color32::GOLD)
+                    } else {
+                        RichText::new(egui_phosphor::fill::STAR).weak()
+                    };
+                    let star_hint = if is_favorite { t!("devices-unfavorite") } else { t!("devices-favorite") };
+                    if ui.add(egui::Button::new(star).frame(false)).on_hover_text(star_hint).clicked() {
+                        action = DeviceListAction::SetFavorite(identifier.clone(), !is_favorite);
+                    }
+
+                    if let Some(texture) = self.thumbnails.get(&identifier) {
+                        let height = if self.detailed { DETAILED_THUMBNAIL_HEIGHT } else { COMPACT_THUMBNAIL_HEIGHT };
+                        let size = texture.size_vec2() * (height / texture.size_vec2().y.max(1.0));
+                        let response = ui
+                            .add(egui::Image::new(texture).fit_to_exact_size(size).sense(egui::Sense::click()))
+                            .on_hover_ui(|ui| {
+                                ui.add(egui::Image::new(texture).fit_to_original_size(1.0));
+                            });
+                        if response.clicked() && is_usable {
+                            self.selected_device = Some(index);
+                        }
+                    }
+
+                    if let Some((serial, text)) = &mut self.renaming
+                        && *serial == identifier
+                    {
+                        let edit = ui.add(egui::TextEdit::singleline(text).desired_width(120.0).hint_text(&device.model));
+                        edit.request_focus();
+                        if edit.lost_focus() {
+                            if !ui.input(|input| input.key_pressed(Key::Escape)) {
+                                let nickname = text.trim();
+                                action = DeviceListAction::Rename(
+                                    identifier.clone(),
+                                    (!nickname.is_empty() && nickname != device.model).then(|| nickname.to_string()),
+                                );
+                            }
+                            self.renaming = None;
+                        }
+                    } else {
+                        let mut response = ui.selectable_label(is_selected, text);
+                        if is_highlighted {
+                            response = response.highlight();
+                        }
+                        if response.clicked() && is_usable {
+                            self.selected_device = Some(index);
+                        }
+                        if response.double_clicked() {
+                            self.renaming = Some((identifier.clone(), name.clone()));
+                        }
+                        response.context_menu(|ui| {
+                            if ui.button(t!("devices-rename")).clicked() {
+                                self.renaming = Some((identifier.clone(), name.clone()));
+                                ui.close();
+                            }
+                            if self.labels.get(&identifier).is_some_and(|label| label.nickname.is_some())
+                                && ui.button(t!("devices-clear-nickname")).clicked()
+                            {
+                                action = DeviceListAction::Rename(identifier.clone(), None);
+                                ui.close();
+                            }
+                            let mut checked = auto_start;
+                            if ui.checkbox(&mut checked, t!("devices-auto-start")).changed() {
+                                action = DeviceListAction::SetAutoStart(identifier.clone(), checked);
+                                ui.close();
+                            }
+                        });
                     }
 
-                    ui.label(status_text);
+                    match self.reconnecting.get(&identifier) {
+                        Some(state) => {
+                            reconnect_status(ui, state);
+                            if ui
+                                .small_button(egui_phosphor::fill::X)
+                                .on_hover_text(t!("devices-reconnect-cancel"))
+                                .clicked()
+                            {
+                                action = DeviceListAction::CancelReconnect(identifier.clone());
+                            }
+                        }
+                        None if device.status == DeviceStatus::Unauthorized => {
+                            if ui
+                                .add(egui::Label::new(status_text).sense(egui::Sense::click()))
+                                .on_hover_text(t!("devices-unauthorized-hint"))
+                                .clicked()
+                            {
+                                action = DeviceListAction::FixAuthorization(identifier.clone());
+                            }
+                        }
+                        None => {
+                            ui.label(status_text);
+                        }
+                    }
+                    if let Some(&latency) = self.health.get(&identifier) {
+                        let hint = match latency {
+                            Some(latency) => t!("devices-health-latency", ms = latency.as_millis()),
+                            None => t!("devices-health-unresponsive"),
+                        };
+                        ui.label(
+                            RichText::new(egui_phosphor::fill::CIRCLE)
+                                .size(9.0)
+                                .color(health_color(LinkHealth::from_latency(latency))),
+                        )
+                        .on_hover_text(hint);
+                    }
+                    if auto_start {
+                        let (marker, hint) = if self.auto_start_enabled {
+                            (RichText::new(egui_phosphor::fill::PLAY_CIRCLE), t!("devices-auto-start-on"))
+                        } else {
+                            (RichText::new(egui_phosphor::fill::PLAY_CIRCLE).weak(), t!("devices-auto-start-off"))
+                        };
+                        ui.label(marker).on_hover_text(hint);
+                    }
+                    let connection = device.connection_type();
+                    let summary = ui.label(RichText::new(connection.icon()).weak());
+                    if !self.detailed {
+                        if let Some(battery) = device.details.battery {
+                            ui.label(RichText::new(format!("{}%", battery)).color(battery_color(battery)).small());
+                        }
+                        summary.on_hover_text(details_line(device));
+                    }
                 });
+                if self.detailed && device.is_usable() {
+                    ui.indent(("device_details", index), |ui| {
+                        ui.label(RichText::new(details_line(device)).small().weak());
+                    });
+                }
 
                 if is_selected {
                     ui.indent("device_info", |ui| {
-                        ui.label(format!("ID: {}", device.identifier));
-                        ui.label(format!("Product: {}", device.product));
-                        ui.label(format!("Model: {}", device.model));
-                        ui.label(format!("Device: {}", device.device));
+                        ui.label(t!("devices-info-id", id = device.identifier));
+                        ui.label(t!("devices-info-product", product = device.product));
+                        ui.label(t!("devices-info-model", model = device.model));
+                        ui.label(t!("devices-info-device", device = device.device));
                     });
                 }
             }
         });
+        action
+    }
+}
+
+/// "Reconnecting…" with the attempt count and time to the next attempt on hover
+fn reconnect_status(ui: &mut Ui, state: &ReconnectState) {
+    let text = RichText::new(format!("{} {}", egui_phosphor::fill::ARROWS_CLOCKWISE, t!("devices-reconnecting")))
+        .color(Color32::YELLOW);
+    let mut hint = t!("devices-reconnect-attempt", attempt = state.attempts + 1, max = MAX_ATTEMPTS);
+    if !state.in_flight {
+        let seconds = state.next_attempt.saturating_duration_since(std::time::Instant::now()).as_secs() + 1;
+        hint = format!("{}\n{}", hint, t!("devices-reconnect-next", seconds = seconds));
+    }
+    ui.label(text).on_hover_text(hint);
+}
+
+/// e.g. "Android 14 (API 34) · 85% · Wi-Fi", leaving out whatever is unknown
+fn details_line(device: &Device) -> String {
+    let DeviceDetails {
+        android_version,
+        api_level,
+        battery,
+    } = &device.details;
+    let mut parts = Vec::new();
+    match (android_version, api_level) {
+        (Some(version), Some(api)) => parts.push(t!("devices-android-api", version = version, api = api)),
+        (Some(version), None) => parts.push(t!("devices-android", version = version)),
+        (None, Some(api)) => parts.push(t!("devices-api", api = api)),
+        (None, None) => {}
+    }
+    if let Some(battery) = battery {
+        parts.push(t!("devices-battery", level = battery));
+    }
+    parts.push(device.connection_type().label().to_string());
+    parts.join(" · ")
+}
+
+fn health_color(health: LinkHealth) -> Color32 {
+    match health {
+        LinkHealth::Good => Color32::GREEN,
+        LinkHealth::Slow => Color32::YELLOW,
+        LinkHealth::Poor => Color32::from_rgb(255, 140, 0),
+        LinkHealth::Unresponsive => Color32::RED,
+    }
+}
+
+fn battery_color(level: u8) -> Color32 {
+    match level {
+        0..=15 => Color32::RED,
+        16..=30 => Color32::YELLOW,
+        _ => Color32::GRAY,
     }
 }
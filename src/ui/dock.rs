@@ -0,0 +1,79 @@
+use crate::dock::{DockLayout, DockPanel};
+use crate::t;
+use egui::{Id, Ui, WidgetText};
+use egui_dock::tab_viewer::OnCloseResponse;
+use egui_dock::{DockArea, LeafTranslations, Style, TabContextMenuTranslations, TabViewer, Translations};
+
+/// Draws each panel's contents with `add_panel` and notes the panels closed from their tab
+struct PanelViewer<F> {
+    add_panel: F,
+    closed: Vec<DockPanel>,
+}
+
+impl<F: FnMut(&mut Ui, DockPanel)> TabViewer for PanelViewer<F> {
+    type Tab = DockPanel;
+
+    fn title(&mut self, panel: &mut DockPanel) -> WidgetText {
+        format!("{} {}", panel.icon(), panel.label()).into()
+    }
+
+    fn ui(&mut self, ui: &mut Ui, panel: &mut DockPanel) {
+        (self.add_panel)(ui, *panel);
+    }
+
+    fn id(&mut self, panel: &mut DockPanel) -> Id {
+        Id::new(("dock_panel", panel.id()))
+    }
+
+    /// Only the panels that Settings can switch back on may be closed
+    fn is_closeable(&self, panel: &DockPanel) -> bool {
+        matches!(panel, DockPanel::Toolkit | DockPanel::QuickActions)
+    }
+
+    fn on_close(&mut self, panel: &mut DockPanel) -> OnCloseResponse {
+        self.closed.push(*panel);
+        OnCloseResponse::Close
+    }
+
+    fn allowed_in_windows(&self, panel: &mut DockPanel) -> bool {
+        *panel != DockPanel::Session
+    }
+
+    fn scroll_bars(&self, _panel: &DockPanel) -> [bool; 2] {
+        [false, true]
+    }
+}
+
+/// Show the panels as `layout` arranges them in the rest of the window; returns the panels
+/// closed from their tab
+pub fn show(ctx: &egui::Context, layout: &mut DockLayout, add_panel: impl FnMut(&mut Ui, DockPanel)) -> Vec<DockPanel> {
+    layout.translations = translations();
+    let mut viewer = PanelViewer { add_panel, closed: Vec::new() };
+    DockArea::new(layout)
+        .id(Id::new("dock"))
+        .style(Style::from_egui(ctx.style().as_ref()))
+        .show_leaf_collapse_buttons(false)
+        .show(ctx, &mut viewer);
+    viewer.closed
+}
+
+fn translations() -> Translations {
+    Translations {
+        tab_context_menu: TabContextMenuTranslations {
+            close_button: t!("dock-close"),
+            eject_button: t!("dock-eject"),
+        },
+        leaf: LeafTranslations {
+            close_button_disabled_tooltip: t!("dock-close-disabled"),
+            close_all_button: t!("dock-close-window"),
+            close_all_button_menu_hint: t!("dock-close-window-menu-hint"),
+            close_all_button_modifier_hint: t!("dock-close-window-modifier-hint"),
+            close_all_button_modifier_menu_hint: t!("dock-close-window-modifier-menu-hint"),
+            close_all_button_disabled_tooltip: t!("dock-close-window-disabled"),
+            minimize_button: t!("dock-minimize-window"),
+            minimize_button_menu_hint: t!("dock-minimize-window-menu-hint"),
+            minimize_button_modifier_hint: t!("dock-minimize-window-modifier-hint"),
+            minimize_button_modifier_menu_hint: t!("dock-minimize-window-modifier-menu-hint"),
+        },
+    }
+}
@@ -0,0 +1,177 @@
+use crate::t;
+use egui::{Color32, RichText, Window};
+use std::path::PathBuf;
+
+/// Common destinations offered in the path picker
+const DESTINATION_PRESETS: [&str; 5] = [
+    "/sdcard/Download",
+    "/sdcard/Documents",
+    "/sdcard/DCIM",
+    "/sdcard/Movies",
+    "/data/local/tmp",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PushStatus {
+    Pending,
+    Pushing,
+    Done,
+    Failed(String),
+}
+
+pub enum FilePushAction {
+    None,
+    Start {
+        /// Pending files with their index in the dialog list
+        files: Vec<(usize, PathBuf)>,
+        destination: String,
+    },
+}
+
+pub struct FilePushDialog {
+    visible: bool,
+    files: Vec<(PathBuf, PushStatus)>,
+    destination: String,
+    running: bool,
+}
+
+impl Default for FilePushDialog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FilePushDialog {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            files: Vec::new(),
+            destination: DESTINATION_PRESETS[0].to_string(),
+            running: false,
+        }
+    }
+
+    /// Open the dialog for freshly dropped files, appending to any pending list
+    pub fn open_with(&mut self, files: Vec<PathBuf>) {
+        if !self.running && self.files.iter().all(|(_, s)| *s != PushStatus::Pending) {
+            self.files.clear();
+        }
+        for file in files {
+            if !self.files.iter().any(|(f, _)| *f == file) {
+                self.files.push((file, PushStatus::Pending));
+            }
+        }
+        self.visible = true;
+    }
+
+    pub fn set_status(&mut self, index: usize, status: PushStatus) {
+        if let Some((_, current)) = self.files.get_mut(index) {
+            *current = status;
+        }
+    }
+
+    pub fn finish(&mut self) {
+        self.running = false;
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> FilePushAction {
+        if !self.visible {
+            return FilePushAction::None;
+        }
+
+        let mut action = FilePushAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::UPLOAD_SIMPLE, t!("push-title")))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(420.0, 300.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(t!("push-destination"));
+                    ui.add_enabled(
+                        !self.running,
+                        egui::TextEdit::singleline(&mut self.destination).desired_width(200.0),
+                    );
+                    ui.add_enabled_ui(!self.running, |ui| {
+                        egui::ComboBox::from_id_salt("push_destination_combo")
+                            .selected_text(t!("push-presets"))
+                            .show_ui(ui, |ui| {
+                                for preset in DESTINATION_PRESETS {
+                                    ui.selectable_value(&mut self.destination, preset.to_string(), preset);
+                                }
+                            });
+                    });
+                });
+
+                ui.separator();
+
+                egui::ScrollArea::vertical().max_height(180.0).show(ui, |ui| {
+                    for (path, status) in &self.files {
+                        ui.horizontal(|ui| {
+                            match status {
+                                PushStatus::Pending => {
+                                    ui.label(RichText::new(egui_phosphor::fill::CLOCK).color(Color32::GRAY));
+                                }
+                                PushStatus::Pushing => {
+                                    ui.add(egui::Spinner::new().size(14.0));
+                                }
+                                PushStatus::Done => {
+                                    ui.label(RichText::new(egui_phosphor::fill::CHECK_CIRCLE).color(Color32::GREEN));
+                                }
+                                PushStatus::Failed(e) => {
+                                    ui.label(RichText::new(egui_phosphor::fill::X_CIRCLE).color(Color32::RED))
+                                        .on_hover_text(e);
+                                }
+                            }
+                            let name = path
+                                .file_name()
+                                .map(|n| n.to_string_lossy().to_string())
+                                .unwrap_or_else(|| path.display().to_string());
+                            let suffix = if path.is_dir() { "/" } else { "" };
+                            ui.label(format!("{}{}", name, suffix)).on_hover_text(path.display().to_string());
+                        });
+                    }
+                });
+
+                let done = self.files.iter().filter(|(_, s)| *s == PushStatus::Done).count();
+                ui.add(
+                    egui::ProgressBar::new(done as f32 / self.files.len().max(1) as f32)
+                        .text(format!("{} / {}", done, self.files.len())),
+                );
+
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    let has_pending = self.files.iter().any(|(_, s)| *s == PushStatus::Pending);
+                    let can_start = !self.running && has_pending && !self.destination.trim().is_empty();
+                    if ui.add_enabled(can_start, egui::Button::new(t!("push-start"))).clicked() {
+                        self.running = true;
+                        action = FilePushAction::Start {
+                            files: self
+                                .files
+                                .iter()
+                                .enumerate()
+                                .filter(|(_, (_, status))| *status == PushStatus::Pending)
+                                .map(|(index, (path, _))| (index, path.clone()))
+                                .collect(),
+                            destination: self.destination.trim().to_string(),
+                        };
+                    }
+                    if ui.add_enabled(!self.running, egui::Button::new(t!("push-clear"))).clicked() {
+                        self.files.clear();
+                    }
+                    if self.running {
+                        ui.add(egui::Spinner::new().size(14.0));
+                    }
+                });
+            });
+
+        if !open && !self.running {
+            self.visible = false;
+        }
+
+        action
+    }
+}
@@ -0,0 +1,222 @@
+use crate::gfxinfo::{summarize, Frame};
+use crate::t;
+use egui::{Color32, RichText, Window};
+use std::collections::VecDeque;
+
+/// Frames kept for the chart and the numbers
+const HISTORY: usize = 600;
+const REFRESH_RATES: [u32; 4] = [60, 90, 120, 144];
+
+pub enum FrameProfilerAction {
+    None,
+    /// Fill in the package of the app in the foreground
+    UseForeground,
+}
+
+/// Live frame times of one app, sampled from gfxinfo while the user interacts with it
+pub struct FrameProfilerWindow {
+    visible: bool,
+    package: String,
+    refresh_rate: u32,
+    running: bool,
+    frames: VecDeque<Frame>,
+    error: Option<String>,
+}
+
+impl Default for FrameProfilerWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FrameProfilerWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            package: String::new(),
+            refresh_rate: 60,
+            running: false,
+            frames: VecDeque::new(),
+            error: None,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.visible = true;
+    }
+
+    /// The package to sample while profiling is on and the window open
+    pub fn sampling(&self) -> Option<&str> {
+        let package = self.package.trim();
+        (self.visible && self.running && !package.is_empty()).then_some(package)
+    }
+
+    pub fn set_package(&mut self, package: String) {
+        if package != self.package {
+            self.frames.clear();
+        }
+        self.package = package;
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    /// Add the frames of a sample that are newer than those already seen
+    pub fn add_frames(&mut self, package: &str, frames: Vec<Frame>) {
+        if package != self.package.trim() {
+            return;
+        }
+        self.error = None;
+        let newest = self.frames.back().map_or(0, |frame| frame.intended_vsync);
+        self.frames.extend(frames.into_iter().filter(|frame| frame.intended_vsync > newest));
+        while self.frames.len() > HISTORY {
+            self.frames.pop_front();
+        }
+    }
+
+    fn budget_ms(&self) -> f32 {
+        1000.0 / self.refresh_rate as f32
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> FrameProfilerAction {
+        if !self.visible {
+            return FrameProfilerAction::None;
+        }
+
+        let mut action = FrameProfilerAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::TIMER, t!("frames-title")))
+            .id(egui::Id::new("frame_profiler"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(560.0, 420.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(t!("frames-package"));
+                    let edit = ui.add(
+                        egui::TextEdit::singleline(&mut self.package)
+                            .hint_text("com.example.app")
+                            .desired_width(220.0),
+                    );
+                    if edit.changed() {
+                        self.frames.clear();
+                    }
+                    if ui.button(t!("frames-foreground")).clicked() {
+                        action = FrameProfilerAction::UseForeground;
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label(t!("frames-refresh-rate"));
+                    for rate in REFRESH_RATES {
+                        ui.radio_value(&mut self.refresh_rate, rate, format!("{} Hz", rate));
+                    }
+                });
+                ui.horizontal(|ui| {
+                    let label = if self.running {
+                        format!("{} {}", egui_phosphor::fill::PAUSE, t!("frames-pause"))
+                    } else {
+                        format!("{} {}", egui_phosphor::fill::PLAY, t!("frames-start"))
+                    };
+                    if ui
+                        .add_enabled(!self.package.trim().is_empty(), egui::Button::new(label))
+                        .clicked()
+                    {
+                        self.running = !self.running;
+                    }
+                    if ui.button(t!("frames-reset")).clicked() {
+                        self.frames.clear();
+                    }
+                    if self.running {
+                        ui.add(egui::Spinner::new().size(14.0));
+                        ui.ctx().request_repaint_after(crate::gfxinfo::POLL_INTERVAL);
+                    }
+                });
+                if let Some(error) = &self.error {
+                    ui.label(RichText::new(error).color(Color32::RED));
+                }
+                ui.separator();
+
+                let durations: Vec<f32> = self.frames.iter().map(|frame| frame.duration_ms).collect();
+                let summary = summarize(&durations, self.budget_ms());
+                egui::Grid::new("frame_summary").num_columns(4).spacing([24.0, 4.0]).show(ui, |ui| {
+                    ui.label(t!("frames-count", count = summary.frames));
+                    ui.label(format!("p50 {:.1} ms", summary.p50));
+                    ui.label(format!("p90 {:.1} ms", summary.p90));
+                    ui.end_row();
+                    let jank_percent = if summary.frames == 0 {
+                        0.0
+                    } else {
+                        summary.janky as f32 * 100.0 / summary.frames as f32
+                    };
+                    ui.label(t!("frames-janky", count = summary.janky, percent = format!("{:.1}", jank_percent)));
+                    ui.label(format!("p95 {:.1} ms", summary.p95));
+                    ui.label(format!("p99 {:.1} ms", summary.p99));
+                    ui.end_row();
+                    ui.label(t!("frames-dropped", count = summary.dropped));
+                    ui.end_row();
+                });
+                ui.separator();
+
+                if durations.is_empty() {
+                    let hint = if self.running { t!("frames-waiting") } else { t!("frames-hint") };
+                    ui.label(RichText::new(hint).color(Color32::GRAY));
+                } else {
+                    self.chart(ui, &durations);
+                }
+            });
+
+        self.visible = open;
+        action
+    }
+
+    /// One bar per frame, newest on the right, against the frame budget line
+    fn chart(&self, ui: &mut egui::Ui, durations: &[f32]) {
+        let budget = self.budget_ms();
+        let height = ui.available_height().max(120.0);
+        let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width(), height), egui::Sense::hover());
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+
+        // Up to three budgets tall so one long frame does not flatten the rest
+        let max_ms = durations.iter().copied().fold(budget * 1.5, f32::max).min(budget * 3.0);
+        let y_of = |ms: f32| rect.bottom() - (ms.min(max_ms) / max_ms) * rect.height();
+        let bar_width = rect.width() / HISTORY as f32;
+        let start = rect.right() - bar_width * durations.len() as f32;
+        for (index, duration) in durations.iter().enumerate() {
+            let x = start + bar_width * index as f32;
+            let color = if *duration > budget * 2.0 {
+                Color32::RED
+            } else if *duration > budget {
+                Color32::YELLOW
+            } else {
+                Color32::from_rgb(80, 180, 90)
+            };
+            painter.rect_filled(
+                egui::Rect::from_min_max(egui::pos2(x, y_of(*duration)), egui::pos2(x + bar_width.max(1.0), rect.bottom())),
+                0.0,
+                color,
+            );
+        }
+        let budget_y = y_of(budget);
+        painter.hline(rect.x_range(), budget_y, egui::Stroke::new(1.0, Color32::LIGHT_BLUE));
+        painter.text(
+            egui::pos2(rect.left() + 4.0, budget_y - 2.0),
+            egui::Align2::LEFT_BOTTOM,
+            format!("{:.1} ms", budget),
+            egui::FontId::proportional(10.0),
+            Color32::LIGHT_BLUE,
+        );
+
+        if let Some(pointer) = response.hover_pos() {
+            let index = ((pointer.x - start) / bar_width).floor();
+            if index >= 0.0
+                && let Some(duration) = durations.get(index as usize)
+            {
+                response.on_hover_text(format!("{:.1} ms", duration));
+            }
+        }
+    }
+}
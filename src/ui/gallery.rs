@@ -0,0 +1,356 @@
+use crate::device_frame::{FrameOptions, FrameStyle};
+use crate::gallery::{CaptureIndex, CaptureKind};
+use crate::t;
+use egui::{Color32, RichText, Window};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::mpsc;
+
+/// Longest edge of gallery thumbnails
+const THUMBNAIL_SIZE: u32 = 160;
+
+/// Thumbnails of the screenshots and recordings in the capture directory
+pub struct GalleryWindow {
+    visible: bool,
+    dir: PathBuf,
+    index: CaptureIndex,
+    thumbnails: HashMap<PathBuf, egui::TextureHandle>,
+    thumbnail_receiver: Option<mpsc::Receiver<(PathBuf, egui::ColorImage)>>,
+    renaming: Option<(usize, String)>,
+    pending_delete: Option<usize>,
+    error: Option<String>,
+    /// Screenshot being exported in a device frame, and how
+    framing: Option<usize>,
+    frame_options: FrameOptions,
+    frame_receiver: Option<mpsc::Receiver<Result<PathBuf, String>>>,
+    framed: Option<Result<PathBuf, String>>,
+}
+
+impl Default for GalleryWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GalleryWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            dir: PathBuf::new(),
+            index: CaptureIndex::default(),
+            thumbnails: HashMap::new(),
+            thumbnail_receiver: None,
+            renaming: None,
+            pending_delete: None,
+            error: None,
+            framing: None,
+            frame_options: FrameOptions::default(),
+            frame_receiver: None,
+            framed: None,
+        }
+    }
+
+    /// Show the captures of `dir`, reloading its index
+    pub fn open(&mut self, dir: PathBuf) {
+        self.visible = true;
+        self.dir = dir;
+        self.reload();
+    }
+
+    fn reload(&mut self) {
+        self.index = CaptureIndex::load(&self.dir);
+        self.renaming = None;
+        self.pending_delete = None;
+        self.framing = self.framing.filter(|index| *index < self.index.entries.len());
+        self.thumbnails.retain(|path, _| path.is_file());
+
+        // Decode screenshots off the UI thread; recordings get an icon instead
+        let missing: Vec<PathBuf> = self
+            .index
+            .entries
+            .iter()
+            .filter(|entry| entry.kind == CaptureKind::Screenshot && !self.thumbnails.contains_key(&entry.path))
+            .map(|entry| entry.path.clone())
+            .collect();
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            for path in missing {
+                let Ok(image) = image::open(&path) else {
+                    continue;
+                };
+                let thumbnail = image.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE).to_rgba8();
+                let size = [thumbnail.width() as usize, thumbnail.height() as usize];
+                let color_image = egui::ColorImage::from_rgba_unmultiplied(size, thumbnail.as_raw());
+                if sender.send((path, color_image)).is_err() {
+                    break;
+                }
+            }
+        });
+        self.thumbnail_receiver = Some(receiver);
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        if !self.visible {
+            return;
+        }
+
+        if let Some(receiver) = &self.thumbnail_receiver {
+            while let Ok((path, image)) = receiver.try_recv() {
+                let texture = ctx.load_texture(path.to_string_lossy(), image, egui::TextureOptions::LINEAR);
+                self.thumbnails.insert(path, texture);
+                ctx.request_repaint();
+            }
+        }
+
+        let mut open = self.visible;
+        let mut reload = false;
+        if let Some(receiver) = &self.frame_receiver
+            && let Ok(result) = receiver.try_recv()
+        {
+            self.frame_receiver = None;
+            reload = result.is_ok();
+            self.framed = Some(result);
+        }
+
+        Window::new(format!("{} {}", egui_phosphor::fill::IMAGES, t!("gallery-title")))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(640.0, 480.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(self.dir.display().to_string()).monospace().size(11.0));
+                    if ui.button(t!("gallery-refresh")).clicked() {
+                        reload = true;
+                    }
+                    if ui.button(format!("{} {}", egui_phosphor::fill::FOLDER_OPEN, t!("gallery-open-folder"))).clicked()
+                        && let Err(e) = crate::utils::open_folder(&self.dir)
+                    {
+                        self.error = Some(e.to_string());
+                    }
+                });
+                if let Some(error) = &self.error {
+                    ui.label(RichText::new(error).color(Color32::RED));
+                }
+                ui.separator();
+
+                if self.index.entries.is_empty() {
+                    ui.label(RichText::new(t!("gallery-empty")).color(Color32::GRAY));
+                    return;
+                }
+
+                egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                    ui.horizontal_wrapped(|ui| {
+                        // Newest first
+                        for index in (0..self.index.entries.len()).rev() {
+                            self.show_card(ui, index);
+                        }
+                    });
+                });
+            });
+
+        self.show_frame_export(ctx);
+        if reload {
+            self.error = None;
+            self.reload();
+        }
+        self.visible = open;
+    }
+
+    /// Options for exporting the chosen screenshot in a device frame
+    fn show_frame_export(&mut self, ctx: &egui::Context) {
+        let Some(entry) = self.framing.and_then(|index| self.index.entries.get(index)).cloned() else {
+            return;
+        };
+        let mut open = true;
+        let exporting = self.frame_receiver.is_some();
+
+        Window::new(format!("{} {}", egui_phosphor::fill::DEVICE_MOBILE, t!("gallery-frame-title")))
+            .id(egui::Id::new("gallery_device_frame"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(RichText::new(entry.file_name()).monospace().size(11.0));
+                ui.add_space(4.0);
+                let options = &mut self.frame_options;
+                egui::Grid::new("gallery_device_frame_grid").num_columns(2).spacing([12.0, 6.0]).show(ui, |ui| {
+                    ui.label(t!("gallery-frame"));
+                    egui::ComboBox::from_id_salt("gallery_frame_style")
+                        .selected_text(options.style.name())
+                        .show_ui(ui, |ui| {
+                            for style in FrameStyle::ALL {
+                                ui.selectable_value(&mut options.style, style, style.name());
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label(t!("gallery-bezel"));
+                    let mut bezel = Color32::from_rgba_unmultiplied(
+                        options.bezel[0],
+                        options.bezel[1],
+                        options.bezel[2],
+                        options.bezel[3],
+                    );
+                    if ui.color_edit_button_srgba(&mut bezel).changed() {
+                        options.bezel = bezel.to_srgba_unmultiplied();
+                    }
+                    ui.end_row();
+
+                    ui.label(t!("gallery-shadow"));
+                    ui.checkbox(&mut options.shadow, "");
+                    ui.end_row();
+
+                    ui.label(t!("gallery-background"));
+                    ui.horizontal(|ui| {
+                        let mut filled = options.background.is_some();
+                        if ui.checkbox(&mut filled, "").changed() {
+                            options.background = filled.then_some([255, 255, 255, 255]);
+                        }
+                        match &mut options.background {
+                            Some(background) => {
+                                let [r, g, b, _] = *background;
+                                let mut color = Color32::from_rgb(r, g, b);
+                                if ui.color_edit_button_srgba(&mut color).changed() {
+                                    *background = color.to_srgba_unmultiplied();
+                                }
+                            }
+                            None => {
+                                ui.label(RichText::new(t!("gallery-transparent")).color(Color32::GRAY));
+                            }
+                        }
+                    });
+                    ui.end_row();
+                });
+                ui.add_space(6.0);
+
+                ui.horizontal(|ui| {
+                    let label = format!("{} {}", egui_phosphor::fill::EXPORT, t!("gallery-export-png"));
+                    if ui.add_enabled(!exporting, egui::Button::new(label)).clicked() {
+                        let (sender, receiver) = mpsc::channel();
+                        let options = self.frame_options;
+                        std::thread::spawn(move || {
+                            let result = crate::device_frame::export(&entry.path, &entry.device, &options);
+                            let _ = sender.send(result.map_err(|e| e.to_string()));
+                        });
+                        self.frame_receiver = Some(receiver);
+                        self.framed = None;
+                    }
+                    if exporting {
+                        ui.add(egui::Spinner::new().size(14.0));
+                        ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                    }
+                });
+                match &self.framed {
+                    Some(Ok(path)) => {
+                        let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+                        ui.label(RichText::new(t!("gallery-saved", name = name)).color(Color32::GREEN));
+                    }
+                    Some(Err(e)) => {
+                        ui.label(RichText::new(e).color(Color32::RED));
+                    }
+                    None => {}
+                }
+            });
+
+        if !open {
+            self.framing = None;
+            self.framed = None;
+        }
+    }
+
+    fn show_card(&mut self, ui: &mut egui::Ui, index: usize) {
+        let entry = self.index.entries[index].clone();
+        let size = egui::vec2(THUMBNAIL_SIZE as f32, THUMBNAIL_SIZE as f32);
+
+        ui.group(|ui| {
+            ui.set_width(size.x);
+            ui.vertical(|ui| {
+                match self.thumbnails.get(&entry.path) {
+                    Some(texture) => {
+                        ui.add(egui::Image::new(texture).max_size(size));
+                    }
+                    None => {
+                        let icon = match entry.kind {
+                            CaptureKind::Screenshot => egui_phosphor::fill::IMAGE,
+                            CaptureKind::Recording => egui_phosphor::fill::FILM_STRIP,
+                        };
+                        ui.allocate_ui(egui::vec2(size.x, size.y / 2.0), |ui| {
+                            ui.centered_and_justified(|ui| {
+                                ui.label(RichText::new(icon).size(40.0).color(Color32::GRAY));
+                            });
+                        });
+                    }
+                }
+
+                match &mut self.renaming {
+                    Some((renaming, name)) if *renaming == index => {
+                        let response = ui.text_edit_singleline(name);
+                        if response.lost_focus() {
+                            let new_name = name.clone();
+                            self.renaming = None;
+                            if ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                                match self.index.rename(index, &new_name).and_then(|_| self.index.save(&self.dir)) {
+                                    Ok(()) => self.error = None,
+                                    Err(e) => self.error = Some(e.to_string()),
+                                }
+                            }
+                        } else {
+                            response.request_focus();
+                        }
+                    }
+                    _ => {
+                        ui.label(RichText::new(entry.file_name()).size(11.0)).on_hover_text(entry.path.display().to_string());
+                    }
+                }
+                ui.label(
+                    RichText::new(format!("{} · {}", entry.captured_at.format("%Y-%m-%d %H:%M"), entry.device))
+                        .size(10.0)
+                        .color(Color32::GRAY),
+                );
+
+                ui.horizontal(|ui| {
+                    if ui.small_button(egui_phosphor::fill::ARROW_SQUARE_OUT).on_hover_text(t!("gallery-open")).clicked()
+                        && let Err(e) = crate::utils::open_url(&entry.path.to_string_lossy())
+                    {
+                        self.error = Some(e.to_string());
+                    }
+                    if ui.small_button(egui_phosphor::fill::FOLDER_OPEN).on_hover_text(t!("gallery-show-in-folder")).clicked()
+                        && let Err(e) = crate::utils::reveal_in_file_manager(&entry.path)
+                    {
+                        self.error = Some(e.to_string());
+                    }
+                    let frame = egui::Button::new(egui_phosphor::fill::DEVICE_MOBILE).small();
+                    if entry.kind == CaptureKind::Screenshot
+                        && ui.add(frame).on_hover_text(t!("gallery-export-frame")).clicked()
+                    {
+                        self.framing = Some(index);
+                        self.framed = None;
+                    }
+                    if ui.small_button(egui_phosphor::fill::PENCIL_SIMPLE).on_hover_text(t!("gallery-rename")).clicked() {
+                        let stem = entry
+                            .path
+                            .file_stem()
+                            .map(|stem| stem.to_string_lossy().to_string())
+                            .unwrap_or_default();
+                        self.renaming = Some((index, stem));
+                    }
+                    if self.pending_delete == Some(index) {
+                        if ui.small_button(RichText::new(t!("gallery-delete-confirm")).color(Color32::RED)).clicked() {
+                            self.pending_delete = None;
+                            match self.index.delete(index).and_then(|_| self.index.save(&self.dir)) {
+                                Ok(()) => {
+                                    self.thumbnails.remove(&entry.path);
+                                    self.error = None;
+                                }
+                                Err(e) => self.error = Some(e.to_string()),
+                            }
+                        }
+                    } else if ui.small_button(egui_phosphor::fill::TRASH).on_hover_text(t!("gallery-delete")).clicked() {
+                        self.pending_delete = Some(index);
+                    }
+                });
+            });
+        });
+    }
+}
@@ -0,0 +1,237 @@
+use crate::config::{Gesture, GestureKind};
+use crate::t;
+use egui::{Color32, RichText, Sense, Stroke, Window};
+
+pub enum GestureDesignerAction {
+    None,
+    /// Perform a gesture on the selected device without saving it
+    Try(Gesture),
+    /// The edited list of gestures to store in the config
+    Save(Vec<Gesture>),
+}
+
+/// Size of the screen drawn in the designer; gestures are stored in fractions of it
+const CANVAS: egui::Vec2 = egui::vec2(180.0, 360.0);
+
+/// Draws paths and pinches on a stand-in screen and keeps the named ones
+pub struct GestureDesigner {
+    visible: bool,
+    gestures: Vec<Gesture>,
+    name: String,
+    kind: GestureKind,
+    duration_ms: u32,
+}
+
+impl Default for GestureDesigner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GestureDesigner {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            gestures: Vec::new(),
+            name: String::new(),
+            kind: GestureKind::Path { points: Vec::new() },
+            duration_ms: 400,
+        }
+    }
+
+    pub fn open(&mut self, gestures: Vec<Gesture>) {
+        if !self.visible {
+            self.gestures = gestures;
+        }
+        self.visible = true;
+    }
+
+    fn draft(&self) -> Gesture {
+        Gesture {
+            name: self.name.trim().to_string(),
+            kind: self.kind.clone(),
+            duration_ms: self.duration_ms,
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        match &self.kind {
+            GestureKind::Path { points } => points.len() >= 2,
+            GestureKind::Pinch { from, to, .. } => from != to,
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> GestureDesignerAction {
+        if !self.visible {
+            return GestureDesignerAction::None;
+        }
+
+        let mut action = GestureDesignerAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::HAND_SWIPE_RIGHT, t!("gestures-title")))
+            .id(egui::Id::new("gesture_designer"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal_top(|ui| {
+                    self.show_canvas(ui);
+                    ui.vertical(|ui| {
+                        ui.set_width(260.0);
+                        self.show_controls(ui, &mut action);
+                        ui.separator();
+                        self.show_saved(ui, &mut action);
+                    });
+                });
+            });
+
+        self.visible = open;
+        action
+    }
+
+    fn show_canvas(&mut self, ui: &mut egui::Ui) {
+        let (response, painter) = ui.allocate_painter(CANVAS, Sense::click());
+        let rect = response.rect;
+        painter.rect_filled(rect, 12.0, ui.visuals().extreme_bg_color);
+        painter.rect_stroke(rect, 12.0, Stroke::new(1.0, Color32::GRAY), egui::StrokeKind::Inside);
+        let to_screen = |point: [f32; 2]| rect.min + egui::vec2(point[0] * rect.width(), point[1] * rect.height());
+
+        if response.clicked()
+            && let Some(position) = response.interact_pointer_pos()
+        {
+            let offset = position - rect.min;
+            let point = [(offset.x / rect.width()).clamp(0.0, 1.0), (offset.y / rect.height()).clamp(0.0, 1.0)];
+            match &mut self.kind {
+                GestureKind::Path { points } => points.push(point),
+                GestureKind::Pinch { center, .. } => *center = point,
+            }
+        }
+
+        let accent = ui.visuals().selection.bg_fill;
+        match &self.kind {
+            GestureKind::Path { points } => {
+                let line: Vec<egui::Pos2> = points.iter().map(|point| to_screen(*point)).collect();
+                painter.add(egui::Shape::line(line.clone(), Stroke::new(2.0, accent)));
+                for (index, position) in line.iter().enumerate() {
+                    let color = if index == 0 { Color32::GREEN } else { accent };
+                    painter.circle_filled(*position, 5.0, color);
+                }
+                if points.is_empty() {
+                    painter.text(
+                        rect.center(),
+                        egui::Align2::CENTER_CENTER,
+                        t!("gestures-canvas-hint"),
+                        egui::FontId::proportional(11.0),
+                        Color32::GRAY,
+                    );
+                }
+            }
+            GestureKind::Pinch { center, from, to } => {
+                let center = to_screen(*center);
+                for (spread, filled) in [(*from, true), (*to, false)] {
+                    let half = egui::vec2(spread * rect.width() / 2.0, 0.0);
+                    for position in [center - half, center + half] {
+                        if filled {
+                            painter.circle_filled(position, 7.0, accent);
+                        } else {
+                            painter.circle_stroke(position, 7.0, Stroke::new(2.0, accent));
+                        }
+                    }
+                }
+                painter.circle_filled(center, 2.0, Color32::GRAY);
+            }
+        }
+    }
+
+    fn show_controls(&mut self, ui: &mut egui::Ui, action: &mut GestureDesignerAction) {
+        ui.horizontal(|ui| {
+            let is_path = matches!(self.kind, GestureKind::Path { .. });
+            if ui.selectable_label(is_path, t!("gestures-path")).clicked() && !is_path {
+                self.kind = GestureKind::Path { points: Vec::new() };
+            }
+            if ui.selectable_label(!is_path, t!("gestures-pinch")).clicked() && is_path {
+                self.kind = GestureKind::Pinch { center: [0.5, 0.5], from: 0.2, to: 0.6 };
+            }
+        });
+
+        match &mut self.kind {
+            GestureKind::Path { points } => {
+                ui.label(RichText::new(t!("gestures-points", count = points.len())).weak());
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(!points.is_empty(), egui::Button::new(t!("gestures-undo"))).clicked() {
+                        points.pop();
+                    }
+                    if ui.add_enabled(!points.is_empty(), egui::Button::new(t!("gestures-clear"))).clicked() {
+                        points.clear();
+                    }
+                });
+            }
+            GestureKind::Pinch { from, to, .. } => {
+                ui.label(RichText::new(t!("gestures-pinch-hint")).size(11.0).color(Color32::GRAY));
+                egui::Grid::new("gesture_pinch").num_columns(2).show(ui, |ui| {
+                    ui.label(t!("gestures-from"));
+                    ui.add(egui::Slider::new(from, 0.05..=0.95).custom_formatter(|n, _| format!("{:.0}%", n * 100.0)));
+                    ui.end_row();
+                    ui.label(t!("gestures-to"));
+                    ui.add(egui::Slider::new(to, 0.05..=0.95).custom_formatter(|n, _| format!("{:.0}%", n * 100.0)));
+                    ui.end_row();
+                });
+            }
+        }
+
+        egui::Grid::new("gesture_form").num_columns(2).show(ui, |ui| {
+            ui.label(t!("gestures-duration"));
+            ui.add(egui::DragValue::new(&mut self.duration_ms).range(50..=10_000).speed(10).suffix(" ms"));
+            ui.end_row();
+            ui.label(t!("gestures-name"));
+            ui.text_edit_singleline(&mut self.name);
+            ui.end_row();
+        });
+
+        ui.horizontal(|ui| {
+            let complete = self.is_complete();
+            let try_button = egui::Button::new(format!("{} {}", egui_phosphor::fill::PLAY, t!("gestures-try")));
+            if ui.add_enabled(complete, try_button).clicked() {
+                *action = GestureDesignerAction::Try(self.draft());
+            }
+            let can_save = complete && !self.name.trim().is_empty();
+            if ui.add_enabled(can_save, egui::Button::new(t!("gestures-save"))).clicked() {
+                let draft = self.draft();
+                // Saving under an existing name replaces that gesture
+                match self.gestures.iter_mut().find(|saved| saved.name == draft.name) {
+                    Some(saved) => *saved = draft,
+                    None => self.gestures.push(draft),
+                }
+                *action = GestureDesignerAction::Save(self.gestures.clone());
+            }
+        });
+    }
+
+    fn show_saved(&mut self, ui: &mut egui::Ui, action: &mut GestureDesignerAction) {
+        if self.gestures.is_empty() {
+            ui.label(RichText::new(t!("gestures-empty")).color(Color32::GRAY));
+        }
+        let mut remove = None;
+        for (index, saved) in self.gestures.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(&saved.name).strong());
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.small_button(egui_phosphor::fill::TRASH).on_hover_text(t!("gestures-delete")).clicked() {
+                        remove = Some(index);
+                    }
+                    if ui.small_button(egui_phosphor::fill::PENCIL_SIMPLE).on_hover_text(t!("gestures-edit")).clicked()
+                    {
+                        self.name = saved.name.clone();
+                        self.kind = saved.kind.clone();
+                        self.duration_ms = saved.duration_ms;
+                    }
+                });
+            });
+        }
+        if let Some(index) = remove {
+            self.gestures.remove(index);
+            *action = GestureDesignerAction::Save(self.gestures.clone());
+        }
+    }
+}
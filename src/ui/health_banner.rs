@@ -0,0 +1,126 @@
+use crate::tools::Package;
+use crate::t;
+use egui::{Color32, RichText};
+
+pub const ADB_DOWNLOAD_URL: &str = "https://developer.android.com/tools/releases/platform-tools";
+pub const SCRCPY_DOWNLOAD_URL: &str = "https://github.com/Genymobile/scrcpy/releases/latest";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tool {
+    Adb,
+    Scrcpy,
+}
+
+impl Tool {
+    pub fn name(self) -> &'static str {
+        match self {
+            Tool::Adb => "adb",
+            Tool::Scrcpy => "scrcpy",
+        }
+    }
+
+    /// What the built-in downloader installs for this tool
+    pub fn package(self) -> Package {
+        match self {
+            Tool::Adb => Package::PlatformTools,
+            Tool::Scrcpy => Package::Scrcpy,
+        }
+    }
+
+    pub fn download_url(self) -> &'static str {
+        match self {
+            Tool::Adb => ADB_DOWNLOAD_URL,
+            Tool::Scrcpy => SCRCPY_DOWNLOAD_URL,
+        }
+    }
+}
+
+pub enum HealthBannerAction {
+    None,
+    Locate(Tool),
+    /// Fetch and set up the official build
+    Install(Tool),
+    /// Open the download page
+    Download(Tool),
+}
+
+/// Banner across the top of the main window for dependencies that could not be found
+pub struct HealthBanner {
+    dismissed: Vec<Tool>,
+}
+
+impl Default for HealthBanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HealthBanner {
+    pub fn new() -> Self {
+        Self { dismissed: Vec::new() }
+    }
+
+    /// `installing` is the tool the built-in downloader is fetching, if any
+    pub fn show(&mut self, ctx: &egui::Context, missing: &[Tool], installing: Option<Tool>) -> HealthBannerAction {
+        // A dismissal only lasts while the tool stays missing, so a later loss shows again
+        self.dismissed.retain(|tool| missing.contains(tool));
+
+        let visible: Vec<Tool> = missing
+            .iter()
+            .copied()
+            .filter(|tool| !self.dismissed.contains(tool))
+            .collect();
+        if visible.is_empty() {
+            return HealthBannerAction::None;
+        }
+
+        let mut action = HealthBannerAction::None;
+
+        egui::TopBottomPanel::top("health_banner")
+            .frame(
+                egui::Frame::new()
+                    .fill(Color32::from_rgb(120, 60, 0))
+                    .inner_margin(egui::Margin::symmetric(8, 6)),
+            )
+            .show(ctx, |ui| {
+                for tool in visible {
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new(format!(
+                                "{} {}",
+                                egui_phosphor::fill::WARNING,
+                                match tool {
+                                    Tool::Adb => t!("banner-adb-missing"),
+                                    Tool::Scrcpy => t!("banner-scrcpy-missing"),
+                                }
+                            ))
+                            .color(Color32::WHITE)
+                            .strong(),
+                        );
+                        if ui.button(t!("banner-locate")).clicked() {
+                            action = HealthBannerAction::Locate(tool);
+                        }
+                        if installing == Some(tool) {
+                            ui.add(egui::Spinner::new().color(Color32::WHITE));
+                        } else if ui
+                            .add_enabled(installing.is_none(), egui::Button::new(t!("banner-install")))
+                            .on_hover_text(t!("banner-install-hint", package = tool.package().name()))
+                            .clicked()
+                        {
+                            action = HealthBannerAction::Install(tool);
+                        }
+                        if ui.button(t!("banner-download")).on_hover_text(t!("banner-download-hint")).clicked() {
+                            action = HealthBannerAction::Download(tool);
+                        }
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui.small_button("✖").on_hover_text(t!("banner-dismiss")).clicked() {
+                                self.dismissed.push(tool);
+                            }
+                        });
+                    });
+                }
+            });
+
+        action
+    }
+}
@@ -0,0 +1,101 @@
+use crate::t;
+use egui::Window;
+use egui_commonmark::{CommonMarkCache, CommonMarkViewer};
+
+/// Pages of the bundled help, compiled into the binary so they work offline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelpTopic {
+    Wireless,
+    ScrcpyOptions,
+    Troubleshooting,
+}
+
+impl HelpTopic {
+    pub const ALL: [HelpTopic; 3] = [HelpTopic::Wireless, HelpTopic::ScrcpyOptions, HelpTopic::Troubleshooting];
+
+    pub fn title(self) -> String {
+        match self {
+            HelpTopic::Wireless => t!("help-topic-wireless"),
+            HelpTopic::ScrcpyOptions => t!("help-topic-scrcpy"),
+            HelpTopic::Troubleshooting => t!("help-topic-troubleshooting"),
+        }
+    }
+
+    fn markdown(self) -> &'static str {
+        match self {
+            HelpTopic::Wireless => include_str!("../../assets/help/wireless.md"),
+            HelpTopic::ScrcpyOptions => include_str!("../../assets/help/scrcpy.md"),
+            HelpTopic::Troubleshooting => include_str!("../../assets/help/troubleshooting.md"),
+        }
+    }
+}
+
+/// Small "?" button placed next to a setting; true when it was clicked
+pub fn help_button(ui: &mut egui::Ui, topic: HelpTopic) -> bool {
+    ui.small_button(egui_phosphor::fill::QUESTION)
+        .on_hover_text(t!("help-button-hint", topic = topic.title()))
+        .clicked()
+}
+
+/// Offline help browser, opened with F1 or the "?" buttons
+pub struct HelpWindow {
+    visible: bool,
+    topic: HelpTopic,
+    markdown: CommonMarkCache,
+}
+
+impl Default for HelpWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HelpWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            topic: HelpTopic::Troubleshooting,
+            markdown: CommonMarkCache::default(),
+        }
+    }
+
+    pub fn open(&mut self, topic: HelpTopic) {
+        self.visible = true;
+        self.topic = topic;
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        if !self.visible {
+            return;
+        }
+
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::QUESTION, t!("help-title")))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(620.0, 480.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    for topic in HelpTopic::ALL {
+                        ui.selectable_value(&mut self.topic, topic, topic.title());
+                    }
+                });
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .id_salt(self.topic.title())
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| {
+                        CommonMarkViewer::new().show(ui, &mut self.markdown, self.topic.markdown());
+                    });
+            });
+
+        self.visible = open;
+    }
+}
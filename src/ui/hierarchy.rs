@@ -0,0 +1,263 @@
+use crate::hierarchy::Node;
+use crate::t;
+use egui::{Color32, RichText, Sense, Stroke, TextureHandle, Window};
+
+pub enum HierarchyAction {
+    None,
+    /// Dump the hierarchy and take a screenshot again
+    Refresh,
+}
+
+/// Longer side of the screenshot shown next to the tree
+pub const INSPECTOR_EDGE: u32 = 1280;
+
+/// The view hierarchy on screen as a tree next to a screenshot, with the bounds of the node under
+/// the pointer outlined on the other side
+pub struct HierarchyWindow {
+    visible: bool,
+    device: String,
+    root: Option<Node>,
+    pending: Option<egui::ColorImage>,
+    texture: Option<TextureHandle>,
+    /// Screen size of the device, which the bounds are in
+    screen: (u32, u32),
+    loading: bool,
+    error: Option<String>,
+    /// Paths of the node under the pointer and the one clicked, as child indices from the root
+    hovered: Option<Vec<usize>>,
+    selected: Option<Vec<usize>>,
+    /// Expand the tree down to the selected node on the next frame
+    reveal: bool,
+}
+
+impl Default for HierarchyWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HierarchyWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            device: String::new(),
+            root: None,
+            pending: None,
+            texture: None,
+            screen: (0, 0),
+            loading: false,
+            error: None,
+            hovered: None,
+            selected: None,
+            reveal: false,
+        }
+    }
+
+    pub fn open(&mut self, device: String) {
+        if device != self.device {
+            self.root = None;
+            self.texture = None;
+            self.selected = None;
+        }
+        self.visible = true;
+        self.device = device;
+    }
+
+    pub fn device(&self) -> &str {
+        &self.device
+    }
+
+    pub fn set_loading(&mut self) {
+        self.loading = true;
+    }
+
+    pub fn set_dump(&mut self, result: Result<(Node, egui::ColorImage, (u32, u32)), String>) {
+        self.loading = false;
+        match result {
+            Ok((root, image, screen)) => {
+                self.root = Some(root);
+                self.pending = Some(image);
+                self.screen = screen;
+                self.error = None;
+                self.selected = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> HierarchyAction {
+        if !self.visible {
+            return HierarchyAction::None;
+        }
+        if let Some(image) = self.pending.take() {
+            self.texture = Some(ctx.load_texture("hierarchy", image, egui::TextureOptions::LINEAR));
+        }
+
+        let mut action = HierarchyAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::TREE_STRUCTURE, t!("hierarchy-title")))
+            .id(egui::Id::new("hierarchy"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(820.0, 720.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(&self.device).weak());
+                    if ui
+                        .add_enabled(!self.loading, egui::Button::new(egui_phosphor::fill::ARROWS_CLOCKWISE))
+                        .on_hover_text(t!("hierarchy-refresh"))
+                        .clicked()
+                    {
+                        action = HierarchyAction::Refresh;
+                    }
+                    if self.loading {
+                        ui.add(egui::Spinner::new().size(14.0));
+                    }
+                });
+                ui.label(RichText::new(t!("hierarchy-hint")).size(11.0).color(Color32::GRAY));
+                if let Some(error) = &self.error {
+                    ui.label(RichText::new(error).color(Color32::RED));
+                }
+                ui.separator();
+
+                let Some(root) = self.root.take() else {
+                    if !self.loading {
+                        ui.label(RichText::new(t!("hierarchy-empty")).color(Color32::GRAY));
+                    }
+                    return;
+                };
+                // Hover is recomputed every frame from whichever side the pointer is on
+                let mut hovered = None;
+                ui.horizontal_top(|ui| {
+                    ui.vertical(|ui| {
+                        ui.set_width(360.0);
+                        egui::ScrollArea::both().id_salt("hierarchy_tree").max_height(420.0).show(ui, |ui| {
+                            self.show_node(ui, &root, &mut Vec::new(), &mut hovered);
+                        });
+                        self.reveal = false;
+                        ui.separator();
+                        self.show_attributes(ui, &root);
+                    });
+                    ui.separator();
+                    self.show_screenshot(ui, &root, &mut hovered);
+                });
+                self.hovered = hovered;
+                self.root = Some(root);
+            });
+
+        self.visible = open;
+        action
+    }
+
+    fn show_node(
+        &mut self,
+        ui: &mut egui::Ui,
+        node: &Node,
+        path: &mut Vec<usize>,
+        hovered: &mut Option<Vec<usize>>,
+    ) {
+        let selected = self.selected.as_ref() == Some(path);
+        let highlighted = selected || self.hovered.as_ref() == Some(path);
+        let mut label = RichText::new(node.label()).monospace().size(12.0);
+        if highlighted {
+            label = label.color(if selected { Color32::RED } else { Color32::YELLOW });
+        }
+
+        let response = if node.children.is_empty() {
+            ui.add(egui::Label::new(label).sense(Sense::click()))
+        } else {
+            let id = ui.make_persistent_id(("hierarchy_node", path.clone()));
+            let mut state =
+                egui::collapsing_header::CollapsingState::load_with_default_open(ui.ctx(), id, path.len() < 3);
+            // Open the branch holding a node picked on the screenshot
+            let inside = |selected: &Vec<usize>| selected.starts_with(path) && selected != path;
+            if self.reveal && self.selected.as_ref().is_some_and(inside) {
+                state.set_open(true);
+            }
+            let (_, header, _) = state
+                .show_header(ui, |ui| ui.add(egui::Label::new(label).sense(Sense::click())))
+                .body(|ui| {
+                    for (index, child) in node.children.iter().enumerate() {
+                        path.push(index);
+                        self.show_node(ui, child, path, hovered);
+                        path.pop();
+                    }
+                });
+            header.inner
+        };
+        if self.reveal && selected {
+            response.scroll_to_me(Some(egui::Align::Center));
+        }
+        if response.hovered() {
+            *hovered = Some(path.clone());
+        }
+        if response.clicked() {
+            self.selected = Some(path.clone());
+        }
+    }
+
+    /// Every attribute of the selected node
+    fn show_attributes(&self, ui: &mut egui::Ui, root: &Node) {
+        let Some(node) = self.selected.as_deref().and_then(|path| root.at(path)) else {
+            ui.label(RichText::new(t!("hierarchy-select")).color(Color32::GRAY));
+            return;
+        };
+        egui::ScrollArea::vertical().id_salt("hierarchy_attributes").show(ui, |ui| {
+            egui::Grid::new("hierarchy_attributes_grid").num_columns(2).striped(true).show(ui, |ui| {
+                for (name, value) in &node.attributes {
+                    ui.label(RichText::new(name).weak());
+                    ui.add(egui::Label::new(RichText::new(value).monospace()).wrap());
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    /// The screenshot with the hovered and selected bounds outlined; hovering it finds the node below
+    fn show_screenshot(&mut self, ui: &mut egui::Ui, root: &Node, hovered: &mut Option<Vec<usize>>) {
+        let Some(texture) = &self.texture else {
+            return;
+        };
+        let (width, height) = self.screen;
+        let image_size = texture.size_vec2();
+        let scale = (ui.available_width() / image_size.x).min(ui.available_height() / image_size.y).min(1.0);
+        let response = ui.add(
+            egui::Image::new(texture)
+                .fit_to_exact_size(image_size * scale)
+                .sense(Sense::click()),
+        );
+        let rect = response.rect;
+        let painter = ui.painter_at(rect);
+        let to_screen = |[left, top, right, bottom]: [u32; 4]| {
+            let point = |x: u32, y: u32| {
+                rect.min
+                    + egui::vec2(
+                        x as f32 / width.max(1) as f32 * rect.width(),
+                        y as f32 / height.max(1) as f32 * rect.height(),
+                    )
+            };
+            egui::Rect::from_min_max(point(left, top), point(right, bottom))
+        };
+
+        if let Some(position) = response.hover_pos() {
+            let offset = position - rect.min;
+            let x = (offset.x / rect.width() * width as f32) as u32;
+            let y = (offset.y / rect.height() * height as f32) as u32;
+            *hovered = root.deepest_at(x, y);
+            if response.clicked() {
+                self.selected = hovered.clone();
+                self.reveal = true;
+            }
+        }
+        let outlines = [(&self.selected, Color32::RED), (&self.hovered, Color32::YELLOW)];
+        for (path, color) in outlines {
+            if let Some(bounds) = path.as_deref().and_then(|path| root.at(path)).and_then(Node::bounds) {
+                let bounds = to_screen(bounds);
+                painter.rect_filled(bounds, 0.0, color.gamma_multiply(0.15));
+                painter.rect_stroke(bounds, 0.0, Stroke::new(2.0, color), egui::StrokeKind::Inside);
+            }
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use crate::intent::{ExtraType, IntentExtra, IntentKind, IntentSpec};
+use crate::t;
+use egui::{Color32, RichText, Window};
+
+pub enum IntentSenderAction {
+    None,
+    Send(IntentSpec),
+}
+
+/// Composer for `am start` / `am broadcast` with a history of sent intents
+pub struct IntentSenderWindow {
+    visible: bool,
+    spec: IntentSpec,
+    sending: bool,
+    output: Option<Result<String, String>>,
+}
+
+impl Default for IntentSenderWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IntentSenderWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            spec: IntentSpec {
+                action: "android.intent.action.VIEW".to_string(),
+                ..Default::default()
+            },
+            sending: false,
+            output: None,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.visible = true;
+    }
+
+    pub fn set_output(&mut self, output: Result<String, String>) {
+        self.sending = false;
+        self.output = Some(output);
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, history: &[IntentSpec]) -> IntentSenderAction {
+        if !self.visible {
+            return IntentSenderAction::None;
+        }
+
+        let mut action = IntentSenderAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::PAPER_PLANE_TILT, t!("intent-title")))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(480.0, 460.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.selectable_value(&mut self.spec.kind, IntentKind::Activity, t!("intent-activity"));
+                    ui.selectable_value(&mut self.spec.kind, IntentKind::Broadcast, t!("intent-broadcast"));
+                });
+
+                egui::Grid::new("intent_fields_grid").num_columns(2).show(ui, |ui| {
+                    ui.label(t!("intent-action"));
+                    ui.add(egui::TextEdit::singleline(&mut self.spec.action).desired_width(320.0));
+                    ui.end_row();
+                    ui.label(t!("intent-data"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.spec.data)
+                            .hint_text(t!("intent-data-hint"))
+                            .desired_width(320.0),
+                    );
+                    ui.end_row();
+                    ui.label(t!("intent-component"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.spec.component)
+                            .hint_text("com.example/.MainActivity")
+                            .desired_width(320.0),
+                    );
+                    ui.end_row();
+                    ui.label(t!("intent-category"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.spec.category)
+                            .hint_text("android.intent.category.BROWSABLE")
+                            .desired_width(320.0),
+                    );
+                    ui.end_row();
+                });
+
+                ui.label(t!("intent-extras"));
+                let mut remove = None;
+                for (index, extra) in self.spec.extras.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.add(egui::TextEdit::singleline(&mut extra.key).hint_text(t!("intent-extra-key")).desired_width(120.0));
+                        egui::ComboBox::from_id_salt(("intent_extra_type", index))
+                            .selected_text(extra.kind.label())
+                            .width(70.0)
+                            .show_ui(ui, |ui| {
+                                for kind in ExtraType::ALL {
+                                    ui.selectable_value(&mut extra.kind, kind, kind.label());
+                                }
+                            });
+                        ui.add(egui::TextEdit::singleline(&mut extra.value).hint_text(t!("intent-extra-value")).desired_width(140.0));
+                        if ui.small_button("✖").clicked() {
+                            remove = Some(index);
+                        }
+                    });
+                }
+                if let Some(index) = remove {
+                    self.spec.extras.remove(index);
+                }
+                if ui.small_button(format!("{} {}", egui_phosphor::fill::PLUS, t!("intent-add-extra"))).clicked() {
+                    self.spec.extras.push(IntentExtra::default());
+                }
+
+                ui.separator();
+                ui.label(RichText::new(self.spec.to_command()).monospace().size(11.0).color(Color32::GRAY));
+
+                ui.horizontal(|ui| {
+                    let can_send = !self.sending && !self.spec.is_empty();
+                    if ui.add_enabled(can_send, egui::Button::new(t!("intent-send"))).clicked() {
+                        self.sending = true;
+                        self.output = None;
+                        action = IntentSenderAction::Send(self.spec.clone());
+                    }
+                    if ui.button(t!("intent-clear")).clicked() {
+                        self.spec = IntentSpec::default();
+                        self.output = None;
+                    }
+                    if self.sending {
+                        ui.add(egui::Spinner::new().size(14.0));
+                    }
+                });
+
+                match &self.output {
+                    Some(Ok(output)) => {
+                        ui.label(RichText::new(output.trim()).monospace().size(11.0));
+                    }
+                    Some(Err(error)) => {
+                        ui.label(RichText::new(error).color(Color32::RED));
+                    }
+                    None => {}
+                }
+
+                if !history.is_empty() {
+                    ui.separator();
+                    egui::CollapsingHeader::new(t!("intent-history", count = history.len()))
+                        .id_salt("intent_history")
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            egui::ScrollArea::vertical().max_height(140.0).show(ui, |ui| {
+                                for spec in history {
+                                    if ui
+                                        .selectable_label(false, spec.summary())
+                                        .on_hover_text(spec.to_command())
+                                        .clicked()
+                                    {
+                                        self.spec = spec.clone();
+                                    }
+                                }
+                            });
+                        });
+                }
+            });
+
+        self.visible = open;
+        action
+    }
+}
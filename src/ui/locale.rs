@@ -0,0 +1,224 @@
+use crate::clock::{drift_size, DriftSize};
+use crate::locale::{is_locale_tag, search_locales, LocaleInfo, TIMEZONES};
+use crate::t;
+use egui::{Color32, RichText, Window};
+
+pub enum LocaleAction {
+    None,
+    Refresh,
+    SetLocale(String),
+    SetTimezone(String),
+    /// Measure how far the device clock is off
+    CheckClock,
+    /// Set the device clock from this computer's
+    SyncClock,
+}
+
+/// Locale, time zone and clock of the selected device, with searchable lists of locales and zones
+pub struct LocaleWindow {
+    visible: bool,
+    device: String,
+    info: Option<Result<LocaleInfo, String>>,
+    busy: bool,
+    /// Milliseconds the device clock is ahead of this computer's
+    drift: Option<Result<i64, String>>,
+    clock_busy: bool,
+    /// Search text for the locale list, also used as a tag of its own
+    locale_query: String,
+    timezone_query: String,
+}
+
+impl Default for LocaleWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LocaleWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            device: String::new(),
+            info: None,
+            busy: false,
+            drift: None,
+            clock_busy: false,
+            locale_query: String::new(),
+            timezone_query: String::new(),
+        }
+    }
+
+    pub fn open(&mut self, device: String) {
+        if device != self.device {
+            self.info = None;
+            self.drift = None;
+        }
+        self.visible = true;
+        self.device = device;
+    }
+
+    pub fn set_busy(&mut self) {
+        self.busy = true;
+    }
+
+    pub fn set_info(&mut self, info: Result<LocaleInfo, String>) {
+        self.info = Some(info);
+        self.busy = false;
+    }
+
+    pub fn set_clock_busy(&mut self) {
+        self.clock_busy = true;
+    }
+
+    pub fn set_drift(&mut self, drift: Result<i64, String>) {
+        self.drift = Some(drift);
+        self.clock_busy = false;
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> LocaleAction {
+        if !self.visible {
+            return LocaleAction::None;
+        }
+
+        let mut action = LocaleAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::TRANSLATE, t!("locale-title")))
+            .id(egui::Id::new("locale"))
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(&self.device).weak());
+                    if ui.add_enabled(!self.busy, egui::Button::new(t!("locale-refresh"))).clicked() {
+                        action = LocaleAction::Refresh;
+                    }
+                    if self.busy {
+                        ui.add(egui::Spinner::new().size(14.0));
+                    }
+                });
+                ui.separator();
+
+                let current = match &self.info {
+                    Some(Ok(info)) => info.clone(),
+                    Some(Err(e)) => {
+                        ui.label(RichText::new(e).color(Color32::RED));
+                        LocaleInfo::default()
+                    }
+                    None => return,
+                };
+
+                egui::Grid::new("locale_current").num_columns(2).spacing([16.0, 4.0]).show(ui, |ui| {
+                    ui.label(t!("locale-current"));
+                    ui.label(RichText::new(&current.locale).strong());
+                    ui.end_row();
+                    ui.label(t!("locale-timezone-current"));
+                    ui.label(RichText::new(&current.timezone).strong());
+                    ui.end_row();
+                });
+                if current.auto_timezone {
+                    ui.label(RichText::new(t!("locale-auto-timezone")).size(11.0).weak());
+                }
+                ui.horizontal(|ui| {
+                    ui.label(format!("{} {}", egui_phosphor::fill::CLOCK, t!("locale-clock")));
+                    match &self.drift {
+                        Some(Ok(drift)) => {
+                            let color = if drift.abs() <= crate::clock::TOLERANCE_MS {
+                                Color32::GREEN
+                            } else {
+                                Color32::ORANGE
+                            };
+                            ui.label(RichText::new(describe_drift(*drift)).color(color));
+                        }
+                        Some(Err(e)) => {
+                            ui.label(RichText::new(e).color(Color32::RED));
+                        }
+                        None => {}
+                    }
+                    if ui.add_enabled(!self.clock_busy, egui::Button::new(t!("locale-clock-check"))).clicked() {
+                        action = LocaleAction::CheckClock;
+                    }
+                    if ui
+                        .add_enabled(!self.clock_busy, egui::Button::new(t!("locale-clock-sync")))
+                        .on_hover_text(t!("locale-clock-sync-hint"))
+                        .clicked()
+                    {
+                        action = LocaleAction::SyncClock;
+                    }
+                    if self.clock_busy {
+                        ui.add(egui::Spinner::new().size(14.0));
+                    }
+                });
+                ui.add_space(6.0);
+
+                ui.columns(2, |columns| {
+                    let ui = &mut columns[0];
+                    ui.label(RichText::new(t!("locale-language")).strong());
+                    search_field(ui, &mut self.locale_query, t!("locale-search"));
+                    egui::ScrollArea::vertical().id_salt("locale_list").max_height(220.0).show(ui, |ui| {
+                        for (tag, name) in search_locales(&self.locale_query) {
+                            let label = format!("{}  {}", tag, name);
+                            if ui.selectable_label(tag == current.locale, label).clicked() && !self.busy {
+                                action = LocaleAction::SetLocale(tag.to_string());
+                            }
+                        }
+                    });
+                    // A tag that is not in the list can be typed into the search field
+                    let typed = self.locale_query.trim();
+                    if is_locale_tag(typed)
+                        && search_locales(typed).is_empty()
+                        && ui.add_enabled(!self.busy, egui::Button::new(t!("locale-use", tag = typed))).clicked()
+                    {
+                        action = LocaleAction::SetLocale(typed.to_string());
+                    }
+
+                    let ui = &mut columns[1];
+                    ui.label(RichText::new(t!("locale-timezone")).strong());
+                    search_field(ui, &mut self.timezone_query, t!("locale-search"));
+                    let query = self.timezone_query.trim().to_lowercase();
+                    egui::ScrollArea::vertical().id_salt("timezone_list").max_height(220.0).show(ui, |ui| {
+                        for timezone in TIMEZONES.iter().filter(|timezone| timezone.to_lowercase().contains(&query)) {
+                            let selected = *timezone == current.timezone;
+                            if ui.selectable_label(selected, *timezone).clicked() && !self.busy {
+                                action = LocaleAction::SetTimezone(timezone.to_string());
+                            }
+                        }
+                    });
+                    let typed = self.timezone_query.trim();
+                    if typed.contains('/')
+                        && !TIMEZONES.contains(&typed)
+                        && ui.add_enabled(!self.busy, egui::Button::new(t!("locale-use", tag = typed))).clicked()
+                    {
+                        action = LocaleAction::SetTimezone(typed.to_string());
+                    }
+                });
+            });
+
+        self.visible = open;
+        action
+    }
+}
+
+/// Drift as "3.2 s ahead", "1 min 5 s behind" or "in sync"
+fn describe_drift(drift_ms: i64) -> String {
+    let amount = match drift_size(drift_ms) {
+        DriftSize::InSync => return t!("locale-clock-in-sync"),
+        DriftSize::Seconds(seconds) => t!("locale-clock-seconds", seconds = format!("{:.1}", seconds)),
+        DriftSize::Minutes(minutes, seconds) => t!("locale-clock-minutes", minutes = minutes, seconds = seconds),
+        DriftSize::Hours(hours, minutes) => t!("locale-clock-hours", hours = hours, minutes = minutes),
+        DriftSize::Days(days, hours) => t!("locale-clock-days", days = days, hours = hours),
+    };
+    if drift_ms > 0 {
+        t!("locale-clock-ahead", amount = amount)
+    } else {
+        t!("locale-clock-behind", amount = amount)
+    }
+}
+
+fn search_field(ui: &mut egui::Ui, query: &mut String, hint: String) {
+    ui.horizontal(|ui| {
+        ui.label(egui_phosphor::fill::MAGNIFYING_GLASS);
+        ui.add(egui::TextEdit::singleline(query).hint_text(hint).desired_width(f32::INFINITY));
+    });
+}
@@ -0,0 +1,159 @@
+use crate::logging::{records_after, LogRecord};
+use crate::t;
+use egui::{Color32, RichText};
+use tracing::Level;
+
+/// Lines kept by the console itself; matches what the log buffer holds
+const MAX_LINES: usize = 5000;
+
+/// Collapsible panel along the bottom of the window showing DroidView's own log
+pub struct LogConsole {
+    pub visible: bool,
+    records: Vec<LogRecord>,
+    last_seq: Option<u64>,
+    min_level: Level,
+    filter: String,
+    follow: bool,
+    error: Option<String>,
+}
+
+impl Default for LogConsole {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LogConsole {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            records: Vec::new(),
+            last_seq: None,
+            min_level: Level::INFO,
+            filter: String::new(),
+            follow: true,
+            error: None,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    fn level_color(level: Level) -> Color32 {
+        match level {
+            Level::ERROR => Color32::RED,
+            Level::WARN => Color32::YELLOW,
+            Level::INFO => Color32::LIGHT_BLUE,
+            _ => Color32::GRAY,
+        }
+    }
+
+    fn matches(&self, record: &LogRecord) -> bool {
+        // `Level` orders ERROR as the smallest, so "at least as severe" is `<=`
+        record.level <= self.min_level
+            && (self.filter.is_empty() || record.message.to_lowercase().contains(&self.filter.to_lowercase()))
+    }
+
+    fn visible_text(&self) -> String {
+        self.records
+            .iter()
+            .filter(|record| self.matches(record))
+            .map(LogRecord::to_line)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn export(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .set_file_name(format!("droidview-{}.log", chrono::Local::now().format("%Y%m%d-%H%M%S")))
+            .add_filter(t!("log-filter-name"), &["log", "txt"])
+            .save_file()
+        else {
+            return;
+        };
+        self.error = std::fs::write(&path, self.visible_text())
+            .err()
+            .map(|e| t!("log-export-failed", path = path.display(), error = e));
+    }
+
+    /// Must be called before the central panel so the console spans the full window width
+    pub fn show(&mut self, ctx: &egui::Context) {
+        let new_records = records_after(self.last_seq);
+        if let Some(last) = new_records.last() {
+            self.last_seq = Some(last.seq);
+            self.records.extend(new_records);
+            if self.records.len() > MAX_LINES {
+                let excess = self.records.len() - MAX_LINES;
+                self.records.drain(..excess);
+            }
+        }
+
+        if !self.visible {
+            return;
+        }
+
+        egui::TopBottomPanel::bottom("log_console")
+            .resizable(true)
+            .default_height(180.0)
+            .min_height(80.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(format!("{} {}", egui_phosphor::fill::TERMINAL_WINDOW, t!("log-title"))).strong());
+                    ui.separator();
+                    egui::ComboBox::from_id_salt("log_console_level")
+                        .selected_text(self.min_level.as_str())
+                        .width(70.0)
+                        .show_ui(ui, |ui| {
+                            for level in [Level::ERROR, Level::WARN, Level::INFO, Level::DEBUG, Level::TRACE] {
+                                ui.selectable_value(&mut self.min_level, level, level.as_str());
+                            }
+                        });
+                    ui.add(egui::TextEdit::singleline(&mut self.filter).hint_text(t!("log-filter")).desired_width(160.0));
+                    ui.checkbox(&mut self.follow, t!("log-follow"));
+                    if ui.button(format!("{} {}", egui_phosphor::fill::COPY, t!("log-copy"))).clicked() {
+                        ui.ctx().copy_text(self.visible_text());
+                    }
+                    if ui.button(format!("{} {}", egui_phosphor::fill::FLOPPY_DISK, t!("log-export"))).clicked() {
+                        self.export();
+                    }
+                    if ui.button(t!("log-clear")).clicked() {
+                        self.records.clear();
+                    }
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.small_button(egui_phosphor::fill::X).on_hover_text(t!("log-hide")).clicked() {
+                            self.visible = false;
+                        }
+                    });
+                });
+                if let Some(error) = &self.error {
+                    ui.label(RichText::new(error).color(Color32::RED));
+                }
+                ui.separator();
+
+                let rows: Vec<&LogRecord> = self.records.iter().filter(|record| self.matches(record)).collect();
+                let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+                egui::ScrollArea::vertical()
+                    .auto_shrink([false, false])
+                    .stick_to_bottom(self.follow)
+                    .show_rows(ui, row_height, rows.len(), |ui, range| {
+                        for record in &rows[range] {
+                            ui.horizontal(|ui| {
+                                ui.label(
+                                    RichText::new(record.at.format("%H:%M:%S%.3f").to_string())
+                                        .monospace()
+                                        .color(Color32::GRAY),
+                                );
+                                ui.label(
+                                    RichText::new(format!("{:>5}", record.level.as_str()))
+                                        .monospace()
+                                        .color(Self::level_color(record.level)),
+                                );
+                                ui.label(RichText::new(&record.message).monospace())
+                                    .on_hover_text(&record.target);
+                            });
+                        }
+                    });
+            });
+    }
+}
@@ -0,0 +1,342 @@
+use crate::config::{InputMacro, MacroInput, MacroStep};
+use crate::device::Device;
+use crate::macros::{describe, COMMON_KEYS, MAX_SPEED, MIN_SPEED};
+use crate::t;
+use egui::{Color32, RichText, Window};
+use std::time::Instant;
+
+pub enum MacroAction {
+    None,
+    /// Send one input to the device now; recorded too while recording
+    Send { serial: String, input: MacroInput },
+    Play { serial: String, name: String, steps: Vec<MacroStep>, speed: f32 },
+    Stop,
+    /// The edited list of macros to store in the config
+    Save(Vec<InputMacro>),
+}
+
+struct Recording {
+    name: String,
+    steps: Vec<MacroStep>,
+    last: Option<Instant>,
+}
+
+/// Saved input macros, a recorder and playback on any connected device
+pub struct MacroWindow {
+    visible: bool,
+    macros: Vec<InputMacro>,
+    device: Option<String>,
+    speed: f32,
+    recording: Option<Recording>,
+    /// Name of the macro being played and the step it is at
+    playing: Option<(String, usize)>,
+    error: Option<String>,
+    tap: [u32; 2],
+    swipe: [u32; 4],
+    swipe_ms: u32,
+    key: String,
+    text: String,
+}
+
+impl Default for MacroWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MacroWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            macros: Vec::new(),
+            device: None,
+            speed: 1.0,
+            recording: None,
+            playing: None,
+            error: None,
+            tap: [540, 1200],
+            swipe: [540, 1600, 540, 600],
+            swipe_ms: 300,
+            key: COMMON_KEYS[0].0.to_string(),
+            text: String::new(),
+        }
+    }
+
+    /// Open on `device`, or on the device chosen last time
+    pub fn open(&mut self, macros: Vec<InputMacro>, device: Option<String>) {
+        if !self.visible {
+            self.macros = macros;
+        }
+        if device.is_some() && self.playing.is_none() {
+            self.device = device;
+        }
+        self.visible = true;
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// Add an input sent elsewhere, e.g. from the swipe panel, to the recording
+    pub fn record(&mut self, input: MacroInput) {
+        let Some(recording) = &mut self.recording else {
+            return;
+        };
+        let now = Instant::now();
+        let delay_ms = recording.last.map_or(0, |last| now.duration_since(last).as_millis() as u64);
+        recording.last = Some(now);
+        recording.steps.push(MacroStep { delay_ms, input });
+    }
+
+    pub fn set_step(&mut self, step: usize) {
+        if let Some((_, current)) = &mut self.playing {
+            *current = step;
+        }
+    }
+
+    pub fn finish(&mut self, result: Result<(), String>) {
+        self.playing = None;
+        self.error = result.err();
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, devices: &[Device]) -> MacroAction {
+        if !self.visible {
+            return MacroAction::None;
+        }
+
+        let mut action = MacroAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::RECORD, t!("macros-title")))
+            .id(egui::Id::new("macros"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(520.0, 460.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(t!("macros-device"));
+                    let selected_text = self.device.clone().unwrap_or_else(|| t!("macros-choose-device"));
+                    ui.add_enabled_ui(self.playing.is_none(), |ui| {
+                        egui::ComboBox::from_id_salt("macro_device")
+                            .selected_text(selected_text)
+                            .show_ui(ui, |ui| {
+                                for device in devices.iter().filter(|device| device.is_usable()) {
+                                    let chosen = self.device.as_deref() == Some(device.identifier.as_str());
+                                    let label = format!("{} ({})", device.model, device.identifier);
+                                    if ui.selectable_label(chosen, label).clicked() {
+                                        self.device = Some(device.identifier.clone());
+                                    }
+                                }
+                            });
+                    });
+                    ui.label(t!("macros-speed"));
+                    ui.add(
+                        egui::Slider::new(&mut self.speed, MIN_SPEED..=MAX_SPEED)
+                            .logarithmic(true)
+                            .suffix("×")
+                            .fixed_decimals(2),
+                    );
+                });
+                ui.separator();
+
+                self.show_saved(ui, &mut action);
+                ui.separator();
+
+                if self.recording.is_some() {
+                    self.show_recorder(ui, &mut action);
+                } else if ui
+                    .add_enabled(
+                        self.playing.is_none(),
+                        egui::Button::new(format!("{} {}", egui_phosphor::fill::RECORD, t!("macros-record"))),
+                    )
+                    .clicked()
+                {
+                    self.recording = Some(Recording {
+                        name: String::new(),
+                        steps: Vec::new(),
+                        last: None,
+                    });
+                    self.error = None;
+                }
+
+                if let Some(error) = &self.error {
+                    ui.label(RichText::new(error).color(Color32::RED));
+                }
+            });
+
+        self.visible = open;
+        action
+    }
+
+    fn show_saved(&mut self, ui: &mut egui::Ui, action: &mut MacroAction) {
+        if self.macros.is_empty() {
+            ui.label(RichText::new(t!("macros-empty")).color(Color32::GRAY));
+        }
+        let mut remove = None;
+        for (index, saved) in self.macros.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(&saved.name).strong());
+                ui.label(RichText::new(t!("macros-steps", count = saved.steps.len())).weak());
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    match &self.playing {
+                        Some((name, step)) if *name == saved.name => {
+                            if ui.button(format!("{} {}", egui_phosphor::fill::STOP, t!("macros-stop"))).clicked() {
+                                *action = MacroAction::Stop;
+                            }
+                            ui.label(format!("{}/{}", step + 1, saved.steps.len()));
+                            ui.spinner();
+                        }
+                        _ => {
+                            let idle = self.playing.is_none() && self.recording.is_none();
+                            if ui
+                                .add_enabled(idle, egui::Button::new(egui_phosphor::fill::TRASH).small())
+                                .on_hover_text(t!("macros-delete"))
+                                .clicked()
+                            {
+                                remove = Some(index);
+                            }
+                            let ready = idle && self.device.is_some() && !saved.steps.is_empty();
+                            if ui
+                                .add_enabled(
+                                    ready,
+                                    egui::Button::new(format!("{} {}", egui_phosphor::fill::PLAY, t!("macros-play"))),
+                                )
+                                .clicked()
+                                && let Some(serial) = self.device.clone()
+                            {
+                                self.playing = Some((saved.name.clone(), 0));
+                                self.error = None;
+                                *action = MacroAction::Play {
+                                    serial,
+                                    name: saved.name.clone(),
+                                    steps: saved.steps.clone(),
+                                    speed: self.speed,
+                                };
+                            }
+                        }
+                    }
+                });
+            });
+        }
+        if let Some(index) = remove {
+            self.macros.remove(index);
+            *action = MacroAction::Save(self.macros.clone());
+        }
+    }
+
+    fn show_recorder(&mut self, ui: &mut egui::Ui, action: &mut MacroAction) {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(egui_phosphor::fill::RECORD).color(Color32::RED));
+            ui.label(RichText::new(t!("macros-recording")).strong());
+        });
+        ui.label(RichText::new(t!("macros-recording-hint")).size(11.0).color(Color32::GRAY));
+
+        let mut send = None;
+        ui.add_enabled_ui(self.device.is_some(), |ui| {
+            egui::Grid::new("macro_inputs").num_columns(2).spacing([8.0, 6.0]).show(ui, |ui| {
+                ui.label(t!("macros-tap"));
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut self.tap[0]).prefix("x "));
+                    ui.add(egui::DragValue::new(&mut self.tap[1]).prefix("y "));
+                    if ui.button(t!("macros-send")).clicked() {
+                        send = Some(MacroInput::Tap { x: self.tap[0], y: self.tap[1] });
+                    }
+                });
+                ui.end_row();
+
+                ui.label(t!("macros-swipe"));
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut self.swipe[0]).prefix("x "));
+                    ui.add(egui::DragValue::new(&mut self.swipe[1]).prefix("y "));
+                    ui.label("→");
+                    ui.add(egui::DragValue::new(&mut self.swipe[2]).prefix("x "));
+                    ui.add(egui::DragValue::new(&mut self.swipe[3]).prefix("y "));
+                    ui.add(egui::DragValue::new(&mut self.swipe_ms).range(1..=10_000).suffix(" ms"));
+                    if ui.button(t!("macros-send")).clicked() {
+                        let [x1, y1, x2, y2] = self.swipe;
+                        send = Some(MacroInput::Swipe { x1, y1, x2, y2, duration_ms: self.swipe_ms });
+                    }
+                });
+                ui.end_row();
+
+                ui.label(t!("macros-key"));
+                ui.horizontal(|ui| {
+                    egui::ComboBox::from_id_salt("macro_key")
+                        .selected_text(self.key.trim_start_matches("KEYCODE_"))
+                        .show_ui(ui, |ui| {
+                            for (code, label) in COMMON_KEYS {
+                                ui.selectable_value(&mut self.key, code.to_string(), label);
+                            }
+                        });
+                    ui.add(egui::TextEdit::singleline(&mut self.key).desired_width(140.0));
+                    if ui.add_enabled(!self.key.trim().is_empty(), egui::Button::new(t!("macros-send"))).clicked() {
+                        send = Some(MacroInput::Key { code: self.key.trim().to_string() });
+                    }
+                });
+                ui.end_row();
+
+                ui.label(t!("macros-text"));
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut self.text).desired_width(220.0));
+                    if ui.add_enabled(!self.text.is_empty(), egui::Button::new(t!("macros-send"))).clicked() {
+                        send = Some(MacroInput::Text { text: std::mem::take(&mut self.text) });
+                    }
+                });
+                ui.end_row();
+            });
+        });
+        if let (Some(input), Some(serial)) = (send, self.device.clone()) {
+            self.record(input.clone());
+            *action = MacroAction::Send { serial, input };
+        }
+
+        let Some(recording) = &mut self.recording else {
+            return;
+        };
+        ui.separator();
+        egui::ScrollArea::vertical().max_height(140.0).id_salt("macro_steps").show(ui, |ui| {
+            if recording.steps.is_empty() {
+                ui.label(RichText::new(t!("macros-no-steps")).color(Color32::GRAY));
+            }
+            let mut remove = None;
+            for (index, step) in recording.steps.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(format!("+{:.1}s", step.delay_ms as f32 / 1000.0)).weak().monospace());
+                    ui.label(describe(&step.input));
+                    if ui.small_button(egui_phosphor::fill::X).clicked() {
+                        remove = Some(index);
+                    }
+                });
+            }
+            if let Some(index) = remove {
+                recording.steps.remove(index);
+            }
+        });
+
+        let mut done = false;
+        ui.horizontal(|ui| {
+            ui.label(t!("macros-name"));
+            ui.text_edit_singleline(&mut recording.name);
+            let name = recording.name.trim().to_string();
+            let can_save = !name.is_empty() && !recording.steps.is_empty();
+            if ui.add_enabled(can_save, egui::Button::new(t!("macros-save"))).clicked() {
+                let steps = std::mem::take(&mut recording.steps);
+                // Saving under an existing name replaces that macro
+                match self.macros.iter_mut().find(|saved| saved.name == name) {
+                    Some(saved) => saved.steps = steps,
+                    None => self.macros.push(InputMacro { name, steps }),
+                }
+                *action = MacroAction::Save(self.macros.clone());
+                done = true;
+            }
+            if ui.button(t!("macros-discard")).clicked() {
+                done = true;
+            }
+        });
+        if done {
+            self.recording = None;
+        }
+    }
+}
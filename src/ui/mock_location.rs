@@ -0,0 +1,260 @@
+use crate::mock_location::{
+    parse_gpx, MockLocationInfo, Position, RoutePoint, HELPER_APK, HELPER_PACKAGE, MAX_SPEED, MIN_SPEED, PRESETS,
+};
+use crate::packages::is_package_name;
+use crate::t;
+use egui::{Color32, RichText, Window};
+
+pub enum MockLocationAction {
+    None,
+    Refresh,
+    /// Install the helper app if needed and make it the mock location app
+    PrepareHelper,
+    SetProvider(String),
+    Set(Position),
+    Stop,
+    PlayRoute { route: Vec<RoutePoint>, speed: f32 },
+    StopRoute,
+}
+
+/// A GPX route loaded for playback
+struct Route {
+    name: String,
+    points: Vec<RoutePoint>,
+}
+
+/// Mock location provider, coordinates and GPX route playback for the selected device
+pub struct MockLocationWindow {
+    visible: bool,
+    device: String,
+    info: Option<Result<MockLocationInfo, String>>,
+    busy: bool,
+    provider: String,
+    latitude: f64,
+    longitude: f64,
+    route: Option<Route>,
+    route_error: Option<String>,
+    speed: f32,
+    /// Index of the point last set while a route plays
+    playing: Option<usize>,
+}
+
+impl Default for MockLocationWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockLocationWindow {
+    pub fn new() -> Self {
+        let (_, latitude, longitude) = PRESETS[0];
+        Self {
+            visible: false,
+            device: String::new(),
+            info: None,
+            busy: false,
+            provider: String::new(),
+            latitude,
+            longitude,
+            route: None,
+            route_error: None,
+            speed: 1.0,
+            playing: None,
+        }
+    }
+
+    pub fn open(&mut self, device: String) {
+        if device != self.device {
+            self.info = None;
+        }
+        self.visible = true;
+        self.device = device;
+    }
+
+    pub fn set_busy(&mut self) {
+        self.busy = true;
+    }
+
+    pub fn set_info(&mut self, info: Result<MockLocationInfo, String>) {
+        self.info = Some(info);
+        self.busy = false;
+    }
+
+    pub fn set_playing(&mut self, point: Option<usize>) {
+        self.playing = point;
+    }
+
+    /// Follow the route being played on the map-free coordinate fields
+    pub fn set_point(&mut self, index: usize) {
+        self.playing = Some(index);
+        if let Some(point) = self.route.as_ref().and_then(|route| route.points.get(index)) {
+            (self.latitude, self.longitude) = (point.position.latitude, point.position.longitude);
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> MockLocationAction {
+        if !self.visible {
+            return MockLocationAction::None;
+        }
+
+        let mut action = MockLocationAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::MAP_PIN, t!("mock-location-title")))
+            .id(egui::Id::new("mock_location"))
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(&self.device).weak());
+                    if ui.add_enabled(!self.busy, egui::Button::new(t!("mock-location-refresh"))).clicked() {
+                        action = MockLocationAction::Refresh;
+                    }
+                    if self.busy {
+                        ui.add(egui::Spinner::new().size(14.0));
+                    }
+                });
+                ui.separator();
+
+                let info = match &self.info {
+                    Some(Ok(info)) => info.clone(),
+                    Some(Err(e)) => {
+                        ui.label(RichText::new(e).color(Color32::RED));
+                        MockLocationInfo::default()
+                    }
+                    None => return,
+                };
+
+                ui.label(RichText::new(t!("mock-location-provider")).strong());
+                if info.emulator {
+                    ui.label(RichText::new(t!("mock-location-emulator")).size(11.0).weak());
+                } else {
+                    let ready = info.helper_installed && info.providers.iter().any(|p| p == HELPER_PACKAGE);
+                    ui.horizontal(|ui| {
+                        let status = if ready {
+                            RichText::new(t!("mock-location-helper-ready")).color(Color32::GREEN)
+                        } else {
+                            RichText::new(t!("mock-location-helper-missing")).color(Color32::ORANGE)
+                        };
+                        ui.label(status);
+                        if !ready
+                            && ui
+                                .add_enabled(!self.busy, egui::Button::new(t!("mock-location-prepare")))
+                                .on_hover_text(t!("mock-location-prepare-hint", apk = HELPER_APK))
+                                .clicked()
+                        {
+                            action = MockLocationAction::PrepareHelper;
+                        }
+                    });
+                }
+                if !info.providers.is_empty() {
+                    ui.label(t!("mock-location-providers", packages = info.providers.join(", ")));
+                }
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.provider)
+                            .hint_text("com.example.mockgps")
+                            .desired_width(220.0),
+                    );
+                    let provider = self.provider.trim();
+                    let allowed = !self.busy && is_package_name(provider);
+                    if ui.add_enabled(allowed, egui::Button::new(t!("mock-location-use-provider"))).clicked() {
+                        action = MockLocationAction::SetProvider(provider.to_string());
+                    }
+                });
+                ui.add_space(6.0);
+
+                let playing = self.playing.is_some();
+                ui.label(RichText::new(t!("mock-location-position")).strong());
+                ui.horizontal_wrapped(|ui| {
+                    for (name, latitude, longitude) in PRESETS {
+                        let selected = (self.latitude, self.longitude) == (latitude, longitude);
+                        if ui.add_enabled(!playing, egui::Button::selectable(selected, name)).clicked() {
+                            (self.latitude, self.longitude) = (latitude, longitude);
+                        }
+                    }
+                });
+                ui.add_enabled_ui(!playing, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(t!("mock-location-latitude"));
+                        let latitude = egui::DragValue::new(&mut self.latitude).range(-90.0..=90.0);
+                        ui.add(latitude.speed(0.0001).max_decimals(6));
+                        ui.label(t!("mock-location-longitude"));
+                        let longitude = egui::DragValue::new(&mut self.longitude).range(-180.0..=180.0);
+                        ui.add(longitude.speed(0.0001).max_decimals(6));
+                    });
+                });
+                ui.horizontal(|ui| {
+                    let position = Position { latitude: self.latitude, longitude: self.longitude };
+                    if ui.add_enabled(!self.busy && !playing, egui::Button::new(t!("mock-location-set"))).clicked() {
+                        action = MockLocationAction::Set(position);
+                    }
+                    if ui.add_enabled(!self.busy && !playing, egui::Button::new(t!("mock-location-stop"))).clicked() {
+                        action = MockLocationAction::Stop;
+                    }
+                });
+                ui.add_space(6.0);
+
+                ui.label(RichText::new(t!("mock-location-route")).strong());
+                ui.horizontal(|ui| {
+                    let pick = format!("{} {}", egui_phosphor::fill::FOLDER_OPEN, t!("mock-location-open-gpx"));
+                    if ui.add_enabled(!playing, egui::Button::new(pick)).clicked()
+                        && let Some(path) = rfd::FileDialog::new().add_filter("GPX", &["gpx"]).pick_file()
+                    {
+                        let points = std::fs::read_to_string(&path)
+                            .map_err(anyhow::Error::from)
+                            .and_then(|text| parse_gpx(&text));
+                        match points {
+                            Ok(points) => {
+                                let name = path.file_name().map(|name| name.to_string_lossy().to_string());
+                                self.route = Some(Route { name: name.unwrap_or_default(), points });
+                                self.route_error = None;
+                            }
+                            Err(e) => self.route_error = Some(e.to_string()),
+                        }
+                    }
+                    if let Some(route) = &self.route {
+                        let count = route.points.len();
+                        let summary = t!("mock-location-route-points", name = route.name.as_str(), count = count);
+                        ui.label(RichText::new(summary).monospace());
+                    }
+                });
+                if let Some(e) = &self.route_error {
+                    ui.label(RichText::new(e).color(Color32::RED));
+                }
+                if let Some(route) = &self.route {
+                    ui.horizontal(|ui| {
+                        ui.label(t!("mock-location-speed"));
+                        ui.add_enabled(
+                            !playing,
+                            egui::Slider::new(&mut self.speed, MIN_SPEED..=MAX_SPEED)
+                                .logarithmic(true)
+                                .suffix("×")
+                                .fixed_decimals(1),
+                        );
+                    });
+                    ui.horizontal(|ui| match self.playing {
+                        Some(index) => {
+                            let stop = format!("{} {}", egui_phosphor::fill::STOP, t!("mock-location-stop-route"));
+                            if ui.button(stop).clicked() {
+                                action = MockLocationAction::StopRoute;
+                            }
+                            ui.add(egui::Spinner::new().size(14.0));
+                            ui.label(t!("mock-location-route-progress", point = index + 1, count = route.points.len()));
+                        }
+                        None => {
+                            let play = format!("{} {}", egui_phosphor::fill::PLAY, t!("mock-location-play"));
+                            if ui.add_enabled(!self.busy, egui::Button::new(play)).clicked() {
+                                let route = route.points.clone();
+                                action = MockLocationAction::PlayRoute { route, speed: self.speed };
+                            }
+                        }
+                    });
+                }
+            });
+
+        self.visible = open;
+        action
+    }
+}
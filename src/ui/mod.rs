@@ -1,10 +1,104 @@
+pub mod activity_viewer;
+pub mod adb_keys;
+pub mod app_details;
+pub mod app_filter;
+pub mod automation;
+pub mod benchmark;
+pub mod bootloader_wizard;
+pub mod capability_wizard;
+pub mod clipboard_history;
+pub mod color_picker;
+pub mod command_palette;
+pub mod device_controls;
 pub mod device_list;
+pub mod dock;
+pub mod file_push;
+pub mod frame_profiler;
+pub mod gallery;
+pub mod gesture_designer;
+pub mod health_banner;
+pub mod help;
+pub mod hierarchy;
+pub mod intent_sender;
+pub mod locale;
+pub mod log_console;
+pub mod macros;
+pub mod mock_location;
+pub mod native_preview;
 pub mod panels;
+pub mod perfetto;
+pub mod permissions;
+pub mod pip;
+pub mod plugins;
+pub mod recording_library;
+pub mod remote_assist;
+pub mod resolution;
+pub mod screenshot_diff;
+pub mod scripts;
 pub mod settings;
+pub mod share;
+pub mod shell_command;
+pub mod shell_console;
+pub mod shortcuts;
+pub mod sideload;
+pub mod standby;
+pub mod stream;
+pub mod tap_picker;
+pub mod theme;
+pub mod toasts;
+pub mod tunnels;
+pub mod update_notice;
+pub mod wifi;
 
-pub use device_list::DeviceList;
+pub use activity_viewer::{ActivityViewer, ActivityViewerAction};
+pub use adb_keys::{AdbKeysAction, AdbKeysWindow};
+pub use app_details::{AppDetailsAction, AppDetailsWindow};
+pub use app_filter::{app_context_menu, app_details, user_selector, AppAction, AppListFilter, AppSort};
+pub use automation::{AutomationWindow, AutomationWindowAction};
+pub use benchmark::{BenchmarkAction, BenchmarkWindow};
+pub use bootloader_wizard::{BootloaderAction, BootloaderWizard};
+pub use capability_wizard::{CapabilityAction, CapabilityWizard};
+pub use clipboard_history::ClipboardHistoryWindow;
+pub use color_picker::{ColorPickerAction, ColorPickerWindow};
+pub use command_palette::{CommandPalette, PaletteEntry};
+pub use device_controls::{battery_simulation_banner, DeviceControlsAction, DeviceControlsPanel};
+pub use device_list::{DeviceList, DeviceListAction, THUMBNAIL_EDGE, THUMBNAIL_INTERVAL};
+pub use file_push::{FilePushAction, FilePushDialog, PushStatus};
+pub use frame_profiler::{FrameProfilerAction, FrameProfilerWindow};
+pub use gallery::GalleryWindow;
+pub use gesture_designer::{GestureDesigner, GestureDesignerAction};
+pub use health_banner::{HealthBanner, HealthBannerAction, Tool};
+pub use help::{help_button, HelpTopic, HelpWindow};
+pub use hierarchy::{HierarchyAction, HierarchyWindow, INSPECTOR_EDGE};
+pub use intent_sender::{IntentSenderAction, IntentSenderWindow};
+pub use locale::{LocaleAction, LocaleWindow};
+pub use log_console::LogConsole;
+pub use macros::{MacroAction, MacroWindow};
+pub use mock_location::{MockLocationAction, MockLocationWindow};
+pub use native_preview::{NativePreviewAction, NativePreviewWindow};
 pub use panels::{
     BottomPanel, BottomPanelAction, SwipeAction, SwipePanel, ToolkitAction, ToolkitPanel, WirelessAdbAction,
     WirelessAdbPanel,
 };
+pub use perfetto::{PerfettoAction, PerfettoWindow};
+pub use permissions::{PermissionsAction, PermissionsWindow};
+pub use pip::{PipAction, PipPane, PIP_EDGE};
+pub use recording_library::{RecordingLibraryAction, RecordingLibraryWindow};
+pub use remote_assist::{RemoteAssistAction, RemoteAssistWizard};
+pub use resolution::{ResolutionAction, ResolutionWindow};
+pub use screenshot_diff::{ScreenshotDiffAction, ScreenshotDiffWindow};
+pub use scripts::{ScriptsAction, ScriptsWindow};
+pub use plugins::{PluginAction, PluginsPanel};
 pub use settings::SettingsWindow;
+pub use share::{ShareAction, ShareWindow};
+pub use shell_command::{ShellCommandAction, ShellCommandWindow};
+pub use shell_console::{ShellConsoleAction, ShellConsoleWindow};
+pub use shortcuts::{ShortcutsAction, ShortcutsWindow};
+pub use sideload::{SideloadAction, SideloadWindow};
+pub use standby::{StandbyAction, StandbyWindow};
+pub use stream::{StreamAction, StreamWindow};
+pub use tap_picker::{TapPicker, TapPickerAction, PICKER_EDGE};
+pub use toasts::{Severity, Toasts};
+pub use tunnels::{TunnelAction, TunnelsWindow};
+pub use update_notice::{UpdateNotice, UpdateNoticeAction};
+pub use wifi::{WifiAction, WifiWindow};
@@ -0,0 +1,127 @@
+use crate::t;
+use egui::{Color32, RichText, TextureHandle, Window};
+use std::time::Instant;
+
+pub enum NativePreviewAction {
+    None,
+    Stop,
+}
+
+/// The device screen decoded by DroidView itself, shown while a preview session runs
+pub struct NativePreviewWindow {
+    visible: bool,
+    device: String,
+    starting: bool,
+    texture: Option<TextureHandle>,
+    /// Frame count and when it was read, for the frame rate
+    sample: Option<(u64, Instant)>,
+    fps: f32,
+}
+
+impl Default for NativePreviewWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NativePreviewWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            device: String::new(),
+            starting: false,
+            texture: None,
+            sample: None,
+            fps: 0.0,
+        }
+    }
+
+    pub fn set_starting(&mut self, device: String) {
+        self.visible = true;
+        self.starting = true;
+        self.device = device;
+        self.texture = None;
+        self.sample = None;
+        self.fps = 0.0;
+    }
+
+    pub fn set_started(&mut self, device_name: &str) {
+        self.starting = false;
+        self.device = format!("{} ({})", device_name, self.device);
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.visible
+    }
+
+    pub fn close(&mut self) {
+        self.visible = false;
+        self.starting = false;
+        self.texture = None;
+    }
+
+    /// Show `frame` from now on, and update the frame rate from the count decoded so far
+    pub fn set_frame(&mut self, ctx: &egui::Context, frame: Option<image::RgbaImage>, count: u64) {
+        if let Some(frame) = frame {
+            let size = [frame.width() as usize, frame.height() as usize];
+            let image = egui::ColorImage::from_rgba_unmultiplied(size, frame.as_raw());
+            match &mut self.texture {
+                Some(texture) => texture.set(image, egui::TextureOptions::LINEAR),
+                None => self.texture = Some(ctx.load_texture("native_preview", image, egui::TextureOptions::LINEAR)),
+            }
+        }
+        let now = Instant::now();
+        match self.sample {
+            Some((previous, since)) if now.duration_since(since).as_secs_f32() >= 1.0 => {
+                self.fps = (count - previous) as f32 / now.duration_since(since).as_secs_f32();
+                self.sample = Some((count, now));
+            }
+            None => self.sample = Some((count, now)),
+            _ => {}
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> NativePreviewAction {
+        if !self.visible {
+            return NativePreviewAction::None;
+        }
+
+        let mut action = NativePreviewAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::MONITOR_PLAY, t!("native-preview-title")))
+            .id(egui::Id::new("native_preview"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(420.0, 820.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(&self.device).weak());
+                    if self.starting {
+                        ui.add(egui::Spinner::new().size(14.0));
+                        ui.label(t!("native-preview-starting"));
+                    } else {
+                        ui.label(RichText::new(format!("{:.0} fps", self.fps)).monospace());
+                        if ui.button(format!("{} {}", egui_phosphor::fill::STOP, t!("native-preview-stop"))).clicked() {
+                            action = NativePreviewAction::Stop;
+                        }
+                    }
+                });
+                ui.label(RichText::new(t!("native-preview-hint")).size(11.0).color(Color32::GRAY));
+                ui.separator();
+
+                if let Some(texture) = &self.texture {
+                    let size = texture.size_vec2();
+                    let available = ui.available_size();
+                    let scale = (available.x / size.x).min(available.y / size.y);
+                    ui.centered_and_justified(|ui| ui.add(egui::Image::new(texture).fit_to_exact_size(size * scale)));
+                }
+            });
+
+        if !open {
+            action = NativePreviewAction::Stop;
+        }
+        action
+    }
+}
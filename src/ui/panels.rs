@@ -1,27 +1,249 @@
+use crate::reboot::RebootTarget;
+use crate::t;
 use egui::Ui;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BottomPanelAction {
     None,
     RefreshDevices,
     RestartAdb,
     OpenSettings,
+    OpenGallery,
+    OpenRecordings,
+    ToggleLogs,
+    ShowShortcuts,
+    OpenScripts,
+    /// Run the pinned script at this index of the list passed to `show`
+    RunScript(usize),
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ToolkitAction {
     None,
     Screenshot,
+    CopyScreenshot,
+    LongScreenshot,
+    ScreenshotDiff,
+    NativePreview,
+    LivePreview,
     RecordScreen,
     InstallApk,
     OpenShell,
+    ShellConsole,
+    ShellCommand,
+    SendIntent,
+    Capabilities,
+    AdbKeys,
+    Automation,
+    Macros,
+    TapPicker,
+    Activities,
+    ClipboardHistory,
+    ShareView,
+    Benchmark,
+    FrameTiming,
+    PerfettoTrace,
+    Bugreport,
     ShowImei,
     DisplayInfo,
+    Resolution,
+    Locale,
+    Standby,
+    MockLocation,
+    LayoutInspector,
+    Ocr,
+    ColorPicker,
     BatteryInfo,
+    WifiInfo,
+    DeviceReport,
+    ReverseTether,
     UninstallApp,
     DisableApp,
     Reboot,
     Shutdown,
     RebootRecovery,
     RebootBootloader,
+    RebootTo(RebootTarget),
+    BootloaderLock,
+    Sideload,
+}
+
+impl ToolkitAction {
+    pub const ALL: [ToolkitAction; 49] = [
+        ToolkitAction::Screenshot,
+        ToolkitAction::CopyScreenshot,
+        ToolkitAction::LongScreenshot,
+        ToolkitAction::ScreenshotDiff,
+        ToolkitAction::NativePreview,
+        ToolkitAction::LivePreview,
+        ToolkitAction::RecordScreen,
+        ToolkitAction::InstallApk,
+        ToolkitAction::OpenShell,
+        ToolkitAction::ShellConsole,
+        ToolkitAction::ShellCommand,
+        ToolkitAction::SendIntent,
+        ToolkitAction::Capabilities,
+        ToolkitAction::AdbKeys,
+        ToolkitAction::Automation,
+        ToolkitAction::Macros,
+        ToolkitAction::TapPicker,
+        ToolkitAction::Activities,
+        ToolkitAction::ClipboardHistory,
+        ToolkitAction::ShareView,
+        ToolkitAction::Benchmark,
+        ToolkitAction::FrameTiming,
+        ToolkitAction::PerfettoTrace,
+        ToolkitAction::Bugreport,
+        ToolkitAction::ShowImei,
+        ToolkitAction::DisplayInfo,
+        ToolkitAction::Resolution,
+        ToolkitAction::Locale,
+        ToolkitAction::Standby,
+        ToolkitAction::MockLocation,
+        ToolkitAction::LayoutInspector,
+        ToolkitAction::Ocr,
+        ToolkitAction::ColorPicker,
+        ToolkitAction::BatteryInfo,
+        ToolkitAction::WifiInfo,
+        ToolkitAction::DeviceReport,
+        ToolkitAction::ReverseTether,
+        ToolkitAction::UninstallApp,
+        ToolkitAction::DisableApp,
+        ToolkitAction::Reboot,
+        ToolkitAction::Shutdown,
+        ToolkitAction::RebootRecovery,
+        ToolkitAction::RebootBootloader,
+        ToolkitAction::RebootTo(RebootTarget::Fastbootd),
+        ToolkitAction::RebootTo(RebootTarget::SafeMode),
+        ToolkitAction::RebootTo(RebootTarget::Sideload),
+        ToolkitAction::RebootTo(RebootTarget::Edl),
+        ToolkitAction::BootloaderLock,
+        ToolkitAction::Sideload,
+    ];
+
+    pub fn label(self) -> String {
+        match self {
+            ToolkitAction::None => String::new(),
+            ToolkitAction::Screenshot => t!("toolkit-screenshot"),
+            ToolkitAction::CopyScreenshot => t!("toolkit-copy-screenshot"),
+            ToolkitAction::LongScreenshot => t!("toolkit-long-screenshot"),
+            ToolkitAction::ScreenshotDiff => t!("toolkit-screenshot-diff"),
+            ToolkitAction::NativePreview => t!("toolkit-native-preview"),
+            ToolkitAction::LivePreview => t!("toolkit-live-preview"),
+            ToolkitAction::RecordScreen => t!("toolkit-record-screen"),
+            ToolkitAction::InstallApk => t!("toolkit-install-apk"),
+            ToolkitAction::OpenShell => t!("toolkit-open-shell"),
+            ToolkitAction::ShellConsole => t!("toolkit-shell-console"),
+            ToolkitAction::ShellCommand => t!("toolkit-shell-command"),
+            ToolkitAction::SendIntent => t!("toolkit-send-intent"),
+            ToolkitAction::Capabilities => t!("toolkit-capabilities"),
+            ToolkitAction::AdbKeys => t!("toolkit-adb-keys"),
+            ToolkitAction::Automation => t!("toolkit-automation"),
+            ToolkitAction::Macros => t!("toolkit-macros"),
+            ToolkitAction::TapPicker => t!("toolkit-tap-picker"),
+            ToolkitAction::Activities => t!("toolkit-activities"),
+            ToolkitAction::ClipboardHistory => t!("toolkit-clipboard-history"),
+            ToolkitAction::ShareView => t!("toolkit-share-view"),
+            ToolkitAction::Benchmark => t!("toolkit-benchmark"),
+            ToolkitAction::FrameTiming => t!("toolkit-frame-timing"),
+            ToolkitAction::PerfettoTrace => t!("toolkit-perfetto"),
+            ToolkitAction::Bugreport => t!("toolkit-bugreport"),
+            ToolkitAction::ShowImei => t!("toolkit-show-imei"),
+            ToolkitAction::DisplayInfo => t!("toolkit-display-info"),
+            ToolkitAction::Resolution => t!("toolkit-resolution"),
+            ToolkitAction::Locale => t!("toolkit-locale"),
+            ToolkitAction::Standby => t!("toolkit-standby"),
+            ToolkitAction::MockLocation => t!("toolkit-mock-location"),
+            ToolkitAction::LayoutInspector => t!("toolkit-layout-inspector"),
+            ToolkitAction::Ocr => t!("toolkit-ocr"),
+            ToolkitAction::ColorPicker => t!("toolkit-color-picker"),
+            ToolkitAction::BatteryInfo => t!("toolkit-battery-info"),
+            ToolkitAction::WifiInfo => t!("toolkit-wifi-info"),
+            ToolkitAction::DeviceReport => t!("toolkit-device-report"),
+            ToolkitAction::ReverseTether => t!("toolkit-reverse-tether"),
+            ToolkitAction::UninstallApp => t!("toolkit-uninstall-app"),
+            ToolkitAction::DisableApp => t!("toolkit-disable-app"),
+            ToolkitAction::Reboot => t!("toolkit-reboot"),
+            ToolkitAction::Shutdown => t!("toolkit-shutdown"),
+            ToolkitAction::RebootRecovery => t!("toolkit-reboot-recovery"),
+            ToolkitAction::RebootBootloader => t!("toolkit-reboot-bootloader"),
+            ToolkitAction::RebootTo(target) => target.label(),
+            ToolkitAction::BootloaderLock => t!("toolkit-bootloader-lock"),
+            ToolkitAction::Sideload => t!("toolkit-sideload"),
+        }
+    }
+
+    /// Name used by the control API
+    pub fn id(self) -> &'static str {
+        match self {
+            ToolkitAction::None => "none",
+            ToolkitAction::Screenshot => "screenshot",
+            ToolkitAction::CopyScreenshot => "copy_screenshot",
+            ToolkitAction::LongScreenshot => "long_screenshot",
+            ToolkitAction::ScreenshotDiff => "screenshot_diff",
+            ToolkitAction::NativePreview => "native_preview",
+            ToolkitAction::LivePreview => "live_preview",
+            ToolkitAction::RecordScreen => "record_screen",
+            ToolkitAction::InstallApk => "install_apk",
+            ToolkitAction::OpenShell => "open_shell",
+            ToolkitAction::ShellConsole => "shell_console",
+            ToolkitAction::ShellCommand => "shell_command",
+            ToolkitAction::SendIntent => "send_intent",
+            ToolkitAction::Capabilities => "capabilities",
+            ToolkitAction::AdbKeys => "adb_keys",
+            ToolkitAction::Automation => "automation",
+            ToolkitAction::Macros => "macros",
+            ToolkitAction::TapPicker => "tap_picker",
+            ToolkitAction::Activities => "activities",
+            ToolkitAction::ClipboardHistory => "clipboard_history",
+            ToolkitAction::ShareView => "share_view",
+            ToolkitAction::Benchmark => "benchmark",
+            ToolkitAction::FrameTiming => "frame_timing",
+            ToolkitAction::PerfettoTrace => "perfetto_trace",
+            ToolkitAction::Bugreport => "bugreport",
+            ToolkitAction::ShowImei => "show_imei",
+            ToolkitAction::DisplayInfo => "display_info",
+            ToolkitAction::Resolution => "resolution",
+            ToolkitAction::Locale => "locale",
+            ToolkitAction::Standby => "standby",
+            ToolkitAction::MockLocation => "mock_location",
+            ToolkitAction::LayoutInspector => "layout_inspector",
+            ToolkitAction::Ocr => "ocr",
+            ToolkitAction::ColorPicker => "color_picker",
+            ToolkitAction::BatteryInfo => "battery_info",
+            ToolkitAction::WifiInfo => "wifi_info",
+            ToolkitAction::DeviceReport => "device_report",
+            ToolkitAction::ReverseTether => "reverse_tether",
+            ToolkitAction::UninstallApp => "uninstall_app",
+            ToolkitAction::DisableApp => "disable_app",
+            ToolkitAction::Reboot => "reboot",
+            ToolkitAction::Shutdown => "shutdown",
+            ToolkitAction::RebootRecovery => "reboot_recovery",
+            ToolkitAction::RebootBootloader => "reboot_bootloader",
+            ToolkitAction::RebootTo(RebootTarget::Fastbootd) => "reboot_fastbootd",
+            ToolkitAction::RebootTo(RebootTarget::SafeMode) => "reboot_safe_mode",
+            ToolkitAction::RebootTo(RebootTarget::Sideload) => "reboot_sideload",
+            ToolkitAction::RebootTo(RebootTarget::Edl) => "reboot_edl",
+            ToolkitAction::BootloaderLock => "bootloader_lock",
+            ToolkitAction::Sideload => "sideload",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.id() == id)
+    }
+
+    /// Asks for confirmation before it runs
+    pub fn needs_confirmation(self) -> bool {
+        matches!(
+            self,
+            ToolkitAction::Reboot
+                | ToolkitAction::Shutdown
+                | ToolkitAction::RebootRecovery
+                | ToolkitAction::RebootBootloader
+                | ToolkitAction::RebootTo(_)
+        )
+    }
 }
 
 pub enum SwipeAction {
@@ -29,6 +251,9 @@ pub enum SwipeAction {
     Down,
     Left,
     Right,
+    /// A gesture saved in the gesture designer
+    Gesture(crate::config::Gesture),
+    OpenDesigner,
 }
 
 pub struct SwipePanel {
@@ -41,6 +266,9 @@ pub struct ToolkitPanel {
     pub show_shutdown_confirm: bool,
     pub show_recovery_confirm: bool,
     pub show_bootloader_confirm: bool,
+    pub reboot_target_confirm: Option<RebootTarget>,
+    /// What the selected device can reboot into; None until it was checked
+    pub reboot_targets: Option<Vec<RebootTarget>>,
 }
 
 pub struct BottomPanel {
@@ -55,6 +283,8 @@ pub struct WirelessAdbPanel {
     pairing_port: String,
     pairing_code: String,
     selected_device: Option<String>,
+    /// Endpoint and text of the history nickname being edited
+    renaming: Option<(String, String)>,
     config: Option<std::sync::Arc<tokio::sync::Mutex<crate::config::AppConfig>>>,
 }
 
@@ -69,7 +299,7 @@ impl SwipePanel {
         Self { visible: true }
     }
 
-    pub fn show(&mut self, ui: &mut Ui) -> Option<SwipeAction> {
+    pub fn show(&mut self, ui: &mut Ui, gestures: &[crate::config::Gesture]) -> Option<SwipeAction> {
         if !self.visible {
             return None;
         }
@@ -77,25 +307,36 @@ impl SwipePanel {
         let mut action = None;
 
         ui.group(|ui| {
-            ui.heading("Swipe Controls");
+            ui.heading(t!("swipe-title"));
 
             ui.horizontal(|ui| {
-                if ui.button(format!("{} Swipe Up", egui_phosphor::fill::ARROW_UP)).clicked() {
+                if ui.button(format!("{} {}", egui_phosphor::fill::ARROW_UP, t!("swipe-swipe-up"))).clicked() {
                     action = Some(SwipeAction::Up);
                 }
-                if ui.button(format!("{} Swipe Down", egui_phosphor::fill::ARROW_DOWN)).clicked() {
+                if ui.button(format!("{} {}", egui_phosphor::fill::ARROW_DOWN, t!("swipe-swipe-down"))).clicked() {
                     action = Some(SwipeAction::Down);
                 }
             });
 
             ui.horizontal(|ui| {
-                if ui.button(format!("{} Swipe Left", egui_phosphor::fill::ARROW_LEFT)).clicked() {
+                if ui.button(format!("{} {}", egui_phosphor::fill::ARROW_LEFT, t!("swipe-swipe-left"))).clicked() {
                     action = Some(SwipeAction::Left);
                 }
-                if ui.button(format!("{} Swipe Right", egui_phosphor::fill::ARROW_RIGHT)).clicked() {
+                if ui.button(format!("{} {}", egui_phosphor::fill::ARROW_RIGHT, t!("swipe-swipe-right"))).clicked() {
                     action = Some(SwipeAction::Right);
                 }
             });
+
+            ui.horizontal_wrapped(|ui| {
+                for gesture in gestures {
+                    if ui.button(&gesture.name).clicked() {
+                        action = Some(SwipeAction::Gesture(gesture.clone()));
+                    }
+                }
+                if ui.button(format!("{} {}", egui_phosphor::fill::HAND_SWIPE_RIGHT, t!("swipe-gesture-designer"))).clicked() {
+                    action = Some(SwipeAction::OpenDesigner);
+                }
+            });
         });
         action
     }
@@ -115,6 +356,20 @@ impl ToolkitPanel {
             show_shutdown_confirm: false,
             show_recovery_confirm: false,
             show_bootloader_confirm: false,
+            reboot_target_confirm: None,
+            reboot_targets: None,
+        }
+    }
+
+    /// Open the confirmation for a power action, as its button would
+    pub fn confirm(&mut self, action: ToolkitAction) {
+        match action {
+            ToolkitAction::Reboot => self.show_reboot_confirm = true,
+            ToolkitAction::Shutdown => self.show_shutdown_confirm = true,
+            ToolkitAction::RebootRecovery => self.show_recovery_confirm = true,
+            ToolkitAction::RebootBootloader => self.show_bootloader_confirm = true,
+            ToolkitAction::RebootTo(target) => self.reboot_target_confirm = Some(target),
+            _ => {}
         }
     }
 
@@ -127,7 +382,7 @@ impl ToolkitPanel {
 
         ui.group(|ui| {
             ui.vertical_centered(|ui| {
-                ui.heading("Toolkit");
+                ui.heading(t!("dock-panel-toolkit"));
             });
 
             ui.vertical_centered(|ui| {
@@ -135,18 +390,79 @@ impl ToolkitPanel {
                 ui.vertical_centered(|ui| {
                     if ui.add(
                         egui::Button::new(
-                            egui::RichText::new(format!("{} Screenshot", egui_phosphor::fill::CROP)).size(13.0)
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::CROP, t!("toolkit-button-screenshot"))).size(13.0)
                         ).min_size(egui::vec2(120.0, 28.0))
                     ).clicked() {
                         action = ToolkitAction::Screenshot;
                     }
                 });
 
+                // Copy Screenshot button
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::CLIPBOARD, t!("toolkit-button-copy-screenshot"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).clicked() {
+                        action = ToolkitAction::CopyScreenshot;
+                    }
+                });
+
+                // Long screenshot of scrolling content
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::ARROWS_VERTICAL, t!("toolkit-button-long-screenshot"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).on_hover_text(t!("toolkit-long-screenshot-hint")).clicked() {
+                        action = ToolkitAction::LongScreenshot;
+                    }
+                    if loading.long_screenshot {
+                        ui.add(egui::Spinner::new().size(16.0));
+                    }
+                });
+
+                // Two screenshots compared pixel by pixel
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::GIT_DIFF, t!("toolkit-button-compare-screenshots"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).on_hover_text(t!("toolkit-screenshot-diff-hint")).clicked() {
+                        action = ToolkitAction::ScreenshotDiff;
+                    }
+                });
+
+                // Experimental: the screen decoded inside DroidView
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::MONITOR_PLAY, t!("toolkit-button-native-preview"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).on_hover_text(t!("toolkit-native-preview-hint")).clicked() {
+                        action = ToolkitAction::NativePreview;
+                    }
+                    if loading.native_preview {
+                        ui.add(egui::Spinner::new().size(16.0));
+                    }
+                });
+
+                // Small live screenshot floating over DroidView
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::PICTURE_IN_PICTURE, t!("toolkit-button-live-preview"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).on_hover_text(t!("toolkit-live-preview-hint")).clicked() {
+                        action = ToolkitAction::LivePreview;
+                    }
+                });
+
                 // Record Screen button
                 ui.vertical_centered(|ui| {
                     if ui.add(
                         egui::Button::new(
-                            egui::RichText::new(format!("{} Record Screen", egui_phosphor::fill::RECORD)).size(13.0)
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::RECORD, t!("toolkit-button-record-screen"))).size(13.0)
                         ).min_size(egui::vec2(120.0, 28.0))
                     ).clicked() {
                         action = ToolkitAction::RecordScreen;
@@ -157,7 +473,7 @@ impl ToolkitPanel {
                 ui.vertical_centered(|ui| {
                     if ui.add(
                         egui::Button::new(
-                            egui::RichText::new(format!("{} Install APK", egui_phosphor::fill::GOOGLE_PLAY_LOGO)).size(13.0)
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::GOOGLE_PLAY_LOGO, t!("toolkit-button-install-apk"))).size(13.0)
                         ).min_size(egui::vec2(120.0, 28.0))
                     ).clicked() {
                         action = ToolkitAction::InstallApk;
@@ -168,18 +484,187 @@ impl ToolkitPanel {
                 ui.vertical_centered(|ui| {
                     if ui.add(
                         egui::Button::new(
-                            egui::RichText::new(format!("{} ADB Shell", egui_phosphor::fill::TERMINAL)).size(13.0)
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::TERMINAL, t!("toolkit-button-adb-shell"))).size(13.0)
                         ).min_size(egui::vec2(120.0, 28.0))
                     ).clicked() {
                         action = ToolkitAction::OpenShell;
                     }
                 });
 
+                // Shell inside DroidView
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::TERMINAL_WINDOW, t!("toolkit-button-shell-console"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).on_hover_text(t!("toolkit-shell-console-hint")).clicked() {
+                        action = ToolkitAction::ShellConsole;
+                    }
+                });
+
+                // One-off shell commands and saved snippets
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::CODE, t!("toolkit-button-run-command"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).on_hover_text(t!("toolkit-shell-command-hint")).clicked() {
+                        action = ToolkitAction::ShellCommand;
+                    }
+                });
+
+                // Send Intent button
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::PAPER_PLANE_TILT, t!("toolkit-button-send-intent"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).clicked() {
+                        action = ToolkitAction::SendIntent;
+                    }
+                });
+
+                // Capabilities button
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::KEY, t!("toolkit-button-capabilities"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).clicked() {
+                        action = ToolkitAction::Capabilities;
+                    }
+                });
+
+                // ADB keys button
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::FINGERPRINT, t!("toolkit-button-adb-keys"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).clicked() {
+                        action = ToolkitAction::AdbKeys;
+                    }
+                });
+
+                // Automation rules button
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::ROBOT, t!("toolkit-button-automation"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).clicked() {
+                        action = ToolkitAction::Automation;
+                    }
+                });
+
+                // Input macros button
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::RECORD, t!("toolkit-button-macros"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).clicked() {
+                        action = ToolkitAction::Macros;
+                    }
+                });
+
+                // Tap picker button
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::HAND_TAP, t!("toolkit-button-tap-picker"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).clicked() {
+                        action = ToolkitAction::TapPicker;
+                    }
+                });
+
+                // Activities button
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::STACK, t!("toolkit-button-activities"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).clicked() {
+                        action = ToolkitAction::Activities;
+                    }
+                });
+
+                // Device Clipboard button
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::CLIPBOARD_TEXT, t!("toolkit-button-device-clipboard"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).clicked() {
+                        action = ToolkitAction::ClipboardHistory;
+                    }
+                });
+
+                // Share View button
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::EYE, t!("toolkit-button-share-view"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).clicked() {
+                        action = ToolkitAction::ShareView;
+                    }
+                });
+
+                // Benchmark button
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::GAUGE, t!("toolkit-button-benchmark"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).clicked() {
+                        action = ToolkitAction::Benchmark;
+                    }
+                });
+
+                // Frame timing button
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::TIMER, t!("toolkit-button-frame-timing"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).clicked() {
+                        action = ToolkitAction::FrameTiming;
+                    }
+                });
+
+                // Perfetto trace button
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::CHART_LINE, t!("toolkit-button-trace"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).clicked() {
+                        action = ToolkitAction::PerfettoTrace;
+                    }
+                });
+
+                // Bug report button with spinner
+                ui.vertical_centered(|ui| {
+                    if ui.add_enabled(
+                        !loading.bugreport,
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::BUG, t!("toolkit-button-bug-report"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).clicked() {
+                        action = ToolkitAction::Bugreport;
+                    }
+                    if loading.bugreport {
+                        ui.add(egui::Spinner::new().size(16.0));
+                    }
+                });
+
                 // Show IMEI button with spinner
                 ui.vertical_centered(|ui| {
                     if ui.add(
                         egui::Button::new(
-                            egui::RichText::new(format!("{} Show IMEI", egui_phosphor::fill::PHONE)).size(13.0)
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::PHONE, t!("toolkit-button-show-imei"))).size(13.0)
                         ).min_size(egui::vec2(120.0, 28.0))
                     ).clicked() {
                         action = ToolkitAction::ShowImei;
@@ -193,7 +678,7 @@ impl ToolkitPanel {
                 ui.vertical_centered(|ui| {
                     if ui.add(
                         egui::Button::new(
-                            egui::RichText::new(format!("{} Display Info", egui_phosphor::fill::MONITOR)).size(13.0)
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::MONITOR, t!("toolkit-button-display-info"))).size(13.0)
                         ).min_size(egui::vec2(120.0, 28.0))
                     ).clicked() {
                         action = ToolkitAction::DisplayInfo;
@@ -203,11 +688,91 @@ impl ToolkitPanel {
                     }
                 });
 
+                // Resolution and density overrides
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::FRAME_CORNERS, t!("toolkit-button-resolution"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).on_hover_text(t!("toolkit-resolution-hint")).clicked() {
+                        action = ToolkitAction::Resolution;
+                    }
+                });
+
+                // Locale and time zone changer
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::TRANSLATE, t!("toolkit-button-locale-time"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).on_hover_text(t!("toolkit-locale-hint")).clicked() {
+                        action = ToolkitAction::Locale;
+                    }
+                });
+
+                // Doze and app standby
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::MOON, t!("toolkit-button-doze-standby"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).on_hover_text(t!("toolkit-standby-hint")).clicked() {
+                        action = ToolkitAction::Standby;
+                    }
+                });
+
+                // Mock location and GPX route playback
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::MAP_PIN, t!("toolkit-button-mock-location"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).on_hover_text(t!("toolkit-mock-location-hint")).clicked() {
+                        action = ToolkitAction::MockLocation;
+                    }
+                });
+
+                // View hierarchy from uiautomator next to a screenshot
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::TREE_STRUCTURE, t!("toolkit-button-layout-inspector"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).on_hover_text(t!("toolkit-layout-inspector-hint")).clicked() {
+                        action = ToolkitAction::LayoutInspector;
+                    }
+                });
+
+                // Text recognition on a screenshot, copied to the clipboard
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::TEXT_T, t!("toolkit-button-copy-screen-text"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).on_hover_text(t!("toolkit-ocr-hint")).clicked() {
+                        action = ToolkitAction::Ocr;
+                    }
+                    if loading.ocr {
+                        ui.add(egui::Spinner::new().size(16.0));
+                    }
+                });
+
+                // Exact pixel colors from a full-resolution screenshot
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::EYEDROPPER, t!("toolkit-button-color-picker"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).on_hover_text(t!("toolkit-color-picker-hint")).clicked() {
+                        action = ToolkitAction::ColorPicker;
+                    }
+                });
+
                 // Show Battery Info button with spinner
                 ui.vertical_centered(|ui| {
                     if ui.add(
                         egui::Button::new(
-                            egui::RichText::new(format!("{} Battery Info", egui_phosphor::fill::BATTERY_FULL)).size(13.0)
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::BATTERY_FULL, t!("toolkit-button-battery-info"))).size(13.0)
                         ).min_size(egui::vec2(120.0, 28.0))
                     ).clicked() {
                         action = ToolkitAction::BatteryInfo;
@@ -217,11 +782,47 @@ impl ToolkitPanel {
                     }
                 });
 
+                // Wi-Fi info button
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::WIFI_HIGH, t!("toolkit-button-wi-fi-info"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).clicked() {
+                        action = ToolkitAction::WifiInfo;
+                    }
+                });
+
+                // Device report export with spinner
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::FILE_TEXT, t!("toolkit-button-device-report"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0))
+                    ).on_hover_text(t!("toolkit-device-report-hint")).clicked() {
+                        action = ToolkitAction::DeviceReport;
+                    }
+                    if loading.device_report {
+                        ui.add(egui::Spinner::new().size(16.0));
+                    }
+                });
+
+                // Reverse tethering toggle, highlighted while the device uses this computer's internet
+                ui.vertical_centered(|ui| {
+                    if ui.add(
+                        egui::Button::new(
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::GLOBE, t!("toolkit-button-share-internet"))).size(13.0)
+                        ).min_size(egui::vec2(120.0, 28.0)).selected(loading.reverse_tether)
+                    ).on_hover_text(t!("toolkit-reverse-tether-hint")).clicked() {
+                        action = ToolkitAction::ReverseTether;
+                    }
+                });
+
                 // Show Uninstall App button with spinner
                 ui.vertical_centered(|ui| {
                     if ui.add(
                         egui::Button::new(
-                            egui::RichText::new(format!("{} Uninstall App", egui_phosphor::fill::TRASH_SIMPLE)).size(13.0)
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::TRASH_SIMPLE, t!("toolkit-button-uninstall-app"))).size(13.0)
                         ).min_size(egui::vec2(120.0, 28.0))
                     ).clicked() {
                         action = ToolkitAction::UninstallApp;
@@ -235,7 +836,7 @@ impl ToolkitPanel {
                 ui.vertical_centered(|ui| {
                     if ui.add(
                         egui::Button::new(
-                            egui::RichText::new(format!("{} Disable App", egui_phosphor::fill::PROHIBIT)).size(13.0)
+                            egui::RichText::new(format!("{} {}", egui_phosphor::fill::PROHIBIT, t!("toolkit-button-disable-app"))).size(13.0)
                         ).min_size(egui::vec2(120.0, 28.0))
                     ).clicked() {
                         action = ToolkitAction::DisableApp;
@@ -247,146 +848,125 @@ impl ToolkitPanel {
 
                 // Device Control Section
                 ui.separator();
-                ui.label(egui::RichText::new("Device Control").size(11.0).color(egui::Color32::GRAY));
+                ui.label(egui::RichText::new(t!("toolkit-device-control")).size(11.0).color(egui::Color32::GRAY));
                 
                 // Reboot/Shutdown buttons in a horizontal row
                 ui.horizontal(|ui| {
                     // Reboot button
                     let reboot_resp = ui.add(
-                        egui::Button::new(egui::RichText::new(format!("{}", egui_phosphor::fill::ARROW_CLOCKWISE)).size(16.0))
+                        egui::Button::new(egui::RichText::new(egui_phosphor::fill::ARROW_CLOCKWISE.to_string()).size(16.0))
                             .min_size(egui::vec2(32.0, 32.0))
                     );
                     if reboot_resp.clicked() {
                         self.show_reboot_confirm = true;
                      }
-                    reboot_resp.on_hover_text("Reboot Device\nRestart the device normally");
+                    reboot_resp.on_hover_text(t!("toolkit-reboot-hint"));
 
                     // Shutdown button
                     let shutdown_resp = ui.add(
-                        egui::Button::new(egui::RichText::new(format!("{}", egui_phosphor::fill::POWER)).size(16.0))
+                        egui::Button::new(egui::RichText::new(egui_phosphor::fill::POWER.to_string()).size(16.0))
                             .min_size(egui::vec2(32.0, 32.0))
                     );
                     if shutdown_resp.clicked() {
                         self.show_shutdown_confirm = true;
                      }
-                    shutdown_resp.on_hover_text("Shutdown Device\nPower off the device completely");
+                    shutdown_resp.on_hover_text(t!("toolkit-shutdown-hint"));
 
                     // Reboot to Recovery button
                     let recovery_resp = ui.add(
-                        egui::Button::new(egui::RichText::new(format!("{}", egui_phosphor::fill::WRENCH)).size(16.0))
+                        egui::Button::new(egui::RichText::new(egui_phosphor::fill::WRENCH.to_string()).size(16.0))
                             .min_size(egui::vec2(32.0, 32.0))
                     );
                     if recovery_resp.clicked() {
                         self.show_recovery_confirm = true;
                      }
-                    recovery_resp.on_hover_text("Reboot to Recovery\nRestart device in recovery mode for system maintenance");
+                    recovery_resp.on_hover_text(t!("toolkit-reboot-recovery-hint"));
 
                     // Reboot to Bootloader button
                     let bootloader_resp = ui.add(
-                        egui::Button::new(egui::RichText::new(format!("{}", egui_phosphor::fill::GEAR)).size(16.0))
+                        egui::Button::new(egui::RichText::new(egui_phosphor::fill::GEAR.to_string()).size(16.0))
                             .min_size(egui::vec2(32.0, 32.0))
                     );
                     if bootloader_resp.clicked() {
                         self.show_bootloader_confirm = true;
                      }
-                    bootloader_resp.on_hover_text("Reboot to Bootloader\nRestart device in bootloader mode for flashing");
+                    bootloader_resp.on_hover_text(t!("toolkit-reboot-bootloader-hint"));
+
+                    // Less common targets, offered when the device supports them
+                    let more_resp = ui.menu_button(
+                        egui::RichText::new(egui_phosphor::fill::DOTS_THREE_OUTLINE.to_string()).size(16.0),
+                        |ui| {
+                            for target in RebootTarget::ALL {
+                                let supported = self
+                                    .reboot_targets
+                                    .as_ref()
+                                    .is_some_and(|targets| targets.contains(&target));
+                                let item = ui.add_enabled(supported, egui::Button::new(target.label()));
+                                let item = match &self.reboot_targets {
+                                    None => item.on_disabled_hover_text(t!("toolkit-reboot-checking")),
+                                    Some(_) => item.on_disabled_hover_text(target.unsupported_reason()),
+                                };
+                                if item.clicked() {
+                                    self.reboot_target_confirm = Some(target);
+                                    ui.close();
+                                }
+                            }
+                        },
+                    );
+                    more_resp.response.on_hover_text(t!("toolkit-reboot-more-hint"));
+
+                    // Bootloader unlock/lock wizard, which asks for its own confirmations
+                    let lock_resp = ui.add(
+                        egui::Button::new(egui::RichText::new(egui_phosphor::fill::LOCK_KEY.to_string()).size(16.0))
+                            .min_size(egui::vec2(32.0, 32.0))
+                    );
+                    if lock_resp.clicked() {
+                        action = ToolkitAction::BootloaderLock;
+                    }
+                    lock_resp.on_hover_text(t!("toolkit-bootloader-lock-hint"));
+
+                    // Sideload an OTA or ROM zip through recovery
+                    let sideload_resp = ui.add(
+                        egui::Button::new(egui::RichText::new(egui_phosphor::fill::FILE_ZIP.to_string()).size(16.0))
+                            .min_size(egui::vec2(32.0, 32.0))
+                    );
+                    if sideload_resp.clicked() {
+                        action = ToolkitAction::Sideload;
+                    }
+                    sideload_resp.on_hover_text(t!("toolkit-sideload-hint"));
                 });
 
                 // Confirmation dialogs
-                if self.show_reboot_confirm {
-                    egui::Window::new("Confirm Reboot")
-                        .collapsible(false)
-                        .resizable(false)
-                        .fixed_size(egui::vec2(300.0, 150.0))
-                        .show(ui.ctx(), |ui| {
-                            ui.vertical_centered(|ui| {
-                                ui.label(egui::RichText::new(format!("{}", egui_phosphor::fill::WARNING)).size(48.0).strong());
-                                ui.add_space(4.0);
-                                ui.label("Are you sure you want to reboot the device?");
-                                ui.add_space(16.0);
-                                ui.horizontal(|ui| {
-                                    if ui.button("OK").clicked() {
-                                        action = ToolkitAction::Reboot;
-                                        self.show_reboot_confirm = false;
-                                    }
-                                    if ui.button("Cancel").clicked() {
-                                        self.show_reboot_confirm = false;
-                                    }
-                                });
-                            });
-                        });
+                if self.show_reboot_confirm && confirm_dialog(ui.ctx(), &t!("confirm-reboot-title"), &t!("confirm-reboot"), &mut self.show_reboot_confirm) {
+                    action = ToolkitAction::Reboot;
                 }
 
-                if self.show_shutdown_confirm {
-                    egui::Window::new("Confirm Shutdown")
-                        .collapsible(false)
-                        .resizable(false)
-                        .fixed_size(egui::vec2(300.0, 150.0))
-                        .show(ui.ctx(), |ui| {
-                            ui.vertical_centered(|ui| {
-                                ui.label(egui::RichText::new(format!("{}", egui_phosphor::fill::WARNING)).size(48.0).strong());
-                                ui.add_space(4.0);
-                                ui.label("Are you sure you want to shutdown the device?");
-                                ui.add_space(16.0);
-                                ui.horizontal(|ui| {
-                                    if ui.button("OK").clicked() {
-                                        action = ToolkitAction::Shutdown;
-                                        self.show_shutdown_confirm = false;
-                                    }
-                                    if ui.button("Cancel").clicked() {
-                                        self.show_shutdown_confirm = false;
-                                    }
-                                });
-                            });
-                        });
+                if self.show_shutdown_confirm && confirm_dialog(ui.ctx(), &t!("confirm-shutdown-title"), &t!("confirm-shutdown"), &mut self.show_shutdown_confirm) {
+                    action = ToolkitAction::Shutdown;
                 }
 
-                if self.show_recovery_confirm {
-                    egui::Window::new("Confirm Recovery Reboot")
-                        .collapsible(false)
-                        .resizable(false)
-                        .fixed_size(egui::vec2(300.0, 150.0))
-                        .show(ui.ctx(), |ui| {
-                            ui.vertical_centered(|ui| {
-                                ui.label(egui::RichText::new(format!("{}", egui_phosphor::fill::WARNING)).size(48.0).strong());
-                                ui.add_space(4.0);
-                                ui.label("Are you sure you want to reboot to recovery mode?");
-                                ui.add_space(16.0);
-                                ui.horizontal(|ui| {
-                                    if ui.button("OK").clicked() {
-                                        action = ToolkitAction::RebootRecovery;
-                                        self.show_recovery_confirm = false;
-                                    }
-                                    if ui.button("Cancel").clicked() {
-                                        self.show_recovery_confirm = false;
-                                    }
-                                });
-                            });
-                        });
+                if self.show_recovery_confirm && confirm_dialog(ui.ctx(), &t!("confirm-recovery-title"), &t!("confirm-recovery"), &mut self.show_recovery_confirm) {
+                    action = ToolkitAction::RebootRecovery;
                 }
 
-                if self.show_bootloader_confirm {
-                    egui::Window::new("Confirm Bootloader Reboot")
-                        .collapsible(false)
-                        .resizable(false)
-                        .fixed_size(egui::vec2(300.0, 150.0))
-                        .show(ui.ctx(), |ui| {
-                            ui.vertical_centered(|ui| {
-                                ui.label(egui::RichText::new(format!("{}", egui_phosphor::fill::WARNING)).size(48.0).strong());
-                                ui.add_space(4.0);
-                                ui.label("Are you sure you want to reboot to bootloader?");
-                                ui.add_space(16.0);
-                                ui.horizontal(|ui| {
-                                    if ui.button("OK").clicked() {
-                                        action = ToolkitAction::RebootBootloader;
-                                        self.show_bootloader_confirm = false;
-                                    }
-                                    if ui.button("Cancel").clicked() {
-                                        self.show_bootloader_confirm = false;
-                                    }
-                                });
-                            });
-                        });
+                if self.show_bootloader_confirm && confirm_dialog(ui.ctx(), &t!("confirm-bootloader-title"), &t!("confirm-bootloader"), &mut self.show_bootloader_confirm) {
+                    action = ToolkitAction::RebootBootloader;
+                }
+
+                if let Some(target) = self.reboot_target_confirm {
+                    let question = match target {
+                        RebootTarget::Fastbootd => t!("confirm-fastbootd"),
+                        RebootTarget::SafeMode => t!("confirm-safe-mode"),
+                        RebootTarget::Sideload => t!("confirm-sideload"),
+                        RebootTarget::Edl => t!("confirm-edl"),
+                    };
+                    let mut open = true;
+                    if confirm_dialog(ui.ctx(), &t!("confirm-reboot-target-title", target = target.label()), &question, &mut open) {
+                        action = ToolkitAction::RebootTo(target);
+                    }
+                    if !open {
+                        self.reboot_target_confirm = None;
+                    }
                 }
             });
         });
@@ -394,15 +974,49 @@ impl ToolkitPanel {
     }
 }
 
+/// Warning window with OK and Cancel; true when OK was clicked. Either button clears `open`.
+fn confirm_dialog(ctx: &egui::Context, title: &str, question: &str, open: &mut bool) -> bool {
+    let mut confirmed = false;
+    egui::Window::new(title)
+        .collapsible(false)
+        .resizable(false)
+        .fixed_size(egui::vec2(300.0, 150.0))
+        .show(ctx, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.label(egui::RichText::new(egui_phosphor::fill::WARNING.to_string()).size(48.0).strong());
+                ui.add_space(4.0);
+                ui.label(question);
+                ui.add_space(16.0);
+                ui.horizontal(|ui| {
+                    if ui.button(t!("confirm-ok")).clicked() {
+                        confirmed = true;
+                        *open = false;
+                    }
+                    if ui.button(t!("confirm-cancel")).clicked() {
+                        *open = false;
+                    }
+                });
+            });
+        });
+    confirmed
+}
+
 // Helper struct for loading states
 pub struct ToolkitLoadingState {
     pub screenshot: bool,
+    pub long_screenshot: bool,
+    pub native_preview: bool,
     pub record_screen: bool,
     pub install_apk: bool,
     pub open_shell: bool,
+    pub bugreport: bool,
+    /// The selected device is using this computer's internet
+    pub reverse_tether: bool,
     pub show_imei: bool,
     pub display_info: bool,
     pub battery_info: bool,
+    pub device_report: bool,
+    pub ocr: bool,
     pub uninstall_app: bool,
     pub disable_app: bool,
 }
@@ -418,7 +1032,7 @@ impl BottomPanel {
         Self { visible: true }
     }
 
-    pub fn show(&mut self, ui: &mut Ui) -> BottomPanelAction {
+    pub fn show(&mut self, ui: &mut Ui, pinned_scripts: &[String]) -> BottomPanelAction {
         if !self.visible {
             return BottomPanelAction::None;
         }
@@ -426,21 +1040,59 @@ impl BottomPanel {
         let mut action = BottomPanelAction::None;
 
         ui.group(|ui| {
-            ui.heading("Quick Actions");
+            ui.heading(t!("quick-actions"));
 
-            ui.horizontal(|ui| {
-                if ui.button("🔄 Refresh Devices").clicked() {
+            ui.horizontal_wrapped(|ui| {
+                if ui.button(t!("quick-refresh-devices")).clicked() {
                     action = BottomPanelAction::RefreshDevices;
                 }
 
-                if ui.button("🔄 Restart ADB").clicked() {
+                if ui.button(t!("quick-restart-adb")).clicked() {
                     action = BottomPanelAction::RestartAdb;
                 }
 
-                if ui.button("🔧 Settings").clicked() {
+                if ui.button(t!("quick-settings")).clicked() {
                     action = BottomPanelAction::OpenSettings;
                 }
+
+                if ui.button(format!("{} {}", egui_phosphor::fill::IMAGES, t!("quick-gallery"))).clicked() {
+                    action = BottomPanelAction::OpenGallery;
+                }
+
+                if ui.button(format!("{} {}", egui_phosphor::fill::FILM_STRIP, t!("quick-recordings"))).clicked() {
+                    action = BottomPanelAction::OpenRecordings;
+                }
+
+                if ui.button(format!("{} {}", egui_phosphor::fill::TERMINAL_WINDOW, t!("quick-logs"))).clicked() {
+                    action = BottomPanelAction::ToggleLogs;
+                }
+
+                if ui
+                    .button(egui_phosphor::fill::KEYBOARD)
+                    .on_hover_text(t!("quick-shortcuts"))
+                    .clicked()
+                {
+                    action = BottomPanelAction::ShowShortcuts;
+                }
+
+                if ui.button(format!("{} {}", egui_phosphor::fill::CODE, t!("quick-scripts"))).clicked() {
+                    action = BottomPanelAction::OpenScripts;
+                }
             });
+
+            if !pinned_scripts.is_empty() {
+                ui.horizontal_wrapped(|ui| {
+                    for (index, name) in pinned_scripts.iter().enumerate() {
+                        if ui
+                            .button(format!("{} {}", egui_phosphor::fill::PLAY, name))
+                            .on_hover_text(t!("quick-run-script"))
+                            .clicked()
+                        {
+                            action = BottomPanelAction::RunScript(index);
+                        }
+                    }
+                });
+            }
         });
 
         action
@@ -463,6 +1115,7 @@ impl WirelessAdbPanel {
             pairing_port: "5555".to_string(),
             pairing_code: String::new(),
             selected_device: None,
+            renaming: None,
             config: None,
         }
     }
@@ -478,17 +1131,124 @@ impl WirelessAdbPanel {
         }
     }
 
+    /// Put `ip` in the direct connection fields, as when it was typed there
+    pub fn set_tcpip_ip(&mut self, ip: String) {
+        self.tcpip_ip = ip;
+        self.save_ips();
+    }
+
     fn save_ips(&mut self) {
-        if let Some(config) = &self.config {
-            if let Ok(mut config_lock) = config.try_lock() {
-                config_lock.wireless_adb.last_tcpip_ip = self.tcpip_ip.clone();
-                config_lock.wireless_adb.last_tcpip_port = self.tcpip_port.clone();
-                config_lock.wireless_adb.last_pairing_ip = self.pairing_ip.clone();
-                config_lock.wireless_adb.last_pairing_port = self.pairing_port.clone();
-                // Save config
-                let _ = config_lock.save();
+        if let Some(config) = &self.config
+            && let Ok(mut config_lock) = config.try_lock() {
+            config_lock.wireless_adb.last_tcpip_ip = self.tcpip_ip.clone();
+            config_lock.wireless_adb.last_tcpip_port = self.tcpip_port.clone();
+            config_lock.wireless_adb.last_pairing_ip = self.pairing_ip.clone();
+            config_lock.wireless_adb.last_pairing_port = self.pairing_port.clone();
+            // Save config
+            let _ = config_lock.save();
+        }
+    }
+
+    /// Saved endpoints, most recently seen first, with connect, rename and forget
+    fn show_history(&mut self, ui: &mut Ui, devices: &[crate::device::Device]) -> Option<WirelessAdbAction> {
+        let config = self.config.clone()?;
+        let Ok(mut config) = config.try_lock() else {
+            return None;
+        };
+        let mut history = config.wireless_adb.saved_endpoints.clone();
+        if history.is_empty() {
+            return None;
+        }
+        history.sort_by_key(|saved| std::cmp::Reverse(saved.last_seen));
+
+        let mut action = None;
+        let mut changed = false;
+        ui.group(|ui| {
+            ui.heading(t!("wireless-history"));
+            for saved in &history {
+                let connected = devices
+                    .iter()
+                    .any(|device| device.identifier == saved.endpoint && device.is_usable());
+                ui.horizontal(|ui| {
+                    if connected {
+                        ui.label(egui::RichText::new(egui_phosphor::fill::WIFI_HIGH).color(egui::Color32::GREEN))
+                            .on_hover_text(t!("wireless-history-connected"));
+                    } else {
+                        ui.label(egui::RichText::new(egui_phosphor::fill::WIFI_SLASH).weak());
+                    }
+
+                    if let Some((endpoint, text)) = &mut self.renaming
+                        && *endpoint == saved.endpoint
+                    {
+                        let edit = ui.add(egui::TextEdit::singleline(text).desired_width(110.0).hint_text(&saved.endpoint));
+                        edit.request_focus();
+                        if edit.lost_focus() {
+                            if !ui.input(|input| input.key_pressed(egui::Key::Escape))
+                                && let Some(entry) = config
+                                    .wireless_adb
+                                    .saved_endpoints
+                                    .iter_mut()
+                                    .find(|entry| entry.endpoint == saved.endpoint)
+                            {
+                                let nickname = text.trim();
+                                entry.nickname = (!nickname.is_empty() && nickname != saved.endpoint)
+                                    .then(|| nickname.to_string());
+                                changed = true;
+                            }
+                            self.renaming = None;
+                        }
+                    } else {
+                        let mut hover = saved.endpoint.clone();
+                        if let Some(last_seen) = saved.last_seen {
+                            hover.push('\n');
+                            hover.push_str(&t!("wireless-history-last-seen", time = last_seen.format("%Y-%m-%d %H:%M")));
+                        }
+                        if saved.paired {
+                            hover.push('\n');
+                            hover.push_str(&t!("wireless-history-paired"));
+                        }
+                        let label = ui.label(saved.display_name()).on_hover_text(hover);
+                        if label.double_clicked() {
+                            self.renaming = Some((saved.endpoint.clone(), saved.display_name().to_string()));
+                        }
+                    }
+
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui
+                            .small_button(egui_phosphor::fill::TRASH)
+                            .on_hover_text(t!("wireless-history-forget"))
+                            .clicked()
+                        {
+                            config.wireless_adb.saved_endpoints.retain(|entry| entry.endpoint != saved.endpoint);
+                            changed = true;
+                        }
+                        if ui
+                            .small_button(egui_phosphor::fill::PENCIL_SIMPLE)
+                            .on_hover_text(t!("wireless-history-rename"))
+                            .clicked()
+                        {
+                            self.renaming = Some((saved.endpoint.clone(), saved.display_name().to_string()));
+                        }
+                        if ui
+                            .add_enabled(!connected, egui::Button::new(t!("wireless-history-connect")).small())
+                            .clicked()
+                            && let Some((ip, port)) = saved.endpoint.rsplit_once(':')
+                            && let Ok(port) = port.parse::<u16>()
+                        {
+                            action = Some(WirelessAdbAction::Connect {
+                                ip: ip.to_string(),
+                                port,
+                            });
+                        }
+                    });
+                });
             }
+        });
+
+        if changed {
+            let _ = config.save();
         }
+        action
     }
 
     pub fn show(
@@ -504,30 +1264,33 @@ impl WirelessAdbPanel {
         let mut action = None;
 
         ui.group(|ui| {
-            ui.heading("Wireless ADB");
+            ui.heading(t!("wireless-title"));
+
+            if let Some(history_action) = self.show_history(ui, devices) {
+                action = Some(history_action);
+            }
 
             // TCP/IP Connection Section
             ui.group(|ui| {
-                ui.heading("Direct TCP/IP Connection");
+                ui.heading(t!("wireless-direct"));
 
                 ui.horizontal(|ui| {
-                    ui.label("IP Address:");
+                    ui.label(t!("wireless-ip"));
                     ui.text_edit_singleline(&mut self.tcpip_ip);
                 });
 
                 ui.horizontal(|ui| {
-                    ui.label("Port:");
+                    ui.label(t!("wireless-port"));
                     ui.text_edit_singleline(&mut self.tcpip_port);
                 });
 
-                if ui.button("🔗 Connect").clicked() {
-                    if let Ok(port) = self.tcpip_port.parse::<u16>() {
-                        self.save_ips(); // Save IPs when connecting
-                        action = Some(WirelessAdbAction::Connect {
-                            ip: self.tcpip_ip.clone(),
-                            port,
-                        });
-                    }
+                if ui.button(t!("wireless-connect")).clicked()
+                    && let Ok(port) = self.tcpip_port.parse::<u16>() {
+                    self.save_ips(); // Save IPs when connecting
+                    action = Some(WirelessAdbAction::Connect {
+                        ip: self.tcpip_ip.clone(),
+                        port,
+                    });
                 }
             });
 
@@ -535,18 +1298,14 @@ impl WirelessAdbPanel {
 
             // TCP/IP Setup Section (for connected devices)
             ui.group(|ui| {
-                ui.heading("Enable TCP/IP on Device");
+                ui.heading(t!("wireless-enable-tcpip-title"));
 
                 if devices.is_empty() {
-                    ui.label("No devices connected");
+                    ui.label(t!("wireless-no-devices"));
                 } else {
                     // Device selection dropdown
                     egui::ComboBox::from_id_salt("device_select")
-                        .selected_text(
-                            self.selected_device
-                                .as_ref()
-                                .unwrap_or(&"Select a device".to_string()),
-                        )
+                        .selected_text(self.selected_device.clone().unwrap_or_else(|| t!("wireless-select-device")))
                         .show_ui(ui, |ui| {
                             for device in devices {
                                 if device.is_usable() {
@@ -559,15 +1318,13 @@ impl WirelessAdbPanel {
                             }
                         });
 
-                    if let Ok(port) = self.tcpip_port.parse::<u16>() {
-                        if ui.button("🌐 Enable TCP/IP").clicked() {
-                            if let Some(device_id) = &self.selected_device {
-                                action = Some(WirelessAdbAction::EnableTcpip {
-                                    device_id: device_id.clone(),
-                                    port,
-                                });
-                            }
-                        }
+                    if let Ok(port) = self.tcpip_port.parse::<u16>()
+                        && ui.button(t!("wireless-enable-tcpip")).clicked()
+                        && let Some(device_id) = &self.selected_device {
+                        action = Some(WirelessAdbAction::EnableTcpip {
+                            device_id: device_id.clone(),
+                            port,
+                        });
                     }
                 }
             });
@@ -576,34 +1333,43 @@ impl WirelessAdbPanel {
 
             // Pairing Section
             ui.group(|ui| {
-                ui.heading("Pair via Code");
+                ui.heading(t!("wireless-pair-title"));
 
                 ui.horizontal(|ui| {
-                    ui.label("IP Address:");
+                    ui.label(t!("wireless-ip"));
                     ui.text_edit_singleline(&mut self.pairing_ip);
                 });
 
                 ui.horizontal(|ui| {
-                    ui.label("Port:");
+                    ui.label(t!("wireless-port"));
                     ui.text_edit_singleline(&mut self.pairing_port);
                 });
 
                 ui.horizontal(|ui| {
-                    ui.label("Pairing Code:");
+                    ui.label(t!("wireless-pairing-code"));
                     ui.text_edit_singleline(&mut self.pairing_code);
                 });
 
-                if ui.button("🔐 Pair").clicked() {
-                    if let Ok(port) = self.pairing_port.parse::<u16>() {
-                        self.save_ips(); // Save IPs when pairing
-                        action = Some(WirelessAdbAction::Pair {
-                            ip: self.pairing_ip.clone(),
-                            port,
-                            code: self.pairing_code.clone(),
-                        });
-                    }
+                if ui.button(t!("wireless-pair")).clicked()
+                    && let Ok(port) = self.pairing_port.parse::<u16>() {
+                    self.save_ips(); // Save IPs when pairing
+                    action = Some(WirelessAdbAction::Pair {
+                        ip: self.pairing_ip.clone(),
+                        port,
+                        code: self.pairing_code.clone(),
+                    });
                 }
             });
+
+            ui.separator();
+
+            if ui
+                .button(format!("{} {}", egui_phosphor::fill::PLUGS_CONNECTED, t!("tunnels-button")))
+                .on_hover_text(t!("tunnels-button-hint"))
+                .clicked()
+            {
+                action = Some(WirelessAdbAction::OpenTunnels);
+            }
         });
 
         action
@@ -614,4 +1380,5 @@ pub enum WirelessAdbAction {
     Connect { ip: String, port: u16 },
     EnableTcpip { device_id: String, port: u16 },
     Pair { ip: String, port: u16, code: String },
+    OpenTunnels,
 }
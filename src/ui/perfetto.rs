@@ -0,0 +1,159 @@
+use crate::perfetto::{CATEGORIES, MAX_DURATION_SECS, PERFETTO_UI};
+use crate::t;
+use egui::{Color32, RichText, Window};
+use std::path::PathBuf;
+use std::time::Instant;
+
+pub enum PerfettoAction {
+    None,
+    Start { categories: Vec<&'static str>, duration_secs: u32 },
+    Stop,
+}
+
+/// Records a Perfetto trace of the selected device and points at ui.perfetto.dev to read it
+pub struct PerfettoWindow {
+    visible: bool,
+    categories: Vec<(&'static str, &'static str, bool)>,
+    duration_secs: u32,
+    /// When the running trace started
+    started: Option<Instant>,
+    stopping: bool,
+    result: Option<Result<PathBuf, String>>,
+}
+
+impl Default for PerfettoWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PerfettoWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            categories: CATEGORIES.to_vec(),
+            duration_secs: 10,
+            started: None,
+            stopping: false,
+            result: None,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.visible = true;
+    }
+
+    pub fn set_started(&mut self) {
+        self.started = Some(Instant::now());
+        self.stopping = false;
+        self.result = None;
+    }
+
+    pub fn finish(&mut self, result: Result<PathBuf, String>) {
+        self.started = None;
+        self.stopping = false;
+        self.result = Some(result);
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> PerfettoAction {
+        if !self.visible {
+            return PerfettoAction::None;
+        }
+
+        let mut action = PerfettoAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::CHART_LINE, t!("perfetto-title")))
+            .id(egui::Id::new("perfetto"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(460.0, 420.0))
+            .show(ctx, |ui| {
+                ui.label(RichText::new(t!("perfetto-intro")).size(11.0).color(Color32::GRAY));
+                ui.separator();
+
+                ui.add_enabled_ui(self.started.is_none(), |ui| {
+                    ui.label(RichText::new(t!("perfetto-categories")).strong());
+                    egui::Grid::new("perfetto_categories").num_columns(2).show(ui, |ui| {
+                        for (index, (category, description, enabled)) in self.categories.iter_mut().enumerate() {
+                            ui.checkbox(enabled, *description).on_hover_text(*category);
+                            if index % 2 == 1 {
+                                ui.end_row();
+                            }
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(t!("perfetto-duration"));
+                        ui.add(egui::Slider::new(&mut self.duration_secs, 1..=MAX_DURATION_SECS).suffix(" s"));
+                    });
+                });
+                ui.separator();
+
+                let chosen: Vec<&'static str> = self
+                    .categories
+                    .iter()
+                    .filter(|(_, _, enabled)| *enabled)
+                    .map(|(category, _, _)| *category)
+                    .collect();
+                match self.started {
+                    Some(started) => {
+                        let elapsed = started.elapsed().as_secs_f32();
+                        let fraction = (elapsed / self.duration_secs as f32).min(1.0);
+                        let text = if self.stopping || fraction >= 1.0 {
+                            t!("perfetto-saving")
+                        } else {
+                            t!("perfetto-recording", elapsed = elapsed as u32, total = self.duration_secs)
+                        };
+                        ui.add(egui::ProgressBar::new(fraction).text(text).animate(true));
+                        let stop = egui::Button::new(format!("{} {}", egui_phosphor::fill::STOP, t!("perfetto-stop")));
+                        if ui.add_enabled(!self.stopping, stop).clicked()
+                        {
+                            self.stopping = true;
+                            action = PerfettoAction::Stop;
+                        }
+                        ui.ctx().request_repaint_after(std::time::Duration::from_millis(250));
+                    }
+                    None => {
+                        if ui
+                            .add_enabled(
+                                !chosen.is_empty(),
+                                egui::Button::new(format!("{} {}", egui_phosphor::fill::RECORD, t!("perfetto-start"))),
+                            )
+                            .clicked()
+                        {
+                            action = PerfettoAction::Start {
+                                categories: chosen,
+                                duration_secs: self.duration_secs,
+                            };
+                        }
+                    }
+                }
+
+                match &self.result {
+                    Some(Ok(path)) => {
+                        ui.separator();
+                        ui.label(RichText::new(t!("perfetto-saved", path = path.display().to_string())).color(Color32::GREEN));
+                        ui.horizontal(|ui| {
+                            if ui.button(format!("{} {}", egui_phosphor::fill::GLOBE, t!("perfetto-open-ui"))).clicked() {
+                                let _ = crate::utils::open_url(PERFETTO_UI);
+                                let _ = crate::utils::reveal_in_file_manager(path);
+                            }
+                            if ui.button(format!("{} {}", egui_phosphor::fill::FOLDER_OPEN, t!("perfetto-show-file"))).clicked() {
+                                let _ = crate::utils::reveal_in_file_manager(path);
+                            }
+                        });
+                        ui.label(RichText::new(t!("perfetto-open-hint")).size(11.0).color(Color32::GRAY));
+                    }
+                    Some(Err(e)) => {
+                        ui.separator();
+                        ui.label(RichText::new(e).color(Color32::RED));
+                    }
+                    None => {}
+                }
+            });
+
+        self.visible = open;
+        action
+    }
+}
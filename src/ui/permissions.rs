@@ -0,0 +1,152 @@
+use crate::packages::RuntimePermission;
+use crate::t;
+use egui::{Color32, RichText, Window};
+
+pub enum PermissionsAction {
+    None,
+    Refresh(String),
+    Set {
+        package: String,
+        permission: String,
+        grant: bool,
+    },
+}
+
+/// Viewer/editor for the runtime permissions of one package
+pub struct PermissionsWindow {
+    visible: bool,
+    package: String,
+    permissions: Vec<RuntimePermission>,
+    loading: bool,
+    error: Option<String>,
+    dangerous_only: bool,
+}
+
+impl Default for PermissionsWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PermissionsWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            package: String::new(),
+            permissions: Vec::new(),
+            loading: false,
+            error: None,
+            dangerous_only: false,
+        }
+    }
+
+    /// Open for a package; the caller starts loading and later calls `set_permissions`
+    pub fn open(&mut self, package: &str) {
+        self.visible = true;
+        self.package = package.to_string();
+        self.permissions.clear();
+        self.error = None;
+        self.loading = true;
+    }
+
+    pub fn package(&self) -> &str {
+        &self.package
+    }
+
+    pub fn set_permissions(&mut self, package: &str, permissions: Vec<RuntimePermission>, error: Option<String>) {
+        if package != self.package {
+            return;
+        }
+        self.permissions = permissions;
+        self.error = error;
+        self.loading = false;
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> PermissionsAction {
+        if !self.visible {
+            return PermissionsAction::None;
+        }
+
+        let mut action = PermissionsAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::SHIELD_CHECK, t!("permissions-title")))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(460.0, 420.0))
+            .show(ctx, |ui| {
+                ui.label(RichText::new(&self.package).strong());
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.dangerous_only, t!("permissions-dangerous-only"));
+                    if ui.add_enabled(!self.loading, egui::Button::new(t!("permissions-refresh"))).clicked() {
+                        self.loading = true;
+                        action = PermissionsAction::Refresh(self.package.clone());
+                    }
+                    if self.loading {
+                        ui.add(egui::Spinner::new().size(14.0));
+                    }
+                });
+
+                if let Some(error) = &self.error {
+                    ui.label(RichText::new(error).color(Color32::RED));
+                }
+
+                ui.separator();
+
+                if !self.loading && self.permissions.is_empty() {
+                    ui.label(t!("permissions-none"));
+                }
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for permission in &self.permissions {
+                        if self.dangerous_only && !permission.is_dangerous() {
+                            continue;
+                        }
+                        ui.horizontal(|ui| {
+                            let mut granted = permission.granted;
+                            let toggle = ui.add_enabled(
+                                !self.loading && !permission.is_fixed(),
+                                egui::Checkbox::without_text(&mut granted),
+                            );
+                            if toggle.changed() {
+                                self.loading = true;
+                                action = PermissionsAction::Set {
+                                    package: self.package.clone(),
+                                    permission: permission.name.clone(),
+                                    grant: granted,
+                                };
+                            }
+
+                            let short_name = permission
+                                .name
+                                .strip_prefix("android.permission.")
+                                .unwrap_or(&permission.name);
+                            let mut text = RichText::new(short_name);
+                            if permission.is_dangerous() {
+                                text = text.color(Color32::from_rgb(255, 140, 0));
+                            }
+                            let label = ui.label(text);
+                            let mut hover = permission.name.clone();
+                            if !permission.flags.is_empty() {
+                                hover.push_str(&format!("\n{}", t!("permissions-flags", flags = permission.flags.join(", "))));
+                            }
+                            label.on_hover_text(hover);
+
+                            if permission.is_dangerous() {
+                                ui.label(RichText::new(egui_phosphor::fill::WARNING).color(Color32::from_rgb(255, 140, 0)))
+                                    .on_hover_text(t!("permissions-dangerous"));
+                            }
+                            if permission.is_fixed() {
+                                ui.label(RichText::new(egui_phosphor::fill::LOCK).color(Color32::GRAY))
+                                    .on_hover_text(t!("permissions-fixed"));
+                            }
+                        });
+                    }
+                });
+            });
+
+        self.visible = open;
+        action
+    }
+}
@@ -0,0 +1,160 @@
+use crate::t;
+use egui::{Color32, RichText, TextureHandle, Window};
+use std::time::Duration;
+
+/// Longer side of the screenshots the preview shows, enough for its largest size
+pub const PIP_EDGE: u32 = 720;
+
+pub enum PipAction {
+    None,
+    /// Open the full scrcpy window for the previewed device
+    Mirror,
+}
+
+/// How often the preview takes a screenshot
+const INTERVALS: [(Duration, &str); 3] = [
+    (Duration::from_millis(500), "2 fps"),
+    (Duration::from_secs(1), "1 fps"),
+    (Duration::from_secs(3), "⅓ fps"),
+];
+/// Widths of the preview
+const SIZES: [f32; 3] = [160.0, 240.0, 360.0];
+
+/// A small live screenshot of the selected device, floating over the rest of DroidView
+pub struct PipPane {
+    visible: bool,
+    device: String,
+    pending: Option<egui::ColorImage>,
+    texture: Option<TextureHandle>,
+    error: Option<String>,
+    interval: usize,
+    size: usize,
+}
+
+impl Default for PipPane {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PipPane {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            device: String::new(),
+            pending: None,
+            texture: None,
+            error: None,
+            interval: 1,
+            size: 1,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn interval(&self) -> Duration {
+        INTERVALS[self.interval].0
+    }
+
+    /// Follow `device`, dropping the screenshot of the one before
+    pub fn set_device(&mut self, device: Option<&str>) {
+        let device = device.unwrap_or_default();
+        if device != self.device {
+            self.device = device.to_string();
+            self.pending = None;
+            self.texture = None;
+            self.error = None;
+        }
+    }
+
+    pub fn set_frame(&mut self, device: &str, result: Result<egui::ColorImage, String>) {
+        if device != self.device {
+            return;
+        }
+        match result {
+            Ok(image) => {
+                self.pending = Some(image);
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> PipAction {
+        if !self.visible {
+            return PipAction::None;
+        }
+        if let Some(image) = self.pending.take() {
+            match &mut self.texture {
+                Some(texture) => texture.set(image, egui::TextureOptions::LINEAR),
+                None => self.texture = Some(ctx.load_texture("pip_preview", image, egui::TextureOptions::LINEAR)),
+            }
+        }
+
+        let mut action = PipAction::None;
+        let width = SIZES[self.size];
+        let screen = ctx.screen_rect();
+
+        Window::new(t!("pip-title"))
+            .id(egui::Id::new("pip_preview"))
+            .title_bar(false)
+            .resizable(false)
+            .default_pos(screen.right_bottom() - egui::vec2(width + 24.0, width * 2.2 + 48.0))
+            .show(ctx, |ui| {
+                ui.set_width(width);
+                ui.horizontal(|ui| {
+                    let name = if self.device.is_empty() { t!("pip-no-device") } else { self.device.clone() };
+                    ui.label(RichText::new(name).size(11.0).weak());
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.small_button(egui_phosphor::fill::X).on_hover_text(t!("pip-close")).clicked() {
+                            self.visible = false;
+                        }
+                        let (grow, shrink) = (egui_phosphor::fill::PLUS, egui_phosphor::fill::MINUS);
+                        if ui.add_enabled(self.size + 1 < SIZES.len(), egui::Button::new(grow).small()).clicked() {
+                            self.size += 1;
+                        }
+                        if ui.add_enabled(self.size > 0, egui::Button::new(shrink).small()).clicked() {
+                            self.size -= 1;
+                        }
+                        egui::ComboBox::from_id_salt("pip_interval")
+                            .selected_text(INTERVALS[self.interval].1)
+                            .width(56.0)
+                            .show_ui(ui, |ui| {
+                                for (index, (_, label)) in INTERVALS.iter().enumerate() {
+                                    ui.selectable_value(&mut self.interval, index, *label);
+                                }
+                            });
+                    });
+                });
+
+                match &self.texture {
+                    Some(texture) => {
+                        let size = texture.size_vec2() * (width / texture.size_vec2().x);
+                        let response = ui
+                            .add(egui::Image::new(texture).fit_to_exact_size(size).sense(egui::Sense::click()))
+                            .on_hover_text(t!("pip-mirror"));
+                        if response.double_clicked() {
+                            action = PipAction::Mirror;
+                        }
+                    }
+                    None => {
+                        ui.allocate_ui(egui::vec2(width, width * 1.6), |ui| {
+                            ui.centered_and_justified(|ui| match &self.error {
+                                Some(e) => ui.label(RichText::new(e).size(11.0).color(Color32::RED)),
+                                None if self.device.is_empty() => ui.label(RichText::new(t!("pip-no-device")).weak()),
+                                None => ui.spinner(),
+                            });
+                        });
+                    }
+                }
+            });
+
+        action
+    }
+}
@@ -0,0 +1,199 @@
+use crate::plugins::{FieldKind, Plugin};
+use crate::t;
+use egui::{Color32, RichText, Ui};
+use std::collections::HashMap;
+
+pub enum PluginAction {
+    /// Run command `command` of plugin `plugin` with the form values
+    Run { plugin: usize, command: usize, values: HashMap<String, String> },
+    Reload,
+    OpenFolder,
+}
+
+/// Forms and output of the plugins found in the plugins folder
+pub struct PluginsPanel {
+    /// Form values by plugin name and command label
+    values: HashMap<(String, String), HashMap<String, String>>,
+    /// Command label and what it printed, or why it failed
+    output: Option<(String, Result<String, String>)>,
+    running: bool,
+}
+
+impl Default for PluginsPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PluginsPanel {
+    pub fn new() -> Self {
+        Self {
+            values: HashMap::new(),
+            output: None,
+            running: false,
+        }
+    }
+
+    /// Current form values of a command, its defaults until the form is used
+    pub fn values(&mut self, plugins: &[Plugin], plugin: usize, command: usize) -> HashMap<String, String> {
+        let (plugin, command) = (&plugins[plugin], &plugins[plugin].commands[command]);
+        self.values
+            .entry((plugin.name.clone(), command.label.clone()))
+            .or_insert_with(|| command.defaults())
+            .clone()
+    }
+
+    pub fn set_running(&mut self) {
+        self.running = true;
+    }
+
+    pub fn finish(&mut self, label: String, result: Result<String, String>) {
+        self.running = false;
+        self.output = Some((label, result));
+    }
+
+    pub fn show(&mut self, ui: &mut Ui, plugins: &[Plugin], errors: &[String]) -> Option<PluginAction> {
+        let mut action = None;
+
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.heading(t!("plugins-title"));
+                if ui
+                    .small_button(egui_phosphor::fill::ARROWS_CLOCKWISE)
+                    .on_hover_text(t!("plugins-reload"))
+                    .clicked()
+                {
+                    action = Some(PluginAction::Reload);
+                }
+                if ui
+                    .small_button(egui_phosphor::fill::FOLDER_OPEN)
+                    .on_hover_text(t!("plugins-open-folder"))
+                    .clicked()
+                {
+                    action = Some(PluginAction::OpenFolder);
+                }
+                if self.running {
+                    ui.spinner();
+                }
+            });
+            for error in errors {
+                ui.label(RichText::new(error).size(11.0).color(Color32::RED));
+            }
+            if plugins.is_empty() {
+                ui.label(RichText::new(t!("plugins-empty")).color(Color32::GRAY));
+            }
+
+            for (plugin_index, plugin) in plugins.iter().enumerate() {
+                egui::CollapsingHeader::new(RichText::new(&plugin.name).strong())
+                    .id_salt(("plugin", &plugin.name))
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        if !plugin.description.is_empty() {
+                            ui.label(RichText::new(&plugin.description).size(11.0).color(Color32::GRAY));
+                        }
+                        for (command_index, command) in plugin.commands.iter().enumerate() {
+                            let values = self
+                                .values
+                                .entry((plugin.name.clone(), command.label.clone()))
+                                .or_insert_with(|| command.defaults());
+                            show_form(ui, plugin_index, command_index, &command.fields, values);
+                            let button = ui
+                                .add_enabled(!self.running, egui::Button::new(&command.label))
+                                .on_hover_text(&command.description);
+                            if button.clicked() {
+                                action = Some(PluginAction::Run {
+                                    plugin: plugin_index,
+                                    command: command_index,
+                                    values: values.clone(),
+                                });
+                            }
+                        }
+                    });
+            }
+
+            if let Some((label, result)) = &self.output {
+                ui.separator();
+                ui.label(RichText::new(label).weak());
+                let (text, color) = match result {
+                    Ok(output) => (output.as_str(), ui.visuals().text_color()),
+                    Err(error) => (error.as_str(), Color32::RED),
+                };
+                egui::ScrollArea::vertical().id_salt("plugin_output").max_height(120.0).show(ui, |ui| {
+                    ui.label(RichText::new(text).monospace().color(color));
+                });
+            }
+        });
+
+        action
+    }
+}
+
+fn show_form(
+    ui: &mut Ui,
+    plugin: usize,
+    command: usize,
+    fields: &[crate::plugins::PluginField],
+    values: &mut HashMap<String, String>,
+) {
+    if fields.is_empty() {
+        return;
+    }
+    egui::Grid::new(("plugin_form", plugin, command)).num_columns(2).show(ui, |ui| {
+        for field in fields {
+            let value = values.entry(field.name.clone()).or_default();
+            ui.label(field.label());
+            match field.kind {
+                FieldKind::Text => {
+                    ui.add(egui::TextEdit::singleline(value).desired_width(160.0));
+                }
+                FieldKind::Number => {
+                    let mut number = value.parse::<f64>().unwrap_or(0.0);
+                    if ui.add(egui::DragValue::new(&mut number)).changed() {
+                        *value = number.to_string();
+                    }
+                }
+                FieldKind::Checkbox => {
+                    let mut checked = value == "1";
+                    if ui.checkbox(&mut checked, "").changed() {
+                        *value = if checked { "1" } else { "0" }.to_string();
+                    }
+                }
+                FieldKind::Choice => {
+                    egui::ComboBox::from_id_salt(("plugin_choice", plugin, command, &field.name))
+                        .selected_text(value.as_str())
+                        .show_ui(ui, |ui| {
+                            for option in &field.options {
+                                ui.selectable_value(value, option.clone(), option);
+                            }
+                        });
+                }
+            }
+            ui.end_row();
+        }
+    });
+}
+
+/// Buttons for the commands plugins put in the toolkit; returns the plugin and command clicked
+pub fn toolkit_buttons(ui: &mut Ui, plugins: &[Plugin]) -> Option<(usize, usize)> {
+    let mut clicked = None;
+    for (plugin_index, plugin) in plugins.iter().enumerate() {
+        for (command_index, command) in plugin.commands.iter().enumerate().filter(|(_, command)| command.toolkit) {
+            ui.vertical_centered(|ui| {
+                if ui
+                    .add(
+                        egui::Button::new(
+                            RichText::new(format!("{} {}", egui_phosphor::fill::PUZZLE_PIECE, command.label))
+                                .size(13.0),
+                        )
+                        .min_size(egui::vec2(120.0, 28.0)),
+                    )
+                    .on_hover_text(format!("{} ({})", command.description, plugin.name))
+                    .clicked()
+                {
+                    clicked = Some((plugin_index, command_index));
+                }
+            });
+        }
+    }
+    clicked
+}
@@ -0,0 +1,268 @@
+use crate::export::{ExportFormat, ExportPreset};
+use crate::gallery::{CaptureIndex, CaptureKind};
+use crate::t;
+use crate::utils::format_file_size;
+use egui::{Color32, RichText, Window};
+use std::path::PathBuf;
+
+pub enum RecordingLibraryAction {
+    None,
+    Export {
+        path: PathBuf,
+        format: ExportFormat,
+        preset: ExportPreset,
+    },
+}
+
+/// Table of the screen recordings in the capture directory, with their recording settings
+pub struct RecordingLibraryWindow {
+    visible: bool,
+    dir: PathBuf,
+    index: CaptureIndex,
+    pending_delete: Option<usize>,
+    error: Option<String>,
+    export_source: Option<PathBuf>,
+    export_format: ExportFormat,
+    export_preset: ExportPreset,
+    exporting: bool,
+    export_result: Option<Result<PathBuf, String>>,
+}
+
+impl Default for RecordingLibraryWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RecordingLibraryWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            dir: PathBuf::new(),
+            index: CaptureIndex::default(),
+            pending_delete: None,
+            error: None,
+            export_source: None,
+            export_format: ExportFormat::Gif,
+            export_preset: ExportPreset::Small,
+            exporting: false,
+            export_result: None,
+        }
+    }
+
+    pub fn set_export_result(&mut self, result: Result<PathBuf, String>) {
+        self.exporting = false;
+        self.export_result = Some(result);
+    }
+
+    /// Show the recordings of `dir`, reloading its index
+    pub fn open(&mut self, dir: PathBuf) {
+        self.visible = true;
+        self.dir = dir;
+        self.reload();
+    }
+
+    fn reload(&mut self) {
+        self.index = CaptureIndex::load(&self.dir);
+        self.pending_delete = None;
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> RecordingLibraryAction {
+        if !self.visible {
+            return RecordingLibraryAction::None;
+        }
+
+        let mut action = RecordingLibraryAction::None;
+        let mut open = self.visible;
+        let mut reload = false;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::FILM_STRIP, t!("recordings-title")))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(720.0, 400.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(self.dir.display().to_string()).monospace().size(11.0));
+                    if ui.button(t!("recordings-refresh")).clicked() {
+                        reload = true;
+                    }
+                });
+                if let Some(error) = &self.error {
+                    ui.label(RichText::new(error).color(Color32::RED));
+                }
+                if self.export_source.is_some() {
+                    ui.separator();
+                    action = self.show_export(ui);
+                }
+                ui.separator();
+
+                // Newest first
+                let recordings: Vec<usize> = (0..self.index.entries.len())
+                    .rev()
+                    .filter(|&index| self.index.entries[index].kind == CaptureKind::Recording)
+                    .collect();
+                if recordings.is_empty() {
+                    ui.label(RichText::new(t!("recordings-empty")).color(Color32::GRAY));
+                    return;
+                }
+
+                egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                    egui::Grid::new("recording_library_grid")
+                        .num_columns(8)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for header in [
+                                "recordings-file",
+                                "recordings-device",
+                                "recordings-recorded",
+                                "recordings-duration",
+                                "recordings-resolution",
+                                "recordings-bitrate",
+                                "recordings-size",
+                            ] {
+                                ui.label(RichText::new(t!(header)).strong());
+                            }
+                            ui.label("");
+                            ui.end_row();
+
+                            for index in recordings {
+                                self.show_row(ui, index);
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+
+        if reload {
+            self.error = None;
+            self.reload();
+        }
+        self.visible = open;
+        action
+    }
+
+    fn show_export(&mut self, ui: &mut egui::Ui) -> RecordingLibraryAction {
+        let mut action = RecordingLibraryAction::None;
+        let Some(source) = self.export_source.clone() else {
+            return action;
+        };
+
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(format!("{} {}", egui_phosphor::fill::EXPORT, t!("recordings-export"))).strong());
+            ui.label(source.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default());
+        });
+        ui.add_enabled_ui(!self.exporting, |ui| {
+            ui.horizontal(|ui| {
+                for format in ExportFormat::ALL {
+                    ui.radio_value(&mut self.export_format, format, format.label());
+                }
+                egui::ComboBox::from_id_salt("export_preset")
+                    .selected_text(self.export_preset.label())
+                    .show_ui(ui, |ui| {
+                        for preset in ExportPreset::ALL {
+                            ui.selectable_value(&mut self.export_preset, preset, preset.label());
+                        }
+                    });
+            });
+        });
+        ui.horizontal(|ui| {
+            if self.exporting {
+                ui.add(egui::Spinner::new().size(14.0));
+                ui.label(t!("recordings-converting"));
+            } else {
+                if ui.button(t!("recordings-convert", format = self.export_format.label())).clicked() {
+                    self.exporting = true;
+                    self.export_result = None;
+                    action = RecordingLibraryAction::Export {
+                        path: source.clone(),
+                        format: self.export_format,
+                        preset: self.export_preset,
+                    };
+                }
+                if ui.button(t!("recordings-close")).clicked() {
+                    self.export_source = None;
+                    self.export_result = None;
+                }
+            }
+        });
+
+        match &self.export_result {
+            Some(Ok(path)) => {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(t!("recordings-saved", path = path.display())).color(Color32::GREEN));
+                    if ui.small_button(egui_phosphor::fill::ARROW_SQUARE_OUT).on_hover_text(t!("recordings-open")).clicked()
+                        && let Err(e) = crate::utils::open_url(&path.to_string_lossy())
+                    {
+                        self.error = Some(e.to_string());
+                    }
+                    if ui.small_button(egui_phosphor::fill::FOLDER_OPEN).on_hover_text(t!("recordings-show-in-folder")).clicked()
+                        && let Err(e) = crate::utils::reveal_in_file_manager(path)
+                    {
+                        self.error = Some(e.to_string());
+                    }
+                });
+            }
+            Some(Err(e)) => {
+                ui.label(RichText::new(e).color(Color32::RED));
+            }
+            None => {}
+        }
+        action
+    }
+
+    fn show_row(&mut self, ui: &mut egui::Ui, index: usize) {
+        let entry = self.index.entries[index].clone();
+
+        ui.label(entry.file_name()).on_hover_text(entry.path.display().to_string());
+        ui.label(&entry.device);
+        ui.label(entry.captured_at.format("%Y-%m-%d %H:%M").to_string());
+        match &entry.details {
+            Some(details) => {
+                ui.label(format!("{}:{:02}", details.duration_secs / 60, details.duration_secs % 60));
+                ui.label(details.resolution.as_deref().unwrap_or("-"));
+                ui.label(format!("{:.1} Mbps", details.bitrate as f32 / 1_000_000.0));
+            }
+            // Recorded before details were tracked
+            None => {
+                ui.label("-");
+                ui.label("-");
+                ui.label("-");
+            }
+        }
+        let size = std::fs::metadata(&entry.path).map(|meta| meta.len()).unwrap_or(0);
+        ui.label(format_file_size(size));
+
+        ui.horizontal(|ui| {
+            if ui.small_button(egui_phosphor::fill::PLAY).on_hover_text(t!("recordings-play")).clicked()
+                && let Err(e) = crate::utils::open_url(&entry.path.to_string_lossy())
+            {
+                self.error = Some(e.to_string());
+            }
+            if ui.small_button(egui_phosphor::fill::FOLDER_OPEN).on_hover_text(t!("recordings-show-in-folder")).clicked()
+                && let Err(e) = crate::utils::reveal_in_file_manager(&entry.path)
+            {
+                self.error = Some(e.to_string());
+            }
+            if ui
+                .add_enabled(!self.exporting, egui::Button::new(egui_phosphor::fill::EXPORT).small())
+                .on_hover_text(t!("recordings-export-hint"))
+                .clicked()
+            {
+                self.export_source = Some(entry.path.clone());
+                self.export_result = None;
+            }
+            if self.pending_delete == Some(index) {
+                if ui.small_button(RichText::new(t!("recordings-delete-confirm")).color(Color32::RED)).clicked() {
+                    self.pending_delete = None;
+                    match self.index.delete(index).and_then(|_| self.index.save(&self.dir)) {
+                        Ok(()) => self.error = None,
+                        Err(e) => self.error = Some(e.to_string()),
+                    }
+                }
+            } else if ui.small_button(egui_phosphor::fill::TRASH).on_hover_text(t!("recordings-delete")).clicked() {
+                self.pending_delete = Some(index);
+            }
+        });
+    }
+}
@@ -0,0 +1,208 @@
+use crate::t;
+use crate::ui::panels::WirelessAdbAction;
+use egui::{Color32, RichText, Window};
+
+pub enum RemoteAssistAction {
+    None,
+    Wireless(WirelessAdbAction),
+    /// Launch the remote assistance template on this serial
+    Start(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Network,
+    DeveloperOptions,
+    Pair,
+    Connect,
+    Start,
+}
+
+impl Step {
+    const ALL: [Step; 5] = [Step::Network, Step::DeveloperOptions, Step::Pair, Step::Connect, Step::Start];
+
+    fn title(self) -> String {
+        match self {
+            Step::Network => t!("remote-assist-step-network"),
+            Step::DeveloperOptions => t!("remote-assist-step-developer"),
+            Step::Pair => t!("remote-assist-step-pair"),
+            Step::Connect => t!("remote-assist-step-connect"),
+            Step::Start => t!("remote-assist-step-start"),
+        }
+    }
+}
+
+/// Split `192.168.1.20:37123` into host and port
+fn parse_address(address: &str) -> Option<(String, u16)> {
+    let (host, port) = address.trim().rsplit_once(':')?;
+    let port = port.trim().parse().ok()?;
+    (!host.trim().is_empty()).then(|| (host.trim().to_string(), port))
+}
+
+/// Step-by-step guide for connecting to someone else's phone over wireless debugging,
+/// written to be read aloud over the phone to a non-technical person
+pub struct RemoteAssistWizard {
+    visible: bool,
+    step: Step,
+    pairing_address: String,
+    pairing_code: String,
+    connect_address: String,
+}
+
+impl Default for RemoteAssistWizard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RemoteAssistWizard {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            step: Step::Network,
+            pairing_address: String::new(),
+            pairing_code: String::new(),
+            connect_address: String::new(),
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.visible = true;
+    }
+
+    /// `connected` lists the serials adb currently reports as usable
+    pub fn show(&mut self, ctx: &egui::Context, connected: &[String]) -> RemoteAssistAction {
+        if !self.visible {
+            return RemoteAssistAction::None;
+        }
+
+        let mut action = RemoteAssistAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::HANDSHAKE, t!("remote-assist-title")))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(520.0, 400.0))
+            .show(ctx, |ui| {
+                ui.horizontal_wrapped(|ui| {
+                    for (index, step) in Step::ALL.into_iter().enumerate() {
+                        if index > 0 {
+                            ui.label(RichText::new(egui_phosphor::fill::CARET_RIGHT).color(Color32::GRAY));
+                        }
+                        ui.selectable_value(&mut self.step, step, format!("{}. {}", index + 1, step.title()));
+                    }
+                });
+                ui.separator();
+
+                match self.step {
+                    Step::Network => {
+                        ui.label(t!("remote-assist-network"));
+                        ui.add_space(4.0);
+                        bullet(ui, &t!("remote-assist-network-same"));
+                        bullet(ui, &t!("remote-assist-network-vpn"));
+                        bullet(ui, &t!("remote-assist-network-wifi"));
+                    }
+                    Step::DeveloperOptions => {
+                        ui.label(t!("remote-assist-developer"));
+                        ui.add_space(4.0);
+                        bullet(ui, &t!("remote-assist-developer-about"));
+                        bullet(ui, &t!("remote-assist-developer-build"));
+                        bullet(ui, &t!("remote-assist-developer-options"));
+                        bullet(ui, &t!("remote-assist-developer-wireless"));
+                    }
+                    Step::Pair => {
+                        ui.label(t!("remote-assist-pair"));
+                        ui.add_space(4.0);
+                        egui::Grid::new("remote_assist_pair_grid").num_columns(2).show(ui, |ui| {
+                            ui.label(t!("remote-assist-address"));
+                            ui.add(egui::TextEdit::singleline(&mut self.pairing_address).hint_text("192.168.1.20:37123"));
+                            ui.end_row();
+                            ui.label(t!("remote-assist-code"));
+                            ui.add(egui::TextEdit::singleline(&mut self.pairing_code).hint_text("123456"));
+                            ui.end_row();
+                        });
+                        let target = parse_address(&self.pairing_address);
+                        let code = self.pairing_code.trim().to_string();
+                        if ui
+                            .add_enabled(target.is_some() && !code.is_empty(), egui::Button::new(format!("{} {}", egui_phosphor::fill::LINK, t!("remote-assist-pair-button"))))
+                            .clicked()
+                            && let Some((ip, port)) = target
+                        {
+                            action = RemoteAssistAction::Wireless(WirelessAdbAction::Pair { ip: ip.clone(), port, code });
+                            // The connect address uses the same host but a different port
+                            if self.connect_address.is_empty() {
+                                self.connect_address = format!("{}:", ip);
+                            }
+                            self.step = Step::Connect;
+                        }
+                        ui.label(
+                            RichText::new(t!("remote-assist-pair-hint"))
+                                .size(11.0)
+                                .color(Color32::GRAY),
+                        );
+                    }
+                    Step::Connect => {
+                        ui.label(t!("remote-assist-connect"));
+                        ui.add_space(4.0);
+                        ui.horizontal(|ui| {
+                            ui.label(t!("remote-assist-address"));
+                            ui.add(egui::TextEdit::singleline(&mut self.connect_address).hint_text("192.168.1.20:41235"));
+                        });
+                        let target = parse_address(&self.connect_address);
+                        if ui
+                            .add_enabled(target.is_some(), egui::Button::new(format!("{} {}", egui_phosphor::fill::PLUGS_CONNECTED, t!("remote-assist-connect-button"))))
+                            .clicked()
+                            && let Some((ip, port)) = target
+                        {
+                            action = RemoteAssistAction::Wireless(WirelessAdbAction::Connect { ip, port });
+                        }
+                        if connected.iter().any(|serial| serial == self.connect_address.trim()) {
+                            ui.label(RichText::new(format!("{} {}", egui_phosphor::fill::CHECK_CIRCLE, t!("remote-assist-connected"))).color(Color32::GREEN));
+                        }
+                    }
+                    Step::Start => {
+                        ui.label(t!("remote-assist-start"));
+                        ui.add_space(4.0);
+                        let serial = self.connect_address.trim().to_string();
+                        let is_connected = connected.contains(&serial);
+                        if !is_connected {
+                            ui.label(RichText::new(t!("remote-assist-not-connected")).color(Color32::YELLOW));
+                        }
+                        if ui
+                            .add_enabled(is_connected, egui::Button::new(format!("{} {}", egui_phosphor::fill::PLAY, t!("remote-assist-start-button"))))
+                            .clicked()
+                        {
+                            action = RemoteAssistAction::Start(serial);
+                        }
+                        ui.label(
+                            RichText::new(t!("remote-assist-done"))
+                                .size(11.0)
+                                .color(Color32::GRAY),
+                        );
+                    }
+                }
+
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    let index = Step::ALL.iter().position(|step| *step == self.step).unwrap_or(0);
+                    if ui.add_enabled(index > 0, egui::Button::new(t!("remote-assist-back"))).clicked() {
+                        self.step = Step::ALL[index - 1];
+                    }
+                    if ui.add_enabled(index + 1 < Step::ALL.len(), egui::Button::new(t!("remote-assist-next"))).clicked() {
+                        self.step = Step::ALL[index + 1];
+                    }
+                });
+            });
+
+        self.visible = open;
+        action
+    }
+}
+
+fn bullet(ui: &mut egui::Ui, text: &str) {
+    ui.horizontal_top(|ui| {
+        ui.label("•");
+        ui.add(egui::Label::new(text).wrap());
+    });
+}
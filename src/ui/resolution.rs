@@ -0,0 +1,195 @@
+use crate::report::DisplayInfo;
+use crate::resolution::{parse_size, risks, Risk, DENSITY_PRESETS, SIZE_PRESETS};
+use crate::t;
+use egui::{Color32, RichText, Window};
+
+pub enum ResolutionAction {
+    None,
+    Refresh,
+    Apply { size: Option<(u32, u32)>, density: Option<u32> },
+    Reset,
+}
+
+/// Resolution and density overrides of the selected device
+pub struct ResolutionWindow {
+    visible: bool,
+    device: String,
+    info: Option<Result<DisplayInfo, String>>,
+    busy: bool,
+    width: u32,
+    height: u32,
+    density: u32,
+    /// Ticked to apply values that were flagged as risky
+    accept_risk: bool,
+}
+
+impl Default for ResolutionWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResolutionWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            device: String::new(),
+            info: None,
+            busy: false,
+            width: 0,
+            height: 0,
+            density: 0,
+            accept_risk: false,
+        }
+    }
+
+    pub fn open(&mut self, device: String) {
+        if device != self.device {
+            self.info = None;
+        }
+        self.visible = true;
+        self.device = device;
+    }
+
+    pub fn set_busy(&mut self) {
+        self.busy = true;
+    }
+
+    /// Current values of the device, which also become the values being edited
+    pub fn set_info(&mut self, info: Result<DisplayInfo, String>) {
+        if let Ok(info) = &info {
+            if let Some((width, height)) = current_size(info) {
+                (self.width, self.height) = (width, height);
+            }
+            if let Some(density) = info.override_density.or(info.physical_density) {
+                self.density = density;
+            }
+        }
+        self.info = Some(info);
+        self.busy = false;
+        self.accept_risk = false;
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> ResolutionAction {
+        if !self.visible {
+            return ResolutionAction::None;
+        }
+
+        let mut action = ResolutionAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::FRAME_CORNERS, t!("resolution-title")))
+            .id(egui::Id::new("resolution"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .default_width(380.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(&self.device).weak());
+                    if ui.add_enabled(!self.busy, egui::Button::new(t!("resolution-refresh"))).clicked() {
+                        action = ResolutionAction::Refresh;
+                    }
+                    if self.busy {
+                        ui.add(egui::Spinner::new().size(14.0));
+                    }
+                });
+                ui.separator();
+
+                let info = match &self.info {
+                    Some(Ok(info)) => info.clone(),
+                    Some(Err(e)) => {
+                        ui.label(RichText::new(e).color(Color32::RED));
+                        return;
+                    }
+                    None => return,
+                };
+                let physical = info.physical_size.as_deref().and_then(parse_size).unwrap_or_default();
+                let physical_density = info.physical_density.unwrap_or(self.density);
+
+                egui::Grid::new("resolution_current").num_columns(2).spacing([16.0, 4.0]).show(ui, |ui| {
+                    ui.label(t!("resolution-panel"));
+                    ui.label(format!("{}x{}, {} dpi", physical.0, physical.1, physical_density));
+                    ui.end_row();
+                    if info.override_size.is_some() || info.override_density.is_some() {
+                        ui.label(t!("resolution-override"));
+                        let size = info.override_size.clone().unwrap_or_else(|| "–".to_string());
+                        let density = info.override_density.map_or_else(|| "–".to_string(), |d| format!("{} dpi", d));
+                        ui.label(RichText::new(format!("{}, {}", size, density)).color(Color32::YELLOW));
+                        ui.end_row();
+                    }
+                });
+                ui.add_space(6.0);
+
+                ui.label(RichText::new(t!("resolution-size")).strong());
+                ui.horizontal_wrapped(|ui| {
+                    for (name, width, height) in SIZE_PRESETS {
+                        let selected = (self.width, self.height) == (width, height);
+                        let preset = ui.selectable_label(selected, name).on_hover_text(format!("{}x{}", width, height));
+                        if preset.clicked() {
+                            (self.width, self.height) = (width, height);
+                        }
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut self.width).range(1..=8192));
+                    ui.label("x");
+                    ui.add(egui::DragValue::new(&mut self.height).range(1..=8192));
+                });
+                ui.add_space(4.0);
+
+                ui.label(RichText::new(t!("resolution-density")).strong());
+                ui.horizontal_wrapped(|ui| {
+                    for density in DENSITY_PRESETS {
+                        ui.selectable_value(&mut self.density, density, density.to_string());
+                    }
+                    ui.add(egui::DragValue::new(&mut self.density).range(72..=1200).suffix(" dpi"));
+                });
+                ui.add_space(6.0);
+
+                let size = (self.width, self.height);
+                let risks = risks(physical, physical_density, size, self.density);
+                for risk in &risks {
+                    let text = match risk {
+                        Risk::TooSmall => t!("resolution-risk-small"),
+                        Risk::TooLarge => t!("resolution-risk-large"),
+                        Risk::AspectRatio => t!("resolution-risk-aspect"),
+                        Risk::DensityTooLow => t!("resolution-risk-density-low"),
+                        Risk::DensityTooHigh => t!("resolution-risk-density-high"),
+                    };
+                    let warning = format!("{} {}", egui_phosphor::fill::WARNING, text);
+                    ui.label(RichText::new(warning).color(Color32::ORANGE));
+                }
+                if !risks.is_empty() {
+                    ui.checkbox(&mut self.accept_risk, t!("resolution-accept-risk"));
+                }
+
+                ui.horizontal(|ui| {
+                    let current_density = info.override_density.or(info.physical_density);
+                    let changed = Some(size) != current_size(&info) || Some(self.density) != current_density;
+                    let allowed = !self.busy && changed && (risks.is_empty() || self.accept_risk);
+                    if ui.add_enabled(allowed, egui::Button::new(t!("resolution-apply"))).clicked() {
+                        action = ResolutionAction::Apply {
+                            size: (Some(size) != current_size(&info)).then_some(size),
+                            density: (Some(self.density) != current_density).then_some(self.density),
+                        };
+                    }
+                    let reset = format!("{} {}", egui_phosphor::fill::ARROW_COUNTER_CLOCKWISE, t!("resolution-reset"));
+                    let reset = egui::Button::new(RichText::new(reset).strong().color(Color32::WHITE))
+                        .fill(Color32::from_rgb(200, 60, 60))
+                        .min_size(egui::vec2(120.0, 28.0));
+                    if ui.add_enabled(!self.busy, reset).on_hover_text(t!("resolution-reset-hint")).clicked() {
+                        action = ResolutionAction::Reset;
+                    }
+                });
+                ui.label(RichText::new(t!("resolution-recovery")).size(11.0).weak());
+            });
+
+        self.visible = open;
+        action
+    }
+}
+
+fn current_size(info: &DisplayInfo) -> Option<(u32, u32)> {
+    info.override_size.as_deref().or(info.physical_size.as_deref()).and_then(parse_size)
+}
@@ -0,0 +1,288 @@
+use crate::image_diff::{compare, Diff, DEFAULT_THRESHOLD};
+use crate::t;
+use egui::{Color32, RichText, TextureHandle, Window};
+use image::RgbaImage;
+
+pub enum ScreenshotDiffAction {
+    None,
+    /// Take a screenshot of `device` for the before (0) or after (1) side
+    Capture { side: usize, device: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiffMode {
+    SideBySide,
+    Overlay,
+    Heatmap,
+}
+
+impl DiffMode {
+    fn label(self) -> String {
+        match self {
+            DiffMode::SideBySide => t!("screenshot-diff-side-by-side"),
+            DiffMode::Overlay => t!("screenshot-diff-overlay"),
+            DiffMode::Heatmap => t!("screenshot-diff-heatmap"),
+        }
+    }
+}
+
+/// One of the two screenshots and where it came from
+#[derive(Default)]
+struct Side {
+    image: Option<RgbaImage>,
+    texture: Option<TextureHandle>,
+    source: String,
+    device: String,
+    loading: bool,
+    error: Option<String>,
+}
+
+/// Two screenshots from files or devices, side by side, overlaid or as a heatmap of what changed
+pub struct ScreenshotDiffWindow {
+    visible: bool,
+    devices: Vec<String>,
+    sides: [Side; 2],
+    mode: DiffMode,
+    /// Opacity of the after screenshot over the before one
+    opacity: f32,
+    threshold: u8,
+    diff: Option<Diff>,
+    heatmap: Option<TextureHandle>,
+}
+
+impl Default for ScreenshotDiffWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScreenshotDiffWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            devices: Vec::new(),
+            sides: Default::default(),
+            mode: DiffMode::Heatmap,
+            opacity: 0.5,
+            threshold: DEFAULT_THRESHOLD,
+            diff: None,
+            heatmap: None,
+        }
+    }
+
+    /// Open with `device` picked for both sides where none is yet
+    pub fn open(&mut self, device: &str) {
+        self.visible = true;
+        for side in &mut self.sides {
+            if side.device.is_empty() {
+                side.device = device.to_string();
+            }
+        }
+    }
+
+    pub fn set_devices(&mut self, devices: Vec<String>) {
+        self.devices = devices;
+    }
+
+    pub fn set_loading(&mut self, side: usize) {
+        self.sides[side].loading = true;
+    }
+
+    pub fn set_capture(&mut self, side: usize, device: &str, result: Result<RgbaImage, String>) {
+        self.sides[side].loading = false;
+        let source = format!("{} · {}", device, chrono::Local::now().format("%H:%M:%S"));
+        self.set_image(side, result, source);
+    }
+
+    fn set_image(&mut self, side: usize, result: Result<RgbaImage, String>, source: String) {
+        let side = &mut self.sides[side];
+        match result {
+            Ok(image) => {
+                side.image = Some(image);
+                side.texture = None;
+                side.source = source;
+                side.error = None;
+                self.update_diff();
+            }
+            Err(e) => side.error = Some(e),
+        }
+    }
+
+    fn update_diff(&mut self) {
+        self.heatmap = None;
+        self.diff = match (&self.sides[0].image, &self.sides[1].image) {
+            (Some(before), Some(after)) => Some(compare(before, after, self.threshold)),
+            _ => None,
+        };
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> ScreenshotDiffAction {
+        if !self.visible {
+            return ScreenshotDiffAction::None;
+        }
+        for (index, side) in self.sides.iter_mut().enumerate() {
+            if side.texture.is_none()
+                && let Some(image) = &side.image
+            {
+                side.texture = Some(load_texture(ctx, &format!("screenshot_diff_{}", index), image));
+            }
+        }
+        if self.heatmap.is_none()
+            && let Some(diff) = &self.diff
+        {
+            self.heatmap = Some(load_texture(ctx, "screenshot_diff_heatmap", &diff.heatmap));
+        }
+
+        let mut action = ScreenshotDiffAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::GIT_DIFF, t!("screenshot-diff-title")))
+            .id(egui::Id::new("screenshot_diff"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(760.0, 720.0))
+            .show(ctx, |ui| {
+                ui.columns(2, |columns| {
+                    for (index, ui) in columns.iter_mut().enumerate() {
+                        if let Some(capture) = self.show_side(ui, index) {
+                            action = capture;
+                        }
+                    }
+                });
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    for mode in [DiffMode::SideBySide, DiffMode::Overlay, DiffMode::Heatmap] {
+                        ui.selectable_value(&mut self.mode, mode, mode.label());
+                    }
+                    ui.separator();
+                    match self.mode {
+                        DiffMode::Overlay => {
+                            ui.label(t!("screenshot-diff-opacity"));
+                            ui.add(egui::Slider::new(&mut self.opacity, 0.0..=1.0).show_value(false));
+                        }
+                        DiffMode::Heatmap => {
+                            ui.label(t!("screenshot-diff-threshold"));
+                            let slider = ui.add(egui::Slider::new(&mut self.threshold, 0..=128));
+                            // Recounted once the slider is let go, as a full screenshot takes a moment
+                            if slider.drag_stopped() || (slider.changed() && !slider.dragged()) {
+                                self.update_diff();
+                            }
+                        }
+                        DiffMode::SideBySide => {}
+                    }
+                });
+                if let Some(diff) = &self.diff {
+                    let summary = t!(
+                        "screenshot-diff-summary",
+                        percent = format!("{:.2}", diff.percent()),
+                        changed = diff.changed,
+                        total = diff.total
+                    );
+                    let color = if diff.changed == 0 { Color32::GREEN } else { Color32::ORANGE };
+                    ui.label(RichText::new(summary).strong().color(color));
+                    if diff.scaled {
+                        ui.label(RichText::new(t!("screenshot-diff-scaled")).size(11.0).color(Color32::GRAY));
+                    }
+                }
+                ui.separator();
+
+                egui::ScrollArea::both().auto_shrink([false, false]).show(ui, |ui| self.show_comparison(ui));
+            });
+
+        self.visible = open;
+        action
+    }
+
+    /// Where one side's screenshot comes from
+    fn show_side(&mut self, ui: &mut egui::Ui, index: usize) -> Option<ScreenshotDiffAction> {
+        let mut action = None;
+        let title = if index == 0 { t!("screenshot-diff-before") } else { t!("screenshot-diff-after") };
+        ui.label(RichText::new(title).strong());
+        let devices = &self.devices;
+        let side = &mut self.sides[index];
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_salt(("screenshot_diff_device", index))
+                .selected_text(side.device.as_str())
+                .width(140.0)
+                .show_ui(ui, |ui| {
+                    for serial in devices {
+                        ui.selectable_value(&mut side.device, serial.clone(), serial);
+                    }
+                });
+            let capture = format!("{} {}", egui_phosphor::fill::CAMERA, t!("screenshot-diff-capture"));
+            let allowed = !side.loading && devices.contains(&side.device);
+            if ui.add_enabled(allowed, egui::Button::new(capture)).clicked() {
+                action = Some(ScreenshotDiffAction::Capture { side: index, device: side.device.clone() });
+            }
+            if side.loading {
+                ui.add(egui::Spinner::new().size(14.0));
+            }
+        });
+        let mut opened = None;
+        let pick = format!("{} {}", egui_phosphor::fill::FOLDER_OPEN, t!("screenshot-diff-open"));
+        if ui.button(pick).clicked()
+            && let Some(path) = rfd::FileDialog::new().add_filter(t!("screenshot-diff-images"), &["png", "jpg", "jpeg", "webp"]).pick_file()
+        {
+            let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+            let image = image::open(&path).map(|image| image.to_rgba8()).map_err(|e| e.to_string());
+            opened = Some((image, name));
+        }
+        if let Some(e) = &side.error {
+            ui.label(RichText::new(e).color(Color32::RED));
+        } else if let Some(image) = &side.image {
+            let size = format!("{} ({}×{})", side.source, image.width(), image.height());
+            ui.label(RichText::new(size).monospace().size(11.0).weak());
+        }
+        if let Some((image, name)) = opened {
+            self.set_image(index, image, name);
+        }
+        action
+    }
+
+    fn show_comparison(&self, ui: &mut egui::Ui) {
+        let [before, after] = [&self.sides[0].texture, &self.sides[1].texture];
+        let (Some(before), Some(after)) = (before, after) else {
+            ui.label(RichText::new(t!("screenshot-diff-empty")).color(Color32::GRAY));
+            return;
+        };
+        let available = ui.available_size();
+        match self.mode {
+            DiffMode::SideBySide => {
+                ui.horizontal_top(|ui| {
+                    for texture in [before, after] {
+                        let size = fit(texture.size_vec2(), egui::vec2(available.x / 2.0 - 8.0, available.y));
+                        ui.add(egui::Image::new(texture).fit_to_exact_size(size));
+                    }
+                });
+            }
+            DiffMode::Overlay => {
+                // The after screenshot is stretched over the before one if their sizes differ
+                let size = fit(before.size_vec2(), available);
+                let response = ui.add(egui::Image::new(before).fit_to_exact_size(size));
+                let uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+                let tint = Color32::WHITE.gamma_multiply(self.opacity);
+                ui.painter().image(after.id(), response.rect, uv, tint);
+            }
+            DiffMode::Heatmap => {
+                if let Some(heatmap) = &self.heatmap {
+                    let size = fit(heatmap.size_vec2(), available);
+                    ui.add(egui::Image::new(heatmap).fit_to_exact_size(size));
+                }
+            }
+        }
+    }
+}
+
+/// `size` scaled down to fit `available`, never up
+fn fit(size: egui::Vec2, available: egui::Vec2) -> egui::Vec2 {
+    let scale = (available.x / size.x).min(available.y.max(200.0) / size.y).min(1.0);
+    size * scale
+}
+
+fn load_texture(ctx: &egui::Context, name: &str, image: &RgbaImage) -> TextureHandle {
+    let size = [image.width() as usize, image.height() as usize];
+    let color_image = egui::ColorImage::from_rgba_unmultiplied(size, image.as_raw());
+    ctx.load_texture(name, color_image, egui::TextureOptions::LINEAR)
+}
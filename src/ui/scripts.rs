@@ -0,0 +1,266 @@
+use crate::config::UserScript;
+use crate::device::Device;
+use crate::t;
+use egui::{Color32, RichText, Window};
+
+pub enum ScriptsAction {
+    None,
+    Run { serial: String, name: String, source: String },
+    Stop,
+    /// The edited list of scripts to store in the config
+    Save(Vec<UserScript>),
+}
+
+/// Lines of output kept for the running script
+const MAX_OUTPUT_LINES: usize = 500;
+
+const EXAMPLE: &str = r#"// `device` is the device the script runs on
+let model = device.shell("getprop ro.product.model");
+print(`Running on ${model}`);
+
+device.key("KEYCODE_HOME");
+sleep(500);
+device.tap(540, 1200);
+let path = screenshot();
+print(`Saved ${path}`);
+"#;
+
+/// Editor for Rhai scripts with their output, run on one device at a time
+pub struct ScriptsWindow {
+    visible: bool,
+    scripts: Vec<UserScript>,
+    /// Index of the script in the editor, or None for a new one
+    selected: Option<usize>,
+    draft: UserScript,
+    device: Option<String>,
+    /// Name of the script that is running
+    running: Option<String>,
+    output: Vec<String>,
+    status: Option<Result<String, String>>,
+}
+
+impl Default for ScriptsWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScriptsWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            scripts: Vec::new(),
+            selected: None,
+            draft: Self::new_script(),
+            device: None,
+            running: None,
+            output: Vec::new(),
+            status: None,
+        }
+    }
+
+    fn new_script() -> UserScript {
+        UserScript {
+            name: String::new(),
+            source: EXAMPLE.to_string(),
+            pinned: false,
+        }
+    }
+
+    /// Open on `device`, or on the device chosen last time
+    pub fn open(&mut self, scripts: Vec<UserScript>, device: Option<String>) {
+        if !self.visible {
+            self.scripts = scripts;
+            self.selected = None;
+            self.draft = Self::new_script();
+        }
+        if device.is_some() && self.running.is_none() {
+            self.device = device;
+        }
+        self.visible = true;
+    }
+
+    /// A script started from elsewhere, e.g. a quick action button
+    pub fn started(&mut self, name: &str) {
+        self.running = Some(name.to_string());
+        self.output.clear();
+        self.status = None;
+    }
+
+    pub fn append_output(&mut self, line: String) {
+        self.output.push(line);
+        if self.output.len() > MAX_OUTPUT_LINES {
+            self.output.drain(..self.output.len() - MAX_OUTPUT_LINES);
+        }
+    }
+
+    pub fn finish(&mut self, result: Result<(), String>) {
+        self.running = None;
+        self.status = Some(result.map(|()| t!("scripts-finished")));
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, devices: &[Device]) -> ScriptsAction {
+        if !self.visible {
+            return ScriptsAction::None;
+        }
+
+        let mut action = ScriptsAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::CODE, t!("scripts-title")))
+            .id(egui::Id::new("scripts"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(720.0, 520.0))
+            .show(ctx, |ui| {
+                ui.horizontal_top(|ui| {
+                    ui.vertical(|ui| {
+                        ui.set_width(160.0);
+                        self.show_list(ui, &mut action);
+                    });
+                    ui.separator();
+                    ui.vertical(|ui| {
+                        self.show_editor(ui, devices, &mut action);
+                    });
+                });
+            });
+
+        self.visible = open;
+        action
+    }
+
+    fn show_list(&mut self, ui: &mut egui::Ui, action: &mut ScriptsAction) {
+        if ui.button(format!("{} {}", egui_phosphor::fill::PLUS, t!("scripts-new"))).clicked() {
+            self.selected = None;
+            self.draft = Self::new_script();
+        }
+        ui.separator();
+        if self.scripts.is_empty() {
+            ui.label(RichText::new(t!("scripts-empty")).color(Color32::GRAY));
+        }
+        let mut pinned_changed = false;
+        for (index, script) in self.scripts.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                let pin = RichText::new(egui_phosphor::fill::PUSH_PIN)
+                    .color(if script.pinned { ui.visuals().selection.bg_fill } else { Color32::GRAY });
+                if ui.small_button(pin).on_hover_text(t!("scripts-pin")).clicked() {
+                    script.pinned = !script.pinned;
+                    pinned_changed = true;
+                }
+                if ui.selectable_label(self.selected == Some(index), &script.name).clicked() {
+                    self.selected = Some(index);
+                    self.draft = script.clone();
+                }
+            });
+        }
+        if pinned_changed {
+            if let Some(index) = self.selected {
+                self.draft.pinned = self.scripts[index].pinned;
+            }
+            *action = ScriptsAction::Save(self.scripts.clone());
+        }
+    }
+
+    fn show_editor(&mut self, ui: &mut egui::Ui, devices: &[Device], action: &mut ScriptsAction) {
+        ui.horizontal(|ui| {
+            ui.label(t!("scripts-name"));
+            ui.add(egui::TextEdit::singleline(&mut self.draft.name).desired_width(180.0));
+            ui.checkbox(&mut self.draft.pinned, t!("scripts-pin"));
+        });
+
+        egui::ScrollArea::vertical().id_salt("script_source").max_height(260.0).show(ui, |ui| {
+            ui.add(
+                egui::TextEdit::multiline(&mut self.draft.source)
+                    .code_editor()
+                    .desired_rows(14)
+                    .desired_width(f32::INFINITY),
+            );
+        });
+
+        ui.horizontal(|ui| {
+            let name = self.draft.name.trim().to_string();
+            if ui.add_enabled(!name.is_empty(), egui::Button::new(t!("scripts-save"))).clicked() {
+                self.draft.name = name.clone();
+                let existing = self.scripts.iter().position(|script| script.name == name);
+                // Saving under another script's name replaces that script
+                match self.selected.or(existing) {
+                    Some(index) => self.scripts[index] = self.draft.clone(),
+                    None => self.scripts.push(self.draft.clone()),
+                }
+                if let (Some(selected), Some(existing)) = (self.selected, existing)
+                    && selected != existing
+                {
+                    self.scripts.remove(existing);
+                }
+                self.selected = self.scripts.iter().position(|script| script.name == name);
+                *action = ScriptsAction::Save(self.scripts.clone());
+            }
+            if let Some(index) = self.selected
+                && self.running.is_none()
+                && ui.button(t!("scripts-delete")).clicked()
+            {
+                self.scripts.remove(index);
+                self.selected = None;
+                self.draft = Self::new_script();
+                *action = ScriptsAction::Save(self.scripts.clone());
+            }
+            if ui.button(format!("{} {}", egui_phosphor::fill::CHECK_CIRCLE, t!("scripts-check"))).clicked() {
+                self.status = Some(crate::scripting::check(&self.draft.source).map(|()| t!("scripts-check-ok")));
+            }
+        });
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            ui.label(t!("scripts-device"));
+            let selected_text = self.device.clone().unwrap_or_else(|| t!("macros-choose-device"));
+            ui.add_enabled_ui(self.running.is_none(), |ui| {
+                egui::ComboBox::from_id_salt("script_device").selected_text(selected_text).show_ui(ui, |ui| {
+                    for device in devices.iter().filter(|device| device.is_usable()) {
+                        let chosen = self.device.as_deref() == Some(device.identifier.as_str());
+                        let label = format!("{} ({})", device.model, device.identifier);
+                        if ui.selectable_label(chosen, label).clicked() {
+                            self.device = Some(device.identifier.clone());
+                        }
+                    }
+                });
+            });
+            match &self.running {
+                Some(name) => {
+                    if ui.button(format!("{} {}", egui_phosphor::fill::STOP, t!("scripts-stop"))).clicked() {
+                        *action = ScriptsAction::Stop;
+                    }
+                    ui.spinner();
+                    ui.label(RichText::new(name).weak());
+                }
+                None => {
+                    let run = egui::Button::new(format!("{} {}", egui_phosphor::fill::PLAY, t!("scripts-run")));
+                    if ui.add_enabled(self.device.is_some(), run).clicked()
+                        && let Some(serial) = self.device.clone()
+                    {
+                        let name = self.draft.name.trim();
+                        let name = if name.is_empty() { t!("scripts-untitled") } else { name.to_string() };
+                        self.started(&name);
+                        *action = ScriptsAction::Run { serial, name, source: self.draft.source.clone() };
+                    }
+                }
+            }
+        });
+
+        match &self.status {
+            Some(Ok(message)) => {
+                ui.label(RichText::new(message).color(Color32::GREEN));
+            }
+            Some(Err(error)) => {
+                ui.label(RichText::new(error).color(Color32::RED));
+            }
+            None => {}
+        }
+        egui::ScrollArea::vertical().id_salt("script_output").stick_to_bottom(true).show(ui, |ui| {
+            ui.set_min_width(ui.available_width());
+            for line in &self.output {
+                ui.label(RichText::new(line).monospace());
+            }
+        });
+    }
+}
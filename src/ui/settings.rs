@@ -1,16 +1,108 @@
-use crate::config::AppConfig;
-use egui::{Ui, Window};
-use std::sync::Arc;
+use crate::bridge::{check_tool_version, ToolVersion};
+use crate::config::{AppConfig, SessionTemplate};
+use crate::config_bundle::{apply_bundle, export_bundle, read_bundle, ConfigBundle, ImportMode};
+use crate::t;
+use crate::ui::help::{help_button, HelpTopic};
+use crate::ui::Tool;
+use egui::{Color32, RichText, Ui, Window};
+use std::sync::{mpsc, Arc};
 use tokio::sync::Mutex;
 
 pub struct SettingsWindow {
     visible: bool,
     config: Arc<Mutex<AppConfig>>,
     just_saved: bool,
+    redetect_requested: bool,
+    install_requested: Option<Tool>,
+    update_check_requested: bool,
+    help_requested: Option<HelpTopic>,
+    new_template_name: String,
+    transfer: TransferState,
+    tool_checks: ToolChecks,
+}
+
+/// Outcome of testing a tool path: the path tested and its version or error
+type ToolCheck = (String, Result<ToolVersion, String>);
+
+/// "Test" results for the adb and scrcpy paths, delivered from a background thread
+struct ToolChecks {
+    adb: Option<ToolCheck>,
+    scrcpy: Option<ToolCheck>,
+    running: usize,
+    sender: mpsc::Sender<(bool, ToolCheck)>,
+    receiver: mpsc::Receiver<(bool, ToolCheck)>,
+    /// Run both checks the next time the config is available
+    pending: bool,
+    /// Save was pressed once with a failing path
+    confirm_save: bool,
+}
+
+impl ToolChecks {
+    fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            adb: None,
+            scrcpy: None,
+            running: 0,
+            sender,
+            receiver,
+            pending: false,
+            confirm_save: false,
+        }
+    }
+
+    fn start(&mut self, adb: bool, path: &str, ctx: &egui::Context) {
+        let path = path.trim().to_string();
+        let sender = self.sender.clone();
+        let ctx = ctx.clone();
+        self.running += 1;
+        std::thread::spawn(move || {
+            let result = check_tool_version(&path, adb);
+            let _ = sender.send((adb, (path, result)));
+            ctx.request_repaint();
+        });
+    }
+
+    fn receive(&mut self) {
+        while let Ok((adb, check)) = self.receiver.try_recv() {
+            self.running = self.running.saturating_sub(1);
+            if adb {
+                self.adb = Some(check);
+            } else {
+                self.scrcpy = Some(check);
+            }
+        }
+    }
+
+    /// Result for `path`, ignoring checks of a path that has since been edited
+    fn result(&self, adb: bool, path: &str) -> Option<&Result<ToolVersion, String>> {
+        let check = if adb { &self.adb } else { &self.scrcpy };
+        check.as_ref().filter(|(tested, _)| tested == path.trim()).map(|(_, result)| result)
+    }
+
+    fn has_failure(&self, config: &AppConfig) -> bool {
+        [(true, &config.adb_path), (false, &config.scrcpy_path)]
+            .into_iter()
+            .any(|(adb, path)| matches!(self.result(adb, path.as_deref().unwrap_or_default()), Some(Err(_))))
+    }
+}
+
+/// Export/import of the settings to another machine
+#[derive(Default)]
+struct TransferState {
+    /// A read bundle waiting for the merge-or-replace choice, with its validation warnings
+    pending: Option<(ConfigBundle, Vec<String>)>,
+    message: Option<Result<String, String>>,
 }
 
 enum SettingsResult {
     Save,
+    /// Settings were imported and saved; apply them but keep the window open
+    Imported,
+    Redetect,
+    Install(Tool),
+    CheckUpdates,
+    Help(HelpTopic),
     Close,
     Reset,
     Nothing,
@@ -22,6 +114,13 @@ impl SettingsWindow {
             visible: false,
             config,
             just_saved: false,
+            redetect_requested: false,
+            install_requested: None,
+            update_check_requested: false,
+            help_requested: None,
+            new_template_name: String::new(),
+            transfer: TransferState::default(),
+            tool_checks: ToolChecks::new(),
         }
     }
 
@@ -31,21 +130,51 @@ impl SettingsWindow {
         }
 
         let mut open = self.visible;
+        self.tool_checks.receive();
         let config_lock = self.config.try_lock();
         if let Ok(mut config) = config_lock {
-            let response = Window::new("Settings")
+            if std::mem::take(&mut self.tool_checks.pending) {
+                for (adb, path) in [(true, config.adb_path.clone()), (false, config.scrcpy_path.clone())] {
+                    if let Some(path) = path.filter(|path| !path.trim().is_empty()) {
+                        self.tool_checks.start(adb, &path, ctx);
+                    }
+                }
+            }
+            let response = Window::new(t!("settings-window"))
+                .id(egui::Id::new("settings_window"))
                 .open(&mut open)
                 .resizable(true)
                 .default_size([400.0, 500.0])
-                .show(ctx, |ui| show_settings_content(ui, &mut config));
+                .show(ctx, |ui| {
+                    show_settings_content(ui, &mut config, &mut self.new_template_name, &mut self.transfer, &mut self.tool_checks)
+                });
 
             if let Some(inner) = response.and_then(|r| r.inner) {
                 match inner {
                     SettingsResult::Save => {
+                        self.tool_checks.confirm_save = false;
                         config.save().ok();
                         self.visible = false;
                         self.just_saved = true;
                     }
+                    SettingsResult::Imported => {
+                        if let Err(e) = config.save() {
+                            self.transfer.message = Some(Err(t!("settings-import-save-failed", error = e)));
+                        }
+                        self.just_saved = true;
+                    }
+                    SettingsResult::Redetect => {
+                        self.redetect_requested = true;
+                    }
+                    SettingsResult::Install(tool) => {
+                        self.install_requested = Some(tool);
+                    }
+                    SettingsResult::CheckUpdates => {
+                        self.update_check_requested = true;
+                    }
+                    SettingsResult::Help(topic) => {
+                        self.help_requested = Some(topic);
+                    }
                     SettingsResult::Close => {
                         self.visible = false;
                     }
@@ -64,6 +193,24 @@ impl SettingsWindow {
 
     pub fn open(&mut self) {
         self.visible = true;
+        self.tool_checks.pending = true;
+        self.tool_checks.confirm_save = false;
+    }
+
+    pub fn take_redetect_requested(&mut self) -> bool {
+        std::mem::take(&mut self.redetect_requested)
+    }
+
+    pub fn take_install_requested(&mut self) -> Option<Tool> {
+        self.install_requested.take()
+    }
+
+    pub fn take_update_check_requested(&mut self) -> bool {
+        std::mem::take(&mut self.update_check_requested)
+    }
+
+    pub fn take_help_requested(&mut self) -> Option<HelpTopic> {
+        self.help_requested.take()
     }
 
     pub fn take_just_saved(&mut self) -> bool {
@@ -73,36 +220,172 @@ impl SettingsWindow {
     }
 }
 
-fn show_settings_content(ui: &mut Ui, config: &mut AppConfig) -> SettingsResult {
+fn show_settings_content(
+    ui: &mut Ui,
+    config: &mut AppConfig,
+    new_template_name: &mut String,
+    transfer: &mut TransferState,
+    tool_checks: &mut ToolChecks,
+) -> SettingsResult {
     let mut result = SettingsResult::Nothing;
 
-    ui.heading("Application Settings");
+    ui.heading(t!("settings-title"));
 
     egui::ScrollArea::vertical().show(ui, |ui| {
         // Paths section
         ui.group(|ui| {
-            ui.heading("Paths");
+            ui.heading(t!("settings-paths"));
+
+            ui.label(t!("settings-adb-path"));
+            show_tool_path(ui, config.adb_path.get_or_insert_with(String::new), true, tool_checks);
+
+            ui.label(t!("settings-scrcpy-path"));
+            show_tool_path(ui, config.scrcpy_path.get_or_insert_with(String::new), false, tool_checks);
 
-            ui.label("ADB Path:");
+            ui.label(t!("settings-gnirehtet-path"));
             ui.horizontal(|ui| {
-                ui.text_edit_singleline(config.adb_path.get_or_insert_with(String::new));
-                if ui.button("Browse").clicked() {
-                    // TODO: Implement file picker
+                let path = config.gnirehtet_path.get_or_insert_with(String::new);
+                ui.add(egui::TextEdit::singleline(path).hint_text(t!("settings-path-auto")));
+                if ui.button(t!("settings-browse")).clicked()
+                    && let Some(picked) = rfd::FileDialog::new().set_title(t!("settings-locate", tool = "gnirehtet")).pick_file()
+                {
+                    *path = picked.display().to_string();
+                }
+                if ui
+                    .link(t!("settings-download"))
+                    .on_hover_text(crate::gnirehtet::DOWNLOAD_URL)
+                    .clicked()
+                {
+                    let _ = crate::utils::open_url(crate::gnirehtet::DOWNLOAD_URL);
                 }
             });
 
-            ui.label("Scrcpy Path:");
             ui.horizontal(|ui| {
-                ui.text_edit_singleline(config.scrcpy_path.get_or_insert_with(String::new));
-                if ui.button("Browse").clicked() {
-                    // TODO: Implement file picker
+                if ui
+                    .button(t!("settings-redetect"))
+                    .on_hover_text(t!("settings-redetect-hint"))
+                    .clicked()
+                {
+                    result = SettingsResult::Redetect;
+                }
+                for tool in [Tool::Adb, Tool::Scrcpy] {
+                    if ui
+                        .button(format!("{} {}", egui_phosphor::fill::DOWNLOAD_SIMPLE, t!("settings-download-tool", tool = tool.name())))
+                        .on_hover_text(t!("settings-download-tool-hint", package = tool.package().name()))
+                        .clicked()
+                    {
+                        result = SettingsResult::Install(tool);
+                    }
                 }
             });
         });
 
+        // Remote adb server
+        ui.group(|ui| {
+            ui.heading(t!("settings-remote-adb"));
+            ui.checkbox(&mut config.remote_adb.enabled, t!("settings-remote-adb-enable"));
+            ui.add_enabled_ui(config.remote_adb.enabled, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(t!("settings-remote-adb-host"));
+                    ui.add(egui::TextEdit::singleline(&mut config.remote_adb.host).desired_width(160.0));
+                    ui.label(t!("settings-remote-adb-port"));
+                    ui.add(egui::DragValue::new(&mut config.remote_adb.port).range(1..=65535));
+                });
+            });
+            ui.label(egui::RichText::new(t!("settings-remote-adb-hint")).size(11.0).weak());
+        });
+
+        // Control API
+        ui.group(|ui| {
+            ui.heading(t!("settings-api"));
+            let api = &mut config.control_api;
+            if ui.checkbox(&mut api.enabled, t!("settings-api-enable")).changed() && api.token.is_empty() {
+                api.token = crate::config::ControlApi::new_token();
+            }
+            ui.add_enabled_ui(api.enabled, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(t!("settings-api-port"));
+                    ui.add(egui::DragValue::new(&mut api.port).range(1024..=65535));
+                });
+                ui.horizontal(|ui| {
+                    ui.label(t!("settings-api-token"));
+                    ui.label(RichText::new(&api.token).monospace());
+                    if ui
+                        .small_button(egui_phosphor::fill::COPY)
+                        .on_hover_text(t!("settings-api-copy"))
+                        .clicked()
+                    {
+                        ui.ctx().copy_text(api.token.clone());
+                    }
+                    if ui
+                        .small_button(egui_phosphor::fill::ARROWS_CLOCKWISE)
+                        .on_hover_text(t!("settings-api-new-token"))
+                        .clicked()
+                    {
+                        api.token = crate::config::ControlApi::new_token();
+                    }
+                });
+            });
+            ui.label(RichText::new(t!("settings-api-hint")).size(11.0).weak());
+        });
+
+        // Captures section
+        ui.group(|ui| {
+            ui.heading(t!("settings-captures"));
+
+            ui.label(t!("settings-capture-folder"));
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(config.screenshot_dir.get_or_insert_with(String::new))
+                        .hint_text(t!("settings-capture-folder-hint")),
+                );
+                if ui.button(t!("settings-browse")).clicked()
+                    && let Some(dir) = rfd::FileDialog::new().pick_folder()
+                {
+                    config.screenshot_dir = Some(dir.to_string_lossy().to_string());
+                }
+            });
+
+            ui.label(t!("settings-screenshot-template"));
+            ui.text_edit_singleline(&mut config.screenshot_template);
+            let placeholders: Vec<String> = crate::utils::FILENAME_PLACEHOLDERS
+                .iter()
+                .map(|(placeholder, meaning)| format!("{} = {}", placeholder, meaning))
+                .collect();
+            ui.label(egui::RichText::new(placeholders.join(", ")).size(11.0).weak());
+            let preview = crate::utils::expand_filename_template(
+                &config.screenshot_template,
+                "R58M123ABC",
+                "Pixel_8",
+                chrono::Local::now(),
+                "png",
+            );
+            ui.label(egui::RichText::new(t!("settings-template-example", example = preview)).size(11.0).monospace());
+            ui.checkbox(&mut config.screenshot_to_clipboard, t!("settings-screenshot-clipboard"));
+
+            ui.label(t!("settings-bugreport-folder"));
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(config.bugreport_dir.get_or_insert_with(String::new))
+                        .hint_text(t!("settings-bugreport-folder-hint")),
+                );
+                if ui.button(t!("settings-browse")).clicked()
+                    && let Some(dir) = rfd::FileDialog::new().pick_folder()
+                {
+                    config.bugreport_dir = Some(dir.to_string_lossy().to_string());
+                }
+            });
+            ui.checkbox(&mut config.bugreport_open_folder, t!("settings-bugreport-open-folder"));
+        });
+
         // Video settings
         ui.group(|ui| {
-            ui.heading("Video Settings");
+            ui.horizontal(|ui| {
+                ui.heading(t!("settings-video"));
+                if help_button(ui, HelpTopic::ScrcpyOptions) {
+                    result = SettingsResult::Help(HelpTopic::ScrcpyOptions);
+                }
+            });
 
             // Bitrate selection with K/M units
             let mut bitrate_value: u32 = {
@@ -123,8 +406,8 @@ fn show_settings_content(ui: &mut Ui, config: &mut AppConfig) -> SettingsResult
             };
 
             ui.horizontal(|ui| {
-                ui.label("Bitrate:");
-                ui.add(egui::Slider::new(&mut bitrate_value, 100..=20000).text("Value"));
+                ui.label(t!("settings-bitrate"));
+                ui.add(egui::Slider::new(&mut bitrate_value, 100..=20000).text(t!("settings-bitrate-value")));
                 egui::ComboBox::from_id_salt("bitrate_unit_combo")
                     .selected_text(bitrate_unit)
                     .show_ui(ui, |ui| {
@@ -138,27 +421,38 @@ fn show_settings_content(ui: &mut Ui, config: &mut AppConfig) -> SettingsResult
                 format!("{}K", bitrate_value)
             };
             config.bitrate = bitrate_str;
-            ui.label(format!("Current: {}", config.bitrate));
+            ui.label(t!("settings-bitrate-current", bitrate = config.bitrate));
 
-            ui.label("Orientation:");
+            ui.horizontal(|ui| {
+                ui.label(t!("settings-video-codec"));
+                egui::ComboBox::from_id_salt("video_codec_combo")
+                    .selected_text(config.video_codec.clone())
+                    .show_ui(ui, |ui| {
+                        for codec in crate::config::VIDEO_CODECS {
+                            ui.selectable_value(&mut config.video_codec, codec.to_string(), codec);
+                        }
+                    });
+            });
+
+            ui.label(t!("settings-orientation"));
             let orientations = [
-                (None, "Default"),
-                (Some("0"), "0°"),
-                (Some("90"), "90°"),
-                (Some("180"), "180°"),
-                (Some("270"), "270°"),
-                (Some("flip0"), "Flip 0°"),
-                (Some("flip90"), "Flip 90°"),
-                (Some("flip180"), "Flip 180°"),
-                (Some("flip270"), "Flip 270°"),
+                (None, t!("settings-orientation-default")),
+                (Some("0"), "0°".to_string()),
+                (Some("90"), "90°".to_string()),
+                (Some("180"), "180°".to_string()),
+                (Some("270"), "270°".to_string()),
+                (Some("flip0"), t!("settings-orientation-flip", angle = 0)),
+                (Some("flip90"), t!("settings-orientation-flip", angle = 90)),
+                (Some("flip180"), t!("settings-orientation-flip", angle = 180)),
+                (Some("flip270"), t!("settings-orientation-flip", angle = 270)),
             ];
             egui::ComboBox::from_id_salt("orientation_combo")
                 .selected_text(
                     orientations
                         .iter()
                         .find(|(val, _)| val.as_ref().map(|v| v.to_string()) == config.orientation)
-                        .map(|(_, label)| *label)
-                        .unwrap_or("Default"),
+                        .map(|(_, label)| label.clone())
+                        .unwrap_or_else(|| t!("settings-orientation-default")),
                 )
                 .show_ui(ui, |ui| {
                     for (val, label) in orientations.iter() {
@@ -167,20 +461,20 @@ fn show_settings_content(ui: &mut Ui, config: &mut AppConfig) -> SettingsResult
                             .as_ref()
                             .map(|v| v == &val.unwrap_or("").to_string())
                             .unwrap_or(val.is_none());
-                        if ui.selectable_label(selected, *label).clicked() {
+                        if ui.selectable_label(selected, label.as_str()).clicked() {
                             config.orientation = val.map(|v| v.to_string());
                         }
                     }
                 });
 
-            ui.checkbox(&mut config.show_touches, "Show touches");
-            ui.checkbox(&mut config.turn_screen_off, "Turn screen off");
-            ui.checkbox(&mut config.fullscreen, "Fullscreen");
+            ui.checkbox(&mut config.show_touches, t!("settings-show-touches"));
+            ui.checkbox(&mut config.turn_screen_off, t!("settings-turn-screen-off"));
+            ui.checkbox(&mut config.fullscreen, t!("settings-fullscreen"));
 
-            ui.label("Max dimension:");
+            ui.label(t!("settings-max-dimension"));
             ui.horizontal(|ui| {
                 let mut custom_dim = config.dimension.is_some();
-                if ui.checkbox(&mut custom_dim, "Custom").changed() {
+                if ui.checkbox(&mut custom_dim, t!("settings-max-dimension-custom")).changed() {
                     if !custom_dim {
                         config.dimension = None;
                     } else {
@@ -196,46 +490,280 @@ fn show_settings_content(ui: &mut Ui, config: &mut AppConfig) -> SettingsResult
                 }
             });
 
-            ui.checkbox(&mut config.force_adb_forward, "Force ADB Forward (--force-adb-forward)");
+            ui.checkbox(&mut config.force_adb_forward, t!("settings-force-adb-forward"));
+            ui.checkbox(&mut config.legacy_audio, t!("settings-legacy-audio"))
+                .on_hover_text(t!("settings-legacy-audio-hint"));
+            if config.legacy_audio {
+                ui.horizontal(|ui| {
+                    ui.label(t!("settings-sndcpy-apk"));
+                    let path = config.sndcpy_apk.get_or_insert_with(String::new);
+                    ui.add(egui::TextEdit::singleline(path).hint_text(t!("settings-path-auto")));
+                    if ui.button(t!("settings-browse")).clicked()
+                        && let Some(picked) = rfd::FileDialog::new()
+                            .set_title(t!("settings-locate", tool = "sndcpy.apk"))
+                            .add_filter("APK", &["apk"])
+                            .pick_file()
+                    {
+                        *path = picked.display().to_string();
+                    }
+                    if ui.link(t!("settings-download")).on_hover_text(crate::sndcpy::DOWNLOAD_URL).clicked() {
+                        let _ = crate::utils::open_url(crate::sndcpy::DOWNLOAD_URL);
+                    }
+                });
+            }
+            ui.checkbox(&mut config.auto_start_enabled, t!("settings-auto-start"))
+                .on_hover_text(t!("settings-auto-start-hint"));
+        });
+
+        // scrcpy shortcut modifier
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.heading(t!("settings-mirror-shortcuts"));
+                if help_button(ui, HelpTopic::ScrcpyOptions) {
+                    result = SettingsResult::Help(HelpTopic::ScrcpyOptions);
+                }
+            });
+            show_shortcut_editor(ui, &mut config.shortcut_mods);
+            ui.separator();
+            show_capture_hotkeys(ui, &mut config.capture_hotkeys);
+        });
+
+        // Session templates
+        ui.group(|ui| {
+            ui.heading(t!("settings-templates"));
+            show_template_editor(ui, config, new_template_name);
         });
 
         // Panels
         ui.group(|ui| {
-            ui.heading("Panels");
-            ui.checkbox(&mut config.panels.swipe, "Swipe Panel");
-            ui.checkbox(&mut config.panels.toolkit, "Toolkit Panel");
-            ui.checkbox(&mut config.panels.bottom, "Bottom Panel");
+            ui.heading(t!("settings-panels"));
+            ui.checkbox(&mut config.panels.swipe, t!("settings-panel-swipe"));
+            ui.checkbox(&mut config.panels.toolkit, t!("settings-panel-toolkit"));
+            ui.checkbox(&mut config.panels.bottom, t!("settings-panel-bottom"));
+            ui.checkbox(&mut config.device_thumbnails, t!("settings-device-thumbnails"));
+            ui.horizontal(|ui| {
+                ui.label(t!("settings-device-poll"));
+                ui.add(egui::DragValue::new(&mut config.device_poll_secs).range(1..=60).suffix(" s"))
+                    .on_hover_text(t!("settings-device-poll-hint"));
+            });
+            ui.horizontal(|ui| {
+                if ui.button(t!("settings-panel-layout-reset")).clicked() {
+                    config.dock = crate::dock::default_layout();
+                }
+                ui.label(egui::RichText::new(t!("settings-panel-layout-hint")).size(11.0).weak());
+            });
+        });
+
+        // Wireless ADB
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.heading(t!("settings-wireless"));
+                if help_button(ui, HelpTopic::Wireless) {
+                    result = SettingsResult::Help(HelpTopic::Wireless);
+                }
+            });
+            ui.checkbox(
+                &mut config.wireless_adb.reconnect_on_startup,
+                t!("settings-wireless-reconnect-startup"),
+            );
+            ui.checkbox(&mut config.wireless_adb.auto_reconnect, t!("settings-auto-reconnect"));
+            if config.wireless_adb.saved_endpoints.is_empty() {
+                ui.label(t!("settings-wireless-none"));
+            } else {
+                let mut forget = None;
+                for (index, endpoint) in config.wireless_adb.saved_endpoints.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.label(endpoint.display_name());
+                        if endpoint.nickname.is_some() {
+                            ui.label(egui::RichText::new(&endpoint.endpoint).weak());
+                        }
+                        if ui.small_button(t!("settings-wireless-forget")).clicked() {
+                            forget = Some(index);
+                        }
+                    });
+                }
+                if let Some(index) = forget {
+                    config.wireless_adb.saved_endpoints.remove(index);
+                }
+            }
         });
 
         // Extra arguments
         ui.group(|ui| {
-            ui.heading("Extra Arguments");
-            ui.label("Additional scrcpy arguments:");
+            ui.horizontal(|ui| {
+                ui.heading(t!("settings-extra-args"));
+                if help_button(ui, HelpTopic::ScrcpyOptions) {
+                    result = SettingsResult::Help(HelpTopic::ScrcpyOptions);
+                }
+            });
+            ui.label(t!("settings-extra-args-label"));
             ui.text_edit_multiline(&mut config.extra_args);
         });
 
         // Theme
         ui.group(|ui| {
-            ui.heading("Theme");
+            ui.heading(t!("settings-theme"));
+            let previous = config.theme.clone();
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut config.theme, "default".to_string(), t!("settings-theme-default"));
+                ui.radio_value(&mut config.theme, "dark".to_string(), t!("settings-theme-dark"));
+                ui.radio_value(&mut config.theme, "light".to_string(), t!("settings-theme-light"));
+            });
+            let edited = crate::ui::theme::show_theme_editor(ui, &config.theme, &mut config.custom_theme);
+            // Preview on the whole window right away; Save keeps it
+            if edited || config.theme != previous {
+                ui.ctx().set_visuals(crate::ui::theme::visuals(&config.theme, &config.custom_theme));
+            }
+        });
+
+        // Scaling
+        ui.group(|ui| {
+            ui.heading(t!("settings-scaling"));
+            let scale = ui.add(
+                egui::Slider::new(&mut config.ui_scale, crate::ui::theme::UI_SCALE_RANGE)
+                    .step_by(0.05)
+                    .fixed_decimals(2)
+                    .suffix("×")
+                    .text(t!("settings-ui-scale")),
+            );
+            let font = ui.add(
+                egui::Slider::new(&mut config.font_size, crate::ui::theme::FONT_SIZE_RANGE)
+                    .step_by(0.5)
+                    .suffix(" pt")
+                    .text(t!("settings-font-size")),
+            );
+            let mut reset = false;
+            if (config.ui_scale != 1.0 || config.font_size != crate::config::default_font_size())
+                && ui.small_button(t!("settings-scaling-reset")).clicked()
+            {
+                config.ui_scale = 1.0;
+                config.font_size = crate::config::default_font_size();
+                reset = true;
+            }
+            // Rescaling mid-drag would move the slider away from the pointer, so wait for the release
+            let settled = |response: &egui::Response| {
+                response.drag_stopped() || (response.changed() && !response.dragged())
+            };
+            if reset || settled(&scale) || settled(&font) {
+                crate::ui::theme::apply_scaling(ui.ctx(), config.ui_scale, config.font_size);
+            }
+            ui.label(egui::RichText::new(t!("settings-scaling-hint")).size(11.0).weak());
+        });
+
+        // Language
+        ui.group(|ui| {
+            ui.heading(t!("settings-language"));
+            let languages = crate::i18n::available_languages();
+            let selected = match &config.language {
+                Some(code) => languages
+                    .iter()
+                    .find(|(language, _)| language == code)
+                    .map(|(_, name)| name.clone())
+                    .unwrap_or_else(|| code.clone()),
+                None => t!("settings-language-system"),
+            };
+            egui::ComboBox::from_id_salt("settings_language")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut config.language, None, t!("settings-language-system"));
+                    for (code, name) in languages {
+                        ui.selectable_value(&mut config.language, Some(code), name);
+                    }
+                });
+            if let Some(dir) = crate::i18n::user_catalog_dir() {
+                ui.label(
+                    egui::RichText::new(t!("settings-language-hint", folder = dir.display()))
+                        .size(11.0)
+                        .weak(),
+                );
+            }
+        });
+
+        // Logging
+        ui.group(|ui| {
+            ui.heading(t!("settings-logging"));
             ui.horizontal(|ui| {
-                ui.radio_value(&mut config.theme, "default".to_string(), "Default");
-                ui.radio_value(&mut config.theme, "dark".to_string(), "Dark");
-                ui.radio_value(&mut config.theme, "light".to_string(), "Light");
+                ui.label(t!("settings-log-level"));
+                egui::ComboBox::from_id_salt("settings_log_level")
+                    .selected_text(&config.log_level)
+                    .show_ui(ui, |ui| {
+                        for level in crate::logging::LOG_LEVELS {
+                            ui.selectable_value(&mut config.log_level, level.to_string(), level);
+                        }
+                    });
+                if let Some(dir) = crate::logging::log_dir()
+                    && ui.button(t!("settings-open-log-folder")).clicked()
+                {
+                    let _ = crate::utils::open_folder(&dir);
+                }
             });
+            ui.label(
+                egui::RichText::new(t!("settings-log-hint"))
+                    .size(11.0)
+                    .weak(),
+            );
+        });
+
+        // Updates
+        ui.group(|ui| {
+            ui.heading(t!("settings-updates"));
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut config.check_for_updates, t!("settings-check-updates"));
+                if ui.button(t!("settings-check-now")).clicked() {
+                    result = SettingsResult::CheckUpdates;
+                }
+            });
+            ui.label(
+                egui::RichText::new(t!("settings-updates-hint", version = crate::update::CURRENT_VERSION))
+                .size(11.0)
+                .weak(),
+            );
+            if let Some(version) = config.skipped_update.clone() {
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(t!("settings-updates-skipping", version = version)).size(11.0).weak());
+                    if ui.small_button(t!("settings-updates-remind")).clicked() {
+                        config.skipped_update = None;
+                    }
+                });
+            }
+        });
+
+        // Backup
+        ui.group(|ui| {
+            ui.heading(t!("settings-transfer"));
+            if show_transfer(ui, config, transfer) {
+                result = SettingsResult::Imported;
+            }
+        });
+
+        // Experimental
+        ui.group(|ui| {
+            ui.heading(t!("settings-experimental"));
+            ui.checkbox(&mut config.share_opt_in, t!("settings-share-opt-in"))
+                .on_hover_text(t!("settings-share-opt-in-hint"));
         });
     });
 
     // Buttons
     ui.horizontal(|ui| {
-        if ui.button("💾 Save").clicked() {
-            result = SettingsResult::Save;
+        let save_label = if tool_checks.confirm_save { t!("settings-save-anyway") } else { t!("settings-save") };
+        if ui.button(save_label).clicked() {
+            if tool_checks.has_failure(config) && !tool_checks.confirm_save {
+                tool_checks.confirm_save = true;
+            } else {
+                result = SettingsResult::Save;
+            }
+        }
+
+        if tool_checks.confirm_save {
+            ui.label(RichText::new(t!("settings-tool-failed")).color(Color32::RED));
         }
 
-        if ui.button("❌ Cancel").clicked() {
+        if ui.button(t!("settings-cancel")).clicked() {
             result = SettingsResult::Close;
         }
 
-        if ui.button("🔄 Reset to Defaults").clicked() {
+        if ui.button(t!("settings-reset")).clicked() {
             *config = AppConfig::default();
             result = SettingsResult::Reset;
         }
@@ -243,3 +771,344 @@ fn show_settings_content(ui: &mut Ui, config: &mut AppConfig) -> SettingsResult
 
     result
 }
+
+/// Path field with Browse and Test buttons and the tested version underneath
+fn show_tool_path(ui: &mut Ui, path: &mut String, adb: bool, tool_checks: &mut ToolChecks) {
+    let name = if adb { "adb" } else { "scrcpy" };
+    ui.horizontal(|ui| {
+        let response = ui.text_edit_singleline(path);
+        let mut test = response.lost_focus() && tool_checks.result(adb, path).is_none();
+        if ui.button(t!("settings-browse")).clicked()
+            && let Some(picked) = rfd::FileDialog::new().set_title(t!("settings-locate", tool = name)).pick_file()
+        {
+            *path = picked.display().to_string();
+            test = true;
+        }
+        if ui
+            .add_enabled(!path.trim().is_empty(), egui::Button::new(t!("settings-test-tool")))
+            .on_hover_text(t!("settings-test-tool-hint", command = if adb { "adb version" } else { "scrcpy --version" }))
+            .clicked()
+        {
+            test = true;
+        }
+        if test && !path.trim().is_empty() {
+            tool_checks.start(adb, path, ui.ctx());
+        }
+    });
+
+    match tool_checks.result(adb, path) {
+        Some(Ok(ToolVersion { version, problem: None })) => {
+            ui.label(RichText::new(format!("{} {} {}", egui_phosphor::fill::CHECK_CIRCLE, name, version)).color(Color32::GREEN));
+        }
+        Some(Ok(ToolVersion { version, problem: Some(problem) })) => {
+            ui.label(
+                RichText::new(format!("{} {} {}: {}", egui_phosphor::fill::WARNING, name, version, problem))
+                    .color(Color32::YELLOW),
+            );
+        }
+        Some(Err(e)) => {
+            ui.label(RichText::new(format!("{} {}", egui_phosphor::fill::X_CIRCLE, e)).color(Color32::RED));
+        }
+        None if tool_checks.running > 0 => {
+            ui.horizontal(|ui| {
+                ui.add(egui::Spinner::new().size(12.0));
+                ui.label(RichText::new(t!("settings-test-tool-checking", tool = name)).size(11.0).weak());
+            });
+        }
+        None => {}
+    }
+}
+
+/// Export the settings and profiles to a file, or import such a file; true once an import was applied
+fn show_transfer(ui: &mut Ui, config: &mut AppConfig, transfer: &mut TransferState) -> bool {
+    let mut imported = false;
+
+    ui.label(
+        RichText::new(t!("settings-transfer-hint"))
+            .size(11.0)
+            .weak(),
+    );
+    ui.horizontal(|ui| {
+        if ui.button(format!("{} {}", egui_phosphor::fill::EXPORT, t!("settings-transfer-export"))).clicked()
+            && let Some(path) = rfd::FileDialog::new()
+                .set_file_name("droidview-settings.toml")
+                .add_filter(t!("settings-transfer-filter"), &["toml"])
+                .save_file()
+        {
+            transfer.message = Some(
+                export_bundle(config, &path)
+                    .map(|_| t!("settings-transfer-exported", path = path.display()))
+                    .map_err(|e| t!("settings-transfer-export-failed", error = e)),
+            );
+        }
+        if ui.button(format!("{} {}", egui_phosphor::fill::DOWNLOAD_SIMPLE, t!("settings-transfer-import"))).clicked()
+            && let Some(path) = rfd::FileDialog::new().add_filter(t!("settings-transfer-filter"), &["toml"]).pick_file()
+        {
+            match read_bundle(&path) {
+                Ok(bundle) => {
+                    transfer.pending = Some(bundle);
+                    transfer.message = None;
+                }
+                Err(e) => transfer.message = Some(Err(e.to_string())),
+            }
+        }
+    });
+
+    let mut choice = None;
+    if let Some((bundle, warnings)) = &transfer.pending {
+        ui.label(t!(
+            "settings-transfer-pending",
+            date = bundle.exported_at.format("%Y-%m-%d %H:%M"),
+            templates = bundle.config.session_templates.len(),
+            profiles = bundle.profiles.len(),
+        ));
+        for warning in warnings {
+            ui.label(RichText::new(format!("{} {}", egui_phosphor::fill::WARNING, warning)).color(Color32::YELLOW));
+        }
+        ui.horizontal(|ui| {
+            if ui
+                .button(t!("settings-transfer-merge"))
+                .on_hover_text(t!("settings-transfer-merge-hint"))
+                .clicked()
+            {
+                choice = Some(ImportMode::Merge);
+            }
+            if ui
+                .button(t!("settings-transfer-replace"))
+                .on_hover_text(t!("settings-transfer-replace-hint"))
+                .clicked()
+            {
+                choice = Some(ImportMode::Replace);
+            }
+            if ui.button(t!("settings-transfer-cancel")).clicked() {
+                transfer.pending = None;
+            }
+        });
+    }
+    if let Some(mode) = choice
+        && let Some((bundle, _)) = transfer.pending.take()
+    {
+        transfer.message = Some(match apply_bundle(&bundle, mode, config) {
+            Ok(summary) => {
+                imported = true;
+                Ok(summary.join("; "))
+            }
+            Err(e) => Err(t!("settings-transfer-import-failed", error = e)),
+        });
+    }
+
+    match &transfer.message {
+        Some(Ok(message)) => {
+            ui.label(RichText::new(message).color(Color32::GREEN));
+        }
+        Some(Err(message)) => {
+            ui.label(RichText::new(message).color(Color32::RED));
+        }
+        None => {}
+    }
+    imported
+}
+
+/// Named scrcpy sessions with their device and hotkey; hotkeys are registered on Save
+fn show_template_editor(ui: &mut Ui, config: &mut AppConfig, new_template_name: &mut String) {
+    ui.label(
+        egui::RichText::new(t!("settings-templates-hint"))
+            .size(11.0)
+            .color(egui::Color32::GRAY),
+    );
+
+    let mut remove = None;
+    let mut recapture = None;
+    for (index, template) in config.session_templates.iter_mut().enumerate() {
+        egui::CollapsingHeader::new(&template.name)
+            .id_salt(("session_template", index))
+            .show(ui, |ui| {
+                egui::Grid::new(("session_template_grid", index)).num_columns(2).show(ui, |ui| {
+                    ui.label(t!("settings-template-name"));
+                    ui.text_edit_singleline(&mut template.name);
+                    ui.end_row();
+                    ui.label(t!("settings-template-device"));
+                    ui.add(egui::TextEdit::singleline(&mut template.device).hint_text(t!("settings-template-device-hint")));
+                    ui.end_row();
+                    ui.label(t!("settings-template-hotkey"));
+                    ui.add(egui::TextEdit::singleline(&mut template.hotkey).hint_text(t!("settings-hotkey-example", hotkey = "Ctrl+Alt+1")));
+                    ui.end_row();
+                });
+                if !template.hotkey.trim().is_empty()
+                    && let Err(e) = crate::hotkeys::parse_hotkey(&template.hotkey)
+                {
+                    ui.label(egui::RichText::new(e).color(egui::Color32::RED).size(11.0));
+                }
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{} · {} · {}{}",
+                        template.video_codec,
+                        template.bitrate,
+                        template.dimension.map(|size| size.to_string()).unwrap_or_else(|| t!("settings-template-native")),
+                        if template.fullscreen { format!(" · {}", t!("settings-template-fullscreen")) } else { String::new() },
+                    ))
+                    .size(11.0)
+                    .color(egui::Color32::GRAY),
+                );
+                ui.horizontal(|ui| {
+                    if ui.small_button(t!("settings-template-update")).clicked() {
+                        recapture = Some(index);
+                    }
+                    if ui.small_button(t!("settings-remove")).clicked() {
+                        remove = Some(index);
+                    }
+                });
+            });
+    }
+    if let Some(index) = recapture {
+        let template = &config.session_templates[index];
+        let updated = SessionTemplate {
+            hotkey: template.hotkey.clone(),
+            ..SessionTemplate::from_config(&template.name, &template.device, config)
+        };
+        config.session_templates[index] = updated;
+    }
+    if let Some(index) = remove {
+        config.session_templates.remove(index);
+    }
+
+    ui.horizontal(|ui| {
+        ui.add(egui::TextEdit::singleline(new_template_name).hint_text(t!("settings-template-new-name")).desired_width(160.0));
+        let name = new_template_name.trim().to_string();
+        let taken = config.session_templates.iter().any(|template| template.name == name);
+        if ui
+            .add_enabled(!name.is_empty() && !taken, egui::Button::new(t!("settings-template-save")))
+            .clicked()
+        {
+            let template = SessionTemplate::from_config(&name, "", config);
+            config.session_templates.push(template);
+            new_template_name.clear();
+        }
+    });
+}
+
+/// scrcpy's built-in shortcuts, triggered with the configured modifier (MOD), with the keys of their descriptions
+const SCRCPY_SHORTCUTS: [(&str, &str); 16] = [
+    ("MOD+f", "scrcpy-shortcut-fullscreen"),
+    ("MOD+Left / MOD+Right", "scrcpy-shortcut-rotate-display"),
+    ("MOD+g", "scrcpy-shortcut-resize-1-1"),
+    ("MOD+w", "scrcpy-shortcut-remove-borders"),
+    ("MOD+h", "scrcpy-shortcut-home"),
+    ("MOD+b", "scrcpy-shortcut-back"),
+    ("MOD+s", "scrcpy-shortcut-app-switch"),
+    ("MOD+m", "scrcpy-shortcut-menu"),
+    ("MOD+Up / MOD+Down", "scrcpy-shortcut-volume"),
+    ("MOD+p", "scrcpy-shortcut-power"),
+    ("MOD+o / MOD+Shift+o", "scrcpy-shortcut-screen-off"),
+    ("MOD+r", "scrcpy-shortcut-rotate-device"),
+    ("MOD+n / MOD+Shift+n", "scrcpy-shortcut-notifications"),
+    ("MOD+c / MOD+x / MOD+v", "scrcpy-shortcut-clipboard"),
+    ("MOD+Shift+v", "scrcpy-shortcut-inject-clipboard"),
+    ("MOD+i", "scrcpy-shortcut-fps"),
+];
+
+/// Global hotkeys for DroidView's own captures, which scrcpy passes on while its window has the focus
+fn show_capture_hotkeys(ui: &mut Ui, hotkeys: &mut crate::config::CaptureHotkeys) {
+    ui.label(t!("settings-capture-hotkeys"));
+    egui::Grid::new("capture_hotkeys_grid").num_columns(2).show(ui, |ui| {
+        for (label, hotkey) in [
+            (t!("settings-capture-hotkey-screenshot"), &mut hotkeys.screenshot),
+            (t!("settings-capture-hotkey-record"), &mut hotkeys.record),
+        ] {
+            ui.label(label);
+            ui.vertical(|ui| {
+                ui.add(egui::TextEdit::singleline(hotkey).hint_text(t!("settings-hotkey-example", hotkey = "Ctrl+Alt+S")));
+                if !hotkey.trim().is_empty()
+                    && let Err(e) = crate::hotkeys::parse_hotkey(hotkey)
+                {
+                    ui.label(egui::RichText::new(e).color(egui::Color32::RED).size(11.0));
+                }
+            });
+            ui.end_row();
+        }
+    });
+}
+
+/// Structured editor for scrcpy's `--shortcut-mod`: each row is one accepted modifier combination
+fn show_shortcut_editor(ui: &mut Ui, shortcut_mods: &mut Vec<String>) {
+    ui.label(t!("settings-shortcut-mod"));
+
+    if shortcut_mods.is_empty() {
+        ui.label(
+            egui::RichText::new(t!("settings-shortcut-mod-default"))
+                .color(egui::Color32::GRAY),
+        );
+    }
+
+    let mut remove = None;
+    for (index, combo) in shortcut_mods.iter_mut().enumerate() {
+        let mut keys: Vec<&str> = combo.split('+').filter(|k| !k.is_empty()).collect();
+        let mut changed = false;
+        ui.horizontal_wrapped(|ui| {
+            for (key, label) in crate::config::SHORTCUT_MODIFIERS {
+                let mut enabled = keys.contains(&key);
+                if ui.checkbox(&mut enabled, label).changed() {
+                    if enabled {
+                        keys.push(key);
+                    } else {
+                        keys.retain(|k| *k != key);
+                    }
+                    changed = true;
+                }
+            }
+            if ui.small_button(t!("settings-remove")).clicked() {
+                remove = Some(index);
+            }
+        });
+        if changed {
+            // Keep a stable order so the generated flag is predictable
+            keys.sort_by_key(|k| {
+                crate::config::SHORTCUT_MODIFIERS
+                    .iter()
+                    .position(|(m, _)| m == k)
+                    .unwrap_or(usize::MAX)
+            });
+            *combo = keys.join("+");
+        }
+    }
+    if let Some(index) = remove {
+        shortcut_mods.remove(index);
+    }
+
+    ui.horizontal(|ui| {
+        if ui.button(t!("settings-shortcut-mod-add")).clicked() {
+            shortcut_mods.push("lalt".to_string());
+        }
+        if !shortcut_mods.is_empty() && ui.button(t!("settings-shortcut-mod-reset")).clicked() {
+            shortcut_mods.clear();
+        }
+    });
+
+    let active: Vec<&str> = shortcut_mods
+        .iter()
+        .map(String::as_str)
+        .filter(|combo| !combo.is_empty())
+        .collect();
+    if !active.is_empty() {
+        ui.label(
+            egui::RichText::new(format!("--shortcut-mod={}", active.join(",")))
+                .monospace()
+                .color(egui::Color32::GRAY),
+        );
+    }
+
+    egui::CollapsingHeader::new(t!("settings-shortcut-reference"))
+        .id_salt("scrcpy_shortcut_reference")
+        .show(ui, |ui| {
+            egui::Grid::new("scrcpy_shortcut_grid")
+                .num_columns(2)
+                .striped(true)
+                .show(ui, |ui| {
+                    for (keys, action) in SCRCPY_SHORTCUTS {
+                        ui.label(egui::RichText::new(keys).monospace());
+                        ui.label(t!(action));
+                        ui.end_row();
+                    }
+                });
+        });
+}
@@ -0,0 +1,125 @@
+use crate::share::{ShareServer, DEFAULT_SHARE_PORT, SHARE_DURATIONS};
+use crate::t;
+use egui::{Color32, RichText, Window};
+use std::time::Duration;
+
+pub enum ShareAction {
+    None,
+    Start { port: u16, duration: Duration },
+    Stop,
+}
+
+/// Start, stop and hand out the link of the view-only LAN share
+pub struct ShareWindow {
+    visible: bool,
+    port: u16,
+    minutes: u64,
+    error: Option<String>,
+}
+
+impl Default for ShareWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShareWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            port: DEFAULT_SHARE_PORT,
+            minutes: SHARE_DURATIONS[1],
+            error: None,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.visible = true;
+        self.error = None;
+    }
+
+    pub fn set_error(&mut self, error: Option<String>) {
+        self.error = error;
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, server: Option<&ShareServer>, opted_in: bool) -> ShareAction {
+        if !self.visible {
+            return ShareAction::None;
+        }
+
+        let mut action = ShareAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::EYE, t!("share-title")))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new(t!("share-warning"))
+                    .size(11.0)
+                    .color(Color32::YELLOW),
+                );
+                ui.separator();
+
+                if !opted_in {
+                    ui.label(t!("share-disabled"));
+                    return;
+                }
+
+                match server {
+                    Some(server) => {
+                        let url = server.url();
+                        ui.label(t!("share-link"));
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(&url).monospace().size(11.0));
+                            if ui.small_button(egui_phosphor::fill::COPY).on_hover_text(t!("share-copy")).clicked() {
+                                ui.ctx().copy_text(url.clone());
+                            }
+                        });
+                        let remaining = server.remaining().as_secs();
+                        ui.label(t!(
+                            "share-status",
+                            time = format!("{}:{:02}", remaining / 60, remaining % 60),
+                            viewers = server.viewer_count()
+                        ));
+                        if ui.button(format!("{} {}", egui_phosphor::fill::STOP, t!("share-stop"))).clicked() {
+                            action = ShareAction::Stop;
+                        }
+                        ui.ctx().request_repaint_after(Duration::from_secs(1));
+                    }
+                    None => {
+                        egui::Grid::new("share_settings_grid").num_columns(2).show(ui, |ui| {
+                            ui.label(t!("share-port"));
+                            ui.add(egui::DragValue::new(&mut self.port).range(1024..=65535));
+                            ui.end_row();
+                            ui.label(t!("share-expires-after"));
+                            egui::ComboBox::from_id_salt("share_duration_combo")
+                                .selected_text(t!("share-minutes", minutes = self.minutes))
+                                .show_ui(ui, |ui| {
+                                    for minutes in SHARE_DURATIONS {
+                                        ui.selectable_value(&mut self.minutes, minutes, t!("share-minutes", minutes = minutes));
+                                    }
+                                });
+                            ui.end_row();
+                        });
+                        if ui.button(format!("{} {}", egui_phosphor::fill::EYE, t!("share-start"))).clicked() {
+                            self.error = None;
+                            action = ShareAction::Start {
+                                port: self.port,
+                                duration: Duration::from_secs(self.minutes * 60),
+                            };
+                        }
+                    }
+                }
+
+                if let Some(error) = &self.error {
+                    ui.label(RichText::new(error).color(Color32::RED));
+                }
+            });
+
+        self.visible = open;
+        action
+    }
+}
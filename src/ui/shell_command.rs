@@ -0,0 +1,213 @@
+use crate::config::ShellSnippet;
+use crate::shell_console::CommandOutput;
+use crate::t;
+use egui::{Color32, RichText, Window};
+
+pub enum ShellCommandAction {
+    None,
+    Run(String),
+    SaveSnippet(ShellSnippet),
+    DeleteSnippet(String),
+}
+
+/// A box for single shell commands on the selected device, with its history and saved snippets
+pub struct ShellCommandWindow {
+    visible: bool,
+    device: Option<String>,
+    command: String,
+    snippet_name: String,
+    running: bool,
+    /// The command that produced `output`
+    ran: String,
+    output: Option<Result<CommandOutput, String>>,
+}
+
+impl Default for ShellCommandWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShellCommandWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            device: None,
+            command: String::new(),
+            snippet_name: String::new(),
+            running: false,
+            ran: String::new(),
+            output: None,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.visible = true;
+    }
+
+    pub fn set_device(&mut self, device: Option<&str>) {
+        self.device = device.map(str::to_string);
+    }
+
+    pub fn set_running(&mut self, command: String) {
+        self.running = true;
+        self.ran = command;
+    }
+
+    pub fn set_output(&mut self, output: Result<CommandOutput, String>) {
+        self.running = false;
+        self.output = Some(output);
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, history: &[String], snippets: &[ShellSnippet]) -> ShellCommandAction {
+        if !self.visible {
+            return ShellCommandAction::None;
+        }
+
+        let mut action = ShellCommandAction::None;
+        let mut open = self.visible;
+        let can_run = self.device.is_some() && !self.running;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::CODE, t!("shell-command-title")))
+            .id(egui::Id::new("shell_command"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(560.0, 520.0))
+            .show(ctx, |ui| {
+                let device = self.device.clone().unwrap_or_else(|| t!("shell-command-no-device"));
+                ui.label(RichText::new(device).weak());
+
+                let response = ui.add(
+                    egui::TextEdit::multiline(&mut self.command)
+                        .font(egui::TextStyle::Monospace)
+                        .hint_text(t!("shell-command-hint"))
+                        .desired_rows(2)
+                        .desired_width(f32::INFINITY),
+                );
+                let submitted = response.has_focus()
+                    && ui.input(|input| input.modifiers.command && input.key_pressed(egui::Key::Enter));
+                if submitted {
+                    // The text box takes the Enter as a new line as well
+                    self.command = self.command.trim_end().to_string();
+                }
+
+                ui.horizontal(|ui| {
+                    let command = self.command.trim();
+                    let mut run = ui.add_enabled(
+                        can_run && !command.is_empty(),
+                        egui::Button::new(format!("{} {}", egui_phosphor::fill::PLAY, t!("shell-command-run"))),
+                    );
+                    if self.running {
+                        run = run.on_disabled_hover_text(t!("shell-command-running"));
+                    }
+                    if (run.clicked() || (submitted && can_run)) && !command.is_empty() {
+                        action = ShellCommandAction::Run(command.to_string());
+                    }
+                    if self.running {
+                        ui.add(egui::Spinner::new().size(14.0));
+                    }
+                    ui.separator();
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.snippet_name)
+                            .hint_text(t!("shell-command-snippet-name"))
+                            .desired_width(140.0),
+                    );
+                    let name = self.snippet_name.trim();
+                    let save = ui
+                        .add_enabled(!name.is_empty() && !command.is_empty(), egui::Button::new(t!("shell-command-save")))
+                        .on_hover_text(t!("shell-command-save-hint"));
+                    if save.clicked() {
+                        action = ShellCommandAction::SaveSnippet(ShellSnippet {
+                            name: name.to_string(),
+                            command: command.to_string(),
+                        });
+                        self.snippet_name.clear();
+                    }
+                });
+
+                if !snippets.is_empty() {
+                    egui::CollapsingHeader::new(t!("shell-command-snippets", count = snippets.len()))
+                        .id_salt("shell_snippets")
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            egui::ScrollArea::vertical().id_salt("shell_snippets_scroll").max_height(120.0).show(ui, |ui| {
+                                for snippet in snippets {
+                                    ui.horizontal(|ui| {
+                                        if ui
+                                            .add_enabled(can_run, egui::Button::new(egui_phosphor::fill::PLAY).small())
+                                            .on_hover_text(t!("shell-command-run"))
+                                            .clicked()
+                                        {
+                                            action = ShellCommandAction::Run(snippet.command.clone());
+                                        }
+                                        if ui.small_button(egui_phosphor::fill::TRASH).clicked() {
+                                            action = ShellCommandAction::DeleteSnippet(snippet.name.clone());
+                                        }
+                                        if ui.selectable_label(false, &snippet.name).on_hover_text(&snippet.command).clicked() {
+                                            self.command = snippet.command.clone();
+                                            self.snippet_name = snippet.name.clone();
+                                        }
+                                    });
+                                }
+                            });
+                        });
+                }
+
+                if !history.is_empty() {
+                    egui::CollapsingHeader::new(t!("shell-command-history", count = history.len()))
+                        .id_salt("shell_history")
+                        .default_open(false)
+                        .show(ui, |ui| {
+                            egui::ScrollArea::vertical().id_salt("shell_history_scroll").max_height(120.0).show(ui, |ui| {
+                                for command in history {
+                                    let label = ui.selectable_label(false, RichText::new(command).monospace());
+                                    if label.clicked() {
+                                        self.command = command.clone();
+                                    }
+                                    if label.double_clicked() && can_run {
+                                        action = ShellCommandAction::Run(command.clone());
+                                    }
+                                }
+                            });
+                        });
+                }
+
+                ui.separator();
+                match &self.output {
+                    Some(Ok(output)) => {
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(&self.ran).monospace().strong());
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                if ui.small_button(egui_phosphor::fill::COPY).on_hover_text(t!("shell-command-copy")).clicked() {
+                                    ui.ctx().copy_text(output.text.clone());
+                                }
+                                ui.label(RichText::new(format!("{} ms", output.elapsed.as_millis())).weak());
+                                match output.exit_code {
+                                    Some(0) => ui.label(RichText::new(t!("shell-command-exit", code = 0)).color(Color32::GREEN)),
+                                    Some(code) => ui.label(RichText::new(t!("shell-command-exit", code = code)).color(Color32::RED)),
+                                    None => ui.label(RichText::new(t!("shell-command-exit-unknown")).weak()),
+                                };
+                            });
+                        });
+                        egui::ScrollArea::both().id_salt("shell_output").auto_shrink([false, false]).show(ui, |ui| {
+                            ui.add(
+                                egui::TextEdit::multiline(&mut output.text.as_str())
+                                    .font(egui::TextStyle::Monospace)
+                                    .desired_width(f32::INFINITY),
+                            );
+                        });
+                    }
+                    Some(Err(error)) => {
+                        ui.label(RichText::new(error).color(Color32::RED));
+                    }
+                    None => {
+                        ui.label(RichText::new(t!("shell-command-empty")).weak());
+                    }
+                }
+            });
+
+        self.visible = open;
+        action
+    }
+}
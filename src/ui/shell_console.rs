@@ -0,0 +1,192 @@
+use crate::shell_console::{Scrollback, INTERRUPT};
+use crate::t;
+use egui::{Color32, RichText, Window};
+
+pub enum ShellConsoleAction {
+    None,
+    /// Keystrokes for the shell
+    Send(Vec<u8>),
+    /// Start a new shell after the last one ended
+    Restart,
+    Close,
+}
+
+/// An `adb shell` session on the selected device, with its scrollback and an input line
+pub struct ShellConsoleWindow {
+    visible: bool,
+    device: String,
+    scrollback: Scrollback,
+    /// The scrollback as one string, rebuilt when output arrives
+    text: String,
+    input: String,
+    /// Lines sent this session, and which one the arrow keys are on
+    sent: Vec<String>,
+    recall: Option<usize>,
+    ended: bool,
+    focus_input: bool,
+}
+
+impl Default for ShellConsoleWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShellConsoleWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            device: String::new(),
+            scrollback: Scrollback::new(),
+            text: String::new(),
+            input: String::new(),
+            sent: Vec::new(),
+            recall: None,
+            ended: false,
+            focus_input: false,
+        }
+    }
+
+    /// Show the console for a new shell on `device`, keeping the scrollback if it is the same one
+    pub fn open(&mut self, device: String) {
+        if device != self.device {
+            self.scrollback.clear();
+            self.text.clear();
+            self.device = device;
+        }
+        self.visible = true;
+        self.ended = false;
+        self.focus_input = true;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.visible
+    }
+
+    pub fn close(&mut self) {
+        self.visible = false;
+    }
+
+    pub fn push_output(&mut self, output: &str) {
+        if output.is_empty() {
+            return;
+        }
+        self.scrollback.push(output);
+        self.text = self.scrollback.text();
+    }
+
+    pub fn set_ended(&mut self) {
+        self.ended = true;
+    }
+
+    /// Step through the lines sent so far, `back` towards the oldest
+    fn recall(&mut self, back: bool) {
+        if self.sent.is_empty() {
+            return;
+        }
+        let index = match (self.recall, back) {
+            (None, true) => Some(self.sent.len() - 1),
+            (None, false) => None,
+            (Some(index), true) => Some(index.saturating_sub(1)),
+            (Some(index), false) if index + 1 < self.sent.len() => Some(index + 1),
+            (Some(_), false) => None,
+        };
+        self.recall = index;
+        self.input = index.map(|index| self.sent[index].clone()).unwrap_or_default();
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> ShellConsoleAction {
+        if !self.visible {
+            return ShellConsoleAction::None;
+        }
+
+        let mut action = ShellConsoleAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::TERMINAL_WINDOW, t!("shell-console-title")))
+            .id(egui::Id::new("shell_console"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(640.0, 420.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(&self.device).weak());
+                    if self.ended {
+                        ui.label(RichText::new(t!("shell-console-ended")).color(Color32::YELLOW));
+                        if ui.button(format!("{} {}", egui_phosphor::fill::ARROW_CLOCKWISE, t!("shell-console-restart"))).clicked() {
+                            action = ShellConsoleAction::Restart;
+                        }
+                    }
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.button(format!("{} {}", egui_phosphor::fill::ERASER, t!("shell-console-clear"))).clicked() {
+                            self.scrollback.clear();
+                            self.text.clear();
+                        }
+                        if ui.button(format!("{} {}", egui_phosphor::fill::COPY, t!("shell-console-copy"))).clicked() {
+                            ui.ctx().copy_text(self.text.clone());
+                        }
+                        let interrupt = ui
+                            .add_enabled(!self.ended, egui::Button::new("Ctrl+C"))
+                            .on_hover_text(t!("shell-console-interrupt"));
+                        if interrupt.clicked() {
+                            action = ShellConsoleAction::Send(vec![INTERRUPT]);
+                        }
+                    });
+                });
+                ui.separator();
+
+                let input_height = 36.0;
+                egui::ScrollArea::vertical()
+                    .max_height((ui.available_height() - input_height).max(80.0))
+                    .stick_to_bottom(true)
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| {
+                        ui.add(
+                            egui::TextEdit::multiline(&mut self.text.as_str())
+                                .font(egui::TextStyle::Monospace)
+                                .desired_width(f32::INFINITY)
+                                .frame(false),
+                        );
+                    });
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("$").monospace());
+                    let response = ui.add_enabled(
+                        !self.ended,
+                        egui::TextEdit::singleline(&mut self.input)
+                            .font(egui::TextStyle::Monospace)
+                            .hint_text(t!("shell-console-hint"))
+                            .desired_width(f32::INFINITY),
+                    );
+                    if std::mem::take(&mut self.focus_input) {
+                        response.request_focus();
+                    }
+                    if response.has_focus() {
+                        if ui.input(|input| input.key_pressed(egui::Key::ArrowUp)) {
+                            self.recall(true);
+                        }
+                        if ui.input(|input| input.key_pressed(egui::Key::ArrowDown)) {
+                            self.recall(false);
+                        }
+                    }
+                    if response.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter)) {
+                        let line = std::mem::take(&mut self.input);
+                        if !line.trim().is_empty() && self.sent.last() != Some(&line) {
+                            self.sent.push(line.clone());
+                        }
+                        self.recall = None;
+                        action = ShellConsoleAction::Send(format!("{}\n", line).into_bytes());
+                        self.focus_input = true;
+                    }
+                });
+            });
+
+        if !open {
+            self.visible = false;
+            action = ShellConsoleAction::Close;
+        }
+        action
+    }
+}
@@ -0,0 +1,139 @@
+use crate::shortcuts::{shortcut_from_press, shortcut_to_string, ShortcutAction, Shortcuts};
+use crate::t;
+use egui::{Color32, RichText, Window};
+
+pub enum ShortcutsAction {
+    None,
+    /// Bind the action to this text form; empty unbinds it
+    Set(ShortcutAction, String),
+    /// Go back to the default binding
+    Reset(ShortcutAction),
+}
+
+/// Cheat sheet of the in-app shortcuts, where each one can also be rebound
+#[derive(Default)]
+pub struct ShortcutsWindow {
+    visible: bool,
+    /// Action waiting for its new key combination
+    capturing: Option<ShortcutAction>,
+    message: Option<String>,
+}
+
+impl ShortcutsWindow {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+        self.capturing = None;
+        self.message = None;
+    }
+
+    /// While a new binding is being recorded, key presses must not trigger shortcuts
+    pub fn is_capturing(&self) -> bool {
+        self.visible && self.capturing.is_some()
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, shortcuts: &Shortcuts) -> ShortcutsAction {
+        if !self.visible {
+            return ShortcutsAction::None;
+        }
+
+        let mut action = ShortcutsAction::None;
+        if let Some(capturing) = self.capturing {
+            action = self.capture(ctx, capturing, shortcuts);
+        }
+
+        let mut open = self.visible;
+        Window::new(format!("{} {}", egui_phosphor::fill::KEYBOARD, t!("shortcuts-title")))
+            .id(egui::Id::new("shortcuts_window"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                egui::Grid::new("shortcuts_grid").num_columns(3).striped(true).show(ui, |ui| {
+                    for shortcut_action in ShortcutAction::ALL {
+                        ui.label(shortcut_action.label());
+                        if self.capturing == Some(shortcut_action) {
+                            ui.label(RichText::new(t!("shortcuts-press-keys")).color(Color32::YELLOW));
+                        } else {
+                            let text = shortcuts
+                                .binding(shortcut_action)
+                                .map(|shortcut| ctx.format_shortcut(&shortcut))
+                                .unwrap_or_else(|| "—".to_string());
+                            ui.label(RichText::new(text).monospace().strong());
+                        }
+                        ui.horizontal(|ui| {
+                            if ui.small_button(t!("shortcuts-change")).clicked() {
+                                self.capturing = Some(shortcut_action);
+                                self.message = None;
+                            }
+                            if ui.small_button(t!("shortcuts-clear")).clicked() {
+                                action = ShortcutsAction::Set(shortcut_action, String::new());
+                            }
+                            if ui.small_button(t!("shortcuts-reset")).on_hover_text(shortcut_action.default_binding()).clicked() {
+                                action = ShortcutsAction::Reset(shortcut_action);
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+                if let Some(message) = &self.message {
+                    ui.label(RichText::new(message).color(Color32::YELLOW));
+                }
+                ui.label(
+                    RichText::new(t!("shortcuts-footer"))
+                        .size(11.0)
+                        .weak(),
+                );
+            });
+
+        if !open {
+            self.visible = false;
+            self.capturing = None;
+        }
+        action
+    }
+
+    /// Take the next key press as the binding for `capturing`
+    fn capture(&mut self, ctx: &egui::Context, capturing: ShortcutAction, shortcuts: &Shortcuts) -> ShortcutsAction {
+        let pressed = ctx.input_mut(|input| {
+            let pressed = input.events.iter().find_map(|event| match event {
+                egui::Event::Key {
+                    key,
+                    pressed: true,
+                    modifiers,
+                    ..
+                } => Some((*key, *modifiers)),
+                _ => None,
+            });
+            // Keep the press away from widgets and the shortcuts themselves
+            if pressed.is_some() {
+                input.events.retain(|event| !matches!(event, egui::Event::Key { .. } | egui::Event::Text(_)));
+            }
+            pressed
+        });
+        let Some((key, modifiers)) = pressed else {
+            return ShortcutsAction::None;
+        };
+
+        self.capturing = None;
+        if key == egui::Key::Escape && modifiers.is_none() {
+            return ShortcutsAction::None;
+        }
+        let shortcut = shortcut_from_press(modifiers, key);
+        match shortcuts.action_for(&shortcut) {
+            Some(other) if other != capturing => {
+                self.message = Some(t!(
+                    "shortcuts-in-use",
+                    shortcut = ctx.format_shortcut(&shortcut),
+                    action = other.label()
+                ));
+                ShortcutsAction::None
+            }
+            _ => ShortcutsAction::Set(capturing, shortcut_to_string(&shortcut)),
+        }
+    }
+}
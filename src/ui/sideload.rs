@@ -0,0 +1,207 @@
+use crate::device::{Device, DeviceStatus};
+use crate::sideload::SideloadStage;
+use crate::t;
+use egui::{Color32, RichText, Window};
+use std::path::PathBuf;
+
+pub enum SideloadAction {
+    None,
+    Start { serial: String, zip: PathBuf, reboot: bool },
+    Cancel,
+}
+
+/// Streams an OTA or ROM zip to recovery with `adb sideload`
+pub struct SideloadWindow {
+    visible: bool,
+    device: Option<String>,
+    zip: Option<PathBuf>,
+    reboot: bool,
+    /// Set while a sideload runs
+    stage: Option<SideloadStage>,
+    result: Option<Result<String, String>>,
+}
+
+impl Default for SideloadWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SideloadWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            device: None,
+            zip: None,
+            reboot: true,
+            stage: None,
+            result: None,
+        }
+    }
+
+    /// Open on `device`, or on the device chosen last time
+    pub fn open(&mut self, device: Option<String>) {
+        self.visible = true;
+        if self.stage.is_some() {
+            return;
+        }
+        if device.is_some() {
+            self.device = device;
+        }
+        self.result = None;
+    }
+
+    pub fn set_stage(&mut self, stage: SideloadStage) {
+        self.stage = Some(stage);
+    }
+
+    pub fn finish(&mut self, result: Result<String, String>) {
+        self.stage = None;
+        self.result = Some(result);
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, devices: &[Device]) -> SideloadAction {
+        if !self.visible {
+            return SideloadAction::None;
+        }
+
+        let mut action = SideloadAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::FILE_ZIP, t!("sideload-title")))
+            .id(egui::Id::new("sideload"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(460.0, 300.0))
+            .show(ctx, |ui| {
+                ui.label(RichText::new(t!("sideload-intro")).size(11.0).color(Color32::GRAY));
+                ui.separator();
+
+                let running = self.stage.is_some();
+                ui.add_enabled_ui(!running, |ui| self.show_setup(ui, devices));
+                ui.separator();
+
+                match self.stage {
+                    Some(stage) => {
+                        let (text, progress) = match stage {
+                            SideloadStage::Rebooting => (t!("sideload-rebooting"), None),
+                            SideloadStage::Waiting => (t!("sideload-waiting"), None),
+                            SideloadStage::Transferring(percent) => {
+                                (t!("sideload-transferring"), Some(f32::from(percent) / 100.0))
+                            }
+                        };
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label(text);
+                        });
+                        if let Some(progress) = progress {
+                            ui.add(egui::ProgressBar::new(progress).show_percentage());
+                        }
+                        if ui.button(t!("sideload-cancel")).clicked() {
+                            action = SideloadAction::Cancel;
+                        }
+                    }
+                    None => {
+                        let ready = self.device.is_some() && self.zip.is_some();
+                        if ui
+                            .add_enabled(
+                                ready,
+                                egui::Button::new(format!("{} {}", egui_phosphor::fill::UPLOAD, t!("sideload-start"))),
+                            )
+                            .clicked()
+                            && let (Some(serial), Some(zip)) = (self.device.clone(), self.zip.clone())
+                        {
+                            self.result = None;
+                            self.stage = Some(if self.reboot { SideloadStage::Rebooting } else { SideloadStage::Waiting });
+                            action = SideloadAction::Start { serial, zip, reboot: self.reboot };
+                        }
+                    }
+                }
+
+                match &self.result {
+                    Some(Ok(message)) => {
+                        ui.label(RichText::new(message).color(Color32::GREEN));
+                        ui.label(RichText::new(t!("sideload-installing-hint")).size(11.0).color(Color32::GRAY));
+                    }
+                    Some(Err(message)) => {
+                        ui.label(RichText::new(message).color(Color32::RED));
+                    }
+                    None => {}
+                }
+            });
+
+        self.visible = open;
+        action
+    }
+
+    fn show_setup(&mut self, ui: &mut egui::Ui, devices: &[Device]) {
+        ui.horizontal(|ui| {
+            ui.label(t!("sideload-device"));
+            let selected = self.device.as_ref().and_then(|id| devices.iter().find(|device| device.identifier == *id));
+            let selected_text = match (selected, &self.device) {
+                (Some(device), _) => device_name(device),
+                // Gone from the list while rebooting, which is expected
+                (None, Some(id)) => id.clone(),
+                (None, None) => t!("sideload-choose-device"),
+            };
+            egui::ComboBox::from_id_salt("sideload_device")
+                .selected_text(selected_text)
+                .show_ui(ui, |ui| {
+                    for device in devices {
+                        let chosen = self.device.as_deref() == Some(device.identifier.as_str());
+                        if ui.selectable_label(chosen, device_name(device)).clicked() {
+                            self.device = Some(device.identifier.clone());
+                            // Already waiting in recovery: rebooting would leave sideload mode
+                            self.reboot = !is_sideloading(device);
+                        }
+                    }
+                });
+        });
+
+        ui.horizontal(|ui| {
+            if ui.button(format!("{} {}", egui_phosphor::fill::FOLDER_OPEN, t!("sideload-pick-zip"))).clicked()
+                && let Some(path) = rfd::FileDialog::new().add_filter("ZIP", &["zip"]).pick_file()
+            {
+                self.zip = Some(path);
+            }
+            match &self.zip {
+                Some(zip) => {
+                    let name = zip.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+                    ui.label(RichText::new(name).monospace()).on_hover_text(zip.display().to_string());
+                }
+                None => {
+                    ui.label(RichText::new(t!("sideload-no-zip")).color(Color32::GRAY));
+                }
+            }
+        });
+
+        let waiting = self
+            .device
+            .as_ref()
+            .and_then(|id| devices.iter().find(|device| device.identifier == *id))
+            .is_some_and(is_sideloading);
+        if waiting {
+            self.reboot = false;
+        }
+        ui.add_enabled(!waiting, egui::Checkbox::new(&mut self.reboot, t!("sideload-reboot-first")))
+            .on_hover_text(t!("sideload-reboot-hint"));
+    }
+}
+
+fn is_sideloading(device: &Device) -> bool {
+    matches!(&device.status, DeviceStatus::Unknown(state) if state == "sideload")
+}
+
+fn device_name(device: &Device) -> String {
+    let name = if device.model.is_empty() {
+        device.identifier.clone()
+    } else {
+        format!("{} ({})", device.model, device.identifier)
+    };
+    if is_sideloading(device) {
+        format!("{} – sideload", name)
+    } else {
+        name
+    }
+}
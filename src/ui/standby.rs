@@ -0,0 +1,194 @@
+use crate::packages::is_package_name;
+use crate::standby::{StandbyBucket, StandbyInfo};
+use crate::t;
+use egui::{Color32, RichText, Window};
+
+pub enum StandbyAction {
+    None,
+    /// Read the Doze states again, and the bucket of the package when one is entered
+    Refresh(Option<String>),
+    /// Look up the app in the foreground and read its bucket
+    UseForeground,
+    ForceIdle(bool),
+    Whitelist(String, bool),
+    SetBucket(String, StandbyBucket),
+}
+
+/// Doze and app standby controls of the selected device
+pub struct StandbyWindow {
+    visible: bool,
+    device: String,
+    info: Option<Result<StandbyInfo, String>>,
+    busy: bool,
+    package: String,
+}
+
+impl Default for StandbyWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StandbyWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            device: String::new(),
+            info: None,
+            busy: false,
+            package: String::new(),
+        }
+    }
+
+    pub fn open(&mut self, device: String) {
+        if device != self.device {
+            self.info = None;
+        }
+        self.visible = true;
+        self.device = device;
+    }
+
+    /// The package entered, if it looks like one
+    pub fn package(&self) -> Option<String> {
+        let package = self.package.trim();
+        is_package_name(package).then(|| package.to_string())
+    }
+
+    pub fn set_busy(&mut self) {
+        self.busy = true;
+    }
+
+    pub fn set_info(&mut self, info: Result<StandbyInfo, String>) {
+        if let Ok(StandbyInfo { package: Some(package), .. }) = &info {
+            self.package = package.clone();
+        }
+        self.info = Some(info);
+        self.busy = false;
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> StandbyAction {
+        if !self.visible {
+            return StandbyAction::None;
+        }
+
+        let mut action = StandbyAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::MOON, t!("standby-title")))
+            .id(egui::Id::new("standby"))
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(400.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(&self.device).weak());
+                    if ui.add_enabled(!self.busy, egui::Button::new(t!("standby-refresh"))).clicked() {
+                        action = StandbyAction::Refresh(self.package());
+                    }
+                    if self.busy {
+                        ui.add(egui::Spinner::new().size(14.0));
+                    }
+                });
+                ui.separator();
+
+                let info = match &self.info {
+                    Some(Ok(info)) => info.clone(),
+                    Some(Err(e)) => {
+                        ui.label(RichText::new(e).color(Color32::RED));
+                        StandbyInfo::default()
+                    }
+                    None => return,
+                };
+
+                ui.label(RichText::new(t!("standby-doze")).strong());
+                egui::Grid::new("standby_doze").num_columns(2).spacing([16.0, 4.0]).show(ui, |ui| {
+                    ui.label(t!("standby-deep"));
+                    ui.label(state_text(&info.deep));
+                    ui.end_row();
+                    ui.label(t!("standby-light"));
+                    ui.label(state_text(&info.light));
+                    ui.end_row();
+                });
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(!self.busy, egui::Button::new(t!("standby-force-idle")))
+                        .on_hover_text(t!("standby-force-idle-hint"))
+                        .clicked()
+                    {
+                        action = StandbyAction::ForceIdle(true);
+                    }
+                    if ui.add_enabled(!self.busy, egui::Button::new(t!("standby-unforce"))).clicked() {
+                        action = StandbyAction::ForceIdle(false);
+                    }
+                });
+                ui.add_space(6.0);
+
+                ui.label(RichText::new(t!("standby-app")).strong());
+                ui.horizontal(|ui| {
+                    let field = egui::TextEdit::singleline(&mut self.package)
+                        .hint_text("com.example.app")
+                        .desired_width(220.0);
+                    if ui.add(field).lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        action = StandbyAction::Refresh(self.package());
+                    }
+                    if ui.add_enabled(!self.busy, egui::Button::new(t!("standby-foreground"))).clicked() {
+                        action = StandbyAction::UseForeground;
+                    }
+                });
+                let package = self.package();
+                let shown = package.is_some() && package == info.package;
+                if let Some(package) = package.clone().filter(|_| shown) {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label(t!("standby-bucket"));
+                        for bucket in StandbyBucket::SETTABLE {
+                            let selected = info.bucket == Some(bucket);
+                            if ui.add_enabled(!self.busy, egui::Button::selectable(selected, bucket.name())).clicked() {
+                                action = StandbyAction::SetBucket(package.clone(), bucket);
+                            }
+                        }
+                    });
+                    if let Some(bucket) = info.bucket.filter(|bucket| !StandbyBucket::SETTABLE.contains(bucket)) {
+                        ui.label(RichText::new(t!("standby-bucket-other", bucket = bucket.name())).weak());
+                    }
+                    let mut whitelisted = info.is_whitelisted(&package);
+                    let checkbox = egui::Checkbox::new(&mut whitelisted, t!("standby-whitelisted"));
+                    if ui.add_enabled(!self.busy, checkbox).changed() {
+                        action = StandbyAction::Whitelist(package, whitelisted);
+                    }
+                } else if package.is_some() {
+                    ui.label(RichText::new(t!("standby-press-enter")).size(11.0).weak());
+                }
+                ui.add_space(6.0);
+
+                let users: Vec<_> = info.whitelist.iter().filter(|entry| entry.user).collect();
+                let system = info.whitelist.len() - users.len();
+                egui::CollapsingHeader::new(t!("standby-whitelist", count = users.len()))
+                    .id_salt("standby_whitelist")
+                    .show(ui, |ui| {
+                        for entry in users {
+                            ui.horizontal(|ui| {
+                                ui.label(&entry.package);
+                                let remove = egui::Button::new(egui_phosphor::fill::X).small();
+                                if ui.add_enabled(!self.busy, remove).on_hover_text(t!("standby-remove")).clicked() {
+                                    action = StandbyAction::Whitelist(entry.package.clone(), false);
+                                }
+                            });
+                        }
+                        ui.label(RichText::new(t!("standby-system-whitelist", count = system)).size(11.0).weak());
+                    });
+            });
+
+        self.visible = open;
+        action
+    }
+}
+
+/// `IDLE` in green, anything else as it is
+fn state_text(state: &str) -> RichText {
+    let text = RichText::new(if state.is_empty() { "–" } else { state }).strong();
+    if state == "IDLE" {
+        text.color(Color32::GREEN)
+    } else {
+        text
+    }
+}
@@ -0,0 +1,180 @@
+use crate::config::StreamConfig;
+use crate::stream::PreflightCheck;
+use crate::t;
+use egui::{Color32, RichText, Window};
+
+pub enum StreamAction {
+    None,
+    Preflight(StreamConfig),
+    Start(StreamConfig),
+    Stop,
+}
+
+/// Settings, preflight checklist and start/stop of the network stream
+pub struct StreamWindow {
+    visible: bool,
+    config: StreamConfig,
+    checks: Option<Vec<PreflightCheck>>,
+    busy: bool,
+    streaming: bool,
+    show_url: bool,
+    message: Option<Result<String, String>>,
+}
+
+impl Default for StreamWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StreamWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            config: StreamConfig::default(),
+            checks: None,
+            busy: false,
+            streaming: false,
+            show_url: false,
+            message: None,
+        }
+    }
+
+    pub fn open(&mut self, config: StreamConfig) {
+        if !self.visible {
+            self.config = config;
+        }
+        self.visible = true;
+    }
+
+    pub fn set_checks(&mut self, checks: Vec<PreflightCheck>) {
+        self.busy = false;
+        self.checks = Some(checks);
+    }
+
+    /// Record the outcome of starting or stopping, or of the pipeline dying on its own
+    pub fn set_streaming(&mut self, streaming: bool, message: Result<String, String>) {
+        self.busy = false;
+        self.streaming = streaming;
+        self.message = Some(message);
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> StreamAction {
+        if !self.visible {
+            return StreamAction::None;
+        }
+
+        let mut action = StreamAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::BROADCAST, t!("stream-title")))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(460.0, 380.0))
+            .show(ctx, |ui| {
+                ui.add_enabled_ui(!self.streaming && !self.busy, |ui| {
+                    egui::Grid::new("stream_settings_grid").num_columns(2).show(ui, |ui| {
+                        ui.label(t!("stream-url"));
+                        ui.horizontal(|ui| {
+                            ui.add(
+                                egui::TextEdit::singleline(&mut self.config.url)
+                                    .password(!self.show_url)
+                                    .hint_text("rtmp://live.example.com/app/stream-key")
+                                    .desired_width(280.0),
+                            );
+                            ui.checkbox(&mut self.show_url, t!("stream-show-url"));
+                        });
+                        ui.end_row();
+
+                        ui.label(t!("stream-video-bitrate"));
+                        ui.add(egui::TextEdit::singleline(&mut self.config.video_bitrate).desired_width(80.0));
+                        ui.end_row();
+
+                        ui.label(t!("stream-audio-bitrate"));
+                        ui.add(egui::TextEdit::singleline(&mut self.config.audio_bitrate).desired_width(80.0));
+                        ui.end_row();
+
+                        ui.label(t!("stream-max-size"));
+                        ui.horizontal(|ui| {
+                            let mut max_size = self.config.max_size.unwrap_or(0);
+                            if ui.add(egui::DragValue::new(&mut max_size).range(0..=4096).speed(10)).changed() {
+                                self.config.max_size = (max_size > 0).then_some(max_size);
+                            }
+                            ui.label(if self.config.max_size.is_some() { "px".to_string() } else { t!("stream-device-resolution") });
+                        });
+                        ui.end_row();
+
+                        ui.label("ffmpeg:");
+                        let mut ffmpeg_path = self.config.ffmpeg_path.clone().unwrap_or_default();
+                        if ui
+                            .add(
+                                egui::TextEdit::singleline(&mut ffmpeg_path)
+                                    .hint_text(t!("stream-ffmpeg-auto"))
+                                    .desired_width(280.0),
+                            )
+                            .changed()
+                        {
+                            self.config.ffmpeg_path = (!ffmpeg_path.trim().is_empty()).then_some(ffmpeg_path);
+                        }
+                        ui.end_row();
+                    });
+                    ui.checkbox(&mut self.config.show_mirror, t!("stream-show-mirror"));
+                });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(!self.busy && !self.streaming, egui::Button::new(t!("stream-preflight")))
+                        .clicked()
+                    {
+                        self.busy = true;
+                        self.message = None;
+                        action = StreamAction::Preflight(self.config.clone());
+                    }
+                    if self.streaming {
+                        if ui.button(format!("{} {}", egui_phosphor::fill::STOP, t!("stream-stop"))).clicked() {
+                            action = StreamAction::Stop;
+                        }
+                    } else if ui
+                        .add_enabled(!self.busy, egui::Button::new(format!("{} {}", egui_phosphor::fill::BROADCAST, t!("stream-start"))))
+                        .clicked()
+                    {
+                        self.busy = true;
+                        self.message = None;
+                        action = StreamAction::Start(self.config.clone());
+                    }
+                    if self.busy {
+                        ui.add(egui::Spinner::new().size(14.0));
+                    }
+                });
+
+                if let Some(checks) = &self.checks {
+                    for check in checks {
+                        ui.horizontal(|ui| {
+                            let (icon, color, text) = match &check.result {
+                                Ok(text) => (egui_phosphor::fill::CHECK_CIRCLE, Color32::GREEN, text),
+                                Err(text) => (egui_phosphor::fill::X_CIRCLE, Color32::RED, text),
+                            };
+                            ui.label(RichText::new(icon).color(color));
+                            ui.label(RichText::new(check.name).strong());
+                            ui.label(RichText::new(text).size(11.0));
+                        });
+                    }
+                }
+
+                match &self.message {
+                    Some(Ok(message)) => {
+                        ui.label(RichText::new(message).color(Color32::GREEN));
+                    }
+                    Some(Err(message)) => {
+                        ui.label(RichText::new(message).color(Color32::RED));
+                    }
+                    None => {}
+                }
+            });
+
+        self.visible = open;
+        action
+    }
+}
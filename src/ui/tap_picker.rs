@@ -0,0 +1,207 @@
+use crate::config::MacroInput;
+use crate::t;
+use egui::{Color32, RichText, Sense, Stroke, TextureHandle, Window};
+use std::time::{Duration, Instant};
+
+pub enum TapPickerAction {
+    None,
+    /// Take a new screenshot
+    Refresh,
+    /// Send a tap or long press; `refresh` asks for a new screenshot once the screen has reacted
+    Send { serial: String, input: MacroInput, refresh: bool },
+}
+
+/// Longer side of the screenshot shown in the picker
+pub const PICKER_EDGE: u32 = 1280;
+/// Holding the mouse button on the screenshot this long sends a long press
+const HOLD: Duration = Duration::from_millis(500);
+
+/// A screenshot of the device where clicks become taps at the same spot on the device
+pub struct TapPicker {
+    visible: bool,
+    device: String,
+    pending: Option<egui::ColorImage>,
+    texture: Option<TextureHandle>,
+    /// Screen size of the device, in the pixels `input tap` takes
+    screen: (u32, u32),
+    loading: bool,
+    error: Option<String>,
+    long_press_ms: u32,
+    refresh_after_tap: bool,
+    /// Where and when the mouse button went down on the screenshot
+    pressed: Option<(Instant, [f32; 2])>,
+    /// Last point sent, as a fraction of the screen
+    last: Option<[f32; 2]>,
+}
+
+impl Default for TapPicker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TapPicker {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            device: String::new(),
+            pending: None,
+            texture: None,
+            screen: (0, 0),
+            loading: false,
+            error: None,
+            long_press_ms: 800,
+            refresh_after_tap: true,
+            pressed: None,
+            last: None,
+        }
+    }
+
+    pub fn open(&mut self, device: String) {
+        if device != self.device {
+            self.texture = None;
+            self.last = None;
+        }
+        self.visible = true;
+        self.device = device;
+    }
+
+    pub fn device(&self) -> &str {
+        &self.device
+    }
+
+    pub fn set_loading(&mut self) {
+        self.loading = true;
+    }
+
+    pub fn set_screenshot(&mut self, result: Result<(egui::ColorImage, (u32, u32)), String>) {
+        self.loading = false;
+        match result {
+            Ok((image, screen)) => {
+                self.pending = Some(image);
+                self.screen = screen;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> TapPickerAction {
+        if !self.visible {
+            return TapPickerAction::None;
+        }
+        if let Some(image) = self.pending.take() {
+            self.texture = Some(ctx.load_texture("tap_picker", image, egui::TextureOptions::LINEAR));
+        }
+
+        let mut action = TapPickerAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::HAND_TAP, t!("tap-picker-title")))
+            .id(egui::Id::new("tap_picker"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(380.0, 720.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(&self.device).weak());
+                    if ui
+                        .add_enabled(!self.loading, egui::Button::new(egui_phosphor::fill::ARROWS_CLOCKWISE))
+                        .on_hover_text(t!("tap-picker-refresh"))
+                        .clicked()
+                    {
+                        action = TapPickerAction::Refresh;
+                    }
+                    if self.loading {
+                        ui.add(egui::Spinner::new().size(14.0));
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.refresh_after_tap, t!("tap-picker-refresh-after"));
+                    ui.label(t!("tap-picker-long-press"));
+                    ui.add(egui::DragValue::new(&mut self.long_press_ms).range(300..=5_000).speed(10).suffix(" ms"));
+                });
+                ui.label(RichText::new(t!("tap-picker-hint")).size(11.0).color(Color32::GRAY));
+                if let Some(error) = &self.error {
+                    ui.label(RichText::new(error).color(Color32::RED));
+                }
+                ui.separator();
+
+                if let Some(input) = self.show_screenshot(ui) {
+                    action = TapPickerAction::Send {
+                        serial: self.device.clone(),
+                        input,
+                        refresh: self.refresh_after_tap,
+                    };
+                }
+            });
+
+        self.visible = open;
+        action
+    }
+
+    /// The screenshot scaled to fit, and the input for a click or hold on it
+    fn show_screenshot(&mut self, ui: &mut egui::Ui) -> Option<MacroInput> {
+        let Some(texture) = &self.texture else {
+            if !self.loading {
+                ui.label(RichText::new(t!("tap-picker-empty")).color(Color32::GRAY));
+            }
+            return None;
+        };
+        let (width, height) = self.screen;
+        let image_size = texture.size_vec2();
+        let scale = (ui.available_width() / image_size.x).min(ui.available_height() / image_size.y).min(1.0);
+        let response = ui.add(
+            egui::Image::new(texture)
+                .fit_to_exact_size(image_size * scale)
+                .sense(Sense::click_and_drag()),
+        );
+        let rect = response.rect;
+        let fraction = |position: egui::Pos2| {
+            let offset = position - rect.min;
+            [(offset.x / rect.width()).clamp(0.0, 1.0), (offset.y / rect.height()).clamp(0.0, 1.0)]
+        };
+        let painter = ui.painter_at(rect);
+
+        if let Some(position) = response.hover_pos() {
+            let (x, y) = crate::gesture::to_pixels(fraction(position), width, height);
+            let stroke = Stroke::new(1.0, Color32::from_white_alpha(160));
+            painter.hline(rect.x_range(), position.y, stroke);
+            painter.vline(position.x, rect.y_range(), stroke);
+            response.clone().on_hover_text_at_pointer(format!("{}, {}", x, y));
+        }
+        if let Some(last) = self.last {
+            let position = rect.min + egui::vec2(last[0] * rect.width(), last[1] * rect.height());
+            painter.circle_stroke(position, 8.0, Stroke::new(2.0, Color32::RED));
+        }
+
+        // A right click or holding the left button is a long press, anything shorter a tap
+        let mut sent = None;
+        if response.secondary_clicked()
+            && let Some(position) = response.interact_pointer_pos()
+        {
+            sent = Some((fraction(position), true));
+        } else if response.is_pointer_button_down_on() {
+            if self.pressed.is_none()
+                && let Some(position) = response.interact_pointer_pos()
+            {
+                self.pressed = Some((Instant::now(), fraction(position)));
+            }
+            ui.ctx().request_repaint();
+        } else if let Some((since, point)) = self.pressed.take()
+            && ui.input(|input| input.pointer.primary_released())
+        {
+            sent = Some((point, since.elapsed() >= HOLD));
+        }
+
+        let (point, long) = sent?;
+        self.last = Some(point);
+        let (x, y) = crate::gesture::to_pixels(point, width, height);
+        Some(if long {
+            MacroInput::Swipe { x1: x, y1: y, x2: x, y2: y, duration_ms: self.long_press_ms }
+        } else {
+            MacroInput::Tap { x, y }
+        })
+    }
+}
@@ -0,0 +1,151 @@
+use crate::config::CustomTheme;
+use crate::t;
+use egui::{Color32, CornerRadius, RichText, Ui, Visuals};
+
+const DEFAULT_ACCENT: [u8; 3] = [0, 150, 136];
+const DEFAULT_TINT: [u8; 3] = [40, 80, 160];
+const DEFAULT_CORNER_RADIUS: u8 = 6;
+const MAX_CORNER_RADIUS: u8 = 16;
+
+pub const UI_SCALE_RANGE: std::ops::RangeInclusive<f32> = 0.5..=3.0;
+pub const FONT_SIZE_RANGE: std::ops::RangeInclusive<f32> = 8.0..=28.0;
+
+fn rgb([r, g, b]: [u8; 3]) -> Color32 {
+    Color32::from_rgb(r, g, b)
+}
+
+/// Black or white, whichever reads better on `background`
+fn text_on(background: Color32) -> Color32 {
+    if background.intensity() > 0.55 {
+        Color32::BLACK
+    } else {
+        Color32::WHITE
+    }
+}
+
+/// `theme` ("default", "dark" or "light") with the user's accent, tint and rounding applied
+pub fn visuals(theme: &str, custom: &CustomTheme) -> Visuals {
+    let mut visuals = match theme {
+        "dark" => Visuals::dark(),
+        "light" => Visuals::light(),
+        _ => Visuals::default(),
+    };
+
+    if let Some(accent) = custom.accent.map(rgb) {
+        visuals.selection.bg_fill = accent;
+        visuals.selection.stroke.color = text_on(accent);
+        visuals.hyperlink_color = accent;
+        visuals.text_cursor.stroke.color = accent;
+        visuals.widgets.hovered.bg_stroke.color = accent;
+        visuals.widgets.active.bg_stroke.color = accent;
+    }
+
+    if let Some(tint) = custom.background_tint.map(rgb) {
+        let strength = custom.tint_strength.clamp(0.0, 0.5);
+        for fill in [
+            &mut visuals.panel_fill,
+            &mut visuals.window_fill,
+            &mut visuals.faint_bg_color,
+            &mut visuals.extreme_bg_color,
+        ] {
+            *fill = fill.lerp_to_gamma(tint, strength);
+        }
+    }
+
+    if let Some(radius) = custom.corner_radius {
+        let radius = CornerRadius::same(radius.min(MAX_CORNER_RADIUS));
+        visuals.window_corner_radius = radius;
+        visuals.menu_corner_radius = radius;
+        for widget in [
+            &mut visuals.widgets.noninteractive,
+            &mut visuals.widgets.inactive,
+            &mut visuals.widgets.hovered,
+            &mut visuals.widgets.active,
+            &mut visuals.widgets.open,
+        ] {
+            widget.corner_radius = radius;
+        }
+    }
+
+    visuals
+}
+
+/// Zoom the whole UI by `scale` over the monitor's native scale factor, and size text so
+/// body text is `font_size` points
+pub fn apply_scaling(ctx: &egui::Context, scale: f32, font_size: f32) {
+    // The zoom factor multiplies the native pixels per point, which may not be known yet at startup
+    ctx.set_zoom_factor(scale.clamp(*UI_SCALE_RANGE.start(), *UI_SCALE_RANGE.end()));
+
+    let defaults = egui::Style::default().text_styles;
+    let body = defaults.get(&egui::TextStyle::Body).map_or(12.5, |font| font.size);
+    let factor = font_size.clamp(*FONT_SIZE_RANGE.start(), *FONT_SIZE_RANGE.end()) / body;
+    ctx.all_styles_mut(|style| {
+        for (text_style, font) in style.text_styles.iter_mut() {
+            if let Some(default) = defaults.get(text_style) {
+                font.size = default.size * factor;
+            }
+        }
+    });
+}
+
+/// One optional color: a checkbox to override the theme and a picker for the color
+fn optional_color(ui: &mut Ui, label: String, value: &mut Option<[u8; 3]>, default: [u8; 3]) -> bool {
+    let mut changed = false;
+    ui.horizontal(|ui| {
+        let mut enabled = value.is_some();
+        if ui.checkbox(&mut enabled, label).changed() {
+            *value = enabled.then_some(default);
+            changed = true;
+        }
+        if let Some(color) = value {
+            changed |= ui.color_edit_button_srgb(color).changed();
+        }
+    });
+    changed
+}
+
+/// Accent, tint and rounding controls with a sample of the result; true when anything changed
+pub fn show_theme_editor(ui: &mut Ui, theme: &str, custom: &mut CustomTheme) -> bool {
+    let mut changed = optional_color(ui, t!("theme-accent"), &mut custom.accent, DEFAULT_ACCENT);
+
+    changed |= optional_color(ui, t!("theme-tint"), &mut custom.background_tint, DEFAULT_TINT);
+    if custom.background_tint.is_some() {
+        changed |= ui
+            .add(egui::Slider::new(&mut custom.tint_strength, 0.0..=0.5).text(t!("theme-tint-strength")))
+            .changed();
+    }
+
+    ui.horizontal(|ui| {
+        let mut rounded = custom.corner_radius.is_some();
+        if ui.checkbox(&mut rounded, t!("theme-corner-radius")).changed() {
+            custom.corner_radius = rounded.then_some(DEFAULT_CORNER_RADIUS);
+            changed = true;
+        }
+        if let Some(radius) = &mut custom.corner_radius {
+            changed |= ui.add(egui::Slider::new(radius, 0..=MAX_CORNER_RADIUS).suffix(" pt")).changed();
+        }
+    });
+
+    if *custom != CustomTheme::default() && ui.small_button(t!("theme-reset")).clicked() {
+        *custom = CustomTheme::default();
+        changed = true;
+    }
+
+    // Sample widgets drawn with the edited look, whatever the window currently uses
+    let preview = visuals(theme, custom);
+    ui.label(RichText::new(t!("theme-preview")).size(11.0).weak());
+    ui.scope(|ui| {
+        ui.style_mut().visuals = preview.clone();
+        egui::Frame::window(ui.style()).show(ui, |ui| {
+            ui.horizontal(|ui| {
+                let _ = ui.button(t!("theme-preview-button"));
+                let _ = ui.selectable_label(true, t!("theme-preview-selected"));
+                ui.hyperlink_to(t!("theme-preview-link"), "https://github.com/Genxster1998/DroidView");
+            });
+            let mut value = 0.6;
+            ui.add(egui::Slider::new(&mut value, 0.0..=1.0).show_value(false));
+        });
+    });
+
+    changed
+}
@@ -0,0 +1,229 @@
+use crate::t;
+use chrono::{DateTime, Local};
+use egui::{Color32, RichText, Window};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// How many past notifications the history drawer keeps
+const HISTORY_LIMIT: usize = 200;
+
+/// At most this many toasts are stacked on screen; older ones stay in the history
+const MAX_VISIBLE: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn color(self) -> Color32 {
+        match self {
+            Severity::Info => Color32::LIGHT_BLUE,
+            Severity::Success => Color32::GREEN,
+            Severity::Warning => Color32::YELLOW,
+            Severity::Error => Color32::RED,
+        }
+    }
+
+    pub fn icon(self) -> &'static str {
+        match self {
+            Severity::Info => egui_phosphor::fill::INFO,
+            Severity::Success => egui_phosphor::fill::CHECK_CIRCLE,
+            Severity::Warning => egui_phosphor::fill::WARNING,
+            Severity::Error => egui_phosphor::fill::X_CIRCLE,
+        }
+    }
+
+    /// Errors stay up long enough to be read even when several tasks finish at once
+    fn lifetime(self) -> Duration {
+        match self {
+            Severity::Info | Severity::Success => Duration::from_secs(4),
+            Severity::Warning => Duration::from_secs(8),
+            Severity::Error => Duration::from_secs(15),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub text: String,
+    pub severity: Severity,
+    pub at: DateTime<Local>,
+    shown_at: Instant,
+}
+
+/// Stack of auto-dismissing notifications, with a history drawer of everything shown
+pub struct Toasts {
+    active: Vec<Toast>,
+    history: VecDeque<Toast>,
+    history_open: bool,
+    unseen_errors: usize,
+}
+
+impl Default for Toasts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Toasts {
+    pub fn new() -> Self {
+        Self {
+            active: Vec::new(),
+            history: VecDeque::new(),
+            history_open: false,
+            unseen_errors: 0,
+        }
+    }
+
+    pub fn push(&mut self, severity: Severity, text: impl Into<String>) {
+        let text = text.into();
+        // The same message again (e.g. a repeated refresh) only restarts its timer
+        if let Some(last) = self.active.last_mut()
+            && last.text == text
+            && last.severity == severity
+        {
+            last.shown_at = Instant::now();
+            return;
+        }
+
+        let toast = Toast {
+            text,
+            severity,
+            at: Local::now(),
+            shown_at: Instant::now(),
+        };
+        if severity == Severity::Error && !self.history_open {
+            self.unseen_errors += 1;
+        }
+        self.history.push_front(toast.clone());
+        self.history.truncate(HISTORY_LIMIT);
+        self.active.push(toast);
+        if self.active.len() > MAX_VISIBLE {
+            self.active.remove(0);
+        }
+    }
+
+    pub fn info(&mut self, text: impl Into<String>) {
+        self.push(Severity::Info, text);
+    }
+
+    pub fn success(&mut self, text: impl Into<String>) {
+        self.push(Severity::Success, text);
+    }
+
+    pub fn warning(&mut self, text: impl Into<String>) {
+        self.push(Severity::Warning, text);
+    }
+
+    pub fn error(&mut self, text: impl Into<String>) {
+        self.push(Severity::Error, text);
+    }
+
+    /// Most recent notification, for the status line
+    pub fn latest(&self) -> Option<&Toast> {
+        self.history.front()
+    }
+
+    pub fn toggle_history(&mut self) {
+        self.history_open = !self.history_open;
+        self.unseen_errors = 0;
+    }
+
+    /// Errors pushed since the history was last opened
+    pub fn unseen_errors(&self) -> usize {
+        self.unseen_errors
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        let hovered = self.show_active(ctx);
+        if !hovered {
+            self.active.retain(|toast| toast.shown_at.elapsed() < toast.severity.lifetime());
+        }
+        if !self.active.is_empty() {
+            ctx.request_repaint_after(Duration::from_millis(250));
+        }
+        self.show_history(ctx);
+    }
+
+    /// Toasts stacked in the bottom-right corner; true while the pointer is over one, which pauses dismissal
+    fn show_active(&mut self, ctx: &egui::Context) -> bool {
+        if self.active.is_empty() {
+            return false;
+        }
+
+        let mut dismiss = None;
+        let response = egui::Area::new(egui::Id::new("toasts"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-12.0, -40.0))
+            .order(egui::Order::Foreground)
+            .interactable(true)
+            .show(ctx, |ui| {
+                ui.set_max_width(360.0);
+                for (index, toast) in self.active.iter().enumerate() {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(toast.severity.icon()).color(toast.severity.color()).size(16.0));
+                            ui.add(egui::Label::new(&toast.text).wrap());
+                            if ui.small_button(egui_phosphor::fill::X).on_hover_text(t!("notifications-dismiss")).clicked() {
+                                dismiss = Some(index);
+                            }
+                        });
+                    });
+                    ui.add_space(4.0);
+                }
+            })
+            .response;
+
+        if let Some(index) = dismiss {
+            self.active.remove(index);
+        }
+        response.contains_pointer()
+    }
+
+    fn show_history(&mut self, ctx: &egui::Context) {
+        if !self.history_open {
+            return;
+        }
+
+        let mut open = self.history_open;
+        Window::new(format!("{} {}", egui_phosphor::fill::BELL, t!("notifications-title")))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(480.0, 360.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(!self.history.is_empty(), egui::Button::new(t!("notifications-clear"))).clicked() {
+                        self.history.clear();
+                    }
+                    if ui.add_enabled(!self.history.is_empty(), egui::Button::new(format!("{} {}", egui_phosphor::fill::COPY, t!("notifications-copy-all")))).clicked() {
+                        let text: Vec<String> = self
+                            .history
+                            .iter()
+                            .map(|toast| format!("{} [{:?}] {}", toast.at.format("%H:%M:%S"), toast.severity, toast.text))
+                            .collect();
+                        ui.ctx().copy_text(text.join("\n"));
+                    }
+                });
+                ui.separator();
+
+                if self.history.is_empty() {
+                    ui.label(RichText::new(t!("notifications-empty")).color(Color32::GRAY));
+                    return;
+                }
+                egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                    for toast in &self.history {
+                        ui.horizontal_top(|ui| {
+                            ui.label(RichText::new(toast.at.format("%H:%M:%S").to_string()).monospace().color(Color32::GRAY));
+                            ui.label(RichText::new(toast.severity.icon()).color(toast.severity.color()));
+                            ui.add(egui::Label::new(&toast.text).wrap());
+                        });
+                    }
+                });
+            });
+        self.history_open = open;
+    }
+}
@@ -0,0 +1,201 @@
+use crate::config::SshTunnelProfile;
+use crate::t;
+use egui::{Color32, RichText, Window};
+
+pub enum TunnelAction {
+    None,
+    Open(SshTunnelProfile),
+    Close(String),
+    /// The edited list of profiles to store in the config
+    Save(Vec<SshTunnelProfile>),
+}
+
+/// Saved SSH tunnels with open/close, and a form to add or edit one
+pub struct TunnelsWindow {
+    visible: bool,
+    profiles: Vec<SshTunnelProfile>,
+    /// Profile in the form, with its index when it is an edit
+    draft: Option<(Option<usize>, SshTunnelProfile)>,
+    draft_error: Option<String>,
+    /// Names of the tunnels that are up, and of those still opening
+    open: Vec<String>,
+    opening: Vec<String>,
+}
+
+impl Default for TunnelsWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TunnelsWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            profiles: Vec::new(),
+            draft: None,
+            draft_error: None,
+            open: Vec::new(),
+            opening: Vec::new(),
+        }
+    }
+
+    pub fn open(&mut self, profiles: Vec<SshTunnelProfile>) {
+        if !self.visible {
+            self.profiles = profiles;
+            self.draft = None;
+        }
+        self.visible = true;
+    }
+
+    pub fn set_status(&mut self, open: Vec<String>, opening: Vec<String>) {
+        self.open = open;
+        self.opening = opening;
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> TunnelAction {
+        if !self.visible {
+            return TunnelAction::None;
+        }
+
+        let mut action = TunnelAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::PLUGS_CONNECTED, t!("tunnels-title")))
+            .id(egui::Id::new("ssh_tunnels"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size(egui::vec2(480.0, 360.0))
+            .show(ctx, |ui| {
+                ui.label(RichText::new(t!("tunnels-hint")).size(11.0).color(Color32::GRAY));
+                ui.separator();
+
+                if self.profiles.is_empty() {
+                    ui.label(RichText::new(t!("tunnels-empty")).color(Color32::GRAY));
+                }
+                let mut remove = None;
+                for (index, profile) in self.profiles.iter().enumerate() {
+                    let is_open = self.open.contains(&profile.name);
+                    let is_opening = self.opening.contains(&profile.name);
+                    ui.horizontal(|ui| {
+                        let (icon, color) = if is_open {
+                            (egui_phosphor::fill::PLUGS_CONNECTED, Color32::GREEN)
+                        } else {
+                            (egui_phosphor::fill::PLUGS, Color32::GRAY)
+                        };
+                        ui.label(RichText::new(icon).color(color));
+                        ui.label(RichText::new(&profile.name).strong()).on_hover_text(format!(
+                            "{} → {}",
+                            profile.ssh_host, profile.target
+                        ));
+                        ui.label(RichText::new(format!("localhost:{}", profile.local_port)).weak());
+
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            ui.add_enabled_ui(!is_open && !is_opening, |ui| {
+                                if ui
+                                    .small_button(egui_phosphor::fill::TRASH)
+                                    .on_hover_text(t!("tunnels-delete"))
+                                    .clicked()
+                                {
+                                    remove = Some(index);
+                                }
+                                if ui
+                                    .small_button(egui_phosphor::fill::PENCIL_SIMPLE)
+                                    .on_hover_text(t!("tunnels-edit"))
+                                    .clicked()
+                                {
+                                    self.draft = Some((Some(index), profile.clone()));
+                                    self.draft_error = None;
+                                }
+                            });
+                            if is_opening {
+                                ui.spinner();
+                            } else if is_open {
+                                if ui.button(t!("tunnels-close")).clicked() {
+                                    action = TunnelAction::Close(profile.name.clone());
+                                }
+                            } else if ui.button(t!("tunnels-open")).clicked() {
+                                action = TunnelAction::Open(profile.clone());
+                            }
+                        });
+                    });
+                }
+                if let Some(index) = remove {
+                    self.profiles.remove(index);
+                    action = TunnelAction::Save(self.profiles.clone());
+                }
+
+                ui.separator();
+                match &mut self.draft {
+                    None => {
+                        if ui.button(format!("{} {}", egui_phosphor::fill::PLUS, t!("tunnels-add"))).clicked() {
+                            self.draft = Some((None, SshTunnelProfile::default()));
+                            self.draft_error = None;
+                        }
+                    }
+                    Some((editing, draft)) => {
+                        egui::Grid::new("tunnel_form").num_columns(2).show(ui, |ui| {
+                            ui.label(t!("tunnels-name"));
+                            ui.text_edit_singleline(&mut draft.name);
+                            ui.end_row();
+                            ui.label(t!("tunnels-ssh-host"));
+                            ui.add(egui::TextEdit::singleline(&mut draft.ssh_host).hint_text("user@lab.example.com"));
+                            ui.end_row();
+                            ui.label(t!("tunnels-ssh-port"));
+                            ui.add(egui::DragValue::new(&mut draft.ssh_port).range(1..=65535));
+                            ui.end_row();
+                            ui.label(t!("tunnels-target")).on_hover_text(t!("tunnels-target-hint"));
+                            ui.add(egui::TextEdit::singleline(&mut draft.target).hint_text("localhost:5555"));
+                            ui.end_row();
+                            ui.label(t!("tunnels-local-port"));
+                            ui.add(egui::DragValue::new(&mut draft.local_port).range(1024..=65535));
+                            ui.end_row();
+                            ui.label(t!("tunnels-identity"));
+                            let identity = draft.identity_file.get_or_insert_with(String::new);
+                            ui.add(egui::TextEdit::singleline(identity).hint_text("~/.ssh/id_ed25519"));
+                            ui.end_row();
+                        });
+
+                        let mut done = false;
+                        ui.horizontal(|ui| {
+                            if ui.button(t!("tunnels-save")).clicked() {
+                                if draft.identity_file.as_deref().is_some_and(|path| path.trim().is_empty()) {
+                                    draft.identity_file = None;
+                                }
+                                let duplicate = self.profiles.iter().enumerate().any(|(index, profile)| {
+                                    profile.name.trim() == draft.name.trim() && Some(index) != *editing
+                                });
+                                match crate::tunnel::validate(draft) {
+                                    Err(e) => self.draft_error = Some(e),
+                                    Ok(()) if duplicate => self.draft_error = Some(t!("tunnels-duplicate")),
+                                    Ok(()) => {
+                                        draft.name = draft.name.trim().to_string();
+                                        match editing {
+                                            Some(index) => self.profiles[*index] = draft.clone(),
+                                            None => self.profiles.push(draft.clone()),
+                                        }
+                                        action = TunnelAction::Save(self.profiles.clone());
+                                        done = true;
+                                    }
+                                }
+                            }
+                            if ui.button(t!("tunnels-cancel")).clicked() {
+                                done = true;
+                            }
+                        });
+                        if let Some(error) = &self.draft_error {
+                            ui.label(RichText::new(error).color(Color32::RED));
+                        }
+                        if done {
+                            self.draft = None;
+                            self.draft_error = None;
+                        }
+                    }
+                }
+            });
+
+        self.visible = open;
+        action
+    }
+}
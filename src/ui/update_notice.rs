@@ -0,0 +1,69 @@
+use crate::t;
+use crate::update::{UpdateInfo, CURRENT_VERSION};
+use egui::{Color32, RichText};
+
+pub enum UpdateNoticeAction {
+    None,
+    OpenChangelog(String),
+    /// Stop mentioning this version
+    Skip(String),
+}
+
+/// Corner notice that a newer release exists; stays until dismissed and never blocks the window
+#[derive(Default)]
+pub struct UpdateNotice {
+    update: Option<UpdateInfo>,
+}
+
+impl UpdateNotice {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&mut self, update: UpdateInfo) {
+        self.update = Some(update);
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> UpdateNoticeAction {
+        let Some(update) = &self.update else {
+            return UpdateNoticeAction::None;
+        };
+
+        let mut action = UpdateNoticeAction::None;
+        let mut dismiss = false;
+        egui::Area::new(egui::Id::new("update_notice"))
+            .anchor(egui::Align2::RIGHT_TOP, egui::vec2(-12.0, 36.0))
+            .order(egui::Order::Foreground)
+            .interactable(true)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_max_width(320.0);
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new(egui_phosphor::fill::ARROW_CIRCLE_UP)
+                                .color(Color32::LIGHT_BLUE)
+                                .size(16.0),
+                        );
+                        ui.label(RichText::new(t!("update-available", version = update.version)).strong());
+                        if ui.small_button(egui_phosphor::fill::X).on_hover_text(t!("update-remind")).clicked() {
+                            dismiss = true;
+                        }
+                    });
+                    ui.label(RichText::new(t!("update-current", version = CURRENT_VERSION)).size(11.0).weak());
+                    ui.horizontal(|ui| {
+                        if ui.button(t!("update-whats-new")).clicked() {
+                            action = UpdateNoticeAction::OpenChangelog(update.url.clone());
+                        }
+                        if ui.button(t!("update-skip")).clicked() {
+                            action = UpdateNoticeAction::Skip(update.version.clone());
+                        }
+                    });
+                });
+            });
+
+        if dismiss || matches!(action, UpdateNoticeAction::Skip(_)) {
+            self.update = None;
+        }
+        action
+    }
+}
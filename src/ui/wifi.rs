@@ -0,0 +1,157 @@
+use crate::t;
+use crate::wifi::WifiInfo;
+use egui::{Color32, RichText, Window};
+
+pub enum WifiAction {
+    None,
+    Refresh,
+    /// Copy the address and put it in the wireless ADB connect fields
+    UseIp(String),
+}
+
+/// The selected device's Wi-Fi connection
+pub struct WifiWindow {
+    visible: bool,
+    device: String,
+    info: Option<Result<WifiInfo, String>>,
+    loading: bool,
+}
+
+impl Default for WifiWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WifiWindow {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            device: String::new(),
+            info: None,
+            loading: false,
+        }
+    }
+
+    pub fn open(&mut self, device: String) {
+        if device != self.device {
+            self.info = None;
+        }
+        self.visible = true;
+        self.device = device;
+    }
+
+    pub fn set_loading(&mut self) {
+        self.loading = true;
+    }
+
+    pub fn set_info(&mut self, info: Result<WifiInfo, String>) {
+        self.info = Some(info);
+        self.loading = false;
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> WifiAction {
+        if !self.visible {
+            return WifiAction::None;
+        }
+
+        let mut action = WifiAction::None;
+        let mut open = self.visible;
+
+        Window::new(format!("{} {}", egui_phosphor::fill::WIFI_HIGH, t!("wifi-title")))
+            .id(egui::Id::new("wifi_info"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .default_width(340.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(&self.device).weak());
+                    if ui.add_enabled(!self.loading, egui::Button::new(t!("wifi-refresh"))).clicked() {
+                        action = WifiAction::Refresh;
+                    }
+                    if self.loading {
+                        ui.add(egui::Spinner::new().size(14.0));
+                    }
+                });
+                ui.separator();
+
+                match &self.info {
+                    Some(Ok(info)) if info.ssid.is_some() => {
+                        if let Some(use_ip) = show_connection(ui, info) {
+                            action = use_ip;
+                        }
+                    }
+                    Some(Ok(info)) => {
+                        let text = if info.enabled { t!("wifi-disconnected") } else { t!("wifi-off") };
+                        ui.label(RichText::new(text).color(Color32::GRAY));
+                    }
+                    Some(Err(e)) => {
+                        ui.label(RichText::new(e).color(Color32::RED));
+                    }
+                    None => {}
+                }
+            });
+
+        self.visible = open;
+        action
+    }
+}
+
+fn show_connection(ui: &mut egui::Ui, info: &WifiInfo) -> Option<WifiAction> {
+    let mut action = None;
+    let unknown = || "–".to_string();
+    egui::Grid::new("wifi_grid").num_columns(2).spacing([16.0, 6.0]).show(ui, |ui| {
+        ui.label(t!("wifi-ssid"));
+        ui.label(RichText::new(info.ssid.clone().unwrap_or_default()).strong());
+        ui.end_row();
+
+        ui.label(t!("wifi-ip"));
+        ui.horizontal(|ui| {
+            match &info.ip {
+                Some(ip) => {
+                    ui.label(RichText::new(ip).monospace());
+                    if ui
+                        .small_button(egui_phosphor::fill::COPY)
+                        .on_hover_text(t!("wifi-use-ip"))
+                        .clicked()
+                    {
+                        action = Some(WifiAction::UseIp(ip.clone()));
+                    }
+                }
+                None => {
+                    ui.label(unknown());
+                }
+            }
+        });
+        ui.end_row();
+
+        ui.label(t!("wifi-signal"));
+        let signal = match (info.rssi_dbm, info.signal_bars()) {
+            (Some(rssi), Some(bars)) => format!("{} dBm  {}", rssi, "▮".repeat(bars as usize) + "▯".repeat(4 - bars as usize).as_str()),
+            _ => unknown(),
+        };
+        ui.label(signal);
+        ui.end_row();
+
+        ui.label(t!("wifi-link-speed"));
+        ui.label(info.link_speed_mbps.map_or_else(unknown, |speed| format!("{} Mbps", speed)));
+        ui.end_row();
+
+        ui.label(t!("wifi-band"));
+        let band = match (info.band(), info.frequency_mhz, &info.standard) {
+            (Some(band), Some(frequency), Some(standard)) => format!("{} ({} MHz, {})", band, frequency, standard),
+            (Some(band), Some(frequency), None) => format!("{} ({} MHz)", band, frequency),
+            _ => unknown(),
+        };
+        ui.label(band);
+        ui.end_row();
+
+        if let Some(bssid) = &info.bssid {
+            ui.label("BSSID");
+            ui.label(RichText::new(bssid).monospace());
+            ui.end_row();
+        }
+    });
+    action
+}
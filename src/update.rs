@@ -0,0 +1,90 @@
+//! Opt-in check for a newer DroidView release on GitHub
+
+use anyhow::Result;
+use serde::Deserialize;
+
+const LATEST_RELEASE: &str = "https://api.github.com/repos/Genxster1998/DroidView/releases/latest";
+
+/// The version this build reports, from Cargo.toml
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// A release newer than the running build
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateInfo {
+    /// Without the leading `v`, e.g. `0.2.0`
+    pub version: String,
+    /// Release page with the changelog
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+    html_url: String,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    prerelease: bool,
+}
+
+/// `v1.2.3`, `1.2` or `1.2.3-beta` as comparable numbers; pre-release suffixes are ignored
+pub fn parse_version(text: &str) -> Option<(u64, u64, u64)> {
+    let text = text.trim().trim_start_matches(['v', 'V']);
+    let core = text.split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|part| part.parse::<u64>());
+    let major = parts.next()?.ok()?;
+    let minor = parts.next().unwrap_or(Ok(0)).ok()?;
+    let patch = parts.next().unwrap_or(Ok(0)).ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((major, minor, patch))
+}
+
+/// Whether `latest` is a higher version than `current`; unparsable versions never are
+pub fn is_newer(latest: &str, current: &str) -> bool {
+    match (parse_version(latest), parse_version(current)) {
+        (Some(latest), Some(current)) => latest > current,
+        _ => false,
+    }
+}
+
+/// Ask GitHub for the latest release; `None` when this build is up to date
+pub async fn check_for_update() -> Result<Option<UpdateInfo>> {
+    let release: Release = crate::utils::http_client()?
+        .get(LATEST_RELEASE)
+        .header("Accept", "application/vnd.github+json")
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    if release.draft || release.prerelease || !is_newer(&release.tag_name, CURRENT_VERSION) {
+        return Ok(None);
+    }
+    Ok(Some(UpdateInfo {
+        version: release.tag_name.trim_start_matches(['v', 'V']).to_string(),
+        url: release.html_url,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn versions() {
+        assert_eq!(parse_version("v0.1.5"), Some((0, 1, 5)));
+        assert_eq!(parse_version("1.2"), Some((1, 2, 0)));
+        assert_eq!(parse_version("2.0.0-beta.1"), Some((2, 0, 0)));
+        assert_eq!(parse_version("nightly"), None);
+        assert_eq!(parse_version("1.2.3.4"), None);
+
+        assert!(is_newer("v0.1.10", "0.1.5"));
+        assert!(is_newer("v1.0", "0.9.9"));
+        assert!(!is_newer("v0.1.5", "0.1.5"));
+        assert!(!is_newer("v0.1.4", "0.1.5"));
+        assert!(!is_newer("latest", "0.1.5"));
+    }
+}
@@ -1,9 +1,66 @@
 use anyhow::Result;
 use std::path::PathBuf;
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 use tracing;
 
+/// Attributes of an XML element in the order they are written, with entities decoded
+pub fn xml_attributes(element: &quick_xml::events::BytesStart) -> Result<Vec<(String, String)>> {
+    element
+        .attributes()
+        .map(|attribute| {
+            let attribute = attribute?;
+            let name = String::from_utf8_lossy(attribute.key.as_ref()).into_owned();
+            Ok((name, attribute.unescape_value()?.into_owned()))
+        })
+        .collect()
+}
+
+/// Folders beside the executable where a portable bundle keeps its tools
+fn bundled_dirs() -> Vec<PathBuf> {
+    let Some(exe_dir) = std::env::current_exe().ok().and_then(|exe| exe.parent().map(PathBuf::from)) else {
+        return Vec::new();
+    };
+
+    let mut dirs = vec![exe_dir.clone(), exe_dir.join("platform-tools")];
+    // Extracted release archives, e.g. scrcpy-win64-v3.1
+    if let Ok(entries) = std::fs::read_dir(&exe_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_tool_dir = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_lowercase().starts_with("scrcpy"))
+                .unwrap_or(false);
+            if is_tool_dir && path.is_dir() {
+                dirs.push(path);
+            }
+        }
+    }
+    dirs
+}
+
+/// A non-executable file, such as an APK, shipped alongside DroidView or a bundled tool
+pub fn find_bundled_file(name: &str) -> Option<PathBuf> {
+    bundled_dirs().into_iter().map(|dir| dir.join(name)).find(|path| path.is_file())
+}
+
 pub fn find_executable(name: &str) -> Option<PathBuf> {
+    // Tools shipped alongside DroidView win, so a USB-stick install is self-contained
+    for dir in bundled_dirs() {
+        let full_path = dir.join(format!("{}{}", name, std::env::consts::EXE_SUFFIX));
+        if full_path.is_file() {
+            return Some(full_path);
+        }
+    }
+    // Then the ones DroidView downloaded itself
+    for dir in crate::tools::installed_dirs() {
+        let full_path = dir.join(format!("{}{}", name, std::env::consts::EXE_SUFFIX));
+        if full_path.is_file() {
+            return Some(full_path);
+        }
+    }
+
     #[cfg(target_os = "windows")]
     {
         // On Windows, use 'where' command to find executable in PATH
@@ -16,16 +73,25 @@ pub fn find_executable(name: &str) -> Option<PathBuf> {
             }
         }
 
-        // Try common Windows paths
+        // Try common Windows paths, including Windows-on-ARM and package manager locations
         let common_paths = [
             "C:\\Program Files\\Android\\android-sdk\\platform-tools",
             "C:\\Program Files (x86)\\Android\\android-sdk\\platform-tools",
+            "C:\\Program Files (Arm)\\Android\\android-sdk\\platform-tools",
+            "%LOCALAPPDATA%\\Android\\Sdk\\platform-tools",
             "C:\\Users\\%USERNAME%\\AppData\\Local\\Android\\Sdk\\platform-tools",
+            "%LOCALAPPDATA%\\Microsoft\\WinGet\\Links",
+            "%USERPROFILE%\\scoop\\shims",
+            "C:\\ProgramData\\chocolatey\\bin",
             "C:\\Android\\platform-tools",
+            "C:\\platform-tools",
         ];
 
         for path in &common_paths {
-            let expanded_path = path.replace("%USERNAME%", &std::env::var("USERNAME").unwrap_or_default());
+            let mut expanded_path = path.to_string();
+            for var in ["USERNAME", "LOCALAPPDATA", "USERPROFILE"] {
+                expanded_path = expanded_path.replace(&format!("%{}%", var), &std::env::var(var).unwrap_or_default());
+            }
             let full_path = PathBuf::from(&expanded_path).join(format!("{}.exe", name));
             if full_path.exists() {
                 return Some(full_path);
@@ -36,11 +102,10 @@ pub fn find_executable(name: &str) -> Option<PathBuf> {
     #[cfg(not(target_os = "windows"))]
     {
         // On Unix-like systems, use 'which' command
-        if let Ok(output) = Command::new("which").arg(name).output() {
-            if output.status.success() {
-                let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                return Some(PathBuf::from(path));
-            }
+        if let Ok(output) = Command::new("which").arg(name).output()
+            && output.status.success() {
+            let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            return Some(PathBuf::from(path));
         }
 
         // Try common Unix paths
@@ -70,6 +135,18 @@ pub fn find_scrcpy() -> Option<PathBuf> {
     find_executable("scrcpy")
 }
 
+/// A configured path is missing when unset, blank, or pointing at a file that no longer exists
+pub fn tool_missing(path: Option<&str>) -> bool {
+    match path.map(str::trim) {
+        None | Some("") => true,
+        Some(path) => {
+            let path = std::path::Path::new(path);
+            // Bare names such as "adb" are resolved through PATH when run
+            path.components().count() > 1 && !path.exists()
+        }
+    }
+}
+
 pub fn is_process_running(process_name: &str) -> bool {
     #[cfg(target_os = "windows")]
     {
@@ -111,6 +188,13 @@ pub fn is_process_running(process_name: &str) -> bool {
     }
 }
 
+/// HTTP client identifying itself as DroidView, which the GitHub API requires
+pub fn http_client() -> Result<reqwest::Client> {
+    Ok(reqwest::Client::builder()
+        .user_agent(concat!("DroidView/", env!("CARGO_PKG_VERSION")))
+        .build()?)
+}
+
 pub fn open_url(url: &str) -> Result<()> {
     #[cfg(target_os = "windows")]
     {
@@ -130,6 +214,116 @@ pub fn open_url(url: &str) -> Result<()> {
     Ok(())
 }
 
+/// Open a folder in the platform file manager
+pub fn open_folder(path: &std::path::Path) -> Result<()> {
+    #[cfg(target_os = "windows")]
+    {
+        Command::new("explorer").arg(path).spawn()?;
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        Command::new("open").arg(path).spawn()?;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        Command::new("xdg-open").arg(path).spawn()?;
+    }
+
+    Ok(())
+}
+
+/// Show a file selected in the platform file manager, or its folder where selecting is unsupported
+pub fn reveal_in_file_manager(path: &std::path::Path) -> Result<()> {
+    #[cfg(target_os = "windows")]
+    {
+        Command::new("explorer").arg(format!("/select,{}", path.display())).spawn()?;
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        Command::new("open").arg("-R").arg(path).spawn()?;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        Command::new("xdg-open").arg(path.parent().unwrap_or(path)).spawn()?;
+    }
+
+    Ok(())
+}
+
+/// Placeholders understood by filename templates
+pub const FILENAME_PLACEHOLDERS: [(&str, &str); 5] = [
+    ("{serial}", "device serial"),
+    ("{model}", "device model"),
+    ("{date}", "YYYYMMDD"),
+    ("{time}", "HHMMSS"),
+    ("{timestamp}", "YYYYMMDD_HHMMSS"),
+];
+
+/// Expand a filename template such as `screenshot_{serial}_{timestamp}` into a safe file name
+pub fn expand_filename_template(
+    template: &str,
+    serial: &str,
+    model: &str,
+    now: chrono::DateTime<chrono::Local>,
+    extension: &str,
+) -> String {
+    let name = template
+        .replace("{serial}", serial)
+        .replace("{model}", model)
+        .replace("{date}", &now.format("%Y%m%d").to_string())
+        .replace("{time}", &now.format("%H%M%S").to_string())
+        .replace("{timestamp}", &now.format("%Y%m%d_%H%M%S").to_string());
+    let name = sanitize_filename(name.trim());
+    let name = if name.is_empty() { "screenshot".to_string() } else { name };
+    format!("{}.{}", name, extension)
+}
+
+/// `path` itself if free, else the first of `name_1.ext`, `name_2.ext`, ... that is
+pub fn unique_path(path: PathBuf) -> PathBuf {
+    if !path.exists() {
+        return path;
+    }
+    let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let extension = path.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
+    (1..)
+        .map(|n| path.with_file_name(format!("{}_{}{}", stem, n, extension)))
+        .find(|candidate| !candidate.exists())
+        .unwrap_or(path)
+}
+
+/// Decode a PNG and place it on the host clipboard as an image
+pub fn copy_png_to_clipboard(clipboard: &mut Option<arboard::Clipboard>, png: &[u8]) -> Result<()> {
+    let rgba = image::load_from_memory(png)?.to_rgba8();
+    let image = arboard::ImageData {
+        width: rgba.width() as usize,
+        height: rgba.height() as usize,
+        bytes: std::borrow::Cow::Owned(rgba.into_raw()),
+    };
+
+    if clipboard.is_none() {
+        *clipboard = Some(arboard::Clipboard::new()?);
+    }
+    if let Some(clipboard) = clipboard {
+        clipboard.set_image(image)?;
+    }
+    Ok(())
+}
+
+/// Place text on the host clipboard
+pub fn copy_text_to_clipboard(clipboard: &mut Option<arboard::Clipboard>, text: &str) -> Result<()> {
+    if clipboard.is_none() {
+        *clipboard = Some(arboard::Clipboard::new()?);
+    }
+    if let Some(clipboard) = clipboard {
+        clipboard.set_text(text)?;
+    }
+    Ok(())
+}
+
 pub fn format_file_size(bytes: u64) -> String {
     const KB: u64 = 1024;
     const MB: u64 = KB * 1024;
@@ -158,3 +352,110 @@ pub fn sanitize_filename(filename: &str) -> String {
         })
         .collect()
 }
+
+/// Sleep for `duration` in short slices so a Stop button does not wait out a long pause;
+/// `false` once `cancel` is set, before or during the sleep
+pub fn sleep_unless_cancelled(duration: Duration, cancel: &AtomicBool) -> bool {
+    let mut remaining = duration;
+    while !remaining.is_zero() {
+        if cancel.load(Ordering::Relaxed) {
+            return false;
+        }
+        let slice = remaining.min(Duration::from_millis(100));
+        std::thread::sleep(slice);
+        remaining -= slice;
+    }
+    !cancel.load(Ordering::Relaxed)
+}
+
+/// Case-insensitive fuzzy match of `query` against `candidate`: `None` unless every query
+/// character appears in order; higher scores for substrings, word starts and adjacent runs
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let query: Vec<char> = query.trim().to_lowercase().chars().filter(|c| !c.is_whitespace()).collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous_match: Option<usize> = None;
+    for (index, c) in candidate.iter().enumerate() {
+        if next == query.len() {
+            break;
+        }
+        if *c != query[next] {
+            continue;
+        }
+        score += 1;
+        if previous_match == Some(index.wrapping_sub(1)) {
+            score += 5;
+        }
+        if index == 0 || !candidate[index - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous_match = Some(index);
+        next += 1;
+    }
+    if next < query.len() {
+        return None;
+    }
+
+    let query: String = query.into_iter().collect();
+    let candidate: String = candidate.into_iter().collect();
+    if candidate.contains(&query) {
+        score += 20;
+    }
+    // Shorter candidates are closer matches for the same query
+    Some(score * 4 - (candidate.len() as i32).min(40) / 10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_score_matches_in_order() {
+        for (query, candidate, matches) in [
+            ("", "Battery info", true),
+            ("   ", "Battery info", true),
+            ("bat", "Battery info", true),
+            ("BAT", "battery info", true),
+            ("show help", "Show help", true),
+            ("bi", "Battery info", true),
+            ("ib", "Battery info", false),
+            ("xyz", "Battery info", false),
+            ("batteryy", "Battery", false),
+        ] {
+            assert_eq!(fuzzy_score(query, candidate).is_some(), matches, "{:?} in {:?}", query, candidate);
+        }
+    }
+
+    #[test]
+    fn fuzzy_score_ranks_closer_matches_higher() {
+        for (query, better, worse) in [
+            // Substrings over scattered letters
+            ("bat", "Battery", "Big alpha test"),
+            ("tog", "Toggle theme", "Tap on glass"),
+            // Word starts over letters inside words
+            ("ks", "Keyboard shortcuts", "Kitchens"),
+            // Shorter candidates for the same letters
+            ("help", "Show help", "Show help and documentation overview"),
+        ] {
+            let (better_score, worse_score) = (fuzzy_score(query, better).unwrap(), fuzzy_score(query, worse).unwrap());
+            assert!(better_score > worse_score, "{:?}: {:?} {} <= {:?} {}", query, better, better_score, worse, worse_score);
+        }
+        assert_eq!(fuzzy_score("BAT", "Battery"), fuzzy_score("bat", "battery"));
+    }
+
+    #[test]
+    fn sleep_unless_cancelled_stops_early() {
+        let cancel = AtomicBool::new(false);
+        assert!(sleep_unless_cancelled(Duration::from_millis(10), &cancel));
+
+        cancel.store(true, Ordering::Relaxed);
+        let started = std::time::Instant::now();
+        assert!(!sleep_unless_cancelled(Duration::from_secs(60), &cancel));
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+}
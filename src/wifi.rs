@@ -0,0 +1,141 @@
+//! The device's Wi-Fi connection, from `dumpsys wifi` and `ip addr`
+//!
+//! `dumpsys wifi` describes the current connection on a line starting with `mWifiInfo`, as
+//! comma-separated `Name: value` pairs. The address comes from the wlan interface instead,
+//! because newer Android versions leave it out of that line.
+
+use crate::bridge::adb_command;
+use anyhow::Result;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WifiInfo {
+    pub enabled: bool,
+    /// None while not connected
+    pub ssid: Option<String>,
+    pub bssid: Option<String>,
+    pub ip: Option<String>,
+    pub link_speed_mbps: Option<u32>,
+    pub rssi_dbm: Option<i32>,
+    pub frequency_mhz: Option<u32>,
+    /// Such as `11ax`
+    pub standard: Option<String>,
+}
+
+impl WifiInfo {
+    /// Signal strength as 0 to 4 bars, with the thresholds Android uses
+    pub fn signal_bars(&self) -> Option<u8> {
+        self.rssi_dbm.map(|rssi| match rssi {
+            r if r >= -55 => 4,
+            r if r >= -66 => 3,
+            r if r >= -77 => 2,
+            r if r >= -88 => 1,
+            _ => 0,
+        })
+    }
+
+    pub fn band(&self) -> Option<&'static str> {
+        self.frequency_mhz.map(|frequency| match frequency {
+            f if f >= 5925 => "6 GHz",
+            f if f >= 4900 => "5 GHz",
+            _ => "2.4 GHz",
+        })
+    }
+}
+
+/// Parse the connection out of `dumpsys wifi` output
+pub fn parse_dumpsys_wifi(output: &str) -> WifiInfo {
+    let mut info = WifiInfo {
+        enabled: output.lines().any(|line| line.trim() == "Wi-Fi is enabled"),
+        ..WifiInfo::default()
+    };
+    let Some(line) = output.lines().map(str::trim).find(|line| line.starts_with("mWifiInfo")) else {
+        return info;
+    };
+    let mut line = line.trim_start_matches("mWifiInfo").trim();
+    // A quoted SSID may itself contain ", "
+    if let Some(rest) = line.strip_prefix("SSID: \"")
+        && let Some((ssid, rest)) = rest.split_once("\", ")
+    {
+        info.ssid = Some(ssid.to_string()).filter(|ssid| !ssid.is_empty());
+        line = rest;
+    }
+    for field in line.split(", ") {
+        let Some((name, value)) = field.split_once(": ") else {
+            continue;
+        };
+        let value = value.trim();
+        let number = |unit: &str| value.trim_end_matches(unit).parse().ok();
+        match name.trim() {
+            "SSID" => {
+                let ssid = value.trim_matches('"');
+                if !ssid.is_empty() && ssid != "<unknown ssid>" {
+                    info.ssid = Some(ssid.to_string());
+                }
+            }
+            "BSSID" if value != "<none>" && value != "02:00:00:00:00:00" => info.bssid = Some(value.to_string()),
+            "RSSI" => info.rssi_dbm = value.parse().ok().filter(|rssi| *rssi > -127),
+            "Link speed" => info.link_speed_mbps = number("Mbps").filter(|speed| *speed > 0),
+            "Frequency" => info.frequency_mhz = number("MHz").filter(|frequency| *frequency > 0),
+            "Wi-Fi standard" => info.standard = Some(value.to_string()),
+            _ => {}
+        }
+    }
+    if info.ssid.is_none() {
+        info.rssi_dbm = None;
+        info.link_speed_mbps = None;
+    }
+    info
+}
+
+/// The IPv4 address in `ip -f inet addr show <interface>` output
+pub fn parse_ip_addr(output: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let address = line.trim().strip_prefix("inet ")?.split_whitespace().next()?;
+        Some(address.split('/').next()?.to_string())
+    })
+}
+
+pub fn wifi_info(adb_path: &str, serial: &str) -> Result<WifiInfo> {
+    let shell = |command: &str| -> Result<String> {
+        let output = adb_command(adb_path).args(["-s", serial, "shell", command]).output()?;
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    };
+    let mut info = parse_dumpsys_wifi(&shell("dumpsys wifi")?);
+    if info.ssid.is_some() {
+        info.ip = parse_ip_addr(&shell("ip -f inet addr show wlan0").unwrap_or_default());
+    }
+    Ok(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_connection() {
+        let output = "Wi-Fi is enabled\n\
+            mWifiInfo SSID: \"Home, 5G\", BSSID: aa:bb:cc:dd:ee:ff, MAC: 02:00:00:00:00:00, \
+            Supplicant state: COMPLETED, Wi-Fi standard: 11ax, RSSI: -58, Link speed: 866Mbps, \
+            Tx Link speed: 866Mbps, Frequency: 5180MHz, Net ID: 0\n";
+        let info = parse_dumpsys_wifi(output);
+        assert!(info.enabled);
+        assert_eq!(info.ssid.as_deref(), Some("Home, 5G"));
+        assert_eq!(info.bssid.as_deref(), Some("aa:bb:cc:dd:ee:ff"));
+        assert_eq!((info.rssi_dbm, info.link_speed_mbps, info.frequency_mhz), (Some(-58), Some(866), Some(5180)));
+        assert_eq!(info.standard.as_deref(), Some("11ax"));
+        assert_eq!((info.signal_bars(), info.band()), (Some(3), Some("5 GHz")));
+    }
+
+    #[test]
+    fn parses_disconnected() {
+        let output = "Wi-Fi is disabled\nmWifiInfo SSID: <unknown ssid>, BSSID: <none>, RSSI: -127, Link speed: -1Mbps\n";
+        assert_eq!(parse_dumpsys_wifi(output), WifiInfo::default());
+    }
+
+    #[test]
+    fn parses_ip_addr() {
+        let output = "30: wlan0: <BROADCAST,MULTICAST,UP,LOWER_UP> mtu 1500\n    inet 192.168.1.20/24 brd 192.168.1.255 scope global wlan0\n";
+        assert_eq!(parse_ip_addr(output).as_deref(), Some("192.168.1.20"));
+        assert_eq!(parse_ip_addr("Device \"wlan0\" does not exist."), None);
+    }
+}
@@ -0,0 +1,136 @@
+//! UI regression tests; run with `cargo test --features test-harness`
+#![cfg(all(feature = "test-harness", unix))]
+
+use droid_view::harness::{Harness, MockTool};
+use eframe::egui;
+use std::time::{Duration, Instant};
+
+const SETTLE: Duration = Duration::from_secs(10);
+
+#[test]
+fn lists_mocked_devices() {
+    let mut harness = Harness::new(MockTool::adb(&[("R58M123ABC", "Pixel_8")]), None).unwrap();
+    assert!(harness.settle(SETTLE));
+
+    assert!(harness.shows("Pixel_8"));
+    assert!(harness.adb_calls().iter().any(|call| call == "devices -l"));
+}
+
+#[test]
+fn slow_device_listing_does_not_hold_up_frames() {
+    let adb = MockTool::adb(&[("R58M123ABC", "Pixel_8")]).delay("devices", Duration::from_secs(2));
+    let started = Instant::now();
+    let mut harness = Harness::new(adb, None).unwrap();
+    harness.run(30);
+    assert!(started.elapsed() < Duration::from_secs(1));
+    assert!(!harness.shows("Pixel_8"));
+
+    assert!(harness.settle(SETTLE));
+    assert!(harness.shows("Pixel_8"));
+}
+
+#[test]
+fn device_search_selects_with_the_keyboard() {
+    let adb = MockTool::adb(&[
+        ("R58M123ABC", "Pixel_8"),
+        ("RF8T456DEF", "Galaxy_S24"),
+        ("RF8T789GHI", "Galaxy_A54"),
+        ("9A1B2C3D", "OnePlus_12"),
+    ]);
+    let mut harness = Harness::new(adb, None).unwrap();
+    assert!(harness.settle(SETTLE));
+    assert_eq!(harness.selected_device().as_deref(), Some("R58M123ABC"));
+
+    // Both Galaxies match, in list order; Down moves to the second one
+    harness.focus("Serial, name or IP").unwrap();
+    harness.type_text("galaxy");
+    assert!(!harness.shows("OnePlus_12"));
+    harness.press_key(egui::Key::ArrowDown, egui::Modifiers::NONE);
+    harness.press_key(egui::Key::Enter, egui::Modifiers::NONE);
+    harness.step();
+    assert_eq!(harness.selected_device().as_deref(), Some("RF8T789GHI"));
+
+    // Escape clears the search and brings the other devices back, keeping the selection
+    harness.press_key(egui::Key::Escape, egui::Modifiers::NONE);
+    harness.step();
+    assert!(harness.shows("OnePlus_12"));
+    assert_eq!(harness.selected_device().as_deref(), Some("RF8T789GHI"));
+}
+
+#[test]
+fn settings_window_opens_from_quick_actions() {
+    let mut harness = Harness::new(MockTool::adb(&[]), None).unwrap();
+    assert!(harness.settle(SETTLE));
+    assert!(!harness.shows("Application Settings"));
+
+    harness.click("Settings").unwrap();
+    assert!(harness.shows("Application Settings"));
+}
+
+#[test]
+fn start_in_debug_mode_does_not_launch_scrcpy() {
+    let mut harness = Harness::new(MockTool::adb(&[("R58M123ABC", "Pixel_8")]), None).unwrap();
+    assert!(harness.settle(SETTLE));
+
+    harness.click("Start Scrcpy").unwrap();
+    assert!(harness.shows("Scrcpy is disabled in debug mode"));
+}
+
+#[test]
+fn keyboard_shortcut_opens_settings() {
+    let mut harness = Harness::new(MockTool::adb(&[]), None).unwrap();
+    assert!(harness.settle(SETTLE));
+
+    harness.press_key(egui::Key::Comma, egui::Modifiers::COMMAND);
+    harness.step();
+    assert!(harness.shows("Application Settings"));
+}
+
+#[test]
+fn keyboard_shortcuts_wait_while_typing() {
+    let mut harness = Harness::new(MockTool::adb(&[]), None).unwrap();
+    assert!(harness.settle(SETTLE));
+
+    // The palette's search field has the focus
+    harness.press_key(egui::Key::P, egui::Modifiers::COMMAND);
+    harness.step();
+    harness.press_key(egui::Key::Comma, egui::Modifiers::COMMAND);
+    harness.step();
+    assert!(!harness.shows("Application Settings"));
+}
+
+#[test]
+fn command_palette_runs_commands() {
+    let mut harness = Harness::new(MockTool::adb(&[]), None).unwrap();
+    assert!(harness.settle(SETTLE));
+
+    harness.press_key(egui::Key::P, egui::Modifiers::COMMAND);
+    harness.step();
+    assert!(harness.shows("Toolkit: Battery info"));
+
+    harness.type_text("keyboard shortcuts");
+    harness.step();
+    harness.press_key(egui::Key::Enter, egui::Modifiers::NONE);
+    harness.step();
+    assert!(harness.shows("Keyboard Shortcuts"));
+    assert!(!harness.shows("Toolkit: Battery info"));
+}
+
+#[test]
+fn command_palette_follows_the_arrow_keys() {
+    let mut harness = Harness::new(MockTool::adb(&[]), None).unwrap();
+    assert!(harness.settle(SETTLE));
+
+    harness.press_key(egui::Key::P, egui::Modifiers::COMMAND);
+    harness.step();
+    // Shortest first: "Show help", "Show IMEI", then "Show keyboard shortcuts"; Up stops at the top
+    harness.type_text("show");
+    harness.step();
+    assert!(harness.shows("DroidView: Show keyboard shortcuts"));
+    harness.press_key(egui::Key::ArrowUp, egui::Modifiers::NONE);
+    harness.press_key(egui::Key::ArrowDown, egui::Modifiers::NONE);
+    harness.press_key(egui::Key::ArrowDown, egui::Modifiers::NONE);
+    harness.press_key(egui::Key::Enter, egui::Modifiers::NONE);
+    harness.step();
+    assert!(harness.shows("Keyboard Shortcuts"));
+}